@@ -0,0 +1,258 @@
+//! Derive macros for [missive](https://docs.rs/missive).
+//!
+//! Enable them with missive's `derive` feature rather than depending on this
+//! crate directly; see `missive::catalog` for usage.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Bind a context struct to a template in the catalog.
+///
+/// ```rust,ignore
+/// #[derive(EmailTemplate)]
+/// #[email(template = "welcome")]
+/// struct WelcomeEmail {
+///     name: String,
+/// }
+/// ```
+///
+/// The template is read at compile time from `<dir>/<template>.subject`,
+/// `<dir>/<template>.html` and `<dir>/<template>.txt` (`dir` defaults to
+/// `templates`, relative to the crate root). Compilation fails if the files
+/// are missing or a `{{ placeholder }}` has no matching field.
+///
+/// Attributes:
+///
+/// * `template = "name"` - template name (required)
+/// * `dir = "path"` - template directory
+/// * `subject = "..."` - subject line, instead of a `.subject` file
+#[proc_macro_derive(EmailTemplate, attributes(email))]
+pub fn derive_email_template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Parsed `#[email(...)]` attribute.
+struct Attrs {
+    template: LitStr,
+    dir: Option<LitStr>,
+    subject: Option<LitStr>,
+}
+
+fn parse_attrs(input: &DeriveInput) -> syn::Result<Attrs> {
+    let mut template = None;
+    let mut dir = None;
+    let mut subject = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("email")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("template") {
+                template = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("dir") {
+                dir = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("subject") {
+                subject = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `template`, `dir` or `subject`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let template = template.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "missing #[email(template = \"...\")] attribute",
+        )
+    })?;
+    Ok(Attrs {
+        template,
+        dir,
+        subject,
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = parse_attrs(&input)?;
+    let fields: Vec<&syn::Ident> = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref())
+                .collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "EmailTemplate needs named fields to match template placeholders",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "EmailTemplate can only be derived for structs",
+            ))
+        }
+    };
+    let keys: Vec<String> = fields
+        .iter()
+        .map(|f| f.to_string().trim_start_matches("r#").to_string())
+        .collect();
+
+    let name = attrs.template.value();
+    let span = attrs.template.span();
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+    let dir = Path::new(&manifest_dir).join(
+        attrs
+            .dir
+            .as_ref()
+            .map_or_else(|| "templates".to_string(), LitStr::value),
+    );
+
+    let subject_file = read(&dir, &name, "subject");
+    let html_file = read(&dir, &name, "html");
+    let text_file = read(&dir, &name, "txt");
+    if html_file.is_none() && text_file.is_none() {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "template `{}` has no body: expected {} or {}",
+                name,
+                dir.join(format!("{}.html", name)).display(),
+                dir.join(format!("{}.txt", name)).display()
+            ),
+        ));
+    }
+
+    let subject_source = match (&attrs.subject, &subject_file) {
+        (Some(subject), _) => subject.value(),
+        (None, Some((_, source))) => source.clone(),
+        (None, None) => {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "template `{}` has no subject: add {} or #[email(subject = \"...\")]",
+                    name,
+                    dir.join(format!("{}.subject", name)).display()
+                ),
+            ))
+        }
+    };
+
+    let sources = [
+        Some(&subject_source),
+        html_file.as_ref().map(|(_, s)| s),
+        text_file.as_ref().map(|(_, s)| s),
+    ];
+    let mut missing = BTreeSet::new();
+    for source in sources.into_iter().flatten() {
+        for key in placeholders(source) {
+            if !keys.iter().any(|k| k == key) {
+                missing.insert(key.to_string());
+            }
+        }
+    }
+    if !missing.is_empty() {
+        let list: Vec<String> = missing.iter().map(|k| format!("`{}`", k)).collect();
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "`{}` is missing fields used by template `{}`: {}",
+                input.ident,
+                name,
+                list.join(", ")
+            ),
+        ));
+    }
+
+    let subject = match (&attrs.subject, &subject_file) {
+        (Some(subject), _) => quote!(#subject),
+        (None, Some((path, _))) => include(path),
+        (None, None) => unreachable!(),
+    };
+    let html = optional(&html_file);
+    let text = optional(&text_file);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::missive::catalog::CatalogEmail for #ident #ty_generics #where_clause {
+            const TEMPLATE: ::missive::catalog::TemplateFiles = ::missive::catalog::TemplateFiles {
+                name: #name,
+                subject: #subject,
+                html: #html,
+                text: #text,
+            };
+
+            fn context(&self) -> ::std::result::Result<::missive::catalog::Context, ::missive::MailError> {
+                let mut context = ::missive::catalog::Context::new();
+                #( context.insert(#keys, &self.#fields)?; )*
+                ::std::result::Result::Ok(context)
+            }
+        }
+    })
+}
+
+/// Read `<dir>/<name>.<ext>`, returning its path and contents.
+fn read(dir: &Path, name: &str, ext: &str) -> Option<(PathBuf, String)> {
+    let path = dir.join(format!("{}.{}", name, ext));
+    let source = std::fs::read_to_string(&path).ok()?;
+    Some((path, source))
+}
+
+/// `include_str!` so the crate is rebuilt when the file changes.
+fn include(path: &Path) -> proc_macro2::TokenStream {
+    let path = path.to_string_lossy();
+    quote!(::std::include_str!(#path))
+}
+
+fn optional(file: &Option<(PathBuf, String)>) -> proc_macro2::TokenStream {
+    match file {
+        Some((path, _)) => {
+            let include = include(path);
+            quote!(::std::option::Option::Some(#include))
+        }
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+/// Top-level keys of the `{{ key }}` / `{{ key.path }}` placeholders in a
+/// template, as rendered by missive.
+fn placeholders(source: &str) -> Vec<&str> {
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + len].trim();
+        if let Some(root) = key.split('.').next().filter(|k| !k.is_empty()) {
+            keys.push(root);
+        }
+        rest = &rest[start + len + 2..];
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("Hi {{ name }}, your {{plan.name}} plan renews {{ renews_at }}"),
+            vec!["name", "plan", "renews_at"]
+        );
+        assert!(placeholders("No placeholders, {{ }} or {{ unterminated").is_empty());
+    }
+}
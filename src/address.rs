@@ -3,6 +3,7 @@
 use crate::error::MailError;
 use email_address::EmailAddress;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fmt;
 
 /// An email address with an optional display name.
@@ -23,8 +24,10 @@ use std::fmt;
 /// assert_eq!(addr.name, Some("Alice".to_string()));
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Address {
     /// Optional display name (e.g., "Alice Smith")
+    #[serde(default)]
     pub name: Option<String>,
     /// Email address (e.g., "alice@example.com")
     pub email: String,
@@ -152,6 +155,76 @@ impl Address {
         })
     }
 
+    /// Parse and validate an RFC 5322 mailbox such as `Jane Doe <jane@example.com>`.
+    ///
+    /// Accepts a bare addr-spec, a name-addr with an optional (possibly
+    /// quoted) display name, and comments in parentheses. Comments are
+    /// dropped, except that `jane@example.com (Jane Doe)` uses the comment as
+    /// the display name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use missive::Address;
+    ///
+    /// let addr = Address::parse_rfc5322("Jane Doe <jane@example.com>").unwrap();
+    /// assert_eq!(addr.email, "jane@example.com");
+    /// assert_eq!(addr.name, Some("Jane Doe".to_string()));
+    ///
+    /// let addr = Address::parse_rfc5322(r#""Doe, Jane" <jane@example.com>"#).unwrap();
+    /// assert_eq!(addr.name, Some("Doe, Jane".to_string()));
+    ///
+    /// assert!(Address::parse_rfc5322("Jane <not-valid>").is_err());
+    /// ```
+    pub fn parse_rfc5322(input: &str) -> Result<Self, MailError> {
+        let input = input.trim();
+        match split_mailbox(input) {
+            Some((name, email)) => Self::parse_with_name(name.as_deref().unwrap_or(""), &email),
+            // Quoted local parts such as `"john doe"@example.com`
+            None if !input.contains('<') => Self::parse(input),
+            None => Err(MailError::InvalidAddress(format!(
+                "'{}' is not a valid RFC 5322 address",
+                input
+            ))),
+        }
+    }
+
+    /// Parse and validate a comma-separated list of RFC 5322 mailboxes.
+    ///
+    /// Each entry may be in any form accepted by [`Address::parse_rfc5322`].
+    /// Groups (`Team: a@example.com, b@example.com;`) are flattened into their
+    /// members, and empty entries are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use missive::Address;
+    ///
+    /// let addrs = Address::parse_list("a@x.com, Bob <b@y.com>").unwrap();
+    /// assert_eq!(addrs.len(), 2);
+    /// assert_eq!(addrs[1].name, Some("Bob".to_string()));
+    ///
+    /// let addrs = Address::parse_list("Team: a@x.com, b@y.com;, c@z.com").unwrap();
+    /// assert_eq!(addrs.len(), 3);
+    /// ```
+    pub fn parse_list(input: &str) -> Result<Vec<Self>, MailError> {
+        let entries = split_list(input).ok_or_else(|| {
+            MailError::InvalidAddress(format!("'{}' is not a valid address list", input))
+        })?;
+        entries.into_iter().map(Self::parse_rfc5322).collect()
+    }
+
+    /// Build an address from a string given to `.to()`, `.from()` etc.
+    ///
+    /// `Name <email>` strings are split; anything else is used as the email.
+    fn from_mailbox(input: &str) -> Self {
+        match split_mailbox(input) {
+            Some((Some(name), email)) => Self::with_name(name, email),
+            Some((None, email)) => Self::new(email),
+            None => Self::new(input),
+        }
+    }
+
     /// Convert the domain part of the email address to ASCII (Punycode).
     ///
     /// This is useful for international domain names (IDN) that contain
@@ -265,23 +338,157 @@ impl Address {
     }
 }
 
+/// Hex-encoded SHA-256 of a lowercased address, for logs that must not
+/// contain recipients.
+pub(crate) fn hash_address(email: &str) -> String {
+    hex::encode(Sha256::digest(email.to_lowercase().as_bytes()))
+}
+
+/// Split an RFC 5322 mailbox into its display name and addr-spec.
+///
+/// Returns `None` if the input isn't a single mailbox (unbalanced quotes,
+/// brackets or parentheses, text after `<...>`, or a bare address containing
+/// whitespace).
+fn split_mailbox(input: &str) -> Option<(Option<String>, String)> {
+    let mut phrase = String::new();
+    let mut comment = String::new();
+    let mut addr_spec: Option<String> = None;
+    let mut chars = input.trim().chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => loop {
+                match chars.next()? {
+                    '\\' => phrase.push(chars.next()?),
+                    '"' => break,
+                    c => phrase.push(c),
+                }
+            },
+            '(' => {
+                let mut depth = 1;
+                let mut text = String::new();
+                while depth > 0 {
+                    match chars.next()? {
+                        '\\' => text.push(chars.next()?),
+                        '(' => {
+                            depth += 1;
+                            text.push('(');
+                        }
+                        ')' => {
+                            depth -= 1;
+                            if depth > 0 {
+                                text.push(')');
+                            }
+                        }
+                        c => text.push(c),
+                    }
+                }
+                if comment.is_empty() {
+                    comment = text.trim().to_string();
+                }
+                if !phrase.ends_with(' ') {
+                    phrase.push(' ');
+                }
+            }
+            '<' if addr_spec.is_none() => {
+                let mut addr = String::new();
+                loop {
+                    match chars.next()? {
+                        '>' => break,
+                        c => addr.push(c),
+                    }
+                }
+                addr_spec = Some(addr.trim().to_string());
+            }
+            c if c.is_whitespace() => {
+                if !phrase.ends_with(' ') {
+                    phrase.push(' ');
+                }
+            }
+            _ if addr_spec.is_some() => return None,
+            c => phrase.push(c),
+        }
+    }
+
+    let phrase = phrase.trim().to_string();
+    let (name, email) = match addr_spec {
+        Some(email) => (phrase, email),
+        None if phrase.contains(' ') => return None,
+        None => (String::new(), phrase),
+    };
+    if email.is_empty() {
+        return None;
+    }
+    let name = if name.is_empty() { comment } else { name };
+    Some(((!name.is_empty()).then_some(name), email))
+}
+
+/// Split an address list on top-level commas, flattening groups.
+///
+/// Returns `None` if quotes, brackets or parentheses are unbalanced.
+fn split_list(input: &str) -> Option<Vec<&str>> {
+    let mut entries = Vec::new();
+    let mut start = 0;
+    let mut comment_depth = 0;
+    let mut quoted = false;
+    let mut bracket: Option<char> = None;
+    let mut escaped = false;
+
+    for (i, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if quoted || comment_depth > 0 => escaped = true,
+            '"' if comment_depth == 0 => quoted = !quoted,
+            _ if quoted => {}
+            '(' => comment_depth += 1,
+            ')' if comment_depth > 0 => comment_depth -= 1,
+            _ if comment_depth > 0 => {}
+            '<' | '[' if bracket.is_none() => bracket = Some(if c == '<' { '>' } else { ']' }),
+            _ if bracket == Some(c) => bracket = None,
+            _ if bracket.is_some() => {}
+            ',' | ';' => {
+                entries.push(&input[start..i]);
+                start = i + 1;
+            }
+            // Group display name
+            ':' => start = i + 1,
+            _ => {}
+        }
+    }
+    if quoted || comment_depth > 0 || bracket.is_some() {
+        return None;
+    }
+    entries.push(&input[start..]);
+
+    Some(
+        entries
+            .into_iter()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect(),
+    )
+}
+
 impl fmt::Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.formatted())
     }
 }
 
-// From &str - just email
+// From &str - email or "Name <email>"
 impl From<&str> for Address {
     fn from(email: &str) -> Self {
-        Self::new(email)
+        Self::from_mailbox(email)
     }
 }
 
-// From String - just email
+// From String - email or "Name <email>"
 impl From<String> for Address {
     fn from(email: String) -> Self {
-        Self::new(email)
+        Self::from_mailbox(&email)
     }
 }
 
@@ -339,6 +546,14 @@ impl From<(String, &str)> for Address {
 /// ```
 pub trait ToAddress {
     fn to_address(&self) -> Address;
+
+    /// Convert to the addresses added by `.to()`, `.cc()` etc.
+    ///
+    /// Defaults to the single [`to_address`](ToAddress::to_address); strings
+    /// override it to split comma-separated lists.
+    fn to_address_list(&self) -> Vec<Address> {
+        vec![self.to_address()]
+    }
 }
 
 // Blanket implementation for references to types that implement ToAddress
@@ -346,6 +561,10 @@ impl<T: ToAddress + ?Sized> ToAddress for &T {
     fn to_address(&self) -> Address {
         (*self).to_address()
     }
+
+    fn to_address_list(&self) -> Vec<Address> {
+        (*self).to_address_list()
+    }
 }
 
 // Implement for Address itself
@@ -355,16 +574,29 @@ impl ToAddress for Address {
     }
 }
 
-// Implement for string types
+// Implement for string types (email or "Name <email>")
 impl ToAddress for str {
     fn to_address(&self) -> Address {
-        Address::new(self)
+        Address::from_mailbox(self)
+    }
+
+    fn to_address_list(&self) -> Vec<Address> {
+        match split_list(self) {
+            Some(entries) if !entries.is_empty() => {
+                entries.into_iter().map(Address::from_mailbox).collect()
+            }
+            _ => vec![self.to_address()],
+        }
     }
 }
 
 impl ToAddress for String {
     fn to_address(&self) -> Address {
-        Address::new(self)
+        Address::from_mailbox(self)
+    }
+
+    fn to_address_list(&self) -> Vec<Address> {
+        self.as_str().to_address_list()
     }
 }
 
@@ -375,6 +607,51 @@ impl<N: AsRef<str>, E: AsRef<str>> ToAddress for (N, E) {
     }
 }
 
+/// Trait for one or more recipients, as accepted by `.to()`, `.cc()`,
+/// `.bcc()` and `.reply_to()`.
+///
+/// Implemented for every [`ToAddress`] type (strings are split on commas)
+/// and for lists of them:
+///
+/// ```rust
+/// use missive::Email;
+///
+/// let email = Email::new()
+///     .to("alice@example.com, Bob <bob@example.com>")
+///     .cc(vec!["carol@example.com", "dave@example.com"])
+///     .bcc([("Eve", "eve@example.com")]);
+///
+/// assert_eq!(email.to.len(), 2);
+/// assert_eq!(email.cc.len(), 2);
+/// ```
+pub trait ToAddresses {
+    fn to_addresses(&self) -> Vec<Address>;
+}
+
+impl<T: ToAddress + ?Sized> ToAddresses for T {
+    fn to_addresses(&self) -> Vec<Address> {
+        self.to_address_list()
+    }
+}
+
+impl<T: ToAddress> ToAddresses for Vec<T> {
+    fn to_addresses(&self) -> Vec<Address> {
+        self.as_slice().to_addresses()
+    }
+}
+
+impl<T: ToAddress> ToAddresses for &[T] {
+    fn to_addresses(&self) -> Vec<Address> {
+        self.iter().flat_map(ToAddress::to_address_list).collect()
+    }
+}
+
+impl<T: ToAddress, const N: usize> ToAddresses for [T; N] {
+    fn to_addresses(&self) -> Vec<Address> {
+        self.as_slice().to_addresses()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -530,6 +807,92 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ========================================================================
+    // Tests for Address::parse_rfc5322() and "Name <email>" strings
+    // ========================================================================
+
+    #[test]
+    fn test_parse_rfc5322() {
+        let addr = Address::parse_rfc5322("Jane Doe <jane@example.com>").unwrap();
+        assert_eq!(addr, Address::with_name("Jane Doe", "jane@example.com"));
+
+        let addr = Address::parse_rfc5322(r#""Doe, \"JD\" Jane" <jane@example.com>"#).unwrap();
+        assert_eq!(addr.name.as_deref(), Some(r#"Doe, "JD" Jane"#));
+
+        let addr = Address::parse_rfc5322("  <jane@example.com>  ").unwrap();
+        assert_eq!(addr, Address::new("jane@example.com"));
+
+        let addr = Address::parse_rfc5322("jane@example.com (Jane (work) Doe)").unwrap();
+        assert_eq!(addr.name.as_deref(), Some("Jane (work) Doe"));
+
+        let addr = Address::parse_rfc5322("Jane (Support)  Doe <jane@example.com>").unwrap();
+        assert_eq!(addr.name.as_deref(), Some("Jane Doe"));
+
+        let addr = Address::parse_rfc5322(r#""john doe"@example.com"#).unwrap();
+        assert_eq!(addr.email, r#""john doe"@example.com"#);
+    }
+
+    #[test]
+    fn test_parse_rfc5322_invalid() {
+        assert!(Address::parse_rfc5322("Jane <jane@example.com").is_err());
+        assert!(Address::parse_rfc5322("\"Jane <jane@example.com>").is_err());
+        assert!(Address::parse_rfc5322("Jane <jane@example.com> extra").is_err());
+        assert!(Address::parse_rfc5322("Jane <not-valid>").is_err());
+        assert!(Address::parse_rfc5322("Jane Doe").is_err());
+        assert!(Address::parse_rfc5322("<>").is_err());
+    }
+
+    #[test]
+    fn test_from_mailbox_string() {
+        let addr: Address = "Jane Doe <jane@example.com>".into();
+        assert_eq!(addr, Address::with_name("Jane Doe", "jane@example.com"));
+
+        let addr = "jane@example.com".to_string().to_address();
+        assert_eq!(addr, Address::new("jane@example.com"));
+
+        // Unparseable strings are kept as the email
+        let addr: Address = "Jane <jane@example.com".into();
+        assert_eq!(addr.email, "Jane <jane@example.com");
+        assert_eq!(addr.name, None);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let addrs =
+            Address::parse_list(r#"a@x.com, "Doe, Jane" <jane@y.com>; (comment, here) c@z.com"#)
+                .unwrap();
+        let emails: Vec<&str> = addrs.iter().map(|a| a.email.as_str()).collect();
+        assert_eq!(emails, ["a@x.com", "jane@y.com", "c@z.com"]);
+        assert_eq!(addrs[1].name.as_deref(), Some("Doe, Jane"));
+
+        let addrs =
+            Address::parse_list("Team: a@x.com, Bob <b@y.com>;, undisclosed-recipients:;").unwrap();
+        assert_eq!(addrs.len(), 2);
+        assert_eq!(addrs[1], Address::with_name("Bob", "b@y.com"));
+
+        assert_eq!(Address::parse_list(" , ").unwrap(), []);
+        assert!(Address::parse_list("a@x.com, \"Jane <j@y.com>").is_err());
+        assert!(Address::parse_list("a@x.com, not-valid").is_err());
+    }
+
+    #[test]
+    fn test_to_addresses() {
+        let addrs = "a@x.com, Bob <b@y.com>".to_addresses();
+        assert_eq!(
+            addrs,
+            [
+                Address::new("a@x.com"),
+                Address::with_name("Bob", "b@y.com")
+            ]
+        );
+        assert_eq!(vec!["a@x.com, b@y.com", "c@z.com"].to_addresses().len(), 3);
+        assert_eq!(
+            ("Doe, Jane", "jane@y.com").to_addresses(),
+            [Address::with_name("Doe, Jane", "jane@y.com")]
+        );
+        assert_eq!(Address::new("a@x.com").to_addresses().len(), 1);
+    }
+
     // ========================================================================
     // Tests for to_ascii() - IDN/Punycode conversion
     // ========================================================================
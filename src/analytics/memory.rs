@@ -0,0 +1,92 @@
+//! In-memory analytics store.
+
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use super::{AnalyticsEvent, AnalyticsStats, AnalyticsStore, EventKind, TimeRange};
+use crate::error::MailError;
+
+/// Keeps analytics events in memory.
+///
+/// Events are lost on restart; use it in tests, or in apps that only need
+/// recent numbers.
+#[derive(Debug, Default)]
+pub struct MemoryAnalyticsStore {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    events: Vec<AnalyticsEvent>,
+    tags: HashMap<String, HashSet<String>>,
+}
+
+impl MemoryAnalyticsStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of events stored.
+    pub fn len(&self) -> usize {
+        self.inner.lock().events.len()
+    }
+
+    /// Whether no events are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all events.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.events.clear();
+        inner.tags.clear();
+    }
+}
+
+#[async_trait]
+impl AnalyticsStore for MemoryAnalyticsStore {
+    async fn record(&self, event: &AnalyticsEvent) -> Result<(), MailError> {
+        let mut inner = self.inner.lock();
+        if !event.tags.is_empty() {
+            inner
+                .tags
+                .entry(event.message_id.clone())
+                .or_default()
+                .extend(event.tags.iter().cloned());
+        }
+        inner.events.push(event.clone());
+        Ok(())
+    }
+
+    async fn stats(
+        &self,
+        tag: Option<&str>,
+        range: TimeRange,
+    ) -> Result<AnalyticsStats, MailError> {
+        let inner = self.inner.lock();
+        let mut seen: HashSet<(EventKind, &str)> = HashSet::new();
+        let mut stats = AnalyticsStats::default();
+        for event in &inner.events {
+            if !range.contains(&event.timestamp) {
+                continue;
+            }
+            if let Some(tag) = tag {
+                let tagged = inner
+                    .tags
+                    .get(&event.message_id)
+                    .is_some_and(|tags| tags.contains(tag));
+                if !tagged {
+                    continue;
+                }
+            }
+            if seen.insert((event.kind, event.message_id.as_str())) {
+                stats.add(event.kind, 1);
+            }
+        }
+        Ok(stats)
+    }
+}
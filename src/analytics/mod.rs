@@ -0,0 +1,627 @@
+//! Email analytics from webhook and tracking events.
+//!
+//! [`Analytics`] records delivery, open, click, bounce and complaint events
+//! in a pluggable [`AnalyticsStore`] and answers rate queries by tag and time
+//! range, so small apps get basic email analytics without a data pipeline:
+//!
+//! ```rust,ignore
+//! use chrono::{Duration, Utc};
+//! use missive::analytics::Analytics;
+//!
+//! let analytics = Analytics::memory();
+//!
+//! // In your webhook handler, with the provider's payload as JSON:
+//! analytics.record_webhook("sendgrid", &payload).await?;
+//!
+//! let last_week = Utc::now() - Duration::days(7)..Utc::now();
+//! let rate = analytics.open_rate(Some("welcome"), last_week).await?;
+//! ```
+//!
+//! ## Stores
+//!
+//! | Store | Feature | Notes |
+//! |-------|---------|-------|
+//! | [`MemoryAnalyticsStore`] | `analytics` | Lost on restart; for tests and single-process apps |
+//! | [`sqlite::SqliteAnalyticsStore`] | `analytics-sqlite` | Durable, one file |
+//!
+//! ## Counting
+//!
+//! Each event belongs to a message (the provider's message ID, or the
+//! tracking ID for [`TrackingEvent`](crate::TrackingEvent)s). Counts are of
+//! distinct messages, so an email opened five times counts once. Tags on any
+//! event of a message apply to all of its events, since open and click
+//! webhooks often don't repeat them. Rates are relative to delivered
+//! messages; the bounce rate is bounced / (delivered + bounced).
+
+use std::fmt;
+use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::bounce::BounceClass;
+use crate::error::MailError;
+
+mod memory;
+#[cfg(feature = "analytics-sqlite")]
+pub mod sqlite;
+mod webhook;
+
+pub use memory::MemoryAnalyticsStore;
+
+/// What happened to a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// Accepted by the receiving server.
+    Delivered,
+    /// The recipient opened the email.
+    Opened,
+    /// The recipient followed a link.
+    Clicked,
+    /// Not delivered, and the provider has stopped trying.
+    Bounced,
+    /// The recipient reported the email as spam.
+    Complained,
+    /// The recipient unsubscribed.
+    Unsubscribed,
+}
+
+impl EventKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [EventKind; 6] = [
+        Self::Delivered,
+        Self::Opened,
+        Self::Clicked,
+        Self::Bounced,
+        Self::Complained,
+        Self::Unsubscribed,
+    ];
+
+    /// The snake_case name, as serialized.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Opened => "opened",
+            Self::Clicked => "clicked",
+            Self::Bounced => "bounced",
+            Self::Complained => "complained",
+            Self::Unsubscribed => "unsubscribed",
+        }
+    }
+
+    /// Parse a name from [`as_str`](Self::as_str).
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == name)
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One delivery or engagement event for a message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    /// What happened.
+    pub kind: EventKind,
+    /// The message it happened to.
+    pub message_id: String,
+    /// The recipient, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    /// Tags (categories) of the message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When it happened.
+    pub timestamp: DateTime<Utc>,
+    /// The link followed, for clicks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Why the message bounced, for bounces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounce: Option<BounceClass>,
+}
+
+impl AnalyticsEvent {
+    /// An event that happened now.
+    pub fn new(kind: EventKind, message_id: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message_id: message_id.into(),
+            recipient: None,
+            tags: Vec::new(),
+            timestamp: Utc::now(),
+            url: None,
+            bounce: None,
+        }
+    }
+
+    /// Set the recipient.
+    pub fn recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipient = Some(recipient.into());
+        self
+    }
+
+    /// Add a tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Set when the event happened.
+    pub fn at(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Set the link followed.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Set why the message bounced.
+    pub fn bounce(mut self, class: BounceClass) -> Self {
+        self.bounce = Some(class);
+        self
+    }
+
+    /// Parse a provider's webhook payload.
+    ///
+    /// `provider` is a [`Mailer::provider_name`](crate::Mailer::provider_name):
+    /// `amazon_ses` (the SNS message or event publishing record), `resend`,
+    /// `sendgrid` (the event array), `mailgun`, `postmark`, `brevo` or
+    /// `mailjet` (one event or an array). Events that aren't tracked
+    /// (sends, deferrals, ...) are skipped.
+    pub fn from_webhook(provider: &str, payload: &Value) -> Vec<Self> {
+        match payload.as_array() {
+            Some(events) => events
+                .iter()
+                .filter_map(|event| webhook::parse(provider, event))
+                .collect(),
+            None => webhook::parse(provider, payload).into_iter().collect(),
+        }
+    }
+
+    /// An open or click from self-hosted [`Tracking`](crate::Tracking),
+    /// keyed by the tracking ID.
+    #[cfg(feature = "tracking")]
+    pub fn from_tracking(event: &crate::TrackingEvent) -> Self {
+        match event {
+            crate::TrackingEvent::Open { id, recipient } => Self {
+                recipient: recipient.clone(),
+                ..Self::new(EventKind::Opened, id)
+            },
+            crate::TrackingEvent::Click { id, recipient, url } => Self {
+                recipient: recipient.clone(),
+                url: Some(url.clone()),
+                ..Self::new(EventKind::Clicked, id)
+            },
+        }
+    }
+}
+
+/// A time range for analytics queries; either end may be open.
+///
+/// Converts from `start..end`, `start..`, `..end` and `..`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    /// Inclusive start.
+    pub start: Option<DateTime<Utc>>,
+    /// Exclusive end.
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Whether `timestamp` is in the range.
+    pub fn contains(&self, timestamp: &DateTime<Utc>) -> bool {
+        let after_start = match self.start {
+            Some(start) => *timestamp >= start,
+            None => true,
+        };
+        let before_end = match self.end {
+            Some(end) => *timestamp < end,
+            None => true,
+        };
+        after_start && before_end
+    }
+}
+
+impl From<Range<DateTime<Utc>>> for TimeRange {
+    fn from(range: Range<DateTime<Utc>>) -> Self {
+        Self {
+            start: Some(range.start),
+            end: Some(range.end),
+        }
+    }
+}
+
+impl From<RangeFrom<DateTime<Utc>>> for TimeRange {
+    fn from(range: RangeFrom<DateTime<Utc>>) -> Self {
+        Self {
+            start: Some(range.start),
+            end: None,
+        }
+    }
+}
+
+impl From<RangeTo<DateTime<Utc>>> for TimeRange {
+    fn from(range: RangeTo<DateTime<Utc>>) -> Self {
+        Self {
+            start: None,
+            end: Some(range.end),
+        }
+    }
+}
+
+impl From<RangeFull> for TimeRange {
+    fn from(_: RangeFull) -> Self {
+        Self::default()
+    }
+}
+
+/// Distinct messages per event kind, for one tag and time range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsStats {
+    /// Messages delivered.
+    pub delivered: u64,
+    /// Messages opened.
+    pub opened: u64,
+    /// Messages with a link followed.
+    pub clicked: u64,
+    /// Messages bounced.
+    pub bounced: u64,
+    /// Messages reported as spam.
+    pub complained: u64,
+    /// Messages whose recipient unsubscribed.
+    pub unsubscribed: u64,
+}
+
+impl AnalyticsStats {
+    /// The count for `kind`.
+    pub fn count(&self, kind: EventKind) -> u64 {
+        match kind {
+            EventKind::Delivered => self.delivered,
+            EventKind::Opened => self.opened,
+            EventKind::Clicked => self.clicked,
+            EventKind::Bounced => self.bounced,
+            EventKind::Complained => self.complained,
+            EventKind::Unsubscribed => self.unsubscribed,
+        }
+    }
+
+    /// Add `count` to `kind`.
+    pub fn add(&mut self, kind: EventKind, count: u64) {
+        let field = match kind {
+            EventKind::Delivered => &mut self.delivered,
+            EventKind::Opened => &mut self.opened,
+            EventKind::Clicked => &mut self.clicked,
+            EventKind::Bounced => &mut self.bounced,
+            EventKind::Complained => &mut self.complained,
+            EventKind::Unsubscribed => &mut self.unsubscribed,
+        };
+        *field += count;
+    }
+
+    /// Opened / delivered (0.0 when nothing was delivered).
+    pub fn open_rate(&self) -> f64 {
+        rate(self.opened, self.delivered)
+    }
+
+    /// Clicked / delivered.
+    pub fn click_rate(&self) -> f64 {
+        rate(self.clicked, self.delivered)
+    }
+
+    /// Clicked / opened: how many readers followed a link.
+    pub fn click_to_open_rate(&self) -> f64 {
+        rate(self.clicked, self.opened)
+    }
+
+    /// Bounced / (delivered + bounced).
+    pub fn bounce_rate(&self) -> f64 {
+        rate(self.bounced, self.delivered + self.bounced)
+    }
+
+    /// Complained / delivered.
+    pub fn complaint_rate(&self) -> f64 {
+        rate(self.complained, self.delivered)
+    }
+
+    /// Unsubscribed / delivered.
+    pub fn unsubscribe_rate(&self) -> f64 {
+        rate(self.unsubscribed, self.delivered)
+    }
+}
+
+fn rate(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// Storage for analytics events.
+///
+/// Implement it to keep events in your own database; see the
+/// [module docs](self) for how events are counted.
+#[async_trait]
+pub trait AnalyticsStore: Send + Sync {
+    /// Store an event.
+    async fn record(&self, event: &AnalyticsEvent) -> Result<(), MailError>;
+
+    /// Count distinct messages per kind with an event in `range`, only
+    /// messages tagged `tag` when set.
+    async fn stats(&self, tag: Option<&str>, range: TimeRange)
+        -> Result<AnalyticsStats, MailError>;
+}
+
+/// Records events in an [`AnalyticsStore`] and queries them.
+///
+/// Cheap to clone; clones share the store.
+#[derive(Clone)]
+pub struct Analytics {
+    store: Arc<dyn AnalyticsStore>,
+}
+
+impl fmt::Debug for Analytics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Analytics").finish_non_exhaustive()
+    }
+}
+
+impl Analytics {
+    /// Use `store`.
+    pub fn new(store: impl AnalyticsStore + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+        }
+    }
+
+    /// Use a new [`MemoryAnalyticsStore`].
+    pub fn memory() -> Self {
+        Self::new(MemoryAnalyticsStore::new())
+    }
+
+    /// Record an event.
+    pub async fn record(&self, event: &AnalyticsEvent) -> Result<(), MailError> {
+        self.store.record(event).await
+    }
+
+    /// Record the events in a provider's webhook payload (see
+    /// [`AnalyticsEvent::from_webhook`]), returning how many were recorded.
+    pub async fn record_webhook(
+        &self,
+        provider: &str,
+        payload: &Value,
+    ) -> Result<usize, MailError> {
+        let events = AnalyticsEvent::from_webhook(provider, payload);
+        for event in &events {
+            self.store.record(event).await?;
+        }
+        Ok(events.len())
+    }
+
+    /// Record an open or click from self-hosted tracking.
+    #[cfg(feature = "tracking")]
+    pub async fn record_tracking(&self, event: &crate::TrackingEvent) -> Result<(), MailError> {
+        self.store
+            .record(&AnalyticsEvent::from_tracking(event))
+            .await
+    }
+
+    /// Counts for messages tagged `tag` (or all messages) in `range`.
+    pub async fn stats(
+        &self,
+        tag: Option<&str>,
+        range: impl Into<TimeRange>,
+    ) -> Result<AnalyticsStats, MailError> {
+        self.store.stats(tag, range.into()).await
+    }
+
+    /// Opened / delivered for `tag` in `range`.
+    pub async fn open_rate(
+        &self,
+        tag: Option<&str>,
+        range: impl Into<TimeRange>,
+    ) -> Result<f64, MailError> {
+        Ok(self.stats(tag, range).await?.open_rate())
+    }
+
+    /// Clicked / delivered for `tag` in `range`.
+    pub async fn click_rate(
+        &self,
+        tag: Option<&str>,
+        range: impl Into<TimeRange>,
+    ) -> Result<f64, MailError> {
+        Ok(self.stats(tag, range).await?.click_rate())
+    }
+
+    /// Bounced / (delivered + bounced) for `tag` in `range`.
+    pub async fn bounce_rate(
+        &self,
+        tag: Option<&str>,
+        range: impl Into<TimeRange>,
+    ) -> Result<f64, MailError> {
+        Ok(self.stats(tag, range).await?.bounce_rate())
+    }
+
+    /// Complained / delivered for `tag` in `range`.
+    pub async fn complaint_rate(
+        &self,
+        tag: Option<&str>,
+        range: impl Into<TimeRange>,
+    ) -> Result<f64, MailError> {
+        Ok(self.stats(tag, range).await?.complaint_rate())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_memory_stats_by_tag_and_range() {
+        let analytics = Analytics::memory();
+        let now = Utc::now();
+        let events = [
+            AnalyticsEvent::new(EventKind::Delivered, "a").tag("welcome"),
+            AnalyticsEvent::new(EventKind::Delivered, "b").tag("welcome"),
+            AnalyticsEvent::new(EventKind::Delivered, "c").tag("digest"),
+            AnalyticsEvent::new(EventKind::Opened, "a"),
+            AnalyticsEvent::new(EventKind::Opened, "a"),
+            AnalyticsEvent::new(EventKind::Opened, "c"),
+            AnalyticsEvent::new(EventKind::Clicked, "a"),
+            AnalyticsEvent::new(EventKind::Bounced, "d").tag("welcome"),
+            AnalyticsEvent::new(EventKind::Delivered, "old")
+                .tag("welcome")
+                .at(now - Duration::days(30)),
+        ];
+        for event in &events {
+            analytics.record(event).await.unwrap();
+        }
+
+        let last_week = now - Duration::days(7)..now + Duration::minutes(1);
+        let welcome = analytics.stats(Some("welcome"), last_week).await.unwrap();
+        assert_eq!(welcome.delivered, 2);
+        assert_eq!(welcome.opened, 1);
+        assert_eq!(welcome.clicked, 1);
+        assert_eq!(welcome.bounced, 1);
+        assert_eq!(welcome.open_rate(), 0.5);
+        assert!((welcome.bounce_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+
+        assert_eq!(analytics.stats(None, ..).await.unwrap().delivered, 4);
+        assert_eq!(analytics.open_rate(Some("digest"), ..).await.unwrap(), 1.0);
+        assert_eq!(
+            analytics.click_rate(Some("missing"), ..).await.unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_time_range() {
+        let now = Utc::now();
+        let range = TimeRange::from(now..now + Duration::seconds(1));
+        assert!(range.contains(&now));
+        assert!(!range.contains(&(now + Duration::seconds(1))));
+        assert!(TimeRange::from(..).contains(&now));
+        assert!(!TimeRange::from(..now).contains(&now));
+    }
+
+    #[test]
+    fn test_sendgrid_webhook() {
+        let payload = json!([
+            {"event": "processed", "sg_message_id": "abc.filter1", "email": "a@example.com"},
+            {"event": "open", "sg_message_id": "abc.filter1", "email": "a@example.com",
+             "timestamp": 1700000000, "category": ["welcome", "onboarding"]},
+            {"event": "click", "sg_message_id": "abc.filter1", "url": "https://example.com"},
+            {"event": "bounce", "sg_message_id": "def.filter2", "status": "5.1.1"}
+        ]);
+        let events = AnalyticsEvent::from_webhook("sendgrid", &payload);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].kind, EventKind::Opened);
+        assert_eq!(events[0].message_id, "abc");
+        assert_eq!(events[0].tags, ["welcome", "onboarding"]);
+        assert_eq!(events[0].timestamp.timestamp(), 1700000000);
+        assert_eq!(events[1].url.as_deref(), Some("https://example.com"));
+        assert_eq!(events[2].bounce, Some(BounceClass::HardBounce));
+    }
+
+    #[test]
+    fn test_mailgun_webhook() {
+        let event = |name: &str, severity: &str| {
+            json!({"event-data": {
+                "event": name,
+                "severity": severity,
+                "recipient": "a@example.com",
+                "timestamp": 1700000000.5,
+                "tags": ["welcome"],
+                "message": {"headers": {"message-id": "<id@mg.example.com>"}}
+            }})
+        };
+        let delivered = AnalyticsEvent::from_webhook("mailgun", &event("delivered", ""));
+        assert_eq!(delivered[0].kind, EventKind::Delivered);
+        assert_eq!(delivered[0].message_id, "id@mg.example.com");
+        assert_eq!(delivered[0].timestamp.timestamp_millis(), 1700000000500);
+        assert!(AnalyticsEvent::from_webhook("mailgun", &event("failed", "temporary")).is_empty());
+        let failed = AnalyticsEvent::from_webhook("mailgun", &event("failed", "permanent"));
+        assert_eq!(failed[0].kind, EventKind::Bounced);
+        assert!(failed[0].bounce.is_some());
+    }
+
+    #[test]
+    fn test_postmark_and_resend_webhooks() {
+        let open = json!({
+            "RecordType": "Open",
+            "MessageID": "pm-1",
+            "Recipient": "a@example.com",
+            "Tag": "welcome",
+            "ReceivedAt": "2024-01-01T00:00:00Z"
+        });
+        let events = AnalyticsEvent::from_webhook("postmark", &open);
+        assert_eq!(events[0].kind, EventKind::Opened);
+        assert_eq!(events[0].tags, ["welcome"]);
+        assert_eq!(
+            events[0].timestamp.to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+
+        let complained = json!({
+            "type": "email.complained",
+            "created_at": "2024-01-01T00:00:00.000Z",
+            "data": {"email_id": "re-1", "to": ["a@example.com"], "tags": {"category": "welcome"}}
+        });
+        let events = AnalyticsEvent::from_webhook("resend", &complained);
+        assert_eq!(events[0].kind, EventKind::Complained);
+        assert_eq!(events[0].recipient.as_deref(), Some("a@example.com"));
+        assert_eq!(events[0].tags, ["welcome"]);
+    }
+
+    #[test]
+    fn test_amazon_ses_sns_webhook() {
+        let message = json!({
+            "notificationType": "Bounce",
+            "mail": {
+                "messageId": "ses-1",
+                "destination": ["a@example.com"],
+                "tags": {"campaign": ["welcome"]}
+            },
+            "bounce": {"bounceType": "Permanent", "bounceSubType": "General",
+                       "timestamp": "2024-01-01T00:00:00.000Z"}
+        });
+        let sns = json!({"Type": "Notification", "Message": message.to_string()});
+        let events = AnalyticsEvent::from_webhook("amazon_ses", &sns);
+        assert_eq!(events[0].kind, EventKind::Bounced);
+        assert_eq!(events[0].message_id, "ses-1");
+        assert_eq!(events[0].tags, ["welcome"]);
+        assert_eq!(events[0].bounce, Some(BounceClass::HardBounce));
+        assert!(AnalyticsEvent::from_webhook("unknown", &message).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_record_webhook_counts_events() {
+        let analytics = Analytics::memory();
+        let payload = json!([
+            {"event": "delivered", "sg_message_id": "a.f", "category": "welcome"},
+            {"event": "open", "sg_message_id": "a.f"},
+            {"event": "deferred", "sg_message_id": "b.f"}
+        ]);
+        assert_eq!(
+            analytics
+                .record_webhook("sendgrid", &payload)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(analytics.open_rate(Some("welcome"), ..).await.unwrap(), 1.0);
+    }
+}
@@ -0,0 +1,196 @@
+//! SQLite-backed analytics store.
+//!
+//! Durable analytics in one file, for apps without a database server:
+//!
+//! ```rust,ignore
+//! use missive::analytics::{sqlite::SqliteAnalyticsStore, Analytics};
+//!
+//! let store = SqliteAnalyticsStore::connect("sqlite://analytics.db").await?;
+//! let analytics = Analytics::new(store);
+//! ```
+//!
+//! Events go in `missive_events` and message tags in
+//! `missive_message_tags`; both tables are created if missing.
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+
+use super::{AnalyticsEvent, AnalyticsStats, AnalyticsStore, EventKind, TimeRange};
+use crate::error::MailError;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS missive_events (
+    message_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    recipient TEXT,
+    url TEXT,
+    bounce TEXT,
+    occurred_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS missive_events_occurred_at ON missive_events (occurred_at);
+CREATE TABLE IF NOT EXISTS missive_message_tags (
+    message_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (message_id, tag)
+);
+";
+
+/// Keeps analytics events in SQLite.
+#[derive(Debug, Clone)]
+pub struct SqliteAnalyticsStore {
+    pool: SqlitePool,
+}
+
+impl SqliteAnalyticsStore {
+    /// Open (or create) the database at `url` (e.g. `sqlite://analytics.db`
+    /// or `sqlite::memory:`) and create the tables.
+    pub async fn connect(url: &str) -> Result<Self, MailError> {
+        let options = SqliteConnectOptions::from_str(url)
+            .map_err(|e| MailError::Configuration(format!("SQLite: {}", e)))?
+            .create_if_missing(true);
+        let mut pool = SqlitePoolOptions::new();
+        // Every connection to `:memory:` is a separate database
+        if url.contains(":memory:") {
+            pool = pool
+                .max_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None);
+        }
+        let pool = pool
+            .connect_with(options)
+            .await
+            .map_err(|e| MailError::Configuration(format!("SQLite: {}", e)))?;
+        let store = Self { pool };
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    /// Use an existing pool. Call [`migrate`](Self::migrate) unless the
+    /// tables already exist.
+    pub fn from_pool(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Create the tables and index if they don't exist.
+    pub async fn migrate(&self) -> Result<(), MailError> {
+        sqlx::raw_sql(SCHEMA)
+            .execute(&self.pool)
+            .await
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    /// The underlying pool.
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl AnalyticsStore for SqliteAnalyticsStore {
+    async fn record(&self, event: &AnalyticsEvent) -> Result<(), MailError> {
+        let mut tx = self.pool.begin().await.map_err(sqlite_error)?;
+        sqlx::query(
+            "INSERT INTO missive_events (message_id, kind, recipient, url, bounce, occurred_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&event.message_id)
+        .bind(event.kind.as_str())
+        .bind(&event.recipient)
+        .bind(&event.url)
+        .bind(event.bounce.map(|class| class.as_str()))
+        .bind(event.timestamp.timestamp_millis())
+        .execute(&mut *tx)
+        .await
+        .map_err(sqlite_error)?;
+        for tag in &event.tags {
+            sqlx::query(
+                "INSERT OR IGNORE INTO missive_message_tags (message_id, tag) VALUES (?, ?)",
+            )
+            .bind(&event.message_id)
+            .bind(tag)
+            .execute(&mut *tx)
+            .await
+            .map_err(sqlite_error)?;
+        }
+        tx.commit().await.map_err(sqlite_error)
+    }
+
+    async fn stats(
+        &self,
+        tag: Option<&str>,
+        range: TimeRange,
+    ) -> Result<AnalyticsStats, MailError> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT kind, COUNT(DISTINCT message_id) FROM missive_events e \
+             WHERE (?1 IS NULL OR occurred_at >= ?1) \
+               AND (?2 IS NULL OR occurred_at < ?2) \
+               AND (?3 IS NULL OR EXISTS (SELECT 1 FROM missive_message_tags t \
+                    WHERE t.message_id = e.message_id AND t.tag = ?3)) \
+             GROUP BY kind",
+        )
+        .bind(range.start.map(|t| t.timestamp_millis()))
+        .bind(range.end.map(|t| t.timestamp_millis()))
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(sqlite_error)?;
+
+        let mut stats = AnalyticsStats::default();
+        for (kind, count) in rows {
+            if let Some(kind) = EventKind::parse(&kind) {
+                stats.add(kind, count as u64);
+            }
+        }
+        Ok(stats)
+    }
+}
+
+fn sqlite_error(e: sqlx::Error) -> MailError {
+    MailError::Internal(format!("SQLite: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::Analytics;
+    use crate::bounce::BounceClass;
+
+    #[tokio::test]
+    async fn test_sqlite_stats() {
+        let store = SqliteAnalyticsStore::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let analytics = Analytics::new(store);
+        let events = [
+            AnalyticsEvent::new(EventKind::Delivered, "a").tag("welcome"),
+            AnalyticsEvent::new(EventKind::Delivered, "b").tag("welcome"),
+            AnalyticsEvent::new(EventKind::Delivered, "c").tag("digest"),
+            AnalyticsEvent::new(EventKind::Opened, "a"),
+            AnalyticsEvent::new(EventKind::Opened, "a"),
+            AnalyticsEvent::new(EventKind::Clicked, "a").url("https://example.com"),
+            AnalyticsEvent::new(EventKind::Bounced, "d")
+                .tag("welcome")
+                .bounce(BounceClass::HardBounce),
+        ];
+        for event in &events {
+            analytics.record(event).await.unwrap();
+        }
+
+        let welcome = analytics.stats(Some("welcome"), ..).await.unwrap();
+        assert_eq!(welcome.delivered, 2);
+        assert_eq!(welcome.opened, 1);
+        assert_eq!(welcome.clicked, 1);
+        assert_eq!(welcome.bounced, 1);
+        assert_eq!(analytics.open_rate(Some("welcome"), ..).await.unwrap(), 0.5);
+        assert_eq!(analytics.stats(None, ..).await.unwrap().delivered, 3);
+
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        assert_eq!(
+            analytics.stats(None, later..).await.unwrap(),
+            AnalyticsStats::default()
+        );
+    }
+}
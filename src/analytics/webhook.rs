@@ -0,0 +1,258 @@
+//! Provider webhook payloads to [`AnalyticsEvent`]s.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+
+use super::{AnalyticsEvent, EventKind};
+use crate::bounce::BounceClassifier;
+
+/// Parse one webhook event from `provider`.
+pub(super) fn parse(provider: &str, event: &Value) -> Option<AnalyticsEvent> {
+    let parsed = match provider {
+        "amazon_ses" => amazon_ses(event),
+        "resend" => resend(event),
+        "sendgrid" => sendgrid(event),
+        "mailgun" => mailgun(event.get("event-data").unwrap_or(event)),
+        "postmark" => postmark(event),
+        "brevo" => brevo(event),
+        "mailjet" => mailjet(event),
+        _ => None,
+    }?;
+    if parsed.kind == EventKind::Bounced && parsed.bounce.is_none() {
+        let class = BounceClassifier::new().classify(provider, event);
+        return Some(AnalyticsEvent {
+            bounce: class,
+            ..parsed
+        });
+    }
+    Some(parsed)
+}
+
+/// SNS notifications, unwrapping the SNS envelope, and event publishing
+/// records (`eventType`).
+fn amazon_ses(event: &Value) -> Option<AnalyticsEvent> {
+    if let Some(message) = str_at(event, &["Message"]) {
+        let inner: Value = serde_json::from_str(message).ok()?;
+        return amazon_ses(&inner);
+    }
+    let kind = str_at(event, &["notificationType"]).or(str_at(event, &["eventType"]))?;
+    let (kind, field) = match kind {
+        "Delivery" => (EventKind::Delivered, "delivery"),
+        "Open" => (EventKind::Opened, "open"),
+        "Click" => (EventKind::Clicked, "click"),
+        "Bounce" => (EventKind::Bounced, "bounce"),
+        "Complaint" => (EventKind::Complained, "complaint"),
+        _ => return None,
+    };
+    let mail = event.get("mail")?;
+    let mut parsed = AnalyticsEvent::new(kind, str_at(mail, &["messageId"])?);
+    parsed.recipient = mail
+        .get("destination")
+        .and_then(|to| to.get(0))
+        .and_then(Value::as_str)
+        .map(String::from);
+    // Message tags are `{"name": ["value"]}`
+    if let Some(tags) = mail.get("tags").and_then(Value::as_object) {
+        parsed.tags = tags
+            .values()
+            .filter_map(Value::as_array)
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect();
+    }
+    if kind == EventKind::Bounced {
+        parsed.bounce = BounceClassifier::new().classify("amazon_ses", event);
+    }
+    let details = event.get(field);
+    parsed.url = details.and_then(|d| str_at(d, &["link"])).map(String::from);
+    if let Some(timestamp) = details
+        .and_then(|d| d.get("timestamp"))
+        .or(mail.get("timestamp"))
+        .and_then(timestamp)
+    {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+fn resend(event: &Value) -> Option<AnalyticsEvent> {
+    let kind = match str_at(event, &["type"])? {
+        "email.delivered" => EventKind::Delivered,
+        "email.opened" => EventKind::Opened,
+        "email.clicked" => EventKind::Clicked,
+        "email.bounced" => EventKind::Bounced,
+        "email.complained" => EventKind::Complained,
+        _ => return None,
+    };
+    let data = event.get("data")?;
+    let mut parsed = AnalyticsEvent::new(kind, str_at(data, &["email_id"])?);
+    parsed.recipient = data
+        .get("to")
+        .and_then(|to| to.get(0))
+        .and_then(Value::as_str)
+        .map(String::from);
+    parsed.tags = tags(data.get("tags"));
+    parsed.url = str_at(data, &["click", "link"]).map(String::from);
+    if let Some(timestamp) = event.get("created_at").and_then(timestamp) {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+/// SendGrid's `sg_message_id` is the `X-Message-Id` returned on send,
+/// followed by `.filter...`.
+fn sendgrid(event: &Value) -> Option<AnalyticsEvent> {
+    let kind = match str_at(event, &["event"])? {
+        "delivered" => EventKind::Delivered,
+        "open" => EventKind::Opened,
+        "click" => EventKind::Clicked,
+        "bounce" | "blocked" | "dropped" => EventKind::Bounced,
+        "spamreport" => EventKind::Complained,
+        "unsubscribe" | "group_unsubscribe" => EventKind::Unsubscribed,
+        _ => return None,
+    };
+    let id = str_at(event, &["sg_message_id"])?;
+    let id = id.split('.').next().unwrap_or(id);
+    let mut parsed = AnalyticsEvent::new(kind, id);
+    parsed.recipient = str_at(event, &["email"]).map(String::from);
+    parsed.tags = tags(event.get("category"));
+    parsed.url = str_at(event, &["url"]).map(String::from);
+    if let Some(timestamp) = event.get("timestamp").and_then(timestamp) {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+/// Temporary failures are retried, so only permanent ones are bounces.
+fn mailgun(event: &Value) -> Option<AnalyticsEvent> {
+    let kind = match str_at(event, &["event"])? {
+        "delivered" => EventKind::Delivered,
+        "opened" => EventKind::Opened,
+        "clicked" => EventKind::Clicked,
+        "failed" if str_at(event, &["severity"]) == Some("temporary") => return None,
+        "failed" => EventKind::Bounced,
+        "complained" => EventKind::Complained,
+        "unsubscribed" => EventKind::Unsubscribed,
+        _ => return None,
+    };
+    let id = str_at(event, &["message", "headers", "message-id"])?;
+    let mut parsed = AnalyticsEvent::new(kind, id.trim_matches(|c| c == '<' || c == '>'));
+    parsed.recipient = str_at(event, &["recipient"]).map(String::from);
+    parsed.tags = tags(event.get("tags"));
+    parsed.url = str_at(event, &["url"]).map(String::from);
+    if let Some(timestamp) = event.get("timestamp").and_then(timestamp) {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+fn postmark(event: &Value) -> Option<AnalyticsEvent> {
+    let (kind, at) = match str_at(event, &["RecordType"])? {
+        "Delivery" => (EventKind::Delivered, "DeliveredAt"),
+        "Open" => (EventKind::Opened, "ReceivedAt"),
+        "Click" => (EventKind::Clicked, "ReceivedAt"),
+        "Bounce" => (EventKind::Bounced, "BouncedAt"),
+        "SpamComplaint" => (EventKind::Complained, "BouncedAt"),
+        "SubscriptionChange" if event.get("SuppressSending") == Some(&Value::Bool(true)) => {
+            (EventKind::Unsubscribed, "ChangedAt")
+        }
+        _ => return None,
+    };
+    let mut parsed = AnalyticsEvent::new(kind, str_at(event, &["MessageID"])?);
+    parsed.recipient = str_at(event, &["Recipient"])
+        .or(str_at(event, &["Email"]))
+        .map(String::from);
+    parsed.tags = tags(event.get("Tag"));
+    parsed.url = str_at(event, &["OriginalLink"]).map(String::from);
+    if let Some(timestamp) = event.get(at).and_then(timestamp) {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+fn brevo(event: &Value) -> Option<AnalyticsEvent> {
+    let kind = match str_at(event, &["event"])? {
+        "delivered" => EventKind::Delivered,
+        "opened" | "unique_opened" | "proxy_open" => EventKind::Opened,
+        "click" => EventKind::Clicked,
+        "hard_bounce" | "soft_bounce" | "invalid_email" | "blocked" => EventKind::Bounced,
+        "spam" | "complaint" => EventKind::Complained,
+        "unsubscribed" => EventKind::Unsubscribed,
+        _ => return None,
+    };
+    let id = str_at(event, &["message-id"])?;
+    let mut parsed = AnalyticsEvent::new(kind, id.trim_matches(|c| c == '<' || c == '>'));
+    parsed.recipient = str_at(event, &["email"]).map(String::from);
+    parsed.tags = tags(event.get("tags").or(event.get("tag")));
+    parsed.url = str_at(event, &["link"]).map(String::from);
+    if let Some(timestamp) = event.get("ts_event").and_then(timestamp) {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+/// Mailjet's `sent` event means the receiving server accepted the email.
+fn mailjet(event: &Value) -> Option<AnalyticsEvent> {
+    let kind = match str_at(event, &["event"])? {
+        "sent" => EventKind::Delivered,
+        "open" => EventKind::Opened,
+        "click" => EventKind::Clicked,
+        "bounce" | "blocked" => EventKind::Bounced,
+        "spam" => EventKind::Complained,
+        "unsub" => EventKind::Unsubscribed,
+        _ => return None,
+    };
+    let id = match event.get("MessageID")? {
+        Value::Number(id) => id.to_string(),
+        Value::String(id) => id.clone(),
+        _ => return None,
+    };
+    let mut parsed = AnalyticsEvent::new(kind, id);
+    parsed.recipient = str_at(event, &["email"]).map(String::from);
+    parsed.tags = tags(event.get("customcampaign"));
+    parsed.url = str_at(event, &["url"]).map(String::from);
+    if let Some(timestamp) = event.get("time").and_then(timestamp) {
+        parsed.timestamp = timestamp;
+    }
+    Some(parsed)
+}
+
+/// Tags as a string, an array of strings, or an object of `name: value`
+/// pairs (whose values are the tags).
+fn tags(value: Option<&Value>) -> Vec<String> {
+    let strings = |values: &mut dyn Iterator<Item = &Value>| {
+        values
+            .filter_map(Value::as_str)
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    match value {
+        Some(Value::String(tag)) if !tag.is_empty() => vec![tag.clone()],
+        Some(Value::Array(tags)) => strings(&mut tags.iter()),
+        Some(Value::Object(tags)) => strings(&mut tags.values()),
+        _ => Vec::new(),
+    }
+}
+
+/// A Unix timestamp in seconds, or an RFC 3339 string.
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Number(secs) => {
+            let millis = (secs.as_f64()? * 1000.0) as i64;
+            Utc.timestamp_millis_opt(millis).single()
+        }
+        Value::String(text) => DateTime::parse_from_rfc3339(text)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+/// The string at `path` in `value`.
+fn str_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(value, |value, key| value.get(key))?
+        .as_str()
+}
@@ -7,6 +7,7 @@ use crate::error::MailError;
 
 /// Type of attachment disposition.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum AttachmentType {
     /// Regular attachment (shown as downloadable file)
     #[default]
@@ -41,21 +42,29 @@ pub enum AttachmentType {
 /// // From path (lazy - file read at delivery time)
 /// let attachment = Attachment::from_path_lazy("/path/to/large-file.pdf")?;
 /// ```
+///
+/// In JSON, `data` is a standard base64 string. Byte arrays are also accepted
+/// when deserializing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Attachment {
     /// Filename for the attachment
     pub filename: String,
     /// MIME content type (e.g., "application/pdf", "image/png")
     pub content_type: String,
     /// Raw attachment data (empty if using path-based lazy loading)
+    #[serde(default, with = "base64_data")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub data: Vec<u8>,
     /// File path for lazy loading.
     /// If set, data will be read from this path when needed.
     #[serde(default)]
     pub path: Option<String>,
     /// Whether this is an inline or regular attachment
+    #[serde(default)]
     pub disposition: AttachmentType,
     /// Content-ID for inline attachments (used as cid: reference)
+    #[serde(default)]
     pub content_id: Option<String>,
     /// Custom headers for the attachment
     #[serde(default)]
@@ -156,6 +165,83 @@ impl Attachment {
         })
     }
 
+    /// Create an attachment backed by an S3 object, fetched at delivery time.
+    ///
+    /// Only the `s3://bucket/key` reference is stored, so the email stays
+    /// small when serialized into a job queue. [`deliver`](crate::deliver)
+    /// downloads the object, or hands a presigned URL to providers that
+    /// accept one; see [`s3`](crate::s3) for credentials.
+    ///
+    /// The filename defaults to the last segment of `key`.
+    #[cfg(feature = "attachment-s3")]
+    pub fn from_s3(bucket: impl AsRef<str>, key: impl AsRef<str>) -> Self {
+        let key = key.as_ref().trim_start_matches('/');
+        let filename = key
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .unwrap_or("attachment")
+            .to_string();
+        let content_type = mime_guess::from_path(&filename)
+            .first_or_octet_stream()
+            .to_string();
+
+        Self {
+            filename,
+            content_type,
+            data: Vec::new(), // Fetched at delivery
+            path: Some(format!("s3://{}/{}", bucket.as_ref(), key)),
+            disposition: AttachmentType::Attachment,
+            content_id: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Default size cap for [`zip`](Self::zip): 10 MiB, the message limit of
+    /// Amazon SES and Postmark. `deliver()` still checks the whole encoded
+    /// message against the provider's limit.
+    #[cfg(feature = "zip")]
+    pub const DEFAULT_ZIP_LIMIT: usize = 10 * 1024 * 1024;
+
+    /// Bundle several attachments into one ZIP attachment.
+    ///
+    /// Useful where a provider limits the number of attachments per message.
+    /// Each file keeps its filename inside the archive. Lazy attachments
+    /// ([`from_path_lazy`](Self::from_path_lazy)) are streamed from disk while
+    /// compressing. Fails if the archive would exceed
+    /// [`DEFAULT_ZIP_LIMIT`](Self::DEFAULT_ZIP_LIMIT); see
+    /// [`zip_with_limit`](Self::zip_with_limit).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let bundle = Attachment::zip("reports.zip", [
+    ///     Attachment::from_path_lazy("q1.csv")?,
+    ///     Attachment::from_path_lazy("q2.csv")?,
+    ///     Attachment::from_bytes("summary.txt", summary.into_bytes()),
+    /// ])?;
+    /// let email = email.attachment(bundle);
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn zip(
+        filename: impl Into<String>,
+        files: impl IntoIterator<Item = Attachment>,
+    ) -> Result<Self, MailError> {
+        Self::zip_with_limit(filename, files, Self::DEFAULT_ZIP_LIMIT)
+    }
+
+    /// Like [`zip`](Self::zip), failing once the archive exceeds `max_bytes`.
+    #[cfg(feature = "zip")]
+    pub fn zip_with_limit(
+        filename: impl Into<String>,
+        files: impl IntoIterator<Item = Attachment>,
+        max_bytes: usize,
+    ) -> Result<Self, MailError> {
+        let filename = filename.into();
+        let data = crate::zip::write(&filename, files.into_iter().collect(), max_bytes)?;
+        Ok(Self::from_bytes(filename, data).content_type("application/zip"))
+    }
+
     /// Set the content type explicitly.
     pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
         self.content_type = content_type.into();
@@ -198,6 +284,12 @@ impl Attachment {
     /// - `AttachmentMissingContent` - No data and no path provided
     pub fn get_data(&self) -> Result<Vec<u8>, MailError> {
         if let Some(ref path) = self.path {
+            if path.starts_with("s3://") {
+                return Err(MailError::AttachmentReadError(format!(
+                    "{}: S3 attachments are fetched by missive::deliver (attachment-s3 feature)",
+                    path
+                )));
+            }
             // Lazy load from path
             std::fs::read(path).map_err(|e| {
                 if e.kind() == std::io::ErrorKind::NotFound {
@@ -252,6 +344,31 @@ impl Attachment {
     }
 }
 
+/// Serialize attachment data as base64, accepting byte arrays as well.
+mod base64_data {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Data {
+        Base64(String),
+        Bytes(Vec<u8>),
+    }
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        match Data::deserialize(deserializer)? {
+            Data::Base64(encoded) => STANDARD.decode(encoded).map_err(serde::de::Error::custom),
+            Data::Bytes(bytes) => Ok(bytes),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,355 @@
+//! Delivery audit log.
+//!
+//! [`Audited`] wraps any mailer and records every delivery attempt (provider,
+//! message ID, recipients, outcome, latency and error) to an [`AuditSink`].
+//! Two sinks are included: [`JsonLinesSink`] appends one JSON object per line
+//! to a file, and [`TracingSink`] emits a `tracing` event per attempt.
+//!
+//! ```rust,ignore
+//! use missive::audit::{Audited, JsonLinesSink};
+//! use missive::providers::ResendMailer;
+//!
+//! let sink = JsonLinesSink::open("/var/log/app/email-audit.jsonl")?;
+//! let mailer = Audited::new(ResendMailer::new(api_key), sink).hash_recipients();
+//! missive::configure(mailer);
+//! ```
+//!
+//! The global mailer is wrapped automatically when `EMAIL_AUDIT_LOG` is set to
+//! a file path. Set `EMAIL_AUDIT_RECIPIENTS=hashed` to store SHA-256 hashes of
+//! recipient addresses instead of the addresses themselves.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+#[cfg(not(feature = "wasm"))]
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+#[cfg(feature = "wasm")]
+use web_time::Instant;
+
+use crate::address::hash_address;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
+
+/// Outcome of a delivery attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditStatus {
+    /// The provider accepted the email.
+    Sent,
+    /// Delivery failed.
+    Failed,
+}
+
+/// One delivery attempt.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    /// When the attempt finished.
+    pub timestamp: DateTime<Utc>,
+    /// Provider name of the wrapped mailer.
+    pub provider: &'static str,
+    /// Provider message ID, if delivery succeeded.
+    pub message_id: Option<String>,
+    /// `to`, `cc` and `bcc` addresses, plain or SHA-256 hashed.
+    pub recipients: Vec<String>,
+    /// Whether the recipients are hashed.
+    pub recipients_hashed: bool,
+    /// Outcome of the attempt.
+    pub status: AuditStatus,
+    /// Time spent in the provider, in milliseconds.
+    pub latency_ms: u64,
+    /// Error message, if delivery failed.
+    pub error: Option<String>,
+}
+
+/// Destination for audit records.
+///
+/// Implementations should persist each record before returning; a sink that
+/// fails should log the error rather than fail the delivery.
+pub trait AuditSink: Send + Sync {
+    /// Record a delivery attempt.
+    fn record(&self, record: &AuditRecord);
+}
+
+impl<S: AuditSink + ?Sized> AuditSink for Arc<S> {
+    fn record(&self, record: &AuditRecord) {
+        (**self).record(record)
+    }
+}
+
+/// Appends each record as one JSON line to a file.
+///
+/// The file is opened in append mode and never truncated or rewritten.
+pub struct JsonLinesSink {
+    file: Mutex<File>,
+}
+
+impl JsonLinesSink {
+    /// Open (or create) the log file for appending.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for JsonLinesSink {
+    fn record(&self, record: &AuditRecord) {
+        let mut line = match serde_json::to_vec(record) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize audit record");
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        // One write per record keeps lines intact across processes.
+        let mut file = self.file.lock();
+        if let Err(e) = file.write_all(&line).and_then(|_| file.flush()) {
+            tracing::error!(error = %e, "Failed to write audit record");
+        }
+    }
+}
+
+/// Emits each record as a `tracing` event with target `missive::audit`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingSink;
+
+impl AuditSink for TracingSink {
+    fn record(&self, record: &AuditRecord) {
+        tracing::info!(
+            target: "missive::audit",
+            provider = record.provider,
+            message_id = record.message_id.as_deref(),
+            recipients = ?record.recipients,
+            status = ?record.status,
+            latency_ms = record.latency_ms,
+            error = record.error.as_deref(),
+            "Email delivery audited"
+        );
+    }
+}
+
+/// A mailer wrapper that records every delivery attempt.
+pub struct Audited<M> {
+    inner: M,
+    sink: Arc<dyn AuditSink>,
+    hash_recipients: bool,
+}
+
+impl<M: Mailer> Audited<M> {
+    /// Wrap a mailer, recording attempts to `sink`.
+    pub fn new(inner: M, sink: impl AuditSink + 'static) -> Self {
+        Self::with_sink(inner, Arc::new(sink))
+    }
+
+    /// Wrap a mailer with a shared sink.
+    pub fn with_sink(inner: M, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            inner,
+            sink,
+            hash_recipients: false,
+        }
+    }
+
+    /// Record SHA-256 hashes of recipient addresses instead of the addresses.
+    ///
+    /// Addresses are lowercased before hashing, so the same recipient always
+    /// hashes to the same value.
+    pub fn hash_recipients(mut self) -> Self {
+        self.hash_recipients = true;
+        self
+    }
+
+    /// Get a reference to the wrapped mailer.
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    fn record(&self, email: &Email, result: Result<&DeliveryResult, &MailError>, started: Instant) {
+        let recipients = email
+            .all_recipients()
+            .iter()
+            .map(|addr| {
+                if self.hash_recipients {
+                    hash_address(&addr.email)
+                } else {
+                    addr.email.clone()
+                }
+            })
+            .collect();
+
+        let (status, message_id, error) = match result {
+            Ok(r) => (AuditStatus::Sent, Some(r.message_id.clone()), None),
+            Err(e) => (AuditStatus::Failed, None, Some(e.to_string())),
+        };
+
+        self.sink.record(&AuditRecord {
+            timestamp: Utc::now(),
+            provider: self.inner.provider_name(),
+            message_id,
+            recipients,
+            recipients_hashed: self.hash_recipients,
+            status,
+            latency_ms: started.elapsed().as_millis() as u64,
+            error,
+        });
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Mailer> Mailer for Audited<M> {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let started = Instant::now();
+        let result = self.inner.deliver(email).await;
+        self.record(email, result.as_ref(), started);
+        result
+    }
+
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        let started = Instant::now();
+        let result = self.inner.deliver_many(emails).await;
+        match &result {
+            Ok(results) => {
+                for (email, r) in emails.iter().zip(results) {
+                    self.record(email, Ok(r), started);
+                }
+            }
+            Err(e) => {
+                for email in emails {
+                    self.record(email, Err(e), started);
+                }
+            }
+        }
+        result
+    }
+
+    fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        self.inner.validate_batch(emails)
+    }
+
+    fn batch_concurrency(&self) -> usize {
+        self.inner.batch_concurrency()
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    fn validate_config(&self) -> Result<(), MailError> {
+        self.inner.validate_config()
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.inner.health_check().await
+    }
+
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        self.inner.message_status(message_id).await
+    }
+}
+
+#[cfg(all(test, feature = "local"))]
+mod tests {
+    use super::*;
+    use crate::providers::{FailingMailer, LocalMailer};
+
+    #[derive(Default)]
+    struct MemorySink(Mutex<Vec<AuditRecord>>);
+
+    impl AuditSink for MemorySink {
+        fn record(&self, record: &AuditRecord) {
+            self.0.lock().push(record.clone());
+        }
+    }
+
+    fn email() -> Email {
+        Email::new()
+            .from("sender@example.com")
+            .to("User@Example.com")
+            .bcc("audit@example.com")
+            .subject("Receipt")
+    }
+
+    #[tokio::test]
+    async fn test_records_success_and_failure() {
+        let sink = Arc::new(MemorySink::default());
+        let failing = FailingMailer::local()
+            .fail_for("bounce@example.com")
+            .error(|_| MailError::SendError("connection refused".into()));
+        let mailer = Audited::with_sink(failing, sink.clone());
+
+        let sent = mailer.deliver(&email()).await.unwrap();
+        assert!(mailer
+            .deliver(&email().to("bounce@example.com"))
+            .await
+            .is_err());
+
+        let records = sink.0.lock();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].status, AuditStatus::Sent);
+        assert_eq!(records[0].provider, "local");
+        assert_eq!(
+            records[0].message_id.as_deref(),
+            Some(sent.message_id.as_str())
+        );
+        assert_eq!(
+            records[0].recipients,
+            ["User@Example.com", "audit@example.com"]
+        );
+        assert_eq!(records[1].status, AuditStatus::Failed);
+        assert!(records[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_hashed_recipients() {
+        let sink = Arc::new(MemorySink::default());
+        let mailer = Audited::with_sink(LocalMailer::new(), sink.clone()).hash_recipients();
+
+        mailer.deliver_many(&[email(), email()]).await.unwrap();
+
+        let records = sink.0.lock();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].recipients_hashed);
+        assert_eq!(records[0].recipients[0], hash_address("user@example.com"));
+        assert_eq!(records[0].recipients[0].len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_sink() {
+        let path =
+            std::env::temp_dir().join(format!("missive-audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let mailer = Audited::new(LocalMailer::new(), JsonLinesSink::open(&path).unwrap());
+
+        mailer.deliver(&email()).await.unwrap();
+        mailer.deliver(&email()).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["status"], "sent");
+        assert_eq!(lines[0]["provider"], "local");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
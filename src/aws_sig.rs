@@ -0,0 +1,44 @@
+//! AWS Signature Version 4 helpers, shared by Amazon SES and S3 attachments.
+
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use sha2::{Digest, Sha256};
+
+pub(crate) const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Hex-encoded signature of `string_to_sign` with the derived signing key.
+pub(crate) fn signature(
+    secret: &str,
+    date_time: &DateTime<Utc>,
+    region: &str,
+    service: &str,
+    string_to_sign: &str,
+) -> String {
+    let k_date = hmac_sha256(
+        format!("AWS4{}", secret).as_bytes(),
+        amz_date(date_time).as_bytes(),
+    );
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()))
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data).as_ref().to_vec()
+}
+
+pub(crate) fn hex_sha256(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex::encode(hasher.finalize())
+}
+
+pub(crate) fn amz_date(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%d").to_string()
+}
+
+pub(crate) fn amz_datetime(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
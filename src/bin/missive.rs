@@ -0,0 +1,277 @@
+//! `missive` command-line tool.
+//!
+//! ```text
+//! missive send --to you@example.com --subject Hi --html body.html --attach report.pdf
+//! missive preview serve --addr 127.0.0.1:8025 emails.json
+//! missive doctor --send-test
+//! ```
+//!
+//! The mailer is configured from the environment exactly as in a library
+//! (`EMAIL_PROVIDER`, `RESEND_API_KEY`, `EMAIL_FROM`, `missive.toml`, ...), so
+//! install it with the providers you want to test:
+//! `cargo install missive --features cli,resend`.
+
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use missive::{Attachment, Email, MailError, MemoryStorage, Storage};
+
+#[derive(Parser)]
+#[command(
+    name = "missive",
+    version,
+    about = "Send and preview emails from the terminal"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Send an email through the configured provider.
+    Send(SendArgs),
+    /// Mailbox preview UI.
+    #[command(subcommand)]
+    Preview(PreviewCommand),
+    /// Check the configuration, provider credentials and sender DNS records.
+    Doctor(DoctorArgs),
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("body").required(true).multiple(true).args(["html", "text"])))]
+struct SendArgs {
+    /// Recipient (repeatable).
+    #[arg(long, required = true)]
+    to: Vec<String>,
+    /// CC recipient (repeatable).
+    #[arg(long)]
+    cc: Vec<String>,
+    /// BCC recipient (repeatable).
+    #[arg(long)]
+    bcc: Vec<String>,
+    /// Sender, defaults to `EMAIL_FROM`.
+    #[arg(long)]
+    from: Option<String>,
+    /// Reply-to address.
+    #[arg(long)]
+    reply_to: Option<String>,
+    /// Subject line.
+    #[arg(long)]
+    subject: String,
+    /// File holding the HTML body.
+    #[arg(long, value_name = "FILE")]
+    html: Option<PathBuf>,
+    /// File holding the plain-text body.
+    #[arg(long, value_name = "FILE")]
+    text: Option<PathBuf>,
+    /// Attach a file (repeatable).
+    #[arg(long, value_name = "FILE")]
+    attach: Vec<PathBuf>,
+    /// Send through a named mailer (`EMAIL_PROVIDER__<NAME>`).
+    #[arg(long)]
+    mailer: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum PreviewCommand {
+    /// Serve the mailbox preview UI until interrupted.
+    Serve(ServeArgs),
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8025")]
+    addr: String,
+    /// Also accept mail over SMTP at this address.
+    #[cfg(feature = "smtp-capture")]
+    #[arg(long, value_name = "ADDR")]
+    smtp: Option<String>,
+    /// Emails to show, as JSON files holding an email or an array of emails.
+    files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct DoctorArgs {
+    /// Check this domain's DNS records instead of the `EMAIL_FROM` domain.
+    #[arg(long)]
+    domain: Option<String>,
+    /// Also send a probe email to `EMAIL_SELF_TEST_TO` (or `EMAIL_FROM`).
+    #[arg(long)]
+    send_test: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Send(args) => send(args),
+        Command::Preview(PreviewCommand::Serve(args)) => serve(args),
+        Command::Doctor(args) => doctor(args),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> Result<F::Output, MailError> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| MailError::Internal(format!("failed to start runtime: {}", e)))?;
+    Ok(runtime.block_on(future))
+}
+
+fn read(path: &Path) -> Result<String, MailError> {
+    fs::read_to_string(path)
+        .map_err(|e| MailError::Configuration(format!("{}: {}", path.display(), e)))
+}
+
+// ============================================================================
+// send
+// ============================================================================
+
+fn send(args: SendArgs) -> Result<ExitCode, MailError> {
+    let mut email = Email::new()
+        .to(args.to)
+        .cc(args.cc)
+        .bcc(args.bcc)
+        .subject(args.subject);
+    if let Some(from) = args.from {
+        email = email.from(from);
+    }
+    if let Some(reply_to) = args.reply_to {
+        email = email.reply_to(reply_to);
+    }
+    if let Some(path) = &args.html {
+        email = email.html_body(read(path)?);
+    }
+    if let Some(path) = &args.text {
+        email = email.text_body(read(path)?);
+    }
+    for path in &args.attach {
+        email = email.attachment(Attachment::from_path(path)?);
+    }
+
+    let result = match &args.mailer {
+        Some(name) => block_on(missive::deliver_via(name, &email))?,
+        None => block_on(missive::deliver(&email))?,
+    }?;
+    println!("Sent: {}", result.message_id);
+    Ok(ExitCode::SUCCESS)
+}
+
+// ============================================================================
+// preview serve
+// ============================================================================
+
+fn serve(args: ServeArgs) -> Result<ExitCode, MailError> {
+    let storage = MemoryStorage::shared();
+    for path in &args.files {
+        let json = read(path)?;
+        let emails = match serde_json::from_str::<Vec<Email>>(&json) {
+            Ok(emails) => emails,
+            Err(_) => vec![serde_json::from_str::<Email>(&json)
+                .map_err(|e| MailError::Configuration(format!("{}: {}", path.display(), e)))?],
+        };
+        for email in emails {
+            storage.push(email);
+        }
+    }
+
+    #[cfg(feature = "smtp-capture")]
+    if let Some(addr) = &args.smtp {
+        missive::smtp_capture::SmtpCaptureServer::new(addr, storage.clone())
+            .map_err(|e| MailError::Configuration(format!("{}: {}", addr, e)))?
+            .spawn();
+        println!("Accepting SMTP on {}", addr);
+    }
+
+    let server = missive::preview::PreviewServer::new(&args.addr, storage)
+        .map_err(|e| MailError::Configuration(format!("{}: {}", args.addr, e)))?;
+    println!("Mailbox preview at http://{}", args.addr);
+    server
+        .run()
+        .map_err(|e| MailError::Internal(format!("preview server stopped: {}", e)))?;
+    Ok(ExitCode::SUCCESS)
+}
+
+// ============================================================================
+// doctor
+// ============================================================================
+
+fn doctor(args: DoctorArgs) -> Result<ExitCode, MailError> {
+    let mut ok = true;
+
+    let mailer = match missive::init() {
+        Ok(()) => missive::mailer(),
+        Err(e) => {
+            println!("provider     FAIL  {}", e);
+            for problem in missive::diagnose().problems {
+                println!("             - {}", problem);
+            }
+            ok = false;
+            None
+        }
+    };
+    if let Some(mailer) = &mailer {
+        println!("provider     ok    {}", mailer.provider_name());
+    }
+
+    let from = missive::default_from();
+    match &from {
+        Some(from) => println!("from         ok    {}", from),
+        None => println!("from         warn  EMAIL_FROM is not set"),
+    }
+
+    if let Some(mailer) = &mailer {
+        match block_on(mailer.health_check())? {
+            Ok(()) => println!("credentials  ok"),
+            Err(e) => {
+                println!("credentials  FAIL  {}", e);
+                ok = false;
+            }
+        }
+        if args.send_test {
+            match block_on(missive::self_test())? {
+                Ok(report) => println!("self-test    ok    {}", report),
+                Err(e) => {
+                    println!("self-test    FAIL  {}", e);
+                    ok = false;
+                }
+            }
+        }
+    }
+
+    let domain = args.domain.or_else(|| {
+        from.as_ref()
+            .and_then(|from| from.email.rsplit_once('@'))
+            .map(|(_, domain)| domain.to_string())
+    });
+    match domain {
+        Some(domain) => match block_on(missive::dns::check_domain(&domain))? {
+            Ok(report) => {
+                ok &= report.is_ok();
+                print!("\n{}", report);
+            }
+            Err(e) => {
+                println!("dns          FAIL  {}", e);
+                ok = false;
+            }
+        },
+        None => println!("dns          skip  no sender domain"),
+    }
+
+    Ok(if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,83 @@
+//! Blocking API for synchronous programs.
+//!
+//! These functions mirror the async ones at the crate root, driving them on
+//! an internal single-threaded Tokio runtime. Use them from binaries that
+//! don't run an async runtime of their own:
+//!
+//! ```rust,ignore
+//! use missive::Email;
+//!
+//! fn main() -> Result<(), missive::MailError> {
+//!     let email = Email::new()
+//!         .to("ops@example.com")
+//!         .subject("Nightly export finished")
+//!         .text_body("All good.");
+//!
+//!     missive::blocking::deliver(&email)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Every provider works, SMTP included. Calling these from inside an async
+//! runtime returns [`MailError::Configuration`]; use the async functions there.
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{DeliveryResult, Mailer};
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn block_on<F: Future>(future: F) -> Result<F::Output, MailError> {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(MailError::Configuration(
+            "missive::blocking cannot be used inside an async runtime; use the async API".into(),
+        ));
+    }
+
+    let runtime = match RUNTIME.get() {
+        Some(runtime) => runtime,
+        None => {
+            let runtime = Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| MailError::Internal(format!("failed to start runtime: {}", e)))?;
+            RUNTIME.get_or_init(|| runtime)
+        }
+    };
+    Ok(runtime.block_on(future))
+}
+
+/// Blocking [`deliver`](crate::deliver).
+pub fn deliver(email: &Email) -> Result<DeliveryResult, MailError> {
+    block_on(crate::deliver(email))?
+}
+
+/// Blocking [`deliver_with`](crate::deliver_with).
+pub fn deliver_with<M: Mailer>(email: &Email, mailer: &M) -> Result<DeliveryResult, MailError> {
+    block_on(crate::deliver_with(email, mailer))?
+}
+
+/// Blocking [`deliver_many`](crate::deliver_many).
+pub fn deliver_many(emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+    block_on(crate::deliver_many(emails))?
+}
+
+/// Blocking [`deliver_personalized`](crate::deliver_personalized).
+pub fn deliver_personalized(email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+    block_on(crate::deliver_personalized(email))?
+}
+
+/// Blocking [`deliver_via`](crate::deliver_via).
+pub fn deliver_via(name: &str, email: &Email) -> Result<DeliveryResult, MailError> {
+    block_on(crate::deliver_via(name, email))?
+}
+
+/// Blocking [`health_check`](crate::health_check).
+pub fn health_check() -> Result<(), MailError> {
+    block_on(crate::health_check())?
+}
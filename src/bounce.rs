@@ -0,0 +1,581 @@
+//! Provider-agnostic bounce classification.
+//!
+//! Every provider reports bounces differently: SES and Resend send a
+//! `bounceType`/`bounceSubType` pair, Postmark a `Type` name, SendGrid and
+//! Mailgun an event name plus an SMTP enhanced status code. A
+//! [`BounceClassifier`] maps all of them, and bare SMTP codes, onto one
+//! [`BounceClass`], so suppression logic doesn't care which provider sent
+//! the webhook:
+//!
+//! ```rust,ignore
+//! use missive::{BounceClass, BounceClassifier};
+//!
+//! let classifier = BounceClassifier::new();
+//!
+//! // In your webhook handler, with the provider's event as JSON:
+//! if let Some(class) = classifier.classify("sendgrid", &event) {
+//!     if class.should_suppress() {
+//!         suppressions.add(&recipient).await?;
+//!     }
+//! }
+//!
+//! assert_eq!(classifier.classify_status("5.1.1"), Some(BounceClass::HardBounce));
+//! assert_eq!(classifier.classify_reply(452), Some(BounceClass::SoftBounce));
+//! ```
+//!
+//! | Provider | Event |
+//! |----------|-------|
+//! | `amazon_ses` | SNS notification or event publishing message (`Bounce`, `Complaint`, `DeliveryDelay`, `Reject`) |
+//! | `resend` | `email.bounced`, `email.complained`, `email.delivery_delayed` |
+//! | `sendgrid` | One event from the Event Webhook array |
+//! | `mailgun` | The webhook body or its `event-data` |
+//! | `postmark` | Bounce and spam complaint webhooks |
+//! | `brevo` | Transactional webhook events |
+//! | `mailjet` | One event from the Event API |
+//!
+//! Events that aren't bounces or complaints (deliveries, opens, ...)
+//! classify as `None`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Why an email wasn't delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BounceClass {
+    /// The address doesn't exist or will never accept mail.
+    HardBounce,
+    /// A temporary failure (mailbox full, greylisting, server down).
+    SoftBounce,
+    /// The recipient marked the email as spam.
+    Complaint,
+    /// The provider skipped the address because of an earlier bounce,
+    /// complaint or unsubscribe.
+    Suppressed,
+    /// The receiving server refused the message for policy or content
+    /// reasons (spam filters, DMARC, blocklisted IP), not the address.
+    Blocked,
+}
+
+impl BounceClass {
+    /// Whether the address should stop receiving email: hard bounces,
+    /// complaints and addresses the provider already suppresses.
+    pub fn should_suppress(self) -> bool {
+        matches!(self, Self::HardBounce | Self::Complaint | Self::Suppressed)
+    }
+
+    /// The snake_case name, as serialized.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HardBounce => "hard_bounce",
+            Self::SoftBounce => "soft_bounce",
+            Self::Complaint => "complaint",
+            Self::Suppressed => "suppressed",
+            Self::Blocked => "blocked",
+        }
+    }
+}
+
+impl fmt::Display for BounceClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Maps provider bounce events and SMTP status codes to a [`BounceClass`].
+///
+/// The built-in mapping follows RFC 3463; [`map_status`](Self::map_status)
+/// overrides it for codes your receivers use differently.
+#[derive(Debug, Clone, Default)]
+pub struct BounceClassifier {
+    overrides: HashMap<String, BounceClass>,
+}
+
+impl BounceClassifier {
+    /// Create a classifier with the built-in mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classify an enhanced status code (`"5.7.1"`) or a class and subject
+    /// (`"5.7"`) as `class`. Exact codes win over class and subject.
+    pub fn map_status(mut self, code: impl Into<String>, class: BounceClass) -> Self {
+        self.overrides.insert(code.into(), class);
+        self
+    }
+
+    /// Classify an SMTP enhanced status code, on its own (`"5.1.1"`) or
+    /// inside a diagnostic message (`"smtp; 550 5.1.1 User unknown"`).
+    ///
+    /// Returns `None` for success codes and text without a status code.
+    pub fn classify_status(&self, status: &str) -> Option<BounceClass> {
+        let code = find_status(status)?;
+        let (class_subject, _) = code.rsplit_once('.')?;
+        if let Some(class) = self
+            .overrides
+            .get(code)
+            .or_else(|| self.overrides.get(class_subject))
+        {
+            return Some(*class);
+        }
+
+        let mut parts = code.split('.');
+        let class = parts.next()?;
+        let subject = parts.next()?;
+        let detail = parts.next()?;
+        Some(match (class, subject, detail) {
+            ("2", _, _) => return None,
+            ("4", _, _) => BounceClass::SoftBounce,
+            // Addressing: bad mailbox, bad domain, no MX
+            ("5", "1", _) => BounceClass::HardBounce,
+            // Mailbox disabled; full or over-size is temporary
+            ("5", "2", "1") => BounceClass::HardBounce,
+            ("5", "2", _) => BounceClass::SoftBounce,
+            // Receiving system, protocol
+            ("5", "3", _) | ("5", "5", _) => BounceClass::SoftBounce,
+            // Routing: no route to the domain
+            ("5", "4", _) => BounceClass::HardBounce,
+            // Content, security and policy
+            ("5", "6", _) | ("5", "7", _) => BounceClass::Blocked,
+            _ => BounceClass::HardBounce,
+        })
+    }
+
+    /// Classify a basic SMTP reply code (`550`).
+    ///
+    /// Prefer [`classify_status`](Self::classify_status) when an enhanced
+    /// code is available; `550` alone can mean an unknown user or a spam
+    /// rejection.
+    pub fn classify_reply(&self, code: u16) -> Option<BounceClass> {
+        match code {
+            400..=499 | 552 => Some(BounceClass::SoftBounce),
+            554 => Some(BounceClass::Blocked),
+            500..=599 => Some(BounceClass::HardBounce),
+            _ => None,
+        }
+    }
+
+    /// Classify a provider's webhook event.
+    ///
+    /// `provider` is the [`Mailer::provider_name`](crate::Mailer::provider_name)
+    /// of the provider that sent it. Returns `None` for events that aren't
+    /// bounces or complaints, and for providers without a mapping.
+    pub fn classify(&self, provider: &str, event: &Value) -> Option<BounceClass> {
+        match provider {
+            "amazon_ses" => self.amazon_ses(event),
+            "resend" => self.resend(event),
+            "sendgrid" => self.sendgrid(event),
+            "mailgun" => self.mailgun(event.get("event-data").unwrap_or(event)),
+            "postmark" => self.postmark(event),
+            "brevo" => brevo(event),
+            "mailjet" => mailjet(event),
+            _ => None,
+        }
+    }
+
+    /// The class of the first status code found in `details`.
+    fn status_class(&self, details: &[Option<&str>]) -> Option<BounceClass> {
+        details
+            .iter()
+            .flatten()
+            .find_map(|text| self.classify_status(text))
+    }
+
+    fn amazon_ses(&self, event: &Value) -> Option<BounceClass> {
+        let kind = str_at(event, &["notificationType"]).or(str_at(event, &["eventType"]));
+        match kind? {
+            "Bounce" => {
+                let bounce = event.get("bounce")?;
+                let recipient = bounce.get("bouncedRecipients").and_then(|r| r.get(0));
+                let details = recipient.map_or([None, None], |r| {
+                    [str_at(r, &["status"]), str_at(r, &["diagnosticCode"])]
+                });
+                Some(self.ses_bounce(
+                    str_at(bounce, &["bounceType"]),
+                    str_at(bounce, &["bounceSubType"]),
+                    &details,
+                ))
+            }
+            "Complaint" => Some(BounceClass::Complaint),
+            "DeliveryDelay" => Some(BounceClass::SoftBounce),
+            "Reject" => Some(BounceClass::Blocked),
+            _ => None,
+        }
+    }
+
+    /// Resend sends through SES and passes its bounce types on.
+    fn resend(&self, event: &Value) -> Option<BounceClass> {
+        match str_at(event, &["type"])? {
+            "email.bounced" => {
+                let bounce = event.get("data")?.get("bounce");
+                let field = |key| bounce.and_then(|b| str_at(b, &[key]));
+                Some(self.ses_bounce(field("type"), field("subType"), &[field("message")]))
+            }
+            "email.complained" => Some(BounceClass::Complaint),
+            "email.delivery_delayed" => Some(BounceClass::SoftBounce),
+            _ => None,
+        }
+    }
+
+    /// SES bounce types, as `Permanent` in notifications or `PERMANENT` in
+    /// the SESv2 API.
+    pub(crate) fn ses_bounce(
+        &self,
+        bounce_type: Option<&str>,
+        sub_type: Option<&str>,
+        details: &[Option<&str>],
+    ) -> BounceClass {
+        let bounce_type = bounce_type.map(str::to_ascii_lowercase);
+        match (bounce_type.as_deref(), sub_type) {
+            (_, Some("Suppressed" | "OnAccountSuppressionList")) => BounceClass::Suppressed,
+            (Some("permanent"), Some("UnsupportedAttachment")) => BounceClass::Blocked,
+            (Some("permanent"), _) => self
+                .status_class(details)
+                .unwrap_or(BounceClass::HardBounce),
+            (Some("transient"), Some("ContentRejected" | "AttachmentRejected")) => {
+                BounceClass::Blocked
+            }
+            (Some("transient"), _) => BounceClass::SoftBounce,
+            // Undetermined
+            _ => self
+                .status_class(details)
+                .unwrap_or(BounceClass::SoftBounce),
+        }
+    }
+
+    fn sendgrid(&self, event: &Value) -> Option<BounceClass> {
+        match str_at(event, &["event"])? {
+            "bounce" if str_at(event, &["type"]) == Some("blocked") => Some(BounceClass::Blocked),
+            "bounce" => Some(
+                self.status_class(&[str_at(event, &["status"]), str_at(event, &["reason"])])
+                    .unwrap_or(BounceClass::HardBounce),
+            ),
+            "blocked" => Some(BounceClass::Blocked),
+            "deferred" => Some(BounceClass::SoftBounce),
+            "spamreport" => Some(BounceClass::Complaint),
+            "dropped" => match str_at(event, &["reason"])? {
+                "Bounced Address" | "Spam Reporting Address" | "Unsubscribed Address" => {
+                    Some(BounceClass::Suppressed)
+                }
+                "Invalid" => Some(BounceClass::HardBounce),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn mailgun(&self, event: &Value) -> Option<BounceClass> {
+        match str_at(event, &["event"])? {
+            "complained" => Some(BounceClass::Complaint),
+            "failed" => {
+                let reason = str_at(event, &["reason"]).unwrap_or_default();
+                if reason.starts_with("suppress-") {
+                    return Some(BounceClass::Suppressed);
+                }
+                if reason == "espblock" {
+                    return Some(BounceClass::Blocked);
+                }
+                if str_at(event, &["severity"]) == Some("temporary") {
+                    return Some(BounceClass::SoftBounce);
+                }
+                let status = self.status_class(&[
+                    str_at(event, &["delivery-status", "enhanced-code"]),
+                    str_at(event, &["delivery-status", "message"]),
+                    str_at(event, &["delivery-status", "description"]),
+                ]);
+                let code = event
+                    .get("delivery-status")
+                    .and_then(|s| s.get("code"))
+                    .and_then(Value::as_u64)
+                    .and_then(|code| u16::try_from(code).ok());
+                Some(
+                    status
+                        .or_else(|| code.and_then(|code| self.classify_reply(code)))
+                        .unwrap_or(BounceClass::HardBounce),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn postmark(&self, event: &Value) -> Option<BounceClass> {
+        if str_at(event, &["RecordType"]) == Some("SpamComplaint") {
+            return Some(BounceClass::Complaint);
+        }
+        match str_at(event, &["Type"])? {
+            "HardBounce" | "BadEmailAddress" => Some(BounceClass::HardBounce),
+            "SoftBounce" | "Transient" | "DnsError" | "ChallengeVerification" => {
+                Some(BounceClass::SoftBounce)
+            }
+            "SpamComplaint" => Some(BounceClass::Complaint),
+            "SpamNotification" | "VirusNotification" | "Blocked" | "DMARCPolicy" => {
+                Some(BounceClass::Blocked)
+            }
+            "ManuallyDeactivated" | "Unsubscribe" => Some(BounceClass::Suppressed),
+            "Unknown" => Some(
+                str_at(event, &["Details"])
+                    .and_then(|details| self.classify_status(details))
+                    .unwrap_or(BounceClass::SoftBounce),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Brevo's event names already distinguish hard and soft bounces;
+/// `blocked` means the address is on the account's blocklist.
+fn brevo(event: &Value) -> Option<BounceClass> {
+    match str_at(event, &["event"])? {
+        "hard_bounce" | "invalid_email" => Some(BounceClass::HardBounce),
+        "soft_bounce" | "deferred" => Some(BounceClass::SoftBounce),
+        "spam" | "complaint" => Some(BounceClass::Complaint),
+        "blocked" => Some(BounceClass::Suppressed),
+        _ => None,
+    }
+}
+
+/// Mailjet flags hard bounces with `hard_bounce`.
+fn mailjet(event: &Value) -> Option<BounceClass> {
+    match str_at(event, &["event"])? {
+        "bounce" => match event.get("hard_bounce").and_then(Value::as_bool) {
+            Some(true) => Some(BounceClass::HardBounce),
+            _ => Some(BounceClass::SoftBounce),
+        },
+        "blocked" => Some(BounceClass::Blocked),
+        "spam" => Some(BounceClass::Complaint),
+        _ => None,
+    }
+}
+
+/// The string at `path` in `value`.
+fn str_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(value, |value, key| value.get(key))?
+        .as_str()
+}
+
+/// The first RFC 3463 enhanced status code (`class.subject.detail`) in `text`.
+fn find_status(text: &str) -> Option<&str> {
+    text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|token| token.trim_matches('.'))
+        .find(|token| {
+            let mut parts = token.split('.');
+            let class = parts.next().unwrap_or_default();
+            let rest: Vec<_> = parts.collect();
+            matches!(class, "2" | "4" | "5")
+                && rest.len() == 2
+                && rest
+                    .iter()
+                    .all(|p| (1..=3).contains(&p.len()) && p.bytes().all(|b| b.is_ascii_digit()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_status() {
+        let c = BounceClassifier::new();
+        assert_eq!(c.classify_status("5.1.1"), Some(BounceClass::HardBounce));
+        assert_eq!(c.classify_status("5.2.2"), Some(BounceClass::SoftBounce));
+        assert_eq!(c.classify_status("4.7.1"), Some(BounceClass::SoftBounce));
+        assert_eq!(c.classify_status("5.7.26"), Some(BounceClass::Blocked));
+        assert_eq!(
+            c.classify_status("smtp; 550 5.1.1 <a@example.com>: Recipient address rejected."),
+            Some(BounceClass::HardBounce)
+        );
+        assert_eq!(c.classify_status("2.0.0"), None);
+        assert_eq!(c.classify_status("User unknown"), None);
+        assert_eq!(c.classify_status("version 1.2.3"), None);
+    }
+
+    #[test]
+    fn test_map_status_overrides() {
+        let c = BounceClassifier::new()
+            .map_status("5.7", BounceClass::SoftBounce)
+            .map_status("5.7.1", BounceClass::HardBounce);
+        assert_eq!(c.classify_status("5.7.1"), Some(BounceClass::HardBounce));
+        assert_eq!(c.classify_status("5.7.26"), Some(BounceClass::SoftBounce));
+        assert_eq!(c.classify_status("5.1.1"), Some(BounceClass::HardBounce));
+    }
+
+    #[test]
+    fn test_classify_reply() {
+        let c = BounceClassifier::new();
+        assert_eq!(c.classify_reply(421), Some(BounceClass::SoftBounce));
+        assert_eq!(c.classify_reply(550), Some(BounceClass::HardBounce));
+        assert_eq!(c.classify_reply(552), Some(BounceClass::SoftBounce));
+        assert_eq!(c.classify_reply(554), Some(BounceClass::Blocked));
+        assert_eq!(c.classify_reply(250), None);
+    }
+
+    #[test]
+    fn test_classify_amazon_ses() {
+        let c = BounceClassifier::new();
+        let bounce = |kind: &str, sub: &str, status: &str| {
+            json!({
+                "notificationType": "Bounce",
+                "bounce": {
+                    "bounceType": kind,
+                    "bounceSubType": sub,
+                    "bouncedRecipients": [{"emailAddress": "a@example.com", "status": status}]
+                }
+            })
+        };
+        let classify = |event| c.classify("amazon_ses", &event);
+        assert_eq!(
+            classify(bounce("Permanent", "General", "5.1.1")),
+            Some(BounceClass::HardBounce)
+        );
+        assert_eq!(
+            classify(bounce("Permanent", "General", "5.7.1")),
+            Some(BounceClass::Blocked)
+        );
+        assert_eq!(
+            classify(bounce("Permanent", "OnAccountSuppressionList", "5.1.1")),
+            Some(BounceClass::Suppressed)
+        );
+        assert_eq!(
+            classify(bounce("Transient", "MailboxFull", "4.2.2")),
+            Some(BounceClass::SoftBounce)
+        );
+        assert_eq!(
+            classify(json!({"eventType": "Complaint", "complaint": {}})),
+            Some(BounceClass::Complaint)
+        );
+        assert_eq!(classify(json!({"eventType": "Delivery"})), None);
+    }
+
+    #[test]
+    fn test_classify_resend() {
+        let c = BounceClassifier::new();
+        let event = json!({
+            "type": "email.bounced",
+            "data": {"bounce": {"type": "Permanent", "subType": "Suppressed", "message": "..."}}
+        });
+        assert_eq!(c.classify("resend", &event), Some(BounceClass::Suppressed));
+        let event = json!({"type": "email.complained", "data": {}});
+        assert_eq!(c.classify("resend", &event), Some(BounceClass::Complaint));
+        let event = json!({"type": "email.delivered", "data": {}});
+        assert_eq!(c.classify("resend", &event), None);
+    }
+
+    #[test]
+    fn test_classify_sendgrid() {
+        let c = BounceClassifier::new();
+        let classify = |event| c.classify("sendgrid", &event);
+        assert_eq!(
+            classify(json!({"event": "bounce", "type": "bounce", "status": "5.1.1"})),
+            Some(BounceClass::HardBounce)
+        );
+        assert_eq!(
+            classify(json!({"event": "bounce", "type": "blocked", "status": "5.0.0"})),
+            Some(BounceClass::Blocked)
+        );
+        assert_eq!(
+            classify(json!({"event": "dropped", "reason": "Bounced Address"})),
+            Some(BounceClass::Suppressed)
+        );
+        assert_eq!(
+            classify(json!({"event": "spamreport"})),
+            Some(BounceClass::Complaint)
+        );
+        assert_eq!(
+            classify(json!({"event": "deferred"})),
+            Some(BounceClass::SoftBounce)
+        );
+        assert_eq!(classify(json!({"event": "open"})), None);
+    }
+
+    #[test]
+    fn test_classify_mailgun() {
+        let c = BounceClassifier::new();
+        let failed = |severity: &str, reason: &str, status: Value| {
+            json!({"event-data": {
+                "event": "failed",
+                "severity": severity,
+                "reason": reason,
+                "delivery-status": status
+            }})
+        };
+        let classify = |event| c.classify("mailgun", &event);
+        assert_eq!(
+            classify(failed(
+                "permanent",
+                "bounce",
+                json!({"code": 550, "enhanced-code": "5.1.1"})
+            )),
+            Some(BounceClass::HardBounce)
+        );
+        assert_eq!(
+            classify(failed("permanent", "generic", json!({"code": 554})),),
+            Some(BounceClass::Blocked)
+        );
+        assert_eq!(
+            classify(failed("permanent", "suppress-bounce", json!({}))),
+            Some(BounceClass::Suppressed)
+        );
+        assert_eq!(
+            classify(failed(
+                "temporary",
+                "generic",
+                json!({"code": 452, "enhanced-code": "4.2.2"})
+            )),
+            Some(BounceClass::SoftBounce)
+        );
+        assert_eq!(
+            classify(json!({"event": "complained"})),
+            Some(BounceClass::Complaint)
+        );
+    }
+
+    #[test]
+    fn test_classify_postmark_brevo_mailjet() {
+        let c = BounceClassifier::new();
+        let postmark = |event| c.classify("postmark", &event);
+        assert_eq!(
+            postmark(json!({"RecordType": "Bounce", "Type": "HardBounce"})),
+            Some(BounceClass::HardBounce)
+        );
+        assert_eq!(
+            postmark(json!({"RecordType": "Bounce", "Type": "DMARCPolicy"})),
+            Some(BounceClass::Blocked)
+        );
+        assert_eq!(
+            postmark(json!({"RecordType": "SpamComplaint", "Type": "SpamComplaint"})),
+            Some(BounceClass::Complaint)
+        );
+        assert_eq!(
+            c.classify("brevo", &json!({"event": "blocked"})),
+            Some(BounceClass::Suppressed)
+        );
+        assert_eq!(
+            c.classify("brevo", &json!({"event": "soft_bounce"})),
+            Some(BounceClass::SoftBounce)
+        );
+        assert_eq!(
+            c.classify("mailjet", &json!({"event": "bounce", "hard_bounce": true})),
+            Some(BounceClass::HardBounce)
+        );
+        assert_eq!(c.classify("smtp", &json!({"event": "bounce"})), None);
+    }
+
+    #[test]
+    fn test_should_suppress() {
+        assert!(BounceClass::HardBounce.should_suppress());
+        assert!(BounceClass::Complaint.should_suppress());
+        assert!(BounceClass::Suppressed.should_suppress());
+        assert!(!BounceClass::SoftBounce.should_suppress());
+        assert!(!BounceClass::Blocked.should_suppress());
+        assert_eq!(
+            serde_json::to_value(BounceClass::HardBounce).unwrap(),
+            "hard_bounce"
+        );
+        assert_eq!(BounceClass::SoftBounce.to_string(), "soft_bounce");
+    }
+}
@@ -0,0 +1,359 @@
+//! Bulk campaign sending.
+//!
+//! A [`Campaign`] takes one template email and a stream of recipients, and
+//! sends them in chunks through [`Mailer::deliver_personalized`], so
+//! providers with batch APIs get whole chunks at once:
+//!
+//! ```rust,ignore
+//! use missive::{Campaign, Email};
+//! use serde_json::json;
+//!
+//! let email = Email::new()
+//!     .from("news@example.com")
+//!     .subject("Hi {{ name }}")
+//!     .text_body("Your plan: {{ plan }}");
+//!
+//! let campaign = Campaign::new(email)
+//!     .chunk_size(500)
+//!     .concurrency(2)
+//!     .rate_limit(100)
+//!     .on_progress(|p| tracing::info!(sent = p.sent, failed = p.failed, "Campaign progress"));
+//!
+//! let control = campaign.control();
+//! let recipients = users.iter().map(|u| (u.email.as_str(), json!({"name": u.name, "plan": u.plan})));
+//! let report = campaign.run(&mailer, recipients).await;
+//! println!("{} sent, {} failed", report.sent, report.failed.len());
+//! ```
+//!
+//! Recipients are pulled from the iterator one chunk at a time, so a campaign
+//! can stream from a database cursor without loading everyone into memory.
+//! [`CampaignControl`] pauses, resumes or cancels a running campaign from
+//! another task; chunks already in flight finish either way.
+//!
+//! A failed chunk doesn't stop the campaign: its recipients are listed in
+//! [`CampaignReport::failed`] along with entries a batch API rejected
+//! individually ([`DeliveryResult::is_failed`]).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::stream::{self, StreamExt};
+use parking_lot::Mutex;
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::address::{Address, ToAddress};
+use crate::email::Email;
+use crate::mailer::{DeliveryResult, Mailer};
+use crate::personalize::Personalization;
+
+/// Recipients per chunk unless [`Campaign::chunk_size`] is set.
+pub const DEFAULT_CHUNK_SIZE: usize = 100;
+
+type ProgressCallback = Arc<dyn Fn(&CampaignProgress) + Send + Sync>;
+
+/// A personalized bulk send to a stream of recipients.
+pub struct Campaign {
+    email: Email,
+    chunk_size: usize,
+    concurrency: usize,
+    interval: Option<Duration>,
+    on_progress: Option<ProgressCallback>,
+    control: CampaignControl,
+}
+
+impl Campaign {
+    /// Create a campaign from a template email.
+    ///
+    /// `{{ key }}` placeholders in the subject and bodies are filled from each
+    /// recipient's data, as with [`Email::personalize`]. Recipients already on
+    /// the email are ignored.
+    pub fn new(email: Email) -> Self {
+        Self {
+            email,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            concurrency: 1,
+            interval: None,
+            on_progress: None,
+            control: CampaignControl::new(),
+        }
+    }
+
+    /// Recipients per `deliver_personalized()` call (default: 100).
+    ///
+    /// A size of `0` is treated as `1`.
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Chunks sent at once (default: 1).
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Send at most `per_second` emails per second.
+    ///
+    /// Whole chunks are spaced out, so a chunk of 500 at 100/s is followed by
+    /// a five second pause. A limit of `0` is treated as `1`.
+    pub fn rate_limit(mut self, per_second: u32) -> Self {
+        self.interval = Some(Duration::from_secs(1) / per_second.max(1));
+        self
+    }
+
+    /// Call `callback` after every chunk with the running totals.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CampaignProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// A handle to pause, resume or cancel this campaign while it runs.
+    pub fn control(&self) -> CampaignControl {
+        self.control.clone()
+    }
+
+    /// Send the campaign to `recipients` and report the outcome.
+    ///
+    /// Never fails as a whole; delivery errors are collected per recipient.
+    pub async fn run<M, A, I>(&self, mailer: &M, recipients: I) -> CampaignReport
+    where
+        M: Mailer + ?Sized,
+        A: ToAddress,
+        I: IntoIterator<Item = (A, Value)>,
+    {
+        let mut recipients = recipients.into_iter().map(|(to, data)| Personalization {
+            to: to.to_address(),
+            data,
+        });
+        let chunks = std::iter::from_fn(|| {
+            let chunk: Vec<_> = recipients.by_ref().take(self.chunk_size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        });
+
+        let next_slot = Mutex::new(Instant::now());
+        let report = Mutex::new(CampaignReport::default());
+
+        stream::iter(chunks)
+            .take_while(|_| {
+                let control = self.control.clone();
+                async move { control.proceed().await }
+            })
+            .map(|chunk| self.send_chunk(mailer, chunk, &next_slot))
+            .buffer_unordered(self.concurrency)
+            .for_each(|(sent, failed)| {
+                let mut report = report.lock();
+                report.sent += sent;
+                report.failed.extend(failed);
+                if let Some(callback) = &self.on_progress {
+                    callback(&CampaignProgress {
+                        sent: report.sent,
+                        failed: report.failed.len(),
+                    });
+                }
+                async {}
+            })
+            .await;
+
+        let mut report = report.into_inner();
+        report.cancelled = self.control.is_cancelled();
+        report
+    }
+
+    /// Send one chunk, returning the number sent and the failures.
+    async fn send_chunk<M: Mailer + ?Sized>(
+        &self,
+        mailer: &M,
+        chunk: Vec<Personalization>,
+        next_slot: &Mutex<Instant>,
+    ) -> (usize, Vec<CampaignFailure>) {
+        if let Some(interval) = self.interval {
+            let start = {
+                let mut next = next_slot.lock();
+                let start = (*next).max(Instant::now());
+                *next = start + interval * chunk.len() as u32;
+                start
+            };
+            tokio::time::sleep_until(start).await;
+        }
+
+        let mut email = self.email.clone();
+        email.to.clear();
+        email.personalizations = chunk;
+
+        match mailer.deliver_personalized(&email).await {
+            Ok(results) => {
+                if results.len() != email.personalizations.len() {
+                    tracing::warn!(
+                        results = results.len(),
+                        recipients = email.personalizations.len(),
+                        "Campaign chunk returned the wrong number of results"
+                    );
+                }
+                let mut sent = 0;
+                let mut failed = Vec::new();
+                let mut results = results.into_iter();
+                for p in email.personalizations {
+                    match results.next() {
+                        Some(result) if !result.is_failed() => sent += 1,
+                        Some(result) => failed.push(CampaignFailure {
+                            recipient: p.to,
+                            error: rejection(&result),
+                        }),
+                        // Not a send we can confirm
+                        None => failed.push(CampaignFailure {
+                            recipient: p.to,
+                            error: "missing batch result".into(),
+                        }),
+                    }
+                }
+                (sent, failed)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    recipients = email.personalizations.len(),
+                    "Campaign chunk failed"
+                );
+                let failed = email
+                    .personalizations
+                    .into_iter()
+                    .map(|p| CampaignFailure {
+                        recipient: p.to,
+                        error: e.to_string(),
+                    })
+                    .collect();
+                (0, failed)
+            }
+        }
+    }
+}
+
+/// Describe an entry a batch API rejected.
+fn rejection(result: &DeliveryResult) -> String {
+    let response = result.provider_response.as_ref();
+    response
+        .and_then(|r| r.get("message").or_else(|| r.get("error")))
+        .and_then(|m| m.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| "rejected by provider".into())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+/// Pauses, resumes or cancels a running [`Campaign`].
+///
+/// Cheap to clone; every clone controls the same campaign.
+#[derive(Clone)]
+pub struct CampaignControl {
+    state: Arc<watch::Sender<State>>,
+}
+
+impl CampaignControl {
+    fn new() -> Self {
+        Self {
+            state: Arc::new(watch::Sender::new(State::Running)),
+        }
+    }
+
+    /// Stop starting new chunks until [`resume`](Self::resume) is called.
+    pub fn pause(&self) {
+        self.set(State::Paused);
+    }
+
+    /// Continue a paused campaign.
+    pub fn resume(&self) {
+        self.set(State::Running);
+    }
+
+    /// Stop the campaign; remaining recipients are not sent.
+    pub fn cancel(&self) {
+        self.state.send_replace(State::Cancelled);
+    }
+
+    /// Whether the campaign is paused.
+    pub fn is_paused(&self) -> bool {
+        *self.state.borrow() == State::Paused
+    }
+
+    /// Whether the campaign was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.state.borrow() == State::Cancelled
+    }
+
+    /// Cancelling is final.
+    fn set(&self, state: State) {
+        self.state.send_if_modified(|current| {
+            let changed = *current != State::Cancelled && *current != state;
+            if changed {
+                *current = state;
+            }
+            changed
+        });
+    }
+
+    /// Wait while paused; `false` once cancelled.
+    async fn proceed(&self) -> bool {
+        let mut rx = self.state.subscribe();
+        let state = match rx.wait_for(|s| *s != State::Paused).await {
+            Ok(state) => *state,
+            Err(_) => State::Cancelled,
+        };
+        state == State::Running
+    }
+}
+
+impl std::fmt::Debug for CampaignControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CampaignControl")
+            .field("state", &*self.state.borrow())
+            .finish()
+    }
+}
+
+/// Running totals passed to [`Campaign::on_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignProgress {
+    /// Emails accepted by the provider so far.
+    pub sent: usize,
+    /// Recipients that failed so far.
+    pub failed: usize,
+}
+
+/// A recipient the campaign couldn't send to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampaignFailure {
+    /// The recipient.
+    pub recipient: Address,
+    /// Why: the chunk's error, or the provider's message for a rejected entry.
+    pub error: String,
+}
+
+/// Outcome of [`Campaign::run`].
+#[derive(Debug, Clone, Default)]
+pub struct CampaignReport {
+    /// Emails accepted by the provider.
+    pub sent: usize,
+    /// Recipients that failed, with the reason.
+    pub failed: Vec<CampaignFailure>,
+    /// Whether the campaign was cancelled before every recipient was sent.
+    pub cancelled: bool,
+}
+
+impl CampaignReport {
+    /// Whether every recipient was sent.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && !self.cancelled
+    }
+}
@@ -0,0 +1,215 @@
+//! Typed catalog of transactional emails.
+//!
+//! Each email in the catalog is a context struct bound to a template with
+//! `#[derive(EmailTemplate)]`. The template is read at compile time, and the
+//! build fails if a `{{ placeholder }}` has no matching field:
+//!
+//! ```text
+//! templates/
+//!   welcome.subject   # Welcome, {{ name }}!
+//!   welcome.html      # <p>Hi {{ name }}, thanks for joining {{ team.name }}.</p>
+//!   welcome.txt       # optional plain-text body
+//! ```
+//!
+//! ```rust,ignore
+//! use missive::{CatalogEmail, EmailTemplate};
+//!
+//! #[derive(EmailTemplate)]
+//! #[email(template = "welcome")]
+//! struct WelcomeEmail {
+//!     name: String,
+//!     team: Team, // any `Serialize` value; `{{ team.name }}` looks up nested fields
+//! }
+//!
+//! WelcomeEmail { name, team }.to(&user.email).deliver().await?;
+//! ```
+//!
+//! This is the same file layout as `templates::remote::TemplateSource::load_dir`,
+//! so a catalog can also be synced to a provider with the `remote-templates`
+//! feature.
+//! Placeholders are rendered locally with the same `{{ key }}` syntax as
+//! [`Email::personalize`].
+//!
+//! ## Attributes
+//!
+//! | Attribute | Description |
+//! |-----------|-------------|
+//! | `template = "name"` | Template name (required) |
+//! | `dir = "path"` | Template directory relative to the crate root (default: `templates`) |
+//! | `subject = "..."` | Subject line, instead of a `<name>.subject` file |
+//!
+//! A missing field is a compile error:
+//!
+//! ```rust,compile_fail
+//! use missive::EmailTemplate;
+//!
+//! #[derive(EmailTemplate)]
+//! #[email(template = "welcome", dir = "tests/templates")]
+//! struct WelcomeEmail {
+//!     nickname: String, // template uses {{ name }}
+//! }
+//! ```
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::address::{ToAddress, ToAddresses};
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{DeliveryResult, Mailer};
+
+/// Template sources embedded by `#[derive(EmailTemplate)]`.
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateFiles {
+    /// Template name.
+    pub name: &'static str,
+    /// Subject line.
+    pub subject: &'static str,
+    /// HTML body.
+    pub html: Option<&'static str>,
+    /// Plain-text body.
+    pub text: Option<&'static str>,
+}
+
+/// Template variables, keyed by field name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context(Map<String, Value>);
+
+impl Context {
+    /// Create an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serialize `value` under `key`.
+    pub fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), MailError> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| MailError::TemplateError(format!("{}: {}", key, e)))?;
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// Get a variable.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+}
+
+/// An email in the catalog. Implement with `#[derive(EmailTemplate)]`.
+pub trait CatalogEmail {
+    /// The bound template.
+    const TEMPLATE: TemplateFiles;
+
+    /// The struct's fields as template variables.
+    fn context(&self) -> Result<Context, MailError>;
+
+    /// Render the template into an email without recipients.
+    ///
+    /// The variables are also stored as [`assigns`](Email::assign) and the
+    /// template name as the private `template` value.
+    fn render(&self) -> Result<Email, MailError> {
+        let context = Value::Object(self.context()?.0);
+        let files = Self::TEMPLATE;
+
+        let mut email = Email::new()
+            .subject(crate::personalize::render_str(
+                files.subject.trim(),
+                &context,
+            ))
+            .put_private("template", files.name);
+        if let Some(html) = files.html {
+            email = email.html_body(crate::personalize::render_str(html, &context));
+        }
+        if let Some(text) = files.text {
+            email = email.text_body(crate::personalize::render_str(text, &context));
+        }
+        if let Value::Object(vars) = context {
+            email.assigns.extend(vars);
+        }
+        Ok(email)
+    }
+
+    /// Render the template and address it to `to`.
+    fn to(&self, to: impl ToAddresses) -> TemplatedEmail
+    where
+        Self: Sized,
+    {
+        TemplatedEmail {
+            email: self.render().map(|email| email.to(to)),
+        }
+    }
+}
+
+/// A rendered catalog email, returned by [`CatalogEmail::to`].
+///
+/// Rendering errors are returned when the email is delivered or taken with
+/// [`into_email`](Self::into_email).
+#[derive(Debug)]
+#[must_use = "a TemplatedEmail does nothing until delivered"]
+pub struct TemplatedEmail {
+    email: Result<Email, MailError>,
+}
+
+impl TemplatedEmail {
+    fn map(self, f: impl FnOnce(Email) -> Email) -> Self {
+        Self {
+            email: self.email.map(f),
+        }
+    }
+
+    /// Set the sender, instead of `EMAIL_FROM`.
+    pub fn from(self, addr: impl ToAddress) -> Self {
+        self.map(|email| email.from(addr))
+    }
+
+    /// Add CC recipients.
+    pub fn cc(self, addrs: impl ToAddresses) -> Self {
+        self.map(|email| email.cc(addrs))
+    }
+
+    /// Add BCC recipients.
+    pub fn bcc(self, addrs: impl ToAddresses) -> Self {
+        self.map(|email| email.bcc(addrs))
+    }
+
+    /// Add reply-to addresses.
+    pub fn reply_to(self, addrs: impl ToAddresses) -> Self {
+        self.map(|email| email.reply_to(addrs))
+    }
+
+    /// Add a custom header.
+    pub fn header(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.map(|email| email.header(name, value))
+    }
+
+    /// Wrap the rendered HTML body in a registered layout (see
+    /// [`Email::with_layout`]).
+    pub fn with_layout(self, layout: &str) -> Self {
+        Self {
+            email: self.email.and_then(|email| match email.html_body.clone() {
+                Some(html) => email.with_layout(layout, &html),
+                None => Ok(email),
+            }),
+        }
+    }
+
+    /// The rendered email, for further changes or inspection.
+    pub fn into_email(self) -> Result<Email, MailError> {
+        self.email
+    }
+
+    /// Deliver with the global mailer (see [`deliver`](crate::deliver)).
+    pub async fn deliver(self) -> Result<DeliveryResult, MailError> {
+        crate::deliver(&self.email?).await
+    }
+
+    /// Deliver with a specific mailer (see [`deliver_with`](crate::deliver_with)).
+    pub async fn deliver_with<M: Mailer>(self, mailer: &M) -> Result<DeliveryResult, MailError> {
+        crate::deliver_with(&self.email?, mailer).await
+    }
+
+    /// Deliver with a named mailer (see [`deliver_via`](crate::deliver_via)).
+    pub async fn deliver_via(self, name: &str) -> Result<DeliveryResult, MailError> {
+        crate::deliver_via(name, &self.email?).await
+    }
+}
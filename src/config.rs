@@ -0,0 +1,726 @@
+//! Configuration sources for the global mailer.
+//!
+//! By default, missive reads its settings from environment variables.
+//! A [`Config`] holds the same settings in memory and takes precedence over
+//! the environment once installed, with env vars still used as a fallback.
+//!
+//! # Profiles (`missive.toml`)
+//!
+//! With the `toml` feature, settings can be loaded from a file with one
+//! table per environment. Keys in the `[default]` table apply to every profile.
+//!
+//! ```toml
+//! [default]
+//! from = "noreply@example.com"
+//! from_name = "My App"
+//!
+//! [production]
+//! provider = "resend"
+//! resend_api_key = "re_xxxxx"
+//! rate_limit = 10
+//!
+//! [staging]
+//! provider = "resend"
+//! resend_api_key = "re_yyyyy"
+//!
+//! [staging.sandbox]
+//! allowed_domains = ["example.com"]
+//! redirect_to = "qa@example.com"
+//!
+//! [test]
+//! provider = "local"
+//! ```
+//!
+//! Keys are the environment variable names in lowercase. The `EMAIL_` prefix
+//! may be omitted, and nested tables are joined with `_`, so `[staging.sandbox]`
+//! `allowed_domains` maps to `EMAIL_SANDBOX_ALLOWED_DOMAINS`.
+//!
+//! ```rust,ignore
+//! use missive::Config;
+//!
+//! Config::from_file("missive.toml", "staging")?.install();
+//! ```
+//!
+//! If no config is installed, `missive.toml` in the working directory (or the
+//! file named by `MISSIVE_CONFIG`) is loaded automatically on first use, using
+//! the profile named by `MISSIVE_PROFILE`.
+//!
+//! # Builder
+//!
+//! Apps that load credentials from a secret manager can configure everything
+//! in code:
+//!
+//! ```rust,ignore
+//! use missive::{Config, Provider};
+//!
+//! Config::builder()
+//!     .provider(Provider::Resend { api_key: secrets.get("resend")? })
+//!     .from(("My App", "noreply@example.com"))
+//!     .rate_limit(10)
+//!     .build()
+//!     .install();
+//! ```
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::address::ToAddress;
+use crate::validation::{FromDomainPolicy, LimitPolicy};
+
+#[cfg(feature = "toml")]
+use std::path::Path;
+
+#[cfg(feature = "toml")]
+use crate::error::MailError;
+
+/// Installed configuration, consulted before environment variables.
+static CONFIG: RwLock<Option<Arc<Config>>> = RwLock::new(None);
+
+/// Path and profile of the autoloaded `missive.toml`, for [`crate::reload`].
+#[cfg(feature = "toml")]
+static AUTOLOADED: RwLock<Option<(String, String)>> = RwLock::new(None);
+
+/// Key prefixes that are already fully qualified (no `EMAIL_` prefix added).
+const QUALIFIED_PREFIXES: &[&str] = &[
+    "EMAIL_",
+    "MISSIVE_",
+    "SMTP_",
+    "PROTON_BRIDGE_",
+    "RESEND_",
+    "UNSENT_",
+    "POSTMARK_",
+    "SENDGRID_",
+    "BREVO_",
+    "MAILGUN_",
+    "AWS_",
+    "MAILTRAP_",
+    "MAILJET_",
+    "ZEPTOMAIL_",
+    "CUSTOMERIO_",
+];
+
+/// In-memory missive settings, keyed by environment variable name.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    values: HashMap<String, String>,
+}
+
+impl Config {
+    /// Create an empty configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start building a configuration in code.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Set a value by its environment variable name (e.g. `RESEND_API_KEY`).
+    ///
+    /// Short names such as `provider` or `from` are expanded to `EMAIL_PROVIDER`
+    /// and `EMAIL_FROM`.
+    pub fn set(mut self, key: impl AsRef<str>, value: impl Into<String>) -> Self {
+        self.values
+            .insert(normalize_key(key.as_ref()), value.into());
+        self
+    }
+
+    /// Get a value by its environment variable name.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(&normalize_key(key)).map(String::as_str)
+    }
+
+    /// Load a profile from a TOML file.
+    ///
+    /// Values from the `[default]` table are applied first, then overridden
+    /// by the named profile. Returns an error if the file cannot be read or
+    /// the profile does not exist.
+    #[cfg(feature = "toml")]
+    pub fn from_file(path: impl AsRef<Path>, profile: &str) -> Result<Self, MailError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            MailError::Configuration(format!("failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_toml_str(&contents, profile)
+    }
+
+    /// Load a profile from a TOML string.
+    ///
+    /// See [`Config::from_file`].
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(contents: &str, profile: &str) -> Result<Self, MailError> {
+        let table: toml::Table = contents
+            .parse()
+            .map_err(|e| MailError::Configuration(format!("invalid missive config: {}", e)))?;
+
+        let mut config = Self::new();
+        if let Some(toml::Value::Table(defaults)) = table.get("default") {
+            flatten_table(&mut config.values, "", defaults);
+        }
+
+        match table.get(profile) {
+            Some(toml::Value::Table(selected)) => {
+                flatten_table(&mut config.values, "", selected);
+            }
+            Some(_) => {
+                return Err(MailError::Configuration(format!(
+                    "profile '{}' must be a table",
+                    profile
+                )));
+            }
+            None if profile == "default" => {}
+            None => {
+                return Err(MailError::Configuration(format!(
+                    "profile '{}' not found in missive config",
+                    profile
+                )));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Install this configuration for the global mailer.
+    ///
+    /// Replaces any previously installed configuration and drops the global
+    /// mailer and the named mailers built from configuration, so the next
+    /// `deliver()` picks up the new settings. Mailers added with
+    /// [`register()`](crate::register) are kept.
+    pub fn install(self) {
+        *CONFIG.write() = Some(Arc::new(self));
+        #[cfg(feature = "toml")]
+        {
+            *AUTOLOADED.write() = None;
+        }
+        crate::invalidate_configured();
+    }
+
+    /// Remove the installed configuration, falling back to environment variables.
+    pub fn uninstall() {
+        *CONFIG.write() = None;
+        #[cfg(feature = "toml")]
+        {
+            *AUTOLOADED.write() = None;
+        }
+        crate::invalidate_configured();
+    }
+}
+
+/// Email provider and its credentials, for [`ConfigBuilder::provider`].
+///
+/// The matching feature flag must still be enabled for the provider to be used.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Provider {
+    /// SMTP server (credentials are optional for local relays).
+    Smtp {
+        host: String,
+        port: u16,
+        username: Option<String>,
+        password: Option<String>,
+    },
+    /// Proton Mail Bridge running locally.
+    ProtonBridge { username: String, password: String },
+    /// Resend API.
+    Resend { api_key: String },
+    /// Unsent API.
+    Unsent { api_key: String },
+    /// Postmark API.
+    Postmark { api_key: String },
+    /// SendGrid API.
+    SendGrid { api_key: String },
+    /// Brevo API.
+    Brevo { api_key: String },
+    /// Mailgun API.
+    Mailgun { api_key: String, domain: String },
+    /// Amazon SES API.
+    AmazonSes {
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+    /// Mailtrap API.
+    Mailtrap { api_key: String },
+    /// ZeptoMail (Zoho) API.
+    ZeptoMail { token: String },
+    /// Customer.io Transactional API.
+    CustomerIo { api_key: String },
+    /// In-memory LocalMailer (development and tests).
+    Local,
+    /// LoggerMailer (brief output).
+    Logger,
+    /// LoggerMailer (full output).
+    LoggerFull,
+    /// LoggerMailer (JSON output).
+    LoggerJson,
+}
+
+impl Provider {
+    /// The `EMAIL_PROVIDER` name for this provider.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::Smtp { .. } => "smtp",
+            Provider::ProtonBridge { .. } => "protonbridge",
+            Provider::Resend { .. } => "resend",
+            Provider::Unsent { .. } => "unsent",
+            Provider::Postmark { .. } => "postmark",
+            Provider::SendGrid { .. } => "sendgrid",
+            Provider::Brevo { .. } => "brevo",
+            Provider::Mailgun { .. } => "mailgun",
+            Provider::AmazonSes { .. } => "amazon_ses",
+            Provider::Mailtrap { .. } => "mailtrap",
+            Provider::ZeptoMail { .. } => "zeptomail",
+            Provider::CustomerIo { .. } => "customerio",
+            Provider::Local => "local",
+            Provider::Logger => "logger",
+            Provider::LoggerFull => "logger_full",
+            Provider::LoggerJson => "logger_json",
+        }
+    }
+
+    /// The settings this provider contributes, keyed by env var name.
+    fn settings(self) -> Vec<(&'static str, String)> {
+        match self {
+            Provider::Smtp {
+                host,
+                port,
+                username,
+                password,
+            } => {
+                let mut settings = vec![("SMTP_HOST", host), ("SMTP_PORT", port.to_string())];
+                if let Some(username) = username {
+                    settings.push(("SMTP_USERNAME", username));
+                }
+                if let Some(password) = password {
+                    settings.push(("SMTP_PASSWORD", password));
+                }
+                settings
+            }
+            Provider::ProtonBridge { username, password } => vec![
+                ("PROTON_BRIDGE_USERNAME", username),
+                ("PROTON_BRIDGE_PASSWORD", password),
+            ],
+            Provider::Resend { api_key } => vec![("RESEND_API_KEY", api_key)],
+            Provider::Unsent { api_key } => vec![("UNSENT_API_KEY", api_key)],
+            Provider::Postmark { api_key } => vec![("POSTMARK_API_KEY", api_key)],
+            Provider::SendGrid { api_key } => vec![("SENDGRID_API_KEY", api_key)],
+            Provider::Brevo { api_key } => vec![("BREVO_API_KEY", api_key)],
+            Provider::Mailgun { api_key, domain } => {
+                vec![("MAILGUN_API_KEY", api_key), ("MAILGUN_DOMAIN", domain)]
+            }
+            Provider::AmazonSes {
+                region,
+                access_key_id,
+                secret_access_key,
+            } => vec![
+                ("AWS_REGION", region),
+                ("AWS_ACCESS_KEY_ID", access_key_id),
+                ("AWS_SECRET_ACCESS_KEY", secret_access_key),
+            ],
+            Provider::Mailtrap { api_key } => vec![("MAILTRAP_API_KEY", api_key)],
+            Provider::ZeptoMail { token } => vec![("ZEPTOMAIL_TOKEN", token)],
+            Provider::CustomerIo { api_key } => vec![("CUSTOMERIO_API_KEY", api_key)],
+            Provider::Local | Provider::Logger | Provider::LoggerFull | Provider::LoggerJson => {
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Builder for [`Config`].
+///
+/// Settings not given here still fall back to environment variables.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Set the provider and its credentials.
+    pub fn provider(mut self, provider: Provider) -> Self {
+        self.config = self.config.set("EMAIL_PROVIDER", provider.name());
+        for (key, value) in provider.settings() {
+            self.config = self.config.set(key, value);
+        }
+        self
+    }
+
+    /// Set the default sender (`EMAIL_FROM` / `EMAIL_FROM_NAME`).
+    pub fn from(mut self, addr: impl ToAddress) -> Self {
+        let addr = addr.to_address();
+        self.config = self.config.set("EMAIL_FROM", addr.email);
+        if let Some(name) = addr.name {
+            self.config = self.config.set("EMAIL_FROM_NAME", name);
+        }
+        self
+    }
+
+    /// Set the default reply-to address (`EMAIL_REPLY_TO`), used when an
+    /// email has none.
+    ///
+    /// Can be called multiple times to add more addresses.
+    pub fn reply_to(self, addr: impl ToAddress) -> Self {
+        self.append("EMAIL_REPLY_TO", addr.to_address().formatted())
+    }
+
+    /// Add a header to every email that doesn't set it (`EMAIL_HEADERS`).
+    pub fn header(self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        let header = format!("{}: {}", name.as_ref(), value.as_ref());
+        self.append("EMAIL_HEADERS", header)
+    }
+
+    /// BCC an archive address on every email (`EMAIL_ALWAYS_BCC`).
+    ///
+    /// Can be called multiple times to add more addresses.
+    pub fn always_bcc(self, addr: impl ToAddress) -> Self {
+        self.append("EMAIL_ALWAYS_BCC", addr.to_address().email)
+    }
+
+    /// Only send from addresses at `domain` (`EMAIL_ALLOWED_FROM_DOMAINS`).
+    ///
+    /// Can be called multiple times to allow more domains.
+    pub fn allowed_from_domain(self, domain: impl Into<String>) -> Self {
+        self.append("EMAIL_ALLOWED_FROM_DOMAINS", domain.into())
+    }
+
+    /// Reject or rewrite senders outside the allowed domains
+    /// (`EMAIL_FROM_DOMAIN_POLICY`, default [`FromDomainPolicy::Reject`]).
+    pub fn from_domain_policy(mut self, policy: FromDomainPolicy) -> Self {
+        let value = match policy {
+            FromDomainPolicy::Reject => "reject",
+            FromDomainPolicy::Rewrite => "rewrite",
+        };
+        self.config = self.config.set("EMAIL_FROM_DOMAIN_POLICY", value);
+        self
+    }
+
+    /// Strictly validate every delivery (`EMAIL_STRICT_VALIDATION`).
+    pub fn strict_validation(mut self, enabled: bool) -> Self {
+        self.config = self
+            .config
+            .set("EMAIL_STRICT_VALIDATION", enabled.to_string());
+        self
+    }
+
+    /// Reject emails larger than `bytes` before sending (`EMAIL_MAX_MESSAGE_SIZE`).
+    ///
+    /// Overrides the provider's own limit; `0` disables the check.
+    pub fn max_message_size(mut self, bytes: usize) -> Self {
+        self.config = self
+            .config
+            .set("EMAIL_MAX_MESSAGE_SIZE", bytes.to_string());
+        self
+    }
+
+    /// Truncate or reject subjects and header values over the mailer's
+    /// limits (`EMAIL_LIMIT_POLICY`, default [`LimitPolicy::Error`]).
+    pub fn limit_policy(mut self, policy: LimitPolicy) -> Self {
+        let value = match policy {
+            LimitPolicy::Error => "error",
+            LimitPolicy::Truncate => "truncate",
+        };
+        self.config = self.config.set("EMAIL_LIMIT_POLICY", value);
+        self
+    }
+
+    /// Limit outbound email to `per_second` (`EMAIL_RATE_LIMIT`).
+    pub fn rate_limit(mut self, per_second: u32) -> Self {
+        self.config = self.config.set("EMAIL_RATE_LIMIT", per_second.to_string());
+        self
+    }
+
+    /// Set any other setting by its environment variable name.
+    pub fn set(mut self, key: impl AsRef<str>, value: impl Into<String>) -> Self {
+        self.config = self.config.set(key, value);
+        self
+    }
+
+    /// Add an item to a comma-separated setting.
+    fn append(mut self, key: &str, item: String) -> Self {
+        let list = match self.config.get(key) {
+            Some(existing) => format!("{},{}", existing, item),
+            None => item,
+        };
+        self.config = self.config.set(key, list);
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+/// Look up a setting, checking the installed config before the environment.
+pub(crate) fn var(key: &str) -> Option<String> {
+    #[cfg(feature = "toml")]
+    autoload();
+
+    let value = match CONFIG.read().as_ref().and_then(|c| c.get(key)) {
+        Some(value) => value.to_string(),
+        None => env::var(key).ok()?,
+    };
+    #[cfg(any(feature = "secrets-aws", feature = "secrets-vault"))]
+    let value = crate::secrets::substitute(key, value)?;
+    Some(value)
+}
+
+/// Every setting with a raw value: the installed config, then environment
+/// variables with a known prefix that it doesn't override.
+#[cfg(any(feature = "secrets-aws", feature = "secrets-vault"))]
+pub(crate) fn settings() -> Vec<(String, String)> {
+    #[cfg(feature = "toml")]
+    autoload();
+
+    let mut settings: Vec<(String, String)> = CONFIG
+        .read()
+        .as_ref()
+        .map(|c| c.values.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    for (key, value) in env_settings() {
+        if !settings.iter().any(|(k, _)| *k == key) {
+            settings.push((key, value));
+        }
+    }
+    settings
+}
+
+/// Environment variables with a known prefix, sorted by name.
+fn env_settings() -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = env::vars_os()
+        .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?)))
+        .filter(|(k, _)| QUALIFIED_PREFIXES.iter().any(|p| k.starts_with(p)))
+        .collect();
+    vars.sort();
+    vars
+}
+
+/// Look up a setting for a named mailer.
+///
+/// Checks `<KEY>__<NAME>` first, then falls back to `<KEY>`. The provider
+/// itself (`EMAIL_PROVIDER`) never falls back, so unknown names are not
+/// silently routed to the default mailer.
+pub(crate) fn scoped_var(key: &str, name: Option<&str>) -> Option<String> {
+    let Some(name) = name else {
+        return var(key);
+    };
+    if let Some(value) = var(&scoped_key(key, Some(name))) {
+        return Some(value);
+    }
+    if key == "EMAIL_PROVIDER" {
+        return None;
+    }
+    var(key)
+}
+
+/// Build the env var name for a setting of a named mailer (`<KEY>__<NAME>`).
+pub(crate) fn scoped_key(key: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{}__{}", key, name.to_uppercase().replace('-', "_")),
+        None => key.to_string(),
+    }
+}
+
+/// Load `missive.toml` once if present and no config was installed.
+#[cfg(feature = "toml")]
+fn autoload() {
+    static AUTOLOAD: std::sync::Once = std::sync::Once::new();
+
+    AUTOLOAD.call_once(|| {
+        if CONFIG.read().is_some() {
+            return;
+        }
+        let path = env::var("MISSIVE_CONFIG").unwrap_or_else(|_| "missive.toml".to_string());
+        if !Path::new(&path).exists() {
+            return;
+        }
+        let profile = env::var("MISSIVE_PROFILE").unwrap_or_else(|_| "default".to_string());
+        match Config::from_file(&path, &profile) {
+            Ok(config) => {
+                tracing::debug!(path = %path, profile = %profile, "Loaded missive config");
+                *CONFIG.write() = Some(Arc::new(config));
+                *AUTOLOADED.write() = Some((path, profile));
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to load missive config"),
+        }
+    });
+}
+
+/// Re-read the autoloaded `missive.toml`, if there is one.
+///
+/// Returns the configuration it replaced, so a failed reload can put it back
+/// with [`restore`].
+pub(crate) fn reload_file() -> Result<Option<Arc<Config>>, crate::error::MailError> {
+    #[cfg(feature = "toml")]
+    if let Some((path, profile)) = AUTOLOADED.read().clone() {
+        let config = Config::from_file(&path, &profile)?;
+        return Ok(CONFIG.write().replace(Arc::new(config)));
+    }
+    Ok(None)
+}
+
+/// Put back a configuration replaced by [`reload_file`].
+pub(crate) fn restore(config: Arc<Config>) {
+    *CONFIG.write() = Some(config);
+}
+
+/// A hash of every setting missive reads from outside the process: the
+/// environment variables with a known prefix and the autoloaded file.
+pub(crate) fn fingerprint() -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    env_settings().hash(&mut hasher);
+    #[cfg(feature = "toml")]
+    if let Some((path, _)) = AUTOLOADED.read().as_ref() {
+        std::fs::read(path).ok().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Uppercase a key and add the `EMAIL_` prefix to short global names.
+fn normalize_key(key: &str) -> String {
+    let key = key.trim().to_uppercase();
+    if QUALIFIED_PREFIXES.iter().any(|p| key.starts_with(p)) {
+        key
+    } else {
+        format!("EMAIL_{}", key)
+    }
+}
+
+/// Flatten a TOML table into env-style keys.
+#[cfg(feature = "toml")]
+fn flatten_table(values: &mut HashMap<String, String>, prefix: &str, table: &toml::Table) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}_{}", prefix, key)
+        };
+
+        let value = match value {
+            toml::Value::Table(nested) => {
+                flatten_table(values, &key, nested);
+                continue;
+            }
+            toml::Value::String(s) => s.clone(),
+            toml::Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    toml::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            other => other.to_string(),
+        };
+
+        values.insert(normalize_key(&key), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_key() {
+        assert_eq!(normalize_key("provider"), "EMAIL_PROVIDER");
+        assert_eq!(normalize_key("from_name"), "EMAIL_FROM_NAME");
+        assert_eq!(normalize_key("resend_api_key"), "RESEND_API_KEY");
+        assert_eq!(
+            normalize_key("proton_bridge_password"),
+            "PROTON_BRIDGE_PASSWORD"
+        );
+        assert_eq!(normalize_key("EMAIL_FROM"), "EMAIL_FROM");
+    }
+
+    #[test]
+    fn test_scoped_key() {
+        assert_eq!(
+            scoped_key("EMAIL_PROVIDER", Some("bulk-mail")),
+            "EMAIL_PROVIDER__BULK_MAIL"
+        );
+        assert_eq!(scoped_key("EMAIL_PROVIDER", None), "EMAIL_PROVIDER");
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let config = Config::new().set("provider", "resend");
+        assert_eq!(config.get("EMAIL_PROVIDER"), Some("resend"));
+        assert_eq!(config.get("provider"), Some("resend"));
+        assert_eq!(config.get("EMAIL_FROM"), None);
+    }
+
+    #[test]
+    fn test_builder() {
+        let config = Config::builder()
+            .provider(Provider::Mailgun {
+                api_key: "key".into(),
+                domain: "mg.example.com".into(),
+            })
+            .from(("My App", "noreply@example.com"))
+            .rate_limit(5)
+            .always_bcc("archive@example.com")
+            .always_bcc("legal@example.com")
+            .reply_to(("Support", "support@example.com"))
+            .header("X-Environment", "staging")
+            .strict_validation(true)
+            .max_message_size(5_000_000)
+            .limit_policy(LimitPolicy::Truncate)
+            .build();
+
+        assert_eq!(config.get("EMAIL_PROVIDER"), Some("mailgun"));
+        assert_eq!(config.get("MAILGUN_API_KEY"), Some("key"));
+        assert_eq!(config.get("MAILGUN_DOMAIN"), Some("mg.example.com"));
+        assert_eq!(config.get("EMAIL_FROM"), Some("noreply@example.com"));
+        assert_eq!(config.get("EMAIL_FROM_NAME"), Some("My App"));
+        assert_eq!(config.get("EMAIL_RATE_LIMIT"), Some("5"));
+        assert_eq!(
+            config.get("EMAIL_ALWAYS_BCC"),
+            Some("archive@example.com,legal@example.com")
+        );
+        assert_eq!(config.get("EMAIL_REPLY_TO"), Some("Support <support@example.com>"));
+        assert_eq!(config.get("EMAIL_HEADERS"), Some("X-Environment: staging"));
+        assert_eq!(config.get("EMAIL_STRICT_VALIDATION"), Some("true"));
+        assert_eq!(config.get("EMAIL_MAX_MESSAGE_SIZE"), Some("5000000"));
+        assert_eq!(config.get("EMAIL_LIMIT_POLICY"), Some("truncate"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_profile_overrides_default() {
+        let toml = r#"
+            [default]
+            from = "noreply@example.com"
+            provider = "logger"
+
+            [staging]
+            provider = "resend"
+            rate_limit = 5
+
+            [staging.sandbox]
+            allowed_domains = ["example.com", "test.com"]
+        "#;
+
+        let config = Config::from_toml_str(toml, "staging").unwrap();
+        assert_eq!(config.get("EMAIL_PROVIDER"), Some("resend"));
+        assert_eq!(config.get("EMAIL_FROM"), Some("noreply@example.com"));
+        assert_eq!(config.get("EMAIL_RATE_LIMIT"), Some("5"));
+        assert_eq!(
+            config.get("EMAIL_SANDBOX_ALLOWED_DOMAINS"),
+            Some("example.com,test.com")
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_missing_profile() {
+        let result = Config::from_toml_str("[production]\nprovider = \"smtp\"", "staging");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,584 @@
+//! Configuration diagnostics.
+//!
+//! [`diagnose`] checks the whole environment at once, rather than stopping
+//! at the first error like [`init`](crate::init):
+//!
+//! ```rust,ignore
+//! let report = missive::diagnose();
+//! if !report.is_ok() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
+//!
+//! The report names the provider that would be used and how it was chosen,
+//! which of its variables are set (never their values), the compiled-in
+//! features, and every problem found: missing or invalid settings, providers
+//! whose feature is not enabled, and credentials for several providers.
+
+use std::fmt;
+
+use crate::address::Address;
+use crate::config;
+use crate::detect;
+
+/// A provider's feature flag and variables.
+struct ProviderSpec {
+    names: &'static [&'static str],
+    feature: Option<&'static str>,
+    compiled: bool,
+    required: &'static [&'static str],
+    optional: &'static [&'static str],
+}
+
+const PROVIDERS: &[ProviderSpec] = &[
+    ProviderSpec {
+        names: &["smtp"],
+        feature: Some("smtp"),
+        compiled: cfg!(feature = "smtp"),
+        required: &["SMTP_HOST"],
+        optional: &[
+            "SMTP_PORT",
+            "SMTP_USERNAME",
+            "SMTP_PASSWORD",
+            "SMTP_TLS",
+            "SMTP_TIMEOUT",
+            "SMTP_POOL_SIZE",
+        ],
+    },
+    ProviderSpec {
+        names: &["protonbridge"],
+        feature: Some("smtp"),
+        compiled: cfg!(feature = "smtp"),
+        required: &["PROTON_BRIDGE_USERNAME", "PROTON_BRIDGE_PASSWORD"],
+        optional: &[
+            "PROTON_BRIDGE_HOST",
+            "PROTON_BRIDGE_PORT",
+            "PROTON_BRIDGE_SSL",
+        ],
+    },
+    ProviderSpec {
+        names: &["resend"],
+        feature: Some("resend"),
+        compiled: cfg!(feature = "resend"),
+        required: &["RESEND_API_KEY"],
+        optional: &[],
+    },
+    ProviderSpec {
+        names: &["unsent"],
+        feature: Some("unsent"),
+        compiled: cfg!(feature = "unsent"),
+        required: &["UNSENT_API_KEY"],
+        optional: &[],
+    },
+    ProviderSpec {
+        names: &["postmark"],
+        feature: Some("postmark"),
+        compiled: cfg!(feature = "postmark"),
+        required: &["POSTMARK_API_KEY"],
+        optional: &["POSTMARK_MESSAGE_STREAM"],
+    },
+    ProviderSpec {
+        names: &["sendgrid"],
+        feature: Some("sendgrid"),
+        compiled: cfg!(feature = "sendgrid"),
+        required: &["SENDGRID_API_KEY"],
+        optional: &["SENDGRID_REGION", "SENDGRID_ON_BEHALF_OF"],
+    },
+    ProviderSpec {
+        names: &["brevo"],
+        feature: Some("brevo"),
+        compiled: cfg!(feature = "brevo"),
+        required: &["BREVO_API_KEY"],
+        optional: &[],
+    },
+    ProviderSpec {
+        names: &["mailgun"],
+        feature: Some("mailgun"),
+        compiled: cfg!(feature = "mailgun"),
+        required: &["MAILGUN_API_KEY", "MAILGUN_DOMAIN"],
+        optional: &["MAILGUN_BASE_URL"],
+    },
+    ProviderSpec {
+        names: &["amazon_ses"],
+        feature: Some("amazon_ses"),
+        compiled: cfg!(feature = "amazon_ses"),
+        required: &["AWS_REGION", "AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"],
+        optional: &[],
+    },
+    ProviderSpec {
+        names: &["mailtrap"],
+        feature: Some("mailtrap"),
+        compiled: cfg!(feature = "mailtrap"),
+        required: &["MAILTRAP_API_KEY"],
+        optional: &["MAILTRAP_SANDBOX_INBOX_ID", "MAILTRAP_BULK"],
+    },
+    ProviderSpec {
+        names: &["zeptomail"],
+        feature: Some("zeptomail"),
+        compiled: cfg!(feature = "zeptomail"),
+        required: &["ZEPTOMAIL_TOKEN"],
+        optional: &["ZEPTOMAIL_BASE_URL"],
+    },
+    ProviderSpec {
+        names: &["customerio"],
+        feature: Some("customerio"),
+        compiled: cfg!(feature = "customerio"),
+        required: &["CUSTOMERIO_API_KEY"],
+        optional: &["CUSTOMERIO_REGION"],
+    },
+    ProviderSpec {
+        names: &["local"],
+        feature: Some("local"),
+        compiled: cfg!(feature = "local"),
+        required: &[],
+        optional: &[
+            "MISSIVE_LOCAL_MAX_EMAILS",
+            "MISSIVE_LOCAL_MAX_BYTES",
+            "MISSIVE_LOCAL_TTL",
+        ],
+    },
+    ProviderSpec {
+        names: &["logger", "logger_full", "logger_json"],
+        feature: None,
+        compiled: true,
+        required: &[],
+        optional: &["LOGGER_REDACT", "LOGGER_FILE"],
+    },
+];
+
+/// Settings checked whatever the provider.
+const GENERAL: &[&str] = &[
+    "EMAIL_PROVIDER",
+    "EMAIL_PROVIDER_PRIORITY",
+    "EMAIL_FROM",
+    "EMAIL_FROM_NAME",
+    "EMAIL_REPLY_TO",
+];
+
+/// Every user-facing feature, in `Cargo.toml` order.
+const FEATURES: &[(&str, bool)] = &[
+    ("smtp", cfg!(feature = "smtp")),
+    ("resend", cfg!(feature = "resend")),
+    ("unsent", cfg!(feature = "unsent")),
+    ("postmark", cfg!(feature = "postmark")),
+    ("sendgrid", cfg!(feature = "sendgrid")),
+    ("brevo", cfg!(feature = "brevo")),
+    ("mailgun", cfg!(feature = "mailgun")),
+    ("amazon_ses", cfg!(feature = "amazon_ses")),
+    ("mailtrap", cfg!(feature = "mailtrap")),
+    ("mailjet", cfg!(feature = "mailjet")),
+    ("zeptomail", cfg!(feature = "zeptomail")),
+    ("customerio", cfg!(feature = "customerio")),
+    ("local", cfg!(feature = "local")),
+    ("snapshots", cfg!(feature = "snapshots")),
+    ("preview", cfg!(feature = "preview")),
+    ("preview-axum", cfg!(feature = "preview-axum")),
+    ("preview-actix", cfg!(feature = "preview-actix")),
+    ("preview-rocket", cfg!(feature = "preview-rocket")),
+    ("smtp-capture", cfg!(feature = "smtp-capture")),
+    ("storage-encryption", cfg!(feature = "storage-encryption")),
+    ("zip", cfg!(feature = "zip")),
+    ("attachment-s3", cfg!(feature = "attachment-s3")),
+    ("metrics", cfg!(feature = "metrics")),
+    ("opentelemetry", cfg!(feature = "opentelemetry")),
+    ("audit", cfg!(feature = "audit")),
+    ("tracking", cfg!(feature = "tracking")),
+    ("socks", cfg!(feature = "socks")),
+    ("templates", cfg!(feature = "templates")),
+    ("remote-templates", cfg!(feature = "remote-templates")),
+    ("derive", cfg!(feature = "derive")),
+    ("dns", cfg!(feature = "dns")),
+    ("verify", cfg!(feature = "verify")),
+    ("smtp-probe", cfg!(feature = "smtp-probe")),
+    ("insights", cfg!(feature = "insights")),
+    ("toml", cfg!(feature = "toml")),
+    ("secrets-aws", cfg!(feature = "secrets-aws")),
+    ("secrets-vault", cfg!(feature = "secrets-vault")),
+    ("wasm", cfg!(feature = "wasm")),
+    ("cli", cfg!(feature = "cli")),
+    ("blocking", cfg!(feature = "blocking")),
+    ("axum", cfg!(feature = "axum")),
+    ("actix", cfg!(feature = "actix")),
+    ("apalis", cfg!(feature = "apalis")),
+    ("fang", cfg!(feature = "fang")),
+    ("sqlxmq", cfg!(feature = "sqlxmq")),
+    ("redis", cfg!(feature = "redis")),
+    ("schemars", cfg!(feature = "schemars")),
+];
+
+/// How the provider was chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// Named by `EMAIL_PROVIDER`.
+    Explicit,
+    /// First configured provider in `EMAIL_PROVIDER_PRIORITY`.
+    Priority,
+    /// Auto-detected from the credentials present.
+    Detected,
+    /// Nothing configured; the `local` provider is the fallback.
+    Fallback,
+    /// No provider could be chosen.
+    None,
+}
+
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selection::Explicit => write!(f, "EMAIL_PROVIDER"),
+            Selection::Priority => write!(f, "EMAIL_PROVIDER_PRIORITY"),
+            Selection::Detected => write!(f, "auto-detected"),
+            Selection::Fallback => write!(f, "fallback"),
+            Selection::None => write!(f, "none"),
+        }
+    }
+}
+
+/// A setting and whether it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    /// Variable name.
+    pub name: &'static str,
+    /// Whether it is set (in the environment or the installed config).
+    pub set: bool,
+    /// Whether the selected provider needs it.
+    pub required: bool,
+}
+
+/// How serious a [`Problem`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Works, but probably not as intended.
+    Warning,
+    /// Delivery will fail until it is fixed.
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Warning => write!(f, "warning"),
+            Level::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// One problem found in the configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// How serious it is.
+    pub level: Level,
+    /// What is wrong and how to fix it.
+    pub message: String,
+}
+
+impl Problem {
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            level: Level::Error,
+            message: message.into(),
+        }
+    }
+
+    fn warning(message: impl Into<String>) -> Self {
+        Self {
+            level: Level::Warning,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.level, self.message)
+    }
+}
+
+/// The result of [`diagnose`].
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    /// The provider that would be used, if one can be chosen.
+    pub provider: Option<String>,
+    /// How it was chosen.
+    pub selection: Selection,
+    /// General settings, then the selected provider's.
+    pub variables: Vec<Variable>,
+    /// Features compiled in.
+    pub features: Vec<&'static str>,
+    /// Everything found wrong, errors first.
+    pub problems: Vec<Problem>,
+}
+
+impl Diagnostics {
+    /// `true` if there are no errors (warnings are allowed).
+    pub fn is_ok(&self) -> bool {
+        !self.problems.iter().any(|p| p.level == Level::Error)
+    }
+
+    /// Required variables that are not set.
+    pub fn missing(&self) -> Vec<&'static str> {
+        self.variables
+            .iter()
+            .filter(|v| v.required && !v.set)
+            .map(|v| v.name)
+            .collect()
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.provider {
+            Some(provider) => writeln!(f, "provider: {} ({})", provider, self.selection)?,
+            None => writeln!(f, "provider: none")?,
+        }
+        writeln!(f, "features: {}", self.features.join(", "))?;
+        writeln!(f, "variables:")?;
+        let width = self
+            .variables
+            .iter()
+            .map(|v| v.name.len())
+            .max()
+            .unwrap_or(0);
+        for var in &self.variables {
+            let state = match (var.set, var.required) {
+                (true, _) => "set",
+                (false, true) => "MISSING",
+                (false, false) => "unset",
+            };
+            writeln!(f, "  {:<width$}  {}", var.name, state, width = width)?;
+        }
+        if self.problems.is_empty() {
+            writeln!(f, "no problems found")
+        } else {
+            writeln!(f, "problems:")?;
+            for problem in &self.problems {
+                writeln!(f, "  - {}", problem)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check the global configuration and report every problem at once.
+///
+/// Looks at the same settings as [`init`](crate::init): the environment
+/// and, with the `toml` feature, the installed [`Config`](crate::Config).
+/// Nothing is sent and no provider is contacted.
+pub fn diagnose() -> Diagnostics {
+    diagnose_with(config::var)
+}
+
+/// [`diagnose`], reading settings with `var`.
+pub(crate) fn diagnose_with(var: impl Fn(&str) -> Option<String>) -> Diagnostics {
+    let mut problems = Vec::new();
+    let mut variables: Vec<Variable> = GENERAL
+        .iter()
+        .map(|name| Variable {
+            name,
+            set: var(name).is_some(),
+            required: false,
+        })
+        .collect();
+
+    let detection = detect::detect(&var);
+    for (key, looks_like) in &detection.mismatched {
+        problems.push(Problem::warning(format!(
+            "{} looks like a {} key; check that it is in the right variable",
+            key, looks_like
+        )));
+    }
+
+    let (provider, selection) = match var("EMAIL_PROVIDER") {
+        Some(p) => (Some(p.trim().to_lowercase()), Selection::Explicit),
+        None => match detection.provider {
+            Some(p) if detection.from_priority => (Some(p.to_string()), Selection::Priority),
+            Some(p) if detection.candidates.is_empty() => {
+                (Some(p.to_string()), Selection::Fallback)
+            }
+            Some(p) => (Some(p.to_string()), Selection::Detected),
+            None => {
+                problems.push(Problem::error(
+                    "EMAIL_PROVIDER is not set and no provider credentials were found",
+                ));
+                (None, Selection::None)
+            }
+        },
+    };
+
+    if let Some(provider) = &provider {
+        let others: Vec<&str> = detection
+            .candidates
+            .iter()
+            .copied()
+            .filter(|c| c != provider)
+            .collect();
+        match selection {
+            Selection::Explicit if !others.is_empty() => problems.push(Problem::warning(format!(
+                "EMAIL_PROVIDER={} but credentials for {} are also set",
+                provider,
+                others.join(", ")
+            ))),
+            Selection::Detected if !others.is_empty() => problems.push(Problem::warning(format!(
+                "Several email providers are configured ({}); using {}. \
+                    Set EMAIL_PROVIDER, or EMAIL_PROVIDER_PRIORITY to rank them",
+                detection.candidates.join(", "),
+                provider
+            ))),
+            _ => {}
+        }
+
+        match PROVIDERS
+            .iter()
+            .find(|s| s.names.contains(&provider.as_str()))
+        {
+            None => problems.push(Problem::error(format!(
+                "Unknown EMAIL_PROVIDER: {}",
+                provider
+            ))),
+            Some(spec) => {
+                for (names, required) in [(spec.required, true), (spec.optional, false)] {
+                    variables.extend(names.iter().map(|name| Variable {
+                        name,
+                        set: var(name).is_some(),
+                        required,
+                    }));
+                }
+                if !spec.compiled {
+                    problems.push(Problem::error(format!(
+                        "EMAIL_PROVIDER={} but the '{}' feature is not enabled",
+                        provider,
+                        spec.feature.unwrap_or_default()
+                    )));
+                }
+                for var in variables.iter().filter(|v| v.required && !v.set) {
+                    problems.push(Problem::error(format!("{} is not set", var.name)));
+                }
+                // Building catches invalid values. Local and logger mailers are
+                // skipped: they touch shared storage and log files.
+                let buildable = spec.compiled
+                    && !spec.required.is_empty()
+                    && spec.required.iter().all(|name| var(name).is_some());
+                if buildable {
+                    if let Err(e) = crate::create_mailer_with(None, &var) {
+                        problems.push(Problem::error(e.to_string()));
+                    }
+                }
+            }
+        }
+    }
+
+    match var("EMAIL_FROM") {
+        None => problems.push(Problem::warning(
+            "EMAIL_FROM is not set; every email needs its own from address",
+        )),
+        Some(from) if Address::parse(from.trim()).is_err() => problems.push(Problem::error(
+            format!("EMAIL_FROM is not a valid email address: {}", from),
+        )),
+        Some(_) => {}
+    }
+
+    problems.sort_by_key(|p| std::cmp::Reverse(p.level));
+    Diagnostics {
+        provider,
+        selection,
+        variables,
+        features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect(),
+        problems,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn diagnose_vars(pairs: &[(&str, &str)]) -> Diagnostics {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        diagnose_with(move |key| map.get(key).cloned())
+    }
+
+    #[test]
+    fn test_reports_every_missing_variable() {
+        let report = diagnose_vars(&[("EMAIL_PROVIDER", "amazon_ses")]);
+        assert_eq!(report.provider.as_deref(), Some("amazon_ses"));
+        assert_eq!(report.selection, Selection::Explicit);
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.missing(),
+            ["AWS_REGION", "AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"]
+        );
+        assert_eq!(report.problems[0].level, Level::Error);
+        assert_eq!(report.problems.last().unwrap().level, Level::Warning);
+
+        let rendered = report.to_string();
+        assert!(rendered.contains("provider: amazon_ses (EMAIL_PROVIDER)"));
+        assert!(rendered
+            .lines()
+            .any(|line| line.starts_with("  AWS_REGION ") && line.ends_with(" MISSING")));
+        assert!(rendered.contains("error: AWS_ACCESS_KEY_ID is not set"));
+    }
+
+    #[test]
+    fn test_unknown_provider() {
+        let report = diagnose_vars(&[("EMAIL_PROVIDER", "mailchimp")]);
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.problems[0].message,
+            "Unknown EMAIL_PROVIDER: mailchimp"
+        );
+    }
+
+    #[test]
+    fn test_logger_is_ok() {
+        let report = diagnose_vars(&[
+            ("EMAIL_PROVIDER", "logger_json"),
+            ("EMAIL_FROM", "noreply@example.com"),
+        ]);
+        assert!(report.is_ok(), "{}", report);
+        assert!(report.problems.is_empty());
+        assert!(report.to_string().contains("no problems found"));
+    }
+
+    #[cfg(feature = "smtp")]
+    #[test]
+    fn test_invalid_smtp_settings() {
+        let report = diagnose_vars(&[
+            ("EMAIL_PROVIDER", "smtp"),
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_PORT", "abc"),
+            ("SMTP_TLS", "maybe"),
+        ]);
+        assert!(report.missing().is_empty());
+        let error = &report.problems[0].message;
+        assert!(
+            error.contains("SMTP_PORT") && error.contains("SMTP_TLS"),
+            "{}",
+            error
+        );
+    }
+
+    #[cfg(all(feature = "resend", feature = "sendgrid"))]
+    #[test]
+    fn test_conflicting_credentials() {
+        let report = diagnose_vars(&[
+            ("EMAIL_PROVIDER", "resend"),
+            ("RESEND_API_KEY", "SG.x"),
+            ("SENDGRID_API_KEY", "SG.y"),
+            ("EMAIL_FROM", "noreply@example.com"),
+        ]);
+        assert!(report.is_ok());
+        let messages: Vec<&str> = report.problems.iter().map(|p| p.message.as_str()).collect();
+        assert!(messages.contains(
+            &"RESEND_API_KEY looks like a sendgrid key; check that it is in the right variable"
+        ));
+        assert!(
+            messages.contains(&"EMAIL_PROVIDER=resend but credentials for sendgrid are also set")
+        );
+    }
+}
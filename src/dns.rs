@@ -0,0 +1,823 @@
+//! Deliverability DNS checks (SPF, DKIM, DMARC).
+//!
+//! Verify that a sending domain is set up correctly before campaigns go out:
+//!
+//! ```rust,ignore
+//! let report = missive::dns::check_domain("example.com").await?;
+//!
+//! if !report.is_ok() {
+//!     for hint in report.hints() {
+//!         tracing::warn!("{}", hint);
+//!     }
+//! }
+//! ```
+//!
+//! `check_domain` probes a list of common DKIM selectors. If you know the
+//! selectors your provider uses, pass them explicitly:
+//!
+//! ```rust,ignore
+//! use missive::dns::check_domain_with_selectors;
+//!
+//! let report = check_domain_with_selectors("example.com", &["resend", "s1"]).await?;
+//! println!("{}", report);
+//! ```
+//!
+//! For a startup health check, [`check_from_domain`] checks the domain of the
+//! configured default sender (`EMAIL_FROM`):
+//!
+//! ```rust,ignore
+//! let report = missive::dns::check_from_domain().await?;
+//! if !report.is_ok() {
+//!     tracing::error!("Email deliverability misconfigured:\n{}", report);
+//! }
+//! ```
+//!
+//! Reports also cover DMARC alignment modes and, if one is published, the
+//! domain's BIMI record.
+
+use std::fmt;
+
+use hickory_resolver::error::ResolveErrorKind;
+use hickory_resolver::TokioAsyncResolver;
+
+use crate::error::MailError;
+
+/// DKIM selectors probed by [`check_domain`].
+pub const COMMON_DKIM_SELECTORS: &[&str] = &[
+    "default",
+    "google",
+    "selector1",
+    "selector2",
+    "s1",
+    "s2",
+    "k1",
+    "k2",
+    "mail",
+    "dkim",
+    "resend",
+    "pm",
+    "mailjet",
+    "smtp",
+    "mg",
+];
+
+/// SPF allows at most 10 DNS-querying mechanisms.
+const SPF_MAX_LOOKUPS: usize = 10;
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    /// Configured correctly.
+    Pass,
+    /// Works, but could be improved.
+    Warn,
+    /// Missing or broken.
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "pass"),
+            CheckStatus::Warn => write!(f, "warn"),
+            CheckStatus::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// Parsed SPF record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpfRecord {
+    /// The raw TXT record.
+    pub raw: String,
+    /// All mechanisms and modifiers after `v=spf1`.
+    pub mechanisms: Vec<String>,
+    /// Domains referenced by `include:`.
+    pub includes: Vec<String>,
+    /// The `all` mechanism qualifier (`+`, `-`, `~`, `?`), if present.
+    pub all: Option<char>,
+    /// Number of mechanisms that require a DNS lookup.
+    pub lookup_count: usize,
+}
+
+impl SpfRecord {
+    /// Parse an SPF record. Returns `None` if it isn't `v=spf1`.
+    pub fn parse(record: &str) -> Option<Self> {
+        let mut terms = record.split_whitespace();
+        if !terms.next()?.eq_ignore_ascii_case("v=spf1") {
+            return None;
+        }
+
+        let mut spf = SpfRecord {
+            raw: record.to_string(),
+            mechanisms: Vec::new(),
+            includes: Vec::new(),
+            all: None,
+            lookup_count: 0,
+        };
+
+        for term in terms {
+            spf.mechanisms.push(term.to_string());
+
+            let (qualifier, mechanism) = match term.chars().next() {
+                Some(q @ ('+' | '-' | '~' | '?')) => (q, &term[1..]),
+                _ => ('+', term),
+            };
+            let name = mechanism
+                .split([':', '/', '='])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+
+            match name.as_str() {
+                "all" => spf.all = Some(qualifier),
+                "include" => {
+                    spf.lookup_count += 1;
+                    if let Some((_, domain)) = mechanism.split_once(':') {
+                        spf.includes.push(domain.to_string());
+                    }
+                }
+                "a" | "mx" | "ptr" | "exists" | "redirect" => spf.lookup_count += 1,
+                _ => {}
+            }
+        }
+
+        Some(spf)
+    }
+}
+
+/// SPF check result.
+#[derive(Debug, Clone)]
+pub struct SpfResult {
+    /// Overall status.
+    pub status: CheckStatus,
+    /// The parsed record, if exactly one was found.
+    pub record: Option<SpfRecord>,
+    /// Remediation hints.
+    pub hints: Vec<String>,
+}
+
+/// DKIM result for a single selector.
+#[derive(Debug, Clone)]
+pub struct DkimSelector {
+    /// The selector name.
+    pub selector: String,
+    /// The raw TXT record.
+    pub record: String,
+    /// Key type (`k=` tag, defaults to `rsa`).
+    pub key_type: String,
+    /// Whether the key was revoked (empty `p=` tag).
+    pub revoked: bool,
+}
+
+/// DKIM check result.
+#[derive(Debug, Clone)]
+pub struct DkimResult {
+    /// Overall status.
+    pub status: CheckStatus,
+    /// Selectors that have a published key.
+    pub selectors: Vec<DkimSelector>,
+    /// Remediation hints.
+    pub hints: Vec<String>,
+}
+
+/// Parsed DMARC record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DmarcRecord {
+    /// The raw TXT record.
+    pub raw: String,
+    /// Policy (`p=`): `none`, `quarantine` or `reject`.
+    pub policy: String,
+    /// Subdomain policy (`sp=`), if set.
+    pub subdomain_policy: Option<String>,
+    /// Percentage of mail the policy applies to (`pct=`, default 100).
+    pub pct: u8,
+    /// Aggregate report addresses (`rua=`).
+    pub rua: Vec<String>,
+    /// Forensic report addresses (`ruf=`).
+    pub ruf: Vec<String>,
+    /// DKIM alignment mode (`adkim=`): `r` (relaxed, default) or `s` (strict).
+    pub adkim: char,
+    /// SPF alignment mode (`aspf=`): `r` (relaxed, default) or `s` (strict).
+    pub aspf: char,
+}
+
+impl DmarcRecord {
+    /// Parse a DMARC record. Returns `None` if it isn't `v=DMARC1` or has no policy.
+    pub fn parse(record: &str) -> Option<Self> {
+        let mut tags = record
+            .split(';')
+            .filter_map(|tag| tag.split_once('='))
+            .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()));
+
+        let (version, value) = tags.next()?;
+        if version != "v" || !value.eq_ignore_ascii_case("DMARC1") {
+            return None;
+        }
+
+        let mut policy = None;
+        let mut dmarc = DmarcRecord {
+            raw: record.to_string(),
+            policy: String::new(),
+            subdomain_policy: None,
+            pct: 100,
+            rua: Vec::new(),
+            ruf: Vec::new(),
+            adkim: 'r',
+            aspf: 'r',
+        };
+
+        for (key, value) in tags {
+            match key.as_str() {
+                "p" => policy = Some(value.to_lowercase()),
+                "sp" => dmarc.subdomain_policy = Some(value.to_lowercase()),
+                "pct" => dmarc.pct = value.parse().unwrap_or(100),
+                "rua" => dmarc.rua = split_uris(&value),
+                "ruf" => dmarc.ruf = split_uris(&value),
+                "adkim" => dmarc.adkim = alignment(&value),
+                "aspf" => dmarc.aspf = alignment(&value),
+                _ => {}
+            }
+        }
+
+        dmarc.policy = policy?;
+        Some(dmarc)
+    }
+}
+
+impl DmarcRecord {
+    /// Whether the policy quarantines or rejects all failing mail.
+    pub fn is_enforced(&self) -> bool {
+        matches!(self.policy.as_str(), "quarantine" | "reject") && self.pct == 100
+    }
+}
+
+fn alignment(value: &str) -> char {
+    if value.eq_ignore_ascii_case("s") {
+        's'
+    } else {
+        'r'
+    }
+}
+
+fn split_uris(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// DMARC check result.
+#[derive(Debug, Clone)]
+pub struct DmarcResult {
+    /// Overall status.
+    pub status: CheckStatus,
+    /// The parsed record, if found.
+    pub record: Option<DmarcRecord>,
+    /// Remediation hints.
+    pub hints: Vec<String>,
+}
+
+/// Parsed BIMI record (`default._bimi.<domain>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BimiRecord {
+    /// The raw TXT record.
+    pub raw: String,
+    /// Logo URL (`l=`), an SVG Tiny PS file.
+    pub logo: Option<String>,
+    /// Verified Mark Certificate URL (`a=`).
+    pub authority: Option<String>,
+}
+
+impl BimiRecord {
+    /// Parse a BIMI record. Returns `None` if it isn't `v=BIMI1`.
+    pub fn parse(record: &str) -> Option<Self> {
+        let mut tags = record
+            .split(';')
+            .filter_map(|tag| tag.split_once('='))
+            .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()));
+
+        let (version, value) = tags.next()?;
+        if version != "v" || !value.eq_ignore_ascii_case("BIMI1") {
+            return None;
+        }
+
+        let mut bimi = BimiRecord {
+            raw: record.to_string(),
+            logo: None,
+            authority: None,
+        };
+        for (key, value) in tags {
+            let value = (!value.is_empty()).then_some(value);
+            match key.as_str() {
+                "l" => bimi.logo = value,
+                "a" => bimi.authority = value,
+                _ => {}
+            }
+        }
+        Some(bimi)
+    }
+}
+
+/// BIMI check result.
+///
+/// BIMI is optional: a domain without a record passes.
+#[derive(Debug, Clone)]
+pub struct BimiResult {
+    /// Overall status.
+    pub status: CheckStatus,
+    /// The parsed record, if found.
+    pub record: Option<BimiRecord>,
+    /// Remediation hints.
+    pub hints: Vec<String>,
+}
+
+/// Full report for a sending domain.
+#[derive(Debug, Clone)]
+pub struct DomainReport {
+    /// The checked domain.
+    pub domain: String,
+    /// SPF result.
+    pub spf: SpfResult,
+    /// DKIM result.
+    pub dkim: DkimResult,
+    /// DMARC result.
+    pub dmarc: DmarcResult,
+    /// BIMI result.
+    pub bimi: BimiResult,
+}
+
+impl DomainReport {
+    /// Worst status across all checks.
+    pub fn status(&self) -> CheckStatus {
+        self.spf
+            .status
+            .max(self.dkim.status)
+            .max(self.dmarc.status)
+            .max(self.bimi.status)
+    }
+
+    /// `true` if no check failed (warnings are allowed).
+    pub fn is_ok(&self) -> bool {
+        self.status() != CheckStatus::Fail
+    }
+
+    /// All remediation hints.
+    pub fn hints(&self) -> Vec<&str> {
+        self.spf
+            .hints
+            .iter()
+            .chain(&self.dkim.hints)
+            .chain(&self.dmarc.hints)
+            .chain(&self.bimi.hints)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl fmt::Display for DomainReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.domain, self.status())?;
+        for (name, status, hints) in [
+            ("SPF", self.spf.status, &self.spf.hints),
+            ("DKIM", self.dkim.status, &self.dkim.hints),
+            ("DMARC", self.dmarc.status, &self.dmarc.hints),
+            ("BIMI", self.bimi.status, &self.bimi.hints),
+        ] {
+            writeln!(f, "  {:<6} {}", name, status)?;
+            for hint in hints {
+                writeln!(f, "         - {}", hint)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+/// Check the domain of the configured default sender (`EMAIL_FROM`).
+///
+/// Returns [`MailError::MissingField`] if no default sender is configured.
+pub async fn check_from_domain() -> Result<DomainReport, MailError> {
+    let from = crate::default_from().ok_or(MailError::MissingField("from"))?;
+    let (_, domain) = from
+        .email
+        .rsplit_once('@')
+        .ok_or_else(|| MailError::InvalidAddress(format!("'{}' has no domain", from.email)))?;
+    check_domain(domain).await
+}
+
+/// Check SPF, DKIM (common selectors), DMARC and BIMI for a sending domain.
+pub async fn check_domain(domain: &str) -> Result<DomainReport, MailError> {
+    check_domain_with_selectors(domain, COMMON_DKIM_SELECTORS).await
+}
+
+/// Check SPF, DKIM (the given selectors), DMARC and BIMI for a sending domain.
+pub async fn check_domain_with_selectors(
+    domain: &str,
+    selectors: &[&str],
+) -> Result<DomainReport, MailError> {
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()
+        .map_err(|e| MailError::Internal(format!("DNS resolver setup failed: {}", e)))?;
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+
+    let spf = evaluate_spf(&lookup_txt(&resolver, &domain).await?);
+
+    let mut found = Vec::new();
+    for selector in selectors {
+        let name = format!("{}._domainkey.{}", selector, domain);
+        found.extend(
+            lookup_txt(&resolver, &name)
+                .await?
+                .into_iter()
+                .map(|r| (*selector, r)),
+        );
+    }
+    let dkim = evaluate_dkim(&found);
+
+    let dmarc = evaluate_dmarc(&lookup_txt(&resolver, &format!("_dmarc.{}", domain)).await?);
+
+    let bimi = evaluate_bimi(
+        &lookup_txt(&resolver, &format!("default._bimi.{}", domain)).await?,
+        dmarc.record.as_ref(),
+    );
+
+    Ok(DomainReport {
+        domain,
+        spf,
+        dkim,
+        dmarc,
+        bimi,
+    })
+}
+
+/// Fetch TXT records, treating "no records" as an empty list.
+async fn lookup_txt(resolver: &TokioAsyncResolver, name: &str) -> Result<Vec<String>, MailError> {
+    match resolver.txt_lookup(name).await {
+        Ok(lookup) => Ok(lookup
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|part| String::from_utf8_lossy(part))
+                    .collect::<String>()
+            })
+            .collect()),
+        Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => Ok(Vec::new()),
+        Err(e) => Err(MailError::Internal(format!(
+            "DNS lookup for {} failed: {}",
+            name, e
+        ))),
+    }
+}
+
+/// Evaluate the TXT records of a domain for SPF.
+pub fn evaluate_spf(records: &[String]) -> SpfResult {
+    let spf: Vec<&String> = records
+        .iter()
+        .filter(|r| r.trim_start().to_lowercase().starts_with("v=spf1"))
+        .collect();
+
+    let mut hints = Vec::new();
+    let record = match spf.as_slice() {
+        [] => {
+            hints.push(
+                "No SPF record found. Add a TXT record like \
+                 `v=spf1 include:<your provider> ~all`."
+                    .to_string(),
+            );
+            return SpfResult {
+                status: CheckStatus::Fail,
+                record: None,
+                hints,
+            };
+        }
+        [one] => SpfRecord::parse(one),
+        _ => {
+            hints.push(format!(
+                "Found {} SPF records; receivers treat this as an error. \
+                 Merge them into a single record.",
+                spf.len()
+            ));
+            return SpfResult {
+                status: CheckStatus::Fail,
+                record: None,
+                hints,
+            };
+        }
+    };
+
+    let Some(record) = record else {
+        hints.push("SPF record could not be parsed.".to_string());
+        return SpfResult {
+            status: CheckStatus::Fail,
+            record: None,
+            hints,
+        };
+    };
+
+    let mut status = CheckStatus::Pass;
+    match record.all {
+        Some('+') => {
+            status = CheckStatus::Fail;
+            hints.push("`+all` lets anyone send as this domain. Use `~all` or `-all`.".into());
+        }
+        Some('?') => {
+            status = CheckStatus::Warn;
+            hints.push("`?all` is neutral and gives no protection. Use `~all` or `-all`.".into());
+        }
+        None if !record.mechanisms.iter().any(|m| m.starts_with("redirect=")) => {
+            status = CheckStatus::Warn;
+            hints.push("SPF record has no `all` mechanism. End it with `~all` or `-all`.".into());
+        }
+        _ => {}
+    }
+
+    if record.lookup_count > SPF_MAX_LOOKUPS {
+        status = CheckStatus::Fail;
+        hints.push(format!(
+            "SPF record needs {} DNS lookups (limit is {}). Remove unused includes.",
+            record.lookup_count, SPF_MAX_LOOKUPS
+        ));
+    }
+
+    SpfResult {
+        status,
+        record: Some(record),
+        hints,
+    }
+}
+
+/// Evaluate `(selector, TXT record)` pairs for DKIM.
+pub fn evaluate_dkim(records: &[(&str, String)]) -> DkimResult {
+    let mut selectors = Vec::new();
+    let mut hints = Vec::new();
+
+    for (selector, record) in records {
+        let tags: Vec<(String, String)> = record
+            .split(';')
+            .filter_map(|tag| tag.split_once('='))
+            .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+            .collect();
+        let tag = |name: &str| tags.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+
+        let Some(key) = tag("p") else {
+            continue;
+        };
+        let revoked = key.is_empty();
+        if revoked {
+            hints.push(format!(
+                "DKIM key for selector `{}` is revoked (empty `p=`).",
+                selector
+            ));
+        }
+        selectors.push(DkimSelector {
+            selector: selector.to_string(),
+            record: record.clone(),
+            key_type: tag("k").unwrap_or_else(|| "rsa".to_string()),
+            revoked,
+        });
+    }
+
+    let status = if selectors.iter().any(|s| !s.revoked) {
+        CheckStatus::Pass
+    } else {
+        if selectors.is_empty() {
+            hints.push(
+                "No DKIM key found for the probed selectors. Publish the DKIM record \
+                 from your provider's domain settings."
+                    .to_string(),
+            );
+        }
+        CheckStatus::Fail
+    };
+
+    DkimResult {
+        status,
+        selectors,
+        hints,
+    }
+}
+
+/// Evaluate the TXT records of `_dmarc.<domain>`.
+pub fn evaluate_dmarc(records: &[String]) -> DmarcResult {
+    let mut hints = Vec::new();
+
+    let Some(record) = records.iter().find_map(|r| DmarcRecord::parse(r)) else {
+        hints.push(
+            "No DMARC record found. Add a TXT record at `_dmarc` like \
+             `v=DMARC1; p=none; rua=mailto:dmarc@<domain>` and tighten it over time."
+                .to_string(),
+        );
+        return DmarcResult {
+            status: CheckStatus::Fail,
+            record: None,
+            hints,
+        };
+    };
+
+    let mut status = CheckStatus::Pass;
+    if record.policy == "none" {
+        status = CheckStatus::Warn;
+        hints.push(
+            "DMARC policy is `p=none` (monitoring only). Move to `quarantine` or `reject` \
+             once reports look clean."
+                .to_string(),
+        );
+    }
+    if record.pct < 100 {
+        status = status.max(CheckStatus::Warn);
+        hints.push(format!(
+            "DMARC policy only applies to {}% of mail.",
+            record.pct
+        ));
+    }
+    if record.rua.is_empty() {
+        status = status.max(CheckStatus::Warn);
+        hints.push("DMARC has no `rua=` address, so you won't receive aggregate reports.".into());
+    }
+    if record.aspf == 's' || record.adkim == 's' {
+        hints.push(
+            "DMARC uses strict alignment (`aspf=s`/`adkim=s`): your provider's bounce \
+             domain and DKIM `d=` must equal the From domain exactly, not a subdomain."
+                .to_string(),
+        );
+    }
+
+    DmarcResult {
+        status,
+        record: Some(record),
+        hints,
+    }
+}
+
+/// Evaluate the TXT records of `default._bimi.<domain>` against the
+/// domain's DMARC record.
+pub fn evaluate_bimi(records: &[String], dmarc: Option<&DmarcRecord>) -> BimiResult {
+    let mut hints = Vec::new();
+
+    let Some(record) = records.iter().find_map(|r| BimiRecord::parse(r)) else {
+        return BimiResult {
+            status: CheckStatus::Pass,
+            record: None,
+            hints,
+        };
+    };
+
+    let mut status = CheckStatus::Pass;
+    match &record.logo {
+        Some(logo) if !logo.starts_with("https://") => {
+            status = CheckStatus::Fail;
+            hints.push(format!("BIMI logo `{}` must be an https:// URL.", logo));
+        }
+        Some(_) => {}
+        None => {
+            status = CheckStatus::Fail;
+            hints.push("BIMI record has no logo (`l=`).".to_string());
+        }
+    }
+    if !dmarc.is_some_and(DmarcRecord::is_enforced) {
+        status = CheckStatus::Fail;
+        hints.push(
+            "BIMI logos are only shown when DMARC is enforced (`p=quarantine` or \
+             `p=reject` with `pct=100`)."
+                .to_string(),
+        );
+    }
+    if record.authority.is_none() {
+        status = status.max(CheckStatus::Warn);
+        hints.push(
+            "BIMI record has no Verified Mark Certificate (`a=`); Gmail and Apple Mail \
+             require one to show the logo."
+                .to_string(),
+        );
+    }
+
+    BimiResult {
+        status,
+        record: Some(record),
+        hints,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spf() {
+        let spf = SpfRecord::parse("v=spf1 include:_spf.google.com include:amazonses.com mx -all")
+            .unwrap();
+        assert_eq!(spf.includes, vec!["_spf.google.com", "amazonses.com"]);
+        assert_eq!(spf.all, Some('-'));
+        assert_eq!(spf.lookup_count, 3);
+        assert!(SpfRecord::parse("google-site-verification=abc").is_none());
+    }
+
+    #[test]
+    fn test_evaluate_spf() {
+        assert_eq!(evaluate_spf(&[]).status, CheckStatus::Fail);
+        assert_eq!(
+            evaluate_spf(&["v=spf1 include:a.com ~all".into()]).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            evaluate_spf(&["v=spf1 +all".into()]).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(
+            evaluate_spf(&["v=spf1 mx".into()]).status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            evaluate_spf(&["v=spf1 -all".into(), "v=spf1 ~all".into()]).status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_spf_lookup_limit() {
+        let includes: Vec<String> = (0..11).map(|i| format!("include:s{}.com", i)).collect();
+        let record = format!("v=spf1 {} ~all", includes.join(" "));
+        let result = evaluate_spf(&[record]);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.hints[0].contains("11 DNS lookups"));
+    }
+
+    #[test]
+    fn test_evaluate_dkim() {
+        let found = vec![("s1", "v=DKIM1; k=rsa; p=MIGfMA0GCSq".to_string())];
+        let result = evaluate_dkim(&found);
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert_eq!(result.selectors[0].key_type, "rsa");
+
+        let revoked = vec![("old", "v=DKIM1; p=".to_string())];
+        assert_eq!(evaluate_dkim(&revoked).status, CheckStatus::Fail);
+        assert_eq!(evaluate_dkim(&[]).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_parse_dmarc() {
+        let dmarc = DmarcRecord::parse(
+            "v=DMARC1; p=quarantine; sp=reject; pct=50; rua=mailto:a@x.com,mailto:b@x.com",
+        )
+        .unwrap();
+        assert_eq!(dmarc.policy, "quarantine");
+        assert_eq!(dmarc.subdomain_policy.as_deref(), Some("reject"));
+        assert_eq!(dmarc.pct, 50);
+        assert_eq!(dmarc.rua.len(), 2);
+        assert_eq!((dmarc.adkim, dmarc.aspf), ('r', 'r'));
+        assert!(!dmarc.is_enforced());
+        assert!(DmarcRecord::parse("v=spf1 -all").is_none());
+
+        let dmarc = DmarcRecord::parse("v=DMARC1; p=reject; adkim=s; aspf=S").unwrap();
+        assert_eq!((dmarc.adkim, dmarc.aspf), ('s', 's'));
+        assert!(dmarc.is_enforced());
+    }
+
+    #[test]
+    fn test_evaluate_dmarc() {
+        assert_eq!(evaluate_dmarc(&[]).status, CheckStatus::Fail);
+        assert_eq!(
+            evaluate_dmarc(&["v=DMARC1; p=none; rua=mailto:d@x.com".into()]).status,
+            CheckStatus::Warn
+        );
+        assert_eq!(
+            evaluate_dmarc(&["v=DMARC1; p=reject; rua=mailto:d@x.com".into()]).status,
+            CheckStatus::Pass
+        );
+    }
+
+    #[test]
+    fn test_evaluate_bimi() {
+        let enforced = DmarcRecord::parse("v=DMARC1; p=reject").unwrap();
+        let monitoring = DmarcRecord::parse("v=DMARC1; p=none").unwrap();
+
+        let result = evaluate_bimi(&[], None);
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(result.hints.is_empty());
+
+        let record = "v=BIMI1; l=https://example.com/logo.svg; a=https://example.com/vmc.pem";
+        let result = evaluate_bimi(&[record.into()], Some(&enforced));
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert_eq!(
+            result.record.unwrap().logo.as_deref(),
+            Some("https://example.com/logo.svg")
+        );
+
+        let result = evaluate_bimi(&[record.into()], Some(&monitoring));
+        assert_eq!(result.status, CheckStatus::Fail);
+
+        let record = "v=BIMI1; l=https://example.com/logo.svg; a=";
+        let result = evaluate_bimi(&[record.into()], Some(&enforced));
+        assert_eq!(result.status, CheckStatus::Warn);
+    }
+}
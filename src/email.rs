@@ -3,8 +3,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::address::{Address, ToAddress};
+use crate::address::{Address, ToAddress, ToAddresses};
 use crate::attachment::Attachment;
+use crate::error::MailError;
+use crate::personalize::Personalization;
 
 /// An email message.
 ///
@@ -31,7 +33,39 @@ use crate::attachment::Attachment;
 /// - `assigns` - Template variables (for use with templating systems)
 /// - `private` - Private storage for libraries/frameworks
 /// - `provider_options` - Provider-specific options (tags, templates, etc.)
+/// - `personalizations` - Per-recipient data for mail merge (see [`Email::personalize`])
+/// - `return_path` - Bounce address (see [`Email::return_path`])
+///
+/// ## JSON
+///
+/// `Email` round-trips through serde, so it can be stored in a job queue and
+/// delivered later. The JSON form uses the field names above; every field is
+/// optional when deserializing, addresses are `{"name": ..., "email": ...}`
+/// objects and attachment `data` is base64:
+///
+/// ```
+/// use missive::Email;
+///
+/// let json = r#"{
+///     "from": {"name": "Acme", "email": "noreply@acme.com"},
+///     "to": [{"email": "user@example.com"}],
+///     "subject": "Welcome",
+///     "text_body": "Hi!",
+///     "attachments": [{"filename": "a.txt", "content_type": "text/plain", "data": "aGk="}]
+/// }"#;
+///
+/// let email: Email = serde_json::from_str(json).unwrap();
+/// assert_eq!(email.attachments[0].data, b"hi");
+///
+/// let copy: Email = serde_json::from_str(&serde_json::to_string(&email).unwrap()).unwrap();
+/// assert_eq!(copy.to, email.to);
+/// ```
+///
+/// With the `schemars` feature, `schemars::schema_for!(Email)` emits a JSON
+/// Schema for this format.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct Email {
     /// Sender address
     pub from: Option<Address>,
@@ -59,6 +93,15 @@ pub struct Email {
     pub private: HashMap<String, serde_json::Value>,
     /// Provider-specific options (e.g., tracking, tags, templates)
     pub provider_options: HashMap<String, serde_json::Value>,
+    /// Recipients and their template data for mail merge.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub personalizations: Vec<Personalization>,
+    /// Bounce address (envelope sender), when it differs from `from`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_path: Option<Address>,
+    /// Pre-formatted header fields sent verbatim, in order (see [`Email::raw_header`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_headers: Vec<String>,
 }
 
 impl Email {
@@ -81,9 +124,10 @@ impl Email {
     /// Add a recipient.
     ///
     /// Can be called multiple times to add multiple recipients.
-    /// Accepts anything that implements `ToAddress`.
-    pub fn to(mut self, addr: impl ToAddress) -> Self {
-        self.to.push(addr.to_address());
+    /// Accepts anything that implements `ToAddresses`: a single address,
+    /// a comma-separated string, or a list of addresses.
+    pub fn to(mut self, addrs: impl ToAddresses) -> Self {
+        self.to.extend(addrs.to_addresses());
         self
     }
 
@@ -94,9 +138,9 @@ impl Email {
     }
 
     /// Add a CC recipient.
-    /// Accepts anything that implements `ToAddress`.
-    pub fn cc(mut self, addr: impl ToAddress) -> Self {
-        self.cc.push(addr.to_address());
+    /// Accepts anything that implements `ToAddresses`.
+    pub fn cc(mut self, addrs: impl ToAddresses) -> Self {
+        self.cc.extend(addrs.to_addresses());
         self
     }
 
@@ -107,9 +151,9 @@ impl Email {
     }
 
     /// Add a BCC recipient.
-    /// Accepts anything that implements `ToAddress`.
-    pub fn bcc(mut self, addr: impl ToAddress) -> Self {
-        self.bcc.push(addr.to_address());
+    /// Accepts anything that implements `ToAddresses`.
+    pub fn bcc(mut self, addrs: impl ToAddresses) -> Self {
+        self.bcc.extend(addrs.to_addresses());
         self
     }
 
@@ -122,9 +166,9 @@ impl Email {
     /// Add a reply-to address.
     ///
     /// Can be called multiple times to add multiple reply-to addresses.
-    /// Accepts anything that implements `ToAddress`.
-    pub fn reply_to(mut self, addr: impl ToAddress) -> Self {
-        self.reply_to.push(addr.to_address());
+    /// Accepts anything that implements `ToAddresses`.
+    pub fn reply_to(mut self, addrs: impl ToAddresses) -> Self {
+        self.reply_to.extend(addrs.to_addresses());
         self
     }
 
@@ -134,6 +178,16 @@ impl Email {
         self
     }
 
+    /// Set the bounce address, e.g. a VERP address per recipient.
+    ///
+    /// Used as the SMTP envelope sender (`MAIL FROM`), Amazon SES `Source`
+    /// (`ReturnPath` for bulk sends) and Mailgun `h:Return-Path`, while the
+    /// From header stays as set by [`from()`](Self::from).
+    pub fn return_path(mut self, addr: impl ToAddress) -> Self {
+        self.return_path = Some(addr.to_address());
+        self
+    }
+
     /// Set the subject line.
     pub fn subject(mut self, subject: impl Into<String>) -> Self {
         self.subject = subject.into();
@@ -152,6 +206,25 @@ impl Email {
         self
     }
 
+    /// Set the HTML body to `content` wrapped in a registered layout.
+    ///
+    /// Set the subject and assigns first; the layout can use them. Fails if
+    /// the layout or one of its partials isn't registered (see
+    /// [`templates::layout`](crate::templates::layout)).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let email = Email::new()
+    ///     .to("user@example.com")
+    ///     .subject("Your receipt")
+    ///     .with_layout("base", "<p>Thanks for your order.</p>")?;
+    /// ```
+    pub fn with_layout(self, layout: &str, content: &str) -> Result<Self, MailError> {
+        let html = crate::templates::layout::render(layout, content, &self)?;
+        Ok(self.html_body(html).put_private("layout", layout))
+    }
+
     /// Add an attachment.
     pub fn attachment(mut self, attachment: Attachment) -> Self {
         self.attachments.push(attachment);
@@ -164,6 +237,122 @@ impl Email {
         self
     }
 
+    /// Add a pre-formatted header field, sent exactly as given.
+    ///
+    /// For signed headers that must survive relaying, such as `ARC-Seal`,
+    /// `ARC-Message-Signature` or `DKIM-Signature`: the field is written at
+    /// the top of the message, in the order added, without being re-folded.
+    /// Fold long values with CRLF followed by a space or tab.
+    ///
+    /// Only providers that send the raw message honor these: SMTP, Amazon
+    /// SES and Mailgun with `mime(true)`.
+    ///
+    /// ```
+    /// use missive::Email;
+    ///
+    /// let email = Email::new()
+    ///     .raw_header("ARC-Seal: i=1; a=rsa-sha256; cv=none; d=example.com;\r\n\tb=dGVzdA==");
+    /// assert!(email.validate_headers().is_ok());
+    /// ```
+    pub fn raw_header(mut self, field: impl Into<String>) -> Self {
+        self.raw_headers.push(field.into());
+        self
+    }
+
+    /// Set the message priority.
+    ///
+    /// Sets the `X-Priority`, `Priority` and `Importance` headers, which
+    /// together cover Outlook, Apple Mail, Thunderbird and Gmail.
+    ///
+    /// ```
+    /// use missive::{Email, Priority};
+    ///
+    /// let email = Email::new().priority(Priority::High);
+    /// assert_eq!(email.headers["X-Priority"], "1 (Highest)");
+    /// assert_eq!(email.headers["Importance"], "high");
+    /// ```
+    pub fn priority(mut self, priority: Priority) -> Self {
+        for (name, value) in priority.headers() {
+            self.headers.insert(name.to_string(), value.to_string());
+        }
+        self
+    }
+
+    /// The priority set by [`priority()`](Self::priority), read from the
+    /// `X-Priority` header.
+    pub fn get_priority(&self) -> Option<Priority> {
+        let value = self.get_header("X-Priority")?;
+        match value.trim().chars().next()?.to_digit(10)? {
+            1 | 2 => Some(Priority::High),
+            3 => Some(Priority::Normal),
+            4 | 5 => Some(Priority::Low),
+            _ => None,
+        }
+    }
+
+    /// Schedule delivery for a later time.
+    ///
+    /// Stored as the `scheduled_at` provider option, which the global
+    /// `deliver()` functions translate for the configured provider (Resend,
+    /// SendGrid, Brevo, Mailgun, Customer.io). Providers without scheduling send
+    /// immediately and log a warning.
+    ///
+    /// ```
+    /// use missive::Email;
+    ///
+    /// let at = "2030-01-01T09:00:00Z".parse().unwrap();
+    /// let email = Email::new().scheduled_at(at);
+    /// assert_eq!(email.get_scheduled_at(), Some(at));
+    /// ```
+    pub fn scheduled_at(mut self, at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.provider_options
+            .insert("scheduled_at".to_string(), at.to_rfc3339().into());
+        self
+    }
+
+    /// The delivery time set by [`scheduled_at()`](Self::scheduled_at).
+    pub fn get_scheduled_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let value = self.provider_options.get("scheduled_at")?.as_str()?;
+        chrono::DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|at| at.with_timezone(&chrono::Utc))
+    }
+
+    /// Ask the recipient's mail client to send a read receipt to `addr`.
+    ///
+    /// Sets `Disposition-Notification-To` (RFC 8098) and the older
+    /// `Return-Receipt-To`. Clients usually ask the user first, and many
+    /// never send one, so don't rely on receiving it.
+    pub fn request_read_receipt(mut self, addr: impl ToAddress) -> Self {
+        let addr = addr.to_address().formatted_rfc5322();
+        self.headers
+            .insert("Disposition-Notification-To".to_string(), addr.clone());
+        self.headers.insert("Return-Receipt-To".to_string(), addr);
+        self
+    }
+
+    /// Get a header value, matching the name case-insensitively.
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Check custom header names and values, and the subject, for characters
+    /// that would break the message or inject headers (CR, LF and other
+    /// control characters).
+    ///
+    /// Called before every send; adapters building requests directly call it
+    /// as well.
+    pub fn validate_headers(&self) -> Result<(), MailError> {
+        crate::header::validate("Subject", &self.subject)?;
+        crate::header::validate_all(&self.headers)?;
+        self.raw_headers
+            .iter()
+            .try_for_each(|field| crate::header::validate_raw(field))
+    }
+
     /// Set a provider-specific option.
     ///
     /// These are passed to the adapter for provider-specific features
@@ -247,6 +436,77 @@ impl Email {
     pub fn regular_attachments(&self) -> Vec<&Attachment> {
         self.attachments.iter().filter(|a| !a.is_inline()).collect()
     }
+
+    /// Approximate size of the encoded MIME message in bytes.
+    ///
+    /// Counts headers, both bodies and base64-encoded attachments (with line
+    /// breaks) plus a fixed overhead per part. Path-based attachments are
+    /// measured from file metadata; unreadable files count as empty.
+    pub fn estimated_size(&self) -> usize {
+        const MESSAGE_OVERHEAD: usize = 512;
+        const PART_OVERHEAD: usize = 200;
+
+        let headers: usize = self
+            .from
+            .iter()
+            .chain(&self.to)
+            .chain(&self.cc)
+            .chain(&self.bcc)
+            .chain(&self.reply_to)
+            .map(|a| a.formatted_rfc5322().len() + 2)
+            .chain(self.headers.iter().map(|(k, v)| k.len() + v.len() + 4))
+            .sum();
+        let bodies = [&self.text_body, &self.html_body]
+            .into_iter()
+            .flatten()
+            .map(|body| body.len() + PART_OVERHEAD)
+            .sum::<usize>();
+        let attachments = self
+            .attachments
+            .iter()
+            .map(|a| {
+                let encoded = a.get_size().unwrap_or(0).div_ceil(3) * 4;
+                encoded + encoded / 76 * 2 + PART_OVERHEAD
+            })
+            .sum::<usize>();
+
+        MESSAGE_OVERHEAD + self.subject.len() + headers + bodies + attachments
+    }
+}
+
+/// Message priority, see [`Email::priority`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Urgent: `X-Priority: 1`, `Importance: high`.
+    High,
+    /// The default when no priority is set.
+    Normal,
+    /// Bulk or informational: `X-Priority: 5`, `Importance: low`.
+    Low,
+}
+
+impl Priority {
+    /// `X-Priority`, `Priority` and `Importance` header values.
+    pub fn headers(self) -> [(&'static str, &'static str); 3] {
+        match self {
+            Priority::High => [
+                ("X-Priority", "1 (Highest)"),
+                ("Priority", "urgent"),
+                ("Importance", "high"),
+            ],
+            Priority::Normal => [
+                ("X-Priority", "3 (Normal)"),
+                ("Priority", "normal"),
+                ("Importance", "normal"),
+            ],
+            Priority::Low => [
+                ("X-Priority", "5 (Lowest)"),
+                ("Priority", "non-urgent"),
+                ("Importance", "low"),
+            ],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -291,6 +551,22 @@ mod tests {
         assert_eq!(from.name, Some("Alice".to_string()));
     }
 
+    #[test]
+    fn test_estimated_size() {
+        let email = Email::new()
+            .from("sender@example.com")
+            .to("user@example.com")
+            .subject("Report")
+            .text_body("See attached.");
+        let base = email.estimated_size();
+        assert!(base > 13 && base < 1024);
+
+        let email = email.attachment(Attachment::from_bytes("report.pdf", vec![0; 3 * 1024]));
+        let added = email.estimated_size() - base;
+        // 4 KiB of base64 plus line breaks and part headers
+        assert!((4096..4096 + 512).contains(&added));
+    }
+
     #[test]
     fn test_is_valid() {
         let invalid = Email::new().to("recipient@example.com");
@@ -310,6 +586,34 @@ mod tests {
 
         assert_eq!(email.headers.get("X-Custom"), Some(&"value".to_string()));
         assert_eq!(email.headers.get("X-Priority"), Some(&"1".to_string()));
+        assert_eq!(email.get_priority(), Some(Priority::High));
+    }
+
+    #[test]
+    fn test_priority() {
+        let email = Email::new().priority(Priority::Low);
+        assert_eq!(email.headers["X-Priority"], "5 (Lowest)");
+        assert_eq!(email.headers["Priority"], "non-urgent");
+        assert_eq!(email.headers["Importance"], "low");
+        assert_eq!(email.get_priority(), Some(Priority::Low));
+
+        let email = email.priority(Priority::Normal);
+        assert_eq!(email.headers.len(), 3);
+        assert_eq!(email.get_priority(), Some(Priority::Normal));
+        assert_eq!(Email::new().get_priority(), None);
+    }
+
+    #[test]
+    fn test_request_read_receipt() {
+        let email = Email::new().request_read_receipt(("Billing", "billing@example.com"));
+        assert_eq!(
+            email.headers["Disposition-Notification-To"],
+            "\"Billing\" <billing@example.com>"
+        );
+        assert_eq!(
+            email.headers["Return-Receipt-To"],
+            "\"Billing\" <billing@example.com>"
+        );
     }
 
     #[test]
@@ -322,6 +626,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_layout() {
+        crate::templates::layout::register("test_with_layout", "<main>{{ content }}</main>");
+
+        let email = Email::new()
+            .with_layout("test_with_layout", "<p>Hi</p>")
+            .unwrap();
+        assert_eq!(email.html_body.as_deref(), Some("<main><p>Hi</p></main>"));
+        assert_eq!(email.private["layout"], "test_with_layout");
+
+        assert!(Email::new().with_layout("test_no_such_layout", "").is_err());
+    }
+
     #[test]
     fn test_to_address_trait() {
         struct User {
@@ -0,0 +1,280 @@
+//! At-rest encryption for any [`Storage`].
+//!
+//! Dev mailboxes fill up with real names and addresses as soon as staging
+//! data is pointed at them. [`EncryptedStorage`] wraps another backend and
+//! encrypts each email with ChaCha20-Poly1305 before it reaches it; reads
+//! decrypt transparently, so the preview UI works unchanged:
+//!
+//! ```rust,ignore
+//! use axum::Router;
+//! use missive::{EncryptedStorage, RedisStorage};
+//!
+//! // EMAIL_STORAGE_KEY=<32 random bytes, base64> (see generate_storage_key)
+//! let storage = EncryptedStorage::from_env(RedisStorage::connect(url)?)?.shared();
+//! let app = Router::new().nest("/dev/mailbox", missive::preview::mailbox_router(storage));
+//! ```
+//!
+//! The wrapped backend only sees an empty email whose text body is the
+//! ciphertext, plus the time it was stored. Emails stored before encryption
+//! was turned on are returned as they are; emails that don't decrypt with
+//! the current key are skipped with a warning.
+
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::config;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::storage::{Storage, StoredEmail};
+
+/// Key length in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// `private` key marking an encrypted email, with the algorithm as its value.
+const MARKER: &str = "encrypted";
+const ALGORITHM: &str = "chacha20poly1305";
+
+/// A [`Storage`] that encrypts emails before passing them to `S`.
+pub struct EncryptedStorage<S> {
+    inner: S,
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl<S: Storage> EncryptedStorage<S> {
+    /// Wrap `inner`, encrypting with a 32-byte `key`.
+    pub fn new(inner: S, key: &[u8]) -> Result<Self, MailError> {
+        let key = UnboundKey::new(&CHACHA20_POLY1305, key).map_err(|_| {
+            MailError::Configuration(format!("Storage key must be {} bytes", KEY_LEN))
+        })?;
+        Ok(Self {
+            inner,
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Wrap `inner` with the base64 key in `EMAIL_STORAGE_KEY`.
+    pub fn from_env(inner: S) -> Result<Self, MailError> {
+        let encoded = config::var("EMAIL_STORAGE_KEY")
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| MailError::Configuration("EMAIL_STORAGE_KEY not set".into()))?;
+        let key = STANDARD.decode(encoded.trim()).map_err(|e| {
+            MailError::Configuration(format!("EMAIL_STORAGE_KEY is not valid base64: {}", e))
+        })?;
+        Self::new(inner, &key)
+    }
+
+    /// Wrap in an `Arc` to share with the preview UI and
+    /// [`SmtpCaptureServer`](crate::smtp_capture::SmtpCaptureServer).
+    pub fn shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
+    /// The wrapped storage, which holds the ciphertext.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn encrypt(&self, email: &Email) -> Result<Email, MailError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| MailError::Internal("Could not generate a nonce".into()))?;
+
+        let mut data = serde_json::to_vec(email)?;
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+            .map_err(|_| MailError::Internal("Could not encrypt email".into()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&data);
+        let mut envelope = Email::new();
+        envelope.text_body = Some(STANDARD.encode(sealed));
+        envelope
+            .private
+            .insert(MARKER.to_string(), serde_json::json!(ALGORITHM));
+        Ok(envelope)
+    }
+
+    /// Decrypt a stored email; `None` if it can't be.
+    fn decrypt(&self, stored: StoredEmail) -> Option<StoredEmail> {
+        if !stored.email.private.contains_key(MARKER) {
+            return Some(stored);
+        }
+
+        let sealed = stored
+            .email
+            .text_body
+            .as_deref()
+            .and_then(|body| STANDARD.decode(body).ok())
+            .filter(|sealed| sealed.len() > NONCE_LEN);
+        let email = sealed.and_then(|mut sealed| {
+            let mut data = sealed.split_off(NONCE_LEN);
+            let nonce = Nonce::try_assume_unique_for_key(&sealed).ok()?;
+            let plain = self
+                .key
+                .open_in_place(nonce, Aad::empty(), &mut data)
+                .ok()?;
+            serde_json::from_slice::<Email>(plain).ok()
+        });
+        let Some(mut email) = email else {
+            tracing::warn!(
+                id = %stored.id,
+                "Skipping stored email that doesn't decrypt (wrong EMAIL_STORAGE_KEY?)"
+            );
+            return None;
+        };
+
+        // Keep what the backend added on push, such as `sent_at`
+        for (key, value) in stored.email.private {
+            if key != MARKER {
+                email.private.insert(key, value);
+            }
+        }
+        Some(StoredEmail {
+            id: stored.id,
+            email,
+            sent_at: stored.sent_at,
+            read: stored.read,
+        })
+    }
+}
+
+/// Generate a random base64 key for `EMAIL_STORAGE_KEY`.
+pub fn generate_storage_key() -> String {
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .expect("system random number generator failed");
+    STANDARD.encode(key)
+}
+
+impl<S: Storage> Storage for EncryptedStorage<S> {
+    fn push(&self, email: Email) -> String {
+        match self.encrypt(&email) {
+            Ok(envelope) => self.inner.push(envelope),
+            Err(e) => {
+                // Never fall back to storing the plaintext
+                tracing::error!(error = %e, "Dropping email that couldn't be encrypted");
+                uuid::Uuid::new_v4().to_string()
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<StoredEmail> {
+        self.inner.pop().and_then(|e| self.decrypt(e))
+    }
+
+    fn get(&self, id: &str) -> Option<StoredEmail> {
+        self.inner.get(id).and_then(|e| self.decrypt(e))
+    }
+
+    fn all(&self) -> Vec<StoredEmail> {
+        self.inner
+            .all()
+            .into_iter()
+            .filter_map(|e| self.decrypt(e))
+            .collect()
+    }
+
+    fn delete(&self, id: &str) -> bool {
+        self.inner.delete(id)
+    }
+
+    fn clear(&self) {
+        self.inner.clear()
+    }
+
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn flush(&self) -> Vec<StoredEmail> {
+        self.inner
+            .flush()
+            .into_iter()
+            .filter_map(|e| self.decrypt(e))
+            .collect()
+    }
+
+    // `find` keeps the default, which filters the decrypted `all()`: the
+    // wrapped storage can't match on ciphertext.
+
+    fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail> {
+        self.inner
+            .page(offset, limit)
+            .into_iter()
+            .filter_map(|e| self.decrypt(e))
+            .collect()
+    }
+
+    fn mark_read(&self, id: &str) -> bool {
+        self.inner.mark_read(id)
+    }
+
+    fn unread_count(&self) -> usize {
+        self.inner.unread_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn storage() -> EncryptedStorage<MemoryStorage> {
+        EncryptedStorage::new(MemoryStorage::new(), &[7; KEY_LEN]).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_hides_plaintext() {
+        let storage = storage();
+        let id = storage.push(
+            Email::new()
+                .from("sender@example.com")
+                .to("jane@example.com")
+                .subject("Your account")
+                .text_body("Hello Jane"),
+        );
+
+        let raw = storage.inner().get(&id).unwrap();
+        let raw_json = serde_json::to_string(&raw.email).unwrap();
+        assert!(!raw_json.contains("jane@example.com"));
+        assert!(!raw_json.contains("Hello Jane"));
+
+        let stored = storage.get(&id).unwrap();
+        assert_eq!(stored.email.to[0].email, "jane@example.com");
+        assert_eq!(stored.email.subject, "Your account");
+        assert!(stored.email.private.contains_key("sent_at"));
+        assert!(!stored.email.private.contains_key(MARKER));
+    }
+
+    #[test]
+    fn test_wrong_key_and_plaintext() {
+        let inner = Arc::new(MemoryStorage::new());
+        let writer = EncryptedStorage::new(Arc::clone(&inner), &[1; KEY_LEN]).unwrap();
+        let reader = EncryptedStorage::new(Arc::clone(&inner), &[2; KEY_LEN]).unwrap();
+
+        writer.push(Email::new().subject("Secret"));
+        inner.push(Email::new().subject("Before encryption"));
+
+        let subjects: Vec<_> = reader.all().into_iter().map(|e| e.email.subject).collect();
+        assert_eq!(subjects, ["Before encryption"]);
+        assert_eq!(reader.count(), 2);
+    }
+
+    #[test]
+    fn test_key_validation() {
+        assert!(matches!(
+            EncryptedStorage::new(MemoryStorage::new(), &[0; 16]),
+            Err(MailError::Configuration(_))
+        ));
+        let key = STANDARD.decode(generate_storage_key()).unwrap();
+        assert!(EncryptedStorage::new(MemoryStorage::new(), &key).is_ok());
+    }
+}
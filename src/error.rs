@@ -1,7 +1,11 @@
 //! Error types for missive.
 
+use std::time::Duration;
+
 use thiserror::Error;
 
+use crate::validation::ValidationReport;
+
 /// Errors that can occur when sending emails.
 #[derive(Debug, Clone, Error)]
 pub enum MailError {
@@ -21,6 +25,44 @@ pub enum MailError {
     #[error("Invalid email address: {0}")]
     InvalidAddress(String),
 
+    /// Invalid custom header name or value (e.g. a value containing CR/LF).
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+
+    /// Strict validation failed (see [`Email::validate_strict`](crate::Email::validate_strict)).
+    #[error("Validation failed: {0}")]
+    Validation(ValidationReport),
+
+    /// The encoded message exceeds the provider's (or configured) size limit.
+    #[error("Message too large: ~{actual} bytes exceeds the limit of {limit} bytes")]
+    TooLarge {
+        /// The size limit in bytes.
+        limit: usize,
+        /// The estimated message size in bytes (see [`Email::estimated_size`](crate::Email::estimated_size)).
+        actual: usize,
+    },
+
+    /// A subject, header value or recipient list is over the mailer's limit
+    /// (see [`Capabilities`](crate::Capabilities)).
+    #[error("{field} exceeds the limit of {limit}: {actual}")]
+    LimitExceeded {
+        /// What is too long: `subject`, `header <name>` or `recipients`.
+        field: String,
+        /// The limit (bytes, or recipients).
+        limit: usize,
+        /// The actual length or recipient count.
+        actual: usize,
+    },
+
+    /// A provider option has a value the provider can't accept.
+    #[error("Invalid provider option `{option}`: {message}")]
+    InvalidOption {
+        /// The provider option key, e.g. `schedule_at`.
+        option: &'static str,
+        /// What's wrong with the value.
+        message: String,
+    },
+
     /// Error reading or processing attachment (generic).
     #[error("Attachment error: {0}")]
     AttachmentError(String),
@@ -45,6 +87,10 @@ pub enum MailError {
     #[error("Send error: {0}")]
     SendError(String),
 
+    /// Email was blocked before delivery (e.g., by the recipient sandbox).
+    #[error("Email blocked: {0}")]
+    Blocked(String),
+
     /// Unsupported feature for this adapter.
     #[error("Unsupported feature: {0}")]
     UnsupportedFeature(String),
@@ -56,6 +102,10 @@ pub enum MailError {
         message: String,
         /// Optional HTTP status code
         status: Option<u16>,
+        /// Provider-specific error code (e.g. Postmark's `ErrorCode`)
+        code: Option<String>,
+        /// How long the provider asked us to wait (from `Retry-After`)
+        retry_after: Option<Duration>,
     },
 
     /// HTTP request failed.
@@ -82,6 +132,8 @@ impl MailError {
             provider,
             message: message.into(),
             status: None,
+            code: None,
+            retry_after: None,
         }
     }
 
@@ -95,8 +147,155 @@ impl MailError {
             provider,
             message: message.into(),
             status: Some(status),
+            code: None,
+            retry_after: None,
+        }
+    }
+
+    /// Attach the provider's own error code to a provider error.
+    ///
+    /// Has no effect on other variants.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        if let Self::ProviderError { code: c, .. } = &mut self {
+            *c = Some(code.into());
+        }
+        self
+    }
+
+    /// Attach a retry delay to a provider error.
+    ///
+    /// Accepts a `Duration` or an `Option<Duration>`. Has no effect on other
+    /// variants.
+    pub fn with_retry_after(mut self, retry_after: impl Into<Option<Duration>>) -> Self {
+        if let Self::ProviderError { retry_after: r, .. } = &mut self {
+            *r = retry_after.into();
+        }
+        self
+    }
+
+    /// HTTP status code returned by the provider, if any.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::ProviderError { status, .. } => *status,
+            _ => None,
+        }
+    }
+
+    /// Provider-specific error code, if the provider returned one.
+    pub fn provider_error_code(&self) -> Option<&str> {
+        match self {
+            Self::ProviderError { code, .. } => code.as_deref(),
+            _ => None,
         }
     }
+
+    /// How long to wait before retrying, if the provider said.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::ProviderError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same email again may succeed.
+    ///
+    /// True for network failures, rate limiting (429), request timeouts (408)
+    /// and provider 5xx errors, or whenever the provider sent `Retry-After`.
+    /// Configuration, validation and other 4xx errors are permanent.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::SendError(_) | Self::HttpError(_) => true,
+            Self::ProviderError {
+                status,
+                retry_after,
+                ..
+            } => retry_after.is_some() || matches!(status, Some(408 | 429 | 500..=599)),
+            _ => false,
+        }
+    }
+
+    /// Coarse category of the error, for metrics and alerting.
+    ///
+    /// Separates problems on our side (`configuration`, `validation`) from
+    /// provider outages (`network`, `provider_5xx`) and throttling
+    /// (`rate_limited`). Returns one of `configuration`, `validation`,
+    /// `blocked`, `network`, `rate_limited`, `provider_4xx`, `provider_5xx`,
+    /// `provider` (no status code) or `internal`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotConfigured | Self::Configuration(_) | Self::UnsupportedFeature(_) => {
+                "configuration"
+            }
+            Self::MissingField(_)
+            | Self::InvalidAddress(_)
+            | Self::InvalidHeader(_)
+            | Self::InvalidOption { .. }
+            | Self::Validation(_)
+            | Self::TooLarge { .. }
+            | Self::LimitExceeded { .. }
+            | Self::AttachmentError(_)
+            | Self::AttachmentMissingContent(_)
+            | Self::AttachmentFileNotFound(_)
+            | Self::AttachmentReadError(_)
+            | Self::BuildError(_)
+            | Self::TemplateError(_) => "validation",
+            Self::Blocked(_) => "blocked",
+            Self::SendError(_) | Self::HttpError(_) => "network",
+            Self::ProviderError { status, .. } => match status {
+                Some(429) => "rate_limited",
+                Some(400..=499) => "provider_4xx",
+                Some(500..=599) => "provider_5xx",
+                _ => "provider",
+            },
+            Self::JsonError(_) | Self::Internal(_) => "internal",
+        }
+    }
+}
+
+/// Parse a `Retry-After` value: delay in seconds or an HTTP date.
+#[cfg(any(
+    feature = "amazon_ses",
+    feature = "brevo",
+    feature = "customerio",
+    feature = "mailgun",
+    feature = "mailjet",
+    feature = "mailtrap",
+    feature = "postmark",
+    feature = "resend",
+    feature = "sendgrid",
+    feature = "unsent",
+    feature = "zeptomail",
+    test
+))]
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Read the `Retry-After` header from a provider response.
+#[cfg(any(
+    feature = "amazon_ses",
+    feature = "brevo",
+    feature = "customerio",
+    feature = "mailgun",
+    feature = "mailjet",
+    feature = "mailtrap",
+    feature = "postmark",
+    feature = "resend",
+    feature = "sendgrid",
+    feature = "unsent",
+    feature = "zeptomail"
+))]
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
 }
 
 #[cfg(feature = "_http")]
@@ -132,3 +331,65 @@ impl From<lettre::address::AddressError> for MailError {
         Self::InvalidAddress(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(MailError::NotConfigured.kind(), "configuration");
+        assert_eq!(MailError::MissingField("from").kind(), "validation");
+        let invalid = MailError::InvalidOption {
+            option: "schedule_at",
+            message: "not a date".into(),
+        };
+        assert_eq!(invalid.kind(), "validation");
+        assert_eq!(MailError::HttpError("reset".into()).kind(), "network");
+        assert_eq!(MailError::provider("resend", "oops").kind(), "provider");
+        assert_eq!(
+            MailError::provider_with_status("resend", "slow down", 429).kind(),
+            "rate_limited"
+        );
+        assert_eq!(
+            MailError::provider_with_status("resend", "bad", 422).kind(),
+            "provider_4xx"
+        );
+        assert_eq!(
+            MailError::provider_with_status("resend", "down", 503).kind(),
+            "provider_5xx"
+        );
+        assert_eq!(
+            MailError::provider_with_status("resend", "down", 503).status_code(),
+            Some(503)
+        );
+    }
+
+    #[test]
+    fn test_retryable() {
+        assert!(MailError::HttpError("reset".into()).is_retryable());
+        assert!(MailError::provider_with_status("resend", "slow down", 429).is_retryable());
+        assert!(MailError::provider_with_status("resend", "down", 502).is_retryable());
+        assert!(!MailError::provider_with_status("resend", "bad", 400).is_retryable());
+        assert!(!MailError::MissingField("to").is_retryable());
+
+        let err = MailError::provider_with_status("postmark", "busy", 503)
+            .with_code("405")
+            .with_retry_after(Duration::from_secs(30));
+        assert_eq!(err.provider_error_code(), Some("405"));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        let later = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+        let delay = parse_retry_after(&later).unwrap();
+        assert!(delay > Duration::from_secs(80) && delay <= Duration::from_secs(90));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
@@ -0,0 +1,187 @@
+//! Custom header validation and folding.
+//!
+//! Header names are RFC 5322 field names: printable ASCII without spaces or
+//! colons. Values may not contain control characters other than tab; a CR or
+//! LF would end the header early and let the rest of the value inject new
+//! headers or a body.
+
+use std::collections::HashMap;
+
+use crate::error::MailError;
+
+/// Preferred maximum line length (RFC 5322 section 2.1.1).
+// Only the raw MIME builder folds headers.
+#[cfg_attr(
+    not(any(
+        feature = "amazon_ses",
+        feature = "preview",
+        feature = "preview-axum",
+        feature = "preview-actix",
+        feature = "preview-rocket"
+    )),
+    allow(dead_code)
+)]
+const MAX_LINE_LENGTH: usize = 78;
+
+/// Check a header name and value.
+pub(crate) fn validate(name: &str, value: &str) -> Result<(), MailError> {
+    if name.is_empty() {
+        return Err(MailError::InvalidHeader("empty header name".into()));
+    }
+    if let Some(c) = name.chars().find(|&c| !c.is_ascii_graphic() || c == ':') {
+        return Err(MailError::InvalidHeader(format!(
+            "'{}': name contains {:?}",
+            name, c
+        )));
+    }
+    if let Some(c) = value.chars().find(|&c| c.is_control() && c != '\t') {
+        return Err(MailError::InvalidHeader(format!(
+            "'{}': value contains {:?}",
+            name, c
+        )));
+    }
+    Ok(())
+}
+
+/// Check a pre-formatted header field such as `ARC-Seal: i=1; ...`.
+///
+/// The field may be folded, but only with CRLF followed by a space or tab;
+/// anything else could end the header early. It must not end in CRLF.
+pub(crate) fn validate_raw(field: &str) -> Result<(), MailError> {
+    let Some((name, value)) = field.split_once(':') else {
+        return Err(MailError::InvalidHeader(format!(
+            "'{}': pre-formatted header has no colon",
+            field
+        )));
+    };
+    let mut lines = value.split("\r\n");
+    let first = lines.next().unwrap_or_default();
+    validate(name, first)?;
+    for line in lines {
+        if !line.starts_with([' ', '\t']) {
+            return Err(MailError::InvalidHeader(format!(
+                "'{}': folded line must start with a space or tab",
+                name
+            )));
+        }
+        validate(name, line)?;
+    }
+    Ok(())
+}
+
+/// Check every header in a map.
+pub(crate) fn validate_all(headers: &HashMap<String, String>) -> Result<(), MailError> {
+    headers
+        .iter()
+        .try_for_each(|(name, value)| validate(name, value))
+}
+
+/// Join pre-formatted header fields into a block with a CRLF after each.
+#[cfg_attr(
+    not(any(
+        feature = "smtp",
+        feature = "amazon_ses",
+        feature = "mailgun",
+        feature = "preview",
+        feature = "preview-axum",
+        feature = "preview-actix",
+        feature = "preview-rocket"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn raw_block(fields: &[String]) -> String {
+    fields.iter().map(|field| format!("{}\r\n", field)).collect()
+}
+
+/// Format `Name: value`, folding at spaces so lines stay within 78
+/// characters where possible. Continuation lines start with a space, so
+/// unfolding restores the original value. No trailing CRLF is added.
+#[cfg_attr(
+    not(any(
+        feature = "amazon_ses",
+        feature = "preview",
+        feature = "preview-axum",
+        feature = "preview-actix",
+        feature = "preview-rocket"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn fold(name: &str, value: &str) -> String {
+    let mut out = String::with_capacity(name.len() + value.len() + 8);
+    out.push_str(name);
+    out.push(':');
+    let mut line_length = out.len();
+    let mut line_has_word = false;
+
+    for word in value.split(' ') {
+        if line_has_word && !word.is_empty() && line_length + 1 + word.len() > MAX_LINE_LENGTH {
+            out.push_str("\r\n");
+            line_length = 0;
+        }
+        out.push(' ');
+        out.push_str(word);
+        line_length += 1 + word.len();
+        line_has_word |= !word.is_empty();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate("X-Campaign", "spring").is_ok());
+        assert!(validate("", "value").is_err());
+        assert!(validate("X Campaign", "value").is_err());
+        assert!(validate("X-Campaign:", "value").is_err());
+        assert!(validate("X-Cämpaign", "value").is_err());
+    }
+
+    #[test]
+    fn test_validate_value() {
+        assert!(validate("X-Note", "tab\tseparated, ünïcode").is_ok());
+        assert!(validate("X-Note", "a\r\nBcc: victim@example.com").is_err());
+        assert!(validate("X-Note", "a\nb").is_err());
+        assert!(validate("X-Note", "nul\0").is_err());
+
+        let err = validate("X-Note", "a\rb").unwrap_err();
+        assert!(matches!(err, MailError::InvalidHeader(_)));
+        assert!(err.to_string().contains("X-Note"));
+    }
+
+    #[test]
+    fn test_validate_raw() {
+        assert!(validate_raw("ARC-Seal: i=1; a=rsa-sha256;\r\n\tb=abc").is_ok());
+        assert!(validate_raw("DKIM-Signature:v=1;\r\n d=example.com").is_ok());
+        assert!(validate_raw("no colon").is_err());
+        assert!(validate_raw("ARC-Seal: i=1;\r\nBcc: eve@example.com").is_err());
+        assert!(validate_raw("ARC-Seal: i=1;\r\n").is_err());
+        assert!(validate_raw("ARC-Seal: i=1;\n b=abc").is_err());
+    }
+
+    #[test]
+    fn test_fold_short() {
+        assert_eq!(fold("X-Short", "value"), "X-Short: value");
+    }
+
+    #[test]
+    fn test_fold_long() {
+        let value = vec!["word"; 40].join(" ");
+        let folded = fold("X-Long", &value);
+
+        assert!(folded.split("\r\n").all(|line| line.len() <= 78));
+        assert!(folded
+            .split("\r\n")
+            .skip(1)
+            .all(|line| line.starts_with(' ')));
+        assert_eq!(folded.replace("\r\n", ""), format!("X-Long: {}", value));
+    }
+
+    #[test]
+    fn test_fold_unbreakable() {
+        let value = "x".repeat(100);
+        assert_eq!(fold("X-Token", &value), format!("X-Token: {}", value));
+    }
+}
@@ -0,0 +1,59 @@
+//! Reloading the global mailer when its configuration changes.
+
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::config;
+
+/// Handle to a background [`watch()`], which stops when dropped.
+#[must_use = "the watcher stops when dropped"]
+pub struct ConfigWatcher {
+    // Dropping the sender disconnects the channel and ends the thread
+    _stop: Sender<()>,
+}
+
+impl ConfigWatcher {
+    /// Stop watching.
+    pub fn stop(self) {}
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher").finish_non_exhaustive()
+    }
+}
+
+/// Check the configuration every `interval` and [`reload()`](crate::reload)
+/// the global mailer when it changes.
+///
+/// Watches the environment variables missive reads (`EMAIL_*`, `SMTP_*`,
+/// `RESEND_*`, ...) and the autoloaded `missive.toml`. Failed reloads are
+/// logged and leave the current mailer in place; the next change is tried
+/// again. Runs on its own thread, so it needs no async runtime.
+///
+/// ```rust,ignore
+/// let _watcher = missive::watch(Duration::from_secs(30));
+/// ```
+pub fn watch(interval: Duration) -> ConfigWatcher {
+    let (stop, stopped) = mpsc::channel::<()>();
+    let mut last = config::fingerprint();
+
+    thread::Builder::new()
+        .name("missive-watch".into())
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let current = config::fingerprint();
+                if current == last {
+                    continue;
+                }
+                last = current;
+                if let Err(e) = crate::reload() {
+                    tracing::warn!(error = %e, "Email configuration changed but failed to reload");
+                }
+            }
+        })
+        .expect("failed to spawn missive-watch thread");
+
+    ConfigWatcher { _stop: stop }
+}
@@ -0,0 +1,236 @@
+//! HTTP client settings for API providers.
+//!
+//! Every API provider builds its `reqwest` client from an [`HttpConfig`]:
+//! the one passed to the mailer's `http_config()` builder, otherwise the one
+//! installed with [`HttpConfig::install`], otherwise [`HttpConfig::from_env`].
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use missive::http::{HttpConfig, Proxy};
+//!
+//! // Every API mailer created from now on
+//! HttpConfig::new()
+//!     .timeout(Duration::from_secs(10))
+//!     .proxy(Proxy::https("http://proxy.internal:3128")?)
+//!     .install();
+//!
+//! // A single mailer
+//! let mailer = ResendMailer::new(api_key)
+//!     .http_config(&HttpConfig::new().timeout(Duration::from_secs(5)));
+//! ```
+//!
+//! | Variable | Description |
+//! |----------|-------------|
+//! | `MISSIVE_HTTP_TIMEOUT` | Request timeout in seconds (default: 30) |
+//! | `MISSIVE_HTTP_CONNECT_TIMEOUT` | Connect timeout in seconds (default: 10) |
+//! | `MISSIVE_HTTP_PROXY` | Proxy URL for all provider requests |
+//! | `MISSIVE_PROXY` | Proxy URL shared with SMTP, used if `MISSIVE_HTTP_PROXY` is unset |
+//!
+//! Without an explicit proxy, reqwest honors `HTTPS_PROXY`, `HTTP_PROXY`,
+//! `ALL_PROXY` and `NO_PROXY`. With the `socks` feature, `socks5://` and
+//! `socks5h://` proxy URLs work too.
+//!
+//! On `wasm32` (the `wasm` feature), requests go through the runtime's
+//! `fetch`: proxies and root certificates are unavailable, and timeouts are
+//! left to the host.
+
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use reqwest::Client;
+
+use crate::error::MailError;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::{Certificate, Proxy};
+
+/// Default total request timeout.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default connect timeout.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Installed configuration, used by mailers created without one.
+static HTTP_CONFIG: RwLock<Option<HttpConfig>> = RwLock::new(None);
+
+/// Timeouts, proxy and TLS roots for provider HTTP clients.
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<Proxy>,
+    #[cfg(not(target_arch = "wasm32"))]
+    root_certificates: Vec<Certificate>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            root_certificates: Vec::new(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Default settings: 30s request timeout, 10s connect timeout, no proxy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `MISSIVE_HTTP_TIMEOUT`, `MISSIVE_HTTP_CONNECT_TIMEOUT` and
+    /// `MISSIVE_HTTP_PROXY` (or `MISSIVE_PROXY`), using the defaults for
+    /// anything unset.
+    ///
+    /// A timeout of `0` disables it. An invalid proxy URL is logged and ignored.
+    pub fn from_env() -> Self {
+        let secs = |key: &str| {
+            crate::config::var(key)
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(|secs| (secs > 0).then(|| Duration::from_secs(secs)))
+        };
+        let mut config = Self::default();
+        if let Some(timeout) = secs("MISSIVE_HTTP_TIMEOUT") {
+            config.timeout = timeout;
+        }
+        if let Some(timeout) = secs("MISSIVE_HTTP_CONNECT_TIMEOUT") {
+            config.connect_timeout = timeout;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let proxy = crate::config::var("MISSIVE_HTTP_PROXY")
+            .or_else(|| crate::config::var("MISSIVE_PROXY"));
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(url) = proxy {
+            match Proxy::all(&url) {
+                Ok(proxy) => config.proxy = Some(proxy),
+                Err(e) => tracing::warn!(error = %e, url = %url, "Ignoring invalid proxy URL"),
+            }
+        }
+        config
+    }
+
+    /// The installed configuration, or [`from_env`](Self::from_env) if none.
+    pub fn global() -> Self {
+        HTTP_CONFIG.read().clone().unwrap_or_else(Self::from_env)
+    }
+
+    /// Use this configuration for API mailers created after this call.
+    ///
+    /// Mailers that already exist keep their client.
+    pub fn install(self) {
+        *HTTP_CONFIG.write() = Some(self);
+    }
+
+    /// Remove the installed configuration, falling back to the environment.
+    pub fn uninstall() {
+        *HTTP_CONFIG.write() = None;
+    }
+
+    /// Total time allowed for a request, from connecting to reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Time allowed to establish a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Wait indefinitely for requests (not recommended).
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self.connect_timeout = None;
+        self
+    }
+
+    /// Send requests through a proxy, e.g. `Proxy::all("http://proxy:3128")?`.
+    ///
+    /// SOCKS5 URLs (`socks5://`, `socks5h://`) need the `socks` feature.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Trust an additional root certificate, e.g. a corporate CA.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn root_certificate(mut self, cert: Certificate) -> Self {
+        self.root_certificates.push(cert);
+        self
+    }
+
+    /// Build a `reqwest` client with these settings.
+    pub fn build_client(&self) -> Result<Client, MailError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = self.native_builder();
+        #[cfg(target_arch = "wasm32")]
+        let builder = Client::builder();
+        builder
+            .build()
+            .map_err(|e| MailError::Configuration(format!("Failed to build HTTP client: {}", e)))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn native_builder(&self) -> reqwest::ClientBuilder {
+        let mut builder = Client::builder();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for cert in &self.root_certificates {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        builder
+    }
+
+    /// Build a client, falling back to reqwest's defaults if that fails.
+    pub(crate) fn client(&self) -> Client {
+        self.build_client().unwrap_or_else(|e| {
+            tracing::error!(error = %e, "Using default HTTP client");
+            Client::new()
+        })
+    }
+}
+
+/// Client for a mailer created without an explicit configuration.
+pub(crate) fn default_client() -> Client {
+    HttpConfig::global().client()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let config = HttpConfig::new();
+        assert_eq!(config.timeout, Some(DEFAULT_TIMEOUT));
+        assert_eq!(config.connect_timeout, Some(DEFAULT_CONNECT_TIMEOUT));
+        assert!(config.build_client().is_ok());
+
+        let config = config.no_timeout();
+        assert_eq!(config.timeout, None);
+        assert_eq!(config.connect_timeout, None);
+    }
+
+    #[test]
+    fn test_proxy() {
+        let config = HttpConfig::new()
+            .proxy(Proxy::all("http://127.0.0.1:3128").unwrap())
+            .timeout(Duration::from_secs(5));
+        assert_eq!(config.timeout, Some(Duration::from_secs(5)));
+        assert!(config.build_client().is_ok());
+    }
+}
@@ -0,0 +1,82 @@
+//! Send quota and statistics from the provider.
+//!
+//! Schedulers can read the remaining quota before a large send and throttle
+//! instead of hitting the provider's limit mid-campaign:
+//!
+//! ```rust,ignore
+//! use missive::insights::Insights;
+//! use missive::providers::AmazonSesMailer;
+//!
+//! let mailer = AmazonSesMailer::new(region, access_key, secret);
+//!
+//! let quota = mailer.send_quota().await?;
+//! if quota.remaining().is_some_and(|left| left < batch.len() as u64) {
+//!     // wait for the window to roll over
+//! }
+//!
+//! for period in mailer.send_statistics(Utc::now() - Duration::days(1)).await? {
+//!     println!("{}: {} sent, {} bounced", period.start, period.attempts, period.bounces);
+//! }
+//! ```
+//!
+//! ## Providers
+//!
+//! | Provider | Feature Flags | Quota | Statistics |
+//! |----------|---------------|-------|------------|
+//! | [`AmazonSesMailer`](crate::providers::AmazonSesMailer) | `insights`, `amazon_ses` | GetSendQuota (rolling 24 hours) | GetSendStatistics (15-minute periods, last two weeks) |
+//! | [`SendGridMailer`](crate::providers::SendGridMailer) | `insights`, `sendgrid` | `GET /user/credits` (plan credit period) | `GET /stats` (daily) |
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::MailError;
+
+/// How much the account may still send.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SendQuota {
+    /// Emails allowed in the quota period, or `None` if unlimited.
+    pub limit: Option<u64>,
+    /// Emails sent in the current quota period.
+    pub sent: u64,
+    /// Maximum emails per second, if the provider enforces one.
+    pub max_send_rate: Option<f64>,
+    /// When the quota period resets, if the provider reports it.
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+impl SendQuota {
+    /// Emails left in the current period, or `None` if unlimited.
+    pub fn remaining(&self) -> Option<u64> {
+        self.limit.map(|limit| limit.saturating_sub(self.sent))
+    }
+}
+
+/// Delivery counts for one reporting period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendStatistics {
+    /// Start of the period.
+    pub start: DateTime<Utc>,
+    /// Emails the provider attempted to deliver.
+    pub attempts: u64,
+    /// Emails accepted by the recipient server, if reported.
+    pub delivered: Option<u64>,
+    /// Hard bounces.
+    pub bounces: u64,
+    /// Spam complaints.
+    pub complaints: u64,
+    /// Emails the provider refused to send.
+    pub rejects: u64,
+}
+
+/// Providers that report send quotas and statistics.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Insights: Send + Sync {
+    /// The account's current send quota.
+    async fn send_quota(&self) -> Result<SendQuota, MailError>;
+
+    /// Statistics for periods starting at or after `since`, oldest first.
+    async fn send_statistics(&self, since: DateTime<Utc>)
+        -> Result<Vec<SendStatistics>, MailError>;
+}
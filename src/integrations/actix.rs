@@ -0,0 +1,72 @@
+//! Actix-web integration.
+//!
+//! ```rust,ignore
+//! use actix_web::{web, App, HttpResponse};
+//! use missive::integrations::actix::MailerData;
+//! use missive::providers::ResendMailer;
+//!
+//! let app = App::new()
+//!     .app_data(MailerData::new(ResendMailer::new(api_key)))
+//!     .route("/signup", web::post().to(signup));
+//!
+//! async fn signup(mailer: MailerData) -> actix_web::Result<HttpResponse> {
+//!     mailer.deliver(&welcome_email()).await.map_err(actix_web::error::ErrorBadGateway)?;
+//!     Ok(HttpResponse::Ok().finish())
+//! }
+//! ```
+
+use std::future::{ready, Ready};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use ::actix_web::dev::Payload;
+use ::actix_web::{error, FromRequest, HttpRequest};
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{DeliveryResult, Mailer};
+
+/// A mailer registered as actix-web app data.
+///
+/// Register it with `App::app_data(...)`; handlers take it as an extractor.
+/// Extraction fails with `500 Internal Server Error` if it isn't registered.
+#[derive(Clone)]
+pub struct MailerData(pub Arc<dyn Mailer>);
+
+impl MailerData {
+    /// Wrap a mailer.
+    pub fn new(mailer: impl Mailer + 'static) -> Self {
+        Self(Arc::new(mailer))
+    }
+
+    /// Wrap a shared mailer, e.g. [`missive::mailer()`](crate::mailer).
+    pub fn from_arc(mailer: Arc<dyn Mailer>) -> Self {
+        Self(mailer)
+    }
+
+    /// Deliver through this mailer with [`deliver_with`](crate::deliver_with).
+    pub async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        crate::deliver_with(email, &self.0).await
+    }
+}
+
+impl Deref for MailerData {
+    type Target = dyn Mailer;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl FromRequest for MailerData {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            req.app_data::<MailerData>()
+                .cloned()
+                .ok_or_else(|| error::ErrorInternalServerError("MailerData is not registered")),
+        )
+    }
+}
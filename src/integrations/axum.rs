@@ -0,0 +1,81 @@
+//! Axum integration.
+//!
+//! ```rust,ignore
+//! use axum::{routing::post, Router};
+//! use missive::integrations::axum::MailerExtension;
+//! use missive::providers::ResendMailer;
+//!
+//! let app = Router::new()
+//!     .route("/signup", post(signup))
+//!     .layer(MailerExtension::new(ResendMailer::new(api_key)));
+//!
+//! async fn signup(mailer: MailerExtension) -> Result<(), AppError> {
+//!     mailer.deliver(&welcome_email()).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use ::axum::extract::FromRequestParts;
+use ::axum::http::request::Parts;
+use ::axum::http::StatusCode;
+use ::axum::middleware::AddExtension;
+use ::axum::Extension;
+use tower_layer::Layer;
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{DeliveryResult, Mailer};
+
+/// A mailer carried in request extensions.
+///
+/// Add it to a router with `.layer(...)`; handlers take it as an extractor.
+/// Extraction fails with `500 Internal Server Error` if the layer is missing.
+#[derive(Clone)]
+pub struct MailerExtension(pub Arc<dyn Mailer>);
+
+impl MailerExtension {
+    /// Wrap a mailer.
+    pub fn new(mailer: impl Mailer + 'static) -> Self {
+        Self(Arc::new(mailer))
+    }
+
+    /// Wrap a shared mailer, e.g. [`missive::mailer()`](crate::mailer).
+    pub fn from_arc(mailer: Arc<dyn Mailer>) -> Self {
+        Self(mailer)
+    }
+
+    /// Deliver through this mailer with [`deliver_with`](crate::deliver_with).
+    pub async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        crate::deliver_with(email, &self.0).await
+    }
+}
+
+impl Deref for MailerExtension {
+    type Target = dyn Mailer;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl<S> Layer<S> for MailerExtension {
+    type Service = AddExtension<S, MailerExtension>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Extension(self.clone()).layer(inner)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for MailerExtension {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<MailerExtension>().cloned().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "MailerExtension layer is missing",
+        ))
+    }
+}
@@ -0,0 +1,19 @@
+//! Web framework integrations.
+//!
+//! Hand a mailer to request handlers through the framework's own state
+//! instead of the global mailer, so tests can give each app instance its own
+//! [`LocalMailer`](crate::providers::LocalMailer).
+//!
+//! | Module | Feature | Type |
+//! |--------|---------|------|
+//! | [`axum`] | `axum` | [`MailerExtension`](axum::MailerExtension): tower layer and extractor |
+//! | [`actix`] | `actix` | [`MailerData`](actix::MailerData): app data and extractor |
+//!
+//! Both deref to [`Mailer`](crate::Mailer), and their `deliver()` runs the
+//! same pipeline as [`deliver_with`](crate::deliver_with) (default sender,
+//! interceptors, validation, telemetry).
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
@@ -20,7 +20,7 @@ use async_trait::async_trait;
 
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
 
 /// A trait for intercepting and transforming emails before delivery.
 ///
@@ -88,7 +88,8 @@ impl<M, I> WithInterceptor<M, I> {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl<M, I> Mailer for WithInterceptor<M, I>
 where
     M: Mailer,
@@ -111,13 +112,29 @@ where
         self.inner.validate_batch(emails)
     }
 
+    fn batch_concurrency(&self) -> usize {
+        self.inner.batch_concurrency()
+    }
+
     fn provider_name(&self) -> &'static str {
         self.inner.provider_name()
     }
 
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
     fn validate_config(&self) -> Result<(), MailError> {
         self.inner.validate_config()
     }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.inner.health_check().await
+    }
+
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        self.inner.message_status(message_id).await
+    }
 }
 
 /// Extension trait for adding interceptors to any mailer.
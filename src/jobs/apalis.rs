@@ -0,0 +1,33 @@
+//! [apalis](https://docs.rs/apalis) integration.
+//!
+//! ```rust,ignore
+//! use apalis::prelude::*;
+//! use missive::jobs::{self, SendEmailJob};
+//!
+//! let worker = WorkerBuilder::new("missive-email")
+//!     .backend(storage.clone())
+//!     .build_fn(jobs::apalis::send_email);
+//!
+//! storage.push(SendEmailJob::new(email)).await?;
+//! ```
+
+use std::sync::Arc;
+
+use apalis_core::error::Error;
+
+use super::SendEmailJob;
+use crate::mailer::DeliveryResult;
+
+/// Deliver a queued email.
+///
+/// Retryable failures return [`Error::Failed`] so apalis retries them;
+/// permanent ones return [`Error::Abort`].
+pub async fn send_email(job: SendEmailJob) -> Result<DeliveryResult, Error> {
+    job.run().await.map_err(|e| {
+        if e.is_retryable() {
+            Error::Failed(Arc::new(Box::new(e)))
+        } else {
+            Error::Abort(Arc::new(Box::new(e)))
+        }
+    })
+}
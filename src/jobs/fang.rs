@@ -0,0 +1,38 @@
+//! [fang](https://docs.rs/fang) integration.
+//!
+//! [`SendEmailJob`] implements `AsyncRunnable`, registered with typetag as
+//! `missive::SendEmailJob`:
+//!
+//! ```rust,ignore
+//! use fang::asynk::async_queue::AsyncQueueable;
+//! use missive::jobs::SendEmailJob;
+//!
+//! queue.insert_task(&SendEmailJob::new(email)).await?;
+//! ```
+
+use ::fang::asynk::async_queue::AsyncQueueable;
+use ::fang::typetag;
+use ::fang::{AsyncRunnable, FangError};
+use async_trait::async_trait;
+
+use super::SendEmailJob;
+
+/// Task type (and worker pool) for email jobs.
+pub const TASK_TYPE: &str = "missive_email";
+
+#[typetag::serde(name = "missive::SendEmailJob")]
+#[async_trait]
+impl AsyncRunnable for SendEmailJob {
+    async fn run(&self, _queue: &dyn AsyncQueueable) -> Result<(), FangError> {
+        SendEmailJob::run(self)
+            .await
+            .map(|_| ())
+            .map_err(|e| FangError {
+                description: e.to_string(),
+            })
+    }
+
+    fn task_type(&self) -> String {
+        TASK_TYPE.to_string()
+    }
+}
@@ -0,0 +1,86 @@
+//! Durable email jobs for background job queues.
+//!
+//! [`SendEmailJob`] wraps an [`Email`] (and optionally a named mailer) in a
+//! serde type that can be stored in any job queue. A worker calls
+//! [`SendEmailJob::run`], which delivers through the global mailer:
+//!
+//! ```rust,ignore
+//! use missive::jobs::SendEmailJob;
+//!
+//! // Producer
+//! let payload = serde_json::to_string(&SendEmailJob::new(email))?;
+//!
+//! // Worker
+//! let job: SendEmailJob = serde_json::from_str(&payload)?;
+//! job.run().await?;
+//! ```
+//!
+//! ## Queue Integrations
+//!
+//! | Module | Feature | Notes |
+//! |--------|---------|-------|
+//! | [`apalis`] | `apalis` | `send_email` handler for `build_fn`; permanent failures abort instead of retrying |
+//! | [`fang`] | `fang` | `SendEmailJob` implements `AsyncRunnable` (Postgres) |
+//! | [`redis`] | `redis` | `RedisQueue` with its own workers, processing list and dead list |
+//! | [`sqlxmq`] | `sqlxmq` | `send_email` job for a `JobRegistry` |
+//!
+//! In every integration, errors that [`MailError::is_retryable`] rejects
+//! (invalid addresses, rejected credentials, ...) are not worth retrying; the
+//! queues that support it stop there.
+
+use serde::{Deserialize, Serialize};
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::DeliveryResult;
+
+#[cfg(feature = "apalis")]
+pub mod apalis;
+#[cfg(feature = "fang")]
+pub mod fang;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "sqlxmq")]
+pub mod sqlxmq;
+
+/// A queued email delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SendEmailJob {
+    /// The email to send.
+    pub email: Email,
+    /// Named mailer to send with (see [`deliver_via`](crate::deliver_via)).
+    /// The global mailer is used when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mailer: Option<String>,
+}
+
+impl SendEmailJob {
+    /// Send `email` with the global mailer.
+    pub fn new(email: Email) -> Self {
+        Self {
+            email,
+            mailer: None,
+        }
+    }
+
+    /// Send with a named mailer instead of the global one.
+    pub fn via(mut self, mailer: impl Into<String>) -> Self {
+        self.mailer = Some(mailer.into());
+        self
+    }
+
+    /// Deliver the email.
+    pub async fn run(&self) -> Result<DeliveryResult, MailError> {
+        match &self.mailer {
+            Some(name) => crate::deliver_via(name, &self.email).await,
+            None => crate::deliver(&self.email).await,
+        }
+    }
+}
+
+impl From<Email> for SendEmailJob {
+    fn from(email: Email) -> Self {
+        Self::new(email)
+    }
+}
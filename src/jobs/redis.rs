@@ -0,0 +1,239 @@
+//! Redis-backed queue for [`SendEmailJob`]s.
+//!
+//! A durable queue that several app instances can share without a separate
+//! job framework:
+//!
+//! ```rust,ignore
+//! use missive::jobs::{redis::RedisQueue, SendEmailJob};
+//!
+//! let queue = RedisQueue::connect("redis://127.0.0.1/").await?;
+//!
+//! // Producer
+//! queue.push(SendEmailJob::new(email)).await?;
+//!
+//! // Worker (any number, in any process)
+//! queue.recover().await?;
+//! queue.run().await?;
+//! ```
+//!
+//! Jobs wait in the `<name>` list and move atomically to `<name>:processing`
+//! while a worker delivers them, so a crashed worker's job isn't lost:
+//! [`recover()`](RedisQueue::recover) puts it back. Retryable failures go to
+//! the back of the queue until [`max_attempts`](RedisQueue::max_attempts);
+//! permanent failures and jobs out of attempts end up in `<name>:dead`.
+
+use std::time::Duration;
+
+use ::redis::aio::MultiplexedConnection;
+use ::redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+use super::SendEmailJob;
+use crate::error::MailError;
+
+/// Queue name unless [`RedisQueue::name`] is set.
+const DEFAULT_NAME: &str = "missive:jobs";
+
+/// A job in the queue, with the deliveries tried so far.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    job: SendEmailJob,
+    #[serde(default)]
+    attempts: u32,
+}
+
+/// A durable [`SendEmailJob`] queue in Redis.
+#[derive(Clone)]
+pub struct RedisQueue {
+    connection: MultiplexedConnection,
+    name: String,
+    max_attempts: u32,
+    poll_interval: Duration,
+    retry_delay: Duration,
+}
+
+impl RedisQueue {
+    /// Connect to Redis at `url` (e.g. `redis://127.0.0.1/`).
+    pub async fn connect(url: &str) -> Result<Self, MailError> {
+        let client = ::redis::Client::open(url)
+            .map_err(|e| MailError::Configuration(format!("Redis: {}", e)))?;
+        let connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(redis_error)?;
+        Ok(Self {
+            connection,
+            name: DEFAULT_NAME.to_string(),
+            max_attempts: 5,
+            poll_interval: Duration::from_secs(1),
+            retry_delay: Duration::from_secs(5),
+        })
+    }
+
+    /// Name of the queue's Redis list (default: `missive:jobs`).
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Deliveries to try before a retryable job goes to the dead list (default: 5).
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// How long [`run()`](Self::run) waits when the queue is empty (default: 1s).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// How long [`run()`](Self::run) pauses after a retryable failure, so an
+    /// outage isn't hammered (default: 5s).
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    fn processing_key(&self) -> String {
+        format!("{}:processing", self.name)
+    }
+
+    fn dead_key(&self) -> String {
+        format!("{}:dead", self.name)
+    }
+
+    /// Add a job to the back of the queue.
+    pub async fn push(&self, job: impl Into<SendEmailJob>) -> Result<(), MailError> {
+        let entry = Entry {
+            job: job.into(),
+            attempts: 0,
+        };
+        let json = serde_json::to_string(&entry)?;
+        let mut conn = self.connection.clone();
+        conn.lpush::<_, _, ()>(&self.name, json)
+            .await
+            .map_err(redis_error)
+    }
+
+    /// Jobs waiting to be delivered.
+    pub async fn len(&self) -> Result<usize, MailError> {
+        let mut conn = self.connection.clone();
+        conn.llen(&self.name).await.map_err(redis_error)
+    }
+
+    /// Whether no jobs are waiting.
+    pub async fn is_empty(&self) -> Result<bool, MailError> {
+        Ok(self.len().await? == 0)
+    }
+
+    /// Jobs that failed permanently or ran out of attempts, oldest first.
+    pub async fn dead(&self) -> Result<Vec<SendEmailJob>, MailError> {
+        let mut conn = self.connection.clone();
+        let entries: Vec<String> = conn
+            .lrange(self.dead_key(), 0, -1)
+            .await
+            .map_err(redis_error)?;
+        entries
+            .iter()
+            .rev()
+            .map(|json| Ok(serde_json::from_str::<Entry>(json)?.job))
+            .collect()
+    }
+
+    /// Move jobs left in processing by a crashed worker back to the queue.
+    ///
+    /// Call it at startup, before any worker in the deployment is running,
+    /// or jobs being delivered right now will be sent twice. Returns the
+    /// number of jobs moved.
+    pub async fn recover(&self) -> Result<usize, MailError> {
+        let mut conn = self.connection.clone();
+        let mut moved = 0;
+        while conn
+            .rpoplpush::<_, _, Option<String>>(self.processing_key(), &self.name)
+            .await
+            .map_err(redis_error)?
+            .is_some()
+        {
+            moved += 1;
+        }
+        Ok(moved)
+    }
+
+    /// Deliver the next job, if there is one.
+    ///
+    /// Returns `Ok(None)` when the queue is empty, otherwise the delivery
+    /// outcome after the job was acknowledged, re-queued or moved to the
+    /// dead list. `Err` means Redis itself failed.
+    pub async fn process_next(&self) -> Result<Option<Result<(), MailError>>, MailError> {
+        let mut conn = self.connection.clone();
+        let Some(raw): Option<String> = conn
+            .rpoplpush(&self.name, self.processing_key())
+            .await
+            .map_err(redis_error)?
+        else {
+            return Ok(None);
+        };
+
+        let mut entry: Entry = match serde_json::from_str(&raw) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!(error = %e, queue = %self.name, "Moving unreadable job to dead list");
+                self.finish(&raw, Some((self.dead_key(), raw.clone())))
+                    .await?;
+                return Ok(Some(Err(e.into())));
+            }
+        };
+
+        let result = entry.job.run().await.map(|_| ());
+        let requeue = match &result {
+            Ok(()) => None,
+            Err(e) => {
+                entry.attempts += 1;
+                let target = if e.is_retryable() && entry.attempts < self.max_attempts {
+                    self.name.clone()
+                } else {
+                    tracing::warn!(
+                        error = %e,
+                        attempts = entry.attempts,
+                        queue = %self.name,
+                        "Moving failed email job to dead list"
+                    );
+                    self.dead_key()
+                };
+                Some((target, serde_json::to_string(&entry)?))
+            }
+        };
+        self.finish(&raw, requeue).await?;
+        Ok(Some(result))
+    }
+
+    /// Remove `raw` from processing, pushing `next` onto a list in the same transaction.
+    async fn finish(&self, raw: &str, next: Option<(String, String)>) -> Result<(), MailError> {
+        let mut conn = self.connection.clone();
+        let mut pipe = ::redis::pipe();
+        pipe.atomic().lrem(self.processing_key(), 1, raw).ignore();
+        if let Some((key, json)) = next {
+            pipe.lpush(key, json).ignore();
+        }
+        pipe.query_async::<()>(&mut conn).await.map_err(redis_error)
+    }
+
+    /// Deliver jobs until Redis fails.
+    ///
+    /// Delivery failures are handled per job (see the module docs); only a
+    /// Redis error ends the loop.
+    pub async fn run(&self) -> Result<(), MailError> {
+        loop {
+            match self.process_next().await? {
+                None => tokio::time::sleep(self.poll_interval).await,
+                Some(Err(e)) if e.is_retryable() => tokio::time::sleep(self.retry_delay).await,
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+fn redis_error(e: ::redis::RedisError) -> MailError {
+    MailError::SendError(format!("Redis: {}", e))
+}
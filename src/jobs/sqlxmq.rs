@@ -0,0 +1,44 @@
+//! [sqlxmq](https://docs.rs/sqlxmq) integration.
+//!
+//! ```rust,ignore
+//! use missive::jobs::{sqlxmq::send_email, SendEmailJob};
+//! use sqlxmq::JobRegistry;
+//!
+//! let registry = JobRegistry::new(&[send_email]);
+//! let runner = registry.runner(&pool).run().await?;
+//!
+//! send_email
+//!     .builder()
+//!     .set_json(&SendEmailJob::new(email))?
+//!     .spawn(&pool)
+//!     .await?;
+//! ```
+
+use std::error::Error;
+
+use ::sqlxmq::{job, CurrentJob};
+
+use super::SendEmailJob;
+
+/// Deliver a queued email, registered as `missive::send_email`.
+///
+/// The job completes on success and on permanent failures; retryable
+/// failures are left for sqlxmq to retry.
+#[job("missive::send_email")]
+pub async fn send_email(
+    mut current_job: CurrentJob,
+) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+    let job: SendEmailJob = current_job.json()?.ok_or("missing SendEmailJob payload")?;
+
+    match job.run().await {
+        Ok(_) => {
+            current_job.complete().await?;
+            Ok(())
+        }
+        Err(e) if !e.is_retryable() => {
+            current_job.complete().await?;
+            Err(e.into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -26,6 +26,9 @@
 //!
 //! That's it. No configuration code needed.
 //!
+//! Settings can also come from a [`Config`], e.g. a `missive.toml` profile
+//! with the `toml` feature.
+//!
 //! ## Per-Call Mailer Override
 //!
 //! ```rust,ignore
@@ -36,29 +39,101 @@
 //! deliver_with(&email, &mailer).await?;
 //! ```
 //!
+//! ## Named Mailers
+//!
+//! ```rust,ignore
+//! missive::register("transactional", PostmarkMailer::new(key));
+//! missive::deliver_via("transactional", &email).await?;
+//! ```
+//!
+//! ## Tenants
+//!
+//! ```rust,ignore
+//! missive::tenant::set_provider(Workspaces(pool)); // impl TenantMailerProvider
+//! missive::deliver_for(&workspace_id, &email).await?;
+//! ```
+//!
+//! ## Diagnostics
+//!
+//! [`diagnose()`] checks the whole configuration at once and reports every
+//! missing or invalid setting, rather than the first error from [`init()`].
+//! [`self_test()`] goes further and sends a probe email to
+//! `EMAIL_SELF_TEST_TO` (or `EMAIL_FROM`) through the full delivery path.
+//!
+//! ## Reloading
+//!
+//! The global mailer is built once. Call [`reload()`] after rotating
+//! credentials, or [`watch()`] to reload whenever the settings change.
+//!
 //! ## Environment Variables
 //!
 //! | Variable | Description |
 //! |----------|-------------|
-//! | `EMAIL_PROVIDER` | `smtp`, `resend`, `unsent`, `postmark`, `sendgrid`, `brevo`, `mailgun`, `amazon_ses`, `logger`, `logger_full` |
+//! | `EMAIL_PROVIDER` | `smtp`, `resend`, `unsent`, `postmark`, `sendgrid`, `brevo`, `mailgun`, `amazon_ses`, `zeptomail`, `customerio`, `logger`, `logger_full`, `logger_json` |
+//! | `EMAIL_PROVIDER_PRIORITY` | Providers to prefer when `EMAIL_PROVIDER` is unset and several are configured |
 //! | `EMAIL_FROM` | Default sender email |
 //! | `EMAIL_FROM_NAME` | Default sender name |
+//! | `EMAIL_REPLY_TO` | Default reply-to address(es), comma-separated |
+//! | `EMAIL_HEADERS` | Headers added to every email (`Name: value`, comma-separated) |
+//! | `EMAIL_ALWAYS_BCC` | Comma-separated archive addresses BCC'd on every email |
+//! | `EMAIL_STRICT_VALIDATION` | `true` to run [`Email::validate_strict_for`] on every delivery |
+//! | `EMAIL_MAX_MESSAGE_SIZE` | Maximum estimated message size in bytes (`0` disables; default: provider limit) |
+//! | `EMAIL_LIMIT_POLICY` | `truncate` to shorten over-long subjects and header values instead of failing |
+//! | `EMAIL_ALLOWED_FROM_DOMAINS` | Comma-separated domains emails may be sent from |
+//! | `EMAIL_FROM_DOMAIN_POLICY` | `rewrite` to send other senders from `EMAIL_FROM` instead of failing |
+//! | `EMAIL_RATE_LIMIT` | Maximum emails per second (see [`RateLimited`]) |
+//! | `EMAIL_SANDBOX_ALLOWED_DOMAINS` | Comma-separated recipient domains allowed (see [`Sandbox`]) |
+//! | `EMAIL_SANDBOX_DENIED_DOMAINS` | Comma-separated recipient domains always blocked |
+//! | `EMAIL_SANDBOX_REDIRECT_TO` | Catch-all address for filtered recipients |
+//! | `EMAIL_SELF_TEST_TO` | Recipient of the [`self_test()`] probe (default: `EMAIL_FROM`) |
+//! | `EMAIL_SELF_TEST_SANDBOX` | `true` to ask SendGrid/Mailgun not to deliver the probe (see [`SelfTest`]) |
+//! | `EMAIL_TRACKING_URL` | Base URL for click/open tracking redirects (`tracking` feature, see [`tracking`]) |
+//! | `EMAIL_TRACKING_SECRET` | Secret used to sign tracking tokens |
+//! | `EMAIL_STORAGE_KEY` | Base64 32-byte key for [`EncryptedStorage::from_env`] (`storage-encryption` feature) |
+//! | `EMAIL_AUDIT_LOG` | Append an audit record per delivery to this file (`audit` feature) |
+//! | `EMAIL_AUDIT_RECIPIENTS` | `hashed` to store SHA-256 hashes of recipients in the audit log |
+//! | `EMAIL_LOG_RECIPIENTS` | Recipients in spans and logger output: `full`, `hashed`, `domain`, `off` (see [`privacy`]) |
+//! | `EMAIL_LOG_SUBJECTS` | `false` to keep subjects out of spans and logger output |
 //! | `SMTP_HOST` | SMTP server host |
 //! | `SMTP_PORT` | SMTP server port (default: 587) |
 //! | `SMTP_USERNAME` | SMTP username |
 //! | `SMTP_PASSWORD` | SMTP password |
+//! | `SMTP_TLS` | `starttls` (default), `tls` or `none` (see [`providers::SmtpConfig`]) |
+//! | `SMTP_TIMEOUT` | SMTP command timeout in seconds (default: 60, `0` disables) |
+//! | `SMTP_POOL_SIZE` | Maximum simultaneous SMTP connections in `deliver_many` (default: 8) |
+//! | `PROTON_BRIDGE_USERNAME` | Proton Mail Bridge username (`EMAIL_PROVIDER=protonbridge`) |
+//! | `PROTON_BRIDGE_PASSWORD` | Proton Mail Bridge password (shown in the Bridge app) |
+//! | `PROTON_BRIDGE_HOST` | Bridge host (optional, default: `127.0.0.1`) |
+//! | `PROTON_BRIDGE_PORT` | Bridge SMTP port (optional, default: 1025) |
+//! | `PROTON_BRIDGE_SSL` | `true` if the Bridge's SMTP security is set to SSL (optional) |
 //! | `RESEND_API_KEY` | Resend API key |
 //! | `UNSENT_API_KEY` | Unsent API key |
 //! | `POSTMARK_API_KEY` | Postmark API key |
+//! | `POSTMARK_MESSAGE_STREAM` | Default Postmark message stream (optional) |
 //! | `SENDGRID_API_KEY` | SendGrid API key |
+//! | `SENDGRID_REGION` | `eu` for SendGrid's EU endpoint (optional) |
+//! | `SENDGRID_ON_BEHALF_OF` | SendGrid subuser to send as (optional) |
 //! | `BREVO_API_KEY` | Brevo API key |
 //! | `MAILGUN_API_KEY` | Mailgun API key |
 //! | `MAILGUN_DOMAIN` | Mailgun sending domain |
-//! | `AWS_REGION` | AWS region for SES |
+//! | `AWS_REGION` | AWS region for SES and S3 attachments |
 //! | `AWS_ACCESS_KEY_ID` | AWS access key |
 //! | `AWS_SECRET_ACCESS_KEY` | AWS secret key |
 //! | `MAILTRAP_API_KEY` | Mailtrap API key |
 //! | `MAILTRAP_SANDBOX_INBOX_ID` | Mailtrap sandbox inbox ID (optional) |
+//! | `MAILTRAP_BULK` | Send through Mailtrap's bulk stream (optional) |
+//! | `ZEPTOMAIL_TOKEN` | ZeptoMail Send Mail token |
+//! | `ZEPTOMAIL_BASE_URL` | ZeptoMail API URL for other data centers, e.g. `https://api.zeptomail.eu/v1.1` (optional) |
+//! | `CUSTOMERIO_API_KEY` | Customer.io App API key |
+//! | `CUSTOMERIO_REGION` | `eu` for Customer.io's EU region (optional) |
+//! | `LOGGER_REDACT` | Logger redaction, comma-separated: `bodies`, `recipients` (hashed) |
+//! | `LOGGER_FILE` | Also append logged emails to this file as JSON lines |
+//! | `MISSIVE_DEV_LATENCY_MS` | Delay every `local` and `logger` delivery by this many milliseconds |
+//! | `MISSIVE_DEV_FAIL_RATE` | Fail this fraction (`0.0` to `1.0`) of `local` and `logger` deliveries with a 503 |
+//! | `MISSIVE_HTTP_TIMEOUT` | API provider request timeout in seconds (default: 30, see [`http`]) |
+//! | `MISSIVE_HTTP_CONNECT_TIMEOUT` | API provider connect timeout in seconds (default: 10) |
+//! | `MISSIVE_HTTP_PROXY` | Proxy URL for API provider requests |
+//! | `MISSIVE_PROXY` | Proxy URL for API providers and, if `socks5://`, SMTP (`socks` feature) |
 //!
 //! ## Feature Flags
 //!
@@ -71,9 +146,36 @@
 //! - `mailgun` - Mailgun API provider
 //! - `amazon_ses` - Amazon SES API provider
 //! - `mailtrap` - Mailtrap API provider (testing/staging)
+//! - `zeptomail` - ZeptoMail (Zoho) API provider
+//! - `customerio` - Customer.io Transactional API provider
 //! - `local` - LocalMailer for development and testing
+//! - `snapshots` - `assert_email_snapshot!` for insta snapshot tests
 //! - `preview` - Mailbox preview web UI
+//! - `zip` - Bundle attachments into one ZIP file with [`Attachment::zip`]
+//! - `attachment-s3` - Fetch S3 objects at delivery with `Attachment::from_s3` (see `s3`)
 //! - `metrics` - Prometheus-style metrics (counters/histograms)
+//! - `socks` - SOCKS5 proxies for API providers and SMTP (see [`http`])
+//! - `opentelemetry` - Send `traceparent` with HTTP provider requests
+//! - `audit` - Delivery audit log with JSON-lines and tracing sinks (see [`audit`])
+//! - `tracking` - Signed click/open tracking for self-hosted analytics (see [`tracking`])
+//! - `dns` - SPF/DKIM/DMARC checks for sending domains (see [`dns`])
+//! - `verify` - Disposable domain and role-account detection, plus recipient MX checks with `dns` (see [`verify`])
+//! - `smtp-probe` - Optional SMTP `RCPT TO` probe for recipient checks
+//! - `insights` - Send quota and statistics for SES and SendGrid (see [`insights`])
+//! - `analytics` - Open/click/bounce rates from webhook and tracking events (see `analytics`)
+//! - `analytics-sqlite` - SQLite store for `analytics`
+//! - `smtp-capture` - Dev SMTP listener that stores incoming mail for the preview UI (see [`smtp_capture`])
+//! - `storage-encryption` - [`EncryptedStorage`] encrypts captured emails at rest
+//! - `toml` - Per-environment profiles from `missive.toml` (see [`Config`])
+//! - `secrets-aws`, `secrets-vault` - Credentials from AWS Secrets Manager/SSM or Vault (see `secrets`)
+//! - `derive` - `#[derive(EmailTemplate)]` for a compile-time checked catalog of templated emails (see [`catalog`])
+//! - `cli` - `missive` command-line tool for sending, previewing and checking configuration
+//! - `wasm` - HTTP API providers on `wasm32-unknown-unknown` (Cloudflare Workers, Deno Deploy)
+//! - `blocking` - Synchronous delivery for programs without an async runtime (see [`blocking`])
+//! - `axum`, `actix` - Hand handlers a mailer through framework state (see [`integrations`])
+//! - `apalis`, `fang`, `sqlxmq` - Job queue integrations for [`jobs::SendEmailJob`]
+//! - `redis` - [`RedisStorage`] for shared dev mailboxes and the [`jobs::redis::RedisQueue`] job queue
+//! - `schemars` - JSON Schema for the serialized [`Email`] format
 //! - `dev` - Enables local and preview
 //!
 //! ## Metrics
@@ -86,6 +188,8 @@
 //! | `missive_delivery_duration_seconds` | Histogram | provider | Delivery duration |
 //! | `missive_batch_total` | Counter | provider, status | Total batch operations |
 //! | `missive_batch_size` | Histogram | provider | Emails per batch |
+//! | `missive_errors_total` | Counter | provider, kind, status_code | Failed emails by [`MailError::kind`] and provider HTTP status |
+//! | `missive_reputation_blocked_total` | Counter | domain, reason | Sends blocked by [`ReputationGuard`] |
 //!
 //! Install a recorder (e.g., `metrics-exporter-prometheus`) in your app to collect them.
 
@@ -93,16 +197,79 @@
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 mod address;
+#[cfg(feature = "analytics")]
+pub mod analytics;
 mod attachment;
+#[cfg(feature = "_aws_sig")]
+mod aws_sig;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod bounce;
+pub mod campaign;
+mod config;
+mod detect;
+pub mod diagnostics;
+#[cfg(feature = "dns")]
+pub mod dns;
 mod email;
 mod error;
+mod header;
+#[cfg(not(target_arch = "wasm32"))]
+mod hot_reload;
+#[cfg(feature = "_http")]
+pub mod http;
+#[cfg(feature = "insights")]
+pub mod insights;
 pub mod interceptor;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod integrations;
+pub mod jobs;
+pub mod links;
 mod mailer;
+#[cfg(any(
+    feature = "amazon_ses",
+    feature = "mailgun",
+    feature = "preview",
+    feature = "preview-axum",
+    feature = "preview-actix",
+    feature = "preview-rocket"
+))]
+mod mime;
+pub mod options;
+mod personalize;
+pub mod privacy;
+mod rate_limit;
+#[cfg(feature = "storage-encryption")]
+mod encrypted_storage;
+#[cfg(feature = "redis")]
+mod redis_storage;
+pub mod reputation;
+mod reply;
+mod router;
+#[cfg(feature = "attachment-s3")]
+pub mod s3;
+pub mod sandbox;
+mod scope;
+mod self_test;
+#[cfg(any(feature = "secrets-aws", feature = "secrets-vault"))]
+pub mod secrets;
+#[cfg(feature = "smtp-capture")]
+pub mod smtp_capture;
 
 pub mod providers;
 
-#[cfg(feature = "local")]
 mod storage;
+mod telemetry;
+pub mod tenant;
+#[cfg(feature = "tracking")]
+pub mod tracking;
+mod validation;
+#[cfg(feature = "verify")]
+pub mod verify;
+#[cfg(feature = "zip")]
+mod zip;
 
 #[cfg(feature = "local")]
 pub mod testing;
@@ -110,32 +277,70 @@ pub mod testing;
 #[cfg(any(
     feature = "preview",
     feature = "preview-axum",
-    feature = "preview-actix"
+    feature = "preview-actix",
+    feature = "preview-rocket"
 ))]
 pub mod preview;
 
 #[cfg(feature = "templates")]
 mod template;
+pub mod templates;
 #[cfg(feature = "templates")]
 pub use template::{EmailTemplate, EmailTemplateExt};
+#[cfg(feature = "derive")]
+pub mod catalog;
+#[cfg(feature = "derive")]
+pub use catalog::CatalogEmail;
+#[cfg(feature = "derive")]
+pub use missive_derive::EmailTemplate;
 
 use parking_lot::RwLock;
-use std::env;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
+use tracing::Instrument;
 
-#[cfg(feature = "metrics")]
+#[cfg(all(feature = "metrics", not(feature = "wasm")))]
 use std::time::Instant;
+#[cfg(all(feature = "metrics", feature = "wasm"))]
+use web_time::Instant;
 
 // Re-exports
-pub use address::{Address, ToAddress};
+pub use address::{Address, ToAddress, ToAddresses};
 pub use attachment::{Attachment, AttachmentType};
-pub use email::Email;
+pub use bounce::{BounceClass, BounceClassifier};
+pub use campaign::{Campaign, CampaignControl, CampaignReport};
+pub use config::{Config, ConfigBuilder, Provider};
+pub use diagnostics::{diagnose, Diagnostics};
+pub use email::{Email, Priority};
 pub use error::MailError;
+#[cfg(not(target_arch = "wasm32"))]
+pub use hot_reload::{watch, ConfigWatcher};
 pub use interceptor::{Interceptor, InterceptorExt, WithInterceptor};
-pub use mailer::{DeliveryResult, Mailer, MailerExt};
-
-#[cfg(feature = "local")]
-pub use storage::{MemoryStorage, Storage, StoredEmail};
+pub use links::LinkRewriter;
+pub use mailer::{
+    Capabilities, DeliveryResult, Mailer, MailerExt, MessageStatus, DEFAULT_BATCH_CONCURRENCY,
+};
+pub use options::translate_options;
+pub use personalize::Personalization;
+pub use privacy::LogRecipients;
+pub use rate_limit::RateLimited;
+pub use reply::ReplyAll;
+pub use reputation::{GuardAction, ReputationGuard, ReputationStats, ReputationTracker};
+pub use router::{Router, Rule};
+pub use sandbox::Sandbox;
+pub use scope::test_scope;
+pub use self_test::{self_test, SelfTest, SelfTestReport, SELF_TEST_HEADER};
+#[cfg(feature = "tracking")]
+pub use tracking::{Tracking, TrackingEvent};
+pub use validation::{FromDomainPolicy, LimitPolicy, ValidationIssue, ValidationReport, MAX_LINE_LENGTH};
+
+pub use tenant::{TenantMailer, TenantMailerProvider};
+
+pub use storage::{AsyncStorage, EmailFilter, Mailboxes, MemoryStorage, Storage, StoredEmail};
+#[cfg(feature = "storage-encryption")]
+pub use encrypted_storage::{generate_storage_key, EncryptedStorage};
+#[cfg(feature = "redis")]
+pub use redis_storage::RedisStorage;
 
 // ============================================================================
 // Global Mailer Configuration
@@ -144,6 +349,13 @@ pub use storage::{MemoryStorage, Storage, StoredEmail};
 /// Global mailer - swappable for testing
 static MAILER: RwLock<Option<Arc<dyn Mailer>>> = RwLock::new(None);
 
+/// Named mailers, registered manually or created from `EMAIL_PROVIDER__<NAME>`.
+static MAILERS: RwLock<BTreeMap<String, Arc<dyn Mailer>>> = RwLock::new(BTreeMap::new());
+
+/// Names of the mailers in `MAILERS` created from `EMAIL_PROVIDER__<NAME>`,
+/// which [`reload()`] rebuilds.
+static ENV_MAILERS: RwLock<BTreeSet<String>> = RwLock::new(BTreeSet::new());
+
 /// Global shared storage for LocalMailer (used by preview UI).
 #[cfg(feature = "local")]
 static LOCAL_STORAGE: std::sync::OnceLock<Arc<MemoryStorage>> = std::sync::OnceLock::new();
@@ -165,72 +377,155 @@ pub fn local_storage() -> Option<Arc<MemoryStorage>> {
     LOCAL_STORAGE.get().cloned()
 }
 
+/// Get the shared LocalMailer storage, creating it if needed.
+///
+/// Capacity limits are read from `MISSIVE_LOCAL_*` on first use.
+#[cfg(feature = "local")]
+pub(crate) fn shared_local_storage() -> Arc<MemoryStorage> {
+    Arc::clone(LOCAL_STORAGE.get_or_init(|| Arc::new(MemoryStorage::from_env())))
+}
+
 /// Get the default from address from environment.
 pub fn default_from() -> Option<Address> {
-    let email = env::var("EMAIL_FROM").ok()?;
-    match env::var("EMAIL_FROM_NAME").ok() {
+    let email = config::var("EMAIL_FROM")?;
+    match config::var("EMAIL_FROM_NAME") {
         Some(name) => Some(Address::with_name(name, email)),
         None => Some(Address::new(email)),
     }
 }
 
-/// Auto-detect provider based on enabled features and available API keys.
-fn detect_provider() -> Option<&'static str> {
-    // Check API keys first (explicit configuration)
-    #[cfg(feature = "resend")]
-    if env::var("RESEND_API_KEY").is_ok() {
-        return Some("resend");
-    }
-    #[cfg(feature = "sendgrid")]
-    if env::var("SENDGRID_API_KEY").is_ok() {
-        return Some("sendgrid");
-    }
-    #[cfg(feature = "postmark")]
-    if env::var("POSTMARK_API_KEY").is_ok() {
-        return Some("postmark");
-    }
-    #[cfg(feature = "unsent")]
-    if env::var("UNSENT_API_KEY").is_ok() {
-        return Some("unsent");
-    }
-    #[cfg(feature = "brevo")]
-    if env::var("BREVO_API_KEY").is_ok() {
-        return Some("brevo");
-    }
-    #[cfg(feature = "mailgun")]
-    if env::var("MAILGUN_API_KEY").is_ok() && env::var("MAILGUN_DOMAIN").is_ok() {
-        return Some("mailgun");
-    }
-    #[cfg(feature = "amazon_ses")]
-    if env::var("AWS_ACCESS_KEY_ID").is_ok()
-        && env::var("AWS_SECRET_ACCESS_KEY").is_ok()
-        && env::var("AWS_REGION").is_ok()
-    {
-        return Some("amazon_ses");
-    }
-    #[cfg(feature = "mailtrap")]
-    if env::var("MAILTRAP_API_KEY").is_ok() {
-        return Some("mailtrap");
+/// Get the default reply-to addresses (`EMAIL_REPLY_TO`, comma-separated).
+pub fn default_reply_to() -> Vec<Address> {
+    address_list("EMAIL_REPLY_TO")
+}
+
+/// Get the archive addresses every email is BCC'd to (`EMAIL_ALWAYS_BCC`).
+pub fn always_bcc() -> Vec<Address> {
+    address_list("EMAIL_ALWAYS_BCC")
+}
+
+/// Get the headers added to every email (`EMAIL_HEADERS`).
+///
+/// The variable holds comma-separated `Name: value` pairs, e.g.
+/// `X-Environment: staging, X-Team: growth`. A segment without a colon is
+/// treated as part of the previous value, so values may contain commas.
+pub fn default_headers() -> Vec<(String, String)> {
+    let Some(list) = config::var("EMAIL_HEADERS") else {
+        return Vec::new();
+    };
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for segment in list.split(',') {
+        match segment.split_once(':') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+            _ => match headers.last_mut() {
+                Some((_, value)) => {
+                    value.push(',');
+                    value.push_str(segment);
+                }
+                None if segment.trim().is_empty() => {}
+                None => {
+                    tracing::warn!(header = segment, "Ignoring invalid EMAIL_HEADERS entry");
+                }
+            },
+        }
     }
-    #[cfg(feature = "smtp")]
-    if env::var("SMTP_HOST").is_ok() {
-        return Some("smtp");
+    headers
+}
+
+/// Whether every delivery is strictly validated (`EMAIL_STRICT_VALIDATION`).
+pub fn strict_validation() -> bool {
+    config::var("EMAIL_STRICT_VALIDATION").is_some_and(|v| {
+        matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "true" | "1" | "yes" | "on"
+        )
+    })
+}
+
+/// What to do with subjects and header values over the mailer's limits
+/// (`EMAIL_LIMIT_POLICY`: `error` or `truncate`, default `error`).
+pub fn limit_policy() -> LimitPolicy {
+    match config::var("EMAIL_LIMIT_POLICY") {
+        Some(v) if v.trim().eq_ignore_ascii_case("truncate") => LimitPolicy::Truncate,
+        _ => LimitPolicy::Error,
     }
-    #[cfg(feature = "local")]
-    {
-        return Some("local");
+}
+
+/// Domains emails may be sent from (`EMAIL_ALLOWED_FROM_DOMAINS`,
+/// comma-separated, lowercase). Empty when every domain is allowed.
+pub fn allowed_from_domains() -> Vec<String> {
+    config::var("EMAIL_ALLOWED_FROM_DOMAINS")
+        .map(|list| {
+            list.split(',')
+                .map(|d| d.trim().to_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// What to do with senders outside [`allowed_from_domains()`]
+/// (`EMAIL_FROM_DOMAIN_POLICY`: `reject` or `rewrite`, default `reject`).
+pub fn from_domain_policy() -> FromDomainPolicy {
+    match config::var("EMAIL_FROM_DOMAIN_POLICY") {
+        Some(v) if v.trim().eq_ignore_ascii_case("rewrite") => FromDomainPolicy::Rewrite,
+        _ => FromDomainPolicy::Reject,
     }
-    #[allow(unreachable_code)]
-    None
+}
+
+/// Parse a comma-separated address list setting (`email` or `Name <email>`
+/// entries), logging and skipping invalid addresses.
+fn address_list(key: &str) -> Vec<Address> {
+    let Some(list) = config::var(key) else {
+        return Vec::new();
+    };
+    list.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| match Address::parse_rfc5322(s) {
+            Ok(addr) => Some(addr),
+            Err(e) => {
+                tracing::warn!(key = key, address = s, error = %e, "Ignoring invalid address");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Auto-detect provider based on enabled features and available API keys.
+fn detect_provider() -> Option<&'static str> {
+    detect::detect(config::var).provider
 }
 
 /// Create mailer from environment variables.
-fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
-    let provider = match env::var("EMAIL_PROVIDER") {
-        Ok(p) => p.to_lowercase(),
-        Err(_) => {
+///
+/// For a named mailer, the provider comes from `EMAIL_PROVIDER__<NAME>` and
+/// each setting is looked up as `<KEY>__<NAME>` before falling back to `<KEY>`.
+fn create_mailer_from_env(name: Option<&str>) -> Result<Arc<dyn Mailer>, MailError> {
+    create_mailer_with(name, |key: &str| config::scoped_var(key, name))
+}
+
+/// [`create_mailer_from_env`], reading settings with `var`.
+fn create_mailer_with(
+    name: Option<&str>,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<Arc<dyn Mailer>, MailError> {
+    let provider = match var("EMAIL_PROVIDER") {
+        Some(p) => p.to_lowercase(),
+        None if name.is_some() => {
+            return Err(MailError::Configuration(format!(
+                "Mailer '{}' is not registered and {} is not set",
+                name.unwrap_or_default(),
+                config::scoped_key("EMAIL_PROVIDER", name)
+            )));
+        }
+        None => {
             // Auto-detect based on features and API keys
-            match detect_provider() {
+            let detection = detect::detect(&var);
+            detection.warn();
+            match detection.provider {
                 Some(p) => {
                     tracing::debug!(provider = p, "Auto-detected email provider");
                     p.to_string()
@@ -249,22 +544,14 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
     match provider.as_str() {
         #[cfg(feature = "smtp")]
         "smtp" => {
-            let host = env::var("SMTP_HOST")
-                .map_err(|_| MailError::Configuration("SMTP_HOST not set".into()))?;
-            let port: u16 = env::var("SMTP_PORT")
-                .unwrap_or_else(|_| "587".to_string())
-                .parse()
-                .unwrap_or(587);
-            let username = env::var("SMTP_USERNAME").unwrap_or_default();
-            let password = env::var("SMTP_PASSWORD").unwrap_or_default();
-
-            let mailer = if username.is_empty() {
-                providers::SmtpMailer::new(&host, port).build()
-            } else {
-                providers::SmtpMailer::new(&host, port)
-                    .credentials(&username, &password)
-                    .build()
+            let builder = providers::SmtpConfig::from_vars(&var)?.builder();
+            #[cfg(feature = "socks")]
+            let builder = match var("MISSIVE_PROXY").filter(|url| url.starts_with("socks5")) {
+                Some(url) => builder.proxy(&url),
+                None => builder,
             };
+            let mailer = builder.build();
+            mailer.validate_config()?;
             Ok(Arc::new(mailer))
         }
         #[cfg(not(feature = "smtp"))]
@@ -273,11 +560,43 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
             Add `features = [\"smtp\"]` to Cargo.toml"
                 .into(),
         )),
+        #[cfg(feature = "smtp")]
+        "protonbridge" => {
+            let username = var("PROTON_BRIDGE_USERNAME").ok_or_else(|| {
+                MailError::Configuration("PROTON_BRIDGE_USERNAME not set".into())
+            })?;
+            let password = var("PROTON_BRIDGE_PASSWORD").ok_or_else(|| {
+                MailError::Configuration("PROTON_BRIDGE_PASSWORD not set".into())
+            })?;
+
+            let mut builder = providers::ProtonBridgeMailer::builder(&username, &password);
+            if let Some(host) = var("PROTON_BRIDGE_HOST") {
+                builder = builder.host(&host);
+            }
+            if let Some(port) = var("PROTON_BRIDGE_PORT").and_then(|p| p.parse().ok()) {
+                builder = builder.port(port);
+            }
+            if let Some(ssl) = var("PROTON_BRIDGE_SSL") {
+                if matches!(
+                    ssl.trim().to_ascii_lowercase().as_str(),
+                    "true" | "1" | "yes" | "on"
+                ) {
+                    builder = builder.ssl();
+                }
+            }
+            Ok(Arc::new(builder.build()))
+        }
+        #[cfg(not(feature = "smtp"))]
+        "protonbridge" => Err(MailError::Configuration(
+            "EMAIL_PROVIDER=protonbridge but 'smtp' feature is not enabled. \
+            Add `features = [\"smtp\"]` to Cargo.toml"
+                .into(),
+        )),
 
         #[cfg(feature = "resend")]
         "resend" => {
-            let key = env::var("RESEND_API_KEY")
-                .map_err(|_| MailError::Configuration("RESEND_API_KEY not set".into()))?;
+            let key = var("RESEND_API_KEY")
+                .ok_or_else(|| MailError::Configuration("RESEND_API_KEY not set".into()))?;
             Ok(Arc::new(providers::ResendMailer::new(&key)))
         }
         #[cfg(not(feature = "resend"))]
@@ -289,8 +608,8 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "unsent")]
         "unsent" => {
-            let key = env::var("UNSENT_API_KEY")
-                .map_err(|_| MailError::Configuration("UNSENT_API_KEY not set".into()))?;
+            let key = var("UNSENT_API_KEY")
+                .ok_or_else(|| MailError::Configuration("UNSENT_API_KEY not set".into()))?;
             Ok(Arc::new(providers::UnsentMailer::new(&key)))
         }
         #[cfg(not(feature = "unsent"))]
@@ -302,9 +621,13 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "postmark")]
         "postmark" => {
-            let key = env::var("POSTMARK_API_KEY")
-                .map_err(|_| MailError::Configuration("POSTMARK_API_KEY not set".into()))?;
-            Ok(Arc::new(providers::PostmarkMailer::new(&key)))
+            let key = var("POSTMARK_API_KEY")
+                .ok_or_else(|| MailError::Configuration("POSTMARK_API_KEY not set".into()))?;
+            let mut mailer = providers::PostmarkMailer::new(&key);
+            if let Some(stream) = var("POSTMARK_MESSAGE_STREAM") {
+                mailer = mailer.message_stream(stream);
+            }
+            Ok(Arc::new(mailer))
         }
         #[cfg(not(feature = "postmark"))]
         "postmark" => Err(MailError::Configuration(
@@ -315,9 +638,16 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "sendgrid")]
         "sendgrid" => {
-            let key = env::var("SENDGRID_API_KEY")
-                .map_err(|_| MailError::Configuration("SENDGRID_API_KEY not set".into()))?;
-            Ok(Arc::new(providers::SendGridMailer::new(&key)))
+            let key = var("SENDGRID_API_KEY")
+                .ok_or_else(|| MailError::Configuration("SENDGRID_API_KEY not set".into()))?;
+            let mut mailer = providers::SendGridMailer::new(&key);
+            if var("SENDGRID_REGION").is_some_and(|r| r.eq_ignore_ascii_case("eu")) {
+                mailer = mailer.eu();
+            }
+            if let Some(subuser) = var("SENDGRID_ON_BEHALF_OF") {
+                mailer = mailer.on_behalf_of(subuser);
+            }
+            Ok(Arc::new(mailer))
         }
         #[cfg(not(feature = "sendgrid"))]
         "sendgrid" => Err(MailError::Configuration(
@@ -328,8 +658,8 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "brevo")]
         "brevo" => {
-            let key = env::var("BREVO_API_KEY")
-                .map_err(|_| MailError::Configuration("BREVO_API_KEY not set".into()))?;
+            let key = var("BREVO_API_KEY")
+                .ok_or_else(|| MailError::Configuration("BREVO_API_KEY not set".into()))?;
             Ok(Arc::new(providers::BrevoMailer::new(&key)))
         }
         #[cfg(not(feature = "brevo"))]
@@ -341,13 +671,13 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "mailgun")]
         "mailgun" => {
-            let key = env::var("MAILGUN_API_KEY")
-                .map_err(|_| MailError::Configuration("MAILGUN_API_KEY not set".into()))?;
-            let domain = env::var("MAILGUN_DOMAIN")
-                .map_err(|_| MailError::Configuration("MAILGUN_DOMAIN not set".into()))?;
+            let key = var("MAILGUN_API_KEY")
+                .ok_or_else(|| MailError::Configuration("MAILGUN_API_KEY not set".into()))?;
+            let domain = var("MAILGUN_DOMAIN")
+                .ok_or_else(|| MailError::Configuration("MAILGUN_DOMAIN not set".into()))?;
             let mut mailer = providers::MailgunMailer::new(&key, &domain);
             // Check for EU endpoint
-            if let Ok(base_url) = env::var("MAILGUN_BASE_URL") {
+            if let Some(base_url) = var("MAILGUN_BASE_URL") {
                 mailer = mailer.base_url(base_url);
             }
             Ok(Arc::new(mailer))
@@ -361,12 +691,12 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "amazon_ses")]
         "amazon_ses" => {
-            let region = env::var("AWS_REGION")
-                .map_err(|_| MailError::Configuration("AWS_REGION not set".into()))?;
-            let access_key = env::var("AWS_ACCESS_KEY_ID")
-                .map_err(|_| MailError::Configuration("AWS_ACCESS_KEY_ID not set".into()))?;
-            let secret = env::var("AWS_SECRET_ACCESS_KEY")
-                .map_err(|_| MailError::Configuration("AWS_SECRET_ACCESS_KEY not set".into()))?;
+            let region = var("AWS_REGION")
+                .ok_or_else(|| MailError::Configuration("AWS_REGION not set".into()))?;
+            let access_key = var("AWS_ACCESS_KEY_ID")
+                .ok_or_else(|| MailError::Configuration("AWS_ACCESS_KEY_ID not set".into()))?;
+            let secret = var("AWS_SECRET_ACCESS_KEY")
+                .ok_or_else(|| MailError::Configuration("AWS_SECRET_ACCESS_KEY not set".into()))?;
             Ok(Arc::new(providers::AmazonSesMailer::new(region, access_key, secret)))
         }
         #[cfg(not(feature = "amazon_ses"))]
@@ -378,13 +708,19 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
 
         #[cfg(feature = "mailtrap")]
         "mailtrap" => {
-            let key = env::var("MAILTRAP_API_KEY")
-                .map_err(|_| MailError::Configuration("MAILTRAP_API_KEY not set".into()))?;
+            let key = var("MAILTRAP_API_KEY")
+                .ok_or_else(|| MailError::Configuration("MAILTRAP_API_KEY not set".into()))?;
             let mut mailer = providers::MailtrapMailer::new(&key);
             // Check for sandbox mode
-            if let Ok(inbox_id) = env::var("MAILTRAP_SANDBOX_INBOX_ID") {
+            if let Some(inbox_id) = var("MAILTRAP_SANDBOX_INBOX_ID") {
                 mailer = mailer.sandbox_inbox_id(inbox_id);
             }
+            if let Some(bulk) = var("MAILTRAP_BULK") {
+                mailer = mailer.bulk(matches!(
+                    bulk.trim().to_ascii_lowercase().as_str(),
+                    "true" | "1" | "yes" | "on"
+                ));
+            }
             Ok(Arc::new(mailer))
         }
         #[cfg(not(feature = "mailtrap"))]
@@ -394,11 +730,45 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
                 .into(),
         )),
 
+        #[cfg(feature = "zeptomail")]
+        "zeptomail" => {
+            let token = var("ZEPTOMAIL_TOKEN")
+                .ok_or_else(|| MailError::Configuration("ZEPTOMAIL_TOKEN not set".into()))?;
+            let mut mailer = providers::ZeptoMailer::new(token);
+            // Accounts outside the US data center
+            if let Some(base_url) = var("ZEPTOMAIL_BASE_URL") {
+                mailer = mailer.base_url(base_url);
+            }
+            Ok(Arc::new(mailer))
+        }
+        #[cfg(not(feature = "zeptomail"))]
+        "zeptomail" => Err(MailError::Configuration(
+            "EMAIL_PROVIDER=zeptomail but 'zeptomail' feature is not enabled. \
+            Add `features = [\"zeptomail\"]` to Cargo.toml"
+                .into(),
+        )),
+
+        #[cfg(feature = "customerio")]
+        "customerio" => {
+            let key = var("CUSTOMERIO_API_KEY")
+                .ok_or_else(|| MailError::Configuration("CUSTOMERIO_API_KEY not set".into()))?;
+            let mut mailer = providers::CustomerIoMailer::new(&key);
+            if var("CUSTOMERIO_REGION").is_some_and(|r| r.eq_ignore_ascii_case("eu")) {
+                mailer = mailer.eu();
+            }
+            Ok(Arc::new(mailer))
+        }
+        #[cfg(not(feature = "customerio"))]
+        "customerio" => Err(MailError::Configuration(
+            "EMAIL_PROVIDER=customerio but 'customerio' feature is not enabled. \
+            Add `features = [\"customerio\"]` to Cargo.toml"
+                .into(),
+        )),
+
         #[cfg(feature = "local")]
         "local" => {
             // Use global shared storage so preview UI can access emails
-            let storage = LOCAL_STORAGE.get_or_init(MemoryStorage::shared);
-            Ok(Arc::new(providers::LocalMailer::with_storage(Arc::clone(storage))))
+            Ok(Arc::new(providers::LocalMailer::with_storage(shared_local_storage())))
         }
         #[cfg(not(feature = "local"))]
         "local" => Err(MailError::Configuration(
@@ -407,18 +777,86 @@ fn create_mailer_from_env() -> Result<Arc<dyn Mailer>, MailError> {
                 .into(),
         )),
 
-        "logger" => Ok(Arc::new(providers::LoggerMailer::new())),
-        "logger_full" => Ok(Arc::new(providers::LoggerMailer::full())),
+        "logger" | "logger_full" | "logger_json" => {
+            let mut mailer = match provider.as_str() {
+                "logger_full" => providers::LoggerMailer::full(),
+                "logger_json" => providers::LoggerMailer::json(),
+                _ => providers::LoggerMailer::new(),
+            };
+            if let Some(redact) = var("LOGGER_REDACT") {
+                for field in redact.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                    mailer = match field {
+                        "bodies" => mailer.redact_bodies(),
+                        "recipients" => mailer.hash_recipients(),
+                        other => {
+                            return Err(MailError::Configuration(format!(
+                                "Invalid LOGGER_REDACT: {} (expected bodies, recipients)",
+                                other
+                            )))
+                        }
+                    };
+                }
+            }
+            if let Some(path) = var("LOGGER_FILE") {
+                mailer = mailer.file(&path).map_err(|e| {
+                    MailError::Configuration(format!("Cannot open LOGGER_FILE {}: {}", path, e))
+                })?;
+            }
+            Ok(Arc::new(mailer))
+        }
 
         _ => Err(MailError::Configuration(format!(
-            "Unknown EMAIL_PROVIDER: {}. Valid providers are: smtp, resend, unsent, postmark, sendgrid, brevo, mailgun, amazon_ses, mailtrap, local, logger, logger_full",
+            "Unknown EMAIL_PROVIDER: {}. Valid providers are: smtp, protonbridge, resend, unsent, postmark, sendgrid, brevo, mailgun, amazon_ses, mailtrap, zeptomail, customerio, local, logger, logger_full, logger_json",
             provider
         ))),
     }
 }
 
+/// Create a mailer from env vars, applying `EMAIL_RATE_LIMIT` and
+/// `EMAIL_AUDIT_LOG` if set.
+fn build_mailer(name: Option<&str>) -> Result<Arc<dyn Mailer>, MailError> {
+    let mut mailer = create_mailer_from_env(name)?;
+    if let Some(limit) = config::scoped_var("EMAIL_RATE_LIMIT", name) {
+        let per_second = limit.trim().parse().map_err(|_| {
+            MailError::Configuration(format!("Invalid EMAIL_RATE_LIMIT: {}", limit))
+        })?;
+        mailer = Arc::new(RateLimited::new(mailer, per_second));
+    }
+    #[cfg(feature = "audit")]
+    if let Some(path) = config::scoped_var("EMAIL_AUDIT_LOG", name) {
+        let sink = audit::JsonLinesSink::open(&path).map_err(|e| {
+            MailError::Configuration(format!("Cannot open EMAIL_AUDIT_LOG {}: {}", path, e))
+        })?;
+        let mut audited = audit::Audited::new(mailer, sink);
+        match config::scoped_var("EMAIL_AUDIT_RECIPIENTS", name).as_deref() {
+            None | Some("plain") => {}
+            Some("hashed") => audited = audited.hash_recipients(),
+            Some(other) => {
+                return Err(MailError::Configuration(format!(
+                    "Invalid EMAIL_AUDIT_RECIPIENTS: {} (expected plain or hashed)",
+                    other
+                )))
+            }
+        }
+        mailer = Arc::new(audited);
+    }
+    Ok(mailer)
+}
+
+/// Whether the global mailer has been built (or configured).
+#[cfg(any(feature = "secrets-aws", feature = "secrets-vault"))]
+pub(crate) fn is_initialized() -> bool {
+    MAILER.read().is_some()
+}
+
 /// Get or initialize the global mailer.
+///
+/// A mailer installed with [`test_scope()`] takes precedence.
 fn get_mailer() -> Result<Arc<dyn Mailer>, MailError> {
+    if let Some(mailer) = scope::scoped_mailer() {
+        return Ok(mailer);
+    }
+
     // Fast path: already configured
     {
         let guard = MAILER.read();
@@ -428,7 +866,7 @@ fn get_mailer() -> Result<Arc<dyn Mailer>, MailError> {
     }
 
     // Slow path: need to configure
-    let mailer = create_mailer_from_env()?;
+    let mailer = build_mailer(None)?;
     let mut guard = MAILER.write();
 
     // Double-check after acquiring write lock
@@ -439,6 +877,20 @@ fn get_mailer() -> Result<Arc<dyn Mailer>, MailError> {
     Ok(guard.as_ref().unwrap().clone())
 }
 
+/// Get or initialize a named mailer.
+fn get_named_mailer(name: &str) -> Result<Arc<dyn Mailer>, MailError> {
+    if let Some(mailer) = MAILERS.read().get(name) {
+        return Ok(Arc::clone(mailer));
+    }
+
+    let mailer = build_mailer(Some(name))?;
+    let mut guard = MAILERS.write();
+    Ok(Arc::clone(guard.entry(name.to_string()).or_insert_with(|| {
+        ENV_MAILERS.write().insert(name.to_string());
+        mailer
+    })))
+}
+
 /// Check if email is configured (env vars are set and feature is enabled).
 ///
 /// Returns `true` only if both:
@@ -450,9 +902,9 @@ fn get_mailer() -> Result<Arc<dyn Mailer>, MailError> {
 ///
 /// Logs a warning if the provider is specified but the feature flag is not enabled.
 pub fn is_configured() -> bool {
-    let provider = match env::var("EMAIL_PROVIDER") {
-        Ok(p) => p,
-        Err(_) => {
+    let provider = match config::var("EMAIL_PROVIDER") {
+        Some(p) => p,
+        None => {
             // Auto-detect
             match detect_provider() {
                 Some(p) => p.to_string(),
@@ -462,7 +914,7 @@ pub fn is_configured() -> bool {
     };
     match provider.to_lowercase().as_str() {
         #[cfg(feature = "smtp")]
-        "smtp" => env::var("SMTP_HOST").is_ok(),
+        "smtp" => config::var("SMTP_HOST").is_some(),
         #[cfg(not(feature = "smtp"))]
         "smtp" => {
             tracing::warn!(
@@ -472,8 +924,22 @@ pub fn is_configured() -> bool {
             false
         }
 
+        #[cfg(feature = "smtp")]
+        "protonbridge" => {
+            config::var("PROTON_BRIDGE_USERNAME").is_some()
+                && config::var("PROTON_BRIDGE_PASSWORD").is_some()
+        }
+        #[cfg(not(feature = "smtp"))]
+        "protonbridge" => {
+            tracing::warn!(
+                "EMAIL_PROVIDER=protonbridge but 'smtp' feature is not enabled. \
+                Add `features = [\"smtp\"]` to Cargo.toml"
+            );
+            false
+        }
+
         #[cfg(feature = "resend")]
-        "resend" => env::var("RESEND_API_KEY").is_ok(),
+        "resend" => config::var("RESEND_API_KEY").is_some(),
         #[cfg(not(feature = "resend"))]
         "resend" => {
             tracing::warn!(
@@ -484,7 +950,7 @@ pub fn is_configured() -> bool {
         }
 
         #[cfg(feature = "unsent")]
-        "unsent" => env::var("UNSENT_API_KEY").is_ok(),
+        "unsent" => config::var("UNSENT_API_KEY").is_some(),
         #[cfg(not(feature = "unsent"))]
         "unsent" => {
             tracing::warn!(
@@ -495,7 +961,7 @@ pub fn is_configured() -> bool {
         }
 
         #[cfg(feature = "postmark")]
-        "postmark" => env::var("POSTMARK_API_KEY").is_ok(),
+        "postmark" => config::var("POSTMARK_API_KEY").is_some(),
         #[cfg(not(feature = "postmark"))]
         "postmark" => {
             tracing::warn!(
@@ -506,7 +972,7 @@ pub fn is_configured() -> bool {
         }
 
         #[cfg(feature = "sendgrid")]
-        "sendgrid" => env::var("SENDGRID_API_KEY").is_ok(),
+        "sendgrid" => config::var("SENDGRID_API_KEY").is_some(),
         #[cfg(not(feature = "sendgrid"))]
         "sendgrid" => {
             tracing::warn!(
@@ -517,7 +983,7 @@ pub fn is_configured() -> bool {
         }
 
         #[cfg(feature = "brevo")]
-        "brevo" => env::var("BREVO_API_KEY").is_ok(),
+        "brevo" => config::var("BREVO_API_KEY").is_some(),
         #[cfg(not(feature = "brevo"))]
         "brevo" => {
             tracing::warn!(
@@ -528,7 +994,9 @@ pub fn is_configured() -> bool {
         }
 
         #[cfg(feature = "mailgun")]
-        "mailgun" => env::var("MAILGUN_API_KEY").is_ok() && env::var("MAILGUN_DOMAIN").is_ok(),
+        "mailgun" => {
+            config::var("MAILGUN_API_KEY").is_some() && config::var("MAILGUN_DOMAIN").is_some()
+        }
         #[cfg(not(feature = "mailgun"))]
         "mailgun" => {
             tracing::warn!(
@@ -540,9 +1008,9 @@ pub fn is_configured() -> bool {
 
         #[cfg(feature = "amazon_ses")]
         "amazon_ses" => {
-            env::var("AWS_REGION").is_ok()
-                && env::var("AWS_ACCESS_KEY_ID").is_ok()
-                && env::var("AWS_SECRET_ACCESS_KEY").is_ok()
+            config::var("AWS_REGION").is_some()
+                && config::var("AWS_ACCESS_KEY_ID").is_some()
+                && config::var("AWS_SECRET_ACCESS_KEY").is_some()
         }
         #[cfg(not(feature = "amazon_ses"))]
         "amazon_ses" => {
@@ -554,7 +1022,7 @@ pub fn is_configured() -> bool {
         }
 
         #[cfg(feature = "mailtrap")]
-        "mailtrap" => env::var("MAILTRAP_API_KEY").is_ok(),
+        "mailtrap" => config::var("MAILTRAP_API_KEY").is_some(),
         #[cfg(not(feature = "mailtrap"))]
         "mailtrap" => {
             tracing::warn!(
@@ -564,6 +1032,28 @@ pub fn is_configured() -> bool {
             false
         }
 
+        #[cfg(feature = "zeptomail")]
+        "zeptomail" => config::var("ZEPTOMAIL_TOKEN").is_some(),
+        #[cfg(not(feature = "zeptomail"))]
+        "zeptomail" => {
+            tracing::warn!(
+                "EMAIL_PROVIDER=zeptomail but 'zeptomail' feature is not enabled. \
+                Add `features = [\"zeptomail\"]` to Cargo.toml"
+            );
+            false
+        }
+
+        #[cfg(feature = "customerio")]
+        "customerio" => config::var("CUSTOMERIO_API_KEY").is_some(),
+        #[cfg(not(feature = "customerio"))]
+        "customerio" => {
+            tracing::warn!(
+                "EMAIL_PROVIDER=customerio but 'customerio' feature is not enabled. \
+                Add `features = [\"customerio\"]` to Cargo.toml"
+            );
+            false
+        }
+
         #[cfg(feature = "local")]
         "local" => true,
         #[cfg(not(feature = "local"))]
@@ -575,7 +1065,7 @@ pub fn is_configured() -> bool {
             false
         }
 
-        "logger" | "logger_full" => true,
+        "logger" | "logger_full" | "logger_json" => true,
 
         _ => false,
     }
@@ -598,6 +1088,36 @@ pub fn init() -> Result<(), MailError> {
     Ok(())
 }
 
+/// Check the global mailer's credentials against its provider.
+///
+/// See [`Mailer::health_check`]. Nothing is sent.
+///
+/// ```rust,ignore
+/// // In main.rs
+/// missive::health_check().await.expect("email provider rejected credentials");
+/// ```
+pub async fn health_check() -> Result<(), MailError> {
+    get_mailer()?.health_check().await
+}
+
+/// Look up a message sent through the global mailer.
+///
+/// See [`Mailer::message_status`]: Postmark, Mailgun and Amazon SES report
+/// where a message is; other providers fail with
+/// [`MailError::UnsupportedFeature`].
+///
+/// ```rust,ignore
+/// let result = missive::deliver(&email).await?;
+/// // Later, e.g. from a background job
+/// match missive::message_status(&result.message_id).await? {
+///     MessageStatus::Bounced(class) if class.should_suppress() => suppress(&email),
+///     _ => {}
+/// }
+/// ```
+pub async fn message_status(message_id: &str) -> Result<MessageStatus, MailError> {
+    get_mailer()?.message_status(message_id).await
+}
+
 /// Validate an email has required fields.
 fn validate(email: &Email) -> Result<(), MailError> {
     if email.from.is_none() && default_from().is_none() {
@@ -606,19 +1126,98 @@ fn validate(email: &Email) -> Result<(), MailError> {
     if email.to.is_empty() {
         return Err(MailError::MissingField("to"));
     }
-    Ok(())
+    email.validate_headers()
 }
 
-/// Prepare email by adding default from address if needed.
-fn prepare_email(email: &Email) -> Email {
+/// Prepare email by adding the default from, reply-to and headers if needed.
+///
+/// Senders outside `EMAIL_ALLOWED_FROM_DOMAINS` are rejected or rewritten
+/// according to `EMAIL_FROM_DOMAIN_POLICY`. Also translates provider options
+/// written for another provider and applies
+/// the installed [`LinkRewriter`], the recipient sandbox when `EMAIL_SANDBOX_*`
+/// variables are set and link tracking when `EMAIL_TRACKING_*` variables are set.
+/// Archive addresses from `EMAIL_ALWAYS_BCC` are added last, so the sandbox
+/// doesn't filter them. With `EMAIL_STRICT_VALIDATION`, the result must pass
+/// [`Email::validate_strict_for`] the provider. Emails over the provider's
+/// size limit (or `EMAIL_MAX_MESSAGE_SIZE`) fail with [`MailError::TooLarge`],
+/// and subjects, header values and recipient lists over the mailer's
+/// [`Capabilities`] are handled according to `EMAIL_LIMIT_POLICY`.
+fn prepare_email(email: &Email, provider: &str, caps: &Capabilities) -> Result<Email, MailError> {
+    let mut email = email.clone();
     if email.from.is_none() {
-        if let Some(from) = default_from() {
-            let mut e = email.clone();
-            e.from = Some(from);
-            return e;
+        email.from = default_from();
+    }
+    if email.reply_to.is_empty() {
+        email.reply_to = default_reply_to();
+    }
+    for (name, value) in default_headers() {
+        if !email.headers.keys().any(|k| k.eq_ignore_ascii_case(&name)) {
+            email.headers.insert(name, value);
+        }
+    }
+    let allowed = allowed_from_domains();
+    if !allowed.is_empty() {
+        validation::check_from_domain(
+            &mut email,
+            &allowed,
+            from_domain_policy(),
+            default_from(),
+        )?;
+    }
+    for warning in translate_options(&mut email, provider) {
+        tracing::warn!(provider = provider, "{}", warning);
+    }
+    if let Some(rewriter) = links::installed() {
+        email = links::rewrite_links(email, rewriter.as_ref());
+    }
+    #[cfg(feature = "tracking")]
+    if let Some(tracking) = Tracking::from_env() {
+        email = tracking.intercept(email)?;
+    }
+    let mut email = match Sandbox::from_env() {
+        Some(sandbox) => sandbox.apply(email)?,
+        None => email,
+    };
+    for addr in always_bcc() {
+        let present = email
+            .to
+            .iter()
+            .chain(&email.cc)
+            .chain(&email.bcc)
+            .any(|a| a.email.eq_ignore_ascii_case(&addr.email));
+        if !present {
+            email.bcc.push(addr);
         }
     }
-    email.clone()
+    if strict_validation() {
+        email.validate_strict_for(provider).into_result()?;
+    }
+    validation::check_limits(&mut email, caps, limit_policy())?;
+    validation::check_size(&email, provider)?;
+    Ok(email)
+}
+
+/// [`prepare_email`] for `mailer`, then fetch or presign S3 attachments.
+async fn prepare<M: Mailer + ?Sized>(email: &Email, mailer: &M) -> Result<Email, MailError> {
+    let provider = mailer.provider_name();
+    #[allow(unused_mut)]
+    let mut email = prepare_email(email, provider, &mailer.capabilities())?;
+    #[cfg(feature = "attachment-s3")]
+    if s3::resolve(&mut email, provider).await? {
+        validation::check_size(&email, provider)?;
+    }
+    Ok(email)
+}
+
+async fn prepare_all<M: Mailer + ?Sized>(
+    emails: &[Email],
+    mailer: &M,
+) -> Result<Vec<Email>, MailError> {
+    let mut prepared = Vec::with_capacity(emails.len());
+    for email in emails {
+        prepared.push(prepare(email, mailer).await?);
+    }
+    Ok(prepared)
 }
 
 /// Deliver an email using the global mailer.
@@ -643,23 +1242,16 @@ pub async fn deliver(email: &Email) -> Result<DeliveryResult, MailError> {
 
     let mailer = get_mailer()?;
     let provider = mailer.provider_name();
-    let email = prepare_email(email);
+    let email = prepare(email, &mailer).await?;
 
     // Emit telemetry span
-    let span = tracing::info_span!(
-        "missive.deliver",
-        provider = provider,
-        to = ?email.to.iter().map(|a| &a.email).collect::<Vec<_>>(),
-        subject = %email.subject,
-    );
-    let _guard = span.enter();
-
-    tracing::debug!("Delivering email");
+    let span = telemetry::deliver_span(provider, &email);
+    span.in_scope(|| tracing::debug!("Delivering email"));
 
     #[cfg(feature = "metrics")]
     let start = Instant::now();
 
-    let result = mailer.deliver(&email).await;
+    let result = mailer.deliver(&email).instrument(span.clone()).await;
 
     // Record metrics
     #[cfg(feature = "metrics")]
@@ -670,12 +1262,12 @@ pub async fn deliver(email: &Email) -> Result<DeliveryResult, MailError> {
             .increment(1);
         metrics::histogram!("missive_delivery_duration_seconds", "provider" => provider)
             .record(duration);
+        if let Err(e) = &result {
+            telemetry::record_error_metric(provider, e, 1);
+        }
     }
 
-    match &result {
-        Ok(r) => tracing::info!(message_id = %r.message_id, "Email delivered"),
-        Err(e) => tracing::error!(error = %e, "Email delivery failed"),
-    }
+    telemetry::record_delivery(&span, &result);
 
     result
 }
@@ -703,23 +1295,16 @@ pub async fn deliver_with<M: Mailer>(
     validate(email)?;
 
     let provider = mailer.provider_name();
-    let email = prepare_email(email);
+    let email = prepare(email, mailer).await?;
 
     // Emit telemetry span
-    let span = tracing::info_span!(
-        "missive.deliver",
-        provider = provider,
-        to = ?email.to.iter().map(|a| &a.email).collect::<Vec<_>>(),
-        subject = %email.subject,
-    );
-    let _guard = span.enter();
-
-    tracing::debug!("Delivering email");
+    let span = telemetry::deliver_span(provider, &email);
+    span.in_scope(|| tracing::debug!("Delivering email"));
 
     #[cfg(feature = "metrics")]
     let start = Instant::now();
 
-    let result = mailer.deliver(&email).await;
+    let result = mailer.deliver(&email).instrument(span.clone()).await;
 
     // Record metrics
     #[cfg(feature = "metrics")]
@@ -730,12 +1315,12 @@ pub async fn deliver_with<M: Mailer>(
             .increment(1);
         metrics::histogram!("missive_delivery_duration_seconds", "provider" => provider)
             .record(duration);
+        if let Err(e) = &result {
+            telemetry::record_error_metric(provider, e, 1);
+        }
     }
 
-    match &result {
-        Ok(r) => tracing::info!(message_id = %r.message_id, "Email delivered"),
-        Err(e) => tracing::error!(error = %e, "Email delivery failed"),
-    }
+    telemetry::record_delivery(&span, &result);
 
     result
 }
@@ -748,17 +1333,38 @@ pub async fn deliver_many(emails: &[Email]) -> Result<Vec<DeliveryResult>, MailE
     }
 
     let mailer = get_mailer()?;
-    let provider = mailer.provider_name();
-    let count = emails.len();
-    let emails: Vec<Email> = emails.iter().map(prepare_email).collect();
+    let emails = prepare_all(emails, &mailer).await?;
+    send_many(mailer.as_ref(), &emails, None).await
+}
 
-    let span = tracing::info_span!("missive.deliver_many", provider = provider, count = count,);
-    let _guard = span.enter();
+/// Send prepared emails as one batch, with the tracing span and metrics
+/// shared by every `deliver_many*()` function.
+async fn send_many<M: Mailer + ?Sized>(
+    mailer: &M,
+    emails: &[Email],
+    name: Option<&str>,
+) -> Result<Vec<DeliveryResult>, MailError> {
+    let delivery = mailer.deliver_many(emails);
+    instrument_batch(mailer.provider_name(), emails.len(), name, delivery).await
+}
+
+/// Run a batch delivery of `count` emails in a tracing span and record its
+/// metrics.
+async fn instrument_batch<F>(
+    provider: &'static str,
+    count: usize,
+    name: Option<&str>,
+    delivery: F,
+) -> Result<Vec<DeliveryResult>, MailError>
+where
+    F: std::future::Future<Output = Result<Vec<DeliveryResult>, MailError>>,
+{
+    let span = telemetry::deliver_many_span(provider, count, name);
 
     #[cfg(feature = "metrics")]
     let start = Instant::now();
 
-    let result = mailer.deliver_many(&emails).await;
+    let result = delivery.instrument(span.clone()).await;
 
     // Record metrics
     #[cfg(feature = "metrics")]
@@ -771,11 +1377,139 @@ pub async fn deliver_many(emails: &[Email]) -> Result<Vec<DeliveryResult>, MailE
             .increment(1);
         metrics::histogram!("missive_delivery_duration_seconds", "provider" => provider, "batch" => "true").record(duration);
         metrics::histogram!("missive_batch_size", "provider" => provider).record(count as f64);
+        if let Err(e) = &result {
+            telemetry::record_error_metric(provider, e, count as u64);
+        }
+    }
+
+    if let Err(e) = &result {
+        telemetry::record_error(&span, e);
     }
 
     result
 }
 
+/// Deliver a personalized email using the global mailer.
+///
+/// Sends to every recipient added with [`Email::personalize`], using the
+/// provider's native batch personalization where available. Returns one
+/// result per recipient, in order.
+///
+/// ```rust,ignore
+/// use missive::{Email, deliver_personalized};
+/// use serde_json::json;
+///
+/// let email = Email::new()
+///     .subject("Hi {{ name }}")
+///     .text_body("Welcome aboard, {{ name }}!")
+///     .personalize(vec![
+///         ("alice@example.com", json!({"name": "Alice"})),
+///         ("bob@example.com", json!({"name": "Bob"})),
+///     ]);
+///
+/// let results = deliver_personalized(&email).await?;
+/// ```
+pub async fn deliver_personalized(email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+    if email.from.is_none() && default_from().is_none() {
+        return Err(MailError::MissingField("from"));
+    }
+    if email.personalizations.is_empty() {
+        return Err(MailError::MissingField("personalizations"));
+    }
+    // Merge data can put line breaks into the subject, so check every
+    // rendered email as well as the template
+    email.validate_headers()?;
+    let rendered = email.render_personalizations();
+    for email in &rendered {
+        validate(email)?;
+    }
+
+    let mailer = get_mailer()?;
+
+    // The sandbox rewrites recipients, so sandboxed merges are sent rendered
+    // and each rendered email goes through it.
+    if Sandbox::from_env().is_some() {
+        let emails = prepare_all(&rendered, &mailer).await?;
+        return send_many(mailer.as_ref(), &emails, None).await;
+    }
+
+    let count = email.personalizations.len();
+    let email = prepare(email, &mailer).await?;
+    let delivery = mailer.deliver_personalized(&email);
+    instrument_batch(mailer.provider_name(), count, None, delivery).await
+}
+
+/// Deliver an email using a named mailer.
+///
+/// The mailer is either registered with [`register()`] or configured from
+/// environment variables with a `__<NAME>` suffix:
+///
+/// ```bash
+/// EMAIL_PROVIDER__TRANSACTIONAL=postmark
+/// POSTMARK_API_KEY__TRANSACTIONAL=xxx
+/// EMAIL_PROVIDER__MARKETING=sendgrid
+/// SENDGRID_API_KEY=xxx  # unsuffixed settings are shared
+/// ```
+///
+/// ```rust,ignore
+/// use missive::{Email, deliver_via};
+///
+/// deliver_via("transactional", &email).await?;
+/// ```
+pub async fn deliver_via(name: &str, email: &Email) -> Result<DeliveryResult, MailError> {
+    let mailer = get_named_mailer(name)?;
+    deliver_with(email, &mailer).await
+}
+
+/// Deliver multiple emails using a named mailer.
+pub async fn deliver_many_via(
+    name: &str,
+    emails: &[Email],
+) -> Result<Vec<DeliveryResult>, MailError> {
+    for email in emails {
+        validate(email)?;
+    }
+
+    let mailer = get_named_mailer(name)?;
+    let emails = prepare_all(emails, &mailer).await?;
+    send_many(mailer.as_ref(), &emails, Some(name)).await
+}
+
+/// Deliver an email for a tenant, with the mailer and sender settings from
+/// the installed [`TenantMailerProvider`] (see [`tenant`]).
+///
+/// The tenant's `from` and `reply_to` are used when the email doesn't set
+/// them, ahead of `EMAIL_FROM` and `EMAIL_REPLY_TO`.
+///
+/// ```rust,ignore
+/// missive::tenant::set_provider(Workspaces(pool));
+/// missive::deliver_for(&workspace_id, &email).await?;
+/// ```
+pub async fn deliver_for(tenant_id: &str, email: &Email) -> Result<DeliveryResult, MailError> {
+    let tenant = tenant::resolve(tenant_id).await?;
+    let email = tenant.apply(tenant_id, email)?;
+    deliver_with(&email, tenant.mailer()).await
+}
+
+/// Deliver multiple emails for a tenant.
+pub async fn deliver_many_for(
+    tenant_id: &str,
+    emails: &[Email],
+) -> Result<Vec<DeliveryResult>, MailError> {
+    let tenant = tenant::resolve(tenant_id).await?;
+    let emails = emails
+        .iter()
+        .map(|email| tenant.apply(tenant_id, email))
+        .collect::<Result<Vec<_>, _>>()?;
+    for email in &emails {
+        validate(email)?;
+    }
+
+    let mailer = tenant.mailer();
+    let emails = prepare_all(&emails, mailer.as_ref()).await?;
+    send_many(mailer.as_ref(), &emails, None).await
+}
+
 // ============================================================================
 // Manual Configuration (for testing or custom setups)
 // ============================================================================
@@ -801,16 +1535,138 @@ pub fn configure_arc(mailer: Arc<dyn Mailer>) {
     *guard = Some(mailer);
 }
 
-/// Reset the global mailer (useful for tests).
+/// Register a named mailer for use with [`deliver_via()`].
+///
+/// Replaces any mailer previously registered under the same name.
+///
+/// ```rust,ignore
+/// use missive::providers::{PostmarkMailer, SendGridMailer};
+///
+/// missive::register("transactional", PostmarkMailer::new(postmark_key));
+/// missive::register("marketing", SendGridMailer::new(sendgrid_key));
+/// ```
+pub fn register<M: Mailer + 'static>(name: &str, mailer: M) {
+    register_arc(name, Arc::new(mailer));
+}
+
+/// Register a named Arc'd mailer.
+pub fn register_arc(name: &str, mailer: Arc<dyn Mailer>) {
+    MAILERS.write().insert(name.to_string(), mailer);
+    ENV_MAILERS.write().remove(name);
+}
+
+/// Remove a named mailer, returning it if it was registered.
+pub fn unregister(name: &str) -> Option<Arc<dyn Mailer>> {
+    ENV_MAILERS.write().remove(name);
+    MAILERS.write().remove(name)
+}
+
+/// Get a named mailer (if registered or already initialized).
+pub fn named_mailer(name: &str) -> Option<Arc<dyn Mailer>> {
+    MAILERS.read().get(name).cloned()
+}
+
+/// Install a [`LinkRewriter`] applied to every email sent with the global
+/// `deliver()` functions, before tracking and the sandbox.
+///
+/// Replaces any previously installed rewriter.
+///
+/// ```rust,ignore
+/// missive::set_link_rewriter(|url: &str, _email: &Email| {
+///     Some(url.replace("https://example.com", "https://staging.example.com"))
+/// });
+/// ```
+pub fn set_link_rewriter<R: LinkRewriter + 'static>(rewriter: R) {
+    links::set(Some(Arc::new(rewriter)));
+}
+
+/// Remove the installed [`LinkRewriter`].
+pub fn clear_link_rewriter() {
+    links::set(None);
+}
+
+/// Reset the global mailer, all named mailers and cached tenants (useful for tests).
 ///
 /// After calling this, the next `deliver()` will re-initialize from env vars.
 pub fn reset() {
     let mut guard = MAILER.write();
     *guard = None;
+    MAILERS.write().clear();
+    ENV_MAILERS.write().clear();
+    tenant::invalidate_all();
+}
+
+/// Drop the global mailer, the named mailers created from
+/// `EMAIL_PROVIDER__<NAME>` and cached tenants, so they are rebuilt from the
+/// current configuration. Mailers added with [`register()`] are kept.
+pub(crate) fn invalidate_configured() {
+    *MAILER.write() = None;
+    let names = std::mem::take(&mut *ENV_MAILERS.write());
+    let mut mailers = MAILERS.write();
+    for name in &names {
+        mailers.remove(name);
+    }
+    drop(mailers);
+    tenant::invalidate_all();
+}
+
+/// Rebuild the global mailer from the current configuration and swap it in,
+/// without restarting the process.
+///
+/// Picks up changed environment variables and, when `missive.toml` was
+/// loaded automatically, changes to that file (for example API keys rotated
+/// by a secrets manager). Named mailers created from `EMAIL_PROVIDER__<NAME>`
+/// are rebuilt too; mailers added with [`register()`] are kept. A mailer set
+/// with [`configure()`] is replaced. Deliveries already in flight finish on
+/// the old mailer.
+///
+/// The swap is all-or-nothing: if any mailer fails to build, the current ones
+/// stay in place and the error is returned. See [`watch()`] to reload
+/// automatically.
+///
+/// ```rust,ignore
+/// std::env::set_var("RESEND_API_KEY", rotated_key);
+/// missive::reload()?;
+/// ```
+pub fn reload() -> Result<(), MailError> {
+    let previous = config::reload_file()?;
+    let rebuilt = rebuild_mailers();
+    if rebuilt.is_err() {
+        if let Some(config) = previous {
+            config::restore(config);
+        }
+    }
+    let (mailer, named) = rebuilt?;
+
+    let provider = mailer.provider_name();
+    *MAILER.write() = Some(mailer);
+    let mut guard = MAILERS.write();
+    for (name, mailer) in named {
+        guard.insert(name, mailer);
+    }
+    tracing::info!(provider = provider, "Reloaded email configuration");
+    Ok(())
+}
+
+/// Build the global mailer and the env-created named mailers afresh.
+#[allow(clippy::type_complexity)]
+fn rebuild_mailers() -> Result<(Arc<dyn Mailer>, Vec<(String, Arc<dyn Mailer>)>), MailError> {
+    let mailer = build_mailer(None)?;
+    let names: Vec<String> = ENV_MAILERS.read().iter().cloned().collect();
+    let named = names
+        .into_iter()
+        .map(|name| build_mailer(Some(&name)).map(|mailer| (name, mailer)))
+        .collect::<Result<_, _>>()?;
+    Ok((mailer, named))
 }
 
 /// Get a reference to the configured mailer (if initialized).
+///
+/// Inside [`test_scope()`], returns the scoped mailer.
 pub fn mailer() -> Option<Arc<dyn Mailer>> {
+    if let Some(mailer) = scope::scoped_mailer() {
+        return Some(mailer);
+    }
     let guard = MAILER.read();
     guard.as_ref().cloned()
 }
@@ -824,8 +1680,14 @@ pub mod prelude {
     pub use crate::MailError;
     pub use crate::Mailer;
     pub use crate::ToAddress;
-    pub use crate::{default_from, deliver, deliver_many, deliver_with, is_configured};
+    pub use crate::ToAddresses;
+    pub use crate::{
+        default_from, deliver, deliver_many, deliver_personalized, deliver_with, is_configured,
+    };
 
     #[cfg(feature = "local")]
     pub use crate::Storage;
+
+    #[cfg(feature = "derive")]
+    pub use crate::{CatalogEmail, EmailTemplate};
 }
@@ -0,0 +1,200 @@
+//! Link rewriting for HTML bodies.
+//!
+//! A [`LinkRewriter`] sees every `href` in an email's HTML body and can
+//! replace it, e.g. to add UTM parameters or point at a per-environment
+//! domain. Install one globally with
+//! [`set_link_rewriter()`](crate::set_link_rewriter) and the `deliver()`
+//! functions apply it to every email, whatever the provider:
+//!
+//! ```rust,ignore
+//! missive::set_link_rewriter(|url: &str, _email: &Email| {
+//!     url.starts_with("https://example.com").then(|| {
+//!         let sep = if url.contains('?') { '&' } else { '?' };
+//!         format!("{}{}utm_source=email", url, sep)
+//!     })
+//! });
+//! ```
+//!
+//! To rewrite links for a single mailer, use [`rewrite_links`] in an
+//! interceptor instead.
+
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::email::Email;
+
+/// Globally installed rewriter, applied by the `deliver()` functions.
+static LINK_REWRITER: RwLock<Option<Arc<dyn LinkRewriter>>> = RwLock::new(None);
+
+/// Rewrites links in HTML bodies before delivery.
+///
+/// Called once per `href` (including `mailto:` and relative links), with
+/// HTML entities in the URL decoded.
+pub trait LinkRewriter: Send + Sync {
+    /// Return the replacement URL, or `None` to keep `url`.
+    fn rewrite(&self, url: &str, email: &Email) -> Option<String>;
+}
+
+/// Blanket implementation for closures.
+impl<F> LinkRewriter for F
+where
+    F: Fn(&str, &Email) -> Option<String> + Send + Sync,
+{
+    fn rewrite(&self, url: &str, email: &Email) -> Option<String> {
+        (self)(url, email)
+    }
+}
+
+/// Apply `rewriter` to every link in the email's HTML body.
+pub fn rewrite_links(mut email: Email, rewriter: &dyn LinkRewriter) -> Email {
+    if let Some(html) = email.html_body.take() {
+        let html = rewrite_hrefs(&html, |url, _| rewriter.rewrite(url, &email));
+        email.html_body = Some(html);
+    }
+    email
+}
+
+pub(crate) fn set(rewriter: Option<Arc<dyn LinkRewriter>>) {
+    *LINK_REWRITER.write() = rewriter;
+}
+
+pub(crate) fn installed() -> Option<Arc<dyn LinkRewriter>> {
+    LINK_REWRITER.read().clone()
+}
+
+/// Replace the `href` of each `<a>` tag with `f(url, tag)`, if it returns one.
+pub(crate) fn rewrite_hrefs(html: &str, mut f: impl FnMut(&str, &str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = find_anchor(rest) {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start..start + end + 1];
+        out.push_str(&rest[..start]);
+        match href_value(tag) {
+            Some((value_start, value_end)) => {
+                let url = unescape(&tag[value_start..value_end]);
+                match f(&url, tag) {
+                    Some(new_url) => {
+                        out.push_str(&tag[..value_start]);
+                        out.push_str(&escape(&new_url));
+                        out.push_str(&tag[value_end..]);
+                    }
+                    None => out.push_str(tag),
+                }
+            }
+            None => out.push_str(tag),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Offset of the next `<a` tag.
+fn find_anchor(html: &str) -> Option<usize> {
+    let bytes = html.as_bytes();
+    let mut from = 0;
+    while let Some(pos) = html[from..].find('<') {
+        let start = from + pos;
+        let is_anchor = bytes
+            .get(start + 1)
+            .is_some_and(|b| b.eq_ignore_ascii_case(&b'a'))
+            && bytes
+                .get(start + 2)
+                .is_some_and(|b| b.is_ascii_whitespace());
+        if is_anchor {
+            return Some(start);
+        }
+        from = start + 1;
+    }
+    None
+}
+
+/// Byte range of the `href` attribute's value within a tag.
+fn href_value(tag: &str) -> Option<(usize, usize)> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find("href") {
+        let name_start = from + pos;
+        from = name_start + 4;
+        let preceded_by_space = lower[..name_start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_whitespace());
+        let after = lower[from..].trim_start();
+        if !preceded_by_space || !after.starts_with('=') {
+            continue;
+        }
+        let value = after[1..].trim_start();
+        let value_offset = tag.len() - value.len();
+        let quote = value.chars().next()?;
+        return if quote == '"' || quote == '\'' {
+            let end = value[1..].find(quote)?;
+            Some((value_offset + 1, value_offset + 1 + end))
+        } else {
+            let end = value
+                .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                .unwrap_or(value.len());
+            Some((value_offset, value_offset + end))
+        };
+    }
+    None
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_hrefs() {
+        let html = r#"<p><a href="https://example.com/?a=1&amp;b=2">x</a> <a name=top>t</a> <abbr>y</abbr> <A HREF=/rel>z</A></p>"#;
+        let mut seen = Vec::new();
+        let out = rewrite_hrefs(html, |url, _| {
+            seen.push(url.to_string());
+            Some(format!("{}&utm=1", url))
+        });
+
+        assert_eq!(seen, ["https://example.com/?a=1&b=2", "/rel"]);
+        assert_eq!(
+            out,
+            r#"<p><a href="https://example.com/?a=1&amp;b=2&amp;utm=1">x</a> <a name=top>t</a> <abbr>y</abbr> <A HREF=/rel&amp;utm=1>z</A></p>"#
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links() {
+        let email = Email::new()
+            .subject("Launch")
+            .html_body(r#"<a href="https://example.com">Go</a>"#);
+        let rewriter = |url: &str, email: &Email| {
+            Some(format!(
+                "{}?utm_campaign={}",
+                url,
+                email.subject.to_lowercase()
+            ))
+        };
+
+        let email = rewrite_links(email, &rewriter);
+        assert_eq!(
+            email.html_body.as_deref(),
+            Some(r#"<a href="https://example.com?utm_campaign=launch">Go</a>"#)
+        );
+    }
+}
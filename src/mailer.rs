@@ -41,11 +41,16 @@
 //! provider selection).
 
 use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
+use crate::bounce::BounceClass;
 use crate::email::Email;
 use crate::error::MailError;
 
+/// Emails the default [`Mailer::deliver_many`] sends at once.
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
 /// Result of a successful email delivery.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeliveryResult {
@@ -72,6 +77,136 @@ impl DeliveryResult {
             provider_response: Some(response),
         }
     }
+
+    /// Whether the provider rejected this message inside an accepted batch.
+    ///
+    /// Batch APIs such as Postmark's answer per message; a rejected entry has
+    /// an empty `message_id` and `"failed": true` in `provider_response`,
+    /// next to the provider's error code and message.
+    pub fn is_failed(&self) -> bool {
+        self.provider_response
+            .as_ref()
+            .and_then(|r| r.get("failed"))
+            .and_then(|f| f.as_bool())
+            .unwrap_or(false)
+    }
+}
+
+/// Where a sent message is, as reported by [`Mailer::message_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageStatus {
+    /// Accepted by the provider, not yet handed to the receiving server.
+    Queued,
+    /// Handed to the receiving server, without a delivery confirmation yet.
+    Sent,
+    /// Accepted by the receiving server.
+    Delivered,
+    /// Failed temporarily; the provider is still retrying.
+    Deferred,
+    /// Not delivered, and the provider has stopped trying.
+    Bounced(BounceClass),
+    /// Delivered, then reported as spam by the recipient.
+    Complained,
+    /// The provider has no record of the message (yet).
+    Unknown,
+}
+
+impl MessageStatus {
+    /// Whether the status can no longer change, except for a later complaint.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Delivered | Self::Bounced(_) | Self::Complained)
+    }
+}
+
+/// What a provider can do, for generic code that adapts per provider.
+///
+/// Returned by [`Mailer::capabilities`]. Queues, routers and chunkers can
+/// read it instead of matching on [`Mailer::provider_name`].
+///
+/// ```
+/// use missive::Capabilities;
+///
+/// // For a custom provider
+/// let caps = Capabilities {
+///     supports_batch: true,
+///     max_recipients: Some(100),
+///     ..Capabilities::default()
+/// };
+/// assert!(caps.supports_attachments_in_batch);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `deliver_many()` uses a provider batch API rather than one request per email.
+    pub supports_batch: bool,
+    /// `deliver_many()` accepts emails with attachments.
+    pub supports_attachments_in_batch: bool,
+    /// Delivery can be scheduled ([`Email::scheduled_at`]).
+    pub supports_scheduling: bool,
+    /// Maximum recipients (to + cc + bcc) per message, if limited.
+    pub max_recipients: Option<usize>,
+    /// Maximum encoded message size in bytes, if limited.
+    ///
+    /// Honors `EMAIL_MAX_MESSAGE_SIZE`, like the check in `deliver()`.
+    pub max_message_size: Option<usize>,
+    /// Hosted templates can be sent through provider options.
+    pub supports_templates: bool,
+    /// Maximum subject length in bytes, if limited.
+    pub max_subject_length: Option<usize>,
+    /// Maximum length of a custom header value in bytes, if limited.
+    pub max_header_length: Option<usize>,
+}
+
+impl Capabilities {
+    /// Defaults with the documented limits of a built-in provider, and the
+    /// RFC 5322 line length for subjects and header values.
+    pub(crate) fn for_provider(provider: &str) -> Self {
+        Self {
+            max_recipients: crate::validation::recipient_limit(provider),
+            max_message_size: crate::validation::size_limit(provider),
+            max_subject_length: Some(crate::validation::MAX_LINE_LENGTH),
+            max_header_length: Some(crate::validation::MAX_LINE_LENGTH),
+            ..Self::default()
+        }
+    }
+
+    /// What both `self` and `other` support, with the lower of each limit.
+    pub fn intersect(self, other: Self) -> Self {
+        fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+        Self {
+            supports_batch: self.supports_batch && other.supports_batch,
+            supports_attachments_in_batch: self.supports_attachments_in_batch
+                && other.supports_attachments_in_batch,
+            supports_scheduling: self.supports_scheduling && other.supports_scheduling,
+            max_recipients: min(self.max_recipients, other.max_recipients),
+            max_message_size: min(self.max_message_size, other.max_message_size),
+            supports_templates: self.supports_templates && other.supports_templates,
+            max_subject_length: min(self.max_subject_length, other.max_subject_length),
+            max_header_length: min(self.max_header_length, other.max_header_length),
+        }
+    }
+}
+
+impl Default for Capabilities {
+    /// What the default [`Mailer`] methods give: one `deliver()` per email
+    /// in `deliver_many()`, so attachments work, and no known limits.
+    fn default() -> Self {
+        Self {
+            supports_batch: false,
+            supports_attachments_in_batch: true,
+            supports_scheduling: false,
+            max_recipients: None,
+            max_message_size: None,
+            supports_templates: false,
+            max_subject_length: None,
+            max_header_length: None,
+        }
+    }
 }
 
 /// Trait for email delivery providers.
@@ -95,7 +230,14 @@ impl DeliveryResult {
 /// let result = mailer.deliver(&email).await?;
 /// println!("Sent with ID: {}", result.message_id);
 /// ```
-#[async_trait]
+///
+/// # WebAssembly
+///
+/// On `wasm32` targets the returned futures are not `Send`: `fetch` responses
+/// live on the JavaScript side. Custom providers there should use
+/// `#[async_trait(?Send)]`, as missive's own do.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait Mailer: Send + Sync {
     /// Send a single email.
     ///
@@ -127,17 +269,39 @@ pub trait Mailer: Send + Sync {
 
     /// Send multiple emails.
     ///
-    /// Default implementation calls `validate_batch()` first, then `deliver()` for each email.
+    /// Default implementation calls `validate_batch()` first, then `deliver()` for each email,
+    /// with up to [`batch_concurrency()`](Self::batch_concurrency) deliveries in flight.
+    /// Results are in the same order as `emails`. On the first error the remaining
+    /// deliveries are cancelled, but emails already in flight may have been sent.
     /// Providers with batch APIs can override for better performance.
     async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
         // Validate batch before sending
         self.validate_batch(emails)?;
 
-        let mut results = Vec::with_capacity(emails.len());
-        for email in emails {
-            results.push(self.deliver(email).await?);
-        }
-        Ok(results)
+        // Collected up front: boxed futures are lazy, and a `map` closure held
+        // across the await would make this future not `Send`.
+        let deliveries: Vec<_> = emails.iter().map(|email| self.deliver(email)).collect();
+        stream::iter(deliveries)
+            .buffered(self.batch_concurrency().max(1))
+            .try_collect()
+            .await
+    }
+
+    /// Send a personalized email to each of its recipients.
+    ///
+    /// See [`Email::personalize`]. Returns one result per personalization, in
+    /// order. The default renders each recipient's email locally and calls
+    /// `deliver_many()`; providers with native batch personalization override it.
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        let emails = email.render_personalizations();
+        self.deliver_many(&emails).await
+    }
+
+    /// Maximum concurrent deliveries in the default `deliver_many()`.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`]. Return `1` to send sequentially.
+    fn batch_concurrency(&self) -> usize {
+        DEFAULT_BATCH_CONCURRENCY
     }
 
     /// Get the provider name (for logging/debugging).
@@ -145,6 +309,16 @@ pub trait Mailer: Send + Sync {
         "unknown"
     }
 
+    /// What this provider supports.
+    ///
+    /// The default has the recipient and size limits of the provider named by
+    /// [`provider_name()`](Self::provider_name), and none of the optional
+    /// features. Providers override it to advertise batch APIs, scheduling
+    /// and templates.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::for_provider(self.provider_name())
+    }
+
     /// Validate configuration.
     ///
     /// Called at startup to verify required configuration is present.
@@ -152,6 +326,27 @@ pub trait Mailer: Send + Sync {
     fn validate_config(&self) -> Result<(), MailError> {
         Ok(())
     }
+
+    /// Verify the credentials against the provider without sending an email.
+    ///
+    /// Call this at startup to fail fast on a revoked or mistyped API key.
+    /// Providers with a cheap authenticated endpoint override it; the
+    /// default only runs [`validate_config()`](Self::validate_config).
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.validate_config()
+    }
+
+    /// Look up a sent message by the `message_id` its [`DeliveryResult`]
+    /// returned, for apps that can't receive webhooks.
+    ///
+    /// Postmark, Mailgun and Amazon SES implement it; the default fails with
+    /// [`MailError::UnsupportedFeature`].
+    async fn message_status(&self, _message_id: &str) -> Result<MessageStatus, MailError> {
+        Err(MailError::UnsupportedFeature(format!(
+            "{} does not report message status",
+            self.provider_name()
+        )))
+    }
 }
 
 /// Extension trait for optional mailer operations.
@@ -170,3 +365,98 @@ pub trait MailerExt: Mailer {
 
 // Auto-implement MailerExt for all Mailers
 impl<T: Mailer> MailerExt for T {}
+
+// Allow shared mailers (e.g. the global `Arc<dyn Mailer>`) to be wrapped.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Mailer + ?Sized> Mailer for std::sync::Arc<M> {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        (**self).deliver(email).await
+    }
+
+    fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        (**self).validate_batch(emails)
+    }
+
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        (**self).deliver_many(emails).await
+    }
+
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        (**self).deliver_personalized(email).await
+    }
+
+    fn batch_concurrency(&self) -> usize {
+        (**self).batch_concurrency()
+    }
+
+    fn provider_name(&self) -> &'static str {
+        (**self).provider_name()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        (**self).capabilities()
+    }
+
+    fn validate_config(&self) -> Result<(), MailError> {
+        (**self).validate_config()
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        (**self).health_check().await
+    }
+
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        (**self).message_status(message_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Custom;
+
+    #[async_trait]
+    impl Mailer for Custom {
+        async fn deliver(&self, _email: &Email) -> Result<DeliveryResult, MailError> {
+            Ok(DeliveryResult::new("1"))
+        }
+    }
+
+    #[test]
+    fn test_default_capabilities() {
+        let caps = Custom.capabilities();
+        assert!(!caps.supports_batch);
+        assert!(caps.supports_attachments_in_batch);
+        assert_eq!(caps.max_recipients, None);
+        assert_eq!(caps.max_subject_length, Some(998));
+        assert_eq!(
+            Capabilities::for_provider("postmark").max_recipients,
+            Some(50)
+        );
+    }
+
+    #[test]
+    fn test_intersect() {
+        let a = Capabilities {
+            supports_batch: true,
+            supports_scheduling: true,
+            max_recipients: Some(50),
+            ..Capabilities::default()
+        };
+        let b = Capabilities {
+            supports_batch: true,
+            supports_attachments_in_batch: false,
+            max_recipients: Some(1000),
+            max_message_size: Some(10),
+            ..Capabilities::default()
+        };
+        let both = a.intersect(b);
+        assert!(both.supports_batch);
+        assert!(!both.supports_scheduling);
+        assert!(!both.supports_attachments_in_batch);
+        assert_eq!(both.max_recipients, Some(50));
+        assert_eq!(both.max_message_size, Some(10));
+    }
+}
@@ -0,0 +1,332 @@
+//! Raw MIME message builder.
+//!
+//! Shared by providers that send raw messages and by the preview UI, so the
+//! "Source" view shows exactly what those providers transmit.
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::header::{fold, raw_block};
+
+/// Build a raw RFC 5322 / MIME message from an Email.
+///
+/// Used by providers that accept raw messages (Amazon SES, Mailgun) and by the
+/// preview UI's source view.
+pub(crate) fn build_mime_message(email: &Email) -> Result<Vec<u8>, MailError> {
+    let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
+
+    if email.to.is_empty() {
+        return Err(MailError::MissingField("to"));
+    }
+    email.validate_headers()?;
+
+    let mut message = String::new();
+    let boundary = format!(
+        "----=_Part_{}",
+        uuid::Uuid::new_v4().to_string().replace("-", "")
+    );
+
+    // Pre-formatted (signed) headers first, byte for byte
+    message.push_str(&raw_block(&email.raw_headers));
+
+    // Headers
+    message.push_str(&format!("From: {}\r\n", from.formatted()));
+    message.push_str(&format!(
+        "To: {}\r\n",
+        email
+            .to
+            .iter()
+            .map(|a| a.formatted())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    if !email.cc.is_empty() {
+        message.push_str(&format!(
+            "Cc: {}\r\n",
+            email
+                .cc
+                .iter()
+                .map(|a| a.formatted())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    // BCC is NOT included in headers (that's the point of BCC)
+    // But we need to include them as recipients in the RCPT TO command
+    // SES handles this via the raw message destinations
+
+    if let Some(reply_to) = email.reply_to.first() {
+        message.push_str(&format!("Reply-To: {}\r\n", reply_to.formatted()));
+    }
+
+    message.push_str(&fold("Subject", &email.subject));
+    message.push_str("\r\n");
+    message.push_str("MIME-Version: 1.0\r\n");
+
+    // Custom headers
+    for (name, value) in &email.headers {
+        message.push_str(&fold(name, value));
+        message.push_str("\r\n");
+    }
+
+    // Determine content structure
+    let has_text = email.text_body.is_some();
+    let has_html = email.html_body.is_some();
+    let has_attachments = !email.attachments.is_empty();
+    let has_inline = email.attachments.iter().any(|a| a.is_inline());
+
+    if !has_attachments {
+        // Simple case: no attachments
+        if has_text && has_html {
+            // Multipart/alternative
+            message.push_str(&format!(
+                "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+                boundary
+            ));
+
+            // Text part
+            message.push_str(&format!("--{}\r\n", boundary));
+            message.push_str("Content-Type: text/plain; charset=utf-8\r\n");
+            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
+            message.push_str(email.text_body.as_ref().unwrap());
+            message.push_str("\r\n");
+
+            // HTML part
+            message.push_str(&format!("--{}\r\n", boundary));
+            message.push_str("Content-Type: text/html; charset=utf-8\r\n");
+            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
+            message.push_str(email.html_body.as_ref().unwrap());
+            message.push_str("\r\n");
+
+            message.push_str(&format!("--{}--\r\n", boundary));
+        } else if has_html {
+            message.push_str("Content-Type: text/html; charset=utf-8\r\n");
+            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
+            message.push_str(email.html_body.as_ref().unwrap());
+        } else if has_text {
+            message.push_str("Content-Type: text/plain; charset=utf-8\r\n");
+            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
+            message.push_str(email.text_body.as_ref().unwrap());
+        } else {
+            message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+        }
+    } else {
+        // Complex case: with attachments
+        let mixed_boundary = format!(
+            "----=_Mixed_{}",
+            uuid::Uuid::new_v4().to_string().replace("-", "")
+        );
+        let alt_boundary = format!(
+            "----=_Alt_{}",
+            uuid::Uuid::new_v4().to_string().replace("-", "")
+        );
+        let related_boundary = format!(
+            "----=_Related_{}",
+            uuid::Uuid::new_v4().to_string().replace("-", "")
+        );
+
+        message.push_str(&format!(
+            "Content-Type: multipart/mixed; boundary=\"{}\"\r\n\r\n",
+            mixed_boundary
+        ));
+
+        // Body part
+        message.push_str(&format!("--{}\r\n", mixed_boundary));
+
+        if has_inline && has_html {
+            // Use multipart/related for inline attachments
+            message.push_str(&format!(
+                "Content-Type: multipart/related; boundary=\"{}\"\r\n\r\n",
+                related_boundary
+            ));
+
+            message.push_str(&format!("--{}\r\n", related_boundary));
+
+            if has_text {
+                // Multipart/alternative inside related
+                message.push_str(&format!(
+                    "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+                    alt_boundary
+                ));
+
+                message.push_str(&format!("--{}\r\n", alt_boundary));
+                message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+                message.push_str(email.text_body.as_ref().unwrap());
+                message.push_str("\r\n");
+
+                message.push_str(&format!("--{}\r\n", alt_boundary));
+                message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
+                message.push_str(email.html_body.as_ref().unwrap());
+                message.push_str("\r\n");
+
+                message.push_str(&format!("--{}--\r\n", alt_boundary));
+            } else {
+                message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
+                message.push_str(email.html_body.as_ref().unwrap());
+                message.push_str("\r\n");
+            }
+
+            // Inline attachments
+            for attachment in email.attachments.iter().filter(|a| a.is_inline()) {
+                message.push_str(&format!("--{}\r\n", related_boundary));
+                message.push_str(&format!("Content-Type: {}\r\n", attachment.content_type));
+                message.push_str("Content-Transfer-Encoding: base64\r\n");
+                message.push_str(&format!(
+                    "Content-Disposition: inline; filename=\"{}\"\r\n",
+                    attachment.filename
+                ));
+                if let Some(ref cid) = attachment.content_id {
+                    message.push_str(&format!("Content-ID: <{}>\r\n", cid));
+                }
+                message.push_str("\r\n");
+                message.push_str(&attachment.base64_data());
+                message.push_str("\r\n");
+            }
+
+            message.push_str(&format!("--{}--\r\n", related_boundary));
+        } else if has_text && has_html {
+            // Multipart/alternative
+            message.push_str(&format!(
+                "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+                alt_boundary
+            ));
+
+            message.push_str(&format!("--{}\r\n", alt_boundary));
+            message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+            message.push_str(email.text_body.as_ref().unwrap());
+            message.push_str("\r\n");
+
+            message.push_str(&format!("--{}\r\n", alt_boundary));
+            message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
+            message.push_str(email.html_body.as_ref().unwrap());
+            message.push_str("\r\n");
+
+            message.push_str(&format!("--{}--\r\n", alt_boundary));
+        } else if has_html {
+            message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
+            message.push_str(email.html_body.as_ref().unwrap());
+            message.push_str("\r\n");
+        } else if has_text {
+            message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+            message.push_str(email.text_body.as_ref().unwrap());
+            message.push_str("\r\n");
+        }
+
+        // Regular attachments
+        for attachment in email.attachments.iter().filter(|a| !a.is_inline()) {
+            message.push_str(&format!("--{}\r\n", mixed_boundary));
+            message.push_str(&format!("Content-Type: {}\r\n", attachment.content_type));
+            message.push_str("Content-Transfer-Encoding: base64\r\n");
+            message.push_str(&format!(
+                "Content-Disposition: attachment; filename=\"{}\"\r\n",
+                attachment.filename
+            ));
+            message.push_str("\r\n");
+            message.push_str(&attachment.base64_data());
+            message.push_str("\r\n");
+        }
+
+        message.push_str(&format!("--{}--\r\n", mixed_boundary));
+    }
+
+    Ok(message.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attachment::Attachment;
+
+    #[test]
+    fn test_simple_message() {
+        let email = Email::new()
+            .from(("Alice", "alice@example.com"))
+            .to("bob@example.com")
+            .subject("Hello")
+            .header("X-Custom", "value")
+            .request_read_receipt("alice@example.com")
+            .text_body("Hi Bob");
+
+        let raw = String::from_utf8(build_mime_message(&email).unwrap()).unwrap();
+
+        assert!(raw.contains("From: Alice <alice@example.com>\r\n"));
+        assert!(raw.contains("To: bob@example.com\r\n"));
+        assert!(raw.contains("Subject: Hello\r\n"));
+        assert!(raw.contains("X-Custom: value\r\n"));
+        assert!(raw.contains("Disposition-Notification-To: alice@example.com\r\n"));
+        assert!(raw.contains("Content-Type: text/plain; charset=utf-8\r\n"));
+        assert!(raw.ends_with("Hi Bob"));
+    }
+
+    #[test]
+    fn test_multipart_with_attachment() {
+        let email = Email::new()
+            .from("alice@example.com")
+            .to("bob@example.com")
+            .text_body("Text")
+            .html_body("<p>HTML</p>")
+            .attachment(Attachment::from_bytes("a.txt", b"hello".to_vec()));
+
+        let raw = String::from_utf8(build_mime_message(&email).unwrap()).unwrap();
+
+        assert!(raw.contains("Content-Type: multipart/mixed;"));
+        assert!(raw.contains("Content-Type: multipart/alternative;"));
+        assert!(raw.contains("Content-Disposition: attachment; filename=\"a.txt\""));
+        assert!(raw.contains("aGVsbG8="));
+    }
+
+    #[test]
+    fn test_requires_from_and_to() {
+        assert!(build_mime_message(&Email::new().to("bob@example.com")).is_err());
+        assert!(build_mime_message(&Email::new().from("alice@example.com")).is_err());
+    }
+
+    #[test]
+    fn test_rejects_header_injection() {
+        let email = Email::new()
+            .from("alice@example.com")
+            .to("bob@example.com")
+            .header("X-Note", "hi\r\nBcc: eve@example.com");
+
+        assert!(matches!(
+            build_mime_message(&email),
+            Err(MailError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_folds_long_headers() {
+        let value = vec!["token"; 30].join(" ");
+        let email = Email::new()
+            .from("alice@example.com")
+            .to("bob@example.com")
+            .header("X-Long", &value)
+            .text_body("Hi");
+
+        let raw = String::from_utf8(build_mime_message(&email).unwrap()).unwrap();
+
+        assert!(raw.contains("X-Long: token"));
+        assert!(raw.contains("\r\n token"));
+        assert!(raw.lines().all(|line| line.len() <= 78));
+    }
+
+    #[test]
+    fn test_raw_headers_come_first_unchanged() {
+        let seal = format!(
+            "ARC-Seal: i=1; a=rsa-sha256; cv=none;\r\n\tb={}",
+            "x".repeat(90)
+        );
+        let email = Email::new()
+            .from("alice@example.com")
+            .to("bob@example.com")
+            .raw_header("ARC-Seal: i=2; cv=pass; b=c2Vjb25k")
+            .raw_header(seal.clone())
+            .text_body("Hi");
+
+        let raw = String::from_utf8(build_mime_message(&email).unwrap()).unwrap();
+
+        let expected = format!("ARC-Seal: i=2; cv=pass; b=c2Vjb25k\r\n{}\r\nFrom: ", seal);
+        assert!(raw.starts_with(&expected));
+    }
+}
@@ -0,0 +1,443 @@
+//! Best-effort translation of `provider_options` between providers.
+//!
+//! Provider options are provider-specific, so switching `EMAIL_PROVIDER`
+//! normally means rewriting every call site that sets them. The global
+//! `deliver()` functions translate the common ones automatically, and a
+//! [`Router`](crate::Router) translates them again for the route it picks:
+//!
+//! | Concept | Keys recognized |
+//! |---------|-----------------|
+//! | Tags | `tag` (Postmark), `categories` (SendGrid), `category` (Mailtrap), `tags` (string arrays) |
+//! | Metadata | `metadata` (Postmark), `custom_args` (SendGrid), `custom_vars` (Mailgun), `custom_variables` (Mailtrap), `tags` (`{name, value}` arrays) |
+//! | Template data | `template_model` (Postmark), `dynamic_template_data` (SendGrid), `params` (Brevo), `variables` (Mailjet), `merge_info` (ZeptoMail), `message_data` (Customer.io) |
+//! | Scheduling | `scheduled_at` (Resend, [`Email::scheduled_at`]), `schedule_at` (Brevo), `send_at` (SendGrid, Customer.io), `deliverytime` (Mailgun) |
+//!
+//! ```rust,ignore
+//! // Written for Postmark...
+//! let email = Email::new()
+//!     .provider_option("tag", "welcome")
+//!     .provider_option("template_model", json!({"name": "Alice"}));
+//!
+//! // ...becomes `categories: ["welcome"]` and `dynamic_template_data` on SendGrid.
+//! let warnings = translate_options(&mut email, "sendgrid");
+//! ```
+//!
+//! Options that have no equivalent on the target provider are left in place
+//! (the provider ignores them) and reported as warnings.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+
+use crate::email::Email;
+
+/// Option keys each provider understands.
+fn known_keys(provider: &str) -> Option<&'static [&'static str]> {
+    let keys: &'static [&'static str] = match provider {
+        "resend" => &["tags", "scheduled_at", "template", "idempotency_key"],
+        "postmark" => &[
+            "tag",
+            "metadata",
+            "message_stream",
+            "track_opens",
+            "track_links",
+            "inline_css",
+            "template_id",
+            "template_alias",
+            "template_model",
+        ],
+        "sendgrid" => &[
+            "categories",
+            "custom_args",
+            "dynamic_template_data",
+            "template_id",
+            "send_at",
+            "batch_id",
+            "asm",
+            "ip_pool_name",
+            "mail_settings",
+            "tracking_settings",
+            "personalizations",
+            "substitutions",
+        ],
+        "brevo" => &["tags", "params", "template_id", "schedule_at", "sender_id"],
+        "mailgun" => &[
+            "tags",
+            "custom_vars",
+            "recipient_vars",
+            "sending_options",
+            "template_name",
+            "template_options",
+            "deliverytime",
+        ],
+        "amazon_ses" => &[
+            "tags",
+            "configuration_set_name",
+            "template",
+            "template_data",
+        ],
+        "mailtrap" => &["category", "custom_variables"],
+        "mailjet" => &[
+            "variables",
+            "template_id",
+            "template_error_deliver",
+            "template_error_reporting",
+            "custom_id",
+            "event_payload",
+        ],
+        "zeptomail" => &[
+            "template_key",
+            "template_alias",
+            "merge_info",
+            "track_clicks",
+            "track_opens",
+            "client_reference",
+        ],
+        "customerio" => &[
+            "transactional_message_id",
+            "message_data",
+            "identifiers",
+            "send_at",
+            "disable_message_retention",
+            "send_to_unsubscribed",
+            "tracked",
+            "queue_draft",
+            "preheader",
+            "language",
+            "disable_css_preprocessing",
+        ],
+        "unsent" => &[],
+        _ => return None,
+    };
+    Some(keys)
+}
+
+/// Providers whose `tags` option is an array of `{name, value}` objects.
+fn uses_named_tags(provider: &str) -> bool {
+    matches!(provider, "resend" | "amazon_ses")
+}
+
+/// Portable meaning extracted from a provider option.
+enum Concept {
+    Tags(Vec<String>),
+    Metadata(Map<String, Value>),
+    TemplateData(Value),
+    ScheduleAt(DateTime<Utc>),
+}
+
+/// Translate provider options on `email` for the target `provider`.
+///
+/// Returns a warning for every option that could not be translated.
+/// Unknown providers (e.g. `local`, `logger`) are left untouched.
+pub fn translate_options(email: &mut Email, provider: &str) -> Vec<String> {
+    let Some(known) = known_keys(provider) else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    let mut concepts = Vec::new();
+
+    let mut keys: Vec<String> = email.provider_options.keys().cloned().collect();
+    keys.sort();
+
+    for key in keys {
+        let value = &email.provider_options[&key];
+        let foreign = !known.contains(&key.as_str());
+        let mismatched_tags = key == "tags" && !foreign && !tags_match(value, provider);
+        if !foreign && !mismatched_tags {
+            continue;
+        }
+
+        match extract(&key, value) {
+            Some(concept) => {
+                let original = email.provider_options.remove(&key);
+                concepts.push((key, original, concept));
+            }
+            None => warnings.push(format!(
+                "provider option '{}' has no equivalent on {}",
+                key, provider
+            )),
+        }
+    }
+
+    for (key, original, concept) in concepts {
+        if let Err(warning) = apply(email, provider, &key, concept) {
+            // Keep the original so nothing is silently lost
+            if let Some(original) = original {
+                email.provider_options.insert(key, original);
+            }
+            warnings.push(warning);
+        }
+    }
+
+    warnings
+}
+
+/// Check that a `tags` value has the shape the target provider expects.
+fn tags_match(value: &Value, provider: &str) -> bool {
+    let Some(items) = value.as_array() else {
+        return true;
+    };
+    if uses_named_tags(provider) {
+        items.iter().all(Value::is_object)
+    } else {
+        items.iter().all(Value::is_string)
+    }
+}
+
+fn extract(key: &str, value: &Value) -> Option<Concept> {
+    match key {
+        "tag" | "category" => value.as_str().map(|s| Concept::Tags(vec![s.to_string()])),
+        "categories" => string_array(value).map(Concept::Tags),
+        "tags" => {
+            if let Some(tags) = string_array(value) {
+                return Some(Concept::Tags(tags));
+            }
+            let mut metadata = Map::new();
+            for item in value.as_array()? {
+                let name = item.get("name")?.as_str()?;
+                metadata.insert(name.to_string(), item.get("value")?.clone());
+            }
+            Some(Concept::Metadata(metadata))
+        }
+        "metadata" | "custom_args" | "custom_vars" | "custom_variables" => {
+            value.as_object().cloned().map(Concept::Metadata)
+        }
+        "template_model"
+        | "dynamic_template_data"
+        | "params"
+        | "variables"
+        | "merge_info"
+        | "message_data" => Some(Concept::TemplateData(value.clone())),
+        "scheduled_at" | "schedule_at" => value
+            .as_str()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| Concept::ScheduleAt(dt.with_timezone(&Utc))),
+        "deliverytime" => value
+            .as_str()
+            .and_then(|s| DateTime::parse_from_rfc2822(s).ok())
+            .map(|dt| Concept::ScheduleAt(dt.with_timezone(&Utc))),
+        "send_at" => value
+            .as_i64()
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .map(Concept::ScheduleAt),
+        _ => None,
+    }
+}
+
+fn string_array(value: &Value) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+fn apply(email: &mut Email, provider: &str, source: &str, concept: Concept) -> Result<(), String> {
+    let unsupported = |what: &str| {
+        format!(
+            "provider option '{}' ({}) has no equivalent on {}",
+            source, what, provider
+        )
+    };
+    let options = &mut email.provider_options;
+
+    match concept {
+        Concept::Tags(tags) => match provider {
+            "postmark" | "mailtrap" => {
+                let key = if provider == "postmark" {
+                    "tag"
+                } else {
+                    "category"
+                };
+                if tags.len() > 1 {
+                    tracing::warn!(
+                        provider = provider,
+                        dropped = ?&tags[1..],
+                        "Only one tag is supported, keeping the first"
+                    );
+                }
+                if let Some(first) = tags.into_iter().next() {
+                    options.entry(key.to_string()).or_insert(json!(first));
+                }
+            }
+            "sendgrid" => append(options, "categories", tags.into_iter().map(Value::from)),
+            "brevo" | "mailgun" => append(options, "tags", tags.into_iter().map(Value::from)),
+            "resend" | "amazon_ses" => append(
+                options,
+                "tags",
+                tags.into_iter()
+                    .map(|t| json!({"name": t, "value": "true"})),
+            ),
+            _ => return Err(unsupported("tags")),
+        },
+        Concept::Metadata(metadata) => match provider {
+            "postmark" => merge(options, "metadata", metadata),
+            "sendgrid" => merge(options, "custom_args", metadata),
+            "mailgun" => merge(options, "custom_vars", metadata),
+            "mailtrap" => merge(options, "custom_variables", metadata),
+            "resend" | "amazon_ses" => append(
+                options,
+                "tags",
+                metadata.into_iter().map(|(name, value)| {
+                    let value = match value {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    json!({"name": name, "value": value})
+                }),
+            ),
+            _ => return Err(unsupported("metadata")),
+        },
+        Concept::TemplateData(data) => {
+            let key = match provider {
+                "postmark" => "template_model",
+                "sendgrid" => "dynamic_template_data",
+                "brevo" => "params",
+                "mailjet" => "variables",
+                "zeptomail" => "merge_info",
+                "customerio" => "message_data",
+                _ => return Err(unsupported("template data")),
+            };
+            options.entry(key.to_string()).or_insert(data);
+        }
+        Concept::ScheduleAt(at) => {
+            let (key, value) = match provider {
+                "resend" => ("scheduled_at", json!(at.to_rfc3339())),
+                "brevo" => ("schedule_at", json!(at.to_rfc3339())),
+                "sendgrid" | "customerio" => ("send_at", json!(at.timestamp())),
+                "mailgun" => ("deliverytime", json!(at.to_rfc2822())),
+                _ => return Err(unsupported("scheduling")),
+            };
+            options.entry(key.to_string()).or_insert(value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Append values to an array option, creating it if missing.
+fn append(options: &mut HashMap<String, Value>, key: &str, values: impl Iterator<Item = Value>) {
+    let entry = options.entry(key.to_string()).or_insert_with(|| json!([]));
+    if let Some(items) = entry.as_array_mut() {
+        items.extend(values);
+    }
+}
+
+/// Merge entries into an object option, keeping existing keys.
+fn merge(options: &mut HashMap<String, Value>, key: &str, values: Map<String, Value>) {
+    let entry = options.entry(key.to_string()).or_insert_with(|| json!({}));
+    if let Some(object) = entry.as_object_mut() {
+        for (k, v) in values {
+            object.entry(k).or_insert(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_postmark_to_sendgrid() {
+        let mut email = Email::new()
+            .provider_option("tag", "welcome")
+            .provider_option("template_model", json!({"name": "Alice"}))
+            .provider_option("metadata", json!({"user_id": "123"}));
+
+        let warnings = translate_options(&mut email, "sendgrid");
+
+        assert!(warnings.is_empty());
+        assert_eq!(email.provider_options["categories"], json!(["welcome"]));
+        assert_eq!(
+            email.provider_options["dynamic_template_data"],
+            json!({"name": "Alice"})
+        );
+        assert_eq!(
+            email.provider_options["custom_args"],
+            json!({"user_id": "123"})
+        );
+        assert!(!email.provider_options.contains_key("tag"));
+    }
+
+    #[test]
+    fn test_mailgun_tags_to_resend() {
+        let mut email = Email::new().provider_option("tags", json!(["welcome"]));
+
+        translate_options(&mut email, "resend");
+
+        assert_eq!(
+            email.provider_options["tags"],
+            json!([{"name": "welcome", "value": "true"}])
+        );
+    }
+
+    #[test]
+    fn test_resend_tags_to_postmark_metadata() {
+        let mut email =
+            Email::new().provider_option("tags", json!([{"name": "plan", "value": "pro"}]));
+
+        translate_options(&mut email, "postmark");
+
+        assert_eq!(email.provider_options["metadata"], json!({"plan": "pro"}));
+    }
+
+    #[test]
+    fn test_schedule_to_sendgrid() {
+        let mut email = Email::new().provider_option("scheduled_at", "2030-01-01T00:00:00Z");
+
+        translate_options(&mut email, "sendgrid");
+
+        assert_eq!(email.provider_options["send_at"], json!(1893456000));
+    }
+
+    #[test]
+    fn test_schedule_to_mailgun() {
+        let at = "2030-01-01T09:30:00Z".parse().unwrap();
+        let mut email = Email::new().scheduled_at(at);
+
+        assert!(translate_options(&mut email, "mailgun").is_empty());
+
+        assert_eq!(
+            email.provider_options["deliverytime"],
+            json!("Tue, 1 Jan 2030 09:30:00 +0000")
+        );
+        assert!(!email.provider_options.contains_key("scheduled_at"));
+    }
+
+    #[test]
+    fn test_template_data_and_schedule_to_customerio() {
+        let mut email = Email::new()
+            .provider_option("template_model", json!({"name": "Alice"}))
+            .provider_option("scheduled_at", "2030-01-01T00:00:00Z");
+
+        assert!(translate_options(&mut email, "customerio").is_empty());
+
+        assert_eq!(
+            email.provider_options["message_data"],
+            json!({"name": "Alice"})
+        );
+        assert_eq!(email.provider_options["send_at"], json!(1893456000));
+    }
+
+    #[test]
+    fn test_untranslatable_is_kept_with_warning() {
+        let mut email = Email::new()
+            .provider_option("message_stream", "broadcasts")
+            .provider_option("template_model", json!({}));
+
+        let warnings = translate_options(&mut email, "resend");
+
+        assert_eq!(warnings.len(), 2);
+        assert!(email.provider_options.contains_key("message_stream"));
+    }
+
+    #[test]
+    fn test_native_options_untouched() {
+        let mut email = Email::new().provider_option("tag", "welcome");
+        assert!(translate_options(&mut email, "postmark").is_empty());
+        assert!(translate_options(&mut email, "local").is_empty());
+        assert_eq!(email.provider_options["tag"], json!("welcome"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Bulk personalization (mail merge).
+//!
+//! One email, many recipients, each with their own data:
+//!
+//! ```rust,ignore
+//! use missive::{Email, Mailer};
+//! use serde_json::json;
+//!
+//! let email = Email::new()
+//!     .from("news@example.com")
+//!     .subject("Hi {{ name }}")
+//!     .text_body("Your plan: {{ account.plan }}")
+//!     .personalize(vec![
+//!         ("alice@example.com", json!({"name": "Alice", "account": {"plan": "pro"}})),
+//!         ("bob@example.com", json!({"name": "Bob", "account": {"plan": "free"}})),
+//!     ]);
+//!
+//! let results = mailer.deliver_personalized(&email).await?;
+//! ```
+//!
+//! Providers with native batch personalization send the whole merge in as
+//! few requests as possible:
+//!
+//! | Provider | Mapping |
+//! |----------|---------|
+//! | SendGrid | One `personalizations` entry per recipient when `template_id` is set |
+//! | Brevo | `messageVersions`, data in `params` |
+//! | Mailjet | One batch of messages, data in `variables` |
+//! | Amazon SES | `SendBulkTemplatedEmail` when the `template` option is set |
+//! | Mailgun | Up to 1000 recipients per request, data in `recipient-variables` |
+//!
+//! Everything else renders one email per recipient locally and sends them
+//! with `deliver_many`. Local rendering replaces `{{ key }}` placeholders in
+//! the subject and bodies with the recipient's data (falling back to
+//! `assigns`); dotted keys reach into nested objects and unknown placeholders
+//! are left as they are.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::address::{Address, ToAddress};
+use crate::email::Email;
+
+/// Provider options that carry template data.
+const DATA_OPTIONS: &[&str] = &[
+    "template_model",
+    "dynamic_template_data",
+    "params",
+    "variables",
+    "template_data",
+];
+
+/// One recipient of a personalized email and their template data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Personalization {
+    /// The recipient.
+    pub to: Address,
+    /// Template data for this recipient, usually a JSON object.
+    #[serde(default)]
+    pub data: Value,
+}
+
+impl Email {
+    /// Send this email to each recipient with their own template data.
+    ///
+    /// Can be called multiple times to add more recipients. Deliver with
+    /// [`Mailer::deliver_personalized`](crate::Mailer::deliver_personalized)
+    /// or [`deliver_personalized`](crate::deliver_personalized); `to` is
+    /// ignored when personalizations are present.
+    pub fn personalize<A: ToAddress>(
+        mut self,
+        recipients: impl IntoIterator<Item = (A, Value)>,
+    ) -> Self {
+        self.personalizations
+            .extend(recipients.into_iter().map(|(to, data)| Personalization {
+                to: to.to_address(),
+                data,
+            }));
+        self
+    }
+
+    /// Render one email per personalization.
+    ///
+    /// Each email goes to a single recipient, has its placeholders replaced,
+    /// and has the recipient's data merged into `assigns` and into any
+    /// template data provider option (`template_model`,
+    /// `dynamic_template_data`, `params`, `variables`, `template_data`).
+    pub fn render_personalizations(&self) -> Vec<Email> {
+        render(self, None)
+    }
+}
+
+/// Render personalized emails, also merging the data into `data_option`
+/// even if the email doesn't set it.
+pub(crate) fn render(email: &Email, data_option: Option<&str>) -> Vec<Email> {
+    email
+        .personalizations
+        .iter()
+        .map(|p| {
+            let context = context(email, p);
+
+            let mut out = email.clone();
+            out.personalizations.clear();
+            out.to = vec![p.to.clone()];
+            out.subject = render_str(&email.subject, &context);
+            out.text_body = email.text_body.as_ref().map(|t| render_str(t, &context));
+            out.html_body = email.html_body.as_ref().map(|t| render_str(t, &context));
+
+            if let Value::Object(data) = &p.data {
+                out.assigns
+                    .extend(data.iter().map(|(k, v)| (k.clone(), v.clone())));
+                for key in DATA_OPTIONS {
+                    let present = out.provider_options.contains_key(*key);
+                    if present || data_option == Some(key) {
+                        merge(
+                            out.provider_options.entry(key.to_string()).or_default(),
+                            data,
+                        );
+                    }
+                }
+            }
+            out
+        })
+        .collect()
+}
+
+/// Placeholder values for `p`: the email's `assigns` overlaid with the
+/// recipient's data.
+pub(crate) fn context(email: &Email, p: &Personalization) -> Value {
+    let mut context: Map<String, Value> = email
+        .assigns
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    if let Value::Object(data) = &p.data {
+        context.extend(data.clone());
+    }
+    Value::Object(context)
+}
+
+/// The recipient's data merged over `base`, as sent to providers that
+/// substitute template data themselves.
+#[cfg(any(feature = "sendgrid", feature = "amazon_ses"))]
+pub(crate) fn merged(base: Option<&Value>, data: &Value) -> Value {
+    let mut value = base.cloned().unwrap_or(Value::Null);
+    match data {
+        Value::Object(data) => merge(&mut value, data),
+        _ if value.is_null() => value = Value::Object(Map::new()),
+        _ => {}
+    }
+    value
+}
+
+fn merge(target: &mut Value, data: &Map<String, Value>) {
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        target.extend(data.clone());
+    }
+}
+
+/// Replace `{{ key }}` placeholders with values from `context`.
+pub(crate) fn render_str(template: &str, context: &Value) -> String {
+    replace(template, |key| lookup_str(context, key))
+}
+
+/// Replace each `{{ key }}` placeholder with `f(key)`, leaving it as it is
+/// when `f` returns `None`.
+pub(crate) fn replace(template: &str, mut f: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + len + 2];
+        let key = placeholder[2..placeholder.len() - 2].trim();
+        out.push_str(&rest[..start]);
+        match f(key) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The value of `key` in `context` as placeholder text.
+pub(crate) fn lookup_str(context: &Value, key: &str) -> Option<String> {
+    match lookup(context, key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => Some(String::new()),
+        value => Some(value.to_string()),
+    }
+}
+
+fn lookup<'a>(context: &'a Value, key: &str) -> Option<&'a Value> {
+    if key.is_empty() {
+        return None;
+    }
+    key.split('.')
+        .try_fold(context, |value, part| value.get(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_str() {
+        let context = json!({"name": "Alice", "account": {"plan": "pro", "seats": 3}});
+        assert_eq!(
+            render_str(
+                "Hi {{ name }}, {{account.plan}} x{{ account.seats }}",
+                &context
+            ),
+            "Hi Alice, pro x3"
+        );
+        assert_eq!(
+            render_str("{{ missing }} {{ name", &context),
+            "{{ missing }} {{ name"
+        );
+    }
+
+    #[test]
+    fn test_render_personalizations() {
+        let email = Email::new()
+            .from("news@example.com")
+            .to("ignored@example.com")
+            .subject("Hi {{ name }}")
+            .text_body("{{ greeting }}, {{ name }}!")
+            .assign("greeting", "Hello")
+            .provider_option("template_model", json!({"company": "Acme"}))
+            .personalize(vec![
+                ("alice@example.com", json!({"name": "Alice"})),
+                ("bob@example.com", json!({"name": "Bob", "greeting": "Yo"})),
+            ]);
+
+        let emails = email.render_personalizations();
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[0].to, vec![Address::new("alice@example.com")]);
+        assert_eq!(emails[0].subject, "Hi Alice");
+        assert_eq!(emails[0].text_body.as_deref(), Some("Hello, Alice!"));
+        assert_eq!(emails[1].text_body.as_deref(), Some("Yo, Bob!"));
+        assert_eq!(emails[1].assigns["name"], "Bob");
+        assert_eq!(
+            emails[1].provider_options["template_model"],
+            json!({"company": "Acme", "name": "Bob", "greeting": "Yo"})
+        );
+        assert!(emails[1].personalizations.is_empty());
+        assert!(!emails[1].provider_options.contains_key("params"));
+
+        let emails = render(&email, Some("params"));
+        assert_eq!(
+            emails[0].provider_options["params"],
+            json!({"name": "Alice"})
+        );
+    }
+}
@@ -1,28 +1,86 @@
 //! Actix-web adapter for mailbox preview.
+//!
+//! Storage calls run on Actix's blocking pool so custom backends may perform
+//! blocking I/O without stalling the async runtime. Endpoint logic lives in
+//! [`core`](super::core); handlers here only extract parameters.
 
 use std::sync::Arc;
 
+use actix_web::http::StatusCode;
 use actix_web::{web, HttpResponse, Responder};
-use serde::Deserialize;
 
-use crate::storage::MemoryStorage;
+use crate::storage::Storage;
 
-use super::core::{self, EmailListResponse, PreviewConfig};
+use super::core::{
+    self, ForwardQuery, IndexQuery, ListQuery, LiveUpdates, MailboxQuery, PreviewConfig,
+    PreviewResponse,
+};
 
 /// Shared state for routes.
 #[derive(Clone)]
 pub struct AppState {
-    pub storage: Arc<MemoryStorage>,
+    pub storage: Arc<dyn Storage>,
     pub config: PreviewConfig,
 }
 
+impl AppState {
+    /// Create state for any storage backend with default config.
+    ///
+    /// Accepts any storage backend, including `Arc<MemoryStorage>` and `Arc<dyn Storage>`.
+    pub fn new(storage: impl Storage + 'static) -> Self {
+        Self::with_config(storage, PreviewConfig::default())
+    }
+
+    /// Create state with CSP nonce configuration.
+    pub fn with_config(storage: impl Storage + 'static, config: PreviewConfig) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            config,
+        }
+    }
+
+    /// Run a storage operation on the blocking thread pool.
+    async fn with_storage<T, F>(&self, f: F) -> Result<T, actix_web::Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn Storage) -> T + Send + 'static,
+    {
+        let storage = Arc::clone(&self.storage);
+        Ok(web::block(move || f(storage.as_ref())).await?)
+    }
+
+    /// Run a shared handler and convert its response.
+    async fn respond<F>(&self, f: F) -> actix_web::Result<HttpResponse>
+    where
+        F: FnOnce(&dyn Storage) -> PreviewResponse + Send + 'static,
+    {
+        Ok(into_response(self.with_storage(f).await?))
+    }
+}
+
+fn into_response(response: PreviewResponse) -> HttpResponse {
+    let status =
+        StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut builder = HttpResponse::build(status);
+    for header in response.headers {
+        builder.insert_header(header);
+    }
+    builder.body(response.body)
+}
+
 /// Configure routes on an Actix scope.
 pub fn configure(cfg: &mut web::ServiceConfig, state: AppState) {
     cfg.app_data(web::Data::new(state))
         .route("/", web::get().to(index))
         .route("/json", web::get().to(list_json))
+        .route("/events", web::get().to(events))
         .route("/{id}", web::get().to(view_email))
+        .route("/{id}", web::delete().to(delete_email))
+        .route("/{id}/read", web::post().to(mark_read))
+        .route("/{id}/forward", web::post().to(forward_email))
         .route("/{id}/html", web::get().to(email_html))
+        .route("/{id}/source", web::get().to(email_source))
+        .route("/{id}/compat", web::get().to(email_compat))
         .route(
             "/{id}/attachments/{idx}",
             web::get().to(download_attachment),
@@ -30,76 +88,165 @@ pub fn configure(cfg: &mut web::ServiceConfig, state: AppState) {
         .route("/clear", web::post().to(clear_all));
 }
 
-/// Query params for CSP nonce override.
-#[derive(Debug, Deserialize, Default)]
-pub struct IndexQuery {
-    script_nonce: Option<String>,
-    style_nonce: Option<String>,
+/// GET / - Render the mailbox UI.
+async fn index(
+    state: web::Data<AppState>,
+    query: web::Query<IndexQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let config = state.config.clone();
+    let query = query.into_inner();
+    state
+        .respond(move |storage| core::handle_index(storage, &config, query))
+        .await
 }
 
-/// GET / - Render the mailbox UI.
-async fn index(state: web::Data<AppState>, query: web::Query<IndexQuery>) -> impl Responder {
-    let emails = core::list_emails(&state.storage);
-    let script_nonce = query
-        .script_nonce
-        .clone()
-        .or(state.config.script_nonce.clone());
-    let style_nonce = query
-        .style_nonce
-        .clone()
-        .or(state.config.style_nonce.clone());
-    let html = core::render_index(&emails, script_nonce, style_nonce);
-    HttpResponse::Ok()
-        .content_type("text/html; charset=utf-8")
-        .body(html)
-}
-
-/// GET /json - Return all emails as JSON.
-async fn list_json(state: web::Data<AppState>) -> impl Responder {
-    let emails = core::list_emails(&state.storage);
-    HttpResponse::Ok().json(EmailListResponse { data: emails })
+/// GET /json - Return emails as JSON.
+async fn list_json(
+    state: web::Data<AppState>,
+    query: web::Query<ListQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let query = query.into_inner();
+    state
+        .respond(move |storage| core::handle_list(storage, query))
+        .await
+}
+
+/// GET /events - Stream new and removed emails as server-sent events.
+async fn events(
+    state: web::Data<AppState>,
+    query: web::Query<MailboxQuery>,
+) -> actix_web::Result<impl Responder> {
+    let mailbox = query.into_inner().mailbox;
+    let updates = state
+        .with_storage(move |storage| LiveUpdates::new(storage, mailbox))
+        .await?;
+
+    let stream = futures_util::stream::unfold((state, updates), |(state, mut updates)| async move {
+        loop {
+            tokio::time::sleep(core::LIVE_POLL_INTERVAL).await;
+            let (next, frames) = state
+                .with_storage(move |storage| {
+                    let frames = updates.poll(storage);
+                    (updates, frames)
+                })
+                .await
+                .ok()?;
+            updates = next;
+            if let Some(frames) = frames {
+                let chunk = Ok::<_, actix_web::Error>(web::Bytes::from(frames));
+                return Some((chunk, (state, updates)));
+            }
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream))
 }
 
 /// GET /{id} - View a single email as JSON.
-async fn view_email(state: web::Data<AppState>, path: web::Path<String>) -> impl Responder {
+async fn view_email(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
     let id = path.into_inner();
-    match core::get_email(&state.storage, &id) {
-        Some(email) => HttpResponse::Ok().json(email),
-        None => HttpResponse::NotFound().finish(),
-    }
+    state
+        .respond(move |storage| core::handle_email(storage, &id))
+        .await
+}
+
+/// DELETE /{id} - Delete a single email.
+async fn delete_email(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let id = path.into_inner();
+    state
+        .respond(move |storage| core::handle_delete(storage, &id))
+        .await
+}
+
+/// POST /{id}/read - Mark an email as read.
+async fn mark_read(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let id = path.into_inner();
+    state
+        .respond(move |storage| core::handle_mark_read(storage, &id))
+        .await
+}
+
+/// POST /{id}/forward - Deliver a copy to a real address.
+async fn forward_email(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ForwardQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let id = path.into_inner();
+    let query = query.into_inner();
+    let response = match state
+        .with_storage(move |storage| core::forward_email(storage, &id, query))
+        .await?
+    {
+        Ok(email) => core::handle_forward(email).await,
+        Err(response) => response,
+    };
+    Ok(into_response(response))
 }
 
 /// GET /{id}/html - Return raw HTML body for iframe embedding.
-async fn email_html(state: web::Data<AppState>, path: web::Path<String>) -> impl Responder {
+async fn email_html(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
     let id = path.into_inner();
-    match core::get_email_html(&state.storage, &id) {
-        Some(html) => HttpResponse::Ok()
-            .content_type("text/html; charset=utf-8")
-            .body(html),
-        None => HttpResponse::NotFound().finish(),
-    }
+    state
+        .respond(move |storage| core::handle_html(storage, &id))
+        .await
+}
+
+/// GET /{id}/source - Return the raw MIME message as plain text.
+async fn email_source(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let id = path.into_inner();
+    state
+        .respond(move |storage| core::handle_source(storage, &id))
+        .await
+}
+
+/// GET /{id}/compat - Return email-client compatibility warnings as JSON.
+async fn email_compat(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let id = path.into_inner();
+    state
+        .respond(move |storage| core::handle_compat(storage, &id))
+        .await
 }
 
 /// GET /{id}/attachments/{idx} - Download an attachment.
 async fn download_attachment(
     state: web::Data<AppState>,
     path: web::Path<(String, usize)>,
-) -> impl Responder {
+) -> actix_web::Result<HttpResponse> {
     let (id, idx) = path.into_inner();
-    match core::get_attachment(&state.storage, &id, idx) {
-        Some(att) => HttpResponse::Ok()
-            .content_type(att.content_type)
-            .insert_header((
-                "Content-Disposition",
-                format!("attachment; filename=\"{}\"", att.filename),
-            ))
-            .body(att.data),
-        None => HttpResponse::NotFound().finish(),
-    }
+    state
+        .respond(move |storage| core::handle_attachment(storage, &id, idx))
+        .await
 }
 
 /// POST /clear - Delete all emails.
-async fn clear_all(state: web::Data<AppState>) -> impl Responder {
-    core::clear_emails(&state.storage);
-    HttpResponse::NoContent().finish()
+async fn clear_all(
+    state: web::Data<AppState>,
+    query: web::Query<MailboxQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let query = query.into_inner();
+    state
+        .respond(move |storage| core::handle_clear(storage, query))
+        .await
 }
@@ -1,117 +1,217 @@
 //! Axum adapter for mailbox preview.
+//!
+//! Storage calls run on Tokio's blocking pool so custom backends may perform
+//! blocking I/O without stalling the async runtime. Endpoint logic lives in
+//! [`core`](super::core); handlers here only extract parameters.
 
+use std::convert::Infallible;
 use std::sync::Arc;
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
     http::{header, StatusCode},
-    response::{Html, IntoResponse, Response},
+    response::{IntoResponse, Response},
     routing::{get, post},
-    Json, Router,
+    Router,
 };
-use serde::Deserialize;
 
-use crate::storage::MemoryStorage;
+use crate::storage::Storage;
 
 use super::core::{
-    self, AttachmentData, EmailListItem, EmailListResponse, PreviewConfig,
+    self, ForwardQuery, IndexQuery, ListQuery, LiveUpdates, MailboxQuery, PreviewConfig,
+    PreviewResponse,
 };
 
 /// Shared state for routes.
 #[derive(Clone)]
 struct AppState {
-    storage: Arc<MemoryStorage>,
+    storage: Arc<dyn Storage>,
     config: PreviewConfig,
 }
 
+impl AppState {
+    /// Run a storage operation on the blocking thread pool.
+    async fn with_storage<T, F>(&self, f: F) -> Result<T, StatusCode>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn Storage) -> T + Send + 'static,
+    {
+        let storage = Arc::clone(&self.storage);
+        tokio::task::spawn_blocking(move || f(storage.as_ref()))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Run a shared handler and convert its response.
+    async fn respond<F>(&self, f: F) -> Response
+    where
+        F: FnOnce(&dyn Storage) -> PreviewResponse + Send + 'static,
+    {
+        match self.with_storage(f).await {
+            Ok(response) => into_response(response),
+            Err(status) => status.into_response(),
+        }
+    }
+}
+
+fn into_response(response: PreviewResponse) -> Response {
+    let mut builder = Response::builder().status(response.status);
+    for (name, value) in response.headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(Body::from(response.body))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
 /// Create the mailbox router with default config.
-pub fn create_router(storage: Arc<MemoryStorage>) -> Router {
+pub fn create_router(storage: Arc<dyn Storage>) -> Router {
     create_router_with_config(storage, PreviewConfig::default())
 }
 
 /// Create the mailbox router with CSP nonce configuration.
-pub fn create_router_with_config(storage: Arc<MemoryStorage>, config: PreviewConfig) -> Router {
+pub fn create_router_with_config(storage: Arc<dyn Storage>, config: PreviewConfig) -> Router {
     let state = AppState { storage, config };
 
     Router::new()
         .route("/", get(index))
         .route("/json", get(list_json))
-        .route("/{id}", get(view_email))
+        .route("/events", get(events))
+        .route("/{id}", get(view_email).delete(delete_email))
+        .route("/{id}/read", post(mark_read))
+        .route("/{id}/forward", post(forward_email))
         .route("/{id}/html", get(email_html))
+        .route("/{id}/source", get(email_source))
+        .route("/{id}/compat", get(email_compat))
         .route("/{id}/attachments/{idx}", get(download_attachment))
         .route("/clear", post(clear_all))
         .with_state(state)
 }
 
-/// Query params for CSP nonce override.
-#[derive(Debug, Deserialize, Default)]
-struct IndexQuery {
-    script_nonce: Option<String>,
-    style_nonce: Option<String>,
+/// GET / - Render the mailbox UI.
+async fn index(State(state): State<AppState>, Query(query): Query<IndexQuery>) -> Response {
+    let config = state.config.clone();
+    state
+        .respond(move |storage| core::handle_index(storage, &config, query))
+        .await
 }
 
-/// GET / - Render the mailbox UI.
-async fn index(
-    State(state): State<AppState>,
-    Query(query): Query<IndexQuery>,
-) -> Html<String> {
-    let emails = core::list_emails(&state.storage);
-    let script_nonce = query.script_nonce.or(state.config.script_nonce.clone());
-    let style_nonce = query.style_nonce.or(state.config.style_nonce.clone());
-    Html(core::render_index(&emails, script_nonce, style_nonce))
+/// GET /json - Return emails as JSON.
+async fn list_json(State(state): State<AppState>, Query(query): Query<ListQuery>) -> Response {
+    state
+        .respond(move |storage| core::handle_list(storage, query))
+        .await
 }
 
-/// GET /json - Return all emails as JSON.
-async fn list_json(State(state): State<AppState>) -> Json<EmailListResponse> {
-    let emails = core::list_emails(&state.storage);
-    Json(EmailListResponse { data: emails })
+/// GET /events - Stream new and removed emails as server-sent events.
+async fn events(
+    State(state): State<AppState>,
+    Query(query): Query<MailboxQuery>,
+) -> Result<Response, StatusCode> {
+    let updates = state
+        .with_storage(move |storage| LiveUpdates::new(storage, query.mailbox))
+        .await?;
+
+    let stream = futures_util::stream::unfold((state, updates), |(state, mut updates)| async move {
+        loop {
+            tokio::time::sleep(core::LIVE_POLL_INTERVAL).await;
+            let (next, frames) = state
+                .with_storage(move |storage| {
+                    let frames = updates.poll(storage);
+                    (updates, frames)
+                })
+                .await
+                .ok()?;
+            updates = next;
+            if let Some(frames) = frames {
+                return Some((Ok::<_, Infallible>(frames), (state, updates)));
+            }
+        }
+    });
+
+    let response = (
+        [
+            (header::CONTENT_TYPE, "text/event-stream"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        Body::from_stream(stream),
+    );
+
+    Ok(response.into_response())
 }
 
 /// GET /:id - View a single email as JSON.
-async fn view_email(
+async fn view_email(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    state
+        .respond(move |storage| core::handle_email(storage, &id))
+        .await
+}
+
+/// DELETE /:id - Delete a single email.
+async fn delete_email(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    state
+        .respond(move |storage| core::handle_delete(storage, &id))
+        .await
+}
+
+/// POST /:id/read - Mark an email as read.
+async fn mark_read(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    state
+        .respond(move |storage| core::handle_mark_read(storage, &id))
+        .await
+}
+
+/// POST /:id/forward - Deliver a copy to a real address.
+async fn forward_email(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> Result<Json<EmailListItem>, StatusCode> {
-    core::get_email(&state.storage, &id)
-        .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+    Query(query): Query<ForwardQuery>,
+) -> Response {
+    match state
+        .with_storage(move |storage| core::forward_email(storage, &id, query))
+        .await
+    {
+        Ok(Ok(email)) => into_response(core::handle_forward(email).await),
+        Ok(Err(response)) => into_response(response),
+        Err(status) => status.into_response(),
+    }
 }
 
 /// GET /:id/html - Return raw HTML body for iframe embedding.
-async fn email_html(
-    State(state): State<AppState>,
-    Path(id): Path<String>,
-) -> Result<Html<String>, StatusCode> {
-    core::get_email_html(&state.storage, &id)
-        .map(Html)
-        .ok_or(StatusCode::NOT_FOUND)
+async fn email_html(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    state
+        .respond(move |storage| core::handle_html(storage, &id))
+        .await
+}
+
+/// GET /:id/source - Return the raw MIME message as plain text.
+async fn email_source(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    state
+        .respond(move |storage| core::handle_source(storage, &id))
+        .await
+}
+
+/// GET /:id/compat - Return email-client compatibility warnings as JSON.
+async fn email_compat(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    state
+        .respond(move |storage| core::handle_compat(storage, &id))
+        .await
 }
 
 /// GET /:id/attachments/:idx - Download an attachment.
 async fn download_attachment(
     State(state): State<AppState>,
     Path((id, idx)): Path<(String, usize)>,
-) -> Result<Response, StatusCode> {
-    let AttachmentData { data, filename, content_type } =
-        core::get_attachment(&state.storage, &id, idx)
-            .ok_or(StatusCode::NOT_FOUND)?;
-
-    let response = (
-        [
-            (header::CONTENT_TYPE, content_type),
-            (
-                header::CONTENT_DISPOSITION,
-                format!("attachment; filename=\"{}\"", filename),
-            ),
-        ],
-        data,
-    );
-
-    Ok(response.into_response())
+) -> Response {
+    state
+        .respond(move |storage| core::handle_attachment(storage, &id, idx))
+        .await
 }
 
 /// POST /clear - Delete all emails.
-async fn clear_all(State(state): State<AppState>) -> StatusCode {
-    core::clear_emails(&state.storage);
-    StatusCode::NO_CONTENT
+async fn clear_all(State(state): State<AppState>, Query(query): Query<MailboxQuery>) -> Response {
+    state
+        .respond(move |storage| core::handle_clear(storage, query))
+        .await
 }
@@ -1,14 +1,20 @@
 //! Shared logic for mailbox preview.
 //!
-//! Framework-agnostic types and rendering functions used by both Axum and Actix adapters.
+//! Framework-agnostic types, rendering and request handlers used by the
+//! standalone, Axum and Actix servers. Each endpoint is implemented once as a
+//! `handle_*` function returning a [`PreviewResponse`]; the adapters only map
+//! routes and convert the response.
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::address::Address;
 use crate::attachment::AttachmentType;
-use crate::storage::{MemoryStorage, Storage, StoredEmail};
+use crate::email::Email;
+use crate::error::MailError;
+use crate::storage::{Storage, StoredEmail};
 
 // ============================================================================
 // Configuration
@@ -38,6 +44,7 @@ pub struct EmailListItem {
     pub bcc: Vec<String>,
     pub reply_to: Option<String>,
     pub sent_at: Option<String>,
+    pub read: bool,
     pub text_body: Option<String>,
     pub html_body: Option<String>,
     pub headers: HashMap<String, String>,
@@ -79,6 +86,7 @@ impl From<&StoredEmail> for EmailListItem {
             bcc: email.bcc.iter().map(|a| a.formatted_rfc5322()).collect(),
             reply_to: email.reply_to.first().map(|a| a.formatted_rfc5322()),
             sent_at: Some(stored.sent_at.to_rfc3339()),
+            read: stored.read,
             text_body: email.text_body.clone(),
             html_body: email.html_body.clone(),
             headers: email.headers.clone(),
@@ -115,6 +123,21 @@ impl From<&StoredEmail> for EmailListItem {
 #[derive(Serialize)]
 pub struct EmailListResponse {
     pub data: Vec<EmailListItem>,
+    /// Total number of stored emails, regardless of paging.
+    pub total: usize,
+    /// Number of stored emails not yet marked read.
+    pub unread: usize,
+    /// Mailboxes the storage is split into, if any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mailboxes: Vec<MailboxInfo>,
+}
+
+/// A mailbox and how many emails it holds.
+#[derive(Serialize)]
+pub struct MailboxInfo {
+    pub name: String,
+    pub count: usize,
+    pub unread: usize,
 }
 
 // ============================================================================
@@ -122,22 +145,33 @@ pub struct EmailListResponse {
 // ============================================================================
 
 /// Get all emails as EmailListItems.
-pub fn list_emails(storage: &Arc<MemoryStorage>) -> Vec<EmailListItem> {
+pub fn list_emails(storage: &dyn Storage) -> Vec<EmailListItem> {
     storage.all().iter().map(EmailListItem::from).collect()
 }
 
 /// Get a single email by ID.
-pub fn get_email(storage: &Arc<MemoryStorage>, id: &str) -> Option<EmailListItem> {
+pub fn get_email(storage: &dyn Storage, id: &str) -> Option<EmailListItem> {
     storage.get(id).map(|e| EmailListItem::from(&e))
 }
 
 /// Get raw HTML body for an email, with CID references replaced.
-pub fn get_email_html(storage: &Arc<MemoryStorage>, id: &str) -> Option<String> {
+pub fn get_email_html(storage: &dyn Storage, id: &str) -> Option<String> {
     let stored = storage.get(id)?;
     let html = stored.email.html_body.clone()?;
     Some(replace_cid_references(&html, id, &stored.email.attachments))
 }
 
+/// Get the full MIME source of an email, as providers sending raw messages would build it.
+///
+/// If the message cannot be built (e.g. no `from`), the error is returned as text.
+pub fn get_email_source(storage: &dyn Storage, id: &str) -> Option<String> {
+    let stored = storage.get(id)?;
+    Some(match crate::mime::build_mime_message(&stored.email) {
+        Ok(raw) => String::from_utf8_lossy(&raw).into_owned(),
+        Err(e) => format!("Unable to build MIME message: {}", e),
+    })
+}
+
 /// Get attachment data and metadata.
 pub struct AttachmentData {
     pub data: Vec<u8>,
@@ -147,7 +181,7 @@ pub struct AttachmentData {
 
 /// Get attachment by email ID and index.
 pub fn get_attachment(
-    storage: &Arc<MemoryStorage>,
+    storage: &dyn Storage,
     id: &str,
     idx: usize,
 ) -> Option<AttachmentData> {
@@ -163,10 +197,593 @@ pub fn get_attachment(
 }
 
 /// Clear all emails from storage.
-pub fn clear_emails(storage: &Arc<MemoryStorage>) {
+pub fn clear_emails(storage: &dyn Storage) {
     storage.clear();
 }
 
+/// List the mailboxes storage is split into, with their email and unread counts.
+pub fn list_mailboxes(storage: &dyn Storage) -> Vec<MailboxInfo> {
+    storage
+        .mailboxes()
+        .into_iter()
+        .filter_map(|name| {
+            let mailbox = storage.mailbox(&name)?;
+            Some(MailboxInfo {
+                count: mailbox.count(),
+                unread: mailbox.unread_count(),
+                name,
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Request Handlers
+// ============================================================================
+
+/// Query params for CSP nonce override and mailbox on the index page.
+#[derive(Debug, Deserialize, Default)]
+pub struct IndexQuery {
+    pub script_nonce: Option<String>,
+    pub style_nonce: Option<String>,
+    pub mailbox: Option<String>,
+}
+
+/// Query params for paging the JSON list.
+#[derive(Debug, Deserialize, Default)]
+pub struct ListQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    pub mailbox: Option<String>,
+}
+
+/// Query params selecting one mailbox; every mailbox when unset.
+#[derive(Debug, Deserialize, Default)]
+pub struct MailboxQuery {
+    pub mailbox: Option<String>,
+}
+
+/// Run `f` on the named mailbox, or on all of storage when `mailbox` is
+/// unset or empty. Unknown mailboxes are a 404.
+fn in_mailbox(
+    storage: &dyn Storage,
+    mailbox: Option<&str>,
+    f: impl FnOnce(&dyn Storage) -> PreviewResponse,
+) -> PreviewResponse {
+    match mailbox.filter(|name| !name.is_empty()) {
+        None => f(storage),
+        Some(name) => match storage.mailbox(name) {
+            Some(mailbox) => f(mailbox.as_ref()),
+            None => PreviewResponse::not_found(),
+        },
+    }
+}
+
+/// Query params for forwarding an email to a real address.
+#[derive(Debug, Deserialize, Default)]
+pub struct ForwardQuery {
+    pub to: Option<String>,
+}
+
+/// Framework-agnostic HTTP response produced by the `handle_*` functions.
+pub struct PreviewResponse {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl PreviewResponse {
+    fn with_type(content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type", content_type.to_string())],
+            body,
+        }
+    }
+
+    fn html(body: String) -> Self {
+        Self::with_type("text/html; charset=utf-8", body.into_bytes())
+    }
+
+    fn text(body: String) -> Self {
+        Self::with_type("text/plain; charset=utf-8", body.into_bytes())
+    }
+
+    fn json<T: Serialize>(data: &T) -> Self {
+        Self::with_type("application/json", serde_json::to_vec(data).unwrap_or_default())
+    }
+
+    fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub(super) fn not_found() -> Self {
+        Self::status(404)
+    }
+
+    pub(super) fn error(status: u16, message: &str) -> Self {
+        let mut response = Self::json(&serde_json::json!({ "error": message }));
+        response.status = status;
+        response
+    }
+}
+
+/// GET / - Render the mailbox UI.
+pub fn handle_index(
+    storage: &dyn Storage,
+    config: &PreviewConfig,
+    query: IndexQuery,
+) -> PreviewResponse {
+    let mailboxes = list_mailboxes(storage);
+    let selected = query.mailbox.as_deref();
+    in_mailbox(storage, selected, |scoped| {
+        let emails = list_emails(scoped);
+        let script_nonce = query.script_nonce.or(config.script_nonce.clone());
+        let style_nonce = query.style_nonce.or(config.style_nonce.clone());
+        let title = render_title(&mailboxes, selected.unwrap_or_default());
+        PreviewResponse::html(render_index(&emails, &title, script_nonce, style_nonce))
+    })
+}
+
+/// GET /json - Return emails as JSON, newest first.
+///
+/// Returns every email unless `offset` or `limit` is given, in which case
+/// only that page is read from storage. With `mailbox`, only that mailbox's
+/// emails are listed.
+pub fn handle_list(storage: &dyn Storage, query: ListQuery) -> PreviewResponse {
+    let mailboxes = list_mailboxes(storage);
+    in_mailbox(storage, query.mailbox.as_deref(), |scoped| {
+        let data = match (query.offset, query.limit) {
+            (None, None) => list_emails(scoped),
+            (offset, limit) => scoped
+                .page(offset.unwrap_or(0), limit.unwrap_or(usize::MAX))
+                .iter()
+                .map(EmailListItem::from)
+                .collect(),
+        };
+        PreviewResponse::json(&EmailListResponse {
+            data,
+            total: scoped.count(),
+            unread: scoped.unread_count(),
+            mailboxes,
+        })
+    })
+}
+
+/// GET /:id - View a single email as JSON.
+pub fn handle_email(storage: &dyn Storage, id: &str) -> PreviewResponse {
+    match get_email(storage, id) {
+        Some(email) => PreviewResponse::json(&email),
+        None => PreviewResponse::not_found(),
+    }
+}
+
+/// DELETE /:id - Delete a single email.
+pub fn handle_delete(storage: &dyn Storage, id: &str) -> PreviewResponse {
+    if storage.delete(id) {
+        PreviewResponse::status(204)
+    } else {
+        PreviewResponse::not_found()
+    }
+}
+
+/// POST /:id/read - Mark an email as read.
+pub fn handle_mark_read(storage: &dyn Storage, id: &str) -> PreviewResponse {
+    if storage.mark_read(id) {
+        PreviewResponse::status(204)
+    } else {
+        PreviewResponse::not_found()
+    }
+}
+
+/// Named mailer that forwarded emails are delivered through, unless
+/// `EMAIL_PREVIEW_FORWARD_VIA` names another.
+pub const DEFAULT_FORWARD_MAILER: &str = "forward";
+
+/// Copy a stored email for forwarding to `query.to`.
+///
+/// The copy keeps the sender, subject, bodies, headers and attachments but
+/// goes only to the given address. On error, returns the response to send.
+pub fn forward_email(
+    storage: &dyn Storage,
+    id: &str,
+    query: ForwardQuery,
+) -> Result<Email, PreviewResponse> {
+    let stored = storage.get(id).ok_or_else(PreviewResponse::not_found)?;
+    let to = query
+        .to
+        .as_deref()
+        .map(str::trim)
+        .filter(|to| !to.is_empty())
+        .ok_or_else(|| PreviewResponse::error(400, "Missing `to` address"))?;
+    let to = Address::parse(to).map_err(|e| PreviewResponse::error(400, &e.to_string()))?;
+
+    let mut email = stored.email;
+    email.to = vec![to];
+    email.cc.clear();
+    email.bcc.clear();
+    email.private.clear();
+    Ok(email)
+}
+
+/// POST /:id/forward - Deliver an email from [`forward_email`] through the
+/// forwarding mailer.
+///
+/// Responds with the provider's message ID, 503 when no forwarding mailer is
+/// configured, or 502 when delivery fails.
+pub async fn handle_forward(email: Email) -> PreviewResponse {
+    let via = crate::config::var("EMAIL_PREVIEW_FORWARD_VIA")
+        .unwrap_or_else(|| DEFAULT_FORWARD_MAILER.to_string());
+    match crate::deliver_via(&via, &email).await {
+        Ok(result) => {
+            tracing::info!(mailer = %via, to = %email.to[0].email, "Forwarded preview email");
+            PreviewResponse::json(&serde_json::json!({ "message_id": result.message_id }))
+        }
+        Err(e @ MailError::Configuration(_)) => PreviewResponse::error(503, &e.to_string()),
+        Err(e) => PreviewResponse::error(502, &e.to_string()),
+    }
+}
+
+/// GET /:id/html - Return raw HTML body for iframe embedding.
+pub fn handle_html(storage: &dyn Storage, id: &str) -> PreviewResponse {
+    match get_email_html(storage, id) {
+        Some(html) => PreviewResponse::html(html),
+        None => PreviewResponse::not_found(),
+    }
+}
+
+/// GET /:id/source - Return the raw MIME message as plain text.
+pub fn handle_source(storage: &dyn Storage, id: &str) -> PreviewResponse {
+    match get_email_source(storage, id) {
+        Some(source) => PreviewResponse::text(source),
+        None => PreviewResponse::not_found(),
+    }
+}
+
+/// GET /:id/compat - Return email-client compatibility warnings as JSON.
+pub fn handle_compat(storage: &dyn Storage, id: &str) -> PreviewResponse {
+    match get_email_compat(storage, id) {
+        Some(warnings) => PreviewResponse::json(&warnings),
+        None => PreviewResponse::not_found(),
+    }
+}
+
+/// GET /:id/attachments/:idx - Download an attachment.
+pub fn handle_attachment(storage: &dyn Storage, id: &str, idx: usize) -> PreviewResponse {
+    match get_attachment(storage, id, idx) {
+        Some(att) => {
+            let mut response = PreviewResponse::with_type(&att.content_type, att.data);
+            response.headers.push((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", att.filename),
+            ));
+            response
+        }
+        None => PreviewResponse::not_found(),
+    }
+}
+
+/// POST /clear - Delete all emails, or only those in `mailbox`.
+pub fn handle_clear(storage: &dyn Storage, query: MailboxQuery) -> PreviewResponse {
+    in_mailbox(storage, query.mailbox.as_deref(), |scoped| {
+        clear_emails(scoped);
+        PreviewResponse::status(204)
+    })
+}
+
+// ============================================================================
+// Compatibility Analysis
+// ============================================================================
+
+/// Gmail clips HTML bodies larger than this.
+const GMAIL_CLIP_BYTES: usize = 102 * 1024;
+
+/// Widest image that fits common email layouts without scaling.
+const MAX_IMAGE_WIDTH: u32 = 600;
+
+/// Inline images larger than this slow down rendering and may be blocked.
+const MAX_INLINE_IMAGE_BYTES: usize = 1024 * 1024;
+
+/// CSS properties with poor email-client support: (pattern, label, clients).
+const UNSUPPORTED_CSS: &[(&str, &str, &str)] = &[
+    (r"display\s*:\s*(?:inline-)?(?:flex|grid)", "display: flex/grid", "Outlook"),
+    (r"(?:^|[;{\s])position\s*:", "position", "Gmail, Outlook"),
+    (r"(?:^|[;{\s])float\s*:", "float", "Outlook"),
+    (r"box-shadow\s*:", "box-shadow", "Gmail, Outlook"),
+    (r"border-radius\s*:", "border-radius", "Outlook"),
+    (r"background-image\s*:", "background-image", "Outlook"),
+    (r"(?:^|[;{\s])max-width\s*:", "max-width", "Outlook"),
+    (r"(?:^|[;{\s])transform\s*:", "transform", "Gmail, Outlook"),
+    (r"(?:animation|transition)\s*:", "animation/transition", "Gmail, Outlook"),
+    (r"var\(--", "CSS variables", "Gmail, Outlook"),
+    (r"calc\(", "calc()", "Outlook"),
+];
+
+/// Tags stripped or ignored by most email clients.
+const UNSUPPORTED_TAGS: &[&str] = &[
+    "script", "form", "iframe", "video", "audio", "embed", "object", "svg",
+];
+
+/// Severity of a compatibility warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Likely to look different in some clients.
+    Warning,
+    /// Will be stripped or broken in most clients.
+    Error,
+}
+
+/// An email-client compatibility issue found in an HTML body.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompatWarning {
+    /// Short rule identifier (e.g. `css-unsupported`, `img-missing-width`).
+    pub rule: &'static str,
+    /// How likely the issue is to break rendering.
+    pub severity: Severity,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl CompatWarning {
+    fn new(rule: &'static str, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+/// Lint an HTML email body for common email-client pitfalls.
+///
+/// Checks for unsupported CSS, unsupported tags, images without `width`
+/// attributes, oversized images, external fonts and Gmail clipping.
+pub fn analyze_html(html: &str) -> Vec<CompatWarning> {
+    use regex::Regex;
+
+    let mut warnings = Vec::new();
+
+    if html.len() > GMAIL_CLIP_BYTES {
+        warnings.push(CompatWarning::new(
+            "gmail-clipping",
+            Severity::Warning,
+            format!(
+                "HTML is {} KB; Gmail clips messages over 102 KB",
+                html.len() / 1024
+            ),
+        ));
+    }
+
+    // CSS from <style> blocks and style attributes
+    let style_block = Regex::new(r"(?is)<style[^>]*>(.*?)</style>").unwrap();
+    let style_attr = Regex::new(r#"(?is)\sstyle\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    let css: Vec<&str> = style_block
+        .captures_iter(html)
+        .chain(style_attr.captures_iter(html))
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str())
+        .collect();
+
+    for (pattern, label, clients) in UNSUPPORTED_CSS {
+        let re = Regex::new(&format!("(?i){}", pattern)).unwrap();
+        let count = css.iter().filter(|block| re.is_match(block)).count();
+        if count > 0 {
+            warnings.push(CompatWarning::new(
+                "css-unsupported",
+                Severity::Warning,
+                format!(
+                    "`{}` is not supported in {} ({} use{})",
+                    label,
+                    clients,
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+            ));
+        }
+    }
+
+    for tag in UNSUPPORTED_TAGS {
+        let re = Regex::new(&format!(r"(?i)<{}[\s>/]", tag)).unwrap();
+        if re.is_match(html) {
+            warnings.push(CompatWarning::new(
+                "tag-unsupported",
+                Severity::Error,
+                format!("<{}> is removed or ignored by most email clients", tag),
+            ));
+        }
+    }
+
+    // Images
+    let img = Regex::new(r"(?is)<img\b[^>]*>").unwrap();
+    let src = Regex::new(r#"(?i)\ssrc\s*=\s*["']([^"']*)["']"#).unwrap();
+    let width = Regex::new(r#"(?i)\swidth\s*=\s*["']?(\d+)"#).unwrap();
+    for tag in img.find_iter(html).map(|m| m.as_str()) {
+        let source = src
+            .captures(tag)
+            .map(|c| c[1].to_string())
+            .unwrap_or_default();
+        let label = if source.chars().count() > 60 {
+            format!("{}...", source.chars().take(57).collect::<String>())
+        } else {
+            source.clone()
+        };
+
+        match width.captures(tag).and_then(|c| c[1].parse::<u32>().ok()) {
+            None => warnings.push(CompatWarning::new(
+                "img-missing-width",
+                Severity::Warning,
+                format!(
+                    "Image `{}` has no width attribute; Outlook renders it at full size",
+                    label
+                ),
+            )),
+            Some(w) if w > MAX_IMAGE_WIDTH => warnings.push(CompatWarning::new(
+                "img-oversized",
+                Severity::Warning,
+                format!(
+                    "Image `{}` is {}px wide; keep images at or below {}px",
+                    label, w, MAX_IMAGE_WIDTH
+                ),
+            )),
+            Some(_) => {}
+        }
+
+        if source.starts_with("data:") {
+            warnings.push(CompatWarning::new(
+                "img-data-uri",
+                Severity::Error,
+                "Data URI images are blocked by Gmail and Outlook; use an attachment with a CID",
+            ));
+        }
+    }
+
+    // External fonts
+    let font_link =
+        Regex::new(r#"(?i)<link[^>]+href\s*=\s*["']([^"']*fonts?[^"']*)["']"#).unwrap();
+    let font_import = Regex::new(r"(?i)@import\s+(?:url\()?\s*[\x22']?([^\x22')\s;]+)").unwrap();
+    let font_face = Regex::new(r"(?i)@font-face").unwrap();
+    let fonts: Vec<String> = font_link
+        .captures_iter(html)
+        .chain(font_import.captures_iter(html))
+        .map(|c| c[1].to_string())
+        .collect();
+    for url in &fonts {
+        warnings.push(CompatWarning::new(
+            "external-font",
+            Severity::Warning,
+            format!(
+                "External font `{}` is ignored by Gmail and Outlook; set a fallback font stack",
+                url
+            ),
+        ));
+    }
+    if fonts.is_empty() && font_face.is_match(html) {
+        warnings.push(CompatWarning::new(
+            "external-font",
+            Severity::Warning,
+            "@font-face is ignored by Gmail and Outlook; set a fallback font stack",
+        ));
+    }
+
+    warnings
+}
+
+/// Analyze an email's HTML body and inline images.
+pub fn analyze_email(email: &Email) -> Vec<CompatWarning> {
+    let mut warnings = email
+        .html_body
+        .as_deref()
+        .map(analyze_html)
+        .unwrap_or_default();
+
+    for attachment in email.attachments.iter().filter(|a| a.is_inline()) {
+        let size = attachment.size();
+        if size > MAX_INLINE_IMAGE_BYTES {
+            warnings.push(CompatWarning::new(
+                "img-oversized",
+                Severity::Warning,
+                format!(
+                    "Inline image `{}` is {} KB; keep embedded images under {} KB",
+                    attachment.filename,
+                    size / 1024,
+                    MAX_INLINE_IMAGE_BYTES / 1024
+                ),
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Get compatibility warnings for an email by ID.
+pub fn get_email_compat(storage: &dyn Storage, id: &str) -> Option<Vec<CompatWarning>> {
+    storage.get(id).map(|stored| analyze_email(&stored.email))
+}
+
+// ============================================================================
+// Live Updates
+// ============================================================================
+
+/// How often live-update streams poll storage for changes.
+pub const LIVE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Idle polls between keep-alive comments (~15s).
+const KEEPALIVE_POLLS: u32 = 30;
+
+/// Tracks which emails a live-update (SSE) client has already seen.
+///
+/// Polling works with any [`Storage`] backend, including ones written to by
+/// other processes.
+pub struct LiveUpdates {
+    known: HashSet<String>,
+    idle_polls: u32,
+    mailbox: Option<String>,
+}
+
+impl LiveUpdates {
+    /// Start tracking from the current contents of storage, or of one
+    /// mailbox when `mailbox` is set.
+    ///
+    /// A mailbox that doesn't exist yet is tracked as empty until it does.
+    pub fn new(storage: &dyn Storage, mailbox: Option<String>) -> Self {
+        let mut updates = Self {
+            known: HashSet::new(),
+            idle_polls: 0,
+            mailbox: mailbox.filter(|name| !name.is_empty()),
+        };
+        updates.known = updates.emails(storage).into_iter().map(|e| e.id).collect();
+        updates
+    }
+
+    fn emails(&self, storage: &dyn Storage) -> Vec<StoredEmail> {
+        match &self.mailbox {
+            None => storage.all(),
+            Some(name) => storage.mailbox(name).map(|m| m.all()).unwrap_or_default(),
+        }
+    }
+
+    /// Poll storage and return the server-sent events to write, if any.
+    ///
+    /// Emits an `email` event (JSON [`EmailListItem`]) for each new email,
+    /// oldest first, and a `remove` event (JSON id) for each removed one.
+    /// Returns a keep-alive comment when nothing has changed for a while.
+    pub fn poll(&mut self, storage: &dyn Storage) -> Option<String> {
+        let emails = self.emails(storage);
+        let current: HashSet<&str> = emails.iter().map(|e| e.id.as_str()).collect();
+
+        let mut frames = String::new();
+        for id in self.known.iter().filter(|id| !current.contains(id.as_str())) {
+            frames.push_str(&sse_frame("remove", &serde_json::json!(id).to_string()));
+        }
+        for stored in emails.iter().rev().filter(|e| !self.known.contains(&e.id)) {
+            let item = serde_json::to_string(&EmailListItem::from(stored)).unwrap_or_default();
+            frames.push_str(&sse_frame("email", &item));
+        }
+        self.known = current.into_iter().map(String::from).collect();
+
+        if !frames.is_empty() {
+            self.idle_polls = 0;
+            return Some(frames);
+        }
+
+        self.idle_polls += 1;
+        if self.idle_polls >= KEEPALIVE_POLLS {
+            self.idle_polls = 0;
+            return Some(": keepalive\n\n".to_string());
+        }
+        None
+    }
+}
+
+fn sse_frame(event: &str, data: &str) -> String {
+    format!("event: {}\ndata: {}\n\n", event, data)
+}
+
 // ============================================================================
 // HTML Rendering
 // ============================================================================
@@ -197,9 +814,10 @@ fn replace_cid_references(
     .to_string()
 }
 
-/// Render the index HTML page.
+/// Render the index HTML page, with `title` (see [`render_title`]) atop the sidebar.
 pub fn render_index(
     emails: &[EmailListItem],
+    title: &str,
     script_nonce: Option<String>,
     style_nonce: Option<String>,
 ) -> String {
@@ -219,11 +837,12 @@ pub fn render_index(
         .iter()
         .map(|e| {
             format!(
-                r#"<div class="email-item" data-id="{id}" onclick="selectEmail('{id}')">
+                r#"<div class="email-item{unread}" data-id="{id}" onclick="selectEmail('{id}')">
                     <div class="email-item-from">{from}</div>
                     <div class="email-item-subject">{subject}</div>
                 </div>"#,
                 id = e.id,
+                unread = if e.read { "" } else { " unread" },
                 from = html_escape(e.from.as_deref().unwrap_or("(no sender)")),
                 subject = html_escape(&e.subject),
             )
@@ -244,6 +863,10 @@ pub fn render_index(
     };
 
     let plural = if emails.len() == 1 { "" } else { "s" };
+    let unread = match emails.iter().filter(|e| !e.read).count() {
+        0 => String::new(),
+        n => format!(", {} unread", n),
+    };
 
     let sun_icon = r#"<svg id="sun-icon" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" style="display:none"><circle cx="12" cy="12" r="5"></circle><line x1="12" y1="1" x2="12" y2="3"></line><line x1="12" y1="21" x2="12" y2="23"></line><line x1="4.22" y1="4.22" x2="5.64" y2="5.64"></line><line x1="18.36" y1="18.36" x2="19.78" y2="19.78"></line><line x1="1" y1="12" x2="3" y2="12"></line><line x1="21" y1="12" x2="23" y2="12"></line><line x1="4.22" y1="19.78" x2="5.64" y2="18.36"></line><line x1="18.36" y1="5.64" x2="19.78" y2="4.22"></line></svg>"#;
     let moon_icon = r#"<svg id="moon-icon" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M21 12.79A9 9 0 1 1 11.21 3 7 7 0 0 0 21 12.79z"></path></svg>"#;
@@ -261,9 +884,9 @@ pub fn render_index(
     <div class="container">
         <aside class="sidebar">
             <div class="sidebar-header">
-                <h1>Mailbox</h1>
+                {title}
                 <div class="sidebar-meta">
-                    <span class="email-count">{count} message{plural}</span>
+                    <span class="email-count">{count} message{plural}{unread}</span>
                     <div class="header-actions">
                         <button class="theme-toggle" onclick="toggleTheme()" title="Toggle theme">
                             {sun_icon}
@@ -300,8 +923,10 @@ pub fn render_index(
 </html>"##,
         css = css,
         js = js,
+        title = title,
         count = emails.len(),
         plural = plural,
+        unread = unread,
         email_items = email_items,
         empty_state = empty_state,
         sun_icon = sun_icon,
@@ -311,6 +936,35 @@ pub fn render_index(
     )
 }
 
+/// Render the sidebar title, or a mailbox switcher when storage is split
+/// into mailboxes.
+fn render_title(mailboxes: &[MailboxInfo], selected: &str) -> String {
+    if mailboxes.is_empty() {
+        return "<h1>Mailbox</h1>".to_string();
+    }
+
+    let option = |value: &str, label: String| {
+        format!(
+            r#"<option value="{value}"{selected}>{label}</option>"#,
+            value = html_escape(value),
+            selected = if value == selected { " selected" } else { "" },
+            label = html_escape(&label),
+        )
+    };
+    let total: usize = mailboxes.iter().map(|m| m.count).sum();
+    let options: String = std::iter::once(option("", format!("All mailboxes ({})", total)))
+        .chain(
+            mailboxes
+                .iter()
+                .map(|m| option(&m.name, format!("{} ({})", m.name, m.count))),
+        )
+        .collect();
+    format!(
+        r#"<select class="mailbox-select" onchange="switchMailbox(this.value)" title="Mailbox">{}</select>"#,
+        options
+    )
+}
+
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -318,3 +972,69 @@ fn html_escape(s: &str) -> String {
         .replace('"', "&quot;")
         .replace('\'', "&#x27;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email::Email;
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn test_analyze_html_flags_pitfalls() {
+        let html = r#"<html><head>
+            <link href="https://fonts.googleapis.com/css?family=Inter" rel="stylesheet">
+            <style>.row { display: flex; }</style>
+        </head><body>
+            <div style="box-shadow: 0 0 4px #000">
+                <img src="https://example.com/logo.png">
+                <img src="https://example.com/hero.png" width="1200">
+                <img src="https://example.com/ok.png" width="200">
+            </div>
+            <script>alert(1)</script>
+        </body></html>"#;
+
+        let warnings = analyze_html(html);
+        let rules: Vec<&str> = warnings.iter().map(|w| w.rule).collect();
+
+        assert_eq!(rules.iter().filter(|r| **r == "css-unsupported").count(), 2);
+        assert!(rules.contains(&"img-missing-width"));
+        assert!(rules.contains(&"img-oversized"));
+        assert!(rules.contains(&"external-font"));
+        assert!(warnings
+            .iter()
+            .any(|w| w.rule == "tag-unsupported" && w.severity == Severity::Error));
+        assert!(!warnings.iter().any(|w| w.message.contains("ok.png")));
+    }
+
+    #[test]
+    fn test_analyze_html_clean() {
+        let html = r#"<table width="600"><tr><td style="color: #333; padding: 8px">
+            <img src="cid:logo" width="120" alt="Logo">
+        </td></tr></table>"#;
+        assert!(analyze_html(html).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_html_gmail_clipping() {
+        let html = format!("<p>{}</p>", "a".repeat(GMAIL_CLIP_BYTES));
+        assert_eq!(analyze_html(&html)[0].rule, "gmail-clipping");
+    }
+
+    #[test]
+    fn test_live_updates() {
+        let storage = MemoryStorage::new();
+        let old = storage.push(Email::new().subject("Old"));
+        let mut updates = LiveUpdates::new(&storage, None);
+        assert!(updates.poll(&storage).is_none());
+
+        storage.push(Email::new().subject("New"));
+        let frames = updates.poll(&storage).unwrap();
+        assert!(frames.starts_with("event: email\ndata: {"));
+        assert!(frames.contains("\"subject\":\"New\""));
+        assert!(!frames.contains("Old"));
+
+        storage.delete(&old);
+        let frames = updates.poll(&storage).unwrap();
+        assert_eq!(frames, format!("event: remove\ndata: \"{}\"\n\n", old));
+    }
+}
@@ -1,11 +1,18 @@
 //! Mailbox preview UI for development.
 //!
-//! Provides a web UI that displays emails stored in `MemoryStorage`.
+//! Provides a web UI that displays emails from any [`Storage`](crate::Storage)
+//! backend. `LocalMailer` uses `MemoryStorage`; implement `Storage` to power
+//! the preview from Redis, SQLite, S3, etc.
 //!
 //! ## Features
 //!
+//! - Live updates via server-sent events (`/events`)
+//! - Mailbox switcher for [`Mailboxes`](crate::Mailboxes) storage (`?mailbox=`)
+//! - Email-client compatibility warnings for HTML bodies ([`analyze_html`])
+//! - Desktop/tablet/mobile widths and simulated dark mode for HTML bodies
 //! - CSP nonce support for Content Security Policy compliance
-//! - Full JSON API with private/provider_options/headers
+//! - Full JSON API with private/provider_options/headers, read state and deletion
+//! - Send to a real address through a named mailer (`/:id/forward`)
 //! - Path-based attachment lazy loading
 //! - RFC 5322 compliant recipient rendering
 //!
@@ -23,6 +30,9 @@
 //! serve("127.0.0.1:3025", mailer.storage())?;
 //! ```
 //!
+//! With `EMAIL_PROVIDER=local`, `serve_local` serves the global LocalMailer
+//! storage without any wiring, which suits CLIs and background workers.
+//!
 //! For background execution:
 //!
 //! ```rust,ignore
@@ -58,16 +68,30 @@
 //!
 //! ```rust,ignore
 //! use missive::providers::LocalMailer;
-//! use missive::preview::{actix_configure, ActixAppState, PreviewConfig};
+//! use missive::preview::{actix_configure, ActixAppState};
 //! use actix_web::{App, web};
 //!
 //! let mailer = LocalMailer::new();
 //! let storage = mailer.storage();
-//! let state = ActixAppState { storage, config: PreviewConfig::default() };
+//! let state = ActixAppState::new(storage);
 //!
 //! let app = App::new()
 //!     .service(web::scope("/dev/mailbox").configure(|cfg| actix_configure(cfg, state)));
 //! ```
+//!
+//! # Rocket Integration
+//!
+//! Mount the preview routes in an existing Rocket application.
+//!
+//! ```rust,ignore
+//! use missive::providers::LocalMailer;
+//! use missive::preview::mailbox_routes;
+//!
+//! let mailer = LocalMailer::new();
+//!
+//! let rocket = rocket::build()
+//!     .mount("/dev/mailbox", mailbox_routes(mailer.storage()));
+//! ```
 
 mod core;
 
@@ -80,21 +104,30 @@ mod axum_routes;
 #[cfg(feature = "preview-actix")]
 mod actix_routes;
 
-#[cfg(feature = "preview-axum")]
+#[cfg(feature = "preview-rocket")]
+mod rocket_routes;
+
+#[cfg(any(feature = "preview-axum", feature = "preview-rocket"))]
 use std::sync::Arc;
 
-#[cfg(feature = "preview-axum")]
-use crate::storage::MemoryStorage;
+#[cfg(any(feature = "preview-axum", feature = "preview-rocket"))]
+use crate::storage::Storage;
 
 // Re-export configuration type
 pub use core::PreviewConfig;
 
+// Name of the mailer used by POST /:id/forward
+pub use core::DEFAULT_FORWARD_MAILER;
+
+// Re-export HTML compatibility analysis
+pub use core::{analyze_email, analyze_html, CompatWarning, Severity};
+
 // ============================================================================
 // Standalone Server
 // ============================================================================
 
 #[cfg(feature = "preview")]
-pub use standalone::{serve, PreviewServer};
+pub use standalone::{serve, serve_local, PreviewServer};
 
 // ============================================================================
 // Axum Support
@@ -120,19 +153,36 @@ pub mod reexports {
 /// |--------|------|-------------|
 /// | GET | `/` | HTML UI listing all emails |
 /// | GET | `/json` | JSON API |
+/// | GET | `/events` | Server-sent events for new/removed emails |
 /// | GET | `/:id` | View single email as JSON |
+/// | DELETE | `/:id` | Delete a single email |
+/// | POST | `/:id/read` | Mark an email as read |
+/// | POST | `/:id/forward?to=<address>` | Send an email to a real address |
 /// | GET | `/:id/html` | Raw HTML body (for iframe) |
+/// | GET | `/:id/source` | Full MIME source (plain text) |
+/// | GET | `/:id/compat` | Email-client compatibility warnings (JSON) |
 /// | GET | `/:id/attachments/:idx` | Download attachment |
 /// | POST | `/clear` | Delete all emails |
+///
+/// `/`, `/json`, `/events` and `/clear` take `?mailbox=<name>` to show or
+/// clear a single mailbox of [`Mailboxes`](crate::Mailboxes) storage.
+///
+/// `/:id/forward` delivers through the named mailer
+/// [`DEFAULT_FORWARD_MAILER`] (or the one named by `EMAIL_PREVIEW_FORWARD_VIA`).
+///
+/// Accepts any storage backend, including `Arc<MemoryStorage>` and `Arc<dyn Storage>`.
 #[cfg(feature = "preview-axum")]
-pub fn mailbox_router(storage: Arc<MemoryStorage>) -> Router {
-    axum_routes::create_router(storage)
+pub fn mailbox_router(storage: impl Storage + 'static) -> Router {
+    axum_routes::create_router(Arc::new(storage))
 }
 
 /// Create a mailbox router with CSP nonce configuration.
 #[cfg(feature = "preview-axum")]
-pub fn mailbox_router_with_config(storage: Arc<MemoryStorage>, config: PreviewConfig) -> Router {
-    axum_routes::create_router_with_config(storage, config)
+pub fn mailbox_router_with_config(
+    storage: impl Storage + 'static,
+    config: PreviewConfig,
+) -> Router {
+    axum_routes::create_router_with_config(Arc::new(storage), config)
 }
 
 // ============================================================================
@@ -144,16 +194,15 @@ pub use actix_routes::AppState as ActixAppState;
 
 /// Configure Actix routes on a scope.
 ///
+/// Serves the same routes as the Axum `mailbox_router`.
+///
 /// ## Example
 ///
 /// ```rust,ignore
 /// use missive::preview::{actix_configure, ActixAppState, PreviewConfig};
 /// use actix_web::{App, web};
 ///
-/// let state = ActixAppState {
-///     storage: mailer.storage(),
-///     config: PreviewConfig::default(),
-/// };
+/// let state = ActixAppState::with_config(mailer.storage(), PreviewConfig::default());
 ///
 /// App::new()
 ///     .service(web::scope("/mailbox").configure(|cfg| actix_configure(cfg, state)));
@@ -162,3 +211,35 @@ pub use actix_routes::AppState as ActixAppState;
 pub fn actix_configure(cfg: &mut actix_web::web::ServiceConfig, state: ActixAppState) {
     actix_routes::configure(cfg, state)
 }
+
+// ============================================================================
+// Rocket Support
+// ============================================================================
+
+/// Create Rocket routes for the mailbox preview UI.
+///
+/// Serves the same routes as the Axum `mailbox_router`. Mount them at a
+/// development path (e.g., `/dev/mailbox`).
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use missive::preview::mailbox_routes;
+///
+/// rocket::build().mount("/dev/mailbox", mailbox_routes(mailer.storage()))
+/// ```
+///
+/// Accepts any storage backend, including `Arc<MemoryStorage>` and `Arc<dyn Storage>`.
+#[cfg(feature = "preview-rocket")]
+pub fn mailbox_routes(storage: impl Storage + 'static) -> Vec<rocket::Route> {
+    mailbox_routes_with_config(storage, PreviewConfig::default())
+}
+
+/// Create Rocket routes with CSP nonce configuration.
+#[cfg(feature = "preview-rocket")]
+pub fn mailbox_routes_with_config(
+    storage: impl Storage + 'static,
+    config: PreviewConfig,
+) -> Vec<rocket::Route> {
+    rocket_routes::create_routes(Arc::new(storage), config)
+}
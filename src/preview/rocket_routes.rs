@@ -0,0 +1,254 @@
+//! Rocket adapter for mailbox preview.
+//!
+//! Storage calls run on Tokio's blocking pool so custom backends may perform
+//! blocking I/O without stalling the async runtime. Endpoint logic lives in
+//! [`core`](super::core); handlers here only extract parameters.
+//!
+//! Routes carry their own state rather than relying on `Rocket::manage`, so
+//! several mailboxes can be mounted in one application.
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use rocket::futures::stream;
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::response::stream::TextStream;
+use rocket::response::{Responder, Response};
+use rocket::route::{Handler, Outcome};
+use rocket::{Data, Request, Route};
+
+use crate::storage::Storage;
+
+use super::core::{
+    self, ForwardQuery, IndexQuery, ListQuery, LiveUpdates, MailboxQuery, PreviewConfig,
+    PreviewResponse,
+};
+
+/// Shared state for routes.
+#[derive(Clone)]
+struct AppState {
+    storage: Arc<dyn Storage>,
+    config: PreviewConfig,
+}
+
+impl AppState {
+    /// Run a storage operation on the blocking thread pool.
+    async fn with_storage<T, F>(&self, f: F) -> Result<T, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn Storage) -> T + Send + 'static,
+    {
+        let storage = Arc::clone(&self.storage);
+        rocket::tokio::task::spawn_blocking(move || f(storage.as_ref()))
+            .await
+            .map_err(|_| Status::InternalServerError)
+    }
+
+    /// Run a shared handler and convert its response.
+    async fn respond<'r, F>(&self, f: F) -> Outcome<'r>
+    where
+        F: FnOnce(&dyn Storage) -> PreviewResponse + Send + 'static,
+    {
+        match self.with_storage(f).await {
+            Ok(response) => Outcome::Success(into_response(response)),
+            Err(status) => Outcome::Error(status),
+        }
+    }
+}
+
+fn into_response<'r>(response: PreviewResponse) -> Response<'r> {
+    let mut builder = Response::build();
+    builder.status(Status::new(response.status));
+    for (name, value) in response.headers {
+        builder.raw_header(name, value);
+    }
+    builder.sized_body(response.body.len(), Cursor::new(response.body));
+    builder.finalize()
+}
+
+/// Preview endpoints, one per route.
+#[derive(Clone, Copy)]
+enum Endpoint {
+    Index,
+    List,
+    Events,
+    Email,
+    Delete,
+    Read,
+    Forward,
+    Html,
+    Source,
+    Compat,
+    Attachment,
+    Clear,
+}
+
+/// Route handler bound to a storage backend.
+#[derive(Clone)]
+struct PreviewHandler {
+    state: AppState,
+    endpoint: Endpoint,
+}
+
+#[rocket::async_trait]
+impl Handler for PreviewHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let state = &self.state;
+        let id = req.routed_segment(0).unwrap_or_default().to_string();
+
+        match self.endpoint {
+            Endpoint::Index => {
+                let config = state.config.clone();
+                let query = IndexQuery {
+                    script_nonce: query_value(req, "script_nonce"),
+                    style_nonce: query_value(req, "style_nonce"),
+                    mailbox: query_value(req, "mailbox"),
+                };
+                state
+                    .respond(move |storage| core::handle_index(storage, &config, query))
+                    .await
+            }
+            Endpoint::List => {
+                let query = ListQuery {
+                    offset: query_value(req, "offset").and_then(|v| v.parse().ok()),
+                    limit: query_value(req, "limit").and_then(|v| v.parse().ok()),
+                    mailbox: query_value(req, "mailbox"),
+                };
+                state
+                    .respond(move |storage| core::handle_list(storage, query))
+                    .await
+            }
+            Endpoint::Events => events(req, state.clone()).await,
+            Endpoint::Email => {
+                state
+                    .respond(move |storage| core::handle_email(storage, &id))
+                    .await
+            }
+            Endpoint::Delete => {
+                state
+                    .respond(move |storage| core::handle_delete(storage, &id))
+                    .await
+            }
+            Endpoint::Read => {
+                state
+                    .respond(move |storage| core::handle_mark_read(storage, &id))
+                    .await
+            }
+            Endpoint::Forward => {
+                let query = ForwardQuery {
+                    to: query_value(req, "to"),
+                };
+                match state
+                    .with_storage(move |storage| core::forward_email(storage, &id, query))
+                    .await
+                {
+                    Ok(Ok(email)) => {
+                        Outcome::Success(into_response(core::handle_forward(email).await))
+                    }
+                    Ok(Err(response)) => Outcome::Success(into_response(response)),
+                    Err(status) => Outcome::Error(status),
+                }
+            }
+            Endpoint::Html => {
+                state
+                    .respond(move |storage| core::handle_html(storage, &id))
+                    .await
+            }
+            Endpoint::Source => {
+                state
+                    .respond(move |storage| core::handle_source(storage, &id))
+                    .await
+            }
+            Endpoint::Compat => {
+                state
+                    .respond(move |storage| core::handle_compat(storage, &id))
+                    .await
+            }
+            Endpoint::Attachment => match req.param::<usize>(2) {
+                Some(Ok(idx)) => {
+                    state
+                        .respond(move |storage| core::handle_attachment(storage, &id, idx))
+                        .await
+                }
+                _ => Outcome::forward(data, Status::NotFound),
+            },
+            Endpoint::Clear => {
+                let query = MailboxQuery {
+                    mailbox: query_value(req, "mailbox"),
+                };
+                state
+                    .respond(move |storage| core::handle_clear(storage, query))
+                    .await
+            }
+        }
+    }
+}
+
+fn query_value(req: &Request<'_>, name: &str) -> Option<String> {
+    req.query_value::<String>(name).and_then(Result::ok)
+}
+
+/// GET /events - Stream new and removed emails as server-sent events.
+async fn events<'r>(req: &'r Request<'_>, state: AppState) -> Outcome<'r> {
+    let mailbox = query_value(req, "mailbox");
+    let updates = match state
+        .with_storage(move |storage| LiveUpdates::new(storage, mailbox))
+        .await
+    {
+        Ok(updates) => updates,
+        Err(status) => return Outcome::Error(status),
+    };
+
+    let stream = stream::unfold((state, updates), |(state, mut updates)| async move {
+        loop {
+            rocket::tokio::time::sleep(core::LIVE_POLL_INTERVAL).await;
+            let (next, frames) = state
+                .with_storage(move |storage| {
+                    let frames = updates.poll(storage);
+                    (updates, frames)
+                })
+                .await
+                .ok()?;
+            updates = next;
+            if let Some(frames) = frames {
+                return Some((frames, (state, updates)));
+            }
+        }
+    });
+
+    match TextStream::from(stream).respond_to(req) {
+        Ok(mut response) => {
+            response.set_header(ContentType::EventStream);
+            response.set_header(Header::new("Cache-Control", "no-cache"));
+            Outcome::Success(response)
+        }
+        Err(status) => Outcome::Error(status),
+    }
+}
+
+/// Create the mailbox routes with CSP nonce configuration.
+pub fn create_routes(storage: Arc<dyn Storage>, config: PreviewConfig) -> Vec<Route> {
+    let state = AppState { storage, config };
+    let route = |method: Method, path: &str, endpoint: Endpoint| {
+        let handler = PreviewHandler {
+            state: state.clone(),
+            endpoint,
+        };
+        Route::new(method, path, handler)
+    };
+
+    vec![
+        route(Method::Get, "/", Endpoint::Index),
+        route(Method::Get, "/json", Endpoint::List),
+        route(Method::Get, "/events", Endpoint::Events),
+        route(Method::Get, "/<id>", Endpoint::Email),
+        route(Method::Delete, "/<id>", Endpoint::Delete),
+        route(Method::Post, "/<id>/read", Endpoint::Read),
+        route(Method::Post, "/<id>/forward", Endpoint::Forward),
+        route(Method::Get, "/<id>/html", Endpoint::Html),
+        route(Method::Get, "/<id>/source", Endpoint::Source),
+        route(Method::Get, "/<id>/compat", Endpoint::Compat),
+        route(Method::Get, "/<id>/attachments/<idx>", Endpoint::Attachment),
+        route(Method::Post, "/clear", Endpoint::Clear),
+    ]
+}
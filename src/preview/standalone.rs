@@ -14,16 +14,34 @@
 //! // Blocking - runs until error or shutdown
 //! serve("127.0.0.1:3025", storage)?;
 //! ```
+//!
+//! Apps configured with `EMAIL_PROVIDER=local` can skip the storage wiring:
+//!
+//! ```rust,ignore
+//! missive::init()?;
+//! missive::preview::PreviewServer::local("127.0.0.1:8025")?.spawn();
+//! ```
+//!
+//! Forwarded emails (`POST /:id/forward`) are delivered in the background,
+//! so a slow mailer doesn't stall the UI. A delivery that takes longer than
+//! 30 seconds fails with `504 Gateway Timeout`.
 
-use std::io;
-use std::sync::Arc;
+use std::any::Any;
+use std::io::{self, Write};
+use std::sync::{Arc, OnceLock};
 use std::thread;
+use std::time::Duration;
 
 use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use tokio::sync::mpsc;
 
-use crate::storage::MemoryStorage;
+use crate::email::Email;
+use crate::storage::Storage;
 
-use super::core::{self, EmailListResponse, PreviewConfig};
+use super::core::{
+    self, ForwardQuery, IndexQuery, ListQuery, LiveUpdates, MailboxQuery, PreviewConfig,
+    PreviewResponse,
+};
 
 // ============================================================================
 // Public API
@@ -42,10 +60,29 @@ use super::core::{self, EmailListResponse, PreviewConfig};
 /// let mailer = LocalMailer::new();
 /// serve("127.0.0.1:3025", mailer.storage())?;
 /// ```
-pub fn serve(addr: &str, storage: Arc<MemoryStorage>) -> io::Result<()> {
+pub fn serve(addr: &str, storage: impl Storage + 'static) -> io::Result<()> {
     PreviewServer::new(addr, storage)?.run()
 }
 
+/// Start a blocking preview server for the global LocalMailer storage.
+///
+/// Shows emails sent through the mailer created by `EMAIL_PROVIDER=local`,
+/// so CLIs and workers without a web framework can inspect sent mail. The
+/// storage is created on first use, so this may be called before
+/// [`init`](crate::init).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use missive::preview::serve_local;
+///
+/// missive::init()?;
+/// serve_local("127.0.0.1:8025")?;
+/// ```
+pub fn serve_local(addr: &str) -> io::Result<()> {
+    PreviewServer::local(addr)?.run()
+}
+
 /// A standalone preview server with lifecycle control.
 ///
 /// Use this when you need to run the server in a background thread
@@ -70,27 +107,36 @@ pub fn serve(addr: &str, storage: Arc<MemoryStorage>) -> io::Result<()> {
 /// ```
 pub struct PreviewServer {
     server: Server,
-    storage: Arc<MemoryStorage>,
+    storage: Arc<dyn Storage>,
     config: PreviewConfig,
 }
 
 impl PreviewServer {
     /// Create a new preview server bound to the given address.
-    pub fn new(addr: &str, storage: Arc<MemoryStorage>) -> io::Result<Self> {
+    pub fn new(addr: &str, storage: impl Storage + 'static) -> io::Result<Self> {
         Self::with_config(addr, storage, PreviewConfig::default())
     }
 
+    /// Create a preview server bound to the global LocalMailer storage.
+    ///
+    /// See [`serve_local`].
+    pub fn local(addr: &str) -> io::Result<Self> {
+        Self::new(addr, crate::shared_local_storage())
+    }
+
     /// Create a new preview server with CSP nonce configuration.
+    ///
+    /// Accepts any storage backend, including `Arc<MemoryStorage>` and `Arc<dyn Storage>`.
     pub fn with_config(
         addr: &str,
-        storage: Arc<MemoryStorage>,
+        storage: impl Storage + 'static,
         config: PreviewConfig,
     ) -> io::Result<Self> {
         let server = Server::http(addr).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
         Ok(Self {
             server,
-            storage,
+            storage: Arc::new(storage),
             config,
         })
     }
@@ -120,7 +166,7 @@ impl PreviewServer {
 
 fn run_server(
     server: &Server,
-    storage: &Arc<MemoryStorage>,
+    storage: &Arc<dyn Storage>,
     config: &PreviewConfig,
 ) -> io::Result<()> {
     loop {
@@ -133,141 +179,252 @@ fn run_server(
     }
 }
 
-fn handle_request(request: Request, storage: &Arc<MemoryStorage>, config: &PreviewConfig) {
+fn handle_request(request: Request, storage: &Arc<dyn Storage>, config: &PreviewConfig) {
     let method = request.method().clone();
     let path = request.url().to_string();
 
     // Parse query string for CSP nonce overrides
     let (path, query) = parse_path_and_query(&path);
+    let storage = match (&method, path) {
+        (Method::Get, "/events") => {
+            let mailbox = query.get("mailbox").map(String::from);
+            return stream_events(request, Arc::clone(storage), mailbox);
+        }
+        (Method::Post, p) => match forward_id(p) {
+            Some(id) => {
+                let query = ForwardQuery {
+                    to: query.get("to").map(String::from),
+                };
+                return match core::forward_email(storage.as_ref(), id, query) {
+                    Ok(email) => forward(email, request),
+                    Err(response) => {
+                        let _ = request.respond(into_response(response));
+                    }
+                };
+            }
+            None => storage.as_ref(),
+        },
+        _ => storage.as_ref(),
+    };
 
     let response = match (&method, path) {
-        (Method::Get, "/") => handle_index(storage, config, &query),
-        (Method::Get, "/json") => handle_list_json(storage),
-        (Method::Post, "/clear") => handle_clear(storage),
+        (Method::Get, "/") => {
+            let query = IndexQuery {
+                script_nonce: query.get("script_nonce").map(String::from),
+                style_nonce: query.get("style_nonce").map(String::from),
+                mailbox: query.get("mailbox").map(String::from),
+            };
+            core::handle_index(storage, config, query)
+        }
+        (Method::Get, "/json") => {
+            let query = ListQuery {
+                offset: query.get("offset").and_then(|v| v.parse().ok()),
+                limit: query.get("limit").and_then(|v| v.parse().ok()),
+                mailbox: query.get("mailbox").map(String::from),
+            };
+            core::handle_list(storage, query)
+        }
+        (Method::Post, "/clear") => {
+            let query = MailboxQuery {
+                mailbox: query.get("mailbox").map(String::from),
+            };
+            core::handle_clear(storage, query)
+        }
         (Method::Get, p) => handle_dynamic_route(p, storage),
-        _ => not_found(),
+        (Method::Delete, p) => match p.strip_prefix('/').filter(|id| is_uuid(id)) {
+            Some(id) => core::handle_delete(storage, id),
+            None => PreviewResponse::not_found(),
+        },
+        (Method::Post, p) => handle_post_route(p, storage),
+        _ => PreviewResponse::not_found(),
     };
 
-    let _ = request.respond(response);
+    let _ = request.respond(into_response(response));
 }
 
-fn handle_dynamic_route(path: &str, storage: &Arc<MemoryStorage>) -> Response<io::Cursor<Vec<u8>>> {
-    // Strip leading slash
-    let path = path.strip_prefix('/').unwrap_or(path);
+/// Stream live updates on a dedicated thread until the client disconnects.
+///
+/// Writes to the raw connection so each event is flushed immediately.
+fn stream_events(request: Request, storage: Arc<dyn Storage>, mailbox: Option<String>) {
+    thread::spawn(move || {
+        let mut updates = LiveUpdates::new(storage.as_ref(), mailbox);
+        let mut writer = request.into_writer();
+        let mut send = |data: &str| {
+            writer
+                .write_all(data.as_bytes())
+                .and_then(|_| writer.flush())
+        };
 
-    // Check for /{uuid}/html
-    if let Some(id) = path.strip_suffix("/html") {
-        if is_uuid(id) {
-            return handle_email_html(id, storage);
+        let head = "HTTP/1.1 200 OK\r\n\
+                    Content-Type: text/event-stream\r\n\
+                    Cache-Control: no-cache\r\n\
+                    Connection: close\r\n\r\n";
+        if send(head).is_err() {
+            return;
         }
-    }
 
-    // Check for /{uuid}/attachments/{idx}
-    if let Some((id, rest)) = path.split_once("/attachments/") {
-        if is_uuid(id) {
-            if let Ok(idx) = rest.parse::<usize>() {
-                return handle_attachment(id, idx, storage);
+        loop {
+            thread::sleep(core::LIVE_POLL_INTERVAL);
+            if let Some(frames) = updates.poll(storage.as_ref()) {
+                if send(&frames).is_err() {
+                    return;
+                }
             }
         }
-    }
+    });
+}
 
-    // Check for /{uuid} (single email)
-    if is_uuid(path) {
-        return handle_view_email(path, storage);
+fn handle_post_route(path: &str, storage: &dyn Storage) -> PreviewResponse {
+    let path = path.strip_prefix('/').unwrap_or(path);
+
+    // Check for /{uuid}/read
+    if let Some(id) = path.strip_suffix("/read") {
+        if is_uuid(id) {
+            return core::handle_mark_read(storage, id);
+        }
     }
 
-    not_found()
+    PreviewResponse::not_found()
 }
 
-// ============================================================================
-// Route Handlers
-// ============================================================================
+/// The id in a `/{uuid}/forward` path.
+fn forward_id(path: &str) -> Option<&str> {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    path.strip_suffix("/forward").filter(|id| is_uuid(id))
+}
 
-fn handle_index(
-    storage: &Arc<MemoryStorage>,
-    config: &PreviewConfig,
-    query: &QueryParams,
-) -> Response<io::Cursor<Vec<u8>>> {
-    let emails = core::list_emails(storage);
-    let script_nonce = query
-        .get("script_nonce")
-        .or(config.script_nonce.as_deref())
-        .map(String::from);
-    let style_nonce = query
-        .get("style_nonce")
-        .or(config.style_nonce.as_deref())
-        .map(String::from);
-
-    let html = core::render_index(&emails, script_nonce, style_nonce);
-    html_response(html)
+/// How long a forwarded email may take to deliver before the request fails
+/// with `504 Gateway Timeout`.
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(30);
+
+type Forward = (Email, Request);
+
+static FORWARDER: OnceLock<mpsc::UnboundedSender<Forward>> = OnceLock::new();
+
+/// Deliver a forwarded email and respond to `request` when it's done.
+///
+/// Deliveries run on one background thread with its own runtime, started on
+/// first use, so they work whether or not the server runs inside a runtime
+/// and don't hold up other requests.
+fn forward(email: Email, request: Request) {
+    let forwarder = FORWARDER.get_or_init(|| {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        thread::spawn(move || run_forwarder(receiver));
+        sender
+    });
+    if let Err(mpsc::error::SendError((_, request))) = forwarder.send((email, request)) {
+        let response = PreviewResponse::error(500, "Forwarding failed: forwarder stopped");
+        let _ = request.respond(into_response(response));
+    }
 }
 
-fn handle_list_json(storage: &Arc<MemoryStorage>) -> Response<io::Cursor<Vec<u8>>> {
-    let emails = core::list_emails(storage);
-    let response = EmailListResponse { data: emails };
-    json_response(&response)
+fn run_forwarder(mut receiver: mpsc::UnboundedReceiver<Forward>) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            let message = format!("Forwarding failed: {}", e);
+            while let Some((_, request)) = receiver.blocking_recv() {
+                let _ = request.respond(into_response(PreviewResponse::error(500, &message)));
+            }
+            return;
+        }
+    };
+    runtime.block_on(async move {
+        while let Some((email, request)) = receiver.recv().await {
+            tokio::spawn(async move {
+                let response = deliver_forward(email).await;
+                let _ = request.respond(into_response(response));
+            });
+        }
+    });
 }
 
-fn handle_view_email(id: &str, storage: &Arc<MemoryStorage>) -> Response<io::Cursor<Vec<u8>>> {
-    match core::get_email(storage, id) {
-        Some(email) => json_response(&email),
-        None => not_found(),
+/// Deliver within [`FORWARD_TIMEOUT`], reporting a panicking mailer's message.
+async fn deliver_forward(email: Email) -> PreviewResponse {
+    let mut delivery = tokio::spawn(core::handle_forward(email));
+    match tokio::time::timeout(FORWARD_TIMEOUT, &mut delivery).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(e)) => {
+            let reason = match e.try_into_panic() {
+                Ok(payload) => panic_message(payload.as_ref()),
+                Err(e) => e.to_string(),
+            };
+            PreviewResponse::error(500, &format!("Forwarding failed: {}", reason))
+        }
+        Err(_) => {
+            delivery.abort();
+            PreviewResponse::error(504, "Forwarding timed out")
+        }
     }
 }
 
-fn handle_email_html(id: &str, storage: &Arc<MemoryStorage>) -> Response<io::Cursor<Vec<u8>>> {
-    match core::get_email_html(storage, id) {
-        Some(html) => html_response(html),
-        None => not_found(),
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "mailer panicked".to_string()
     }
 }
 
-fn handle_attachment(
-    id: &str,
-    idx: usize,
-    storage: &Arc<MemoryStorage>,
-) -> Response<io::Cursor<Vec<u8>>> {
-    match core::get_attachment(storage, id, idx) {
-        Some(att) => {
-            let cursor = io::Cursor::new(att.data);
-            let content_type =
-                Header::from_bytes("Content-Type", att.content_type.as_bytes()).unwrap();
-            let disposition = Header::from_bytes(
-                "Content-Disposition",
-                format!("attachment; filename=\"{}\"", att.filename).as_bytes(),
-            )
-            .unwrap();
-
-            Response::from_data(cursor.into_inner())
-                .with_header(content_type)
-                .with_header(disposition)
+fn handle_dynamic_route(path: &str, storage: &dyn Storage) -> PreviewResponse {
+    // Strip leading slash
+    let path = path.strip_prefix('/').unwrap_or(path);
+
+    // Check for /{uuid}/html
+    if let Some(id) = path.strip_suffix("/html") {
+        if is_uuid(id) {
+            return core::handle_html(storage, id);
         }
-        None => not_found(),
     }
-}
 
-fn handle_clear(storage: &Arc<MemoryStorage>) -> Response<io::Cursor<Vec<u8>>> {
-    core::clear_emails(storage);
-    Response::from_data(Vec::new()).with_status_code(StatusCode(204))
-}
+    // Check for /{uuid}/source
+    if let Some(id) = path.strip_suffix("/source") {
+        if is_uuid(id) {
+            return core::handle_source(storage, id);
+        }
+    }
 
-// ============================================================================
-// Response Helpers
-// ============================================================================
+    // Check for /{uuid}/compat
+    if let Some(id) = path.strip_suffix("/compat") {
+        if is_uuid(id) {
+            return core::handle_compat(storage, id);
+        }
+    }
 
-fn html_response(body: String) -> Response<io::Cursor<Vec<u8>>> {
-    let header = Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap();
-    Response::from_data(body.into_bytes()).with_header(header)
-}
+    // Check for /{uuid}/attachments/{idx}
+    if let Some((id, rest)) = path.split_once("/attachments/") {
+        if is_uuid(id) {
+            if let Ok(idx) = rest.parse::<usize>() {
+                return core::handle_attachment(storage, id, idx);
+            }
+        }
+    }
+
+    // Check for /{uuid} (single email)
+    if is_uuid(path) {
+        return core::handle_email(storage, path);
+    }
 
-fn json_response<T: serde::Serialize>(data: &T) -> Response<io::Cursor<Vec<u8>>> {
-    let body = serde_json::to_vec(data).unwrap_or_default();
-    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
-    Response::from_data(body).with_header(header)
+    PreviewResponse::not_found()
 }
 
-fn not_found() -> Response<io::Cursor<Vec<u8>>> {
-    Response::from_data(Vec::new()).with_status_code(StatusCode(404))
+// ============================================================================
+// Response Conversion
+// ============================================================================
+
+fn into_response(response: PreviewResponse) -> Response<io::Cursor<Vec<u8>>> {
+    response.headers.into_iter().fold(
+        Response::from_data(response.body).with_status_code(StatusCode(response.status)),
+        |res, (name, value)| match Header::from_bytes(name, value.as_bytes()) {
+            Ok(header) => res.with_header(header),
+            Err(()) => res,
+        },
+    )
 }
 
 // ============================================================================
@@ -320,13 +477,42 @@ fn parse_path_and_query(url: &str) -> (&str, QueryParams) {
         .filter(|s| !s.is_empty())
         .filter_map(|pair| {
             let (k, v) = pair.split_once('=')?;
-            Some((k.to_string(), v.to_string()))
+            Some((decode_component(k), decode_component(v)))
         })
         .collect();
 
     (path, QueryParams { params })
 }
 
+/// Decode `%XX` escapes and `+` (as a space) in a query component.
+fn decode_component(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (_, Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (b'+', None) => {
+                out.push(b' ');
+                i += 1;
+            }
+            (byte, None) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -358,5 +544,9 @@ mod tests {
         assert_eq!(path, "/");
         assert_eq!(query.get("a"), Some("1"));
         assert_eq!(query.get("b"), Some("2"));
+
+        let (_, query) = parse_path_and_query("/x/forward?to=me%40example.com&name=A+B%zz");
+        assert_eq!(query.get("to"), Some("me@example.com"));
+        assert_eq!(query.get("name"), Some("A B%zz"));
     }
 }
@@ -0,0 +1,169 @@
+//! How recipients and subjects appear in logs.
+//!
+//! By default the `missive.deliver` span records every `to` address and the
+//! subject, which suits development but not deployments where addresses are
+//! personal data. Choose what is recorded with [`set_log_recipients()`]
+//! (or `EMAIL_LOG_RECIPIENTS`) and [`set_log_subjects()`] (or
+//! `EMAIL_LOG_SUBJECTS`):
+//!
+//! ```rust,ignore
+//! use missive::privacy::{self, LogRecipients};
+//!
+//! privacy::set_log_recipients(LogRecipients::DomainOnly); // "example.com"
+//! privacy::set_log_subjects(false);
+//! ```
+//!
+//! The setting applies to delivery spans and to [`LoggerMailer`] output.
+//! Metric labels never contain recipients or subjects.
+//!
+//! [`LoggerMailer`]: crate::providers::LoggerMailer
+
+use std::str::FromStr;
+
+use parking_lot::RwLock;
+
+use crate::address::hash_address;
+use crate::error::MailError;
+
+/// Installed settings; `None` falls back to the environment.
+static LOG_RECIPIENTS: RwLock<Option<LogRecipients>> = RwLock::new(None);
+static LOG_SUBJECTS: RwLock<Option<bool>> = RwLock::new(None);
+
+/// How recipient addresses are written to logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogRecipients {
+    /// The full address (default).
+    #[default]
+    Full,
+    /// Hex-encoded SHA-256 of the lowercased address, matching the audit log.
+    Hashed,
+    /// Only the domain, e.g. `example.com`.
+    DomainOnly,
+    /// Recipients are not logged.
+    Off,
+}
+
+impl LogRecipients {
+    /// The address as it should be logged, or `None` if it must be left out.
+    pub fn apply(self, email: &str) -> Option<String> {
+        match self {
+            LogRecipients::Full => Some(email.to_string()),
+            LogRecipients::Hashed => Some(hash_address(email)),
+            LogRecipients::DomainOnly => Some(
+                email
+                    .rsplit_once('@')
+                    .map_or(email, |(_, domain)| domain)
+                    .to_lowercase(),
+            ),
+            LogRecipients::Off => None,
+        }
+    }
+
+    /// Read `EMAIL_LOG_RECIPIENTS`, defaulting to [`Full`](Self::Full).
+    ///
+    /// An invalid value is logged and treated as [`Off`](Self::Off), so a
+    /// typo never leaks addresses.
+    pub fn from_env() -> Self {
+        match crate::config::var("EMAIL_LOG_RECIPIENTS") {
+            None => LogRecipients::Full,
+            Some(value) => value.parse().unwrap_or_else(|e: MailError| {
+                tracing::warn!(error = %e, "Not logging recipients");
+                LogRecipients::Off
+            }),
+        }
+    }
+}
+
+impl FromStr for LogRecipients {
+    type Err = MailError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(LogRecipients::Full),
+            "hashed" => Ok(LogRecipients::Hashed),
+            "domain" | "domain_only" => Ok(LogRecipients::DomainOnly),
+            "off" | "none" => Ok(LogRecipients::Off),
+            other => Err(MailError::Configuration(format!(
+                "Invalid EMAIL_LOG_RECIPIENTS: {} (expected full, hashed, domain or off)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Set how recipients are logged, overriding `EMAIL_LOG_RECIPIENTS`.
+pub fn set_log_recipients(mode: LogRecipients) {
+    *LOG_RECIPIENTS.write() = Some(mode);
+}
+
+/// Set whether subjects are logged, overriding `EMAIL_LOG_SUBJECTS`.
+pub fn set_log_subjects(enabled: bool) {
+    *LOG_SUBJECTS.write() = Some(enabled);
+}
+
+/// Remove installed settings, falling back to the environment.
+pub fn reset() {
+    *LOG_RECIPIENTS.write() = None;
+    *LOG_SUBJECTS.write() = None;
+}
+
+/// The installed recipient mode, or [`LogRecipients::from_env`].
+pub fn log_recipients() -> LogRecipients {
+    (*LOG_RECIPIENTS.read()).unwrap_or_else(LogRecipients::from_env)
+}
+
+/// Whether subjects are logged: the installed setting, otherwise
+/// `EMAIL_LOG_SUBJECTS` (default `true`).
+pub fn log_subjects() -> bool {
+    (*LOG_SUBJECTS.read()).unwrap_or_else(|| {
+        !crate::config::var("EMAIL_LOG_SUBJECTS").is_some_and(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "false" | "0" | "no" | "off"
+            )
+        })
+    })
+}
+
+/// Apply the current recipient mode to a list of addresses.
+pub(crate) fn recipients<'a>(emails: impl IntoIterator<Item = &'a str>) -> Option<Vec<String>> {
+    let mode = log_recipients();
+    if mode == LogRecipients::Off {
+        return None;
+    }
+    Some(emails.into_iter().filter_map(|e| mode.apply(e)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let email = "Alice@Example.com";
+        assert_eq!(LogRecipients::Full.apply(email).as_deref(), Some(email));
+        assert_eq!(
+            LogRecipients::Hashed.apply(email),
+            Some(hash_address("alice@example.com"))
+        );
+        assert_eq!(
+            LogRecipients::DomainOnly.apply(email).as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(LogRecipients::Off.apply(email), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "hashed".parse::<LogRecipients>().unwrap(),
+            LogRecipients::Hashed
+        );
+        assert_eq!(
+            " Domain ".parse::<LogRecipients>().unwrap(),
+            LogRecipients::DomainOnly
+        );
+        assert_eq!("off".parse::<LogRecipients>().unwrap(), LogRecipients::Off);
+        assert!("partial".parse::<LogRecipients>().is_err());
+    }
+}
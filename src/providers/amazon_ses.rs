@@ -39,6 +39,31 @@
 //! * `tags` (list[{name, value}]) - Message tags for tracking
 //! * `configuration_set_name` (string) - SES configuration set name
 //! * `security_token` (string) - Temporary security token for IAM roles
+//! * `template` (string) - SES template for [`Mailer::deliver_personalized`]
+//!   and [`Mailer::deliver_many`]
+//! * `template_data` (map) - Data for the template (the defaults, in a mail merge)
+//!
+//! [`Email::return_path`](crate::Email::return_path) is sent as `Source`
+//! (overriding `ses_source`), or as `ReturnPath` for bulk sends.
+//!
+//! ## Mail Merge
+//!
+//! With a `template` option, [`Mailer::deliver_personalized`] uses
+//! SendBulkTemplatedEmail, passing each recipient's data as
+//! `ReplacementTemplateData`:
+//!
+//! ```rust,ignore
+//! let email = Email::new()
+//!     .from("news@example.com")
+//!     .provider_option("template", "monthly-digest")
+//!     .personalize(vec![("alice@example.com", json!({"name": "Alice"}))]);
+//!
+//! mailer.deliver_personalized(&email).await?;
+//! ```
+//!
+//! `deliver_many` does the same for emails with a `template` option: emails
+//! that differ only in recipients and `template_data` are sent together with
+//! SendBulkTemplatedEmail, 50 per request, and the rest with SendRawEmail.
 //!
 //! ## IAM Role Authentication
 //!
@@ -51,21 +76,38 @@
 //!     .provider_option("security_token", temporary_session_token);
 //! ```
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use base64::Engine;
 use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
 use reqwest::Client;
-use ring::hmac;
-use sha2::{Digest, Sha256};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
 
+use crate::address::Address;
+use crate::aws_sig::{self, amz_date, amz_datetime, hex_sha256};
+use crate::bounce::{BounceClass, BounceClassifier};
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+#[cfg(feature = "insights")]
+use crate::insights::{Insights, SendQuota, SendStatistics};
+use crate::mailer::{
+    Capabilities, DeliveryResult, Mailer, MessageStatus, DEFAULT_BATCH_CONCURRENCY,
+};
 
 const SERVICE_NAME: &str = "ses";
 const ACTION: &str = "SendRawEmail";
+const BULK_ACTION: &str = "SendBulkTemplatedEmail";
+const QUOTA_ACTION: &str = "GetSendQuota";
+#[cfg(feature = "insights")]
+const STATISTICS_ACTION: &str = "GetSendStatistics";
+/// Destinations allowed in one SendBulkTemplatedEmail request.
+const MAX_BULK_DESTINATIONS: usize = 50;
 const VERSION: &str = "2010-12-01";
-const ENCODING: &str = "AWS4-HMAC-SHA256";
+const ENCODING: &str = aws_sig::ALGORITHM;
 
 /// Amazon SES API email provider.
 pub struct AmazonSesMailer {
@@ -79,6 +121,7 @@ pub struct AmazonSesMailer {
     ses_source_arn: Option<String>,
     ses_from_arn: Option<String>,
     ses_return_path_arn: Option<String>,
+    concurrency: usize,
 }
 
 impl AmazonSesMailer {
@@ -93,11 +136,12 @@ impl AmazonSesMailer {
             access_key: access_key.into(),
             secret: secret.into(),
             host: None,
-            client: Client::new(),
+            client: crate::http::default_client(),
             ses_source: None,
             ses_source_arn: None,
             ses_from_arn: None,
             ses_return_path_arn: None,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
         }
     }
 
@@ -118,6 +162,7 @@ impl AmazonSesMailer {
             ses_source_arn: None,
             ses_from_arn: None,
             ses_return_path_arn: None,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
         }
     }
 
@@ -127,6 +172,20 @@ impl AmazonSesMailer {
         self
     }
 
+    /// Send up to `concurrency` emails at once in `deliver_many()` (default: 8).
+    ///
+    /// Keep this at or below your SES maximum send rate.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     /// Set the SES Source parameter.
     pub fn ses_source(mut self, source: impl Into<String>) -> Self {
         self.ses_source = Some(source.into());
@@ -163,7 +222,7 @@ impl AmazonSesMailer {
     }
 
     fn build_body(&self, email: &Email) -> Result<String, MailError> {
-        let raw_message = build_mime_message(email)?;
+        let raw_message = crate::mime::build_mime_message(email)?;
         let encoded = base64::engine::general_purpose::STANDARD.encode(&raw_message);
         let url_encoded = urlencoding::encode(&encoded);
 
@@ -173,8 +232,12 @@ impl AmazonSesMailer {
             ("RawMessage.Data".to_string(), url_encoded.into_owned()),
         ];
 
-        // Optional SES parameters
-        if let Some(ref source) = self.ses_source {
+        // Optional SES parameters; an email's bounce address wins over ses_source
+        if let Some(return_path) = &email.return_path {
+            // Values here aren't encoded below; VERP addresses contain `+` and `=`
+            let source = urlencoding::encode(&return_path.email).into_owned();
+            params.push(("Source".to_string(), source));
+        } else if let Some(ref source) = self.ses_source {
             params.push(("Source".to_string(), source.clone()));
         }
         if let Some(ref source_arn) = self.ses_source_arn {
@@ -221,8 +284,227 @@ impl AmazonSesMailer {
         Ok(body)
     }
 
+    fn build_bulk_body(
+        &self,
+        email: &Email,
+        template: &str,
+        default_data: &serde_json::Value,
+        destinations: &[BulkDestination<'_>],
+    ) -> Result<String, MailError> {
+        let source = match &self.ses_source {
+            Some(source) => source.clone(),
+            None => email
+                .from
+                .as_ref()
+                .ok_or(MailError::MissingField("from"))?
+                .formatted_rfc5322_ascii()?,
+        };
+        let mut params = vec![
+            ("Action".to_string(), BULK_ACTION.to_string()),
+            ("Version".to_string(), VERSION.to_string()),
+            ("Source".to_string(), source),
+            ("Template".to_string(), template.to_string()),
+            (
+                "DefaultTemplateData".to_string(),
+                serde_json::to_string(default_data)?,
+            ),
+        ];
+        if let Some(ref source_arn) = self.ses_source_arn {
+            params.push(("SourceArn".to_string(), source_arn.clone()));
+        }
+        if let Some(return_path) = &email.return_path {
+            params.push(("ReturnPath".to_string(), return_path.email.clone()));
+        }
+        if let Some(name) = email
+            .provider_options
+            .get("configuration_set_name")
+            .and_then(|v| v.as_str())
+        {
+            params.push(("ConfigurationSetName".to_string(), name.to_string()));
+        }
+        for (i, reply_to) in email.reply_to.iter().enumerate() {
+            params.push((
+                format!("ReplyToAddresses.member.{}", i + 1),
+                reply_to.formatted_rfc5322_ascii()?,
+            ));
+        }
+        if let Some(tags) = email
+            .provider_options
+            .get("tags")
+            .and_then(|v| v.as_array())
+        {
+            let tags = tags
+                .iter()
+                .filter_map(|tag| Some((tag.get("name")?.as_str()?, tag.get("value")?.as_str()?)));
+            for (i, (name, value)) in tags.enumerate() {
+                params.push((
+                    format!("DefaultTags.member.{}.Name", i + 1),
+                    name.to_string(),
+                ));
+                params.push((
+                    format!("DefaultTags.member.{}.Value", i + 1),
+                    value.to_string(),
+                ));
+            }
+        }
+        for (i, destination) in destinations.iter().enumerate() {
+            let prefix = format!("Destinations.member.{}", i + 1);
+            for (field, addresses) in [
+                ("ToAddresses", destination.to),
+                ("CcAddresses", destination.cc),
+                ("BccAddresses", destination.bcc),
+            ] {
+                for (j, address) in addresses.iter().enumerate() {
+                    params.push((
+                        format!("{}.Destination.{}.member.{}", prefix, field, j + 1),
+                        address.formatted_rfc5322_ascii()?,
+                    ));
+                }
+            }
+            params.push((
+                format!("{}.ReplacementTemplateData", prefix),
+                serde_json::to_string(&destination.data)?,
+            ));
+        }
+
+        params.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&"))
+    }
+
+    /// Send one SendBulkTemplatedEmail request, returning a result per
+    /// destination. SES merges each destination's data over `default_data`.
+    async fn send_bulk(
+        &self,
+        email: &Email,
+        template: &str,
+        default_data: &serde_json::Value,
+        destinations: &[BulkDestination<'_>],
+    ) -> Result<Vec<DeliveryResult>, MailError> {
+        let body = self.build_bulk_body(email, template, default_data, destinations)?;
+        let response: SendBulkTemplatedEmailResponse =
+            parse_xml(&self.post(body, security_token(email)).await?)?;
+        let request_id = response.response_metadata.request_id;
+
+        let statuses = response.send_bulk_templated_email_result.status.member;
+        let mut results = Vec::with_capacity(destinations.len());
+        for (destination, status) in destinations.iter().zip(&statuses) {
+            if status.status != "Success" {
+                let recipient = destination.to.first().map_or("", |a| a.email.as_str());
+                return Err(MailError::provider(
+                    "amazon_ses",
+                    format!("[{}] {}: {}", status.status, recipient, status.error),
+                )
+                .with_code(status.status.clone()));
+            }
+            results.push(DeliveryResult::with_response(
+                status.message_id.clone(),
+                serde_json::json!({
+                    "provider": "amazon_ses",
+                    "request_id": request_id,
+                }),
+            ));
+        }
+        if statuses.len() < destinations.len() {
+            return Err(MailError::provider(
+                "amazon_ses",
+                format!(
+                    "Expected {} bulk statuses, got {}",
+                    destinations.len(),
+                    statuses.len()
+                ),
+            ));
+        }
+        Ok(results)
+    }
+
+    /// Sign and send a request, returning the response body on success.
+    async fn post(&self, body: String, security_token: Option<&str>) -> Result<String, MailError> {
+        let headers = self.sign_request("POST", "/", &body, Utc::now(), security_token);
+        let url = self.base_url();
+
+        let mut request = self.client.post(&url);
+        for (name, value) in headers {
+            request = request.header(&name, &value);
+        }
+        request = request.header("User-Agent", format!("missive/{}", crate::VERSION));
+        request = request.headers(crate::telemetry::trace_headers());
+        request = request.body(body);
+
+        let response = request.send().await?;
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+        let body = response.text().await?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            let error = quick_xml::de::from_str::<SesErrorResponse>(&body)
+                .map(|response| response.error)
+                .unwrap_or_default();
+
+            Err(MailError::provider_with_status(
+                "amazon_ses",
+                format!("[{}] {}", error.code, error.message),
+                error.status(status.as_u16()),
+            )
+            .with_code(error.code)
+            .with_retry_after(retry_after))
+        }
+    }
+
+    /// Sign and send a SESv2 `GET`, returning the response body on success.
+    async fn get(&self, path: &str) -> Result<String, MailError> {
+        let headers = self.sign_request("GET", path, "", Utc::now(), None);
+        let url = format!("{}{}", self.base_url(), path);
+
+        let mut request = self.client.get(&url);
+        for (name, value) in headers {
+            request = request.header(&name, &value);
+        }
+        request = request.header("User-Agent", format!("missive/{}", crate::VERSION));
+        request = request.headers(crate::telemetry::trace_headers());
+
+        let response = request.send().await?;
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+        // `NotFoundException:http://internal.amazon.com/...`
+        let code = response
+            .headers()
+            .get("x-amzn-ErrorType")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(':').next())
+            .unwrap_or_default()
+            .to_string();
+        let body = response.text().await?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            let message = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|v| {
+                    let message = v.get("message").or_else(|| v.get("Message"))?;
+                    message.as_str().map(String::from)
+                })
+                .unwrap_or(body);
+            Err(MailError::provider_with_status(
+                "amazon_ses",
+                format!("[{}] {}", code, message),
+                status.as_u16(),
+            )
+            .with_code(code)
+            .with_retry_after(retry_after))
+        }
+    }
+
     fn sign_request(
         &self,
+        method: &str,
+        path: &str,
         body: &str,
         date_time: DateTime<Utc>,
         security_token: Option<&str>,
@@ -233,11 +515,16 @@ impl AmazonSesMailer {
 
         // Build headers map
         let mut headers = vec![
-            ("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()),
             ("Host".to_string(), host.clone()),
             ("X-Amz-Date".to_string(), amz_date_str.clone()),
-            ("Content-Length".to_string(), body.len().to_string()),
         ];
+        if method == "POST" {
+            headers.push((
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            ));
+            headers.push(("Content-Length".to_string(), body.len().to_string()));
+        }
 
         // Add security token if present
         if let Some(token) = security_token {
@@ -266,8 +553,8 @@ impl AmazonSesMailer {
 
         // Build canonical request
         let canonical_request = format!(
-            "POST\n/\n\n{}\n\n{}\n{}",
-            canonical_headers, signed_headers, body_hash
+            "{}\n{}\n\n{}\n\n{}\n{}",
+            method, path, canonical_headers, signed_headers, body_hash
         );
 
         let request_hash = hex_sha256(canonical_request.as_bytes());
@@ -294,321 +581,459 @@ impl AmazonSesMailer {
     }
 
     fn generate_signature(&self, string_to_sign: &str, date_time: &DateTime<Utc>) -> String {
-        let date = amz_date(date_time);
-
-        // AWS4 + secret
-        let k_secret = format!("AWS4{}", self.secret);
+        aws_sig::signature(
+            &self.secret,
+            date_time,
+            &self.region,
+            SERVICE_NAME,
+            string_to_sign,
+        )
+    }
+}
 
-        // Sign date
-        let k_date = hmac_sha256(k_secret.as_bytes(), date.as_bytes());
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Mailer for AmazonSesMailer {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let body = self.build_body(email)?;
+        let response: SendRawEmailResponse =
+            parse_xml(&self.post(body, security_token(email)).await?)?;
+
+        Ok(DeliveryResult::with_response(
+            response.send_raw_email_result.message_id,
+            serde_json::json!({
+                "provider": "amazon_ses",
+                "request_id": response.response_metadata.request_id,
+            }),
+        ))
+    }
 
-        // Sign region
-        let k_region = hmac_sha256(&k_date, self.region.as_bytes());
+    /// With a `template` provider option, send the merge with
+    /// SendBulkTemplatedEmail, up to 50 recipients per request. Otherwise
+    /// each recipient's email is rendered locally and sent separately.
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        let Some(template) = email
+            .provider_options
+            .get("template")
+            .and_then(|v| v.as_str())
+        else {
+            let emails = email.render_personalizations();
+            return self.deliver_many(&emails).await;
+        };
+
+        let default_data = email
+            .provider_options
+            .get("template_data")
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({}));
+        let mut results = Vec::with_capacity(email.personalizations.len());
+        for chunk in email.personalizations.chunks(MAX_BULK_DESTINATIONS) {
+            let destinations: Vec<BulkDestination> = chunk
+                .iter()
+                .map(|p| BulkDestination {
+                    to: std::slice::from_ref(&p.to),
+                    cc: &[],
+                    bcc: &[],
+                    data: crate::personalize::merged(None, &p.data),
+                })
+                .collect();
+            results.extend(
+                self.send_bulk(email, template, &default_data, &destinations)
+                    .await?,
+            );
+        }
+        Ok(results)
+    }
 
-        // Sign service
-        let k_service = hmac_sha256(&k_region, SERVICE_NAME.as_bytes());
+    /// Emails with a `template` provider option go out with
+    /// SendBulkTemplatedEmail, grouped by everything but their recipients
+    /// and `template_data`, up to 50 per request. The rest are sent with
+    /// SendRawEmail, [`batch_concurrency`](Self::batch_concurrency) at a time.
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        // Group (and validate) every templated email before sending any
+        let mut groups: Vec<(&Email, &str, Vec<usize>)> = Vec::new();
+        let mut keys: HashMap<String, usize> = HashMap::new();
+        let mut single = Vec::new();
+        for (i, email) in emails.iter().enumerate() {
+            let Some(template) = email
+                .provider_options
+                .get("template")
+                .and_then(|v| v.as_str())
+            else {
+                single.push(i);
+                continue;
+            };
+            if email.to.is_empty() {
+                return Err(MailError::MissingField("to"));
+            }
+            let mut shared = email.clone();
+            shared.to.clear();
+            shared.cc.clear();
+            shared.bcc.clear();
+            shared.provider_options.remove("template_data");
+            let key = serde_json::to_string(&shared)?;
+            match keys.get(&key) {
+                Some(&group) => groups[group].2.push(i),
+                None => {
+                    keys.insert(key, groups.len());
+                    groups.push((email, template, vec![i]));
+                }
+            }
+        }
 
-        // Sign "aws4_request"
-        let k_signing = hmac_sha256(&k_service, b"aws4_request");
+        // Each email's `template_data` goes in its own destination; a shared
+        // default would leak one recipient's values into another's email
+        let no_default = serde_json::json!({});
+        let mut results: Vec<Option<DeliveryResult>> = emails.iter().map(|_| None).collect();
+        for (email, template, members) in groups {
+            for chunk in members.chunks(MAX_BULK_DESTINATIONS) {
+                let destinations: Vec<BulkDestination> = chunk
+                    .iter()
+                    .map(|&i| BulkDestination {
+                        to: &emails[i].to,
+                        cc: &emails[i].cc,
+                        bcc: &emails[i].bcc,
+                        data: emails[i]
+                            .provider_options
+                            .get("template_data")
+                            .cloned()
+                            .unwrap_or_else(|| serde_json::json!({})),
+                    })
+                    .collect();
+                let sent = self
+                    .send_bulk(email, template, &no_default, &destinations)
+                    .await?;
+                for (&i, result) in chunk.iter().zip(sent) {
+                    results[i] = Some(result);
+                }
+            }
+        }
 
-        // Sign the string to sign
-        let signature = hmac_sha256(&k_signing, string_to_sign.as_bytes());
+        let deliveries: Vec<_> = single.iter().map(|&i| self.deliver(&emails[i])).collect();
+        let sent: Vec<DeliveryResult> = stream::iter(deliveries)
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+        for (i, result) in single.into_iter().zip(sent) {
+            results[i] = Some(result);
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
 
-        hex::encode(signature)
+    fn batch_concurrency(&self) -> usize {
+        self.concurrency
     }
-}
 
-fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
-    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
-    hmac::sign(&key, data).as_ref().to_vec()
-}
+    fn provider_name(&self) -> &'static str {
+        "amazon_ses"
+    }
 
-fn hex_sha256(data: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    hex::encode(hasher.finalize())
-}
+    /// Only emails with a `template` option are sent through the bulk API.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("amazon_ses")
+        }
+    }
 
-fn amz_date(dt: &DateTime<Utc>) -> String {
-    dt.format("%Y%m%d").to_string()
-}
+    /// Calls GetSendQuota, which needs valid credentials but sends nothing.
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.post(action_body(QUOTA_ACTION), None).await.map(|_| ())
+    }
 
-fn amz_datetime(dt: &DateTime<Utc>) -> String {
-    dt.format("%Y%m%dT%H%M%SZ").to_string()
+    /// Reads the message's events with the SESv2 GetMessageInsights API,
+    /// which needs the Virtual Deliverability Manager enabled. The latest
+    /// event for the first recipient wins.
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        let path = format!("/v2/email/insights/{}/", urlencoding::encode(message_id));
+        match self.get(&path).await {
+            Ok(body) => Ok(serde_json::from_str::<SesMessageInsights>(&body)?.message_status()),
+            Err(e) if e.status_code() == Some(404) => Ok(MessageStatus::Unknown),
+            Err(e) => Err(e),
+        }
+    }
 }
 
-/// Build a MIME message from an Email.
-fn build_mime_message(email: &Email) -> Result<Vec<u8>, MailError> {
-    let from = email
-        .from
-        .as_ref()
-        .ok_or(MailError::MissingField("from"))?;
-
-    if email.to.is_empty() {
-        return Err(MailError::MissingField("to"));
+#[cfg(feature = "insights")]
+impl AmazonSesMailer {
+    /// Current sending limits (GetSendQuota), over a rolling 24 hours.
+    pub async fn get_send_quota(&self) -> Result<SendQuota, MailError> {
+        let response: GetSendQuotaResponse =
+            parse_xml(&self.post(action_body(QUOTA_ACTION), None).await?)?;
+        let quota = response.get_send_quota_result;
+
+        // SES reports an unlimited quota as -1.
+        let max = quota.max_24_hour_send;
+        let rate = quota.max_send_rate;
+        Ok(SendQuota {
+            limit: (max >= 0.0).then_some(max as u64),
+            sent: quota.sent_last_24_hours as u64,
+            max_send_rate: (rate > 0.0).then_some(rate),
+            resets_at: None,
+        })
     }
 
-    let mut message = String::new();
-    let boundary = format!("----=_Part_{}", uuid::Uuid::new_v4().to_string().replace("-", ""));
-
-    // Headers
-    message.push_str(&format!("From: {}\r\n", from.formatted()));
-    message.push_str(&format!(
-        "To: {}\r\n",
-        email
-            .to
-            .iter()
-            .map(|a| a.formatted())
-            .collect::<Vec<_>>()
-            .join(", ")
-    ));
-
-    if !email.cc.is_empty() {
-        message.push_str(&format!(
-            "Cc: {}\r\n",
-            email
-                .cc
-                .iter()
-                .map(|a| a.formatted())
-                .collect::<Vec<_>>()
-                .join(", ")
-        ));
+    /// Delivery counts in 15-minute periods for the last two weeks
+    /// (GetSendStatistics), oldest first.
+    pub async fn get_send_statistics(&self) -> Result<Vec<SendStatistics>, MailError> {
+        let response: GetSendStatisticsResponse =
+            parse_xml(&self.post(action_body(STATISTICS_ACTION), None).await?)?;
+
+        let mut periods: Vec<SendStatistics> = response
+            .get_send_statistics_result
+            .send_data_points
+            .member
+            .into_iter()
+            .filter_map(|point| {
+                Some(SendStatistics {
+                    start: DateTime::parse_from_rfc3339(point.timestamp.trim())
+                        .ok()?
+                        .with_timezone(&Utc),
+                    attempts: point.delivery_attempts,
+                    delivered: None,
+                    bounces: point.bounces,
+                    complaints: point.complaints,
+                    rejects: point.rejects,
+                })
+            })
+            .collect();
+        periods.sort_by_key(|p| p.start);
+        Ok(periods)
     }
+}
 
-    // BCC is NOT included in headers (that's the point of BCC)
-    // But we need to include them as recipients in the RCPT TO command
-    // SES handles this via the raw message destinations
-
-    if let Some(reply_to) = email.reply_to.first() {
-        message.push_str(&format!("Reply-To: {}\r\n", reply_to.formatted()));
+#[cfg(feature = "insights")]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Insights for AmazonSesMailer {
+    async fn send_quota(&self) -> Result<SendQuota, MailError> {
+        self.get_send_quota().await
     }
 
-    message.push_str(&format!("Subject: {}\r\n", email.subject));
-    message.push_str("MIME-Version: 1.0\r\n");
-
-    // Custom headers
-    for (name, value) in &email.headers {
-        message.push_str(&format!("{}: {}\r\n", name, value));
+    async fn send_statistics(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<SendStatistics>, MailError> {
+        let mut periods = self.get_send_statistics().await?;
+        periods.retain(|p| p.start >= since);
+        Ok(periods)
     }
+}
 
-    // Determine content structure
-    let has_text = email.text_body.is_some();
-    let has_html = email.html_body.is_some();
-    let has_attachments = !email.attachments.is_empty();
-    let has_inline = email.attachments.iter().any(|a| a.is_inline());
+/// Recipients and template data for one SendBulkTemplatedEmail destination.
+struct BulkDestination<'a> {
+    to: &'a [Address],
+    cc: &'a [Address],
+    bcc: &'a [Address],
+    data: Value,
+}
 
-    if !has_attachments {
-        // Simple case: no attachments
-        if has_text && has_html {
-            // Multipart/alternative
-            message.push_str(&format!(
-                "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
-                boundary
-            ));
+/// Request body for an action without parameters.
+fn action_body(action: &str) -> String {
+    format!("Action={}&Version={}", action, VERSION)
+}
 
-            // Text part
-            message.push_str(&format!("--{}\r\n", boundary));
-            message.push_str("Content-Type: text/plain; charset=utf-8\r\n");
-            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
-            message.push_str(email.text_body.as_ref().unwrap());
-            message.push_str("\r\n");
-
-            // HTML part
-            message.push_str(&format!("--{}\r\n", boundary));
-            message.push_str("Content-Type: text/html; charset=utf-8\r\n");
-            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
-            message.push_str(email.html_body.as_ref().unwrap());
-            message.push_str("\r\n");
-
-            message.push_str(&format!("--{}--\r\n", boundary));
-        } else if has_html {
-            message.push_str("Content-Type: text/html; charset=utf-8\r\n");
-            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
-            message.push_str(email.html_body.as_ref().unwrap());
-        } else if has_text {
-            message.push_str("Content-Type: text/plain; charset=utf-8\r\n");
-            message.push_str("Content-Transfer-Encoding: quoted-printable\r\n\r\n");
-            message.push_str(email.text_body.as_ref().unwrap());
-        } else {
-            message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
-        }
-    } else {
-        // Complex case: with attachments
-        let mixed_boundary = format!("----=_Mixed_{}", uuid::Uuid::new_v4().to_string().replace("-", ""));
-        let alt_boundary = format!("----=_Alt_{}", uuid::Uuid::new_v4().to_string().replace("-", ""));
-        let related_boundary = format!("----=_Related_{}", uuid::Uuid::new_v4().to_string().replace("-", ""));
-
-        message.push_str(&format!(
-            "Content-Type: multipart/mixed; boundary=\"{}\"\r\n\r\n",
-            mixed_boundary
-        ));
-
-        // Body part
-        message.push_str(&format!("--{}\r\n", mixed_boundary));
-
-        if has_inline && has_html {
-            // Use multipart/related for inline attachments
-            message.push_str(&format!(
-                "Content-Type: multipart/related; boundary=\"{}\"\r\n\r\n",
-                related_boundary
-            ));
+/// Temporary security token from provider options.
+fn security_token(email: &Email) -> Option<&str> {
+    email
+        .provider_options
+        .get("security_token")
+        .and_then(|v| v.as_str())
+}
 
-            message.push_str(&format!("--{}\r\n", related_boundary));
+/// Parse a successful response body.
+fn parse_xml<T: DeserializeOwned>(xml: &str) -> Result<T, MailError> {
+    quick_xml::de::from_str(xml)
+        .map_err(|e| MailError::provider("amazon_ses", format!("Invalid response XML: {}", e)))
+}
 
-            if has_text {
-                // Multipart/alternative inside related
-                message.push_str(&format!(
-                    "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
-                    alt_boundary
-                ));
+// ============================================================================
+// SES API Types
+// ============================================================================
 
-                message.push_str(&format!("--{}\r\n", alt_boundary));
-                message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
-                message.push_str(email.text_body.as_ref().unwrap());
-                message.push_str("\r\n");
-
-                message.push_str(&format!("--{}\r\n", alt_boundary));
-                message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
-                message.push_str(email.html_body.as_ref().unwrap());
-                message.push_str("\r\n");
-
-                message.push_str(&format!("--{}--\r\n", alt_boundary));
-            } else {
-                message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
-                message.push_str(email.html_body.as_ref().unwrap());
-                message.push_str("\r\n");
-            }
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ResponseMetadata {
+    request_id: String,
+}
 
-            // Inline attachments
-            for attachment in email.attachments.iter().filter(|a| a.is_inline()) {
-                message.push_str(&format!("--{}\r\n", related_boundary));
-                message.push_str(&format!("Content-Type: {}\r\n", attachment.content_type));
-                message.push_str("Content-Transfer-Encoding: base64\r\n");
-                message.push_str(&format!(
-                    "Content-Disposition: inline; filename=\"{}\"\r\n",
-                    attachment.filename
-                ));
-                if let Some(ref cid) = attachment.content_id {
-                    message.push_str(&format!("Content-ID: <{}>\r\n", cid));
-                }
-                message.push_str("\r\n");
-                message.push_str(&attachment.base64_data());
-                message.push_str("\r\n");
-            }
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SendRawEmailResponse {
+    send_raw_email_result: SendRawEmailResult,
+    response_metadata: ResponseMetadata,
+}
 
-            message.push_str(&format!("--{}--\r\n", related_boundary));
-        } else if has_text && has_html {
-            // Multipart/alternative
-            message.push_str(&format!(
-                "Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
-                alt_boundary
-            ));
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SendRawEmailResult {
+    message_id: String,
+}
 
-            message.push_str(&format!("--{}\r\n", alt_boundary));
-            message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
-            message.push_str(email.text_body.as_ref().unwrap());
-            message.push_str("\r\n");
-
-            message.push_str(&format!("--{}\r\n", alt_boundary));
-            message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
-            message.push_str(email.html_body.as_ref().unwrap());
-            message.push_str("\r\n");
-
-            message.push_str(&format!("--{}--\r\n", alt_boundary));
-        } else if has_html {
-            message.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
-            message.push_str(email.html_body.as_ref().unwrap());
-            message.push_str("\r\n");
-        } else if has_text {
-            message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
-            message.push_str(email.text_body.as_ref().unwrap());
-            message.push_str("\r\n");
-        }
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SendBulkTemplatedEmailResponse {
+    send_bulk_templated_email_result: SendBulkTemplatedEmailResult,
+    response_metadata: ResponseMetadata,
+}
 
-        // Regular attachments
-        for attachment in email.attachments.iter().filter(|a| !a.is_inline()) {
-            message.push_str(&format!("--{}\r\n", mixed_boundary));
-            message.push_str(&format!("Content-Type: {}\r\n", attachment.content_type));
-            message.push_str("Content-Transfer-Encoding: base64\r\n");
-            message.push_str(&format!(
-                "Content-Disposition: attachment; filename=\"{}\"\r\n",
-                attachment.filename
-            ));
-            message.push_str("\r\n");
-            message.push_str(&attachment.base64_data());
-            message.push_str("\r\n");
-        }
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SendBulkTemplatedEmailResult {
+    status: Members<BulkEmailDestinationStatus>,
+}
 
-        message.push_str(&format!("--{}--\r\n", mixed_boundary));
-    }
+/// An AWS query API list: `<member>` elements.
+#[derive(Debug, Deserialize)]
+struct Members<T> {
+    #[serde(default = "Vec::new")]
+    member: Vec<T>,
+}
 
-    Ok(message.into_bytes())
+impl<T> Default for Members<T> {
+    fn default() -> Self {
+        Self { member: Vec::new() }
+    }
 }
 
-#[async_trait]
-impl Mailer for AmazonSesMailer {
-    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
-        let body = self.build_body(email)?;
-        let date_time = Utc::now();
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct BulkEmailDestinationStatus {
+    status: String,
+    error: String,
+    message_id: String,
+}
 
-        // Get security token from provider options
-        let security_token = email
-            .provider_options
-            .get("security_token")
-            .and_then(|v| v.as_str());
+/// SESv2 GetMessageInsights response (JSON).
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SesMessageInsights {
+    insights: Vec<SesEmailInsights>,
+}
 
-        let headers = self.sign_request(&body, date_time, security_token);
-        let url = self.base_url();
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SesEmailInsights {
+    events: Vec<SesInsightsEvent>,
+}
 
-        let mut request = self.client.post(&url);
-        for (name, value) in headers {
-            request = request.header(&name, &value);
-        }
-        request = request.header("User-Agent", format!("missive/{}", crate::VERSION));
-        request = request.body(body);
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SesInsightsEvent {
+    #[serde(rename = "Type")]
+    kind: String,
+    details: Value,
+}
 
-        let response = request.send().await?;
-        let status = response.status();
-        let body = response.text().await?;
+impl SesMessageInsights {
+    fn message_status(&self) -> MessageStatus {
+        let Some(insights) = self.insights.first() else {
+            return MessageStatus::Unknown;
+        };
+        insights
+            .events
+            .iter()
+            .rev()
+            .find_map(|event| match event.kind.as_str() {
+                "SEND" => Some(MessageStatus::Sent),
+                "DELIVERY" => Some(MessageStatus::Delivered),
+                "DELIVERY_DELAY" => Some(MessageStatus::Deferred),
+                "COMPLAINT" => Some(MessageStatus::Complained),
+                "REJECT" => Some(MessageStatus::Bounced(BounceClass::Blocked)),
+                "BOUNCE" => {
+                    let bounce = event.details.get("Bounce");
+                    let field = |key| bounce.and_then(|b| b.get(key)).and_then(Value::as_str);
+                    Some(MessageStatus::Bounced(BounceClassifier::new().ses_bounce(
+                        field("BounceType"),
+                        field("BounceSubType"),
+                        &[field("DiagnosticCode")],
+                    )))
+                }
+                _ => None,
+            })
+            .unwrap_or(MessageStatus::Queued)
+    }
+}
 
-        if status.is_success() {
-            // Parse XML response
-            let message_id = extract_xml_value(&body, "MessageId").unwrap_or_default();
-            let request_id = extract_xml_value(&body, "RequestId").unwrap_or_default();
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SesErrorResponse {
+    error: SesError,
+}
 
-            Ok(DeliveryResult::with_response(
-                message_id,
-                serde_json::json!({
-                    "provider": "amazon_ses",
-                    "request_id": request_id,
-                }),
-            ))
-        } else {
-            // Parse error XML
-            let error_code = extract_xml_value(&body, "Code").unwrap_or_else(|| "Unknown".to_string());
-            let error_message =
-                extract_xml_value(&body, "Message").unwrap_or_else(|| "Unknown error".to_string());
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SesError {
+    code: String,
+    message: String,
+}
 
-            Err(MailError::provider_with_status(
-                "amazon_ses",
-                format!("[{}] {}", error_code, error_message),
-                status.as_u16(),
-            ))
+impl Default for SesError {
+    fn default() -> Self {
+        Self {
+            code: "Unknown".to_string(),
+            message: "Unknown error".to_string(),
         }
     }
+}
 
-    fn provider_name(&self) -> &'static str {
-        "amazon_ses"
+impl SesError {
+    /// HTTP status to report for this error.
+    ///
+    /// SES answers throttling with a 400, which would make it a permanent
+    /// error; report it as a 429 so it is rate limited and retryable. An
+    /// exceeded daily quota won't clear on a retry, so it stays a 400.
+    fn status(&self, status: u16) -> u16 {
+        let throttled = matches!(self.code.as_str(), "Throttling" | "ThrottlingException");
+        if throttled && !self.message.contains("Daily message quota") {
+            429
+        } else {
+            status
+        }
     }
 }
 
-/// Simple XML value extractor (avoids XML parsing dependency).
-fn extract_xml_value(xml: &str, tag: &str) -> Option<String> {
-    let start_tag = format!("<{}>", tag);
-    let end_tag = format!("</{}>", tag);
+#[cfg(feature = "insights")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct GetSendQuotaResponse {
+    get_send_quota_result: SendQuotaResult,
+}
+
+#[cfg(feature = "insights")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SendQuotaResult {
+    #[serde(rename = "Max24HourSend")]
+    max_24_hour_send: f64,
+    #[serde(rename = "MaxSendRate")]
+    max_send_rate: f64,
+    #[serde(rename = "SentLast24Hours")]
+    sent_last_24_hours: f64,
+}
 
-    let start = xml.find(&start_tag)? + start_tag.len();
-    let end = xml[start..].find(&end_tag)? + start;
+#[cfg(feature = "insights")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct GetSendStatisticsResponse {
+    get_send_statistics_result: SendStatisticsResult,
+}
+
+#[cfg(feature = "insights")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SendStatisticsResult {
+    send_data_points: Members<SendDataPoint>,
+}
 
-    Some(xml[start..end].to_string())
+#[cfg(feature = "insights")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct SendDataPoint {
+    timestamp: String,
+    delivery_attempts: u64,
+    bounces: u64,
+    complaints: u64,
+    rejects: u64,
 }
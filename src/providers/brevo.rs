@@ -34,7 +34,16 @@
 //! * `template_id` (integer) - ID of the active transactional email template
 //! * `params` (map) - Key/value attributes to customize the template
 //! * `tags` (list[string]) - Tags for filtering in Brevo dashboard
-//! * `schedule_at` (string) - RFC3339 UTC datetime to schedule the email
+//! * `schedule_at` (string) - RFC3339 UTC datetime to schedule the email;
+//!   other values fail with `MailError::InvalidOption`
+//!
+//! ## Batch Sending
+//!
+//! `deliver_many` sends up to 1000 emails per request as `messageVersions`.
+//! The sender, attachments, tags and `schedule_at` are shared by the whole
+//! batch and come from the first email, so every email must have the same
+//! sender; otherwise `deliver_many` fails with `MailError::UnsupportedFeature`
+//! before sending anything.
 //!
 //! ## Using Template Default Sender
 //!
@@ -54,10 +63,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
 
 const BREVO_BASE_URL: &str = "https://api.brevo.com/v3";
 const BREVO_API_ENDPOINT: &str = "/smtp/email";
+/// Message versions sent per request by `deliver_many`.
+const MAX_MESSAGE_VERSIONS: usize = 1000;
 
 /// Brevo API email provider.
 pub struct BrevoMailer {
@@ -72,7 +83,7 @@ impl BrevoMailer {
         Self {
             api_key: api_key.into(),
             base_url: BREVO_BASE_URL.to_string(),
-            client: Client::new(),
+            client: crate::http::default_client(),
         }
     }
 
@@ -91,7 +102,15 @@ impl BrevoMailer {
         self
     }
 
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     fn build_request(&self, email: &Email) -> Result<BrevoRequest, MailError> {
+        email.validate_headers()?;
+
         let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
 
         if email.to.is_empty() {
@@ -143,9 +162,7 @@ impl BrevoMailer {
         if let Some(tags) = email.provider_options.get("tags") {
             request.tags = serde_json::from_value(tags.clone()).ok();
         }
-        if let Some(schedule_at) = email.provider_options.get("schedule_at") {
-            request.scheduled_at = schedule_at.as_str().map(|s| s.to_string());
-        }
+        request.scheduled_at = schedule_at(email)?;
 
         // Add attachments
         if !email.attachments.is_empty() {
@@ -163,87 +180,11 @@ impl BrevoMailer {
 
         Ok(request)
     }
-}
 
-/// Check if sender should use template default (email is "TEMPLATE").
-fn is_template_sender(from: &crate::Address) -> bool {
-    from.email == "TEMPLATE"
-}
-
-fn prepare_sender(from: &crate::Address, email: &Email) -> Option<BrevoSender> {
-    // When from email is "TEMPLATE", don't send sender - use template default
-    if is_template_sender(from) {
-        return None;
-    }
-
-    // Check for sender_id provider option
-    if let Some(sender_id) = email.provider_options.get("sender_id") {
-        if let Some(id) = sender_id.as_i64() {
-            return Some(BrevoSender {
-                id: Some(id),
-                email: Some(from.email.clone()),
-                name: None,
-            });
-        }
-    }
-
-    Some(BrevoSender {
-        id: None,
-        email: Some(from.email.clone()),
-        name: from.name.clone(),
-    })
-}
-
-fn prepare_recipient(addr: &crate::Address) -> BrevoRecipient {
-    BrevoRecipient {
-        email: addr.email.clone(),
-        name: addr.name.clone(),
-    }
-}
-
-#[async_trait]
-impl Mailer for BrevoMailer {
-    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
-        let request = self.build_request(email)?;
-        let url = format!("{}{}", self.base_url, BREVO_API_ENDPOINT);
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header("User-Agent", format!("missive/{}", crate::VERSION))
-            .header("Api-Key", &self.api_key)
-            .json(&request)
-            .send()
-            .await?;
-
-        let status = response.status();
-
-        if status.is_success() {
-            let result: BrevoResponse = response.json().await?;
-            Ok(DeliveryResult::with_response(
-                result.message_id,
-                serde_json::json!({ "provider": "brevo" }),
-            ))
-        } else {
-            let error: BrevoError = response.json().await.unwrap_or(BrevoError {
-                code: "unknown".to_string(),
-                message: "Unknown error".to_string(),
-            });
-            Err(MailError::provider_with_status(
-                "brevo",
-                format!("[{}] {}", error.code, error.message),
-                status.as_u16(),
-            ))
-        }
-    }
-
-    /// Send multiple emails in a single API call using Brevo's messageVersions.
+    /// Send one batch request using Brevo's messageVersions.
     ///
     /// Global parameters (from first email): sender, attachments, tags, scheduled_at
-    /// Per-email parameters: to, cc, bcc, subject, content, template_id, params, headers, reply_to
-    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+    async fn send_batch(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
         if emails.is_empty() {
             return Ok(vec![]);
         }
@@ -286,10 +227,7 @@ impl Mailer for BrevoMailer {
                         .collect(),
                 )
             },
-            scheduled_at: first_email
-                .provider_options
-                .get("schedule_at")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
+            scheduled_at: schedule_at(first_email)?,
             message_versions: emails.iter().map(prepare_message_version).collect(),
         };
 
@@ -309,12 +247,14 @@ impl Mailer for BrevoMailer {
             .header("Accept", "application/json")
             .header("Content-Type", "application/json")
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .header("Api-Key", &self.api_key)
             .json(&batch_request)
             .send()
             .await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let result: BrevoBatchResponse = response.json().await?;
@@ -334,13 +274,176 @@ impl Mailer for BrevoMailer {
                 "brevo",
                 format!("[{}] {}", error.code, error.message),
                 status.as_u16(),
+            )
+            .with_code(error.code)
+            .with_retry_after(retry_after))
+        }
+    }
+}
+
+/// The `schedule_at` option, which must be an RFC 3339 datetime.
+fn schedule_at(email: &Email) -> Result<Option<String>, MailError> {
+    let Some(value) = email.provider_options.get("schedule_at") else {
+        return Ok(None);
+    };
+    let invalid = |message: String| MailError::InvalidOption {
+        option: "schedule_at",
+        message,
+    };
+    let schedule_at = value
+        .as_str()
+        .ok_or_else(|| invalid(format!("expected an RFC 3339 string, got {}", value)))?;
+    chrono::DateTime::parse_from_rfc3339(schedule_at).map_err(|e| {
+        invalid(format!(
+            "{:?} is not an RFC 3339 datetime ({})",
+            schedule_at, e
+        ))
+    })?;
+    Ok(Some(schedule_at.to_string()))
+}
+
+/// The parts of an email that make up a batch's sender.
+fn batch_sender(email: &Email) -> Result<(&str, Option<&str>, Option<i64>), MailError> {
+    let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
+    let sender_id = email
+        .provider_options
+        .get("sender_id")
+        .and_then(|v| v.as_i64());
+    Ok((from.email.as_str(), from.name.as_deref(), sender_id))
+}
+
+/// Check if sender should use template default (email is "TEMPLATE").
+fn is_template_sender(from: &crate::Address) -> bool {
+    from.email == "TEMPLATE"
+}
+
+fn prepare_sender(from: &crate::Address, email: &Email) -> Option<BrevoSender> {
+    // When from email is "TEMPLATE", don't send sender - use template default
+    if is_template_sender(from) {
+        return None;
+    }
+
+    // Check for sender_id provider option
+    if let Some(sender_id) = email.provider_options.get("sender_id") {
+        if let Some(id) = sender_id.as_i64() {
+            return Some(BrevoSender {
+                id: Some(id),
+                email: Some(from.email.clone()),
+                name: None,
+            });
+        }
+    }
+
+    Some(BrevoSender {
+        id: None,
+        email: Some(from.email.clone()),
+        name: from.name.clone(),
+    })
+}
+
+fn prepare_recipient(addr: &crate::Address) -> BrevoRecipient {
+    BrevoRecipient {
+        email: addr.email.clone(),
+        name: addr.name.clone(),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Mailer for BrevoMailer {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let request = self.build_request(email)?;
+        let url = format!("{}{}", self.base_url, BREVO_API_ENDPOINT);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+            .header("Api-Key", &self.api_key)
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+
+        if status.is_success() {
+            let result: BrevoResponse = response.json().await?;
+            Ok(DeliveryResult::with_response(
+                result.message_id,
+                serde_json::json!({ "provider": "brevo" }),
             ))
+        } else {
+            let error: BrevoError = response.json().await.unwrap_or(BrevoError {
+                code: "unknown".to_string(),
+                message: "Unknown error".to_string(),
+            });
+            Err(MailError::provider_with_status(
+                "brevo",
+                format!("[{}] {}", error.code, error.message),
+                status.as_u16(),
+            )
+            .with_code(error.code)
+            .with_retry_after(retry_after))
+        }
+    }
+
+    /// Send emails as Brevo `messageVersions`, up to 1000 per request.
+    ///
+    /// Per-email parameters: to, cc, bcc, subject, content, template_id, params, headers, reply_to
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        self.validate_batch(emails)?;
+
+        let mut results = Vec::with_capacity(emails.len());
+        for chunk in emails.chunks(MAX_MESSAGE_VERSIONS) {
+            results.extend(self.send_batch(chunk).await?);
+        }
+        Ok(results)
+    }
+
+    /// Every email in a batch shares the first email's sender.
+    fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        let Some(first) = emails.first() else {
+            return Ok(());
+        };
+        let sender = batch_sender(first)?;
+        for email in &emails[1..] {
+            let other = batch_sender(email)?;
+            if other != sender {
+                return Err(MailError::UnsupportedFeature(format!(
+                    "Brevo batch sends need a single sender, got {} and {}",
+                    sender.0, other.0
+                )));
+            }
         }
+        Ok(())
+    }
+
+    /// Send a mail merge as `messageVersions`, with each recipient's data in
+    /// `params` and up to 1000 recipients per request.
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        let emails = crate::personalize::render(email, Some("params"));
+        self.deliver_many(&emails).await
     }
 
     fn provider_name(&self) -> &'static str {
         "brevo"
     }
+
+    /// Attachments are shared by a whole batch, so per-email attachments
+    /// aren't supported in `deliver_many()`.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            supports_attachments_in_batch: false,
+            supports_scheduling: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("brevo")
+        }
+    }
 }
 
 fn prepare_message_version(email: &Email) -> BrevoMessageVersion {
@@ -0,0 +1,329 @@
+//! Customer.io Transactional API provider.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use missive::providers::CustomerIoMailer;
+//!
+//! // An App API key (not a Track API key)
+//! let mailer = CustomerIoMailer::new("app_api_key");
+//!
+//! // Workspaces in the EU region
+//! let mailer = CustomerIoMailer::new("app_api_key").eu();
+//! ```
+//!
+//! ## Identifiers
+//!
+//! Every transactional message is attributed to a person. Set
+//! `identifiers` to `{"id": ...}`, `{"email": ...}` or `{"cio_id": ...}`;
+//! without it the first `to` address is used as `{"email": ...}`.
+//!
+//! ```rust,ignore
+//! let email = Email::new()
+//!     .from("sender@example.com")
+//!     .to("recipient@example.com")
+//!     .subject("Hello")
+//!     .provider_option("identifiers", json!({ "id": "user-42" }));
+//! ```
+//!
+//! ## Template Support
+//!
+//! Send a transactional message built in Customer.io by its ID or trigger
+//! name, with `message_data` for its Liquid variables. Fields set on the
+//! email (subject, bodies, from) override the template's:
+//!
+//! ```rust,ignore
+//! let email = Email::new()
+//!     .to("recipient@example.com")
+//!     .provider_option("transactional_message_id", 44)
+//!     .provider_option("message_data", json!({ "password_reset_token": "abc123" }));
+//! ```
+//!
+//! ## Other Options
+//!
+//! `send_at` (Unix timestamp, also set by
+//! [`Email::scheduled_at`](crate::Email::scheduled_at)), `disable_message_retention`,
+//! `send_to_unsubscribed`, `tracked`, `queue_draft`, `preheader`, `language`
+//! and `disable_css_preprocessing` are passed through as they are.
+//!
+//! The API has no CC field, so emails with CC recipients are rejected, and
+//! attachments can't be inline: they are all sent as regular attachments.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
+
+const CUSTOMERIO_API_URL: &str = "https://api.customer.io/v1";
+const CUSTOMERIO_EU_API_URL: &str = "https://api-eu.customer.io/v1";
+
+/// Provider options copied into the request unchanged.
+const PASSTHROUGH_OPTIONS: &[&str] = &[
+    "disable_message_retention",
+    "send_to_unsubscribed",
+    "tracked",
+    "queue_draft",
+    "preheader",
+    "language",
+    "disable_css_preprocessing",
+];
+
+/// Customer.io Transactional API email provider.
+pub struct CustomerIoMailer {
+    api_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl CustomerIoMailer {
+    /// Create a new Customer.io mailer with the given App API key.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            client: crate::http::default_client(),
+            base_url: CUSTOMERIO_API_URL.to_string(),
+        }
+    }
+
+    /// Create with a custom reqwest client.
+    pub fn with_client(api_key: impl Into<String>, client: Client) -> Self {
+        Self {
+            api_key: api_key.into(),
+            client,
+            base_url: CUSTOMERIO_API_URL.to_string(),
+        }
+    }
+
+    /// Set a custom base URL (for testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Use the EU region, for workspaces hosted there.
+    pub fn eu(self) -> Self {
+        self.base_url(CUSTOMERIO_EU_API_URL)
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
+    fn build_request(&self, email: &Email) -> Result<CustomerIoRequest, MailError> {
+        email.validate_headers()?;
+
+        let first_to = email.to.first().ok_or(MailError::MissingField("to"))?;
+
+        if !email.cc.is_empty() {
+            return Err(MailError::UnsupportedFeature(
+                "Customer.io transactional emails can't have CC recipients".into(),
+            ));
+        }
+
+        let transactional_message_id = email
+            .provider_options
+            .get("transactional_message_id")
+            .cloned();
+        // Without a template, Customer.io needs the sender from the email
+        if transactional_message_id.is_none() && email.from.is_none() {
+            return Err(MailError::MissingField("from"));
+        }
+
+        let identifiers = match email.provider_options.get("identifiers") {
+            Some(value) => value.clone(),
+            None => serde_json::json!({ "email": first_to.email }),
+        };
+
+        let send_at = match email.provider_options.get("send_at") {
+            Some(value) => value.as_i64(),
+            None => email.get_scheduled_at().map(|at| at.timestamp()),
+        };
+
+        let mut options = Map::new();
+        for key in PASSTHROUGH_OPTIONS {
+            if let Some(value) = email.provider_options.get(*key) {
+                options.insert(key.to_string(), value.clone());
+            }
+        }
+
+        Ok(CustomerIoRequest {
+            transactional_message_id,
+            identifiers,
+            to: email
+                .to
+                .iter()
+                .map(|a| a.formatted())
+                .collect::<Vec<_>>()
+                .join(", "),
+            from: email.from.as_ref().map(|a| a.formatted()),
+            subject: if email.subject.is_empty() {
+                None
+            } else {
+                Some(email.subject.clone())
+            },
+            body: email.html_body.clone(),
+            body_plain: email.text_body.clone(),
+            bcc: if email.bcc.is_empty() {
+                None
+            } else {
+                Some(
+                    email
+                        .bcc
+                        .iter()
+                        .map(|a| a.formatted())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            },
+            reply_to: email.reply_to.first().map(|a| a.formatted()),
+            headers: if email.headers.is_empty() {
+                None
+            } else {
+                Some(email.headers.clone())
+            },
+            message_data: email.provider_options.get("message_data").cloned(),
+            attachments: if email.attachments.is_empty() {
+                None
+            } else {
+                // Keyed by filename; there is no content type or disposition
+                Some(
+                    email
+                        .attachments
+                        .iter()
+                        .map(|a| (a.filename.clone(), a.base64_data()))
+                        .collect(),
+                )
+            },
+            send_at,
+            options,
+        })
+    }
+
+    fn parse_error(
+        status: reqwest::StatusCode,
+        body: &str,
+        retry_after: Option<std::time::Duration>,
+    ) -> MailError {
+        let message = serde_json::from_str::<CustomerIoError>(body)
+            .ok()
+            .and_then(|e| match e.meta.error {
+                Some(error) => Some(error),
+                None if !e.meta.errors.is_empty() => Some(e.meta.errors.join("; ")),
+                None => None,
+            })
+            .unwrap_or_else(|| body.to_string());
+        MailError::provider_with_status("customerio", message, status.as_u16())
+            .with_retry_after(retry_after)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Mailer for CustomerIoMailer {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let request = self.build_request(email)?;
+
+        let url = format!("{}/send/email", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+
+        if status.is_success() {
+            let result: CustomerIoResponse = response.json().await?;
+            Ok(DeliveryResult::with_response(
+                result.delivery_id,
+                serde_json::json!({
+                    "provider": "customerio",
+                    "queued_at": result.queued_at,
+                }),
+            ))
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(Self::parse_error(status, &body, retry_after))
+        }
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "customerio"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_scheduling: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("customerio")
+        }
+    }
+}
+
+// ============================================================================
+// Customer.io API Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct CustomerIoRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transactional_message_id: Option<Value>,
+    identifiers: Value,
+    to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body_plain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bcc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    send_at: Option<i64>,
+    #[serde(flatten)]
+    options: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomerIoResponse {
+    delivery_id: String,
+    #[serde(default)]
+    queued_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomerIoError {
+    meta: CustomerIoErrorMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomerIoErrorMeta {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    errors: Vec<String>,
+}
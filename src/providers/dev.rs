@@ -0,0 +1,73 @@
+//! Simulated latency and failures for the development mailers.
+//!
+//! [`LocalMailer`](super::LocalMailer) and [`LoggerMailer`](super::LoggerMailer)
+//! read these settings on every delivery, so local environments can behave
+//! like a slow or flaky provider:
+//!
+//! ```bash
+//! MISSIVE_DEV_LATENCY_MS=800   # wait 800ms before each delivery
+//! MISSIVE_DEV_FAIL_RATE=0.2    # fail 20% of deliveries with a 503
+//! ```
+
+use std::time::Duration;
+
+use crate::error::MailError;
+
+/// Wait for `MISSIVE_DEV_LATENCY_MS`, then fail at random with probability
+/// `MISSIVE_DEV_FAIL_RATE`.
+pub(crate) async fn simulate(provider: &'static str) -> Result<(), MailError> {
+    let latency = match crate::config::var("MISSIVE_DEV_LATENCY_MS") {
+        Some(ms) => ms.trim().parse().map(Duration::from_millis).map_err(|_| {
+            MailError::Configuration(format!("Invalid MISSIVE_DEV_LATENCY_MS: {}", ms))
+        })?,
+        None => Duration::ZERO,
+    };
+    let fail_rate = match crate::config::var("MISSIVE_DEV_FAIL_RATE") {
+        Some(rate) => match rate.trim().parse::<f64>() {
+            Ok(r) if (0.0..=1.0).contains(&r) => r,
+            _ => {
+                return Err(MailError::Configuration(format!(
+                    "Invalid MISSIVE_DEV_FAIL_RATE (expected 0.0 to 1.0): {}",
+                    rate
+                )))
+            }
+        },
+        None => 0.0,
+    };
+
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+    if fail_rate > 0.0 && random() < fail_rate {
+        tracing::debug!(provider, fail_rate, "Simulated delivery failure");
+        return Err(MailError::ProviderError {
+            provider,
+            message: "simulated failure (MISSIVE_DEV_FAIL_RATE)".into(),
+            status: Some(503),
+            code: None,
+            retry_after: None,
+        });
+    }
+    Ok(())
+}
+
+/// A uniform value in `[0, 1)`, from the 53 low bits of a v4 UUID (which
+/// are all random).
+fn random() -> f64 {
+    const BITS: u32 = 53;
+    let bits = uuid::Uuid::new_v4().as_u128() as u64 & ((1 << BITS) - 1);
+    bits as f64 / (1u64 << BITS) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_is_in_range() {
+        for _ in 0..1000 {
+            let r = random();
+            assert!((0.0..1.0).contains(&r));
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Failure injection for testing.
+//!
+//! [`FailingMailer`] wraps another mailer (a [`LocalMailer`] by default) and
+//! makes chosen deliveries fail, so application retry and error-handling
+//! paths can be tested without mocking HTTP.
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use missive::providers::{FailingMailer, LocalMailer};
+//!
+//! let local = LocalMailer::new();
+//! let mailer = FailingMailer::new(local.clone())
+//!     .fail_first(2)
+//!     .fail_for("bounce@example.com")
+//!     .latency(Duration::from_millis(50));
+//!
+//! // The first two deliveries fail, the third reaches `local`
+//! send_with_retries(&mailer, &email).await?;
+//! assert_eq!(mailer.failures(), 2);
+//! assert_eq!(local.email_count(), 1);
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{DeliveryResult, Mailer, MessageStatus};
+use crate::providers::LocalMailer;
+
+type ErrorFn = Arc<dyn Fn(&Email) -> MailError + Send + Sync>;
+
+/// A mailer that fails deliveries on demand.
+///
+/// Failures are deterministic: the first `n` attempts, and any email with a
+/// listed recipient, fail with the configured error. Everything else is passed
+/// to the wrapped mailer. By default the error is a `503` provider error, which
+/// callers should treat as transient.
+pub struct FailingMailer<M = LocalMailer> {
+    inner: M,
+    fail_first: usize,
+    recipients: Vec<String>,
+    latency: Duration,
+    error: ErrorFn,
+    attempts: AtomicUsize,
+    failures: AtomicUsize,
+}
+
+impl FailingMailer<LocalMailer> {
+    /// Wrap a fresh [`LocalMailer`].
+    pub fn local() -> Self {
+        Self::new(LocalMailer::new())
+    }
+}
+
+impl Default for FailingMailer<LocalMailer> {
+    fn default() -> Self {
+        Self::local()
+    }
+}
+
+impl<M: Mailer> FailingMailer<M> {
+    /// Wrap a mailer. No failures are injected until configured.
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            fail_first: 0,
+            recipients: Vec::new(),
+            latency: Duration::ZERO,
+            error: Arc::new(|_| MailError::ProviderError {
+                provider: "failing",
+                message: "injected failure".into(),
+                status: Some(503),
+                code: None,
+                retry_after: None,
+            }),
+            attempts: AtomicUsize::new(0),
+            failures: AtomicUsize::new(0),
+        }
+    }
+
+    /// Fail the first `n` delivery attempts.
+    pub fn fail_first(mut self, n: usize) -> Self {
+        self.fail_first = n;
+        self
+    }
+
+    /// Fail every email sent to this recipient.
+    ///
+    /// Pass `@example.com` to fail a whole domain. Matching is
+    /// case-insensitive and covers `to`, `cc` and `bcc`.
+    pub fn fail_for(mut self, recipient: impl Into<String>) -> Self {
+        self.recipients.push(recipient.into().to_lowercase());
+        self
+    }
+
+    /// Wait this long before every attempt, successful or not.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Build the error returned for injected failures.
+    pub fn error(mut self, f: impl Fn(&Email) -> MailError + Send + Sync + 'static) -> Self {
+        self.error = Arc::new(f);
+        self
+    }
+
+    /// Get a reference to the wrapped mailer.
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Number of delivery attempts so far.
+    pub fn attempts(&self) -> usize {
+        self.attempts.load(Ordering::SeqCst)
+    }
+
+    /// Number of injected failures so far.
+    pub fn failures(&self) -> usize {
+        self.failures.load(Ordering::SeqCst)
+    }
+
+    /// Reset the attempt and failure counters, re-arming `fail_first`.
+    pub fn reset(&self) {
+        self.attempts.store(0, Ordering::SeqCst);
+        self.failures.store(0, Ordering::SeqCst);
+    }
+
+    fn fails_for_recipient(&self, email: &Email) -> bool {
+        email.all_recipients().iter().any(|addr| {
+            let address = addr.email.to_lowercase();
+            self.recipients.iter().any(|r| {
+                if r.starts_with('@') {
+                    address.ends_with(r.as_str())
+                } else {
+                    address == *r
+                }
+            })
+        })
+    }
+}
+
+impl<M: Mailer> fmt::Debug for FailingMailer<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FailingMailer")
+            .field("inner", &self.inner.provider_name())
+            .field("fail_first", &self.fail_first)
+            .field("recipients", &self.recipients)
+            .field("latency", &self.latency)
+            .field("attempts", &self.attempts())
+            .field("failures", &self.failures())
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Mailer> Mailer for FailingMailer<M> {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+
+        if attempt < self.fail_first || self.fails_for_recipient(email) {
+            self.failures.fetch_add(1, Ordering::SeqCst);
+            return Err((self.error)(email));
+        }
+
+        self.inner.deliver(email).await
+    }
+
+    fn batch_concurrency(&self) -> usize {
+        self.inner.batch_concurrency()
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn validate_config(&self) -> Result<(), MailError> {
+        self.inner.validate_config()
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.inner.health_check().await
+    }
+
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        self.inner.message_status(message_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(to: &str) -> Email {
+        Email::new().from("sender@example.com").to(to).subject("Hi")
+    }
+
+    #[tokio::test]
+    async fn test_fail_first() {
+        let local = LocalMailer::new();
+        let mailer = FailingMailer::new(local.clone()).fail_first(2);
+
+        assert!(mailer.deliver(&email("a@example.com")).await.is_err());
+        assert!(mailer.deliver(&email("a@example.com")).await.is_err());
+        assert!(mailer.deliver(&email("a@example.com")).await.is_ok());
+
+        assert_eq!(mailer.attempts(), 3);
+        assert_eq!(mailer.failures(), 2);
+        assert_eq!(local.email_count(), 1);
+
+        mailer.reset();
+        assert!(mailer.deliver(&email("a@example.com")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fail_for_recipient() {
+        let mailer = FailingMailer::local()
+            .fail_for("Bounce@example.com")
+            .fail_for("@blocked.test");
+
+        assert!(mailer.deliver(&email("bounce@EXAMPLE.com")).await.is_err());
+        assert!(mailer
+            .deliver(&email("ok@example.com").bcc("x@blocked.test"))
+            .await
+            .is_err());
+        assert!(mailer.deliver(&email("ok@example.com")).await.is_ok());
+        assert_eq!(mailer.inner().email_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_custom_error() {
+        let mailer = FailingMailer::local()
+            .fail_first(1)
+            .error(|_| MailError::HttpError("connection reset".into()));
+
+        let err = mailer.deliver(&email("a@example.com")).await.unwrap_err();
+        assert!(matches!(err, MailError::HttpError(_)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency() {
+        let mailer = FailingMailer::local().latency(Duration::from_secs(2));
+
+        let start = tokio::time::Instant::now();
+        mailer.deliver(&email("a@example.com")).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_deliver_many_is_concurrent_and_ordered() {
+        let local = LocalMailer::new();
+        let mailer = FailingMailer::new(local.clone()).latency(Duration::from_secs(1));
+        let emails: Vec<Email> = (0..16)
+            .map(|i| email(&format!("user{}@example.com", i)))
+            .collect();
+
+        let start = tokio::time::Instant::now();
+        let results = mailer.deliver_many(&emails).await.unwrap();
+
+        // 16 emails, 8 at a time
+        assert!(start.elapsed() < Duration::from_secs(3));
+        let stored = local.emails();
+        for (i, result) in results.iter().enumerate() {
+            let sent = stored.iter().find(|s| s.id == result.message_id).unwrap();
+            assert_eq!(sent.email.to[0].email, format!("user{}@example.com", i));
+        }
+    }
+}
@@ -43,7 +43,7 @@ use std::sync::Arc;
 use crate::email::Email;
 use crate::error::MailError;
 use crate::mailer::{DeliveryResult, Mailer};
-use crate::storage::{MemoryStorage, Storage, StoredEmail};
+use crate::storage::{Mailboxes, MemoryStorage, Storage, StoredEmail};
 
 /// Local mailer that stores emails in memory.
 ///
@@ -52,6 +52,8 @@ use crate::storage::{MemoryStorage, Storage, StoredEmail};
 /// - **Testing**: Assert on sent emails with [testing helpers](crate::testing)
 pub struct LocalMailer {
     storage: Arc<MemoryStorage>,
+    /// If set, deliver() stores each email in its mailbox instead.
+    mailboxes: Option<Arc<Mailboxes>>,
     /// If set, deliver() will return this error (for testing error paths).
     fail_with: std::sync::RwLock<Option<String>>,
 }
@@ -61,6 +63,7 @@ impl LocalMailer {
     pub fn new() -> Self {
         Self {
             storage: MemoryStorage::shared(),
+            mailboxes: None,
             fail_with: std::sync::RwLock::new(None),
         }
     }
@@ -71,6 +74,20 @@ impl LocalMailer {
     pub fn with_storage(storage: Arc<MemoryStorage>) -> Self {
         Self {
             storage,
+            mailboxes: None,
+            fail_with: std::sync::RwLock::new(None),
+        }
+    }
+
+    /// Create a local mailer that stores each email in its [`Mailboxes`]
+    /// mailbox, by `X-Missive-Mailbox` header or tenant.
+    ///
+    /// [`storage()`](Self::storage) and the assertion helpers see only the
+    /// default mailbox; use [`Mailboxes::named`] for the others.
+    pub fn with_mailboxes(mailboxes: Arc<Mailboxes>) -> Self {
+        Self {
+            storage: mailboxes.named(Mailboxes::DEFAULT),
+            mailboxes: Some(mailboxes),
             fail_with: std::sync::RwLock::new(None),
         }
     }
@@ -201,20 +218,26 @@ impl Clone for LocalMailer {
     fn clone(&self) -> Self {
         Self {
             storage: Arc::clone(&self.storage),
+            mailboxes: self.mailboxes.clone(),
             fail_with: std::sync::RwLock::new(self.fail_with.read().unwrap().clone()),
         }
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for LocalMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
         // Check for configured failure
         if let Some(ref message) = *self.fail_with.read().unwrap() {
             return Err(MailError::SendError(message.clone()));
         }
+        super::dev::simulate(self.provider_name()).await?;
 
-        let message_id = self.storage.push(email.clone());
+        let message_id = match &self.mailboxes {
+            Some(mailboxes) => mailboxes.push(email.clone()),
+            None => self.storage.push(email.clone()),
+        };
         Ok(DeliveryResult::new(message_id))
     }
 
@@ -2,28 +2,75 @@
 //!
 //! Useful for staging environments or when you want to see what would be sent
 //! without actually sending or storing emails.
+//!
+//! ```rust,ignore
+//! use missive::providers::LoggerMailer;
+//!
+//! // One structured event per email, safe to ship to a log pipeline
+//! let mailer = LoggerMailer::json()
+//!     .redact_bodies()
+//!     .hash_recipients()
+//!     .file("/var/log/app/emails.jsonl")?;
+//! ```
+
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
 
 use crate::email::Email;
 use crate::error::MailError;
 use crate::mailer::{DeliveryResult, Mailer};
+use crate::privacy::{self, LogRecipients};
+
+/// Placeholder for redacted bodies.
+const REDACTED: &str = "[redacted]";
 
 /// Logger mailer that emits tracing events for emails.
 pub struct LoggerMailer {
     /// If true, log full email details. If false, just log recipient summary.
     log_full: bool,
+    /// If true, log the serialized email as one JSON field.
+    log_json: bool,
+    redact_bodies: bool,
+    /// Recipient mode, or `None` for the global [`privacy`] setting.
+    recipients: Option<LogRecipients>,
+    file: Option<Mutex<File>>,
+}
+
+/// One logged email, as written in JSON mode and to the file sink.
+#[derive(Serialize)]
+struct LogRecord<'a> {
+    timestamp: DateTime<Utc>,
+    message_id: &'a str,
+    email: &'a Email,
 }
 
 impl LoggerMailer {
     /// Create a logger mailer with brief output (just recipients).
     pub fn new() -> Self {
-        Self { log_full: false }
+        Self {
+            log_full: false,
+            log_json: false,
+            redact_bodies: false,
+            recipients: None,
+            file: None,
+        }
     }
 
     /// Create a logger mailer with full email details.
     pub fn full() -> Self {
-        Self { log_full: true }
+        Self::new().log_full(true)
+    }
+
+    /// Create a logger mailer that logs the whole email as JSON.
+    pub fn json() -> Self {
+        Self::new().log_json(true)
     }
 
     /// Set whether to log full email details.
@@ -31,6 +78,94 @@ impl LoggerMailer {
         self.log_full = full;
         self
     }
+
+    /// Set whether to log the serialized email (in the stable
+    /// [`Email` JSON format](crate::Email#json)) as a single `email` field.
+    pub fn log_json(mut self, json: bool) -> Self {
+        self.log_json = json;
+        self
+    }
+
+    /// Replace text and HTML bodies with `[redacted]`, and drop attachment
+    /// contents, template assigns and personalization data.
+    pub fn redact_bodies(mut self) -> Self {
+        self.redact_bodies = true;
+        self
+    }
+
+    /// Log SHA-256 hashes of recipient addresses instead of the addresses.
+    ///
+    /// Hashes match those in the [audit log](crate::audit), so the same
+    /// recipient can be correlated across both.
+    pub fn hash_recipients(self) -> Self {
+        self.log_recipients(LogRecipients::Hashed)
+    }
+
+    /// Log recipients this way, instead of following the global
+    /// [`privacy`] setting.
+    pub fn log_recipients(mut self, mode: LogRecipients) -> Self {
+        self.recipients = Some(mode);
+        self
+    }
+
+    /// Also append each email to a file, one JSON object per line.
+    ///
+    /// Redaction applies to the file as well.
+    pub fn file(mut self, path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.file = Some(Mutex::new(file));
+        Ok(self)
+    }
+
+    /// The email as it should be logged, after redaction.
+    fn redacted<'a>(&self, email: &'a Email) -> Cow<'a, Email> {
+        let recipients = self.recipients.unwrap_or_else(privacy::log_recipients);
+        let subjects = privacy::log_subjects();
+        if !self.redact_bodies && recipients == LogRecipients::Full && subjects {
+            return Cow::Borrowed(email);
+        }
+        let mut email = email.clone();
+        if !subjects {
+            email.subject = REDACTED.to_string();
+        }
+        if self.redact_bodies {
+            for body in [&mut email.text_body, &mut email.html_body]
+                .into_iter()
+                .flatten()
+            {
+                *body = REDACTED.to_string();
+            }
+            for attachment in &mut email.attachments {
+                attachment.data.clear();
+            }
+            email.assigns.clear();
+            for personalization in &mut email.personalizations {
+                personalization.data = serde_json::Value::Null;
+            }
+        }
+        match recipients {
+            LogRecipients::Full => {}
+            LogRecipients::Off => {
+                email.to.clear();
+                email.cc.clear();
+                email.bcc.clear();
+                email.personalizations.clear();
+            }
+            mode => {
+                let addresses = email
+                    .to
+                    .iter_mut()
+                    .chain(&mut email.cc)
+                    .chain(&mut email.bcc)
+                    .chain(email.personalizations.iter_mut().map(|p| &mut p.to));
+                for address in addresses {
+                    address.email = mode.apply(&address.email).unwrap_or_default();
+                    address.name = None;
+                }
+            }
+        }
+        Cow::Owned(email)
+    }
 }
 
 impl Default for LoggerMailer {
@@ -39,12 +174,36 @@ impl Default for LoggerMailer {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for LoggerMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        super::dev::simulate(self.provider_name()).await?;
         let message_id = uuid::Uuid::new_v4().to_string();
+        let email = self.redacted(email);
+
+        if let Some(file) = &self.file {
+            let record = LogRecord {
+                timestamp: Utc::now(),
+                message_id: &message_id,
+                email: &email,
+            };
+            let mut line = serde_json::to_vec(&record)
+                .map_err(|e| MailError::Internal(format!("Failed to serialize email: {}", e)))?;
+            line.push(b'\n');
+
+            // One write per record keeps lines intact across processes.
+            let mut file = file.lock();
+            if let Err(e) = file.write_all(&line).and_then(|_| file.flush()) {
+                tracing::error!(error = %e, "Failed to write email log");
+            }
+        }
 
-        if self.log_full {
+        if self.log_json {
+            let json = serde_json::to_string(&*email)
+                .map_err(|e| MailError::Internal(format!("Failed to serialize email: {}", e)))?;
+            tracing::info!(message_id = %message_id, email = %json, "Email logged");
+        } else if self.log_full {
             // Log full email details
             tracing::info!(
                 message_id = %message_id,
@@ -145,4 +304,93 @@ mod tests {
         let mailer = LoggerMailer::default();
         assert!(!mailer.log_full);
     }
+
+    #[test]
+    fn test_redact_bodies() {
+        let mailer = LoggerMailer::json().redact_bodies();
+        let email = Email::new()
+            .to("bob@example.com")
+            .text_body("secret")
+            .html_body("<p>secret</p>")
+            .assign("token", "abc")
+            .attachment(crate::Attachment::from_bytes("a.txt", b"secret".to_vec()));
+
+        let redacted = mailer.redacted(&email);
+        assert_eq!(redacted.text_body.as_deref(), Some(REDACTED));
+        assert_eq!(redacted.html_body.as_deref(), Some(REDACTED));
+        assert!(redacted.attachments[0].data.is_empty());
+        assert_eq!(redacted.attachments[0].filename, "a.txt");
+        assert!(redacted.assigns.is_empty());
+        assert_eq!(redacted.to[0].email, "bob@example.com");
+    }
+
+    #[test]
+    fn test_hash_recipients() {
+        use crate::address::hash_address;
+
+        let mailer = LoggerMailer::new().hash_recipients();
+        let email = Email::new()
+            .to(("Bob", "Bob@Example.com"))
+            .cc("carol@example.com")
+            .text_body("Hi");
+
+        let redacted = mailer.redacted(&email);
+        assert_eq!(redacted.to[0].email, hash_address("bob@example.com"));
+        assert_eq!(redacted.to[0].name, None);
+        assert_eq!(redacted.cc[0].email, hash_address("carol@example.com"));
+        assert_eq!(redacted.text_body.as_deref(), Some("Hi"));
+    }
+
+    #[test]
+    fn test_no_redaction_borrows() {
+        let email = Email::new().to("bob@example.com");
+        assert!(matches!(
+            LoggerMailer::json()
+                .log_recipients(LogRecipients::Full)
+                .redacted(&email),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_log_recipients() {
+        let email = Email::new().to("bob@example.com").bcc("carol@example.org");
+
+        let domains = LoggerMailer::new().log_recipients(LogRecipients::DomainOnly);
+        let redacted = domains.redacted(&email);
+        assert_eq!(redacted.to[0].email, "example.com");
+        assert_eq!(redacted.bcc[0].email, "example.org");
+
+        let off = LoggerMailer::new().log_recipients(LogRecipients::Off);
+        let redacted = off.redacted(&email);
+        assert!(redacted.to.is_empty());
+        assert!(redacted.bcc.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_file_sink() {
+        let path =
+            std::env::temp_dir().join(format!("missive-logger-{}.jsonl", uuid::Uuid::new_v4()));
+        let mailer = LoggerMailer::json().redact_bodies().file(&path).unwrap();
+
+        let email = Email::new()
+            .from("alice@example.com")
+            .to("bob@example.com")
+            .subject("Hello")
+            .text_body("secret");
+        let first = mailer.deliver(&email).await.unwrap();
+        mailer.deliver(&email).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message_id"], first.message_id);
+        assert_eq!(lines[0]["email"]["subject"], "Hello");
+        assert_eq!(lines[0]["email"]["text_body"], REDACTED);
+        assert!(lines[0]["timestamp"].is_string());
+    }
 }
@@ -15,7 +15,7 @@
 //! * `api_key` - Your Mailgun API key
 //! * `domain` - Your sending domain (e.g., "mg.yourdomain.com" or sandbox domain)
 //!
-//! For EU domains, use `.base_url("https://api.eu.mailgun.net/v3")`.
+//! For EU domains, use `MailgunMailer::eu(api_key, domain)`.
 //!
 //! ## Provider Options
 //!
@@ -45,6 +45,23 @@
 //! * `tags` (list[string]) - Tags for analytics (max 3)
 //! * `template_name` (string) - Name of stored Mailgun template
 //! * `template_options` (map) - Template options like `version`, `text`
+//! * `deliverytime` (string) - RFC 2822 delivery time, also set by `Email::scheduled_at`
+//!
+//! ## Batch Sending
+//!
+//! [`Mailer::deliver_personalized`] sends up to 1000 recipients per request.
+//! `{{ key }}` placeholders in the subject and bodies become
+//! `%recipient.key%` and each recipient's values go in
+//! `recipient-variables`, so Mailgun sends everyone their own copy. Emails
+//! with cc/bcc or their own `recipient_vars`, and all emails in MIME mode,
+//! are rendered locally instead.
+//!
+//! ## MIME Sending
+//!
+//! `MailgunMailer::new(api_key, domain).mime(true)` builds the full MIME
+//! message itself and posts it to `messages.mime`, so Mailgun sends the
+//! headers exactly as built. Options other than `template_name` and
+//! `template_options` still apply.
 
 use async_trait::async_trait;
 use base64::Engine;
@@ -55,11 +72,15 @@ use reqwest::{
 use serde::Deserialize;
 use serde_json::Value;
 
+use crate::bounce::BounceClassifier;
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
 
 const MAILGUN_BASE_URL: &str = "https://api.mailgun.net/v3";
+const MAILGUN_EU_BASE_URL: &str = "https://api.eu.mailgun.net/v3";
+/// Recipients allowed in one batch request.
+const MAX_BATCH_RECIPIENTS: usize = 1000;
 
 /// Mailgun API email provider.
 pub struct MailgunMailer {
@@ -67,6 +88,7 @@ pub struct MailgunMailer {
     domain: String,
     base_url: String,
     client: Client,
+    mime: bool,
 }
 
 impl MailgunMailer {
@@ -76,10 +98,16 @@ impl MailgunMailer {
             api_key: api_key.into(),
             domain: domain.into(),
             base_url: MAILGUN_BASE_URL.to_string(),
-            client: Client::new(),
+            client: crate::http::default_client(),
+            mime: false,
         }
     }
 
+    /// Create a mailer for a domain in Mailgun's EU region.
+    pub fn eu(api_key: impl Into<String>, domain: impl Into<String>) -> Self {
+        Self::new(api_key, domain).base_url(MAILGUN_EU_BASE_URL)
+    }
+
     /// Create with a custom reqwest client.
     pub fn with_client(
         api_key: impl Into<String>,
@@ -91,6 +119,7 @@ impl MailgunMailer {
             domain: domain.into(),
             base_url: MAILGUN_BASE_URL.to_string(),
             client,
+            mime: false,
         }
     }
 
@@ -100,6 +129,22 @@ impl MailgunMailer {
         self
     }
 
+    /// Send raw MIME messages to `messages.mime` instead of form fields.
+    ///
+    /// Use this when Mailgun must not rewrite the message, e.g. for exact
+    /// header control or S/MIME-signed messages. Stored templates are not
+    /// available in this mode.
+    pub fn mime(mut self, enabled: bool) -> Self {
+        self.mime = enabled;
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     fn auth_header(&self) -> String {
         let credentials = format!("api:{}", self.api_key);
         let encoded = base64::engine::general_purpose::STANDARD.encode(credentials.as_bytes());
@@ -107,6 +152,8 @@ impl MailgunMailer {
     }
 
     fn build_form(&self, email: &Email) -> Result<Form, MailError> {
+        email.validate_headers()?;
+
         let from = email
             .from
             .as_ref()
@@ -173,40 +220,7 @@ impl MailgunMailer {
             form = form.text(format!("h:{}", name), value.clone());
         }
 
-        // Provider options: custom_vars -> h:X-Mailgun-Variables
-        if let Some(custom_vars) = email.provider_options.get("custom_vars") {
-            if let Ok(json_str) = serde_json::to_string(custom_vars) {
-                form = form.text("h:X-Mailgun-Variables", json_str);
-            }
-        }
-
-        // Provider options: recipient_vars -> recipient-variables
-        if let Some(recipient_vars) = email.provider_options.get("recipient_vars") {
-            if let Ok(json_str) = serde_json::to_string(recipient_vars) {
-                form = form.text("recipient-variables", json_str);
-            }
-        }
-
-        // Provider options: sending_options -> o:key
-        if let Some(sending_options) = email.provider_options.get("sending_options") {
-            if let Some(obj) = sending_options.as_object() {
-                for (key, value) in obj {
-                    let value_str = encode_variable(value);
-                    form = form.text(format!("o:{}", key), value_str);
-                }
-            }
-        }
-
-        // Provider options: tags -> o:tag (can have multiple)
-        if let Some(tags) = email.provider_options.get("tags") {
-            if let Some(arr) = tags.as_array() {
-                for tag in arr {
-                    if let Some(tag_str) = tag.as_str() {
-                        form = form.text("o:tag", tag_str.to_string());
-                    }
-                }
-            }
-        }
+        form = add_options(form, email);
 
         // Provider options: template_name -> template
         if let Some(template_name) = email.provider_options.get("template_name") {
@@ -246,6 +260,91 @@ impl MailgunMailer {
 
         Ok(form)
     }
+
+    /// Build the `messages.mime` form: the raw message plus every recipient,
+    /// since bcc isn't in the message headers.
+    fn build_mime_form(&self, email: &Email) -> Result<Form, MailError> {
+        let message = crate::mime::build_mime_message(email)?;
+        let to = email
+            .to
+            .iter()
+            .chain(&email.cc)
+            .chain(&email.bcc)
+            .map(|a| a.email.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let form = Form::new()
+            .text("to", to)
+            .part("message", Part::bytes(message).file_name("message.mime"));
+        Ok(add_options(form, email))
+    }
+}
+
+/// Mailgun options shared by the form and MIME endpoints.
+fn add_options(mut form: Form, email: &Email) -> Form {
+    // Bounce address -> h:Return-Path
+    if let Some(return_path) = &email.return_path {
+        form = form.text("h:Return-Path", return_path.email.clone());
+    }
+
+    // Provider options: custom_vars -> h:X-Mailgun-Variables
+    if let Some(custom_vars) = email.provider_options.get("custom_vars") {
+        if let Ok(json_str) = serde_json::to_string(custom_vars) {
+            form = form.text("h:X-Mailgun-Variables", json_str);
+        }
+    }
+
+    // Provider options: recipient_vars -> recipient-variables
+    if let Some(recipient_vars) = email.provider_options.get("recipient_vars") {
+        if let Ok(json_str) = serde_json::to_string(recipient_vars) {
+            form = form.text("recipient-variables", json_str);
+        }
+    }
+
+    // Provider options: sending_options -> o:key
+    if let Some(sending_options) = email.provider_options.get("sending_options") {
+        if let Some(obj) = sending_options.as_object() {
+            for (key, value) in obj {
+                let value_str = encode_variable(value);
+                form = form.text(format!("o:{}", key), value_str);
+            }
+        }
+    }
+
+    // Provider options: tags -> o:tag (can have multiple)
+    if let Some(tags) = email.provider_options.get("tags") {
+        if let Some(arr) = tags.as_array() {
+            for tag in arr {
+                if let Some(tag_str) = tag.as_str() {
+                    form = form.text("o:tag", tag_str.to_string());
+                }
+            }
+        }
+    }
+
+    // Scheduling: deliverytime (RFC 2822) or scheduled_at -> o:deliverytime
+    let deliverytime = match email.provider_options.get("deliverytime") {
+        Some(value) => value.as_str().map(str::to_string),
+        None => email.get_scheduled_at().map(|at| at.to_rfc2822()),
+    };
+    if let Some(deliverytime) = deliverytime {
+        form = form.text("o:deliverytime", deliverytime);
+    }
+
+    form
+}
+
+/// Recipient variable name for a placeholder key (`account.plan` becomes
+/// `account_plan`), falling back to `varN` for other characters or clashes.
+fn recipient_variable(key: &str, taken: &[(String, String)]) -> String {
+    let name = key.replace('.', "_");
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid && !taken.iter().any(|(_, n)| *n == name) {
+        name
+    } else {
+        format!("var{}", taken.len())
+    }
 }
 
 fn encode_variable(value: &Value) -> String {
@@ -256,22 +355,29 @@ fn encode_variable(value: &Value) -> String {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for MailgunMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
-        let form = self.build_form(email)?;
-        let url = format!("{}/{}/messages", self.base_url, self.domain);
+        let (form, endpoint) = if self.mime {
+            (self.build_mime_form(email)?, "messages.mime")
+        } else {
+            (self.build_form(email)?, "messages")
+        };
+        let url = format!("{}/{}/{}", self.base_url, self.domain, endpoint);
 
         let response = self
             .client
             .post(&url)
             .header("Authorization", self.auth_header())
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .multipart(form)
             .send()
             .await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let result: MailgunResponse = response.json().await?;
@@ -288,17 +394,121 @@ impl Mailer for MailgunMailer {
                 .map(|e| e.message)
                 .unwrap_or(error_body);
 
-            Err(MailError::provider_with_status(
-                "mailgun",
-                error_msg,
-                status.as_u16(),
-            ))
+            Err(
+                MailError::provider_with_status("mailgun", error_msg, status.as_u16())
+                    .with_retry_after(retry_after),
+            )
         }
     }
 
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        // The MIME To header would list every recipient
+        if self.mime
+            || !email.cc.is_empty()
+            || !email.bcc.is_empty()
+            || email.provider_options.contains_key("recipient_vars")
+        {
+            let emails = email.render_personalizations();
+            return self.deliver_many(&emails).await;
+        }
+
+        // Every placeholder becomes a recipient variable
+        let mut variables: Vec<(String, String)> = Vec::new();
+        let mut template = |source: &str| {
+            crate::personalize::replace(source, |key| {
+                let index = match variables.iter().position(|(k, _)| k == key) {
+                    Some(index) => index,
+                    None => {
+                        let name = recipient_variable(key, &variables);
+                        variables.push((key.to_string(), name));
+                        variables.len() - 1
+                    }
+                };
+                Some(format!("%recipient.{}%", variables[index].1))
+            })
+        };
+        let mut batch = email.clone();
+        batch.personalizations.clear();
+        batch.subject = template(&email.subject);
+        batch.text_body = email.text_body.as_deref().map(&mut template);
+        batch.html_body = email.html_body.as_deref().map(&mut template);
+
+        let mut results = Vec::with_capacity(email.personalizations.len());
+        for chunk in email.personalizations.chunks(MAX_BATCH_RECIPIENTS) {
+            let mut recipient_vars = serde_json::Map::new();
+            for p in chunk {
+                let context = crate::personalize::context(email, p);
+                let values: serde_json::Map<String, Value> = variables
+                    .iter()
+                    .map(|(key, name)| {
+                        // Unknown placeholders are left as they are
+                        let value = crate::personalize::lookup_str(&context, key)
+                            .unwrap_or_else(|| format!("{{{{ {} }}}}", key));
+                        (name.clone(), Value::String(value))
+                    })
+                    .collect();
+                recipient_vars.insert(p.to.email.clone(), Value::Object(values));
+            }
+            batch.to = chunk.iter().map(|p| p.to.clone()).collect();
+            batch
+                .provider_options
+                .insert("recipient_vars".into(), Value::Object(recipient_vars));
+
+            let result = self.deliver(&batch).await?;
+            results.extend(chunk.iter().map(|p| {
+                DeliveryResult::with_response(
+                    result.message_id.clone(),
+                    serde_json::json!({ "provider": "mailgun", "recipient": p.to.email }),
+                )
+            }));
+        }
+        Ok(results)
+    }
+
     fn provider_name(&self) -> &'static str {
         "mailgun"
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_scheduling: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("mailgun")
+        }
+    }
+
+    /// Reads the domain's events for the message (`GET /events`); the
+    /// latest delivery event wins. Mailgun keeps events for a few days only,
+    /// so older messages are [`MessageStatus::Unknown`].
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        let url = format!("{}/{}/events", self.base_url, self.domain);
+        let message_id = message_id.trim_start_matches('<').trim_end_matches('>');
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("message-id", message_id), ("ascending", "yes")])
+            .header("Authorization", self.auth_header())
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+            .send()
+            .await?;
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+
+        if status.is_success() {
+            let events: MailgunEvents = response.json().await?;
+            Ok(events.message_status())
+        } else {
+            let error_body = response.text().await.unwrap_or_default();
+            let error_msg = serde_json::from_str::<MailgunError>(&error_body)
+                .map(|e| e.message)
+                .unwrap_or(error_body);
+            Err(
+                MailError::provider_with_status("mailgun", error_msg, status.as_u16())
+                    .with_retry_after(retry_after),
+            )
+        }
+    }
 }
 
 // ============================================================================
@@ -315,3 +525,32 @@ struct MailgunResponse {
 struct MailgunError {
     message: String,
 }
+
+#[derive(Debug, Deserialize)]
+struct MailgunEvents {
+    #[serde(default)]
+    items: Vec<Value>,
+}
+
+impl MailgunEvents {
+    /// Events come oldest first (`ascending=yes`).
+    fn message_status(&self) -> MessageStatus {
+        let classifier = BounceClassifier::new();
+        self.items
+            .iter()
+            .rev()
+            .find_map(|event| match event.get("event")?.as_str()? {
+                "accepted" => Some(MessageStatus::Queued),
+                "delivered" => Some(MessageStatus::Delivered),
+                "complained" => Some(MessageStatus::Complained),
+                "failed" if event.get("severity").and_then(Value::as_str) == Some("temporary") => {
+                    Some(MessageStatus::Deferred)
+                }
+                "failed" => classifier
+                    .classify("mailgun", event)
+                    .map(MessageStatus::Bounced),
+                _ => None,
+            })
+            .unwrap_or(MessageStatus::Unknown)
+    }
+}
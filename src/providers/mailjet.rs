@@ -44,9 +44,11 @@ use serde_json::Value;
 
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
 
 const MAILJET_API_URL: &str = "https://api.mailjet.com/v3.1";
+/// Messages per Send API request.
+const MAX_MESSAGES: usize = 50;
 
 /// Mailjet API email provider.
 pub struct MailjetMailer {
@@ -62,7 +64,7 @@ impl MailjetMailer {
         Self {
             api_key: api_key.into(),
             secret_key: secret_key.into(),
-            client: Client::new(),
+            client: crate::http::default_client(),
             base_url: MAILJET_API_URL.to_string(),
         }
     }
@@ -87,12 +89,20 @@ impl MailjetMailer {
         self
     }
 
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     fn auth_header(&self) -> String {
         let credentials = format!("{}:{}", self.api_key, self.secret_key);
         format!("Basic {}", BASE64.encode(credentials.as_bytes()))
     }
 
     fn build_message(&self, email: &Email) -> Result<MailjetMessage, MailError> {
+        email.validate_headers()?;
+
         let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
 
         if email.to.is_empty() {
@@ -240,7 +250,8 @@ impl MailjetMailer {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for MailjetMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
         let message = self.build_message(email)?;
@@ -255,11 +266,13 @@ impl Mailer for MailjetMailer {
             .header("Authorization", self.auth_header())
             .header("Content-Type", "application/json")
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .json(&request)
             .send()
             .await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
         let body: MailjetResponse = response.json().await?;
 
         if status.is_success() {
@@ -287,7 +300,8 @@ impl Mailer for MailjetMailer {
                         "mailjet",
                         error_msg,
                         status.as_u16(),
-                    ));
+                    )
+                    .with_retry_after(retry_after));
                 }
             }
             // Fallback success
@@ -308,18 +322,18 @@ impl Mailer for MailjetMailer {
                         "mailjet",
                         error_msg,
                         status.as_u16(),
-                    ));
+                    )
+                    .with_retry_after(retry_after));
                 }
             }
             // Global error
             let error_msg = body
                 .error_message
                 .unwrap_or_else(|| "Unknown error".to_string());
-            Err(MailError::provider_with_status(
-                "mailjet",
-                error_msg,
-                status.as_u16(),
-            ))
+            Err(
+                MailError::provider_with_status("mailjet", error_msg, status.as_u16())
+                    .with_retry_after(retry_after),
+            )
         }
     }
 
@@ -340,11 +354,13 @@ impl Mailer for MailjetMailer {
             .header("Authorization", self.auth_header())
             .header("Content-Type", "application/json")
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .json(&request)
             .send()
             .await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
         let body: MailjetResponse = response.json().await?;
 
         if status.is_success() {
@@ -373,17 +389,35 @@ impl Mailer for MailjetMailer {
             let error_msg = body
                 .error_message
                 .unwrap_or_else(|| "Unknown error".to_string());
-            Err(MailError::provider_with_status(
-                "mailjet",
-                error_msg,
-                status.as_u16(),
-            ))
+            Err(
+                MailError::provider_with_status("mailjet", error_msg, status.as_u16())
+                    .with_retry_after(retry_after),
+            )
+        }
+    }
+
+    /// Send a mail merge as batches of messages, with each recipient's data
+    /// in `variables` and up to 50 recipients per request.
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        let emails = crate::personalize::render(email, Some("variables"));
+        let mut results = Vec::with_capacity(emails.len());
+        for chunk in emails.chunks(MAX_MESSAGES) {
+            results.extend(self.deliver_many(chunk).await?);
         }
+        Ok(results)
     }
 
     fn provider_name(&self) -> &'static str {
         "mailjet"
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("mailjet")
+        }
+    }
 }
 
 // ============================================================================
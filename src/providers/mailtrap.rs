@@ -19,6 +19,20 @@
 //!     .sandbox_inbox_id("111111");
 //! ```
 //!
+//! ## Bulk Sending
+//!
+//! Marketing and other bulk email goes through Mailtrap's bulk stream:
+//!
+//! ```rust,ignore
+//! let mailer = MailtrapMailer::new("your-api-key").bulk(true);
+//! ```
+//!
+//! Emails go to the sandbox when an inbox ID is set, otherwise to the bulk
+//! or transactional production host. `deliver_many` uses the batch endpoint
+//! on the same host, 500 emails per request. An email the batch rejects gets
+//! a result with `"failed": true` and the provider's `errors`; see
+//! [`DeliveryResult::is_failed`](crate::DeliveryResult::is_failed).
+//!
 //! ## Provider Options
 //!
 //! Mailtrap-specific options can be set via `provider_option`:
@@ -47,17 +61,22 @@ use serde_json::Value;
 
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
 
 const MAILTRAP_BASE_URL: &str = "https://send.api.mailtrap.io";
+const MAILTRAP_BULK_BASE_URL: &str = "https://bulk.api.mailtrap.io";
 const MAILTRAP_SANDBOX_BASE_URL: &str = "https://sandbox.api.mailtrap.io";
 const MAILTRAP_API_ENDPOINT: &str = "/api/send";
+const MAILTRAP_BATCH_ENDPOINT: &str = "/api/batch";
+/// Emails allowed in one batch request.
+const MAX_BATCH_SIZE: usize = 500;
 
 /// Mailtrap API email provider.
 pub struct MailtrapMailer {
     api_key: String,
     base_url: Option<String>,
     sandbox_inbox_id: Option<String>,
+    bulk: bool,
     client: Client,
 }
 
@@ -68,7 +87,8 @@ impl MailtrapMailer {
             api_key: api_key.into(),
             base_url: None,
             sandbox_inbox_id: None,
-            client: Client::new(),
+            bulk: false,
+            client: crate::http::default_client(),
         }
     }
 
@@ -78,6 +98,7 @@ impl MailtrapMailer {
             api_key: api_key.into(),
             base_url: None,
             sandbox_inbox_id: None,
+            bulk: false,
             client,
         }
     }
@@ -88,26 +109,78 @@ impl MailtrapMailer {
         self
     }
 
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     /// Enable sandbox mode with the given inbox ID.
     pub fn sandbox_inbox_id(mut self, inbox_id: impl Into<String>) -> Self {
         self.sandbox_inbox_id = Some(inbox_id.into());
         self
     }
 
-    fn prepare_url(&self) -> String {
+    /// Send through the bulk stream instead of the transactional one.
+    ///
+    /// Ignored in sandbox mode.
+    pub fn bulk(mut self, enabled: bool) -> Self {
+        self.bulk = enabled;
+        self
+    }
+
+    fn prepare_url(&self, endpoint: &str) -> String {
         if let Some(ref inbox_id) = self.sandbox_inbox_id {
             let base = self
                 .base_url
                 .as_deref()
                 .unwrap_or(MAILTRAP_SANDBOX_BASE_URL);
-            format!("{}{}/{}", base, MAILTRAP_API_ENDPOINT, inbox_id)
+            format!("{}{}/{}", base, endpoint, inbox_id)
         } else {
-            let base = self.base_url.as_deref().unwrap_or(MAILTRAP_BASE_URL);
-            format!("{}{}", base, MAILTRAP_API_ENDPOINT)
+            let host = if self.bulk {
+                MAILTRAP_BULK_BASE_URL
+            } else {
+                MAILTRAP_BASE_URL
+            };
+            let base = self.base_url.as_deref().unwrap_or(host);
+            format!("{}{}", base, endpoint)
+        }
+    }
+
+    async fn post<T: Serialize + ?Sized>(
+        &self,
+        url: &str,
+        body: &T,
+    ) -> Result<reqwest::Response, MailError> {
+        let response = self
+            .client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
         }
+
+        let retry_after = crate::error::retry_after(response.headers());
+        let error: MailtrapError = response.json().await.unwrap_or(MailtrapError {
+            errors: vec!["Unknown error".to_string()],
+        });
+        Err(
+            MailError::provider_with_status("mailtrap", error.errors.join("; "), status.as_u16())
+                .with_retry_after(retry_after),
+        )
     }
 
     fn build_request(&self, email: &Email) -> Result<MailtrapRequest, MailError> {
+        email.validate_headers()?;
+
         let from = email
             .from
             .as_ref()
@@ -215,55 +288,78 @@ impl MailtrapMailer {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for MailtrapMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
         let request = self.build_request(email)?;
-        let url = self.prepare_url();
+        let url = self.prepare_url(MAILTRAP_API_ENDPOINT);
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("User-Agent", format!("missive/{}", crate::VERSION))
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await?;
+        let response = self.post(&url, &request).await?;
+        let result: MailtrapResponse = response.json().await?;
+        // Return the first message ID, or join them if multiple
+        let message_id = result
+            .message_ids
+            .first()
+            .cloned()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-        let status = response.status();
+        Ok(DeliveryResult::with_response(
+            message_id,
+            serde_json::json!({
+                "provider": "mailtrap",
+                "message_ids": result.message_ids,
+            }),
+        ))
+    }
 
-        if status.is_success() {
-            let result: MailtrapResponse = response.json().await?;
-            // Return the first message ID, or join them if multiple
-            let message_id = result
-                .message_ids
-                .first()
-                .cloned()
-                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-            Ok(DeliveryResult::with_response(
-                message_id,
-                serde_json::json!({
-                    "provider": "mailtrap",
-                    "message_ids": result.message_ids,
-                }),
-            ))
-        } else {
-            let error: MailtrapError = response.json().await.unwrap_or(MailtrapError {
-                errors: vec!["Unknown error".to_string()],
-            });
-            Err(MailError::provider_with_status(
-                "mailtrap",
-                error.errors.join("; "),
-                status.as_u16(),
-            ))
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        let requests = emails
+            .iter()
+            .map(|email| self.build_request(email))
+            .collect::<Result<Vec<_>, _>>()?;
+        let url = self.prepare_url(MAILTRAP_BATCH_ENDPOINT);
+
+        let mut results = Vec::with_capacity(requests.len());
+        for chunk in requests.chunks(MAX_BATCH_SIZE) {
+            let batch = MailtrapBatchRequest { requests: chunk };
+            let response = self.post(&url, &batch).await?;
+            let result: MailtrapBatchResponse = response.json().await?;
+
+            results.extend(result.responses.into_iter().map(|r| {
+                // Rejected entries don't stop the rest of the batch
+                let failed = !r.success;
+                if failed {
+                    tracing::warn!(
+                        provider = "mailtrap",
+                        "Batch message rejected: {}",
+                        r.errors.join("; ")
+                    );
+                }
+                DeliveryResult::with_response(
+                    r.message_ids.first().cloned().unwrap_or_default(),
+                    serde_json::json!({
+                        "provider": "mailtrap",
+                        "failed": failed,
+                        "message_ids": r.message_ids,
+                        "errors": r.errors,
+                    }),
+                )
+            }));
         }
+        Ok(results)
     }
 
     fn provider_name(&self) -> &'static str {
         "mailtrap"
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            ..Capabilities::for_provider("mailtrap")
+        }
+    }
 }
 
 // ============================================================================
@@ -293,6 +389,11 @@ struct MailtrapRequest {
     custom_variables: Option<Value>,
 }
 
+#[derive(Debug, Serialize)]
+struct MailtrapBatchRequest<'a> {
+    requests: &'a [MailtrapRequest],
+}
+
 #[derive(Debug, Serialize)]
 struct MailtrapEmailItem {
     email: String,
@@ -317,6 +418,21 @@ struct MailtrapResponse {
     message_ids: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct MailtrapBatchResponse {
+    #[serde(default)]
+    responses: Vec<MailtrapBatchItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MailtrapBatchItem {
+    success: bool,
+    #[serde(default)]
+    message_ids: Vec<String>,
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct MailtrapError {
     #[serde(default)]
@@ -7,6 +7,7 @@
 //! | Provider | Feature Flag | Description |
 //! |----------|-------------|-------------|
 //! | [`SmtpMailer`] | `smtp` | SMTP via lettre |
+//! | [`ProtonBridgeMailer`] | `smtp` | Proton Mail Bridge (local SMTP with a self-signed certificate) |
 //! | [`ResendMailer`] | `resend` | Resend API |
 //! | [`UnsentMailer`] | `unsent` | Unsent API |
 //! | [`PostmarkMailer`] | `postmark` | Postmark API |
@@ -16,18 +17,27 @@
 //! | [`AmazonSesMailer`] | `amazon_ses` | Amazon SES API |
 //! | [`MailtrapMailer`] | `mailtrap` | Mailtrap API (testing/staging) |
 //! | [`MailjetMailer`] | `mailjet` | Mailjet API |
+//! | [`ZeptoMailer`] | `zeptomail` | ZeptoMail (Zoho) API |
+//! | [`CustomerIoMailer`] | `customerio` | Customer.io Transactional API |
 //! | [`LocalMailer`] | `local` | In-memory storage for dev/testing |
+//! | [`FailingMailer`] | `local` | Failure and latency injection for tests |
 //! | [`LoggerMailer`] | (none) | Logs emails without storing |
 
 #[cfg(feature = "smtp")]
 mod smtp;
 #[cfg(feature = "smtp")]
-pub use smtp::SmtpMailer;
+pub use smtp::{SmtpBuilder, SmtpConfig, SmtpMailer, TlsMode};
+#[cfg(feature = "smtp")]
+mod proton_bridge;
+#[cfg(feature = "smtp")]
+pub use proton_bridge::{ProtonBridgeBuilder, ProtonBridgeMailer};
+#[cfg(all(feature = "smtp", feature = "socks"))]
+mod smtp_socks;
 
 #[cfg(feature = "resend")]
 mod resend;
 #[cfg(feature = "resend")]
-pub use resend::ResendMailer;
+pub use resend::{ResendAudience, ResendBroadcast, ResendContact, ResendMailer};
 
 #[cfg(feature = "unsent")]
 mod unsent;
@@ -69,10 +79,27 @@ mod mailjet;
 #[cfg(feature = "mailjet")]
 pub use mailjet::MailjetMailer;
 
+#[cfg(feature = "zeptomail")]
+mod zeptomail;
+#[cfg(feature = "zeptomail")]
+pub use zeptomail::ZeptoMailer;
+
+#[cfg(feature = "customerio")]
+mod customerio;
+#[cfg(feature = "customerio")]
+pub use customerio::CustomerIoMailer;
+
 #[cfg(feature = "local")]
 mod local;
 #[cfg(feature = "local")]
 pub use local::LocalMailer;
 
+#[cfg(feature = "local")]
+mod failing;
+#[cfg(feature = "local")]
+pub use failing::FailingMailer;
+
 mod logger;
 pub use logger::LoggerMailer;
+
+mod dev;
@@ -25,6 +25,13 @@
 //!     .provider_option("inline_css", true);
 //! ```
 //!
+//! To send everything through one stream, set it on the mailer (or with
+//! `POSTMARK_MESSAGE_STREAM`); the `message_stream` option still wins:
+//!
+//! ```rust,ignore
+//! let mailer = PostmarkMailer::new(token).message_stream("broadcast");
+//! ```
+//!
 //! ## Template Support
 //!
 //! Send emails using Postmark templates:
@@ -58,23 +65,32 @@
 //! let emails = vec![email1, email2, email3];
 //! let results = mailer.deliver_many(&emails).await?;
 //! ```
+//!
+//! Postmark accepts a batch even if it rejects some messages (e.g. an
+//! inactive recipient). Those results have an empty `message_id` and
+//! [`is_failed()`](crate::DeliveryResult::is_failed) returns true; the
+//! `provider_response` holds Postmark's `error_code` and `message`.
 
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::bounce::{BounceClass, BounceClassifier};
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
 
 const POSTMARK_API_URL: &str = "https://api.postmarkapp.com";
+/// Error code for an unknown message ID.
+const MESSAGE_NOT_FOUND: i32 = 701;
 
 /// Postmark API email provider.
 pub struct PostmarkMailer {
     api_token: String,
     client: Client,
     base_url: String,
+    message_stream: Option<String>,
 }
 
 impl PostmarkMailer {
@@ -82,8 +98,9 @@ impl PostmarkMailer {
     pub fn new(api_token: impl Into<String>) -> Self {
         Self {
             api_token: api_token.into(),
-            client: Client::new(),
+            client: crate::http::default_client(),
             base_url: POSTMARK_API_URL.to_string(),
+            message_stream: None,
         }
     }
 
@@ -93,6 +110,7 @@ impl PostmarkMailer {
             api_token: api_token.into(),
             client,
             base_url: POSTMARK_API_URL.to_string(),
+            message_stream: None,
         }
     }
 
@@ -102,6 +120,20 @@ impl PostmarkMailer {
         self
     }
 
+    /// Send through this message stream (e.g. `"broadcast"`) unless an email
+    /// sets the `message_stream` provider option. Postmark uses the server's
+    /// default transactional stream otherwise.
+    pub fn message_stream(mut self, stream: impl Into<String>) -> Self {
+        self.message_stream = Some(stream.into());
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     /// Check if this email uses a template.
     fn is_template_email(email: &Email) -> bool {
         email.provider_options.contains_key("template_id")
@@ -109,6 +141,8 @@ impl PostmarkMailer {
     }
 
     fn build_request(&self, email: &Email) -> Result<PostmarkRequest, MailError> {
+        email.validate_headers()?;
+
         let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
 
         if email.to.is_empty() {
@@ -158,7 +192,7 @@ impl PostmarkMailer {
             tag: None,
             track_opens: None,
             track_links: None,
-            message_stream: None,
+            message_stream: self.message_stream.clone(),
             metadata: None,
             inline_css: None,
             template_id: None,
@@ -252,6 +286,7 @@ impl PostmarkMailer {
             .header("Content-Type", "application/json")
             .header("Accept", "application/json")
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .json(body)
             .send()
             .await?)
@@ -267,16 +302,23 @@ impl PostmarkMailer {
         )
     }
 
-    fn parse_error(status: reqwest::StatusCode, error: PostmarkError) -> MailError {
+    fn parse_error(
+        status: reqwest::StatusCode,
+        error: PostmarkError,
+        retry_after: Option<std::time::Duration>,
+    ) -> MailError {
         MailError::provider_with_status(
             "postmark",
             format!("[{}] {}", error.error_code, error.message),
             status.as_u16(),
         )
+        .with_code(error.error_code.to_string())
+        .with_retry_after(retry_after)
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for PostmarkMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
         let request = self.build_request(email)?;
@@ -290,6 +332,7 @@ impl Mailer for PostmarkMailer {
 
         let response = self.send_request(&url, &request).await?;
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let result: PostmarkResponse = response.json().await?;
@@ -299,7 +342,7 @@ impl Mailer for PostmarkMailer {
                 error_code: 0,
                 message: "Unknown error".to_string(),
             });
-            Err(Self::parse_error(status, error))
+            Err(Self::parse_error(status, error, retry_after))
         }
     }
 
@@ -333,16 +376,29 @@ impl Mailer for PostmarkMailer {
         };
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let results: Vec<PostmarkBatchResponse> = response.json().await?;
             Ok(results
                 .into_iter()
                 .map(|r| {
+                    // Non-zero codes are per-message rejections, e.g. 406
+                    // for an inactive recipient; the rest were still sent.
+                    let failed = r.error_code != 0;
+                    if failed {
+                        tracing::warn!(
+                            provider = "postmark",
+                            code = r.error_code,
+                            "Batch message rejected: {}",
+                            r.message
+                        );
+                    }
                     DeliveryResult::with_response(
                         r.message_id,
                         serde_json::json!({
                             "provider": "postmark",
+                            "failed": failed,
                             "error_code": r.error_code,
                             "message": r.message,
                             "to": r.to,
@@ -356,13 +412,52 @@ impl Mailer for PostmarkMailer {
                 error_code: 0,
                 message: "Unknown error".to_string(),
             });
-            Err(Self::parse_error(status, error))
+            Err(Self::parse_error(status, error, retry_after))
         }
     }
 
     fn provider_name(&self) -> &'static str {
         "postmark"
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("postmark")
+        }
+    }
+
+    /// Reads `GET /messages/outbound/{id}/details`. The latest delivery
+    /// event wins; without one, the message's own status is used.
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        let url = format!("{}/messages/outbound/{}/details", self.base_url, message_id);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Postmark-Server-Token", &self.api_token)
+            .header("Accept", "application/json")
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+            .send()
+            .await?;
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+
+        if status.is_success() {
+            let details: PostmarkMessageDetails = response.json().await?;
+            Ok(details.message_status())
+        } else {
+            let error: PostmarkError = response.json().await.unwrap_or(PostmarkError {
+                error_code: 0,
+                message: "Unknown error".to_string(),
+            });
+            if status == reqwest::StatusCode::NOT_FOUND || error.error_code == MESSAGE_NOT_FOUND {
+                return Ok(MessageStatus::Unknown);
+            }
+            Err(Self::parse_error(status, error, retry_after))
+        }
+    }
 }
 
 // ============================================================================
@@ -460,3 +555,48 @@ struct PostmarkError {
     error_code: i32,
     message: String,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PostmarkMessageDetails {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    message_events: Vec<PostmarkMessageEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PostmarkMessageEvent {
+    #[serde(rename = "Type")]
+    kind: String,
+    #[serde(default)]
+    details: Value,
+}
+
+impl PostmarkMessageDetails {
+    fn message_status(&self) -> MessageStatus {
+        let latest = self
+            .message_events
+            .iter()
+            .rev()
+            .find_map(|event| match event.kind.as_str() {
+                "Delivered" => Some(MessageStatus::Delivered),
+                "Transient" => Some(MessageStatus::Deferred),
+                "SpamComplaint" => Some(MessageStatus::Complained),
+                "Bounced" => {
+                    let summary = event.details.get("Summary").and_then(Value::as_str);
+                    let class = summary
+                        .and_then(|summary| BounceClassifier::new().classify_status(summary))
+                        .unwrap_or(BounceClass::HardBounce);
+                    Some(MessageStatus::Bounced(class))
+                }
+                _ => None,
+            });
+        latest.unwrap_or(match self.status.as_str() {
+            "Queued" => MessageStatus::Queued,
+            "Sent" | "Processed" => MessageStatus::Sent,
+            _ => MessageStatus::Unknown,
+        })
+    }
+}
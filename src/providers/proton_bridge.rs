@@ -0,0 +1,135 @@
+//! Proton Mail Bridge preset for [`SmtpMailer`].
+//!
+//! Proton Mail has no SMTP relay; its desktop Bridge app runs one on
+//! localhost. The Bridge listens on `127.0.0.1:1025` with STARTTLS and a
+//! self-signed certificate, and takes the username and password it shows
+//! in its settings (not your Proton account password).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use missive::providers::ProtonBridgeMailer;
+//!
+//! let mailer = ProtonBridgeMailer::new("you@proton.me", "bridge-password");
+//!
+//! // Bridge set to SSL, or on another port
+//! let mailer = ProtonBridgeMailer::builder("you@proton.me", "bridge-password")
+//!     .port(1465)
+//!     .ssl()
+//!     .build();
+//! ```
+//!
+//! Or set `EMAIL_PROVIDER=protonbridge` with `PROTON_BRIDGE_USERNAME` and
+//! `PROTON_BRIDGE_PASSWORD`.
+//!
+//! The certificate check is skipped because the Bridge generates its own;
+//! don't point this at anything but a Bridge on a trusted host.
+
+use async_trait::async_trait;
+
+use super::smtp::TlsMode;
+use super::SmtpMailer;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{DeliveryResult, Mailer};
+
+/// Bridge's default SMTP host.
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// Bridge's default SMTP port.
+pub const DEFAULT_PORT: u16 = 1025;
+
+/// [`SmtpMailer`] configured for Proton Mail Bridge.
+pub struct ProtonBridgeMailer {
+    inner: SmtpMailer,
+}
+
+impl ProtonBridgeMailer {
+    /// Connect to the Bridge on `127.0.0.1:1025` with STARTTLS.
+    pub fn new(username: &str, password: &str) -> Self {
+        Self::builder(username, password).build()
+    }
+
+    /// Start from the Bridge defaults and adjust the host, port or TLS mode.
+    pub fn builder(username: &str, password: &str) -> ProtonBridgeBuilder {
+        ProtonBridgeBuilder {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            username: username.to_string(),
+            password: password.to_string(),
+            tls: TlsMode::StartTls,
+        }
+    }
+}
+
+/// Builder for [`ProtonBridgeMailer`].
+pub struct ProtonBridgeBuilder {
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
+    tls: TlsMode,
+}
+
+impl ProtonBridgeBuilder {
+    /// Bridge host (default: `127.0.0.1`), e.g. when it runs in another container.
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = host.to_string();
+        self
+    }
+
+    /// Bridge SMTP port (default: 1025).
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Use implicit TLS, for a Bridge with its SMTP security set to SSL.
+    pub fn ssl(mut self) -> Self {
+        self.tls = TlsMode::Tls;
+        self
+    }
+
+    /// Build the mailer.
+    pub fn build(self) -> ProtonBridgeMailer {
+        let inner = SmtpMailer::new(&self.host, self.port)
+            .credentials(&self.username, &self.password)
+            .tls(self.tls)
+            .dangerous_accept_invalid_certs(true)
+            .build();
+        ProtonBridgeMailer { inner }
+    }
+}
+
+#[async_trait]
+impl Mailer for ProtonBridgeMailer {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        self.inner.deliver(email).await
+    }
+
+    fn batch_concurrency(&self) -> usize {
+        self.inner.batch_concurrency()
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "protonbridge"
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_name() {
+        let mailer = ProtonBridgeMailer::builder("you@proton.me", "secret")
+            .port(1465)
+            .ssl()
+            .build();
+        assert_eq!(mailer.provider_name(), "protonbridge");
+    }
+}
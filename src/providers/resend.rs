@@ -45,15 +45,36 @@
 //!         }
 //!     }));
 //! ```
+//!
+//! ## Broadcasts
+//!
+//! Marketing sends go through audiences and broadcasts with the same key:
+//!
+//! ```rust,ignore
+//! use missive::providers::{ResendBroadcast, ResendContact};
+//!
+//! let audience = mailer.create_audience("Newsletter").await?;
+//! mailer
+//!     .add_contact(&audience.id, &ResendContact::new("jane@example.com").first_name("Jane"))
+//!     .await?;
+//!
+//! let broadcast = ResendBroadcast::new(&audience.id, "news@example.com", "June update")
+//!     .html("<p>Hi {{{FIRST_NAME|there}}}</p>");
+//! let id = mailer.create_broadcast(&broadcast).await?;
+//! mailer.send_broadcast(&id).await?;
+//! ```
 
 use async_trait::async_trait;
-use reqwest::Client;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::address::Address;
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
 
 const RESEND_API_URL: &str = "https://api.resend.com";
 
@@ -62,6 +83,7 @@ pub struct ResendMailer {
     api_key: String,
     client: Client,
     base_url: String,
+    batch_fallback: bool,
 }
 
 impl ResendMailer {
@@ -69,8 +91,9 @@ impl ResendMailer {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
-            client: Client::new(),
+            client: crate::http::default_client(),
             base_url: RESEND_API_URL.to_string(),
+            batch_fallback: false,
         }
     }
 
@@ -80,6 +103,7 @@ impl ResendMailer {
             api_key: api_key.into(),
             client,
             base_url: RESEND_API_URL.to_string(),
+            batch_fallback: false,
         }
     }
 
@@ -89,7 +113,25 @@ impl ResendMailer {
         self
     }
 
+    /// Send emails the batch API can't take (attachments, `scheduled_at`)
+    /// individually instead of failing `deliver_many()`.
+    ///
+    /// The rest still go out in one batch request. Results keep the input
+    /// order.
+    pub fn batch_fallback(mut self, enabled: bool) -> Self {
+        self.batch_fallback = enabled;
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     fn build_request(&self, email: &Email) -> Result<ResendRequest, MailError> {
+        email.validate_headers()?;
+
         let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
 
         if email.to.is_empty() {
@@ -149,9 +191,17 @@ impl ResendMailer {
                     } else {
                         None
                     };
+                    // Hosted files (presigned S3 URLs) are fetched by Resend
+                    let (content, path) = match &a.path {
+                        Some(url) if url.starts_with("https://") || url.starts_with("http://") => {
+                            (None, Some(url.clone()))
+                        }
+                        _ => (Some(a.base64_data()), None),
+                    };
                     ResendAttachment {
                         filename: a.filename.clone(),
-                        content: a.base64_data(),
+                        content,
+                        path,
                         content_type: Some(a.content_type.clone()),
                         content_id,
                     }
@@ -175,7 +225,8 @@ impl ResendMailer {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for ResendMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
         let request = self.build_request(email)?;
@@ -186,7 +237,8 @@ impl Mailer for ResendMailer {
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .header("User-Agent", format!("missive/{}", crate::VERSION));
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers());
 
         // Add idempotency key header if provided
         if let Some(idempotency_key) = email.provider_options.get("idempotency_key") {
@@ -198,6 +250,7 @@ impl Mailer for ResendMailer {
         let response = req.json(&request).send().await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let result: ResendResponse = response.json().await?;
@@ -210,11 +263,12 @@ impl Mailer for ResendMailer {
                 message: "Unknown error".to_string(),
                 name: None,
             });
-            Err(MailError::provider_with_status(
-                "resend",
-                error.message,
-                status.as_u16(),
-            ))
+            let mut err = MailError::provider_with_status("resend", error.message, status.as_u16())
+                .with_retry_after(retry_after);
+            if let Some(name) = error.name {
+                err = err.with_code(name);
+            }
+            Err(err)
         }
     }
 
@@ -223,17 +277,18 @@ impl Mailer for ResendMailer {
     /// Resend's batch API does not support:
     /// - `scheduled_at` option
     /// - Attachments
+    ///
+    /// With [`batch_fallback`](ResendMailer::batch_fallback), such emails are
+    /// sent individually instead.
     fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        if self.batch_fallback {
+            return Ok(());
+        }
         for (i, email) in emails.iter().enumerate() {
-            if email.provider_options.contains_key("scheduled_at") {
+            if let Some(reason) = batch_unsupported(email) {
                 return Err(MailError::UnsupportedFeature(format!(
-                    "scheduled_at is not supported in batch sends (email {})",
-                    i + 1
-                )));
-            }
-            if !email.attachments.is_empty() {
-                return Err(MailError::UnsupportedFeature(format!(
-                    "attachments are not supported in Resend batch sends (email {})",
+                    "{} (email {})",
+                    reason,
                     i + 1
                 )));
             }
@@ -249,9 +304,79 @@ impl Mailer for ResendMailer {
         // Validate batch restrictions
         self.validate_batch(emails)?;
 
+        let (batch, single): (Vec<usize>, Vec<usize>) =
+            (0..emails.len()).partition(|&i| batch_unsupported(&emails[i]).is_none());
+        if single.is_empty() {
+            return self.send_batch(emails.iter()).await;
+        }
+
+        let mut results: Vec<Option<DeliveryResult>> = emails.iter().map(|_| None).collect();
+        if !batch.is_empty() {
+            let sent = self.send_batch(batch.iter().map(|&i| &emails[i])).await?;
+            for (i, result) in batch.into_iter().zip(sent) {
+                results[i] = Some(result);
+            }
+        }
+        for i in single {
+            results[i] = Some(self.deliver(&emails[i]).await?);
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "resend"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            supports_attachments_in_batch: self.batch_fallback,
+            supports_scheduling: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("resend")
+        }
+    }
+
+    /// Lists domains with `GET /domains`. Sending-only keys can't list
+    /// domains, but Resend names them `restricted_api_key`, which proves the
+    /// key is valid.
+    async fn health_check(&self) -> Result<(), MailError> {
+        let url = format!("{}/domains", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let error: ResendError = response.json().await.unwrap_or(ResendError {
+            message: "Unknown error".to_string(),
+            name: None,
+        });
+        if error.name.as_deref() == Some("restricted_api_key") {
+            return Ok(());
+        }
+        let mut err = MailError::provider_with_status("resend", error.message, status.as_u16());
+        if let Some(name) = error.name {
+            err = err.with_code(name);
+        }
+        Err(err)
+    }
+}
+
+impl ResendMailer {
+    /// Send emails with one `/emails/batch` request.
+    async fn send_batch<'a>(
+        &self,
+        emails: impl Iterator<Item = &'a Email>,
+    ) -> Result<Vec<DeliveryResult>, MailError> {
         // Build requests
         let requests: Vec<ResendRequest> = emails
-            .iter()
             .map(|email| self.build_request(email))
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -262,11 +387,13 @@ impl Mailer for ResendMailer {
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .json(&requests)
             .send()
             .await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let result: ResendBatchResponse = response.json().await?;
@@ -282,16 +409,248 @@ impl Mailer for ResendMailer {
                 message: "Unknown error".to_string(),
                 name: None,
             });
-            Err(MailError::provider_with_status(
-                "resend",
-                error.message,
-                status.as_u16(),
-            ))
+            let mut err = MailError::provider_with_status("resend", error.message, status.as_u16())
+                .with_retry_after(retry_after);
+            if let Some(name) = error.name {
+                err = err.with_code(name);
+            }
+            Err(err)
         }
     }
+}
 
-    fn provider_name(&self) -> &'static str {
-        "resend"
+/// Why the batch API can't send `email`, if it can't.
+fn batch_unsupported(email: &Email) -> Option<&'static str> {
+    if email.provider_options.contains_key("scheduled_at") {
+        Some("scheduled_at is not supported in batch sends")
+    } else if !email.attachments.is_empty() {
+        Some("attachments are not supported in Resend batch sends")
+    } else {
+        None
+    }
+}
+
+// ============================================================================
+// Audiences and Broadcasts
+// ============================================================================
+
+impl ResendMailer {
+    /// Create an audience (a contact list for broadcasts).
+    pub async fn create_audience(&self, name: &str) -> Result<ResendAudience, MailError> {
+        let response = self
+            .api(Method::POST, "/audiences")
+            .json(&serde_json::json!({ "name": name }))
+            .send()
+            .await?;
+        let created: ResendResponse = parse(response).await?;
+        Ok(ResendAudience {
+            id: created.id,
+            name: name.to_string(),
+        })
+    }
+
+    /// List all audiences.
+    pub async fn list_audiences(&self) -> Result<Vec<ResendAudience>, MailError> {
+        let response = self.api(Method::GET, "/audiences").send().await?;
+        let list: ResendList<ResendAudience> = parse(response).await?;
+        Ok(list.data)
+    }
+
+    /// Add a contact to an audience, returning the contact ID.
+    pub async fn add_contact(
+        &self,
+        audience_id: &str,
+        contact: &ResendContact,
+    ) -> Result<String, MailError> {
+        let path = format!("/audiences/{}/contacts", audience_id);
+        let response = self.api(Method::POST, &path).json(contact).send().await?;
+        let created: ResendResponse = parse(response).await?;
+        Ok(created.id)
+    }
+
+    /// Remove a contact, by ID or email address, from an audience.
+    pub async fn remove_contact(&self, audience_id: &str, contact: &str) -> Result<(), MailError> {
+        let path = format!("/audiences/{}/contacts/{}", audience_id, contact);
+        let response = self.api(Method::DELETE, &path).send().await?;
+        parse::<Value>(response).await.map(drop)
+    }
+
+    /// Create a draft broadcast, returning its ID.
+    pub async fn create_broadcast(&self, broadcast: &ResendBroadcast) -> Result<String, MailError> {
+        if broadcast.html.is_none() && broadcast.text.is_none() {
+            return Err(MailError::MissingField("html"));
+        }
+        let response = self
+            .api(Method::POST, "/broadcasts")
+            .json(broadcast)
+            .send()
+            .await?;
+        let created: ResendResponse = parse(response).await?;
+        Ok(created.id)
+    }
+
+    /// Send a broadcast to its audience now.
+    pub async fn send_broadcast(&self, broadcast_id: &str) -> Result<(), MailError> {
+        self.post_send(broadcast_id, serde_json::json!({})).await
+    }
+
+    /// Schedule a broadcast to be sent at `at`.
+    pub async fn schedule_broadcast(
+        &self,
+        broadcast_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(), MailError> {
+        self.post_send(
+            broadcast_id,
+            serde_json::json!({ "scheduled_at": at.to_rfc3339() }),
+        )
+        .await
+    }
+
+    async fn post_send(&self, broadcast_id: &str, body: Value) -> Result<(), MailError> {
+        let path = format!("/broadcasts/{}/send", broadcast_id);
+        let response = self.api(Method::POST, &path).json(&body).send().await?;
+        parse::<Value>(response).await.map(drop)
+    }
+
+    fn api(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+    }
+}
+
+/// Deserialize a successful response or convert the error body.
+async fn parse<T: DeserializeOwned>(response: Response) -> Result<T, MailError> {
+    let status = response.status();
+    let retry_after = crate::error::retry_after(response.headers());
+    if status.is_success() {
+        return Ok(response.json().await?);
+    }
+    let error: ResendError = response.json().await.unwrap_or(ResendError {
+        message: "Unknown error".to_string(),
+        name: None,
+    });
+    let mut err = MailError::provider_with_status("resend", error.message, status.as_u16())
+        .with_retry_after(retry_after);
+    if let Some(name) = error.name {
+        err = err.with_code(name);
+    }
+    Err(err)
+}
+
+/// A Resend audience.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ResendAudience {
+    /// Audience ID.
+    pub id: String,
+    /// Display name.
+    pub name: String,
+}
+
+/// A contact to add to an audience.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResendContact {
+    email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_name: Option<String>,
+    unsubscribed: bool,
+}
+
+impl ResendContact {
+    /// Create a subscribed contact.
+    pub fn new(email: impl Into<String>) -> Self {
+        Self {
+            email: email.into(),
+            first_name: None,
+            last_name: None,
+            unsubscribed: false,
+        }
+    }
+
+    /// Set the first name (`{{{FIRST_NAME}}}` in broadcasts).
+    pub fn first_name(mut self, name: impl Into<String>) -> Self {
+        self.first_name = Some(name.into());
+        self
+    }
+
+    /// Set the last name (`{{{LAST_NAME}}}` in broadcasts).
+    pub fn last_name(mut self, name: impl Into<String>) -> Self {
+        self.last_name = Some(name.into());
+        self
+    }
+
+    /// Add the contact as unsubscribed, so broadcasts skip it.
+    pub fn unsubscribed(mut self, unsubscribed: bool) -> Self {
+        self.unsubscribed = unsubscribed;
+        self
+    }
+}
+
+/// A broadcast: one email sent to every subscribed contact in an audience.
+///
+/// Resend fills `{{{FIRST_NAME}}}`, `{{{LAST_NAME}}}`, `{{{EMAIL}}}` and
+/// `{{{RESEND_UNSUBSCRIBE_URL}}}` per contact.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResendBroadcast {
+    audience_id: String,
+    from: String,
+    subject: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    reply_to: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl ResendBroadcast {
+    /// Create a broadcast to `audience_id`. Set a body with
+    /// [`html`](Self::html) or [`text`](Self::text).
+    pub fn new(
+        audience_id: impl Into<String>,
+        from: impl Into<Address>,
+        subject: impl Into<String>,
+    ) -> Self {
+        Self {
+            audience_id: audience_id.into(),
+            from: from.into().formatted(),
+            subject: subject.into(),
+            reply_to: Vec::new(),
+            html: None,
+            text: None,
+            name: None,
+        }
+    }
+
+    /// Set the HTML body.
+    pub fn html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
+    /// Set the plain text body.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Add a reply-to address.
+    pub fn reply_to(mut self, address: impl Into<Address>) -> Self {
+        self.reply_to.push(address.into().formatted());
+        self
+    }
+
+    /// Set an internal name, shown in the Resend dashboard.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
     }
 }
 
@@ -336,7 +695,10 @@ struct ResendHeader {
 #[derive(Debug, Serialize)]
 struct ResendAttachment {
     filename: String,
-    content: String, // Base64 encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>, // Base64 encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>, // URL
     #[serde(skip_serializing_if = "Option::is_none")]
     content_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -354,6 +716,11 @@ struct ResendResponse {
     id: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct ResendList<T> {
+    data: Vec<T>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ResendBatchResponse {
     data: Vec<ResendResponse>,
@@ -363,6 +730,5 @@ struct ResendBatchResponse {
 struct ResendError {
     message: String,
     #[serde(default)]
-    #[allow(dead_code)]
     name: Option<String>,
 }
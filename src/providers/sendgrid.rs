@@ -8,6 +8,16 @@
 //! let mailer = SendGridMailer::new("SG.xxxxx");
 //! ```
 //!
+//! EU-pinned accounts use the EU endpoint, and a parent account's key can
+//! send as a subuser:
+//!
+//! ```rust,ignore
+//! let mailer = SendGridMailer::new("SG.xxxxx").eu().on_behalf_of("acme-eu");
+//! ```
+//!
+//! `health_check()` fails unless the key (or subuser) has the `mail.send`
+//! scope.
+//!
 //! ## Provider Options
 //!
 //! SendGrid-specific options can be set via `provider_option`:
@@ -54,6 +64,21 @@
 //!         {"to": [{"email": "user2@example.com"}], "subject": "Custom 2"}
 //!     ]));
 //! ```
+//!
+//! ### Mail Merge
+//!
+//! With a `template_id`, [`Mailer::deliver_personalized`] sends one
+//! personalization per recipient, carrying their data as
+//! `dynamic_template_data`. Without one, each recipient's email is rendered
+//! locally and sent separately.
+//!
+//! ### Batch Sending
+//!
+//! `deliver_many` sends emails that differ only in their recipients and
+//! personalization options (`dynamic_template_data`, `custom_args`,
+//! `substitutions`) as one `/mail/send` request, with a personalization per
+//! email and up to 1000 per request. Other emails get their own requests.
+//! Grouped emails share the request's message ID.
 
 use async_trait::async_trait;
 use flate2::write::GzEncoder;
@@ -61,13 +86,21 @@ use flate2::Compression;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::io::Write;
 
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+#[cfg(feature = "insights")]
+use crate::insights::{Insights, SendQuota, SendStatistics};
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
 
 const SENDGRID_API_URL: &str = "https://api.sendgrid.com/v3";
+const SENDGRID_EU_API_URL: &str = "https://api.eu.sendgrid.com/v3";
+/// Personalizations allowed in one request.
+const MAX_PERSONALIZATIONS: usize = 1000;
+/// Recipients (to, cc and bcc) allowed in one request.
+const MAX_RECIPIENTS: usize = 1000;
 
 /// SendGrid API email provider.
 pub struct SendGridMailer {
@@ -75,6 +108,7 @@ pub struct SendGridMailer {
     client: Client,
     base_url: String,
     compress: bool,
+    on_behalf_of: Option<String>,
 }
 
 impl SendGridMailer {
@@ -82,9 +116,10 @@ impl SendGridMailer {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
-            client: Client::new(),
+            client: crate::http::default_client(),
             base_url: SENDGRID_API_URL.to_string(),
             compress: false,
+            on_behalf_of: None,
         }
     }
 
@@ -95,6 +130,7 @@ impl SendGridMailer {
             client,
             base_url: SENDGRID_API_URL.to_string(),
             compress: false,
+            on_behalf_of: None,
         }
     }
 
@@ -104,6 +140,23 @@ impl SendGridMailer {
         self
     }
 
+    /// Use the EU regional endpoint, for EU-pinned subusers.
+    pub fn eu(self) -> Self {
+        self.base_url(SENDGRID_EU_API_URL)
+    }
+
+    /// Send as a subuser from a parent account's key (`on-behalf-of`).
+    pub fn on_behalf_of(mut self, subuser: impl Into<String>) -> Self {
+        self.on_behalf_of = Some(subuser.into());
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     /// Enable gzip compression for requests.
     pub fn compress(mut self, enabled: bool) -> Self {
         self.compress = enabled;
@@ -111,6 +164,8 @@ impl SendGridMailer {
     }
 
     fn build_request(&self, email: &Email) -> Result<SendGridRequest, MailError> {
+        email.validate_headers()?;
+
         let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
 
         // Check if custom personalizations are provided
@@ -313,6 +368,22 @@ impl SendGridMailer {
         personalization
     }
 
+    fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        self.subuser(
+            self.client
+                .get(format!("{}{}", self.base_url, path))
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("User-Agent", format!("missive/{}", crate::VERSION)),
+        )
+    }
+
+    fn subuser(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.on_behalf_of {
+            Some(subuser) => request.header("on-behalf-of", subuser),
+            None => request,
+        }
+    }
+
     fn compress_body(&self, body: &[u8]) -> Result<Vec<u8>, MailError> {
         let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
         encoder.write_all(body).map_err(|e| {
@@ -322,22 +393,20 @@ impl SendGridMailer {
             MailError::provider("sendgrid", format!("Failed to finish compression: {}", e))
         })
     }
-}
-
-#[async_trait]
-impl Mailer for SendGridMailer {
-    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
-        let request = self.build_request(email)?;
 
+    /// POST a request to `/mail/send`.
+    async fn send(&self, request: &SendGridRequest) -> Result<DeliveryResult, MailError> {
         let url = format!("{}/mail/send", self.base_url);
-        let json_body = serde_json::to_vec(&request)?;
+        let json_body = serde_json::to_vec(request)?;
 
         let mut req = self
             .client
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
-            .header("User-Agent", format!("missive/{}", crate::VERSION));
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers());
+        req = self.subuser(req);
 
         let body = if self.compress {
             req = req.header("Content-Encoding", "gzip");
@@ -349,6 +418,7 @@ impl Mailer for SendGridMailer {
         let response = req.body(body).send().await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         // SendGrid returns 202 Accepted on success with no body
         if status.is_success() {
@@ -380,24 +450,280 @@ impl Mailer for SendGridMailer {
                 .collect::<Vec<_>>()
                 .join("; ");
 
+            Err(
+                MailError::provider_with_status("sendgrid", error_msg, status.as_u16())
+                    .with_retry_after(retry_after),
+            )
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Mailer for SendGridMailer {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let request = self.build_request(email)?;
+        self.send(&request).await
+    }
+
+    /// Send emails that share everything but their recipients and
+    /// personalization options as one request, with a personalization each.
+    /// Results keep the input order; grouped emails share a message ID.
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        // Build (and validate) every request before sending any
+        let mut groups: Vec<(SendGridRequest, Vec<(usize, Vec<SendGridPersonalization>)>)> =
+            Vec::new();
+        let mut keys: HashMap<String, usize> = HashMap::new();
+        for (i, email) in emails.iter().enumerate() {
+            let mut request = self.build_request(email)?;
+            let personalizations = std::mem::take(&mut request.personalizations);
+            // Custom personalizations are sent as given
+            let key = if email.provider_options.contains_key("personalizations") {
+                None
+            } else {
+                Some(serde_json::to_string(&request)?)
+            };
+            match key.as_ref().and_then(|k| keys.get(k)) {
+                Some(&group) => groups[group].1.push((i, personalizations)),
+                None => {
+                    if let Some(key) = key {
+                        keys.insert(key, groups.len());
+                    }
+                    groups.push((request, vec![(i, personalizations)]));
+                }
+            }
+        }
+
+        let mut results: Vec<Option<DeliveryResult>> = emails.iter().map(|_| None).collect();
+        for (mut request, members) in groups {
+            for chunk in chunk_personalizations(members) {
+                let (indexes, personalizations): (Vec<usize>, Vec<_>) = chunk.into_iter().unzip();
+                request.personalizations = personalizations.into_iter().flatten().collect();
+                let result = self.send(&request).await?;
+                for i in indexes {
+                    results[i] = Some(DeliveryResult::with_response(
+                        result.message_id.clone(),
+                        serde_json::json!({ "provider": "sendgrid" }),
+                    ));
+                }
+            }
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        if !email.provider_options.contains_key("template_id") {
+            let emails = email.render_personalizations();
+            return self.deliver_many(&emails).await;
+        }
+
+        let mut results = Vec::with_capacity(email.personalizations.len());
+        for chunk in email.personalizations.chunks(MAX_PERSONALIZATIONS) {
+            let mut batch = email.clone();
+            batch.personalizations.clear();
+            let personalizations: Vec<SendGridPersonalization> = chunk
+                .iter()
+                .map(|p| {
+                    batch.to = vec![p.to.clone()];
+                    let mut personalization = self.build_personalization(&batch);
+                    personalization.dynamic_template_data = Some(crate::personalize::merged(
+                        email.provider_options.get("dynamic_template_data"),
+                        &p.data,
+                    ));
+                    personalization
+                })
+                .collect();
+            batch.provider_options.insert(
+                "personalizations".into(),
+                serde_json::to_value(personalizations)?,
+            );
+
+            let result = self.deliver(&batch).await?;
+            results.extend(chunk.iter().map(|p| {
+                DeliveryResult::with_response(
+                    result.message_id.clone(),
+                    serde_json::json!({ "provider": "sendgrid", "recipient": p.to.email }),
+                )
+            }));
+        }
+        Ok(results)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "sendgrid"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: true,
+            supports_scheduling: true,
+            supports_templates: true,
+            ..Capabilities::for_provider("sendgrid")
+        }
+    }
+
+    /// Checks the key with `GET /scopes` and requires the `mail.send` scope.
+    async fn health_check(&self) -> Result<(), MailError> {
+        let response = self.get("/scopes").send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let scopes: SendGridScopes = response.json().await?;
+        if scopes.scopes.iter().any(|s| s == "mail.send") {
+            Ok(())
+        } else {
             Err(MailError::provider_with_status(
                 "sendgrid",
-                error_msg,
+                "API key is missing the mail.send scope",
                 status.as_u16(),
             ))
         }
     }
+}
 
-    fn provider_name(&self) -> &'static str {
-        "sendgrid"
+#[cfg(feature = "insights")]
+impl SendGridMailer {
+    /// Remaining email credits for the plan period (`GET /user/credits`).
+    pub async fn get_send_quota(&self) -> Result<SendQuota, MailError> {
+        let response = self.get("/user/credits").send().await?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let credits: SendGridCredits = response.json().await?;
+        Ok(SendQuota {
+            limit: Some(credits.total),
+            sent: credits.used,
+            max_send_rate: None,
+            resets_at: credits
+                .next_reset
+                .as_deref()
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc()),
+        })
+    }
+
+    /// Daily global statistics from `start_date` (`GET /stats`), oldest first.
+    pub async fn get_send_statistics(
+        &self,
+        start_date: chrono::NaiveDate,
+    ) -> Result<Vec<SendStatistics>, MailError> {
+        let response = self
+            .get("/stats")
+            .query(&[
+                ("start_date", start_date.format("%Y-%m-%d").to_string()),
+                ("aggregated_by", "day".to_string()),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let days: Vec<SendGridStatsDay> = response.json().await?;
+        Ok(days
+            .into_iter()
+            .filter_map(|day| {
+                let start = chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+                    .ok()?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                let mut period = SendStatistics {
+                    start,
+                    attempts: 0,
+                    delivered: Some(0),
+                    bounces: 0,
+                    complaints: 0,
+                    rejects: 0,
+                };
+                for metrics in day.stats.iter().map(|s| &s.metrics) {
+                    period.attempts += metrics.requests;
+                    period.delivered = period.delivered.map(|d| d + metrics.delivered);
+                    period.bounces += metrics.bounces;
+                    period.complaints += metrics.spam_reports;
+                    period.rejects += metrics.blocks;
+                }
+                Some(period)
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "insights")]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Insights for SendGridMailer {
+    async fn send_quota(&self) -> Result<SendQuota, MailError> {
+        self.get_send_quota().await
     }
+
+    async fn send_statistics(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<SendStatistics>, MailError> {
+        self.get_send_statistics(since.date_naive()).await
+    }
+}
+
+/// Split grouped emails into requests within SendGrid's personalization and
+/// recipient limits, keeping each email's personalizations together.
+fn chunk_personalizations(
+    members: Vec<(usize, Vec<SendGridPersonalization>)>,
+) -> Vec<Vec<(usize, Vec<SendGridPersonalization>)>> {
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    let (mut personalizations, mut recipients) = (0, 0);
+    for member in members {
+        let count = member.1.len();
+        let addresses: usize = member
+            .1
+            .iter()
+            .map(SendGridPersonalization::recipients)
+            .sum();
+        if !chunk.is_empty()
+            && (personalizations + count > MAX_PERSONALIZATIONS
+                || recipients + addresses > MAX_RECIPIENTS)
+        {
+            chunks.push(std::mem::take(&mut chunk));
+            (personalizations, recipients) = (0, 0);
+        }
+        personalizations += count;
+        recipients += addresses;
+        chunk.push(member);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Build an error from a failed API response.
+async fn api_error(response: reqwest::Response) -> MailError {
+    let status = response.status();
+    let error: SendGridError = response.json().await.unwrap_or(SendGridError {
+        errors: vec![SendGridErrorDetail {
+            message: "Unknown error".to_string(),
+            field: None,
+            help: None,
+        }],
+    });
+    let error_msg = error
+        .errors
+        .iter()
+        .map(|e| e.message.clone())
+        .collect::<Vec<_>>()
+        .join("; ");
+    MailError::provider_with_status("sendgrid", error_msg, status.as_u16())
 }
 
 // ============================================================================
 // SendGrid API Types
 // ============================================================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct SendGridRequest {
     personalizations: Vec<SendGridPersonalization>,
     from: SendGridAddress,
@@ -430,7 +756,7 @@ struct SendGridRequest {
     ip_pool_name: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SendGridPersonalization {
     to: Vec<SendGridAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -445,21 +771,27 @@ struct SendGridPersonalization {
     substitutions: Option<Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl SendGridPersonalization {
+    fn recipients(&self) -> usize {
+        self.to.len() + self.cc.as_ref().map_or(0, Vec::len) + self.bcc.as_ref().map_or(0, Vec::len)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SendGridAddress {
     email: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct SendGridContent {
     #[serde(rename = "type")]
     content_type: String,
     value: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct SendGridAttachment {
     content: String, // Base64 encoded
     filename: String,
@@ -471,6 +803,43 @@ struct SendGridAttachment {
     content_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct SendGridScopes {
+    scopes: Vec<String>,
+}
+
+#[cfg(feature = "insights")]
+#[derive(Debug, Deserialize)]
+struct SendGridCredits {
+    total: u64,
+    used: u64,
+    next_reset: Option<String>,
+}
+
+#[cfg(feature = "insights")]
+#[derive(Debug, Deserialize)]
+struct SendGridStatsDay {
+    date: String,
+    stats: Vec<SendGridStats>,
+}
+
+#[cfg(feature = "insights")]
+#[derive(Debug, Deserialize)]
+struct SendGridStats {
+    metrics: SendGridMetrics,
+}
+
+#[cfg(feature = "insights")]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SendGridMetrics {
+    requests: u64,
+    delivered: u64,
+    bounces: u64,
+    spam_reports: u64,
+    blocks: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct SendGridError {
     errors: Vec<SendGridErrorDetail>,
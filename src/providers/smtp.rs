@@ -12,26 +12,58 @@
 //!
 //! // Without authentication (local relay)
 //! let mailer = SmtpMailer::localhost();
+//!
+//! // Presets for common providers, still adjustable before `build()`
+//! let mailer = SmtpMailer::gmail("you@gmail.com", "app-password").build();
+//! let mailer = SmtpMailer::ses_smtp("eu-west-1", "AKIA...", "smtp-password").build();
+//!
+//! // From SMTP_HOST, SMTP_PORT, SMTP_TLS, ... with every setting validated
+//! let mailer = SmtpConfig::from_env()?.builder().build();
+//!
+//! // Through a SOCKS5 proxy (`socks` feature)
+//! let mailer = SmtpMailer::new("smtp.example.com", 587)
+//!     .credentials("username", "password")
+//!     .proxy("socks5://proxy.internal:1080")
+//!     .build();
 //! ```
 
+use std::fmt;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use lettre::{
+    address::Envelope,
     message::{
-        header::ContentType, Attachment as LettreAttachment, Mailbox, MultiPart, SinglePart,
+        header::{ContentType, HeaderName, HeaderValue},
+        Attachment as LettreAttachment, Mailbox, MultiPart, SinglePart,
+    },
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Tls, TlsParameters},
+        response::Response,
     },
-    transport::smtp::authentication::Credentials,
     AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
 };
 
-use crate::address::Address;
+use crate::address::{Address, ToAddress};
 use crate::attachment::AttachmentType;
 use crate::email::Email;
 use crate::error::MailError;
-use crate::mailer::{DeliveryResult, Mailer};
+use crate::mailer::{DeliveryResult, Mailer, DEFAULT_BATCH_CONCURRENCY};
+
+/// Default SMTP command timeout (lettre's).
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// lettre's error when the envelope needs SMTPUTF8 but the server lacks it.
+const SMTPUTF8_UNSUPPORTED: &str = "server does not support SMTPUTF8";
 
 /// SMTP email provider.
 pub struct SmtpMailer {
     transport: AsyncSmtpTransport<Tokio1Executor>,
+    concurrency: usize,
+    envelope_from: Option<Address>,
+    #[cfg(feature = "socks")]
+    proxy: Option<Result<super::smtp_socks::SocksRelay, MailError>>,
 }
 
 impl SmtpMailer {
@@ -42,6 +74,12 @@ impl SmtpMailer {
             port,
             credentials: None,
             tls: TlsMode::StartTls,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            envelope_from: None,
+            accept_invalid_certs: false,
+            timeout: Some(DEFAULT_TIMEOUT),
+            #[cfg(feature = "socks")]
+            proxy: None,
         }
     }
 
@@ -51,11 +89,82 @@ impl SmtpMailer {
             .port(25)
             .build();
 
-        Self { transport }
+        Self {
+            transport,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            envelope_from: None,
+            #[cfg(feature = "socks")]
+            proxy: None,
+        }
+    }
+
+    /// Gmail or Google Workspace (`smtp.gmail.com:587`, STARTTLS).
+    ///
+    /// Google only accepts an [app password](https://myaccount.google.com/apppasswords)
+    /// here, not the account password.
+    pub fn gmail(user: &str, app_password: &str) -> SmtpBuilder {
+        Self::new("smtp.gmail.com", 587).credentials(user, app_password)
+    }
+
+    /// Microsoft 365 / Outlook (`smtp.office365.com:587`, STARTTLS).
+    ///
+    /// SMTP AUTH must be enabled for the mailbox in the Microsoft 365 admin center.
+    pub fn office365(user: &str, password: &str) -> SmtpBuilder {
+        Self::new("smtp.office365.com", 587).credentials(user, password)
+    }
+
+    /// Amazon SES SMTP interface in `region` (`email-smtp.<region>.amazonaws.com:587`,
+    /// STARTTLS).
+    ///
+    /// Takes the SMTP credentials created in the SES console, which are not
+    /// the IAM access key and secret.
+    pub fn ses_smtp(region: &str, username: &str, password: &str) -> SmtpBuilder {
+        Self::new(&format!("email-smtp.{}.amazonaws.com", region), 587)
+            .credentials(username, password)
+    }
+
+    /// Fastmail (`smtp.fastmail.com:465`, implicit TLS), with an app password.
+    pub fn fastmail(user: &str, app_password: &str) -> SmtpBuilder {
+        Self::new("smtp.fastmail.com", 465)
+            .credentials(user, app_password)
+            .tls(TlsMode::Tls)
+    }
+
+    /// Send every message with this envelope sender (`MAIL FROM`) instead
+    /// of the From address, so bounces go to it.
+    ///
+    /// [`Email::return_path`] overrides it per email.
+    pub fn envelope_from(mut self, addr: impl ToAddress) -> Self {
+        self.envelope_from = Some(addr.to_address());
+        self
+    }
+
+    /// Send a built message over the configured transport, with the email's
+    /// pre-formatted headers prepended as they are (lettre would replace
+    /// repeated names such as `ARC-Seal` and re-encode the values).
+    async fn send(&self, email: &Email, message: Message) -> Result<Response, MailError> {
+        let mut raw = crate::header::raw_block(&email.raw_headers).into_bytes();
+        raw.extend_from_slice(&message.formatted());
+        let envelope = message.envelope();
+
+        #[cfg(feature = "socks")]
+        match &self.proxy {
+            Some(Ok(relay)) => return relay.send(envelope, &raw).await,
+            Some(Err(e)) => return Err(e.clone()),
+            None => {}
+        }
+        self.transport
+            .send_raw(envelope, &raw)
+            .await
+            .map_err(|e| MailError::SendError(e.to_string()))
     }
 
     /// Build a lettre Message from our Email struct.
-    fn build_message(&self, email: &Email) -> Result<Message, MailError> {
+    ///
+    /// With `ascii`, domains are Punycode-encoded for servers without SMTPUTF8.
+    fn build_message(&self, email: &Email, ascii: bool) -> Result<Message, MailError> {
+        email.validate_headers()?;
+
         let from = email
             .from
             .as_ref()
@@ -66,29 +175,46 @@ impl SmtpMailer {
         }
 
         let mut builder = Message::builder()
-            .from(address_to_mailbox(from)?)
+            .from(address_to_mailbox(from, ascii)?)
             .subject(&email.subject);
 
         // Add recipients
         for to in &email.to {
-            builder = builder.to(address_to_mailbox(to)?);
+            builder = builder.to(address_to_mailbox(to, ascii)?);
         }
         for cc in &email.cc {
-            builder = builder.cc(address_to_mailbox(cc)?);
+            builder = builder.cc(address_to_mailbox(cc, ascii)?);
         }
         for bcc in &email.bcc {
-            builder = builder.bcc(address_to_mailbox(bcc)?);
+            builder = builder.bcc(address_to_mailbox(bcc, ascii)?);
         }
 
         // Reply-to (supports multiple, use first one for SMTP)
         if let Some(reply_to) = email.reply_to.first() {
-            builder = builder.reply_to(address_to_mailbox(reply_to)?);
+            builder = builder.reply_to(address_to_mailbox(reply_to, ascii)?);
         }
 
-        // Custom headers - note: lettre requires implementing Header trait for custom headers.
-        // For now, we skip custom headers in SMTP. Use provider_options for SMTP-specific headers.
-        // TODO: Add support for common custom headers (X-Priority, X-Mailer, etc.)
-        let _ = &email.headers; // Acknowledge but don't use
+        // Envelope sender for bounces; recipients still come from the headers
+        if let Some(sender) = email.return_path.as_ref().or(self.envelope_from.as_ref()) {
+            let recipients = email
+                .to
+                .iter()
+                .chain(&email.cc)
+                .chain(&email.bcc)
+                .map(|a| address_to_mailbox(a, ascii).map(|m| m.email))
+                .collect::<Result<Vec<_>, _>>()?;
+            let sender = address_to_mailbox(sender, ascii)?.email;
+            let envelope = Envelope::new(Some(sender), recipients)
+                .map_err(|e| MailError::BuildError(e.to_string()))?;
+            builder = builder.envelope(envelope);
+        }
+
+        // Custom headers (X-Priority, List-Unsubscribe, ...)
+        for (name, value) in &email.headers {
+            let name = HeaderName::new_from_ascii(name.clone())
+                .map_err(|_| MailError::BuildError(format!("Invalid header name: {}", name)))?;
+            builder = builder.raw_header(HeaderValue::new(name, value.clone()));
+        }
 
         // Build body
         let message = if email.attachments.is_empty() {
@@ -162,13 +288,16 @@ impl SmtpMailer {
 #[async_trait]
 impl Mailer for SmtpMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
-        let message = self.build_message(email)?;
+        let message = self.build_message(email, false)?;
 
-        let response = self
-            .transport
-            .send(message)
-            .await
-            .map_err(|e| MailError::SendError(e.to_string()))?;
+        // lettre refuses non-ASCII envelopes before MAIL FROM when the server
+        // lacks SMTPUTF8, so nothing has been sent yet and we can retry in ASCII.
+        let response = match self.send(email, message).await {
+            Err(MailError::SendError(e)) if e.contains(SMTPUTF8_UNSUPPORTED) => {
+                self.send(email, self.build_message(email, true)?).await?
+            }
+            result => result?,
+        };
 
         // Extract message ID from SMTP response, or generate one
         let message_id = response
@@ -181,13 +310,46 @@ impl Mailer for SmtpMailer {
         Ok(DeliveryResult::new(message_id))
     }
 
+    fn batch_concurrency(&self) -> usize {
+        self.concurrency
+    }
+
     fn provider_name(&self) -> &'static str {
         "smtp"
     }
+
+    /// Connects, authenticates and sends NOOP.
+    async fn health_check(&self) -> Result<(), MailError> {
+        #[cfg(feature = "socks")]
+        match &self.proxy {
+            Some(Ok(relay)) => return relay.test_connection().await,
+            Some(Err(e)) => return Err(e.clone()),
+            None => {}
+        }
+
+        let connected = self
+            .transport
+            .test_connection()
+            .await
+            .map_err(|e| MailError::SendError(e.to_string()))?;
+        if connected {
+            Ok(())
+        } else {
+            Err(MailError::SendError("SMTP server did not answer NOOP".into()))
+        }
+    }
+
+    #[cfg(feature = "socks")]
+    fn validate_config(&self) -> Result<(), MailError> {
+        match &self.proxy {
+            Some(Err(e)) => Err(e.clone()),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// TLS mode for SMTP connection.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TlsMode {
     /// No TLS (dangerous, only for localhost)
     None,
@@ -197,12 +359,172 @@ pub enum TlsMode {
     Tls,
 }
 
+/// SMTP settings, validated from the environment.
+///
+/// | Variable | Description |
+/// |----------|-------------|
+/// | `SMTP_HOST` | Server host (required) |
+/// | `SMTP_PORT` | Server port (default: 465 with `SMTP_TLS=tls`, else 587) |
+/// | `SMTP_USERNAME` | Username, set together with `SMTP_PASSWORD` |
+/// | `SMTP_PASSWORD` | Password |
+/// | `SMTP_TLS` | `starttls` (default, or `required`), `tls` (implicit, port 465) or `none` |
+/// | `SMTP_TIMEOUT` | Command timeout in seconds (default: 60, `0` disables) |
+/// | `SMTP_POOL_SIZE` | Maximum simultaneous connections in `deliver_many` (default: 8) |
+#[derive(Clone)]
+pub struct SmtpConfig {
+    /// Server host.
+    pub host: String,
+    /// Server port.
+    pub port: u16,
+    /// Username and password, if the server requires authentication.
+    pub credentials: Option<(String, String)>,
+    /// How the connection is secured.
+    pub tls: TlsMode,
+    /// Command timeout, or `None` to wait indefinitely.
+    pub timeout: Option<Duration>,
+    /// Maximum simultaneous connections in `deliver_many`; `None` uses the
+    /// default (8). Each message is sent on its own connection.
+    pub pool_size: Option<usize>,
+}
+
+impl SmtpConfig {
+    /// Read and validate the `SMTP_*` variables.
+    ///
+    /// Every problem is reported in one [`MailError::Configuration`], so a
+    /// misconfigured environment can be fixed in one pass.
+    pub fn from_env() -> Result<Self, MailError> {
+        Self::from_vars(crate::config::var)
+    }
+
+    /// [`from_env`](Self::from_env), reading variables with `var`.
+    pub(crate) fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, MailError> {
+        let mut problems = Vec::new();
+
+        let host = var("SMTP_HOST").map(|h| h.trim().to_string()).unwrap_or_default();
+        if host.is_empty() {
+            problems.push("SMTP_HOST not set".to_string());
+        }
+
+        let tls = match var("SMTP_TLS").map(|t| t.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("starttls" | "required") => TlsMode::StartTls,
+            Some("tls") => TlsMode::Tls,
+            Some("none") => TlsMode::None,
+            Some(other) => {
+                problems.push(format!(
+                    "SMTP_TLS must be starttls, tls or none, not '{}'",
+                    other
+                ));
+                TlsMode::StartTls
+            }
+        };
+
+        let default_port = if tls == TlsMode::Tls { 465 } else { 587 };
+        let port = match var("SMTP_PORT") {
+            None => default_port,
+            Some(port) => match port.trim().parse::<u16>() {
+                Ok(port) if port > 0 => port,
+                _ => {
+                    problems.push(format!("SMTP_PORT must be 1-65535, not '{}'", port));
+                    default_port
+                }
+            },
+        };
+
+        let username = var("SMTP_USERNAME").filter(|u| !u.is_empty());
+        let password = var("SMTP_PASSWORD").filter(|p| !p.is_empty());
+        let credentials = match (username, password) {
+            (Some(username), Some(password)) => Some((username, password)),
+            (None, None) => None,
+            (Some(_), None) => {
+                problems.push("SMTP_USERNAME is set but SMTP_PASSWORD is not".to_string());
+                None
+            }
+            (None, Some(_)) => {
+                problems.push("SMTP_PASSWORD is set but SMTP_USERNAME is not".to_string());
+                None
+            }
+        };
+
+        let timeout = match var("SMTP_TIMEOUT") {
+            None => Some(DEFAULT_TIMEOUT),
+            Some(secs) => match secs.trim().parse::<u64>() {
+                Ok(secs) => (secs > 0).then(|| Duration::from_secs(secs)),
+                Err(_) => {
+                    problems.push(format!("SMTP_TIMEOUT must be whole seconds, not '{}'", secs));
+                    Some(DEFAULT_TIMEOUT)
+                }
+            },
+        };
+
+        let pool_size = match var("SMTP_POOL_SIZE") {
+            None => None,
+            Some(size) => match size.trim().parse::<usize>() {
+                Ok(size) if size > 0 => Some(size),
+                _ => {
+                    problems.push(format!(
+                        "SMTP_POOL_SIZE must be a positive number, not '{}'",
+                        size
+                    ));
+                    None
+                }
+            },
+        };
+
+        if !problems.is_empty() {
+            return Err(MailError::Configuration(format!(
+                "Invalid SMTP settings: {}",
+                problems.join("; ")
+            )));
+        }
+        Ok(Self {
+            host,
+            port,
+            credentials,
+            tls,
+            timeout,
+            pool_size,
+        })
+    }
+
+    /// A mailer builder with these settings, for further adjustment.
+    pub fn builder(&self) -> SmtpBuilder {
+        let mut builder = SmtpMailer::new(&self.host, self.port).tls(self.tls);
+        if let Some((username, password)) = &self.credentials {
+            builder = builder.credentials(username, password);
+        }
+        builder = builder.timeout(self.timeout);
+        if let Some(size) = self.pool_size {
+            builder = builder.concurrency(size);
+        }
+        builder
+    }
+}
+
+impl fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.credentials.as_ref().map(|(u, _)| u))
+            .field("tls", &self.tls)
+            .field("timeout", &self.timeout)
+            .field("pool_size", &self.pool_size)
+            .finish()
+    }
+}
+
 /// Builder for SmtpMailer.
 pub struct SmtpBuilder {
     host: String,
     port: u16,
     credentials: Option<Credentials>,
     tls: TlsMode,
+    concurrency: usize,
+    envelope_from: Option<Address>,
+    accept_invalid_certs: bool,
+    timeout: Option<Duration>,
+    #[cfg(feature = "socks")]
+    proxy: Option<String>,
 }
 
 impl SmtpBuilder {
@@ -218,57 +540,204 @@ impl SmtpBuilder {
         self
     }
 
+    /// Send up to `concurrency` emails at once in `deliver_many()` (default: 8).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set the envelope sender (see [`SmtpMailer::envelope_from`]).
+    pub fn envelope_from(mut self, addr: impl ToAddress) -> Self {
+        self.envelope_from = Some(addr.to_address());
+        self
+    }
+
+    /// Give up on an SMTP command after this long (default: 60 seconds);
+    /// `None` waits indefinitely.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     /// Disable TLS (dangerous, only for localhost/testing).
     pub fn no_tls(mut self) -> Self {
         self.tls = TlsMode::None;
         self
     }
 
+    /// Accept any TLS certificate, including self-signed ones (dangerous,
+    /// only for local bridges such as Proton Mail Bridge).
+    pub fn dangerous_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// TLS parameters for the host, honoring `dangerous_accept_invalid_certs`.
+    fn tls_parameters(&self) -> Result<TlsParameters, lettre::transport::smtp::Error> {
+        TlsParameters::builder(self.host.clone())
+            .dangerous_accept_invalid_certs(self.accept_invalid_certs)
+            .build()
+    }
+
+    /// Connect through a SOCKS5 proxy (`socks5://[user:pass@]host[:port]`).
+    ///
+    /// Each message uses its own connection. Only STARTTLS and plain
+    /// connections are supported through a proxy, not implicit TLS.
+    #[cfg(feature = "socks")]
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self
+    }
+
     /// Build the SmtpMailer.
     pub fn build(self) -> SmtpMailer {
-        let transport = match self.tls {
-            TlsMode::None => {
-                let mut t = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
-                    .port(self.port);
-                if let Some(creds) = self.credentials {
-                    t = t.credentials(creds);
+        #[cfg(feature = "socks")]
+        let proxy = self.proxy.as_deref().map(|url| {
+            super::smtp_socks::SocksProxy::parse(url).map(|proxy| {
+                super::smtp_socks::SocksRelay {
+                    proxy,
+                    host: self.host.clone(),
+                    port: self.port,
+                    tls: self.tls,
+                    credentials: self.credentials.clone(),
+                    accept_invalid_certs: self.accept_invalid_certs,
                 }
-                t.build()
-            }
+            })
+        });
+
+        let mut t = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+            .port(self.port)
+            .timeout(self.timeout);
+        match self.tls {
+            TlsMode::None => {}
             TlsMode::StartTls => {
-                let mut t = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)
-                    .unwrap_or_else(|_| {
-                        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
-                    })
-                    .port(self.port);
-                if let Some(creds) = self.credentials {
-                    t = t.credentials(creds);
+                if let Ok(params) = self.tls_parameters() {
+                    t = t.tls(Tls::Required(params));
                 }
-                t.build()
             }
             TlsMode::Tls => {
-                let mut t = AsyncSmtpTransport::<Tokio1Executor>::relay(&self.host)
-                    .unwrap_or_else(|_| {
-                        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
-                    })
-                    .port(self.port);
-                if let Some(creds) = self.credentials {
-                    t = t.credentials(creds);
+                if let Ok(params) = self.tls_parameters() {
+                    t = t.tls(Tls::Wrapper(params));
                 }
-                t.build()
             }
-        };
-
-        SmtpMailer { transport }
+        }
+        if let Some(creds) = self.credentials {
+            t = t.credentials(creds);
+        }
+        let transport = t.build();
+
+        SmtpMailer {
+            transport,
+            concurrency: self.concurrency,
+            envelope_from: self.envelope_from,
+            #[cfg(feature = "socks")]
+            proxy,
+        }
     }
 }
 
 /// Convert our Address to lettre's Mailbox.
-fn address_to_mailbox(addr: &Address) -> Result<Mailbox, MailError> {
-    let email = addr
-        .email
+///
+/// With `ascii`, the domain is converted to Punycode. A non-ASCII local part
+/// has no ASCII form, so it is rejected.
+fn address_to_mailbox(addr: &Address, ascii: bool) -> Result<Mailbox, MailError> {
+    let email = if ascii {
+        let email = addr.to_ascii()?;
+        if !email.is_ascii() {
+            return Err(MailError::InvalidAddress(format!(
+                "'{}' has a non-ASCII local part, but the SMTP server does not support SMTPUTF8",
+                addr.email
+            )));
+        }
+        email
+    } else {
+        addr.email.clone()
+    };
+    let email = email
         .parse()
         .map_err(|e: lettre::address::AddressError| MailError::InvalidAddress(e.to_string()))?;
 
     Ok(Mailbox::new(addr.name.clone(), email))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let gmail = SmtpMailer::gmail("you@gmail.com", "app-password");
+        assert_eq!((gmail.host.as_str(), gmail.port), ("smtp.gmail.com", 587));
+        assert!(matches!(gmail.tls, TlsMode::StartTls));
+        assert!(gmail.credentials.is_some());
+
+        let ses = SmtpMailer::ses_smtp("eu-west-1", "AKIA", "secret");
+        assert_eq!(ses.host, "email-smtp.eu-west-1.amazonaws.com");
+
+        let fastmail = SmtpMailer::fastmail("you@fastmail.com", "app-password");
+        assert_eq!(fastmail.port, 465);
+        assert!(matches!(fastmail.tls, TlsMode::Tls));
+    }
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<SmtpConfig, MailError> {
+        SmtpConfig::from_vars(|key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn test_config_from_vars() {
+        let config = from_vars(&[("SMTP_HOST", "smtp.example.com")]).unwrap();
+        assert_eq!(config.port, 587);
+        assert_eq!(config.tls, TlsMode::StartTls);
+        assert_eq!(config.timeout, Some(Duration::from_secs(60)));
+        assert!(config.credentials.is_none());
+
+        let config = from_vars(&[
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_TLS", "TLS"),
+            ("SMTP_USERNAME", "user"),
+            ("SMTP_PASSWORD", "secret"),
+            ("SMTP_TIMEOUT", "0"),
+            ("SMTP_POOL_SIZE", "4"),
+        ])
+        .unwrap();
+        assert_eq!(config.port, 465);
+        assert_eq!(config.tls, TlsMode::Tls);
+        assert_eq!(config.timeout, None);
+        assert_eq!(config.pool_size, Some(4));
+        assert!(!format!("{:?}", config).contains("secret"));
+
+        let builder = config.builder();
+        assert_eq!(builder.port, 465);
+        assert_eq!(builder.concurrency, 4);
+        assert!(builder.credentials.is_some());
+    }
+
+    #[test]
+    fn test_config_reports_every_problem() {
+        let err = from_vars(&[
+            ("SMTP_PORT", "587x"),
+            ("SMTP_TLS", "ssl"),
+            ("SMTP_PASSWORD", "secret"),
+            ("SMTP_TIMEOUT", "1.5"),
+            ("SMTP_POOL_SIZE", "0"),
+        ])
+        .unwrap_err();
+        let MailError::Configuration(message) = err else {
+            panic!("expected a configuration error, got {:?}", err);
+        };
+        for problem in [
+            "SMTP_HOST not set",
+            "SMTP_PORT must be 1-65535, not '587x'",
+            "SMTP_TLS must be starttls, tls or none, not 'ssl'",
+            "SMTP_PASSWORD is set but SMTP_USERNAME is not",
+            "SMTP_TIMEOUT must be whole seconds, not '1.5'",
+            "SMTP_POOL_SIZE must be a positive number, not '0'",
+        ] {
+            assert!(message.contains(problem), "{} missing from {}", problem, message);
+        }
+    }
+}
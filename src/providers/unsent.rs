@@ -30,7 +30,7 @@ impl UnsentMailer {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
-            client: Client::new(),
+            client: crate::http::default_client(),
             base_url: UNSENT_API_URL.to_string(),
         }
     }
@@ -50,7 +50,15 @@ impl UnsentMailer {
         self
     }
 
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
     fn build_request(&self, email: &Email) -> Result<UnsentRequest, MailError> {
+        email.validate_headers()?;
+
         let from = email
             .from
             .as_ref()
@@ -81,7 +89,8 @@ impl UnsentMailer {
     }
 }
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Mailer for UnsentMailer {
     async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
         let request = self.build_request(email)?;
@@ -93,11 +102,13 @@ impl Mailer for UnsentMailer {
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
             .json(&request)
             .send()
             .await?;
 
         let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
 
         if status.is_success() {
             let result: UnsentResponse = response.json().await?;
@@ -107,11 +118,10 @@ impl Mailer for UnsentMailer {
             ))
         } else {
             let error_text = response.text().await.unwrap_or_default();
-            Err(MailError::provider_with_status(
-                "unsent",
-                error_text,
-                status.as_u16(),
-            ))
+            Err(
+                MailError::provider_with_status("unsent", error_text, status.as_u16())
+                    .with_retry_after(retry_after),
+            )
         }
     }
 
@@ -0,0 +1,404 @@
+//! ZeptoMail (Zoho) API provider.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use missive::providers::ZeptoMailer;
+//!
+//! // The "Send Mail token" from Mail Agents → SMTP/API, with or without
+//! // its `Zoho-enczapikey` prefix
+//! let mailer = ZeptoMailer::new("wSsVR61...");
+//!
+//! // Accounts in another data center use that region's API host
+//! let mailer = ZeptoMailer::new(token).base_url("https://api.zeptomail.eu/v1.1");
+//! ```
+//!
+//! ## Provider Options
+//!
+//! ```rust,ignore
+//! let email = Email::new()
+//!     .from("sender@example.com")
+//!     .to("recipient@example.com")
+//!     .subject("Hello")
+//!     .provider_option("track_opens", true)
+//!     .provider_option("track_clicks", false)
+//!     .provider_option("client_reference", "order-1234");
+//! ```
+//!
+//! [`Email::return_path`](crate::Email::return_path) is sent as
+//! `bounce_address`, which must be on a bounce domain set up in ZeptoMail.
+//!
+//! ## Template Support
+//!
+//! Emails with a `template_key` or `template_alias` go to the template
+//! endpoint, which takes the subject and body from the template:
+//!
+//! ```rust,ignore
+//! let email = Email::new()
+//!     .from("sender@example.com")
+//!     .to("recipient@example.com")
+//!     .provider_option("template_key", "2d6f.6b5b2ca0.k1.a7ee3f70...")
+//!     .provider_option("merge_info", json!({ "name": "John" }));
+//! ```
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::address::Address;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{Capabilities, DeliveryResult, Mailer};
+
+const ZEPTOMAIL_API_URL: &str = "https://api.zeptomail.com/v1.1";
+
+/// Scheme ZeptoMail expects in the `Authorization` header.
+const AUTH_SCHEME: &str = "Zoho-enczapikey";
+
+/// ZeptoMail API email provider.
+pub struct ZeptoMailer {
+    token: String,
+    client: Client,
+    base_url: String,
+}
+
+impl ZeptoMailer {
+    /// Create a new ZeptoMail mailer with the given Send Mail token.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self::with_client(token, crate::http::default_client())
+    }
+
+    /// Create with a custom reqwest client.
+    pub fn with_client(token: impl Into<String>, client: Client) -> Self {
+        let token = token.into();
+        // The dashboard copies the token with its scheme
+        let token = token
+            .trim()
+            .strip_prefix(AUTH_SCHEME)
+            .unwrap_or(token.trim())
+            .trim()
+            .to_string();
+        Self {
+            token,
+            client,
+            base_url: ZEPTOMAIL_API_URL.to_string(),
+        }
+    }
+
+    /// Set a custom base URL (another data center, or testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
+    /// Check if this email uses a template.
+    fn is_template_email(email: &Email) -> bool {
+        email.provider_options.contains_key("template_key")
+            || email.provider_options.contains_key("template_alias")
+    }
+
+    fn build_request(&self, email: &Email) -> Result<ZeptoRequest, MailError> {
+        email.validate_headers()?;
+
+        let from = email.from.as_ref().ok_or(MailError::MissingField("from"))?;
+
+        if email.to.is_empty() {
+            return Err(MailError::MissingField("to"));
+        }
+
+        let recipients = |addresses: &[Address]| -> Option<Vec<ZeptoRecipient>> {
+            if addresses.is_empty() {
+                None
+            } else {
+                Some(
+                    addresses
+                        .iter()
+                        .map(|a| ZeptoRecipient {
+                            email_address: ZeptoAddress::from(a),
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        let template = Self::is_template_email(email);
+        let option_str = |key: &str| {
+            email
+                .provider_options
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        };
+        let option_bool = |key: &str| email.provider_options.get(key).and_then(|v| v.as_bool());
+
+        let mut request = ZeptoRequest {
+            from: ZeptoAddress::from(from),
+            to: recipients(&email.to).unwrap_or_default(),
+            cc: recipients(&email.cc),
+            bcc: recipients(&email.bcc),
+            reply_to: if email.reply_to.is_empty() {
+                None
+            } else {
+                Some(email.reply_to.iter().map(ZeptoAddress::from).collect())
+            },
+            subject: if template || email.subject.is_empty() {
+                None
+            } else {
+                Some(email.subject.clone())
+            },
+            htmlbody: if template {
+                None
+            } else {
+                email.html_body.clone()
+            },
+            textbody: if template {
+                None
+            } else {
+                email.text_body.clone()
+            },
+            bounce_address: email.return_path.as_ref().map(|a| a.email.clone()),
+            track_clicks: option_bool("track_clicks"),
+            track_opens: option_bool("track_opens"),
+            client_reference: option_str("client_reference"),
+            mime_headers: if email.headers.is_empty() {
+                None
+            } else {
+                Some(email.headers.clone())
+            },
+            attachments: None,
+            inline_images: None,
+            template_key: option_str("template_key"),
+            template_alias: option_str("template_alias"),
+            merge_info: email.provider_options.get("merge_info").cloned(),
+        };
+
+        // Inline images are referenced from the HTML by cid
+        let (inline, attached): (Vec<_>, Vec<_>) =
+            email.attachments.iter().partition(|a| a.is_inline());
+        if !attached.is_empty() {
+            request.attachments = Some(
+                attached
+                    .into_iter()
+                    .map(|a| ZeptoAttachment {
+                        name: a.filename.clone(),
+                        content: a.base64_data(),
+                        mime_type: a.content_type.clone(),
+                        cid: None,
+                    })
+                    .collect(),
+            );
+        }
+        if !inline.is_empty() {
+            request.inline_images = Some(
+                inline
+                    .into_iter()
+                    .map(|a| ZeptoAttachment {
+                        name: a.filename.clone(),
+                        content: a.base64_data(),
+                        mime_type: a.content_type.clone(),
+                        cid: Some(a.content_id.clone().unwrap_or_else(|| a.filename.clone())),
+                    })
+                    .collect(),
+            );
+        }
+
+        Ok(request)
+    }
+
+    fn parse_error(
+        status: reqwest::StatusCode,
+        body: &str,
+        retry_after: Option<std::time::Duration>,
+    ) -> MailError {
+        let Ok(ZeptoErrorResponse { error }) = serde_json::from_str(body) else {
+            return MailError::provider_with_status("zeptomail", body, status.as_u16())
+                .with_retry_after(retry_after);
+        };
+
+        // Details name the offending field, e.g. `to: Invalid email address`
+        let mut message = error.message;
+        for detail in &error.details {
+            if let Some(target) = &detail.target {
+                message.push_str(&format!("; {}: {}", target, detail.message));
+            }
+        }
+        MailError::provider_with_status(
+            "zeptomail",
+            format!("[{}] {}", error.code, message),
+            status.as_u16(),
+        )
+        .with_code(error.code)
+        .with_retry_after(retry_after)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Mailer for ZeptoMailer {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let request = self.build_request(email)?;
+
+        let url = if Self::is_template_email(email) {
+            format!("{}/email/template", self.base_url)
+        } else {
+            format!("{}/email", self.base_url)
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("{} {}", AUTH_SCHEME, self.token))
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .header("User-Agent", format!("missive/{}", crate::VERSION))
+            .headers(crate::telemetry::trace_headers())
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let retry_after = crate::error::retry_after(response.headers());
+
+        if status.is_success() {
+            let result: ZeptoResponse = response.json().await?;
+            let code = result.data.first().map(|d| d.code.clone());
+            Ok(DeliveryResult::with_response(
+                result.request_id,
+                serde_json::json!({
+                    "provider": "zeptomail",
+                    "code": code,
+                    "message": result.message,
+                }),
+            ))
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(Self::parse_error(status, &body, retry_after))
+        }
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "zeptomail"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_templates: true,
+            ..Capabilities::for_provider("zeptomail")
+        }
+    }
+}
+
+// ============================================================================
+// ZeptoMail API Types
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct ZeptoRequest {
+    from: ZeptoAddress,
+    to: Vec<ZeptoRecipient>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cc: Option<Vec<ZeptoRecipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bcc: Option<Vec<ZeptoRecipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<Vec<ZeptoAddress>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    htmlbody: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    textbody: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bounce_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_clicks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_opens: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<Vec<ZeptoAttachment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inline_images: Option<Vec<ZeptoAttachment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template_alias: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_info: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct ZeptoAddress {
+    address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl From<&Address> for ZeptoAddress {
+    fn from(addr: &Address) -> Self {
+        Self {
+            address: addr.email.clone(),
+            name: addr.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ZeptoRecipient {
+    email_address: ZeptoAddress,
+}
+
+#[derive(Debug, Serialize)]
+struct ZeptoAttachment {
+    name: String,
+    content: String, // Base64 encoded
+    mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeptoResponse {
+    #[serde(default)]
+    data: Vec<ZeptoResponseData>,
+    #[serde(default)]
+    message: String,
+    request_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeptoResponseData {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeptoErrorResponse {
+    error: ZeptoError,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeptoError {
+    code: String,
+    message: String,
+    #[serde(default)]
+    details: Vec<ZeptoErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZeptoErrorDetail {
+    message: String,
+    #[serde(default)]
+    target: Option<String>,
+}
@@ -0,0 +1,135 @@
+//! Outbound rate limiting.
+//!
+//! [`RateLimited`] wraps any mailer and spaces deliveries so that no more than
+//! `per_second` emails are handed to the provider each second. Callers that
+//! exceed the limit wait their turn instead of failing.
+//!
+//! ```rust,ignore
+//! use missive::RateLimited;
+//! use missive::providers::ResendMailer;
+//!
+//! let mailer = RateLimited::new(ResendMailer::new(api_key), 10);
+//! ```
+//!
+//! The global mailer is wrapped automatically when `EMAIL_RATE_LIMIT` is set.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use tokio::time::Instant;
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
+
+/// A mailer wrapper that limits deliveries per second.
+pub struct RateLimited<M> {
+    inner: M,
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl<M: Mailer> RateLimited<M> {
+    /// Wrap a mailer, allowing at most `per_second` emails per second.
+    ///
+    /// A limit of `0` is treated as `1`.
+    pub fn new(inner: M, per_second: u32) -> Self {
+        Self {
+            inner,
+            interval: Duration::from_secs(1) / per_second.max(1),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Get a reference to the wrapped mailer.
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Reserve `count` consecutive send slots, returning when the first one starts.
+    fn reserve(&self, count: u32) -> Instant {
+        let mut next = self.next_slot.lock();
+        let start = (*next).max(Instant::now());
+        *next = start + self.interval * count;
+        start
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Mailer> Mailer for RateLimited<M> {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let slot = self.reserve(1);
+        tokio::time::sleep_until(slot).await;
+        self.inner.deliver(email).await
+    }
+
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        self.inner.validate_batch(emails)?;
+
+        let start = self.reserve(emails.len() as u32);
+        let mut results = Vec::with_capacity(emails.len());
+        for (i, email) in emails.iter().enumerate() {
+            tokio::time::sleep_until(start + self.interval * i as u32).await;
+            results.push(self.inner.deliver(email).await?);
+        }
+        Ok(results)
+    }
+
+    fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        self.inner.validate_batch(emails)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    /// Batches are sent one email at a time to keep the pace.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_batch: false,
+            ..self.inner.capabilities()
+        }
+    }
+
+    fn validate_config(&self) -> Result<(), MailError> {
+        self.inner.validate_config()
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.inner.health_check().await
+    }
+
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        self.inner.message_status(message_id).await
+    }
+}
+
+#[cfg(all(test, feature = "local"))]
+mod tests {
+    use super::*;
+    use crate::providers::LocalMailer;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spaces_deliveries() {
+        let mailer = RateLimited::new(LocalMailer::new(), 2);
+        let email = Email::new()
+            .from("sender@example.com")
+            .to("user@example.com");
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            mailer.deliver(&email).await.unwrap();
+        }
+
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(mailer.inner().emails().len(), 3);
+    }
+
+    #[test]
+    fn test_zero_limit_is_clamped() {
+        let mailer = RateLimited::new(LocalMailer::new(), 0);
+        assert_eq!(mailer.interval, Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,239 @@
+//! Redis-backed [`Storage`] for mailboxes shared between processes.
+//!
+//! [`MemoryStorage`](crate::MemoryStorage) lives in one process. In a shared
+//! dev or staging environment with several app instances, point them all at
+//! one Redis and every captured email shows up in each preview UI:
+//!
+//! ```rust,ignore
+//! use axum::Router;
+//! use missive::RedisStorage;
+//! use missive::smtp_capture::SmtpCaptureServer;
+//!
+//! let storage = RedisStorage::connect("redis://127.0.0.1/")?.prefix("staging").shared();
+//! SmtpCaptureServer::new("127.0.0.1:1025", storage.clone())?.spawn();
+//! let app = Router::new().nest("/dev/mailbox", missive::preview::mailbox_router(storage));
+//! ```
+//!
+//! Emails are kept as JSON in a hash (`<prefix>:emails`, ID to email) with
+//! their order in a list (`<prefix>:order`, newest first). Storage methods
+//! are synchronous and can't fail, so Redis errors are logged and treated as
+//! an empty mailbox.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use redis::{Commands, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::storage::{Storage, StoredEmail};
+
+/// Key prefix unless [`RedisStorage::prefix`] is set.
+const DEFAULT_PREFIX: &str = "missive";
+
+/// Overwrites an email's record only if it wasn't deleted in the meantime.
+const MARK_READ_SCRIPT: &str = "if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 1 then \
+    redis.call('HSET', KEYS[1], ARGV[1], ARGV[2]) return 1 end return 0";
+
+/// Captured emails stored in Redis.
+pub struct RedisStorage {
+    connection: Mutex<Connection>,
+    prefix: String,
+}
+
+/// A [`StoredEmail`] as saved in Redis.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    id: String,
+    email: Email,
+    sent_at: DateTime<Utc>,
+    #[serde(default)]
+    read: bool,
+}
+
+impl From<Record> for StoredEmail {
+    fn from(record: Record) -> Self {
+        Self {
+            id: record.id,
+            email: record.email,
+            sent_at: record.sent_at,
+            read: record.read,
+        }
+    }
+}
+
+impl RedisStorage {
+    /// Connect to Redis at `url` (e.g. `redis://127.0.0.1/`).
+    pub fn connect(url: &str) -> Result<Self, MailError> {
+        let connection = redis::Client::open(url)
+            .and_then(|client| client.get_connection())
+            .map_err(|e| MailError::Configuration(format!("Redis: {}", e)))?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+            prefix: DEFAULT_PREFIX.to_string(),
+        })
+    }
+
+    /// Namespace the keys (default: `missive`), e.g. per environment.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Wrap in an `Arc` to share with the preview UI and
+    /// [`SmtpCaptureServer`](crate::smtp_capture::SmtpCaptureServer).
+    pub fn shared(self) -> std::sync::Arc<Self> {
+        std::sync::Arc::new(self)
+    }
+
+    fn emails_key(&self) -> String {
+        format!("{}:emails", self.prefix)
+    }
+
+    fn order_key(&self) -> String {
+        format!("{}:order", self.prefix)
+    }
+
+    /// Run a command, logging failures.
+    fn with<T>(&self, f: impl FnOnce(&mut Connection) -> redis::RedisResult<T>) -> Option<T> {
+        f(&mut self.connection.lock())
+            .map_err(|e| tracing::warn!(error = %e, "Redis storage command failed"))
+            .ok()
+    }
+
+    /// Load emails by ID, in the given order, skipping missing ones.
+    fn load(&self, ids: &[String]) -> Vec<StoredEmail> {
+        if ids.is_empty() {
+            return Vec::new();
+        }
+        let records: Vec<Option<String>> = self
+            .with(|conn| {
+                redis::cmd("HMGET")
+                    .arg(self.emails_key())
+                    .arg(ids)
+                    .query(conn)
+            })
+            .unwrap_or_default();
+        records.into_iter().flatten().filter_map(decode).collect()
+    }
+
+    /// IDs from `start` to `stop` (inclusive, `-1` for the end), newest first.
+    fn ids(&self, start: isize, stop: isize) -> Vec<String> {
+        self.with(|conn| conn.lrange(self.order_key(), start, stop))
+            .unwrap_or_default()
+    }
+}
+
+fn decode(json: String) -> Option<StoredEmail> {
+    serde_json::from_str::<Record>(&json)
+        .map_err(|e| tracing::warn!(error = %e, "Skipping unreadable email in Redis storage"))
+        .ok()
+        .map(StoredEmail::from)
+}
+
+impl Storage for RedisStorage {
+    fn push(&self, mut email: Email) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let sent_at = Utc::now();
+        email.private.insert(
+            "sent_at".to_string(),
+            serde_json::json!(sent_at.to_rfc3339()),
+        );
+
+        let record = Record {
+            id: id.clone(),
+            email,
+            sent_at,
+            read: false,
+        };
+        match serde_json::to_string(&record) {
+            Ok(json) => {
+                self.with(|conn| {
+                    redis::pipe()
+                        .atomic()
+                        .hset(self.emails_key(), &id, json)
+                        .lpush(self.order_key(), &id)
+                        .query::<()>(conn)
+                });
+            }
+            Err(e) => tracing::warn!(error = %e, "Could not serialize email for Redis storage"),
+        }
+        id
+    }
+
+    fn pop(&self) -> Option<StoredEmail> {
+        let id: Option<String> = self.with(|conn| conn.lpop(self.order_key(), None))?;
+        let id = id?;
+        let json: Option<String> = self.with(|conn| conn.hget(self.emails_key(), &id))?;
+        self.with(|conn| conn.hdel::<_, _, ()>(self.emails_key(), &id));
+        json.and_then(decode)
+    }
+
+    fn get(&self, id: &str) -> Option<StoredEmail> {
+        let json: Option<String> = self.with(|conn| conn.hget(self.emails_key(), id))?;
+        json.and_then(decode)
+    }
+
+    fn all(&self) -> Vec<StoredEmail> {
+        self.load(&self.ids(0, -1))
+    }
+
+    fn delete(&self, id: &str) -> bool {
+        self.with(|conn| {
+            let (removed, _): (usize, usize) = redis::pipe()
+                .atomic()
+                .hdel(self.emails_key(), id)
+                .lrem(self.order_key(), 1, id)
+                .query(conn)?;
+            Ok(removed > 0)
+        })
+        .unwrap_or(false)
+    }
+
+    fn clear(&self) {
+        self.with(|conn| conn.del::<_, ()>(&[self.emails_key(), self.order_key()]));
+    }
+
+    fn count(&self) -> usize {
+        self.with(|conn| conn.hlen(self.emails_key())).unwrap_or(0)
+    }
+
+    fn flush(&self) -> Vec<StoredEmail> {
+        let emails = self.all();
+        self.clear();
+        emails
+    }
+
+    fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail> {
+        if limit == 0 {
+            return Vec::new();
+        }
+        let start = offset as isize;
+        self.load(&self.ids(start, start + limit as isize - 1))
+    }
+
+    fn mark_read(&self, id: &str) -> bool {
+        let Some(mut record) = self
+            .with(|conn| conn.hget::<_, _, Option<String>>(self.emails_key(), id))
+            .flatten()
+            .and_then(|json| serde_json::from_str::<Record>(&json).ok())
+        else {
+            return false;
+        };
+        record.read = true;
+        let Ok(json) = serde_json::to_string(&record) else {
+            return false;
+        };
+
+        self.with(|conn| {
+            redis::cmd("EVAL")
+                .arg(MARK_READ_SCRIPT)
+                .arg(1)
+                .arg(self.emails_key())
+                .arg(id)
+                .arg(json)
+                .query::<bool>(conn)
+        })
+        .unwrap_or(false)
+    }
+}
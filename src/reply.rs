@@ -0,0 +1,260 @@
+//! Reply and forward constructors.
+//!
+//! Build a response to a received message, e.g. one parsed with
+//! [`smtp_capture::parse_message`](crate::smtp_capture::parse_message) or
+//! loaded from storage. Recipients, the `Re:`/`Fwd:` subject, quoted bodies
+//! and threading headers are filled in; set `from` and your own text as usual.
+//!
+//! ```rust,ignore
+//! use missive::{Email, ReplyAll};
+//!
+//! let reply = Email::reply_to_message(&ticket, ReplyAll::No).from("support@example.com");
+//! let quoted = reply.text_body.clone().unwrap_or_default();
+//! let reply = reply.text_body(format!("Thanks, we're looking into it.{}", quoted));
+//! ```
+
+use crate::address::Address;
+use crate::email::Email;
+
+/// Whether a reply goes to everyone on the original message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyAll {
+    /// Reply to the sender (or its `Reply-To`) only.
+    No,
+    /// Also copy the original `to` and `cc` recipients.
+    Yes,
+}
+
+impl Email {
+    /// Start a reply to `original`.
+    ///
+    /// - `to` is the original `Reply-To`, or its sender if there is none.
+    /// - With [`ReplyAll::Yes`], the original `to` and `cc` are copied to `cc`.
+    ///   Remove your own address if it was among them.
+    /// - The subject gets a `Re: ` prefix unless it already has one.
+    /// - `In-Reply-To` and `References` point at the original `Message-ID`.
+    /// - The text and HTML bodies quote the original, starting with a blank
+    ///   line so your reply can be prepended.
+    pub fn reply_to_message(original: &Email, reply_all: ReplyAll) -> Email {
+        let mut email = Email::new();
+
+        email.to = if original.reply_to.is_empty() {
+            original.from.iter().cloned().collect()
+        } else {
+            original.reply_to.clone()
+        };
+        if reply_all == ReplyAll::Yes {
+            for addr in original.to.iter().chain(&original.cc) {
+                if !contains(&email.to, addr) && !contains(&email.cc, addr) {
+                    email.cc.push(addr.clone());
+                }
+            }
+        }
+
+        email.subject = prefixed("Re:", &original.subject);
+        thread(&mut email, original, true);
+
+        let attribution = match (original.get_header("Date"), &original.from) {
+            (Some(date), Some(from)) => format!("On {}, {} wrote:", date, from.formatted()),
+            (None, Some(from)) => format!("{} wrote:", from.formatted()),
+            _ => "Original message:".to_string(),
+        };
+        email.text_body = original.text_body.as_ref().map(|text| {
+            let quoted: Vec<String> = text
+                .lines()
+                .map(|line| {
+                    if line.is_empty() {
+                        ">".to_string()
+                    } else {
+                        format!("> {}", line)
+                    }
+                })
+                .collect();
+            format!("\n\n{}\n{}", attribution, quoted.join("\n"))
+        });
+        email.html_body = original.html_body.as_ref().map(|html| {
+            format!(
+                "<br><br><div>{}</div><blockquote type=\"cite\">{}</blockquote>",
+                escape_html(&attribution),
+                html
+            )
+        });
+
+        email
+    }
+
+    /// Start forwarding `original`.
+    ///
+    /// Recipients are left empty. The subject gets a `Fwd: ` prefix, the
+    /// bodies contain the original with a forwarded-message header block,
+    /// attachments are kept and `References` points at the original
+    /// `Message-ID`.
+    pub fn forward(original: &Email) -> Email {
+        let mut email = Email::new();
+        email.subject = prefixed("Fwd:", &original.subject);
+        email.attachments = original.attachments.clone();
+        thread(&mut email, original, false);
+
+        let mut fields = Vec::new();
+        if let Some(from) = &original.from {
+            fields.push(("From", from.formatted()));
+        }
+        if let Some(date) = original.get_header("Date") {
+            fields.push(("Date", date.to_string()));
+        }
+        fields.push(("Subject", original.subject.clone()));
+        if !original.to.is_empty() {
+            fields.push(("To", join(&original.to)));
+        }
+        if !original.cc.is_empty() {
+            fields.push(("Cc", join(&original.cc)));
+        }
+
+        email.text_body = original.text_body.as_ref().map(|text| {
+            let header: Vec<String> = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            format!(
+                "\n\n---------- Forwarded message ---------\n{}\n\n{}",
+                header.join("\n"),
+                text
+            )
+        });
+        email.html_body = original.html_body.as_ref().map(|html| {
+            let header: Vec<String> = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}<br>", name, escape_html(value)))
+                .collect();
+            format!(
+                "<br><br><div>---------- Forwarded message ---------<br>{}</div><br>{}",
+                header.join(""),
+                html
+            )
+        });
+
+        email
+    }
+}
+
+/// Add `prefix` to a subject unless it already starts with it.
+fn prefixed(prefix: &str, subject: &str) -> String {
+    let has_prefix = subject
+        .get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix));
+    if has_prefix {
+        subject.to_string()
+    } else {
+        format!("{} {}", prefix, subject)
+    }
+}
+
+/// Set `References` (and `In-Reply-To` for replies) from the original's IDs.
+fn thread(email: &mut Email, original: &Email, in_reply_to: bool) {
+    let Some(message_id) = original.get_header("Message-ID") else {
+        return;
+    };
+    let references = match original.get_header("References") {
+        Some(refs) => format!("{} {}", refs, message_id),
+        None => message_id.to_string(),
+    };
+    if in_reply_to {
+        email
+            .headers
+            .insert("In-Reply-To".to_string(), message_id.to_string());
+    }
+    email.headers.insert("References".to_string(), references);
+}
+
+fn contains(list: &[Address], addr: &Address) -> bool {
+    list.iter()
+        .any(|a| a.email.eq_ignore_ascii_case(&addr.email))
+}
+
+fn join(addrs: &[Address]) -> String {
+    addrs
+        .iter()
+        .map(|a| a.formatted())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn original() -> Email {
+        Email::new()
+            .from(("Alice", "alice@example.com"))
+            .to("support@example.com")
+            .cc("bob@example.com")
+            .subject("Printer on fire")
+            .text_body("It's on fire.\n\nPlease help.")
+            .html_body("<p>It's on fire.</p>")
+            .header("Message-ID", "<2@example.com>")
+            .header("References", "<1@example.com>")
+    }
+
+    #[test]
+    fn test_reply() {
+        let reply = Email::reply_to_message(&original(), ReplyAll::No);
+
+        assert_eq!(reply.to[0].email, "alice@example.com");
+        assert!(reply.cc.is_empty());
+        assert_eq!(reply.subject, "Re: Printer on fire");
+        assert_eq!(reply.headers["In-Reply-To"], "<2@example.com>");
+        assert_eq!(
+            reply.headers["References"],
+            "<1@example.com> <2@example.com>"
+        );
+        assert_eq!(
+            reply.text_body.as_deref(),
+            Some("\n\nAlice <alice@example.com> wrote:\n> It's on fire.\n>\n> Please help.")
+        );
+        assert!(reply
+            .html_body
+            .as_deref()
+            .unwrap()
+            .contains("<div>Alice &lt;alice@example.com&gt; wrote:</div><blockquote"));
+
+        let again = Email::reply_to_message(&reply.from("bob@example.com"), ReplyAll::No);
+        assert_eq!(again.subject, "Re: Printer on fire");
+    }
+
+    #[test]
+    fn test_reply_all_uses_reply_to() {
+        let original = original().reply_to("tickets@example.com");
+        let reply = Email::reply_to_message(&original, ReplyAll::Yes);
+
+        assert_eq!(reply.to[0].email, "tickets@example.com");
+        let cc: Vec<&str> = reply.cc.iter().map(|a| a.email.as_str()).collect();
+        assert_eq!(cc, ["support@example.com", "bob@example.com"]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let original =
+            original().attachment(crate::Attachment::from_bytes("photo.jpg", vec![1, 2, 3]));
+        let forward = Email::forward(&original);
+
+        assert!(forward.to.is_empty());
+        assert_eq!(forward.subject, "Fwd: Printer on fire");
+        assert_eq!(forward.attachments.len(), 1);
+        assert!(!forward.headers.contains_key("In-Reply-To"));
+        assert_eq!(
+            forward.headers["References"],
+            "<1@example.com> <2@example.com>"
+        );
+        let text = forward.text_body.unwrap();
+        assert!(text
+            .contains("---------- Forwarded message ---------\nFrom: Alice <alice@example.com>\n"));
+        assert!(text.contains("Cc: bob@example.com\n\nIt's on fire."));
+    }
+}
@@ -0,0 +1,511 @@
+//! Sender reputation guard.
+//!
+//! A [`ReputationTracker`] keeps rolling bounce and complaint counts per
+//! sending domain. Feed it from your provider's webhook handler, and wrap the
+//! mailer in a [`ReputationGuard`] to stop bulk sends when a domain's rates
+//! climb past safe thresholds.
+//!
+//! ```rust,ignore
+//! use missive::{ReputationGuard, ReputationTracker};
+//! use missive::providers::ResendMailer;
+//!
+//! let tracker = ReputationTracker::new();
+//! let mailer = ReputationGuard::new(ResendMailer::new(api_key), tracker.clone())
+//!     .max_bounce_rate(0.05)
+//!     .max_complaint_rate(0.001)
+//!     .guard_category("marketing");
+//!
+//! // In your webhook handler:
+//! tracker.record_bounce("example.com");
+//! ```
+//!
+//! Emails are categorized with the `category` private key:
+//!
+//! ```rust,ignore
+//! let email = Email::new()
+//!     .to("user@example.com")
+//!     .put_private("category", "marketing");
+//! ```
+//!
+//! Sends to a guarded category fail with [`MailError::Blocked`] while the
+//! sender's domain is over a threshold. With [`GuardAction::RequireConfirmation`],
+//! emails that set `put_private("reputation_confirmed", true)` still go out.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(not(feature = "wasm"))]
+use std::time::Instant;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+#[cfg(feature = "wasm")]
+use web_time::Instant;
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
+
+/// Private key used to categorize an email (e.g. `"marketing"`).
+pub const CATEGORY_KEY: &str = "category";
+
+/// Private key used to confirm a send under [`GuardAction::RequireConfirmation`].
+pub const CONFIRMED_KEY: &str = "reputation_confirmed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Sent,
+    Bounced,
+    Complained,
+}
+
+/// Number of buckets the rolling window is split into.
+const BUCKETS: u64 = 60;
+
+/// Counts for one slice of the window, oldest first.
+type Buckets = VecDeque<(u64, ReputationStats)>;
+
+/// Delivery counts for a sending domain within the tracking window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReputationStats {
+    /// Emails delivered to the provider.
+    pub sent: u64,
+    /// Bounces reported.
+    pub bounced: u64,
+    /// Spam complaints reported.
+    pub complained: u64,
+}
+
+impl ReputationStats {
+    /// Bounces as a fraction of sent emails (0.0 when nothing was sent).
+    pub fn bounce_rate(&self) -> f64 {
+        rate(self.bounced, self.sent)
+    }
+
+    /// Complaints as a fraction of sent emails (0.0 when nothing was sent).
+    pub fn complaint_rate(&self) -> f64 {
+        rate(self.complained, self.sent)
+    }
+}
+
+fn rate(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// Rolling per-domain bounce and complaint tracker.
+///
+/// The window is split into 60 buckets of counters, so memory per domain is
+/// fixed whatever the volume, and events expire a bucket at a time.
+///
+/// Cheap to clone; clones share the same counters.
+#[derive(Debug, Clone)]
+pub struct ReputationTracker {
+    window: Duration,
+    started: Instant,
+    buckets: Arc<Mutex<HashMap<String, Buckets>>>,
+}
+
+impl Default for ReputationTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReputationTracker {
+    /// Create a tracker with a 24 hour rolling window.
+    pub fn new() -> Self {
+        Self::with_window(Duration::from_secs(24 * 60 * 60))
+    }
+
+    /// Create a tracker with a custom rolling window.
+    pub fn with_window(window: Duration) -> Self {
+        Self {
+            window,
+            started: Instant::now(),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Record a delivered email for a sending domain.
+    pub fn record_sent(&self, domain: &str) {
+        self.record(domain, Event::Sent);
+    }
+
+    /// Record a bounce for a sending domain.
+    pub fn record_bounce(&self, domain: &str) {
+        self.record(domain, Event::Bounced);
+    }
+
+    /// Record a spam complaint for a sending domain.
+    pub fn record_complaint(&self, domain: &str) {
+        self.record(domain, Event::Complained);
+    }
+
+    /// Get the counts for a sending domain within the window.
+    pub fn stats(&self, domain: &str) -> ReputationStats {
+        let mut buckets = self.buckets.lock();
+        let Some(queue) = buckets.get_mut(&domain.to_lowercase()) else {
+            return ReputationStats::default();
+        };
+        self.prune(queue, self.bucket());
+
+        let mut stats = ReputationStats::default();
+        for (_, counts) in queue.iter() {
+            stats.sent += counts.sent;
+            stats.bounced += counts.bounced;
+            stats.complained += counts.complained;
+        }
+        stats
+    }
+
+    /// Forget all recorded events for a domain (e.g. after remediation).
+    pub fn clear(&self, domain: &str) {
+        self.buckets.lock().remove(&domain.to_lowercase());
+    }
+
+    fn record(&self, domain: &str, event: Event) {
+        let mut buckets = self.buckets.lock();
+        // Under the lock, so buckets are pushed in order
+        let bucket = self.bucket();
+        let queue = buckets.entry(domain.to_lowercase()).or_default();
+        self.prune(queue, bucket);
+        if queue.back().map(|(index, _)| *index) != Some(bucket) {
+            queue.push_back((bucket, ReputationStats::default()));
+        }
+        let counts = &mut queue.back_mut().expect("bucket was just pushed").1;
+        match event {
+            Event::Sent => counts.sent += 1,
+            Event::Bounced => counts.bounced += 1,
+            Event::Complained => counts.complained += 1,
+        }
+    }
+
+    /// Index of the bucket covering now.
+    fn bucket(&self) -> u64 {
+        let width = (self.window.as_nanos() / u128::from(BUCKETS)).max(1);
+        (self.started.elapsed().as_nanos() / width) as u64
+    }
+
+    /// Drop buckets that have left the window ending at `current`.
+    fn prune(&self, queue: &mut Buckets, current: u64) {
+        while let Some((index, _)) = queue.front() {
+            if current.saturating_sub(*index) >= BUCKETS {
+                queue.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// What the guard does when a threshold is exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GuardAction {
+    /// Block guarded sends until rates recover.
+    #[default]
+    Pause,
+    /// Block guarded sends unless the email is explicitly confirmed.
+    RequireConfirmation,
+}
+
+/// A mailer wrapper that blocks bulk sends from domains with poor reputation.
+pub struct ReputationGuard<M> {
+    inner: M,
+    tracker: ReputationTracker,
+    max_bounce_rate: f64,
+    max_complaint_rate: f64,
+    min_volume: u64,
+    categories: Vec<String>,
+    action: GuardAction,
+}
+
+impl<M: Mailer> ReputationGuard<M> {
+    /// Wrap a mailer with default thresholds (5% bounces, 0.1% complaints,
+    /// evaluated once a domain has sent at least 100 emails).
+    pub fn new(inner: M, tracker: ReputationTracker) -> Self {
+        Self {
+            inner,
+            tracker,
+            max_bounce_rate: 0.05,
+            max_complaint_rate: 0.001,
+            min_volume: 100,
+            categories: Vec::new(),
+            action: GuardAction::Pause,
+        }
+    }
+
+    /// Set the maximum bounce rate (0.0 - 1.0).
+    pub fn max_bounce_rate(mut self, rate: f64) -> Self {
+        self.max_bounce_rate = rate;
+        self
+    }
+
+    /// Set the maximum complaint rate (0.0 - 1.0).
+    pub fn max_complaint_rate(mut self, rate: f64) -> Self {
+        self.max_complaint_rate = rate;
+        self
+    }
+
+    /// Minimum sends in the window before thresholds are enforced.
+    pub fn min_volume(mut self, count: u64) -> Self {
+        self.min_volume = count;
+        self
+    }
+
+    /// Guard a category. If no categories are set, every email is guarded.
+    pub fn guard_category(mut self, category: impl Into<String>) -> Self {
+        self.categories.push(category.into());
+        self
+    }
+
+    /// Set what happens when a threshold is exceeded.
+    pub fn action(mut self, action: GuardAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Get the shared tracker.
+    pub fn tracker(&self) -> &ReputationTracker {
+        &self.tracker
+    }
+
+    /// Check an email against the thresholds.
+    pub fn check(&self, email: &Email) -> Result<(), MailError> {
+        let Some(domain) = sender_domain(email) else {
+            return Ok(());
+        };
+
+        if !self.categories.is_empty() {
+            let category = email.private.get(CATEGORY_KEY).and_then(|v| v.as_str());
+            if !category.is_some_and(|c| self.categories.iter().any(|g| g == c)) {
+                return Ok(());
+            }
+        }
+
+        if self.action == GuardAction::RequireConfirmation
+            && email.private.get(CONFIRMED_KEY) == Some(&serde_json::Value::Bool(true))
+        {
+            return Ok(());
+        }
+
+        let stats = self.tracker.stats(&domain);
+        if stats.sent < self.min_volume {
+            return Ok(());
+        }
+
+        let reason = if stats.bounce_rate() > self.max_bounce_rate {
+            Some(("bounce_rate", stats.bounce_rate(), self.max_bounce_rate))
+        } else if stats.complaint_rate() > self.max_complaint_rate {
+            Some((
+                "complaint_rate",
+                stats.complaint_rate(),
+                self.max_complaint_rate,
+            ))
+        } else {
+            None
+        };
+
+        let Some((reason, actual, limit)) = reason else {
+            return Ok(());
+        };
+
+        tracing::warn!(
+            domain = %domain,
+            reason = reason,
+            rate = actual,
+            threshold = limit,
+            "Reputation guard blocked email"
+        );
+
+        #[cfg(feature = "metrics")]
+        metrics::counter!("missive_reputation_blocked_total", "domain" => domain.clone(), "reason" => reason)
+            .increment(1);
+
+        let hint = match self.action {
+            GuardAction::Pause => "sending is paused until the rate recovers",
+            GuardAction::RequireConfirmation => {
+                "set the `reputation_confirmed` private key to send anyway"
+            }
+        };
+        Err(MailError::Blocked(format!(
+            "{} for {} is {:.2}% (limit {:.2}%); {}",
+            reason.replace('_', " "),
+            domain,
+            actual * 100.0,
+            limit * 100.0,
+            hint
+        )))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M: Mailer> Mailer for ReputationGuard<M> {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        self.check(email)?;
+        let result = self.inner.deliver(email).await?;
+        if let Some(domain) = sender_domain(email) {
+            self.tracker.record_sent(&domain);
+        }
+        Ok(result)
+    }
+
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        for email in emails {
+            self.check(email)?;
+        }
+        let results = self.inner.deliver_many(emails).await?;
+        for email in emails {
+            if let Some(domain) = sender_domain(email) {
+                self.tracker.record_sent(&domain);
+            }
+        }
+        Ok(results)
+    }
+
+    fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        self.inner.validate_batch(emails)
+    }
+
+    fn batch_concurrency(&self) -> usize {
+        self.inner.batch_concurrency()
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    fn validate_config(&self) -> Result<(), MailError> {
+        self.inner.validate_config()
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        self.inner.health_check().await
+    }
+
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        self.inner.message_status(message_id).await
+    }
+}
+
+/// Get the lowercase domain of the sender address.
+fn sender_domain(email: &Email) -> Option<String> {
+    let from = email.from.as_ref()?;
+    from.email
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker_with(sent: u64, bounced: u64, complained: u64) -> ReputationTracker {
+        let tracker = ReputationTracker::new();
+        for _ in 0..sent {
+            tracker.record_sent("example.com");
+        }
+        for _ in 0..bounced {
+            tracker.record_bounce("example.com");
+        }
+        for _ in 0..complained {
+            tracker.record_complaint("example.com");
+        }
+        tracker
+    }
+
+    fn marketing() -> Email {
+        Email::new()
+            .from("news@Example.com")
+            .to("user@gmail.com")
+            .put_private(CATEGORY_KEY, "marketing")
+    }
+
+    #[test]
+    fn test_stats_rates() {
+        let stats = tracker_with(100, 10, 1).stats("EXAMPLE.com");
+        assert_eq!(stats.sent, 100);
+        assert!((stats.bounce_rate() - 0.1).abs() < f64::EPSILON);
+        assert!((stats.complaint_rate() - 0.01).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_window_expires_events() {
+        let tracker = ReputationTracker::with_window(Duration::ZERO);
+        tracker.record_bounce("example.com");
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(tracker.stats("example.com"), ReputationStats::default());
+    }
+
+    #[test]
+    fn test_counts_are_bucketed() {
+        let tracker = tracker_with(10_000, 0, 0);
+        assert_eq!(tracker.stats("example.com").sent, 10_000);
+        let buckets = tracker.buckets.lock();
+        assert!(buckets["example.com"].len() as u64 <= BUCKETS);
+    }
+
+    #[test]
+    fn test_prune_with_stale_current_keeps_buckets() {
+        let tracker = ReputationTracker::new();
+        let mut queue: Buckets = VecDeque::from([(5, ReputationStats::default())]);
+        tracker.prune(&mut queue, 4);
+        assert_eq!(queue.len(), 1);
+        tracker.prune(&mut queue, 5 + BUCKETS);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_blocks_over_threshold() {
+        let guard = ReputationGuard::new(NoopMailer, tracker_with(100, 10, 0));
+        let err = guard.check(&marketing()).unwrap_err();
+        assert!(matches!(err, MailError::Blocked(msg) if msg.contains("bounce rate")));
+    }
+
+    #[test]
+    fn test_below_min_volume_is_allowed() {
+        let guard = ReputationGuard::new(NoopMailer, tracker_with(10, 5, 0));
+        assert!(guard.check(&marketing()).is_ok());
+    }
+
+    #[test]
+    fn test_unguarded_category_is_allowed() {
+        let guard =
+            ReputationGuard::new(NoopMailer, tracker_with(100, 0, 5)).guard_category("newsletter");
+        assert!(guard.check(&marketing()).is_ok());
+    }
+
+    #[test]
+    fn test_require_confirmation() {
+        let guard = ReputationGuard::new(NoopMailer, tracker_with(100, 0, 5))
+            .action(GuardAction::RequireConfirmation);
+        assert!(guard.check(&marketing()).is_err());
+        assert!(guard
+            .check(&marketing().put_private(CONFIRMED_KEY, true))
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_deliver_records_sent() {
+        let guard = ReputationGuard::new(NoopMailer, ReputationTracker::new());
+        guard.deliver(&marketing()).await.unwrap();
+        assert_eq!(guard.tracker().stats("example.com").sent, 1);
+    }
+
+    struct NoopMailer;
+
+    #[async_trait]
+    impl Mailer for NoopMailer {
+        async fn deliver(&self, _email: &Email) -> Result<DeliveryResult, MailError> {
+            Ok(DeliveryResult::new("test"))
+        }
+    }
+}
@@ -0,0 +1,434 @@
+//! Per-email provider routing.
+//!
+//! A [`Router`] is a mailer that picks which underlying mailer delivers each
+//! email. Rules are checked in the order they were added; the first match
+//! wins, and emails that match nothing go to the fallback mailer.
+//!
+//! ```rust,ignore
+//! use missive::{Router, Rule};
+//! use missive::providers::{PostmarkMailer, ResendMailer};
+//!
+//! let mailer = Router::new(ResendMailer::new(resend_key))
+//!     .route(Rule::recipient_domain("gmail.com"), PostmarkMailer::new(postmark_key))
+//!     .route(Rule::category("marketing"), missive::named_mailer("bulk").unwrap());
+//!
+//! missive::configure(mailer);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::{Capabilities, DeliveryResult, Mailer, MessageStatus};
+use crate::options::translate_options;
+use crate::reputation::CATEGORY_KEY;
+
+type Predicate = Arc<dyn Fn(&Email) -> bool + Send + Sync>;
+
+/// A condition that selects a route.
+#[derive(Clone)]
+pub enum Rule {
+    /// Any `to` recipient is at one of these domains (case-insensitive).
+    RecipientDomain(Vec<String>),
+    /// The `category` private key equals this value.
+    Category(String),
+    /// A `tag`, `tags`, `category` or `categories` provider option contains this value.
+    Tag(String),
+    /// A header is present with this value (case-insensitive name).
+    Header(String, String),
+    /// The `X-Priority` header is at most this value (1 = highest).
+    Priority(u8),
+    /// Custom predicate.
+    Custom(Predicate),
+}
+
+impl Rule {
+    /// Match recipients at a domain.
+    pub fn recipient_domain(domain: impl Into<String>) -> Self {
+        Rule::RecipientDomain(vec![domain.into().to_lowercase()])
+    }
+
+    /// Match recipients at any of several domains.
+    pub fn recipient_domains<I, S>(domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Rule::RecipientDomain(
+            domains
+                .into_iter()
+                .map(|d| d.into().to_lowercase())
+                .collect(),
+        )
+    }
+
+    /// Match the `category` private key.
+    pub fn category(category: impl Into<String>) -> Self {
+        Rule::Category(category.into())
+    }
+
+    /// Match a provider option tag.
+    pub fn tag(tag: impl Into<String>) -> Self {
+        Rule::Tag(tag.into())
+    }
+
+    /// Match a header value.
+    pub fn header(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Rule::Header(name.into(), value.into())
+    }
+
+    /// Match emails with `X-Priority` of `max` or higher priority (lower number).
+    pub fn priority(max: u8) -> Self {
+        Rule::Priority(max)
+    }
+
+    /// Match with a custom predicate.
+    pub fn custom(f: impl Fn(&Email) -> bool + Send + Sync + 'static) -> Self {
+        Rule::Custom(Arc::new(f))
+    }
+
+    /// Check whether an email matches this rule.
+    pub fn matches(&self, email: &Email) -> bool {
+        match self {
+            Rule::RecipientDomain(domains) => email.to.iter().any(|addr| {
+                addr.email
+                    .rsplit_once('@')
+                    .is_some_and(|(_, d)| domains.iter().any(|x| d.eq_ignore_ascii_case(x)))
+            }),
+            Rule::Category(category) => email
+                .private
+                .get(CATEGORY_KEY)
+                .and_then(|v| v.as_str())
+                .is_some_and(|c| c == category),
+            Rule::Tag(tag) => ["tag", "tags", "category", "categories"]
+                .iter()
+                .filter_map(|key| email.provider_options.get(*key))
+                .any(|value| match value {
+                    serde_json::Value::String(s) => s == tag,
+                    serde_json::Value::Array(items) => {
+                        items.iter().any(|item| item.as_str() == Some(tag.as_str()))
+                    }
+                    _ => false,
+                }),
+            Rule::Header(name, value) => header(email, name).is_some_and(|v| v == value),
+            Rule::Priority(max) => header(email, "X-Priority")
+                .and_then(|v| v.trim().chars().next())
+                .and_then(|c| c.to_digit(10))
+                .is_some_and(|p| p as u8 <= *max),
+            Rule::Custom(f) => f(email),
+        }
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::RecipientDomain(d) => f.debug_tuple("RecipientDomain").field(d).finish(),
+            Rule::Category(c) => f.debug_tuple("Category").field(c).finish(),
+            Rule::Tag(t) => f.debug_tuple("Tag").field(t).finish(),
+            Rule::Header(n, v) => f.debug_tuple("Header").field(n).field(v).finish(),
+            Rule::Priority(p) => f.debug_tuple("Priority").field(p).finish(),
+            Rule::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+fn header<'a>(email: &'a Email, name: &str) -> Option<&'a str> {
+    email
+        .headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// A mailer that routes each email to one of several mailers.
+pub struct Router {
+    routes: Vec<(Rule, Arc<dyn Mailer>)>,
+    fallback: Arc<dyn Mailer>,
+}
+
+impl Router {
+    /// Create a router that sends unmatched emails through `fallback`.
+    pub fn new(fallback: impl Mailer + 'static) -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: Arc::new(fallback),
+        }
+    }
+
+    /// Send emails matching `rule` through `mailer`.
+    pub fn route(mut self, rule: Rule, mailer: impl Mailer + 'static) -> Self {
+        self.routes.push((rule, Arc::new(mailer)));
+        self
+    }
+
+    /// Get the mailer that would deliver this email.
+    pub fn mailer_for(&self, email: &Email) -> &dyn Mailer {
+        self.mailer_at(self.route_index(email))
+    }
+
+    /// Index of the matching route, or `routes.len()` for the fallback.
+    fn route_index(&self, email: &Email) -> usize {
+        self.routes
+            .iter()
+            .position(|(rule, _)| rule.matches(email))
+            .unwrap_or(self.routes.len())
+    }
+
+    /// Group emails by route, keeping their original positions.
+    fn group(&self, emails: &[Email]) -> BTreeMap<usize, Vec<usize>> {
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, email) in emails.iter().enumerate() {
+            groups.entry(self.route_index(email)).or_default().push(i);
+        }
+        groups
+    }
+
+    fn mailer_at(&self, index: usize) -> &dyn Mailer {
+        match self.routes.get(index) {
+            Some((_, mailer)) => &**mailer,
+            None => &*self.fallback,
+        }
+    }
+}
+
+/// `email` with its provider options translated for `mailer`.
+///
+/// The global `deliver()` only sees the router, so translation for the
+/// route's real provider happens here.
+fn translate(mut email: Email, mailer: &dyn Mailer) -> Email {
+    let provider = mailer.provider_name();
+    for warning in translate_options(&mut email, provider) {
+        tracing::warn!(provider = provider, "{}", warning);
+    }
+    email
+}
+
+impl fmt::Debug for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field(
+                "routes",
+                &self
+                    .routes
+                    .iter()
+                    .map(|(rule, m)| (rule, m.provider_name()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("fallback", &self.fallback.provider_name())
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Mailer for Router {
+    async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+        let mailer = self.mailer_for(email);
+        mailer.deliver(&translate(email.clone(), mailer)).await
+    }
+
+    async fn deliver_many(&self, emails: &[Email]) -> Result<Vec<DeliveryResult>, MailError> {
+        self.validate_batch(emails)?;
+
+        let mut results: Vec<Option<DeliveryResult>> = vec![None; emails.len()];
+        for (index, positions) in self.group(emails) {
+            let mailer = self.mailer_at(index);
+            let batch: Vec<Email> = positions
+                .iter()
+                .map(|&i| translate(emails[i].clone(), mailer))
+                .collect();
+            let delivered = mailer.deliver_many(&batch).await?;
+            for (position, result) in positions.into_iter().zip(delivered) {
+                results[position] = Some(result);
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|r| r.ok_or_else(|| MailError::SendError("missing batch result".into())))
+            .collect()
+    }
+
+    fn validate_batch(&self, emails: &[Email]) -> Result<(), MailError> {
+        for (index, positions) in self.group(emails) {
+            let batch: Vec<Email> = positions.iter().map(|&i| emails[i].clone()).collect();
+            self.mailer_at(index).validate_batch(&batch)?;
+        }
+        Ok(())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "router"
+    }
+
+    /// What every route and the fallback support.
+    fn capabilities(&self) -> Capabilities {
+        self.routes
+            .iter()
+            .fold(self.fallback.capabilities(), |caps, (_, mailer)| {
+                caps.intersect(mailer.capabilities())
+            })
+    }
+
+    fn validate_config(&self) -> Result<(), MailError> {
+        for (_, mailer) in &self.routes {
+            mailer.validate_config()?;
+        }
+        self.fallback.validate_config()
+    }
+
+    async fn health_check(&self) -> Result<(), MailError> {
+        for (_, mailer) in &self.routes {
+            mailer.health_check().await?;
+        }
+        self.fallback.health_check().await
+    }
+
+    /// Asks each route, then the fallback, since the router doesn't know
+    /// which one sent the message. Returns the first status other than
+    /// [`MessageStatus::Unknown`].
+    async fn message_status(&self, message_id: &str) -> Result<MessageStatus, MailError> {
+        let mut result = Err(MailError::UnsupportedFeature(
+            "no route reports message status".to_string(),
+        ));
+        let mailers = self.routes.iter().map(|(_, mailer)| mailer);
+        for mailer in mailers.chain(std::iter::once(&self.fallback)) {
+            match mailer.message_status(message_id).await {
+                Ok(MessageStatus::Unknown) => result = Ok(MessageStatus::Unknown),
+                Ok(status) => return Ok(status),
+                Err(MailError::UnsupportedFeature(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        result
+    }
+}
+
+#[cfg(all(test, feature = "local"))]
+mod tests {
+    use super::*;
+    use crate::providers::LocalMailer;
+
+    fn email(to: &str) -> Email {
+        Email::new().from("sender@example.com").to(to)
+    }
+
+    #[test]
+    fn test_rules() {
+        assert!(Rule::recipient_domain("Gmail.com").matches(&email("a@GMAIL.com")));
+        assert!(!Rule::recipient_domain("gmail.com").matches(&email("a@example.com")));
+        assert!(Rule::category("marketing")
+            .matches(&email("a@x.com").put_private(CATEGORY_KEY, "marketing")));
+        assert!(Rule::tag("welcome")
+            .matches(&email("a@x.com").provider_option("tags", serde_json::json!(["welcome"]))));
+        assert!(Rule::priority(2).matches(&email("a@x.com").header("X-Priority", "1 (Highest)")));
+        assert!(!Rule::priority(2).matches(&email("a@x.com").header("X-Priority", "3")));
+        assert!(Rule::header("x-stream", "tx").matches(&email("a@x.com").header("X-Stream", "tx")));
+    }
+
+    #[tokio::test]
+    async fn test_routes_first_match() {
+        let gmail = LocalMailer::new();
+        let fallback = LocalMailer::new();
+        let router = Router::new(fallback.clone())
+            .route(Rule::recipient_domain("gmail.com"), gmail.clone())
+            .route(Rule::custom(|_| true), LocalMailer::new());
+
+        router.deliver(&email("a@gmail.com")).await.unwrap();
+        assert_eq!(gmail.emails().len(), 1);
+        assert_eq!(fallback.emails().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_many_preserves_order() {
+        let gmail = LocalMailer::new();
+        let fallback = LocalMailer::new();
+        let router =
+            Router::new(fallback.clone()).route(Rule::recipient_domain("gmail.com"), gmail.clone());
+
+        let emails = vec![email("a@x.com"), email("b@gmail.com"), email("c@x.com")];
+        let results = router.deliver_many(&emails).await.unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(gmail.emails().len(), 1);
+        assert_eq!(fallback.emails().len(), 2);
+        let stored = gmail.emails();
+        assert_eq!(results[1].message_id, stored[0].id);
+    }
+
+    /// A local mailer that reports itself as Postmark.
+    struct Postmark(LocalMailer);
+
+    #[async_trait]
+    impl Mailer for Postmark {
+        async fn deliver(&self, email: &Email) -> Result<DeliveryResult, MailError> {
+            self.0.deliver(email).await
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "postmark"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translates_options_for_route_provider() {
+        let postmark = LocalMailer::new();
+        let router = Router::new(LocalMailer::new())
+            .route(Rule::custom(|_| true), Postmark(postmark.clone()));
+        let email = email("a@x.com").provider_option(
+            "tags",
+            serde_json::json!([{"name": "plan", "value": "pro"}]),
+        );
+
+        router.deliver(&email).await.unwrap();
+        router.deliver_many(&[email]).await.unwrap();
+
+        for stored in postmark.emails() {
+            let options = &stored.email.provider_options;
+            assert_eq!(options["metadata"], serde_json::json!({"plan": "pro"}));
+            assert!(!options.contains_key("tags"));
+        }
+        assert_eq!(postmark.emails().len(), 2);
+    }
+
+    struct Reports(MessageStatus);
+
+    #[async_trait]
+    impl Mailer for Reports {
+        async fn deliver(&self, _email: &Email) -> Result<DeliveryResult, MailError> {
+            Ok(DeliveryResult::new("1"))
+        }
+
+        async fn message_status(&self, _message_id: &str) -> Result<MessageStatus, MailError> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_message_status_asks_every_route() {
+        let router = Router::new(LocalMailer::new())
+            .route(Rule::custom(|_| true), Reports(MessageStatus::Unknown))
+            .route(Rule::custom(|_| true), Reports(MessageStatus::Delivered));
+        assert_eq!(
+            router.message_status("1").await.unwrap(),
+            MessageStatus::Delivered
+        );
+
+        let router = Router::new(Reports(MessageStatus::Unknown));
+        assert_eq!(
+            router.message_status("1").await.unwrap(),
+            MessageStatus::Unknown
+        );
+
+        let err = Router::new(LocalMailer::new())
+            .message_status("1")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MailError::UnsupportedFeature(_)));
+    }
+}
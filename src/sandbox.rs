@@ -0,0 +1,231 @@
+//! Recipient sandbox for staging and development environments.
+//!
+//! A [`Sandbox`] filters every recipient against an allowlist and/or denylist.
+//! Recipients outside the allowed set are either redirected to a catch-all
+//! address or dropped, so non-production environments never email real customers.
+//!
+//! # In Code
+//!
+//! `Sandbox` implements [`Interceptor`], so it can wrap any mailer:
+//!
+//! ```rust,ignore
+//! use missive::providers::ResendMailer;
+//! use missive::{InterceptorExt, Sandbox};
+//!
+//! let mailer = ResendMailer::new(api_key).with_interceptor(
+//!     Sandbox::new()
+//!         .allow_domain("example.com")
+//!         .redirect_to("qa@example.com"),
+//! );
+//! ```
+//!
+//! # Via Environment
+//!
+//! When any of the sandbox variables is set, the global `deliver()`,
+//! `deliver_with()` and `deliver_many()` functions apply the sandbox automatically:
+//!
+//! ```bash
+//! EMAIL_SANDBOX_ALLOWED_DOMAINS=example.com,mycompany.com
+//! EMAIL_SANDBOX_DENIED_DOMAINS=customer.com
+//! EMAIL_SANDBOX_REDIRECT_TO=qa@example.com
+//! ```
+//!
+//! Without `EMAIL_SANDBOX_REDIRECT_TO`, disallowed recipients are dropped.
+//! With only `EMAIL_SANDBOX_REDIRECT_TO`, every recipient is redirected.
+
+use crate::address::{Address, ToAddress};
+use crate::config;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::interceptor::Interceptor;
+
+/// Recipient allowlist/denylist filter.
+///
+/// A recipient is allowed when it is not on the denylist and either the
+/// allowlist is empty or the recipient matches an allowed domain or address.
+/// With a catch-all address set, an empty allowlist allows nobody (except the
+/// catch-all itself), so every recipient is redirected.
+/// Domain and address matching is case-insensitive.
+#[derive(Debug, Clone, Default)]
+pub struct Sandbox {
+    allowed_domains: Vec<String>,
+    allowed_addresses: Vec<String>,
+    denied_domains: Vec<String>,
+    redirect_to: Option<Address>,
+}
+
+impl Sandbox {
+    /// Create an empty sandbox (allows everything until rules are added).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a sandbox from environment variables (or the installed [`Config`](crate::Config)).
+    ///
+    /// Reads `EMAIL_SANDBOX_ALLOWED_DOMAINS`, `EMAIL_SANDBOX_DENIED_DOMAINS`
+    /// (comma-separated) and `EMAIL_SANDBOX_REDIRECT_TO`.
+    /// Returns `None` if none of them are set.
+    pub fn from_env() -> Option<Self> {
+        let allowed = config::var("EMAIL_SANDBOX_ALLOWED_DOMAINS");
+        let denied = config::var("EMAIL_SANDBOX_DENIED_DOMAINS");
+        let redirect = config::var("EMAIL_SANDBOX_REDIRECT_TO");
+
+        if allowed.is_none() && denied.is_none() && redirect.is_none() {
+            return None;
+        }
+
+        let mut sandbox = Self::new();
+        for domain in split_list(allowed.as_deref().unwrap_or_default()) {
+            sandbox = sandbox.allow_domain(domain);
+        }
+        for domain in split_list(denied.as_deref().unwrap_or_default()) {
+            sandbox = sandbox.deny_domain(domain);
+        }
+        if let Some(redirect) = redirect.filter(|r| !r.trim().is_empty()) {
+            sandbox = sandbox.redirect_to(redirect.trim());
+        }
+        Some(sandbox)
+    }
+
+    /// Allow all recipients at a domain.
+    pub fn allow_domain(mut self, domain: impl Into<String>) -> Self {
+        self.allowed_domains.push(normalize_domain(&domain.into()));
+        self
+    }
+
+    /// Allow a specific recipient address.
+    pub fn allow_address(mut self, email: impl Into<String>) -> Self {
+        self.allowed_addresses.push(email.into().to_lowercase());
+        self
+    }
+
+    /// Deny all recipients at a domain (takes precedence over the allowlist).
+    pub fn deny_domain(mut self, domain: impl Into<String>) -> Self {
+        self.denied_domains.push(normalize_domain(&domain.into()));
+        self
+    }
+
+    /// Redirect disallowed recipients to a catch-all address instead of dropping them.
+    pub fn redirect_to(mut self, addr: impl ToAddress) -> Self {
+        self.redirect_to = Some(addr.to_address());
+        self
+    }
+
+    /// Check whether a recipient passes the sandbox rules.
+    pub fn is_allowed(&self, addr: &Address) -> bool {
+        let email = addr.email.to_lowercase();
+        let domain = email.rsplit_once('@').map(|(_, d)| d).unwrap_or_default();
+
+        if self.denied_domains.iter().any(|d| d == domain) {
+            return false;
+        }
+
+        if self.allowed_domains.is_empty() && self.allowed_addresses.is_empty() {
+            return match self.redirect_to {
+                Some(ref catch_all) => catch_all.email.eq_ignore_ascii_case(&email),
+                None => true,
+            };
+        }
+
+        self.allowed_domains.iter().any(|d| d == domain) || self.allowed_addresses.contains(&email)
+    }
+
+    /// Apply the sandbox rules to an email.
+    ///
+    /// Disallowed `to`/`cc`/`bcc` recipients are replaced by the catch-all
+    /// address (once, in `to`) or dropped. Returns [`MailError::Blocked`] if
+    /// no `to` recipients remain.
+    pub fn apply(&self, mut email: Email) -> Result<Email, MailError> {
+        let mut filtered = 0;
+
+        for list in [&mut email.to, &mut email.cc, &mut email.bcc] {
+            let before = list.len();
+            list.retain(|addr| self.is_allowed(addr));
+            filtered += before - list.len();
+        }
+
+        if filtered > 0 {
+            tracing::debug!(filtered = filtered, "Sandbox filtered recipients");
+
+            if let Some(ref catch_all) = self.redirect_to {
+                if !email
+                    .to
+                    .iter()
+                    .any(|a| a.email.eq_ignore_ascii_case(&catch_all.email))
+                {
+                    email.to.push(catch_all.clone());
+                }
+            }
+        }
+
+        if email.to.is_empty() {
+            return Err(MailError::Blocked(
+                "all recipients were filtered by the sandbox".into(),
+            ));
+        }
+
+        Ok(email)
+    }
+}
+
+impl Interceptor for Sandbox {
+    fn intercept(&self, email: Email) -> Result<Email, MailError> {
+        self.apply(email)
+    }
+}
+
+/// Lowercase a domain and strip a leading `@`.
+fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// Split a comma-separated list, skipping empty entries.
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_sandbox_allows_everything() {
+        let sandbox = Sandbox::new();
+        assert!(sandbox.is_allowed(&Address::new("anyone@anywhere.com")));
+    }
+
+    #[test]
+    fn test_allow_domain_case_insensitive() {
+        let sandbox = Sandbox::new().allow_domain("@Example.com");
+        assert!(sandbox.is_allowed(&Address::new("user@EXAMPLE.COM")));
+        assert!(!sandbox.is_allowed(&Address::new("user@other.com")));
+    }
+
+    #[test]
+    fn test_deny_takes_precedence() {
+        let sandbox = Sandbox::new()
+            .allow_domain("example.com")
+            .deny_domain("example.com");
+        assert!(!sandbox.is_allowed(&Address::new("user@example.com")));
+    }
+
+    #[test]
+    fn test_allow_address() {
+        let sandbox = Sandbox::new().allow_address("QA@gmail.com");
+        assert!(sandbox.is_allowed(&Address::new("qa@gmail.com")));
+        assert!(!sandbox.is_allowed(&Address::new("someone@gmail.com")));
+    }
+
+    #[test]
+    fn test_redirect_without_allowlist_allows_nobody() {
+        let sandbox = Sandbox::new().redirect_to("qa@example.com");
+        assert!(!sandbox.is_allowed(&Address::new("anyone@anywhere.com")));
+        assert!(sandbox.is_allowed(&Address::new("QA@example.com")));
+    }
+
+    #[test]
+    fn test_split_list() {
+        let items: Vec<&str> = split_list(" a.com, ,b.com,").collect();
+        assert_eq!(items, vec!["a.com", "b.com"]);
+    }
+}
@@ -0,0 +1,93 @@
+//! Scoped mailer overrides.
+//!
+//! [`test_scope`] runs a future with its own mailer in place of the global
+//! one, so parallel tests can each capture their own emails without racing on
+//! [`configure()`](crate::configure) and [`reset()`](crate::reset).
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::mailer::Mailer;
+
+thread_local! {
+    static SCOPED: RefCell<Option<Arc<dyn Mailer>>> = const { RefCell::new(None) };
+}
+
+/// Run a future with `mailer` overriding the global mailer.
+///
+/// Inside the future, [`deliver()`](crate::deliver),
+/// [`deliver_many()`](crate::deliver_many) and [`mailer()`](crate::mailer) use
+/// `mailer`; everything outside keeps using the global one. Scopes nest, and
+/// the override follows the future across threads on multi-threaded runtimes.
+///
+/// Tasks spawned from inside the scope (e.g. `tokio::spawn`) do not inherit
+/// it; wrap them in their own `test_scope`. Named mailers are not affected.
+///
+/// ```rust,ignore
+/// use missive::providers::LocalMailer;
+///
+/// #[tokio::test]
+/// async fn sends_welcome_email() {
+///     let mailer = LocalMailer::new();
+///
+///     missive::test_scope(mailer.clone(), async {
+///         signup("user@example.com").await;  // calls missive::deliver()
+///     })
+///     .await;
+///
+///     assert_eq!(mailer.email_count(), 1);
+/// }
+/// ```
+pub fn test_scope<M, F>(mailer: M, future: F) -> impl Future<Output = F::Output>
+where
+    M: Mailer + 'static,
+    F: Future,
+{
+    Scoped {
+        mailer: Arc::new(mailer),
+        future: Box::pin(future),
+    }
+}
+
+/// The mailer for the current scope, if any.
+pub(crate) fn scoped_mailer() -> Option<Arc<dyn Mailer>> {
+    SCOPED.with(|scoped| scoped.borrow().clone())
+}
+
+/// Future that installs its mailer for the duration of each poll.
+struct Scoped<F> {
+    mailer: Arc<dyn Mailer>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let _guard = ScopeGuard::enter(Arc::clone(&this.mailer));
+        this.future.as_mut().poll(cx)
+    }
+}
+
+/// Restores the enclosing scope's mailer on drop, even if the poll panics.
+struct ScopeGuard {
+    previous: Option<Arc<dyn Mailer>>,
+}
+
+impl ScopeGuard {
+    fn enter(mailer: Arc<dyn Mailer>) -> Self {
+        let previous = SCOPED.with(|scoped| scoped.replace(Some(mailer)));
+        Self { previous }
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED.with(|scoped| *scoped.borrow_mut() = previous);
+    }
+}
@@ -0,0 +1,212 @@
+//! AWS Secrets Manager and Systems Manager Parameter Store.
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::aws_sig::{self, amz_date, amz_datetime, hex_sha256};
+use crate::error::MailError;
+
+/// Signed JSON-protocol client for the two AWS services.
+pub(super) struct AwsSecrets {
+    region: String,
+    access_key: String,
+    secret: String,
+    session_token: Option<String>,
+    endpoint: Option<String>,
+    client: Client,
+}
+
+/// The client in `slot`, created from the environment on first use.
+pub(super) fn client(slot: &mut Option<AwsSecrets>) -> Result<&AwsSecrets, MailError> {
+    if slot.is_none() {
+        *slot = Some(AwsSecrets::from_env()?);
+    }
+    Ok(slot.as_ref().unwrap())
+}
+
+impl AwsSecrets {
+    fn from_env() -> Result<Self, MailError> {
+        let var = |key: &'static str| {
+            crate::config::var(key)
+                .ok_or_else(|| MailError::Configuration(format!("{} not set", key)))
+        };
+        Ok(Self {
+            region: var("AWS_REGION")?,
+            access_key: var("AWS_ACCESS_KEY_ID")?,
+            secret: var("AWS_SECRET_ACCESS_KEY")?,
+            session_token: crate::config::var("AWS_SESSION_TOKEN"),
+            endpoint: crate::config::var("AWS_ENDPOINT_URL")
+                .map(|url| url.trim_end_matches('/').to_string()),
+            client: crate::http::default_client(),
+        })
+    }
+
+    /// The `SecretString` of a Secrets Manager secret (name or ARN).
+    pub(super) async fn get_secret_value(&self, id: &str) -> Result<String, MailError> {
+        // arn:aws:secretsmanager:<region>:<account>:secret:<name>
+        let region = match id.strip_prefix("arn:") {
+            Some(arn) => arn.split(':').nth(2).unwrap_or(&self.region),
+            None => &self.region,
+        };
+        let body = json!({ "SecretId": id });
+        let response: SecretValue = self
+            .call(
+                "secretsmanager",
+                region,
+                "secretsmanager.GetSecretValue",
+                &body,
+                id,
+            )
+            .await?;
+        response.secret_string.ok_or_else(|| {
+            MailError::Configuration(format!("aws-sm:{}: secret has no SecretString", id))
+        })
+    }
+
+    /// The decrypted value of an SSM parameter.
+    pub(super) async fn get_parameter(&self, name: &str) -> Result<String, MailError> {
+        let body = json!({ "Name": name, "WithDecryption": true });
+        let response: ParameterResult = self
+            .call("ssm", &self.region, "AmazonSSM.GetParameter", &body, name)
+            .await?;
+        Ok(response.parameter.value)
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        service: &str,
+        region: &str,
+        target: &str,
+        body: &serde_json::Value,
+        name: &str,
+    ) -> Result<T, MailError> {
+        let (url, host) = match &self.endpoint {
+            Some(endpoint) => {
+                let host = endpoint
+                    .split_once("://")
+                    .map_or(endpoint.as_str(), |(_, host)| host)
+                    .to_string();
+                (format!("{}/", endpoint), host)
+            }
+            None => {
+                let host = format!("{}.{}.amazonaws.com", service, region);
+                (format!("https://{}/", host), host)
+            }
+        };
+        let payload = body.to_string();
+        let now = Utc::now();
+
+        let mut headers = vec![
+            (
+                "content-type".to_string(),
+                "application/x-amz-json-1.1".to_string(),
+            ),
+            ("host".to_string(), host),
+            ("x-amz-date".to_string(), amz_datetime(&now)),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        headers.push(("x-amz-target".to_string(), target.to_string()));
+
+        let authorization = self.authorization(&now, service, region, &headers, &payload);
+        let mut request = self
+            .client
+            .post(&url)
+            .header("Authorization", authorization)
+            .body(payload);
+        for (name, value) in headers.iter().filter(|(k, _)| k != "host") {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = serde_json::from_str::<AwsError>(&text)
+                .ok()
+                .and_then(|e| e.message.or(e.kind))
+                .unwrap_or(text);
+            return Err(MailError::Configuration(format!(
+                "Cannot read {} from {}: {} {}",
+                name,
+                service,
+                status.as_u16(),
+                message
+            )));
+        }
+        serde_json::from_str(&text).map_err(|e| {
+            MailError::Configuration(format!("Invalid {} response for {}: {}", service, name, e))
+        })
+    }
+
+    fn authorization(
+        &self,
+        now: &DateTime<Utc>,
+        service: &str,
+        region: &str,
+        headers: &[(String, String)],
+        payload: &str,
+    ) -> String {
+        let signed_headers = headers
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v))
+            .collect();
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_headers,
+            hex_sha256(payload.as_bytes())
+        );
+        let scope = format!("{}/{}/{}/aws4_request", amz_date(now), region, service);
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            aws_sig::ALGORITHM,
+            amz_datetime(now),
+            scope,
+            hex_sha256(canonical_request.as_bytes())
+        );
+        let signature = aws_sig::signature(&self.secret, now, region, service, &string_to_sign);
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            aws_sig::ALGORITHM,
+            self.access_key,
+            scope,
+            signed_headers,
+            signature
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct SecretValue {
+    #[serde(rename = "SecretString")]
+    secret_string: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ParameterResult {
+    #[serde(rename = "Parameter")]
+    parameter: Parameter,
+}
+
+#[derive(Deserialize)]
+struct Parameter {
+    #[serde(rename = "Value")]
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct AwsError {
+    #[serde(rename = "__type")]
+    kind: Option<String>,
+    #[serde(alias = "Message")]
+    message: Option<String>,
+}
@@ -0,0 +1,259 @@
+//! Provider credentials from a secrets manager.
+//!
+//! Any setting can hold a reference to a secret instead of the secret itself:
+//!
+//! | Reference | Source | Feature |
+//! |-----------|--------|---------|
+//! | `aws-sm:<secret-id>` | AWS Secrets Manager (`SecretString`) | `secrets-aws` |
+//! | `aws-sm:<secret-id>#<field>` | One field of a JSON secret | `secrets-aws` |
+//! | `aws-ssm:<parameter-name>` | AWS Systems Manager Parameter Store (decrypted) | `secrets-aws` |
+//! | `vault:<path>#<field>` | HashiCorp Vault KV, v1 or v2 | `secrets-vault` |
+//!
+//! ```bash
+//! RESEND_API_KEY=aws-sm:prod/missive#resend_api_key
+//! POSTMARK_API_KEY=vault:secret/data/missive#postmark_api_key
+//! ```
+//!
+//! References are resolved by [`load`], once at startup, and can be kept
+//! fresh with [`refresh_every`], which [reloads](crate::reload) the global
+//! mailer when a secret changes (for example after rotation):
+//!
+//! ```rust,ignore
+//! missive::secrets::load().await?;
+//! let _refresher = missive::secrets::refresh_every(Duration::from_secs(300));
+//! ```
+//!
+//! Until it is loaded, a setting holding a reference reads as unset.
+//!
+//! AWS credentials come from `AWS_REGION`, `AWS_ACCESS_KEY_ID`,
+//! `AWS_SECRET_ACCESS_KEY` and optionally `AWS_SESSION_TOKEN`; the region
+//! of a secret given by ARN is taken from the ARN. `AWS_ENDPOINT_URL`
+//! points at another endpoint (LocalStack, ...). Vault is reached at
+//! `VAULT_ADDR` with `VAULT_TOKEN`, and `VAULT_NAMESPACE` for Vault
+//! Enterprise namespaces.
+
+#[cfg(feature = "secrets-aws")]
+mod aws;
+#[cfg(feature = "secrets-vault")]
+mod vault;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+
+use crate::error::MailError;
+
+/// Resolved secrets, keyed by reference.
+static RESOLVED: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+/// A setting value that points to a secret.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(
+    not(all(feature = "secrets-aws", feature = "secrets-vault")),
+    allow(dead_code)
+)]
+enum Reference<'a> {
+    /// `aws-sm:<secret-id>[#<field>]`
+    AwsSecret { id: &'a str, field: Option<&'a str> },
+    /// `aws-ssm:<parameter-name>`
+    AwsParameter { name: &'a str },
+    /// `vault:<path>#<field>`
+    Vault { path: &'a str, field: &'a str },
+}
+
+impl<'a> Reference<'a> {
+    fn parse(value: &'a str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(rest) = value.strip_prefix("aws-sm:") {
+            // ARNs contain colons but never `#`
+            let (id, field) = match rest.rsplit_once('#') {
+                Some((id, field)) => (id, Some(field)),
+                None => (rest, None),
+            };
+            Some(Self::AwsSecret { id, field })
+        } else if let Some(name) = value.strip_prefix("aws-ssm:") {
+            Some(Self::AwsParameter { name })
+        } else if let Some(rest) = value.strip_prefix("vault:") {
+            let (path, field) = rest.rsplit_once('#')?;
+            Some(Self::Vault {
+                path: path.trim_matches('/'),
+                field,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Replace a reference read from setting `key` with its resolved secret.
+///
+/// Values that aren't references are returned as they are.
+pub(crate) fn substitute(key: &str, value: String) -> Option<String> {
+    if Reference::parse(&value).is_none() {
+        return Some(value);
+    }
+    let resolved = RESOLVED.read().get(&value).cloned();
+    if resolved.is_none() {
+        tracing::warn!(
+            key = key,
+            reference = %value,
+            "Secret reference is not resolved; call missive::secrets::load() first"
+        );
+    }
+    resolved
+}
+
+/// Resolve every secret reference in the settings.
+///
+/// Returns the number of distinct references. If secrets changed and the
+/// global mailer was already built, it is [reloaded](crate::reload); when
+/// that fails, the previous secrets are kept and the error is returned.
+pub async fn load() -> Result<usize, MailError> {
+    let mut resolved = BTreeMap::new();
+    #[cfg(feature = "secrets-aws")]
+    let mut aws_client = None;
+    #[cfg(feature = "secrets-vault")]
+    let mut vault_client = None;
+
+    for (key, value) in crate::config::settings() {
+        let Some(reference) = Reference::parse(&value) else {
+            continue;
+        };
+        if resolved.contains_key(&value) {
+            continue;
+        }
+        let secret = match reference {
+            #[cfg(feature = "secrets-aws")]
+            Reference::AwsSecret { id, field } => {
+                let client = aws::client(&mut aws_client)?;
+                let secret = client.get_secret_value(id).await?;
+                match field {
+                    Some(field) => json_field(&secret, field, &value)?,
+                    None => secret,
+                }
+            }
+            #[cfg(feature = "secrets-aws")]
+            Reference::AwsParameter { name } => {
+                aws::client(&mut aws_client)?.get_parameter(name).await?
+            }
+            #[cfg(feature = "secrets-vault")]
+            Reference::Vault { path, field } => {
+                vault::client(&mut vault_client)?.read(path, field).await?
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(MailError::Configuration(format!(
+                    "{} references a secrets manager whose feature is not enabled: {}",
+                    key, value
+                )))
+            }
+        };
+        resolved.insert(value, secret);
+    }
+
+    let count = resolved.len();
+    let previous = std::mem::replace(&mut *RESOLVED.write(), resolved);
+    let changed = previous != *RESOLVED.read();
+    if changed && crate::is_initialized() {
+        if let Err(e) = crate::reload() {
+            *RESOLVED.write() = previous;
+            return Err(e);
+        }
+    }
+    tracing::debug!(count = count, changed = changed, "Loaded secrets");
+    Ok(count)
+}
+
+/// Handle to a background [`refresh_every`], which stops when dropped.
+#[must_use = "refreshing stops when the handle is dropped"]
+#[derive(Debug)]
+pub struct SecretsRefresher {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SecretsRefresher {
+    /// Stop refreshing.
+    pub fn stop(self) {}
+}
+
+impl Drop for SecretsRefresher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// [`load`] secrets again every `interval` on a Tokio task.
+///
+/// Failures are logged and leave the current secrets in place. Must be
+/// called from within a Tokio runtime.
+pub fn refresh_every(interval: Duration) -> SecretsRefresher {
+    let task = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = load().await {
+                tracing::warn!(error = %e, "Failed to refresh secrets");
+            }
+        }
+    });
+    SecretsRefresher { task }
+}
+
+/// Extract `field` from a JSON object secret.
+#[cfg(feature = "secrets-aws")]
+fn json_field(secret: &str, field: &str, reference: &str) -> Result<String, MailError> {
+    let object: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(secret).map_err(|_| {
+            MailError::Configuration(format!("{}: secret is not a JSON object", reference))
+        })?;
+    match object.get(field) {
+        Some(serde_json::Value::String(s)) => Ok(s.clone()),
+        Some(other) => Ok(other.to_string()),
+        None => Err(MailError::Configuration(format!(
+            "{}: secret has no field {}",
+            reference, field
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_references() {
+        assert_eq!(
+            Reference::parse("aws-sm:prod/missive#resend_api_key"),
+            Some(Reference::AwsSecret {
+                id: "prod/missive",
+                field: Some("resend_api_key")
+            })
+        );
+        assert_eq!(
+            Reference::parse("aws-sm:arn:aws:secretsmanager:eu-west-1:123:secret:missive"),
+            Some(Reference::AwsSecret {
+                id: "arn:aws:secretsmanager:eu-west-1:123:secret:missive",
+                field: None
+            })
+        );
+        assert_eq!(
+            Reference::parse("aws-ssm:/prod/missive/key"),
+            Some(Reference::AwsParameter {
+                name: "/prod/missive/key"
+            })
+        );
+        assert_eq!(
+            Reference::parse("vault:/secret/data/missive/#postmark"),
+            Some(Reference::Vault {
+                path: "secret/data/missive",
+                field: "postmark"
+            })
+        );
+        // Vault references need a field
+        assert_eq!(Reference::parse("vault:secret/data/missive"), None);
+        assert_eq!(Reference::parse("re_123abc"), None);
+    }
+}
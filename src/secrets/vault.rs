@@ -0,0 +1,87 @@
+//! HashiCorp Vault KV secrets engine.
+
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::error::MailError;
+
+/// Reads KV secrets with a token.
+pub(super) struct Vault {
+    addr: String,
+    token: String,
+    namespace: Option<String>,
+    client: Client,
+}
+
+/// The client in `slot`, created from the environment on first use.
+pub(super) fn client(slot: &mut Option<Vault>) -> Result<&Vault, MailError> {
+    if slot.is_none() {
+        *slot = Some(Vault::from_env()?);
+    }
+    Ok(slot.as_ref().unwrap())
+}
+
+impl Vault {
+    fn from_env() -> Result<Self, MailError> {
+        let var = |key: &'static str| {
+            crate::config::var(key)
+                .ok_or_else(|| MailError::Configuration(format!("{} not set", key)))
+        };
+        Ok(Self {
+            addr: var("VAULT_ADDR")?.trim_end_matches('/').to_string(),
+            token: var("VAULT_TOKEN")?,
+            namespace: crate::config::var("VAULT_NAMESPACE"),
+            client: crate::http::default_client(),
+        })
+    }
+
+    /// One field of the secret at `path`.
+    ///
+    /// KV v2 paths include the `data/` segment (`secret/data/missive`); the
+    /// field is looked up in the v2 `data.data` object, then in v1's `data`.
+    pub(super) async fn read(&self, path: &str, field: &str) -> Result<String, MailError> {
+        let mut request = self
+            .client
+            .get(format!("{}/v1/{}", self.addr, path))
+            .header("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            let errors = body["errors"]
+                .as_array()
+                .map(|errors| {
+                    errors
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+                .unwrap_or_default();
+            return Err(MailError::Configuration(format!(
+                "Cannot read {} from Vault: {} {}",
+                path,
+                status.as_u16(),
+                errors
+            )));
+        }
+
+        let data = &body["data"];
+        let value = match &data["data"] {
+            Value::Object(v2) => v2.get(field),
+            _ => data.get(field),
+        };
+        match value {
+            Some(Value::String(s)) => Ok(s.clone()),
+            Some(Value::Null) | None => Err(MailError::Configuration(format!(
+                "vault:{}#{}: secret has no field {}",
+                path, field, field
+            ))),
+            Some(other) => Ok(other.to_string()),
+        }
+    }
+}
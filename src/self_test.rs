@@ -0,0 +1,226 @@
+//! Deliverability self-test.
+//!
+//! [`self_test`] sends a probe email through the global mailer, with the
+//! same defaults, sandbox and validation as [`deliver`](crate::deliver), and
+//! reports whether the provider accepted it. Run it after a deploy to check
+//! the whole email path, not just the credentials:
+//!
+//! ```rust,ignore
+//! let report = missive::self_test().await?;
+//! println!("{}", report);
+//! ```
+//!
+//! The probe goes to `EMAIL_SELF_TEST_TO`, or to `EMAIL_FROM` when that's
+//! unset. With `EMAIL_SELF_TEST_SANDBOX=true`, providers that support it
+//! validate the email without delivering it:
+//!
+//! | Provider | Sandbox |
+//! |----------|---------|
+//! | SendGrid | `mail_settings.sandbox_mode` |
+//! | Mailgun | `o:testmode` |
+//! | Local, Logger | Always (nothing leaves the process) |
+//!
+//! Other providers fail with [`MailError::Configuration`] in sandbox mode.
+
+use std::fmt;
+use std::time::Duration;
+#[cfg(not(feature = "wasm"))]
+use std::time::Instant;
+
+use serde_json::json;
+#[cfg(feature = "wasm")]
+use web_time::Instant;
+
+use crate::address::{Address, ToAddress};
+use crate::config;
+use crate::email::Email;
+use crate::error::MailError;
+
+/// Header carrying the probe's ID, so it can be found in an inbox or log.
+pub const SELF_TEST_HEADER: &str = "X-Missive-Self-Test";
+
+/// Providers that never deliver outside the process.
+const OFFLINE_PROVIDERS: &[&str] = &["local", "logger"];
+
+/// A deliverability probe.
+///
+/// [`self_test`] runs one configured from the environment; build one
+/// directly to choose the recipient or sandbox mode in code.
+#[derive(Debug, Clone, Default)]
+pub struct SelfTest {
+    to: Option<Address>,
+    sandbox: bool,
+}
+
+impl SelfTest {
+    /// Create a probe to `EMAIL_FROM`, outside sandbox mode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `EMAIL_SELF_TEST_TO` and `EMAIL_SELF_TEST_SANDBOX` from the
+    /// environment (or the installed [`Config`](crate::Config)).
+    pub fn from_env() -> Result<Self, MailError> {
+        let to = match config::var("EMAIL_SELF_TEST_TO") {
+            Some(to) => Some(Address::parse(&to).map_err(|_| {
+                MailError::Configuration(format!("Invalid EMAIL_SELF_TEST_TO: {}", to))
+            })?),
+            None => None,
+        };
+        let sandbox = config::var("EMAIL_SELF_TEST_SANDBOX").is_some_and(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "true" | "1" | "yes" | "on"
+            )
+        });
+        Ok(Self { to, sandbox })
+    }
+
+    /// Send the probe to this address instead of `EMAIL_FROM`.
+    pub fn to(mut self, to: impl ToAddress) -> Self {
+        self.to = Some(to.to_address());
+        self
+    }
+
+    /// Ask the provider to validate the probe without delivering it.
+    pub fn sandbox(mut self, sandbox: bool) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Send the probe through the global mailer.
+    pub async fn run(&self) -> Result<SelfTestReport, MailError> {
+        let mailer = crate::get_mailer()?;
+        let provider = mailer.provider_name();
+        let to = self
+            .to
+            .clone()
+            .or_else(crate::default_from)
+            .ok_or_else(|| {
+                MailError::Configuration(
+                    "Self-test needs EMAIL_SELF_TEST_TO or EMAIL_FROM".to_string(),
+                )
+            })?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut email = Email::new()
+            .to(to.clone())
+            .subject(format!("missive self-test {}", &id[..8]))
+            .text_body(format!(
+                "This is a deliverability probe sent by missive::self_test() via {}.\n\
+                 Probe ID: {}\n",
+                provider, id
+            ))
+            .header(SELF_TEST_HEADER, id.clone());
+        if self.sandbox {
+            email = sandboxed(email, provider)?;
+        }
+
+        let start = Instant::now();
+        let result = crate::deliver(&email).await?;
+        Ok(SelfTestReport {
+            provider,
+            to,
+            probe_id: id,
+            message_id: result.message_id,
+            sandbox: self.sandbox,
+            elapsed: start.elapsed(),
+        })
+    }
+}
+
+/// Add the provider's sandbox option to `email`.
+fn sandboxed(email: Email, provider: &str) -> Result<Email, MailError> {
+    match provider {
+        "sendgrid" => {
+            Ok(email.provider_option("mail_settings", json!({"sandbox_mode": {"enable": true}})))
+        }
+        "mailgun" => Ok(email.provider_option("sending_options", json!({"testmode": "yes"}))),
+        p if OFFLINE_PROVIDERS.contains(&p) => Ok(email),
+        p => Err(MailError::Configuration(format!(
+            "{} has no sandbox mode; set EMAIL_SELF_TEST_TO instead of EMAIL_SELF_TEST_SANDBOX",
+            p
+        ))),
+    }
+}
+
+/// The outcome of a successful [`self_test`].
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    /// Provider that accepted the probe.
+    pub provider: &'static str,
+    /// Where the probe was sent.
+    pub to: Address,
+    /// Value of the [`SELF_TEST_HEADER`] header on the probe.
+    pub probe_id: String,
+    /// Message ID returned by the provider.
+    pub message_id: String,
+    /// Whether the provider was asked not to deliver the probe.
+    pub sandbox: bool,
+    /// Time from sending until the provider accepted the probe.
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} accepted probe {} for {} in {}ms (message id: {})",
+            self.provider,
+            self.probe_id,
+            self.to.email,
+            self.elapsed.as_millis(),
+            self.message_id
+        )?;
+        if self.sandbox {
+            write!(f, " [sandbox]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Send a probe email through the global mailer and report the result.
+///
+/// The probe goes to `EMAIL_SELF_TEST_TO` (default `EMAIL_FROM`). With
+/// `EMAIL_SELF_TEST_SANDBOX=true`, SendGrid and Mailgun accept it without
+/// delivering it; see [`SelfTest`] to configure the probe in code.
+///
+/// ```rust,ignore
+/// // In a post-deploy check
+/// let report = missive::self_test().await?;
+/// tracing::info!("{}", report);
+/// ```
+pub async fn self_test() -> Result<SelfTestReport, MailError> {
+    SelfTest::from_env()?.run().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email() -> Email {
+        Email::new().to("user@example.com")
+    }
+
+    #[test]
+    fn test_sandboxed_options() {
+        let sendgrid = sandboxed(email(), "sendgrid").unwrap();
+        assert_eq!(
+            sendgrid.provider_options["mail_settings"]["sandbox_mode"]["enable"],
+            true
+        );
+        let mailgun = sandboxed(email(), "mailgun").unwrap();
+        assert_eq!(
+            mailgun.provider_options["sending_options"]["testmode"],
+            "yes"
+        );
+        assert!(sandboxed(email(), "local")
+            .unwrap()
+            .provider_options
+            .is_empty());
+        assert!(matches!(
+            sandboxed(email(), "resend"),
+            Err(MailError::Configuration(_))
+        ));
+    }
+}
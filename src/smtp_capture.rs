@@ -0,0 +1,517 @@
+//! Development SMTP capture server.
+//!
+//! Accepts SMTP connections on a local address, parses each message into an
+//! [`Email`], and stores it in a [`Storage`] backend. Point legacy code or
+//! external tools that only speak SMTP at it, and their mail shows up in the
+//! same preview UI as `LocalMailer` emails.
+//!
+//! Nothing is relayed; every message is accepted and kept. Authentication and
+//! TLS are not supported, so bind to localhost only.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use missive::smtp_capture::SmtpCaptureServer;
+//!
+//! // Capture into the same storage as EMAIL_PROVIDER=local
+//! SmtpCaptureServer::local("127.0.0.1:1025")?.spawn();
+//!
+//! // Then point SMTP clients at localhost:1025
+//! ```
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use mail_parser::{Address as ParsedAddress, MessageParser, MimeHeaders};
+
+use crate::address::Address;
+use crate::attachment::Attachment;
+use crate::email::Email;
+use crate::storage::Storage;
+
+/// Largest message accepted, in bytes (advertised via `SIZE`).
+pub const MAX_MESSAGE_SIZE: usize = 25 * 1024 * 1024;
+
+/// Headers mapped onto [`Email`] fields rather than kept as custom headers.
+const STRUCTURAL_HEADERS: &[&str] = &[
+    "from",
+    "to",
+    "cc",
+    "bcc",
+    "reply-to",
+    "subject",
+    "date",
+    "mime-version",
+    "content-type",
+    "content-transfer-encoding",
+];
+
+/// Signed headers kept byte for byte in [`Email::raw_headers`], so a relayed
+/// message still carries a verifiable ARC chain and DKIM signature.
+const SIGNATURE_HEADERS: &[&str] = &[
+    "arc-seal",
+    "arc-message-signature",
+    "arc-authentication-results",
+    "dkim-signature",
+];
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// A local SMTP listener that stores every received message.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use missive::MemoryStorage;
+/// use missive::smtp_capture::SmtpCaptureServer;
+///
+/// let storage = MemoryStorage::shared();
+/// let server = SmtpCaptureServer::new("127.0.0.1:1025", storage.clone())?;
+/// server.spawn();
+/// ```
+pub struct SmtpCaptureServer {
+    listener: TcpListener,
+    storage: Arc<dyn Storage>,
+    smtputf8: bool,
+}
+
+impl SmtpCaptureServer {
+    /// Create a capture server bound to the given address.
+    ///
+    /// Accepts any storage backend, including `Arc<MemoryStorage>` and `Arc<dyn Storage>`.
+    pub fn new(addr: &str, storage: impl Storage + 'static) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            storage: Arc::new(storage),
+            smtputf8: true,
+        })
+    }
+
+    /// Create a capture server for the global LocalMailer storage.
+    ///
+    /// Captured mail appears alongside emails sent with `EMAIL_PROVIDER=local`.
+    pub fn local(addr: &str) -> io::Result<Self> {
+        Self::new(addr, crate::shared_local_storage())
+    }
+
+    /// Advertise the SMTPUTF8 extension (default: true).
+    ///
+    /// Turn off to see how clients handle internationalized addresses on
+    /// servers that only accept ASCII.
+    pub fn smtputf8(mut self, enabled: bool) -> Self {
+        self.smtputf8 = enabled;
+        self
+    }
+
+    /// The address the server is listening on.
+    ///
+    /// Useful when binding to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Run the server, blocking the current thread.
+    ///
+    /// Each connection is handled on its own thread.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let storage = Arc::clone(&self.storage);
+            let smtputf8 = self.smtputf8;
+            thread::spawn(move || {
+                let _ = handle_connection(stream, storage.as_ref(), smtputf8);
+            });
+        }
+        Ok(())
+    }
+
+    /// Spawn the server in a background thread.
+    ///
+    /// Like the preview server, this is fire-and-forget and runs until the
+    /// process exits.
+    pub fn spawn(self) {
+        thread::spawn(move || {
+            let _ = self.run();
+        });
+    }
+}
+
+/// Parse a raw RFC 5322 message into an [`Email`].
+///
+/// Returns `None` if the message cannot be parsed. Envelope recipients are
+/// not known here, so `bcc` is left empty. ARC and DKIM signature headers are
+/// kept as received in [`Email::raw_headers`], so forwarding the email sends
+/// them unchanged.
+pub fn parse_message(raw: &[u8]) -> Option<Email> {
+    let message = MessageParser::default().parse(raw)?;
+    let mut email = Email::new();
+
+    email.from = message.from().and_then(|a| addresses(a).into_iter().next());
+    email.to = message.to().map(addresses).unwrap_or_default();
+    email.cc = message.cc().map(addresses).unwrap_or_default();
+    email.bcc = message.bcc().map(addresses).unwrap_or_default();
+    email.reply_to = message.reply_to().map(addresses).unwrap_or_default();
+    email.subject = message.subject().unwrap_or_default().to_string();
+
+    email.text_body = message
+        .text_part(0)
+        .filter(|part| !part.is_text_html())
+        .and_then(|part| part.text_contents())
+        .map(String::from);
+    email.html_body = message
+        .html_part(0)
+        .filter(|part| part.is_text_html())
+        .and_then(|part| part.text_contents())
+        .map(String::from);
+
+    for part in message.attachments() {
+        let content_type = part
+            .content_type()
+            .map(|ct| match ct.subtype() {
+                Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                None => ct.ctype().to_string(),
+            })
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let filename = part.attachment_name().unwrap_or("attachment").to_string();
+
+        let mut attachment =
+            Attachment::from_bytes(filename, part.contents().to_vec()).content_type(content_type);
+        if part.content_disposition().is_some_and(|d| d.is_inline()) {
+            attachment = attachment.inline();
+        }
+        if let Some(cid) = part.content_id() {
+            attachment = attachment.content_id(cid);
+        }
+        email.attachments.push(attachment);
+    }
+
+    for (name, value) in message.headers_raw() {
+        if SIGNATURE_HEADERS
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h))
+        {
+            email.raw_headers.push(raw_field(name, value));
+            continue;
+        }
+        if STRUCTURAL_HEADERS
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h))
+        {
+            continue;
+        }
+        email.headers.insert(name.to_string(), unfold(value));
+    }
+
+    Some(email)
+}
+
+// ============================================================================
+// SMTP Session
+// ============================================================================
+
+/// Envelope collected between `MAIL FROM` and the end of `DATA`.
+#[derive(Default)]
+struct Envelope {
+    from: Option<String>,
+    recipients: Vec<String>,
+}
+
+fn handle_connection(stream: TcpStream, storage: &dyn Storage, smtputf8: bool) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut envelope = Envelope::default();
+    let mut line = String::new();
+
+    reply(&mut writer, "220 localhost missive SMTP capture ready")?;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let command = line.trim_end();
+        let verb = command
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        let arg = command.get(verb.len()..).unwrap_or_default().trim();
+
+        match verb.as_str() {
+            "EHLO" => reply(
+                &mut writer,
+                &format!(
+                    "250-localhost\r\n250-8BITMIME\r\n{}250 SIZE {}",
+                    if smtputf8 { "250-SMTPUTF8\r\n" } else { "" },
+                    MAX_MESSAGE_SIZE
+                ),
+            )?,
+            "HELO" => reply(&mut writer, "250 localhost")?,
+            "MAIL" => match path_arg(arg, "FROM:") {
+                Some(from) => {
+                    envelope = Envelope {
+                        from: Some(from),
+                        recipients: Vec::new(),
+                    };
+                    reply(&mut writer, "250 OK")?;
+                }
+                None => reply(&mut writer, "501 Syntax: MAIL FROM:<address>")?,
+            },
+            "RCPT" => match path_arg(arg, "TO:") {
+                Some(recipient) if envelope.from.is_some() => {
+                    envelope.recipients.push(recipient);
+                    reply(&mut writer, "250 OK")?;
+                }
+                Some(_) => reply(&mut writer, "503 Need MAIL command")?,
+                None => reply(&mut writer, "501 Syntax: RCPT TO:<address>")?,
+            },
+            "DATA" if envelope.recipients.is_empty() => {
+                reply(&mut writer, "503 Need RCPT command")?
+            }
+            "DATA" => {
+                reply(&mut writer, "354 End data with <CR><LF>.<CR><LF>")?;
+                let Some(data) = read_data(&mut reader)? else {
+                    reply(&mut writer, "552 Message exceeds maximum size")?;
+                    envelope = Envelope::default();
+                    continue;
+                };
+
+                match parse_message(&data) {
+                    Some(email) => {
+                        let id = storage.push(with_envelope(email, &envelope));
+                        reply(&mut writer, &format!("250 OK queued as {}", id))?;
+                    }
+                    None => reply(&mut writer, "554 Message could not be parsed")?,
+                }
+                envelope = Envelope::default();
+            }
+            "RSET" => {
+                envelope = Envelope::default();
+                reply(&mut writer, "250 OK")?;
+            }
+            "NOOP" => reply(&mut writer, "250 OK")?,
+            "QUIT" => {
+                reply(&mut writer, "221 Bye")?;
+                return Ok(());
+            }
+            _ => reply(&mut writer, "502 Command not implemented")?,
+        }
+    }
+}
+
+fn reply(writer: &mut impl Write, message: &str) -> io::Result<()> {
+    writer.write_all(message.as_bytes())?;
+    writer.write_all(b"\r\n")?;
+    writer.flush()
+}
+
+/// Read a `DATA` payload up to the terminating `.` line, undoing dot-stuffing.
+///
+/// Returns `None` if the message is larger than [`MAX_MESSAGE_SIZE`]; the rest
+/// of the payload is still consumed so the session stays in sync.
+fn read_data(reader: &mut impl BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut data = Vec::new();
+    let mut line = Vec::new();
+    let mut too_large = false;
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if line == b".\r\n" || line == b".\n" {
+            break;
+        }
+        let content = line.strip_prefix(b".").unwrap_or(&line);
+        if data.len() + content.len() > MAX_MESSAGE_SIZE {
+            too_large = true;
+        }
+        if !too_large {
+            data.extend_from_slice(content);
+        }
+    }
+
+    Ok((!too_large).then_some(data))
+}
+
+/// Extract the address from `FROM:<addr>` / `TO:<addr>`, ignoring parameters.
+fn path_arg(arg: &str, prefix: &str) -> Option<String> {
+    let rest = arg.get(..prefix.len())?;
+    if !rest.eq_ignore_ascii_case(prefix) {
+        return None;
+    }
+    let path = arg[prefix.len()..].trim_start();
+    let path = path.split_whitespace().next().unwrap_or_default();
+    Some(
+        path.trim_start_matches('<')
+            .trim_end_matches('>')
+            .to_string(),
+    )
+}
+
+/// Fill in anything the headers don't say from the SMTP envelope.
+///
+/// Envelope recipients missing from `To`/`Cc` were Bcc'd.
+fn with_envelope(mut email: Email, envelope: &Envelope) -> Email {
+    if email.from.is_none() {
+        email.from = envelope
+            .from
+            .as_deref()
+            .filter(|from| !from.is_empty())
+            .map(Address::new);
+    }
+
+    // A bounce address that differs from the From header
+    if let Some(sender) = envelope.from.as_deref().filter(|from| !from.is_empty()) {
+        let same = email
+            .from
+            .as_ref()
+            .is_some_and(|from| from.email.eq_ignore_ascii_case(sender));
+        if !same {
+            email.return_path = Some(Address::new(sender));
+        }
+    }
+
+    for recipient in &envelope.recipients {
+        let listed = email
+            .to
+            .iter()
+            .chain(&email.cc)
+            .chain(&email.bcc)
+            .any(|a| a.email.eq_ignore_ascii_case(recipient));
+        if !listed {
+            email.bcc.push(Address::new(recipient.as_str()));
+        }
+    }
+
+    email
+}
+
+fn addresses(parsed: &ParsedAddress<'_>) -> Vec<Address> {
+    parsed
+        .iter()
+        .filter_map(|addr| {
+            let email = addr.address()?;
+            Some(match addr.name() {
+                Some(name) => Address::with_name(name, email),
+                None => Address::new(email),
+            })
+        })
+        .collect()
+}
+
+/// Unfold a raw header value onto one line.
+fn unfold(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Rebuild a header field as received, with CRLF line breaks.
+fn raw_field(name: &str, value: &str) -> String {
+    let value = value
+        .trim_end_matches(['\r', '\n'])
+        .replace("\r\n", "\n")
+        .replace('\n', "\r\n");
+    format!("{}:{}", name, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &str = "From: Alice <alice@example.com>\r\n\
+        To: bob@example.com\r\n\
+        Subject: Hello\r\n\
+        X-Campaign: spring\r\n\
+        MIME-Version: 1.0\r\n\
+        Content-Type: multipart/alternative; boundary=\"b\"\r\n\
+        \r\n\
+        --b\r\n\
+        Content-Type: text/plain\r\n\
+        \r\n\
+        Hi Bob\r\n\
+        --b\r\n\
+        Content-Type: text/html\r\n\
+        \r\n\
+        <p>Hi Bob</p>\r\n\
+        --b--\r\n";
+
+    #[test]
+    fn test_parse_message() {
+        let email = parse_message(RAW.as_bytes()).unwrap();
+
+        let from = email.from.unwrap();
+        assert_eq!(from.email, "alice@example.com");
+        assert_eq!(from.name.as_deref(), Some("Alice"));
+        assert_eq!(email.to[0].email, "bob@example.com");
+        assert_eq!(email.subject, "Hello");
+        assert_eq!(email.text_body.as_deref().map(str::trim), Some("Hi Bob"));
+        assert_eq!(
+            email.html_body.as_deref().map(str::trim),
+            Some("<p>Hi Bob</p>")
+        );
+        assert_eq!(email.headers.get("X-Campaign").unwrap(), "spring");
+        assert!(!email.headers.contains_key("Content-Type"));
+    }
+
+    #[test]
+    fn test_parse_keeps_signatures_verbatim() {
+        let raw = format!(
+            "ARC-Seal: i=2; a=rsa-sha256; cv=pass;\r\n\tb=c2Vjb25k\r\n\
+             ARC-Seal: i=1; a=rsa-sha256; cv=none;\r\n\tb=Zmlyc3Q=\r\n\
+             DKIM-Signature: v=1; d=example.com;\r\n  h=from:to; b=ZGtpbQ==\r\n{}",
+            RAW
+        );
+        let email = parse_message(raw.as_bytes()).unwrap();
+
+        assert_eq!(
+            email.raw_headers,
+            [
+                "ARC-Seal: i=2; a=rsa-sha256; cv=pass;\r\n\tb=c2Vjb25k",
+                "ARC-Seal: i=1; a=rsa-sha256; cv=none;\r\n\tb=Zmlyc3Q=",
+                "DKIM-Signature: v=1; d=example.com;\r\n  h=from:to; b=ZGtpbQ==",
+            ]
+        );
+        assert!(email.get_header("ARC-Seal").is_none());
+        assert!(email.validate_headers().is_ok());
+    }
+
+    #[test]
+    fn test_envelope_adds_bcc() {
+        let email = parse_message(RAW.as_bytes()).unwrap();
+        let envelope = Envelope {
+            from: Some("bounce@example.com".into()),
+            recipients: vec!["BOB@example.com".into(), "audit@example.com".into()],
+        };
+
+        let email = with_envelope(email, &envelope);
+        assert_eq!(email.from.unwrap().email, "alice@example.com");
+        assert_eq!(email.bcc.len(), 1);
+        assert_eq!(email.bcc[0].email, "audit@example.com");
+    }
+
+    #[test]
+    fn test_read_data_unstuffs_dots() {
+        let mut input = "line one\r\n..dotted\r\n.\r\nQUIT\r\n".as_bytes();
+        let data = read_data(&mut input).unwrap().unwrap();
+        assert_eq!(data, b"line one\r\n.dotted\r\n");
+    }
+
+    #[test]
+    fn test_path_arg() {
+        assert_eq!(
+            path_arg("FROM:<a@example.com> SIZE=100", "FROM:").as_deref(),
+            Some("a@example.com")
+        );
+        assert_eq!(
+            path_arg("to: <b@example.com>", "TO:").as_deref(),
+            Some("b@example.com")
+        );
+        assert_eq!(path_arg("FROM:<>", "FROM:").as_deref(), Some(""));
+        assert_eq!(path_arg("<a@example.com>", "TO:"), None);
+    }
+}
@@ -1,9 +1,18 @@
 //! Storage trait and implementations for local/test mailers.
+//!
+//! [`Storage`] is object-safe, so custom backends can be shared as
+//! `Arc<dyn Storage>` and used to power the preview UI. Backends with async
+//! clients can implement [`AsyncStorage`] instead; every `Storage` is also an
+//! `AsyncStorage`.
 
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
+use crate::address::Address;
 use crate::email::Email;
 
 /// A stored email with metadata.
@@ -15,9 +24,216 @@ pub struct StoredEmail {
     pub email: Email,
     /// When the email was "sent" (stored).
     pub sent_at: DateTime<Utc>,
+    /// Whether the email has been marked read, e.g. opened in the preview UI.
+    pub read: bool,
+}
+
+/// Headers whose values change on every send and are redacted from snapshots.
+const VOLATILE_HEADERS: &[&str] = &["date", "message-id", "x-message-id"];
+
+impl StoredEmail {
+    /// Render a stable, human-readable snapshot of this email.
+    ///
+    /// The output excludes the storage ID and timestamp, lists headers in
+    /// sorted order, redacts volatile headers (`Date`, `Message-ID`), and
+    /// shows attachments by name, type and size rather than content. Use it
+    /// with snapshot testing tools such as `insta`:
+    ///
+    /// ```rust,ignore
+    /// insta::assert_snapshot!(mailer.last_email().unwrap().to_snapshot());
+    /// ```
+    pub fn to_snapshot(&self) -> String {
+        let email = &self.email;
+        let mut out = String::new();
+        let addresses = |list: &[Address]| {
+            list.iter()
+                .map(|a| a.formatted())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        if let Some(from) = &email.from {
+            out.push_str(&format!("From: {}\n", from.formatted()));
+        }
+        for (label, list) in [
+            ("To", &email.to),
+            ("Cc", &email.cc),
+            ("Bcc", &email.bcc),
+            ("Reply-To", &email.reply_to),
+        ] {
+            if !list.is_empty() {
+                out.push_str(&format!("{}: {}\n", label, addresses(list)));
+            }
+        }
+        out.push_str(&format!("Subject: {}\n", email.subject));
+
+        let mut headers: Vec<_> = email.headers.iter().collect();
+        headers.sort_by_key(|(name, _)| name.to_ascii_lowercase());
+        for (name, value) in headers {
+            let value = if VOLATILE_HEADERS
+                .iter()
+                .any(|h| name.eq_ignore_ascii_case(h))
+            {
+                "[redacted]"
+            } else {
+                value.as_str()
+            };
+            out.push_str(&format!("{}: {}\n", name, value));
+        }
+
+        if let Some(text) = &email.text_body {
+            out.push_str(&format!("\n--- text ---\n{}\n", text.trim_end()));
+        }
+        if let Some(html) = &email.html_body {
+            out.push_str(&format!("\n--- html ---\n{}\n", html.trim_end()));
+        }
+
+        if !email.attachments.is_empty() {
+            out.push_str("\n--- attachments ---\n");
+            for attachment in &email.attachments {
+                let size = attachment.get_size().unwrap_or_else(|_| attachment.size());
+                out.push_str(&format!(
+                    "{} ({}, {} bytes",
+                    attachment.filename, attachment.content_type, size
+                ));
+                if attachment.is_inline() {
+                    out.push_str(", inline");
+                }
+                if let Some(cid) = &attachment.content_id {
+                    out.push_str(&format!(", cid:{}", cid));
+                }
+                out.push_str(")\n");
+            }
+        }
+
+        out
+    }
+}
+
+/// Criteria for [`Storage::find`].
+///
+/// All set criteria must match. Text comparisons are case-insensitive.
+///
+/// ```rust
+/// use missive::EmailFilter;
+///
+/// let filter = EmailFilter::new()
+///     .to("user@example.com")
+///     .subject("welcome");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmailFilter {
+    to: Option<String>,
+    from: Option<String>,
+    subject: Option<String>,
+    body: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl EmailFilter {
+    /// Create a filter that matches every email.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match emails with this address in `to`, `cc` or `bcc`.
+    pub fn to(mut self, email: impl Into<String>) -> Self {
+        self.to = Some(email.into().to_lowercase());
+        self
+    }
+
+    /// Match emails from this address.
+    pub fn from(mut self, email: impl Into<String>) -> Self {
+        self.from = Some(email.into().to_lowercase());
+        self
+    }
+
+    /// Match emails whose subject contains this text.
+    pub fn subject(mut self, text: impl Into<String>) -> Self {
+        self.subject = Some(text.into().to_lowercase());
+        self
+    }
+
+    /// Match emails whose text or HTML body contains this text.
+    pub fn body(mut self, text: impl Into<String>) -> Self {
+        self.body = Some(text.into().to_lowercase());
+        self
+    }
+
+    /// Match emails stored at or after this time.
+    pub fn since(mut self, time: DateTime<Utc>) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Match emails stored before this time.
+    pub fn until(mut self, time: DateTime<Utc>) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Check whether a stored email matches.
+    pub fn matches(&self, stored: &StoredEmail) -> bool {
+        let email = &stored.email;
+        let contains = |haystack: Option<&String>, needle: &str| {
+            haystack.is_some_and(|h| h.to_lowercase().contains(needle))
+        };
+
+        if let Some(to) = &self.to {
+            if !email
+                .all_recipients()
+                .iter()
+                .any(|a| a.email.eq_ignore_ascii_case(to))
+            {
+                return false;
+            }
+        }
+        if let Some(from) = &self.from {
+            if !email
+                .from
+                .as_ref()
+                .is_some_and(|a| a.email.eq_ignore_ascii_case(from))
+            {
+                return false;
+            }
+        }
+        if let Some(subject) = &self.subject {
+            if !contains(Some(&email.subject), subject) {
+                return false;
+            }
+        }
+        if let Some(body) = &self.body {
+            if !contains(email.text_body.as_ref(), body)
+                && !contains(email.html_body.as_ref(), body)
+            {
+                return false;
+            }
+        }
+        if self.since.is_some_and(|since| stored.sent_at < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| stored.sent_at >= until) {
+            return false;
+        }
+        true
+    }
 }
 
 /// Trait for email storage backends.
+///
+/// Implement this to back `LocalMailer` previews with your own store
+/// (Redis, SQLite, S3, ...). Methods are synchronous; the Axum and Actix
+/// preview adapters call them on a blocking thread pool, so implementations
+/// may perform blocking I/O.
+///
+/// ```rust,ignore
+/// use std::sync::Arc;
+/// use missive::{Storage, preview::mailbox_router};
+///
+/// let storage: Arc<dyn Storage> = Arc::new(RedisStorage::connect(url)?);
+/// let app = Router::new().nest("/dev/mailbox", mailbox_router(storage));
+/// ```
 pub trait Storage: Send + Sync {
     /// Store an email and return its ID.
     fn push(&self, email: Email) -> String;
@@ -42,16 +258,139 @@ pub trait Storage: Send + Sync {
 
     /// Remove and return all stored emails.
     fn flush(&self) -> Vec<StoredEmail>;
+
+    /// Get the emails matching `filter`, newest first.
+    ///
+    /// The default implementation filters [`all()`](Storage::all); backends
+    /// with an index should override it.
+    fn find(&self, filter: &EmailFilter) -> Vec<StoredEmail> {
+        self.all()
+            .into_iter()
+            .filter(|e| filter.matches(e))
+            .collect()
+    }
+
+    /// Get up to `limit` emails, newest first, skipping the first `offset`.
+    ///
+    /// The default implementation slices [`all()`](Storage::all); backends
+    /// that can page natively should override it.
+    fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail> {
+        self.all().into_iter().skip(offset).take(limit).collect()
+    }
+
+    /// Mark an email as read. Returns `false` if there is no such email.
+    ///
+    /// The default implementation doesn't track read state and always
+    /// returns `false`.
+    fn mark_read(&self, _id: &str) -> bool {
+        false
+    }
+
+    /// Get the count of emails not yet marked read.
+    ///
+    /// The default implementation counts [`all()`](Storage::all).
+    fn unread_count(&self) -> usize {
+        self.all().iter().filter(|e| !e.read).count()
+    }
+
+    /// Names of the mailboxes this storage is split into, if any.
+    ///
+    /// The preview UI shows a mailbox switcher when this is non-empty.
+    fn mailboxes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get the mailbox called `name`, if this storage is split into mailboxes.
+    fn mailbox(&self, _name: &str) -> Option<Arc<dyn Storage>> {
+        None
+    }
+}
+
+/// Async counterpart of [`Storage`] for backends with async clients.
+///
+/// Every [`Storage`] implements this by calling its synchronous methods
+/// directly, so code written against `AsyncStorage` accepts both kinds of
+/// backend. Implement it directly only for backends that do not implement
+/// `Storage`.
+#[async_trait]
+pub trait AsyncStorage: Send + Sync {
+    /// Store an email and return its ID.
+    async fn push(&self, email: Email) -> String;
+
+    /// Get an email by ID.
+    async fn get(&self, id: &str) -> Option<StoredEmail>;
+
+    /// Get the emails matching `filter`, newest first.
+    async fn find(&self, filter: &EmailFilter) -> Vec<StoredEmail>;
+
+    /// Get up to `limit` emails, newest first, skipping the first `offset`.
+    async fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail>;
+
+    /// Get the count of stored emails.
+    async fn count(&self) -> usize;
+
+    /// Delete an email by ID.
+    async fn delete(&self, id: &str) -> bool;
+
+    /// Clear all stored emails.
+    async fn clear(&self);
+}
+
+#[async_trait]
+impl<S: Storage + ?Sized> AsyncStorage for S {
+    async fn push(&self, email: Email) -> String {
+        Storage::push(self, email)
+    }
+
+    async fn get(&self, id: &str) -> Option<StoredEmail> {
+        Storage::get(self, id)
+    }
+
+    async fn find(&self, filter: &EmailFilter) -> Vec<StoredEmail> {
+        Storage::find(self, filter)
+    }
+
+    async fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail> {
+        Storage::page(self, offset, limit)
+    }
+
+    async fn count(&self) -> usize {
+        Storage::count(self)
+    }
+
+    async fn delete(&self, id: &str) -> bool {
+        Storage::delete(self, id)
+    }
+
+    async fn clear(&self) {
+        Storage::clear(self)
+    }
 }
 
 /// Thread-safe in-memory storage for emails.
 ///
-/// Used by `LocalMailer` for development and testing.
+/// Used by `LocalMailer` for development and testing. Unbounded by default;
+/// long-running dev servers can cap it by count, size or age, evicting the
+/// oldest emails first:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use missive::MemoryStorage;
+///
+/// let storage = MemoryStorage::with_capacity(500)
+///     .max_bytes(50 * 1024 * 1024)
+///     .ttl(Duration::from_secs(3600));
+/// ```
 #[derive(Debug, Default)]
 pub struct MemoryStorage {
     emails: RwLock<HashMap<String, StoredEmail>>,
     /// Order of email IDs for maintaining insertion order.
-    order: RwLock<Vec<String>>,
+    order: RwLock<VecDeque<String>>,
+    /// Approximate size of all stored emails, in bytes.
+    bytes: AtomicUsize,
+    max_emails: Option<usize>,
+    max_bytes: Option<usize>,
+    ttl: Option<Duration>,
 }
 
 impl MemoryStorage {
@@ -64,10 +403,118 @@ impl MemoryStorage {
     pub fn shared() -> Arc<Self> {
         Arc::new(Self::new())
     }
+
+    /// Create storage that keeps at most `max_emails` emails.
+    ///
+    /// Count and size limits never evict the newest email, so the last
+    /// delivery can always be inspected.
+    pub fn with_capacity(max_emails: usize) -> Self {
+        Self {
+            max_emails: Some(max_emails),
+            ..Self::default()
+        }
+    }
+
+    /// Create storage with limits from the environment.
+    ///
+    /// Reads `MISSIVE_LOCAL_MAX_EMAILS`, `MISSIVE_LOCAL_MAX_BYTES` and
+    /// `MISSIVE_LOCAL_TTL` (seconds). Unset or invalid values leave that
+    /// limit off.
+    pub fn from_env() -> Self {
+        let limit =
+            |key: &str| crate::config::var(key).and_then(|v| v.trim().parse::<usize>().ok());
+        Self {
+            max_emails: limit("MISSIVE_LOCAL_MAX_EMAILS"),
+            max_bytes: limit("MISSIVE_LOCAL_MAX_BYTES"),
+            ttl: limit("MISSIVE_LOCAL_TTL").map(|secs| Duration::from_secs(secs as u64)),
+            ..Self::default()
+        }
+    }
+
+    /// Keep at most `max_bytes` of email content (bodies, headers and
+    /// attachment data).
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Drop emails older than `ttl`.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Approximate size of all stored emails, in bytes.
+    pub fn size_bytes(&self) -> usize {
+        self.expire();
+        self.bytes.load(Ordering::SeqCst)
+    }
+
+    /// Remove an email while holding both locks, keeping the byte count in sync.
+    fn remove_locked(
+        &self,
+        emails: &mut HashMap<String, StoredEmail>,
+        id: &str,
+    ) -> Option<StoredEmail> {
+        let stored = emails.remove(id)?;
+        self.bytes
+            .fetch_sub(approximate_size(&stored.email), Ordering::SeqCst);
+        Some(stored)
+    }
+
+    /// Evict the oldest emails until the count and size limits hold.
+    fn evict(&self, emails: &mut HashMap<String, StoredEmail>, order: &mut VecDeque<String>) {
+        let over = |count: usize, bytes: usize| {
+            self.max_emails.is_some_and(|max| count > max)
+                || self.max_bytes.is_some_and(|max| bytes > max)
+        };
+        while order.len() > 1 && over(order.len(), self.bytes.load(Ordering::SeqCst)) {
+            if let Some(id) = order.pop_front() {
+                self.remove_locked(emails, &id);
+            }
+        }
+    }
+
+    /// Drop emails older than the TTL, if one is set.
+    fn expire(&self) {
+        let Some(ttl) = self.ttl else {
+            return;
+        };
+        let Ok(ttl) = chrono::Duration::from_std(ttl) else {
+            return;
+        };
+        let cutoff = Utc::now() - ttl;
+
+        let mut emails = self.emails.write().unwrap();
+        let mut order = self.order.write().unwrap();
+        while let Some(id) = order.front() {
+            if emails.get(id).is_some_and(|e| e.sent_at > cutoff) {
+                break;
+            }
+            if let Some(id) = order.pop_front() {
+                self.remove_locked(&mut emails, &id);
+            }
+        }
+    }
+}
+
+/// Approximate in-memory size of an email's content.
+fn approximate_size(email: &Email) -> usize {
+    email.subject.len()
+        + email.text_body.as_ref().map_or(0, String::len)
+        + email.html_body.as_ref().map_or(0, String::len)
+        + email
+            .headers
+            .iter()
+            .map(|(name, value)| name.len() + value.len())
+            .sum::<usize>()
+        + email.attachments.iter().map(|a| a.size()).sum::<usize>()
 }
 
 impl Storage for MemoryStorage {
     fn push(&self, email: Email) -> String {
+        self.expire();
+
         let id = uuid::Uuid::new_v4().to_string();
         let sent_at = Utc::now();
 
@@ -77,39 +524,46 @@ impl Storage for MemoryStorage {
             .private
             .insert("sent_at".to_string(), serde_json::json!(sent_at.to_rfc3339()));
 
+        let size = approximate_size(&email);
         let stored = StoredEmail {
             id: id.clone(),
             email,
             sent_at,
+            read: false,
         };
 
         {
             let mut emails = self.emails.write().unwrap();
             let mut order = self.order.write().unwrap();
             emails.insert(id.clone(), stored);
-            order.push(id.clone());
+            order.push_back(id.clone());
+            self.bytes.fetch_add(size, Ordering::SeqCst);
+            self.evict(&mut emails, &mut order);
         }
 
         id
     }
 
     fn pop(&self) -> Option<StoredEmail> {
+        self.expire();
         let mut emails = self.emails.write().unwrap();
         let mut order = self.order.write().unwrap();
 
-        if let Some(id) = order.pop() {
-            emails.remove(&id)
+        if let Some(id) = order.pop_back() {
+            self.remove_locked(&mut emails, &id)
         } else {
             None
         }
     }
 
     fn get(&self, id: &str) -> Option<StoredEmail> {
+        self.expire();
         let emails = self.emails.read().unwrap();
         emails.get(id).cloned()
     }
 
     fn all(&self) -> Vec<StoredEmail> {
+        self.expire();
         let emails = self.emails.read().unwrap();
         let order = self.order.read().unwrap();
 
@@ -125,7 +579,7 @@ impl Storage for MemoryStorage {
         let mut emails = self.emails.write().unwrap();
         let mut order = self.order.write().unwrap();
 
-        if emails.remove(id).is_some() {
+        if self.remove_locked(&mut emails, id).is_some() {
             order.retain(|x| x != id);
             true
         } else {
@@ -138,14 +592,17 @@ impl Storage for MemoryStorage {
         let mut order = self.order.write().unwrap();
         emails.clear();
         order.clear();
+        self.bytes.store(0, Ordering::SeqCst);
     }
 
     fn count(&self) -> usize {
+        self.expire();
         let emails = self.emails.read().unwrap();
         emails.len()
     }
 
     fn flush(&self) -> Vec<StoredEmail> {
+        self.expire();
         let mut emails = self.emails.write().unwrap();
         let mut order = self.order.write().unwrap();
 
@@ -159,12 +616,209 @@ impl Storage for MemoryStorage {
         // Clear storage
         emails.clear();
         order.clear();
+        self.bytes.store(0, Ordering::SeqCst);
 
         result
     }
+
+    fn find(&self, filter: &EmailFilter) -> Vec<StoredEmail> {
+        self.expire();
+        let emails = self.emails.read().unwrap();
+        let order = self.order.read().unwrap();
+
+        order
+            .iter()
+            .rev()
+            .filter_map(|id| emails.get(id))
+            .filter(|e| filter.matches(e))
+            .cloned()
+            .collect()
+    }
+
+    fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail> {
+        self.expire();
+        let emails = self.emails.read().unwrap();
+        let order = self.order.read().unwrap();
+
+        order
+            .iter()
+            .rev()
+            .skip(offset)
+            .take(limit)
+            .filter_map(|id| emails.get(id).cloned())
+            .collect()
+    }
+
+    fn mark_read(&self, id: &str) -> bool {
+        let mut emails = self.emails.write().unwrap();
+        emails.get_mut(id).map(|e| e.read = true).is_some()
+    }
+
+    fn unread_count(&self) -> usize {
+        self.expire();
+        let emails = self.emails.read().unwrap();
+        emails.values().filter(|e| !e.read).count()
+    }
+}
+
+/// In-memory storage split into named mailboxes.
+///
+/// Each email goes to the mailbox named by its `X-Missive-Mailbox` header,
+/// else to its tenant's mailbox when sent with
+/// [`deliver_for`](crate::deliver_for), else to `"default"`. Reads cover
+/// every mailbox; the preview UI shows a switcher to view one at a time.
+///
+/// ```rust,ignore
+/// use missive::providers::LocalMailer;
+/// use missive::Mailboxes;
+///
+/// let mailboxes = Mailboxes::shared();
+/// let mailer = LocalMailer::with_mailboxes(mailboxes.clone());
+///
+/// // Or give each tenant its own LocalMailer
+/// let acme = LocalMailer::with_storage(mailboxes.named("acme"));
+/// ```
+///
+/// Mailboxes are created on first use, with limits from `MISSIVE_LOCAL_*`
+/// (see [`MemoryStorage::from_env`]).
+#[derive(Debug, Default)]
+pub struct Mailboxes {
+    mailboxes: RwLock<BTreeMap<String, Arc<MemoryStorage>>>,
+}
+
+impl Mailboxes {
+    /// Header naming the mailbox an email is stored in.
+    pub const HEADER: &'static str = "X-Missive-Mailbox";
+
+    /// Mailbox for emails without a mailbox header or tenant.
+    pub const DEFAULT: &'static str = "default";
+
+    /// Create storage with no mailboxes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create storage wrapped in an Arc for sharing.
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    /// Get the mailbox called `name`, creating it if needed.
+    pub fn named(&self, name: &str) -> Arc<MemoryStorage> {
+        if let Some(mailbox) = self.mailboxes.read().unwrap().get(name) {
+            return Arc::clone(mailbox);
+        }
+        let mut mailboxes = self.mailboxes.write().unwrap();
+        Arc::clone(
+            mailboxes
+                .entry(name.to_string())
+                .or_insert_with(|| Arc::new(MemoryStorage::from_env())),
+        )
+    }
+
+    /// Names of all mailboxes, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.mailboxes.read().unwrap().keys().cloned().collect()
+    }
+
+    /// The mailbox `email` belongs in.
+    fn mailbox_for(email: &Email) -> String {
+        email
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(Self::HEADER))
+            .map(|(_, value)| value.trim().to_string())
+            .or_else(|| {
+                email
+                    .private
+                    .get(crate::tenant::TENANT_KEY)
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            })
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| Self::DEFAULT.to_string())
+    }
+
+    fn each(&self) -> Vec<Arc<MemoryStorage>> {
+        self.mailboxes.read().unwrap().values().cloned().collect()
+    }
+
+    /// Emails from every mailbox, newest first.
+    fn merged(mut emails: Vec<StoredEmail>) -> Vec<StoredEmail> {
+        emails.sort_by_key(|e| std::cmp::Reverse(e.sent_at));
+        emails
+    }
+}
+
+impl Storage for Mailboxes {
+    fn push(&self, email: Email) -> String {
+        Storage::push(&*self.named(&Self::mailbox_for(&email)), email)
+    }
+
+    fn pop(&self) -> Option<StoredEmail> {
+        let (_, newest) = self
+            .each()
+            .into_iter()
+            .filter_map(|m| Storage::page(&*m, 0, 1).pop().map(|e| (e.sent_at, m)))
+            .max_by_key(|(sent_at, _)| *sent_at)?;
+        Storage::pop(&*newest)
+    }
+
+    fn get(&self, id: &str) -> Option<StoredEmail> {
+        self.each().iter().find_map(|m| Storage::get(&**m, id))
+    }
+
+    fn all(&self) -> Vec<StoredEmail> {
+        Self::merged(self.each().iter().flat_map(|m| m.all()).collect())
+    }
+
+    fn delete(&self, id: &str) -> bool {
+        self.each().iter().any(|m| Storage::delete(&**m, id))
+    }
+
+    fn clear(&self) {
+        for mailbox in self.each() {
+            Storage::clear(&*mailbox);
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.each().iter().map(|m| Storage::count(&**m)).sum()
+    }
+
+    fn flush(&self) -> Vec<StoredEmail> {
+        Self::merged(self.each().iter().flat_map(|m| m.flush()).collect())
+    }
+
+    fn find(&self, filter: &EmailFilter) -> Vec<StoredEmail> {
+        Self::merged(
+            self.each()
+                .iter()
+                .flat_map(|m| Storage::find(&**m, filter))
+                .collect(),
+        )
+    }
+
+    fn mark_read(&self, id: &str) -> bool {
+        self.each().iter().any(|m| m.mark_read(id))
+    }
+
+    fn unread_count(&self) -> usize {
+        self.each().iter().map(|m| m.unread_count()).sum()
+    }
+
+    fn mailboxes(&self) -> Vec<String> {
+        self.names()
+    }
+
+    fn mailbox(&self, name: &str) -> Option<Arc<dyn Storage>> {
+        let mailbox = self.mailboxes.read().unwrap().get(name).cloned()?;
+        Some(mailbox)
+    }
 }
 
-impl Storage for Arc<MemoryStorage> {
+// Allow shared storage (including `Arc<dyn Storage>`) to be used directly.
+impl<S: Storage + ?Sized> Storage for Arc<S> {
     fn push(&self, email: Email) -> String {
         (**self).push(email)
     }
@@ -196,11 +850,38 @@ impl Storage for Arc<MemoryStorage> {
     fn flush(&self) -> Vec<StoredEmail> {
         (**self).flush()
     }
+
+    fn find(&self, filter: &EmailFilter) -> Vec<StoredEmail> {
+        (**self).find(filter)
+    }
+
+    fn page(&self, offset: usize, limit: usize) -> Vec<StoredEmail> {
+        (**self).page(offset, limit)
+    }
+
+    fn mark_read(&self, id: &str) -> bool {
+        (**self).mark_read(id)
+    }
+
+    fn unread_count(&self) -> usize {
+        (**self).unread_count()
+    }
+
+    fn mailboxes(&self) -> Vec<String> {
+        (**self).mailboxes()
+    }
+
+    fn mailbox(&self, name: &str) -> Option<Arc<dyn Storage>> {
+        (**self).mailbox(name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    // Import `Storage` but not `AsyncStorage`, so method calls resolve unambiguously.
+    use super::{EmailFilter, Mailboxes, MemoryStorage, Storage};
+    use crate::email::Email;
+    use std::time::Duration;
 
     #[test]
     fn test_memory_storage() {
@@ -274,4 +955,206 @@ mod tests {
         let empty_flush = storage.flush();
         assert!(empty_flush.is_empty());
     }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let storage = MemoryStorage::with_capacity(2);
+        storage.push(Email::new().subject("First"));
+        storage.push(Email::new().subject("Second"));
+        storage.push(Email::new().subject("Third"));
+
+        let subjects: Vec<_> = storage.all().into_iter().map(|e| e.email.subject).collect();
+        assert_eq!(subjects, ["Third", "Second"]);
+    }
+
+    #[test]
+    fn test_max_bytes() {
+        let storage = MemoryStorage::new().max_bytes(10);
+        storage.push(Email::new().subject("aaaaaa"));
+        assert_eq!(storage.size_bytes(), 6);
+
+        storage.push(Email::new().subject("bbbbbb"));
+        assert_eq!(storage.count(), 1);
+        assert_eq!(storage.size_bytes(), 6);
+
+        // The newest email is kept even when it alone is over the limit
+        storage.push(Email::new().subject("c".repeat(20)));
+        assert_eq!(storage.count(), 1);
+        assert_eq!(storage.all()[0].email.subject.len(), 20);
+
+        storage.clear();
+        assert_eq!(storage.size_bytes(), 0);
+    }
+
+    #[test]
+    fn test_ttl() {
+        let storage = MemoryStorage::new().ttl(Duration::ZERO);
+        storage.push(Email::new().subject("Expired"));
+        assert_eq!(storage.count(), 0);
+        assert_eq!(storage.size_bytes(), 0);
+
+        let storage = MemoryStorage::new().ttl(Duration::from_secs(3600));
+        storage.push(Email::new().subject("Fresh"));
+        assert_eq!(storage.count(), 1);
+    }
+
+    #[test]
+    fn test_find() {
+        let storage = MemoryStorage::new();
+        storage.push(
+            Email::new()
+                .from("shop@example.com")
+                .to("a@example.com")
+                .subject("Your receipt")
+                .text_body("Order #42"),
+        );
+        storage.push(
+            Email::new()
+                .from("news@example.com")
+                .to("b@example.com")
+                .cc("a@example.com")
+                .subject("Weekly news"),
+        );
+
+        assert_eq!(
+            storage.find(&EmailFilter::new().to("A@example.com")).len(),
+            2
+        );
+        assert_eq!(
+            storage.find(&EmailFilter::new().subject("RECEIPT")).len(),
+            1
+        );
+        assert_eq!(storage.find(&EmailFilter::new().body("#42")).len(), 1);
+
+        let found = storage.find(
+            &EmailFilter::new()
+                .to("a@example.com")
+                .from("news@example.com"),
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].email.subject, "Weekly news");
+
+        let since = chrono::Utc::now() + chrono::Duration::seconds(1);
+        assert!(storage.find(&EmailFilter::new().since(since)).is_empty());
+        assert_eq!(storage.find(&EmailFilter::new().until(since)).len(), 2);
+    }
+
+    #[test]
+    fn test_page() {
+        let storage = MemoryStorage::new();
+        for i in 0..5 {
+            storage.push(Email::new().subject(format!("Email {}", i)));
+        }
+
+        let subjects = |page: Vec<super::StoredEmail>| {
+            page.into_iter()
+                .map(|e| e.email.subject)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(subjects(storage.page(0, 2)), ["Email 4", "Email 3"]);
+        assert_eq!(subjects(storage.page(4, 2)), ["Email 0"]);
+        assert!(storage.page(5, 2).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_async_storage() {
+        use super::AsyncStorage;
+
+        let storage: std::sync::Arc<dyn AsyncStorage> = std::sync::Arc::new(MemoryStorage::new());
+        let id = storage.push(Email::new().subject("Async")).await;
+
+        assert_eq!(storage.count().await, 1);
+        assert_eq!(storage.get(&id).await.unwrap().email.subject, "Async");
+        assert_eq!(
+            storage
+                .find(&EmailFilter::new().subject("async"))
+                .await
+                .len(),
+            1
+        );
+        assert_eq!(storage.page(0, 10).await.len(), 1);
+        assert!(storage.delete(&id).await);
+        assert_eq!(storage.count().await, 0);
+    }
+
+    #[test]
+    fn test_mark_read() {
+        let storage = MemoryStorage::new();
+        let id = storage.push(Email::new().subject("Test"));
+        storage.push(Email::new().subject("Other"));
+
+        assert!(!storage.get(&id).unwrap().read);
+        assert_eq!(storage.unread_count(), 2);
+
+        assert!(storage.mark_read(&id));
+        assert!(storage.get(&id).unwrap().read);
+        assert_eq!(storage.unread_count(), 1);
+        assert!(!storage.mark_read("missing"));
+    }
+
+    #[test]
+    fn test_mailboxes() {
+        let storage = Mailboxes::new();
+        let first = storage.push(Email::new().subject("First"));
+        std::thread::sleep(Duration::from_millis(2));
+        storage.push(
+            Email::new()
+                .subject("Header")
+                .header("x-missive-mailbox", "acme"),
+        );
+        std::thread::sleep(Duration::from_millis(2));
+        storage.push(
+            Email::new()
+                .subject("Tenant")
+                .put_private(crate::tenant::TENANT_KEY, "globex"),
+        );
+
+        assert_eq!(storage.mailboxes(), vec!["acme", "default", "globex"]);
+        assert_eq!(storage.count(), 3);
+        assert_eq!(storage.named("acme").all()[0].email.subject, "Header");
+        assert_eq!(storage.mailbox("globex").unwrap().count(), 1);
+        assert!(storage.mailbox("initech").is_none());
+
+        // Reads span every mailbox, newest first
+        assert_eq!(storage.get(&first).unwrap().email.subject, "First");
+        assert_eq!(storage.all().last().unwrap().id, first);
+        assert!(storage.delete(&first));
+        assert_eq!(storage.named("default").count(), 0);
+        assert_eq!(storage.pop().unwrap().email.subject, "Tenant");
+
+        storage.clear();
+        assert_eq!(storage.count(), 0);
+        assert_eq!(storage.mailboxes().len(), 3);
+    }
+
+    #[test]
+    fn test_to_snapshot() {
+        let storage = MemoryStorage::new();
+        let id = storage.push(
+            Email::new()
+                .from(("Admin", "admin@example.com"))
+                .to("a@example.com")
+                .to("b@example.com")
+                .subject("Hello")
+                .header("X-Campaign", "spring")
+                .header("Message-ID", "<123@example.com>")
+                .text_body("Hi there\n")
+                .html_body("<p>Hi there</p>")
+                .attachment(crate::Attachment::from_bytes("report.csv", b"a,b".to_vec())),
+        );
+
+        let snapshot = storage.get(&id).unwrap().to_snapshot();
+        assert_eq!(
+            snapshot,
+            "From: Admin <admin@example.com>\n\
+             To: a@example.com, b@example.com\n\
+             Subject: Hello\n\
+             Message-ID: [redacted]\n\
+             X-Campaign: spring\n\
+             \n--- text ---\nHi there\n\
+             \n--- html ---\n<p>Hi there</p>\n\
+             \n--- attachments ---\nreport.csv (text/csv, 3 bytes)\n"
+        );
+        assert!(!snapshot.contains(&id));
+    }
 }
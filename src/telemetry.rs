@@ -0,0 +1,161 @@
+//! Delivery spans and trace context propagation.
+//!
+//! Delivery spans carry OpenTelemetry messaging semantic-convention attributes
+//! (`messaging.system`, `messaging.destination.name`, `messaging.message.id`)
+//! next to missive's own fields, and failures set `otel.status_code`,
+//! `error.type` and an `exception` event. With the `opentelemetry` feature,
+//! HTTP providers also send the current trace context (`traceparent`) using
+//! the globally installed propagator.
+//!
+//! Recipients and the subject are recorded according to the
+//! [`privacy`](crate::privacy) settings.
+
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::DeliveryResult;
+use crate::privacy;
+
+/// Span for a single delivery.
+pub(crate) fn deliver_span(provider: &'static str, email: &Email) -> Span {
+    let span = tracing::info_span!(
+        "missive.deliver",
+        otel.kind = "producer",
+        otel.status_code = Empty,
+        otel.status_message = Empty,
+        provider = provider,
+        to = Empty,
+        subject = Empty,
+        messaging.system = provider,
+        messaging.operation.type = "send",
+        messaging.destination.name = Empty,
+        messaging.message.id = Empty,
+        error.type = Empty,
+    );
+    if span.is_disabled() {
+        return span;
+    }
+    if let Some(to) = privacy::recipients(email.to.iter().map(|a| a.email.as_str())) {
+        span.record("to", tracing::field::debug(&to));
+        span.record("messaging.destination.name", to.join(", ").as_str());
+    }
+    if privacy::log_subjects() {
+        span.record("subject", email.subject.as_str());
+    }
+    span
+}
+
+/// Span for a batch delivery, optionally through a named mailer.
+pub(crate) fn deliver_many_span(
+    provider: &'static str,
+    count: usize,
+    mailer: Option<&str>,
+) -> Span {
+    tracing::info_span!(
+        "missive.deliver_many",
+        otel.kind = "producer",
+        otel.status_code = Empty,
+        otel.status_message = Empty,
+        mailer = mailer,
+        provider = provider,
+        count = count,
+        messaging.system = provider,
+        messaging.operation.type = "send",
+        messaging.batch.message_count = count,
+        error.type = Empty,
+    )
+}
+
+/// Record the outcome of a single delivery on its span.
+pub(crate) fn record_delivery(span: &Span, result: &Result<DeliveryResult, MailError>) {
+    match result {
+        Ok(r) => {
+            span.record("messaging.message.id", r.message_id.as_str());
+            span.in_scope(|| tracing::info!(message_id = %r.message_id, "Email delivered"));
+        }
+        Err(e) => record_error(span, e),
+    }
+}
+
+/// Mark a span as failed and attach the error as an `exception` event.
+pub(crate) fn record_error(span: &Span, error: &MailError) {
+    let message = error.to_string();
+    span.record("otel.status_code", "ERROR");
+    span.record("otel.status_message", message.as_str());
+    span.record("error.type", error.kind());
+    span.in_scope(|| {
+        tracing::error!(
+            error = %message,
+            exception.type = error.kind(),
+            exception.message = %message,
+            "Email delivery failed"
+        )
+    });
+}
+
+/// Count a failed delivery in `missive_errors_total`.
+#[cfg(feature = "metrics")]
+pub(crate) fn record_error_metric(provider: &'static str, error: &MailError, count: u64) {
+    let status = error
+        .status_code()
+        .map_or_else(|| "none".to_string(), |s| s.to_string());
+    metrics::counter!(
+        "missive_errors_total",
+        "provider" => provider,
+        "kind" => error.kind(),
+        "status_code" => status
+    )
+    .increment(count);
+}
+
+/// Trace context headers (e.g. `traceparent`) for an outgoing provider request.
+///
+/// Empty unless the `opentelemetry` feature is enabled and a text map
+/// propagator has been installed with
+/// `opentelemetry::global::set_text_map_propagator`.
+#[cfg(any(
+    feature = "amazon_ses",
+    feature = "brevo",
+    feature = "customerio",
+    feature = "mailgun",
+    feature = "mailjet",
+    feature = "mailtrap",
+    feature = "postmark",
+    feature = "resend",
+    feature = "sendgrid",
+    feature = "unsent",
+    feature = "zeptomail"
+))]
+pub(crate) fn trace_headers() -> reqwest::header::HeaderMap {
+    #[allow(unused_mut)]
+    let mut headers = reqwest::header::HeaderMap::new();
+
+    #[cfg(feature = "opentelemetry")]
+    {
+        use opentelemetry::propagation::Injector;
+        use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+        impl Injector for HeaderInjector<'_> {
+            fn set(&mut self, key: &str, value: String) {
+                if let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(key.as_bytes()),
+                    HeaderValue::from_str(&value),
+                ) {
+                    self.0.insert(name, value);
+                }
+            }
+        }
+
+        let context = Span::current().context();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut HeaderInjector(&mut headers))
+        });
+    }
+
+    headers
+}
@@ -0,0 +1,133 @@
+//! Shared HTML layouts and partials.
+//!
+//! Register the chrome every product email shares (header, footer, branding)
+//! once, then wrap each email's content with [`Email::with_layout`]:
+//!
+//! ```rust,ignore
+//! use missive::templates::layout;
+//!
+//! layout::register_partial("footer", "<footer>Acme Inc. · {{ unsubscribe_url }}</footer>");
+//! layout::register(
+//!     "base",
+//!     "<html><body><h1>{{ subject }}</h1>{{ content }}{{> footer }}</body></html>",
+//! );
+//!
+//! let email = Email::new()
+//!     .to("user@example.com")
+//!     .subject("Your receipt")
+//!     .assign("unsubscribe_url", "https://example.com/unsubscribe")
+//!     .with_layout("base", "<p>Thanks for your order.</p>")?;
+//! ```
+//!
+//! In a layout, `{{ content }}` is the wrapped HTML, `{{ subject }}` the
+//! subject, `{{> name }}` includes a partial and other `{{ key }}`
+//! placeholders are filled from the email's [`assigns`](Email::assign).
+//! Partials can use placeholders too, but can't include other partials.
+
+use std::collections::BTreeMap;
+
+use parking_lot::RwLock;
+use serde_json::Value;
+
+use crate::email::Email;
+use crate::error::MailError;
+
+static LAYOUTS: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+static PARTIALS: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+/// Register a layout, replacing any layout with the same name.
+pub fn register(name: impl Into<String>, html: impl Into<String>) {
+    LAYOUTS.write().insert(name.into(), html.into());
+}
+
+/// Register a partial for `{{> name }}`, replacing any with the same name.
+pub fn register_partial(name: impl Into<String>, html: impl Into<String>) {
+    PARTIALS.write().insert(name.into(), html.into());
+}
+
+/// Remove all layouts and partials.
+pub fn reset() {
+    LAYOUTS.write().clear();
+    PARTIALS.write().clear();
+}
+
+/// Render layout `name` around `content`, with placeholders from `email`.
+pub fn render(name: &str, content: &str, email: &Email) -> Result<String, MailError> {
+    let layout = LAYOUTS
+        .read()
+        .get(name)
+        .cloned()
+        .ok_or_else(|| MailError::TemplateError(format!("unknown layout '{}'", name)))?;
+    let layout = include_partials(name, &layout)?;
+
+    let mut context: serde_json::Map<String, Value> = email
+        .assigns
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    context.insert("subject".into(), email.subject.clone().into());
+    context.insert("content".into(), content.into());
+
+    Ok(crate::personalize::render_str(
+        &layout,
+        &Value::Object(context),
+    ))
+}
+
+/// Replace `{{> partial }}` tags with registered partials.
+fn include_partials(layout: &str, source: &str) -> Result<String, MailError> {
+    let partials = PARTIALS.read();
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("{{>") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 3..start + len].trim();
+        let partial = partials.get(name).ok_or_else(|| {
+            MailError::TemplateError(format!("unknown partial '{}' in layout '{}'", name, layout))
+        })?;
+        out.push_str(&rest[..start]);
+        out.push_str(partial);
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The registry is global, so names are unique per test.
+
+    #[test]
+    fn test_render() {
+        register_partial("test_render_footer", "<footer>{{ company }}</footer>");
+        register(
+            "test_render",
+            "<h1>{{ subject }}</h1>{{ content }}{{> test_render_footer }}",
+        );
+        let email = Email::new().subject("Receipt").assign("company", "Acme");
+
+        let html = render("test_render", "<p>{{ not_rendered }}</p>", &email).unwrap();
+
+        assert_eq!(
+            html,
+            "<h1>Receipt</h1><p>{{ not_rendered }}</p><footer>Acme</footer>"
+        );
+    }
+
+    #[test]
+    fn test_unknown_layout_and_partial() {
+        register("test_unknown_partial", "{{ content }}{{> test_missing }}");
+
+        let err = render("test_missing_layout", "", &Email::new()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unknown layout 'test_missing_layout'"));
+
+        let err = render("test_unknown_partial", "", &Email::new()).unwrap_err();
+        assert!(err.to_string().contains("unknown partial 'test_missing'"));
+    }
+}
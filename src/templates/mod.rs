@@ -0,0 +1,9 @@
+//! Shared layouts and provider-hosted templates.
+//!
+//! See [`layout`] for HTML layouts and partials wrapped around email bodies,
+//! and `remote` (`remote-templates` feature) for listing, creating and
+//! updating templates stored by SendGrid, Postmark, Mailjet and Brevo.
+
+pub mod layout;
+#[cfg(feature = "remote-templates")]
+pub mod remote;
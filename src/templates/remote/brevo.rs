@@ -0,0 +1,152 @@
+//! Brevo template management.
+//!
+//! For reference: [Brevo SMTP templates API](https://developers.brevo.com/reference/gettemplates)
+
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde_json::{json, Value};
+
+use super::{id_string, send, set, RemoteTemplate, TemplateClient, TemplateSource};
+use crate::address::{Address, ToAddress};
+use crate::error::MailError;
+
+const BREVO_BASE_URL: &str = "https://api.brevo.com/v3";
+const PAGE_SIZE: usize = 1000;
+
+/// Manages the account's Brevo transactional templates.
+///
+/// Brevo templates have no text part; `text_body` is ignored.
+pub struct BrevoTemplates {
+    api_key: String,
+    sender: Option<Address>,
+    client: Client,
+    base_url: String,
+}
+
+impl BrevoTemplates {
+    /// Create a client with the given API key.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            sender: None,
+            client: crate::http::default_client(),
+            base_url: BREVO_BASE_URL.to_string(),
+        }
+    }
+
+    /// Sender for newly created templates (required by Brevo).
+    pub fn sender(mut self, sender: impl ToAddress) -> Self {
+        self.sender = Some(sender.to_address());
+        self
+    }
+
+    /// Set a custom base URL (for testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, MailError> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("Api-Key", &self.api_key);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        send("brevo", request).await
+    }
+}
+
+fn body(template: &TemplateSource) -> Value {
+    let mut body = json!({ "templateName": template.name });
+    set(&mut body, "subject", &template.subject);
+    set(&mut body, "htmlContent", &template.html_body);
+    body
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl TemplateClient for BrevoTemplates {
+    async fn list(&self) -> Result<Vec<RemoteTemplate>, MailError> {
+        let mut templates = Vec::new();
+        loop {
+            let path = format!(
+                "/smtp/templates?limit={}&offset={}",
+                PAGE_SIZE,
+                templates.len()
+            );
+            let page = self.request(Method::GET, &path, None).await?;
+            let items = page["templates"].as_array().cloned().unwrap_or_default();
+            for item in &items {
+                let id = item
+                    .get("id")
+                    .and_then(id_string)
+                    .ok_or_else(|| MailError::provider("brevo", "Response is missing id"))?;
+                templates.push(RemoteTemplate {
+                    id,
+                    name: item["name"].as_str().unwrap_or_default().to_string(),
+                });
+            }
+            if items.len() < PAGE_SIZE {
+                return Ok(templates);
+            }
+        }
+    }
+
+    async fn create(&self, template: &TemplateSource) -> Result<RemoteTemplate, MailError> {
+        let sender = self.sender.as_ref().ok_or_else(|| {
+            MailError::Configuration(
+                "Creating Brevo templates requires a sender; use BrevoTemplates::sender".into(),
+            )
+        })?;
+
+        let mut body = body(template);
+        body["isActive"] = json!(true);
+        body["sender"] = match &sender.name {
+            Some(name) => json!({ "email": sender.email, "name": name }),
+            None => json!({ "email": sender.email }),
+        };
+
+        let created = self
+            .request(Method::POST, "/smtp/templates", Some(body))
+            .await?;
+        let id = created
+            .get("id")
+            .and_then(id_string)
+            .ok_or_else(|| MailError::provider("brevo", "Response is missing id"))?;
+        Ok(RemoteTemplate {
+            id,
+            name: template.name.clone(),
+        })
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        template: &TemplateSource,
+    ) -> Result<RemoteTemplate, MailError> {
+        let path = format!("/smtp/templates/{}", id);
+        self.request(Method::PUT, &path, Some(body(template)))
+            .await?;
+        Ok(RemoteTemplate {
+            id: id.to_string(),
+            name: template.name.clone(),
+        })
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "brevo"
+    }
+}
@@ -0,0 +1,137 @@
+//! Mailjet template management.
+//!
+//! For reference: [Mailjet Template API](https://dev.mailjet.com/email/reference/templates/)
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use reqwest::{Client, Method};
+use serde_json::{json, Value};
+
+use super::{id_string, send, set, RemoteTemplate, TemplateClient, TemplateSource};
+use crate::error::MailError;
+
+const MAILJET_API_URL: &str = "https://api.mailjet.com/v3/REST";
+const PAGE_SIZE: usize = 1000;
+
+/// Manages the account's Mailjet templates.
+///
+/// The subject and bodies are stored as the template's detail content.
+pub struct MailjetTemplates {
+    api_key: String,
+    secret_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl MailjetTemplates {
+    /// Create a client with the given API key and secret key.
+    pub fn new(api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            secret_key: secret_key.into(),
+            client: crate::http::default_client(),
+            base_url: MAILJET_API_URL.to_string(),
+        }
+    }
+
+    /// Set a custom base URL (for testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, MailError> {
+        let credentials = format!("{}:{}", self.api_key, self.secret_key);
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header(
+                "Authorization",
+                format!("Basic {}", BASE64.encode(credentials)),
+            );
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        send("mailjet", request).await
+    }
+}
+
+fn content(template: &TemplateSource) -> Value {
+    let mut body = json!({});
+    set(&mut body, "Html-part", &template.html_body);
+    set(&mut body, "Text-part", &template.text_body);
+    if let Some(subject) = &template.subject {
+        body["Headers"] = json!({ "Subject": subject });
+    }
+    body
+}
+
+fn parse(value: &Value) -> Result<RemoteTemplate, MailError> {
+    let id = value
+        .get("ID")
+        .and_then(id_string)
+        .ok_or_else(|| MailError::provider("mailjet", "Response is missing ID"))?;
+    Ok(RemoteTemplate {
+        id,
+        name: value["Name"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl TemplateClient for MailjetTemplates {
+    async fn list(&self) -> Result<Vec<RemoteTemplate>, MailError> {
+        let mut templates = Vec::new();
+        loop {
+            let path = format!(
+                "/template?OwnerType=user&Limit={}&Offset={}",
+                PAGE_SIZE,
+                templates.len()
+            );
+            let page = self.request(Method::GET, &path, None).await?;
+            let items = page["Data"].as_array().cloned().unwrap_or_default();
+            for item in &items {
+                templates.push(parse(item)?);
+            }
+            if items.len() < PAGE_SIZE {
+                return Ok(templates);
+            }
+        }
+    }
+
+    async fn create(&self, template: &TemplateSource) -> Result<RemoteTemplate, MailError> {
+        let body = json!({ "Name": template.name, "OwnerType": "user" });
+        let created = self.request(Method::POST, "/template", Some(body)).await?;
+        let created = parse(&created["Data"][0])?;
+        self.update(&created.id, template).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        template: &TemplateSource,
+    ) -> Result<RemoteTemplate, MailError> {
+        let path = format!("/template/{}/detailcontent", id);
+        self.request(Method::POST, &path, Some(content(template)))
+            .await?;
+        Ok(RemoteTemplate {
+            id: id.to_string(),
+            name: template.name.clone(),
+        })
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "mailjet"
+    }
+}
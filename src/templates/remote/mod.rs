@@ -0,0 +1,362 @@
+//! Manage templates hosted by email providers.
+//!
+//! Keep templates in your repository and deploy them with the rest of your
+//! infrastructure: load them from files, then [`sync`] creates the ones the
+//! provider doesn't have yet and updates the rest, matching by name.
+//!
+//! ```rust,ignore
+//! use missive::templates::remote::{self, PostmarkTemplates, TemplateSource};
+//!
+//! let client = PostmarkTemplates::new(std::env::var("POSTMARK_API_KEY")?);
+//! let templates = TemplateSource::load_dir("emails/templates")?;
+//!
+//! let report = remote::sync(&client, &templates).await?;
+//! println!("{} created, {} updated", report.created.len(), report.updated.len());
+//! ```
+//!
+//! ## Clients
+//!
+//! | Client | Feature Flags | Notes |
+//! |--------|---------------|-------|
+//! | [`SendGridTemplates`] | `remote-templates`, `sendgrid` | Dynamic templates; updates add a new active version |
+//! | [`PostmarkTemplates`] | `remote-templates`, `postmark` | Server token |
+//! | [`MailjetTemplates`] | `remote-templates`, `mailjet` | Content is stored as the template's detail content |
+//! | [`BrevoTemplates`] | `remote-templates`, `brevo` | New templates need a [`sender`](BrevoTemplates::sender) |
+//!
+//! ## File Layout
+//!
+//! [`TemplateSource::load_dir`] groups files by name:
+//!
+//! ```text
+//! emails/templates/
+//!   welcome.subject   # subject line
+//!   welcome.html      # HTML body
+//!   welcome.txt       # text body
+//!   receipt.html
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+
+use crate::error::MailError;
+
+#[cfg(feature = "brevo")]
+mod brevo;
+#[cfg(feature = "brevo")]
+pub use brevo::BrevoTemplates;
+
+#[cfg(feature = "mailjet")]
+mod mailjet;
+#[cfg(feature = "mailjet")]
+pub use mailjet::MailjetTemplates;
+
+#[cfg(feature = "postmark")]
+mod postmark;
+#[cfg(feature = "postmark")]
+pub use postmark::PostmarkTemplates;
+
+#[cfg(feature = "sendgrid")]
+mod sendgrid;
+#[cfg(feature = "sendgrid")]
+pub use sendgrid::SendGridTemplates;
+
+/// A template defined locally, to be created or updated on the provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateSource {
+    /// Template name, used to match remote templates.
+    pub name: String,
+    /// Subject line.
+    pub subject: Option<String>,
+    /// HTML body.
+    pub html_body: Option<String>,
+    /// Plain text body.
+    pub text_body: Option<String>,
+}
+
+impl TemplateSource {
+    /// Create an empty template with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the subject line.
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// Set the HTML body.
+    pub fn html_body(mut self, html: impl Into<String>) -> Self {
+        self.html_body = Some(html.into());
+        self
+    }
+
+    /// Set the plain text body.
+    pub fn text_body(mut self, text: impl Into<String>) -> Self {
+        self.text_body = Some(text.into());
+        self
+    }
+
+    /// Load every template in a directory.
+    ///
+    /// `<name>.subject`, `<name>.html` and `<name>.txt` make up the template
+    /// `<name>`; other files are ignored. Templates are sorted by name.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>, MailError> {
+        let dir = dir.as_ref();
+        let read_error =
+            |e: std::io::Error| MailError::TemplateError(format!("{}: {}", dir.display(), e));
+
+        let mut templates: BTreeMap<String, Self> = BTreeMap::new();
+        for entry in std::fs::read_dir(dir).map_err(read_error)? {
+            let path = entry.map_err(read_error)?.path();
+            let (Some(name), Some(ext)) = (
+                path.file_stem().and_then(|s| s.to_str()),
+                path.extension().and_then(|s| s.to_str()),
+            ) else {
+                continue;
+            };
+            if !matches!(ext, "subject" | "html" | "txt") {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| MailError::TemplateError(format!("{}: {}", path.display(), e)))?;
+            let template = templates
+                .entry(name.to_string())
+                .or_insert_with(|| Self::new(name));
+            match ext {
+                "subject" => template.subject = Some(content.trim().to_string()),
+                "html" => template.html_body = Some(content),
+                _ => template.text_body = Some(content),
+            }
+        }
+        Ok(templates.into_values().collect())
+    }
+}
+
+/// A template stored by the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTemplate {
+    /// Provider's template ID, as used in the `template_id` provider option.
+    pub id: String,
+    /// Template name.
+    pub name: String,
+}
+
+/// Lists, creates and updates a provider's hosted templates.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait TemplateClient: Send + Sync {
+    /// List all templates.
+    async fn list(&self) -> Result<Vec<RemoteTemplate>, MailError>;
+
+    /// Create a template.
+    async fn create(&self, template: &TemplateSource) -> Result<RemoteTemplate, MailError>;
+
+    /// Replace the content of the template with the given ID.
+    async fn update(
+        &self,
+        id: &str,
+        template: &TemplateSource,
+    ) -> Result<RemoteTemplate, MailError>;
+
+    /// Get the provider name (for logging/debugging).
+    fn provider_name(&self) -> &'static str;
+}
+
+/// What [`sync`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Templates that didn't exist remotely.
+    pub created: Vec<RemoteTemplate>,
+    /// Existing templates whose content was replaced.
+    pub updated: Vec<RemoteTemplate>,
+}
+
+/// Create or update each template, matching remote templates by name.
+///
+/// Remote templates that aren't in `templates` are left alone. Stops at the
+/// first error; templates synced before it stay changed.
+pub async fn sync(
+    client: &dyn TemplateClient,
+    templates: &[TemplateSource],
+) -> Result<SyncReport, MailError> {
+    let existing: BTreeMap<String, String> = client
+        .list()
+        .await?
+        .into_iter()
+        .map(|t| (t.name, t.id))
+        .collect();
+
+    let mut report = SyncReport::default();
+    for template in templates {
+        match existing.get(&template.name) {
+            Some(id) => {
+                tracing::info!(provider = client.provider_name(), name = %template.name, "Updating template");
+                report.updated.push(client.update(id, template).await?);
+            }
+            None => {
+                tracing::info!(provider = client.provider_name(), name = %template.name, "Creating template");
+                report.created.push(client.create(template).await?);
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Send a management request and decode its JSON response (`Null` if empty).
+#[cfg(any(
+    feature = "brevo",
+    feature = "mailjet",
+    feature = "postmark",
+    feature = "sendgrid"
+))]
+async fn send(
+    provider: &'static str,
+    request: reqwest::RequestBuilder,
+) -> Result<serde_json::Value, MailError> {
+    let response = request
+        .header("Accept", "application/json")
+        .header("User-Agent", format!("missive/{}", crate::VERSION))
+        .send()
+        .await?;
+
+    let status = response.status();
+    let retry_after = crate::error::retry_after(response.headers());
+    let body = response.text().await?;
+    let json: serde_json::Value = if body.trim().is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_str(&body).unwrap_or(serde_json::Value::Null)
+    };
+
+    if status.is_success() {
+        return Ok(json);
+    }
+
+    let message = ["message", "Message", "ErrorMessage"]
+        .iter()
+        .find_map(|key| json.get(key).and_then(|v| v.as_str()))
+        .or_else(|| json.pointer("/errors/0/message").and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .unwrap_or(body);
+    Err(
+        MailError::provider_with_status(provider, message, status.as_u16())
+            .with_retry_after(retry_after),
+    )
+}
+
+/// A string or numeric ID as a string.
+#[cfg(any(
+    feature = "brevo",
+    feature = "mailjet",
+    feature = "postmark",
+    feature = "sendgrid"
+))]
+fn id_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Set `key` on a JSON object if `value` is present.
+#[cfg(any(
+    feature = "brevo",
+    feature = "mailjet",
+    feature = "postmark",
+    feature = "sendgrid"
+))]
+fn set(object: &mut serde_json::Value, key: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        object[key] = serde_json::Value::String(value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct FakeClient {
+        calls: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl TemplateClient for FakeClient {
+        async fn list(&self) -> Result<Vec<RemoteTemplate>, MailError> {
+            Ok(vec![RemoteTemplate {
+                id: "1".into(),
+                name: "welcome".into(),
+            }])
+        }
+
+        async fn create(&self, template: &TemplateSource) -> Result<RemoteTemplate, MailError> {
+            self.calls.lock().push(format!("create {}", template.name));
+            Ok(RemoteTemplate {
+                id: "2".into(),
+                name: template.name.clone(),
+            })
+        }
+
+        async fn update(
+            &self,
+            id: &str,
+            template: &TemplateSource,
+        ) -> Result<RemoteTemplate, MailError> {
+            self.calls.lock().push(format!("update {}", id));
+            Ok(RemoteTemplate {
+                id: id.into(),
+                name: template.name.clone(),
+            })
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "fake"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync() {
+        let client = FakeClient::default();
+        let templates = [
+            TemplateSource::new("welcome"),
+            TemplateSource::new("receipt"),
+        ];
+
+        let report = sync(&client, &templates).await.unwrap();
+        assert_eq!(report.updated[0].id, "1");
+        assert_eq!(report.created[0].name, "receipt");
+        assert_eq!(*client.calls.lock(), ["update 1", "create receipt"]);
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("missive-templates-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("welcome.subject"), "Welcome, {{name}}\n").unwrap();
+        std::fs::write(dir.join("welcome.html"), "<p>Hi</p>").unwrap();
+        std::fs::write(dir.join("receipt.txt"), "Thanks").unwrap();
+        std::fs::write(dir.join("README.md"), "ignored").unwrap();
+
+        let templates = TemplateSource::load_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            templates,
+            [
+                TemplateSource::new("receipt").text_body("Thanks"),
+                TemplateSource::new("welcome")
+                    .subject("Welcome, {{name}}")
+                    .html_body("<p>Hi</p>"),
+            ]
+        );
+    }
+}
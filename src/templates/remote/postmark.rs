@@ -0,0 +1,121 @@
+//! Postmark template management.
+//!
+//! For reference: [Postmark Templates API](https://postmarkapp.com/developer/api/templates-api)
+
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde_json::{json, Value};
+
+use super::{id_string, send, set, RemoteTemplate, TemplateClient, TemplateSource};
+use crate::error::MailError;
+
+const POSTMARK_API_URL: &str = "https://api.postmarkapp.com";
+const PAGE_SIZE: usize = 500;
+
+/// Manages a Postmark server's templates.
+pub struct PostmarkTemplates {
+    api_token: String,
+    client: Client,
+    base_url: String,
+}
+
+impl PostmarkTemplates {
+    /// Create a client with the given server token.
+    pub fn new(api_token: impl Into<String>) -> Self {
+        Self {
+            api_token: api_token.into(),
+            client: crate::http::default_client(),
+            base_url: POSTMARK_API_URL.to_string(),
+        }
+    }
+
+    /// Set a custom base URL (for testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, MailError> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("X-Postmark-Server-Token", &self.api_token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        send("postmark", request).await
+    }
+}
+
+fn body(template: &TemplateSource) -> Value {
+    let mut body = json!({ "Name": template.name });
+    set(&mut body, "Subject", &template.subject);
+    set(&mut body, "HtmlBody", &template.html_body);
+    set(&mut body, "TextBody", &template.text_body);
+    body
+}
+
+fn parse(value: &Value) -> Result<RemoteTemplate, MailError> {
+    let id = value
+        .get("TemplateId")
+        .and_then(id_string)
+        .ok_or_else(|| MailError::provider("postmark", "Response is missing TemplateId"))?;
+    Ok(RemoteTemplate {
+        id,
+        name: value["Name"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl TemplateClient for PostmarkTemplates {
+    async fn list(&self) -> Result<Vec<RemoteTemplate>, MailError> {
+        let mut templates = Vec::new();
+        loop {
+            let path = format!("/templates?count={}&offset={}", PAGE_SIZE, templates.len());
+            let page = self.request(Method::GET, &path, None).await?;
+            let items = page["Templates"].as_array().cloned().unwrap_or_default();
+            for item in &items {
+                templates.push(parse(item)?);
+            }
+            let total = page["TotalCount"].as_u64().unwrap_or(0) as usize;
+            if items.is_empty() || templates.len() >= total {
+                return Ok(templates);
+            }
+        }
+    }
+
+    async fn create(&self, template: &TemplateSource) -> Result<RemoteTemplate, MailError> {
+        let created = self
+            .request(Method::POST, "/templates", Some(body(template)))
+            .await?;
+        parse(&created)
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        template: &TemplateSource,
+    ) -> Result<RemoteTemplate, MailError> {
+        let path = format!("/templates/{}", id);
+        let updated = self
+            .request(Method::PUT, &path, Some(body(template)))
+            .await?;
+        parse(&updated)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "postmark"
+    }
+}
@@ -0,0 +1,127 @@
+//! SendGrid dynamic template management.
+//!
+//! For reference: [SendGrid Templates API](https://www.twilio.com/docs/sendgrid/api-reference/transactional-templates)
+
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde_json::{json, Value};
+
+use super::{id_string, send, set, RemoteTemplate, TemplateClient, TemplateSource};
+use crate::error::MailError;
+
+const SENDGRID_API_URL: &str = "https://api.sendgrid.com/v3";
+
+/// Manages SendGrid dynamic templates.
+///
+/// Creating a template also creates its first version; updating one adds a
+/// new active version, so earlier content stays in SendGrid's history.
+pub struct SendGridTemplates {
+    api_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl SendGridTemplates {
+    /// Create a client with the given API key.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            client: crate::http::default_client(),
+            base_url: SENDGRID_API_URL.to_string(),
+        }
+    }
+
+    /// Set a custom base URL (for testing).
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Build the HTTP client from `config` (timeouts, proxy, root certificates).
+    pub fn http_config(mut self, config: &crate::http::HttpConfig) -> Self {
+        self.client = config.client();
+        self
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<Value>,
+    ) -> Result<Value, MailError> {
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("Authorization", format!("Bearer {}", self.api_key));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        send("sendgrid", request).await
+    }
+}
+
+fn version(template: &TemplateSource) -> Value {
+    let mut body = json!({ "name": template.name, "active": 1 });
+    set(&mut body, "subject", &template.subject);
+    set(&mut body, "html_content", &template.html_body);
+    set(&mut body, "plain_content", &template.text_body);
+    body
+}
+
+fn parse(value: &Value) -> Result<RemoteTemplate, MailError> {
+    let id = value
+        .get("id")
+        .and_then(id_string)
+        .ok_or_else(|| MailError::provider("sendgrid", "Response is missing id"))?;
+    Ok(RemoteTemplate {
+        id,
+        name: value["name"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl TemplateClient for SendGridTemplates {
+    async fn list(&self) -> Result<Vec<RemoteTemplate>, MailError> {
+        let mut templates = Vec::new();
+        let mut url = format!(
+            "{}/templates?generations=dynamic&page_size=200",
+            self.base_url
+        );
+        loop {
+            let page = self.request(Method::GET, &url, None).await?;
+            for item in page["result"].as_array().into_iter().flatten() {
+                templates.push(parse(item)?);
+            }
+            match page.pointer("/_metadata/next").and_then(|v| v.as_str()) {
+                Some(next) if next != url => url = next.to_string(),
+                _ => return Ok(templates),
+            }
+        }
+    }
+
+    async fn create(&self, template: &TemplateSource) -> Result<RemoteTemplate, MailError> {
+        let url = format!("{}/templates", self.base_url);
+        let body = json!({ "name": template.name, "generation": "dynamic" });
+        let created = parse(&self.request(Method::POST, &url, Some(body)).await?)?;
+        self.update(&created.id, template).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        template: &TemplateSource,
+    ) -> Result<RemoteTemplate, MailError> {
+        let url = format!("{}/templates/{}/versions", self.base_url, id);
+        self.request(Method::POST, &url, Some(version(template)))
+            .await?;
+        Ok(RemoteTemplate {
+            id: id.to_string(),
+            name: template.name.clone(),
+        })
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "sendgrid"
+    }
+}
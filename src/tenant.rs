@@ -0,0 +1,217 @@
+//! Per-tenant mailers for multi-tenant apps.
+//!
+//! When each customer workspace sends with its own API key and from-domain,
+//! implement [`TenantMailerProvider`] to build a tenant's mailer (typically
+//! from your database) and install it once. [`deliver_for`](crate::deliver_for)
+//! then resolves the tenant, caching the result, and delivers through the
+//! usual pipeline (defaults, sandbox, validation, telemetry).
+//!
+//! ```rust,ignore
+//! use missive::providers::PostmarkMailer;
+//! use missive::tenant::{TenantMailer, TenantMailerProvider};
+//!
+//! struct Workspaces(PgPool);
+//!
+//! #[async_trait]
+//! impl TenantMailerProvider for Workspaces {
+//!     async fn resolve(&self, tenant_id: &str) -> Result<TenantMailer, MailError> {
+//!         let ws = load_workspace(&self.0, tenant_id).await?;
+//!         Ok(TenantMailer::new(PostmarkMailer::new(ws.postmark_key))
+//!             .from((ws.name, ws.sender))
+//!             .from_domain(ws.domain))
+//!     }
+//! }
+//!
+//! missive::tenant::set_provider(Workspaces(pool));
+//! missive::deliver_for("acme", &email).await?;
+//! ```
+//!
+//! Resolved tenants are cached for five minutes by default (see
+//! [`set_cache_ttl`]). Call [`invalidate`] after changing a tenant's
+//! settings to pick them up on the next delivery.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use tokio::time::Instant;
+
+use crate::address::{Address, ToAddress, ToAddresses};
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::Mailer;
+
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// Private key recording the tenant an email was delivered for.
+pub(crate) const TENANT_KEY: &str = "tenant";
+
+static REGISTRY: RwLock<Registry> = RwLock::new(Registry {
+    provider: None,
+    ttl: DEFAULT_TTL,
+    cache: BTreeMap::new(),
+});
+
+struct Registry {
+    provider: Option<Arc<dyn TenantMailerProvider>>,
+    ttl: Duration,
+    cache: BTreeMap<String, (Arc<TenantMailer>, Instant)>,
+}
+
+/// Builds the mailer for a tenant.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait TenantMailerProvider: Send + Sync {
+    /// The mailer and sender settings for `tenant_id`.
+    async fn resolve(&self, tenant_id: &str) -> Result<TenantMailer, MailError>;
+}
+
+/// A tenant's mailer and sender settings.
+#[derive(Clone)]
+pub struct TenantMailer {
+    mailer: Arc<dyn Mailer>,
+    from: Option<Address>,
+    reply_to: Vec<Address>,
+    from_domain: Option<String>,
+}
+
+impl TenantMailer {
+    /// Send with `mailer`.
+    pub fn new<M: Mailer + 'static>(mailer: M) -> Self {
+        Self::new_arc(Arc::new(mailer))
+    }
+
+    /// Send with an Arc'd mailer.
+    pub fn new_arc(mailer: Arc<dyn Mailer>) -> Self {
+        Self {
+            mailer,
+            from: None,
+            reply_to: Vec::new(),
+            from_domain: None,
+        }
+    }
+
+    /// Sender for emails without one, in place of `EMAIL_FROM`.
+    pub fn from(mut self, from: impl ToAddress) -> Self {
+        self.from = Some(from.to_address());
+        self
+    }
+
+    /// Reply-to addresses for emails without any, in place of `EMAIL_REPLY_TO`.
+    pub fn reply_to(mut self, reply_to: impl ToAddresses) -> Self {
+        self.reply_to = reply_to.to_addresses();
+        self
+    }
+
+    /// Only send from addresses at `domain` (case-insensitive); other
+    /// senders are rejected with [`MailError::InvalidAddress`].
+    pub fn from_domain(mut self, domain: impl Into<String>) -> Self {
+        self.from_domain = Some(domain.into().to_lowercase());
+        self
+    }
+
+    /// The tenant's mailer.
+    pub fn mailer(&self) -> &Arc<dyn Mailer> {
+        &self.mailer
+    }
+
+    /// Fill in the tenant's sender settings and check the from-domain.
+    pub(crate) fn apply(&self, tenant_id: &str, email: &Email) -> Result<Email, MailError> {
+        let mut email = email.clone().put_private(TENANT_KEY, tenant_id);
+        if email.from.is_none() {
+            email.from = self.from.clone();
+        }
+        if email.reply_to.is_empty() {
+            email.reply_to = self.reply_to.clone();
+        }
+        if let Some(domain) = &self.from_domain {
+            let from = email
+                .from
+                .as_ref()
+                .map(|a| a.email.clone())
+                .or_else(|| crate::default_from().map(|a| a.email))
+                .unwrap_or_default();
+            let from_domain = from.rsplit_once('@').map(|(_, d)| d).unwrap_or_default();
+            if !from_domain.eq_ignore_ascii_case(domain) {
+                return Err(MailError::InvalidAddress(format!(
+                    "{} is not at the tenant's domain {}",
+                    from, domain
+                )));
+            }
+        }
+        Ok(email)
+    }
+}
+
+impl fmt::Debug for TenantMailer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantMailer")
+            .field("provider", &self.mailer.provider_name())
+            .field("from", &self.from)
+            .field("reply_to", &self.reply_to)
+            .field("from_domain", &self.from_domain)
+            .finish()
+    }
+}
+
+/// Install the provider used by [`deliver_for`](crate::deliver_for),
+/// replacing any previous one and clearing the cache.
+pub fn set_provider<P: TenantMailerProvider + 'static>(provider: P) {
+    let mut registry = REGISTRY.write();
+    registry.provider = Some(Arc::new(provider));
+    registry.cache.clear();
+}
+
+/// Remove the installed provider and clear the cache.
+pub fn clear_provider() {
+    let mut registry = REGISTRY.write();
+    registry.provider = None;
+    registry.cache.clear();
+}
+
+/// How long a resolved tenant is reused (default 5 minutes).
+pub fn set_cache_ttl(ttl: Duration) {
+    REGISTRY.write().ttl = ttl;
+}
+
+/// Forget a tenant, so the next delivery resolves it again.
+pub fn invalidate(tenant_id: &str) {
+    REGISTRY.write().cache.remove(tenant_id);
+}
+
+/// Forget every cached tenant.
+pub fn invalidate_all() {
+    REGISTRY.write().cache.clear();
+}
+
+/// The cached mailer for `tenant_id`, resolving it if needed.
+pub async fn resolve(tenant_id: &str) -> Result<Arc<TenantMailer>, MailError> {
+    let provider = {
+        let registry = REGISTRY.read();
+        if let Some((tenant, resolved_at)) = registry.cache.get(tenant_id) {
+            if resolved_at.elapsed() < registry.ttl {
+                return Ok(Arc::clone(tenant));
+            }
+        }
+        registry.provider.clone().ok_or_else(|| {
+            MailError::Configuration(
+                "No tenant provider installed; call missive::tenant::set_provider()".into(),
+            )
+        })?
+    };
+
+    let tenant = Arc::new(provider.resolve(tenant_id).await?);
+    tracing::debug!(
+        tenant = tenant_id,
+        provider = tenant.mailer.provider_name(),
+        "Resolved tenant mailer"
+    );
+    REGISTRY
+        .write()
+        .cache
+        .insert(tenant_id.to_string(), (Arc::clone(&tenant), Instant::now()));
+    Ok(tenant)
+}
@@ -24,6 +24,14 @@
 //!     assert_email_html_matches(&mailer, r"<h1>.*</h1>");
 //! }
 //! ```
+//!
+//! Every assertion also has a macro form that reports the failing line in
+//! your test and takes an optional custom message:
+//!
+//! ```rust,ignore
+//! assert_email_to!(mailer, "user@example.com");
+//! assert_email_subject_contains!(mailer, "Welcome", "signup for {} should greet", user.id);
+//! ```
 
 use regex::Regex;
 
@@ -68,6 +76,26 @@ fn format_email_summary(emails: &[StoredEmail]) -> String {
         .join("\n")
 }
 
+
+/// Panic with the failure message, reporting the caller's location.
+#[track_caller]
+fn assert_ok(result: Result<(), String>) {
+    if let Err(message) = result {
+        panic!("{}", message);
+    }
+}
+
+/// Get the most recent email, or the standard "none sent" failure.
+fn last_email(emails: &[StoredEmail]) -> Result<&StoredEmail, String> {
+    emails
+        .first()
+        .ok_or_else(|| "Expected at least one email to be sent, but none were sent".to_string())
+}
+
+fn compile(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("Invalid regex pattern '{}': {}", pattern, e))
+}
+
 // ============================================================================
 // Basic Assertions
 // ============================================================================
@@ -77,11 +105,18 @@ fn format_email_summary(emails: &[StoredEmail]) -> String {
 /// # Panics
 ///
 /// Panics if no emails were sent.
+#[track_caller]
 pub fn assert_email_sent(mailer: &LocalMailer) {
-    assert!(
-        mailer.has_emails(),
-        "Expected at least one email to be sent, but none were sent"
-    );
+    assert_ok(check_email_sent(mailer));
+}
+
+#[doc(hidden)]
+pub fn check_email_sent(mailer: &LocalMailer) -> Result<(), String> {
+    if mailer.has_emails() {
+        Ok(())
+    } else {
+        Err("Expected at least one email to be sent, but none were sent".into())
+    }
 }
 
 /// Assert that no emails were sent.
@@ -89,14 +124,22 @@ pub fn assert_email_sent(mailer: &LocalMailer) {
 /// # Panics
 ///
 /// Panics if any email was sent.
+#[track_caller]
 pub fn assert_no_emails_sent(mailer: &LocalMailer) {
+    assert_ok(check_no_emails_sent(mailer));
+}
+
+#[doc(hidden)]
+pub fn check_no_emails_sent(mailer: &LocalMailer) -> Result<(), String> {
     let emails = mailer.emails();
-    assert!(
-        emails.is_empty(),
+    if emails.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
         "Expected no emails to be sent, but {} were sent.\n\nEmails sent:\n{}",
         emails.len(),
         format_email_summary(&emails)
-    );
+    ))
 }
 
 /// Assert that exactly N emails were sent.
@@ -104,15 +147,23 @@ pub fn assert_no_emails_sent(mailer: &LocalMailer) {
 /// # Panics
 ///
 /// Panics if the count doesn't match.
+#[track_caller]
 pub fn assert_email_count(mailer: &LocalMailer, expected: usize) {
+    assert_ok(check_email_count(mailer, expected));
+}
+
+#[doc(hidden)]
+pub fn check_email_count(mailer: &LocalMailer, expected: usize) -> Result<(), String> {
     let actual = mailer.email_count();
-    assert!(
-        actual == expected,
+    if actual == expected {
+        return Ok(());
+    }
+    Err(format!(
         "Expected {} email(s) to be sent, but {} were sent.\n\nEmails sent:\n{}",
         expected,
         actual,
         format_email_summary(&mailer.emails())
-    );
+    ))
 }
 
 /// Assert that an email was sent to a specific address.
@@ -120,18 +171,26 @@ pub fn assert_email_count(mailer: &LocalMailer, expected: usize) {
 /// # Panics
 ///
 /// Panics if no email was sent to the address.
+#[track_caller]
 pub fn assert_email_to(mailer: &LocalMailer, email: &str) {
+    assert_ok(check_email_to(mailer, email));
+}
+
+#[doc(hidden)]
+pub fn check_email_to(mailer: &LocalMailer, email: &str) -> Result<(), String> {
     let emails = mailer.emails();
     let found = emails
         .iter()
         .any(|stored| stored.email.to.iter().any(|a| a.email.eq_ignore_ascii_case(email)));
 
-    assert!(
-        found,
+    if found {
+        return Ok(());
+    }
+    Err(format!(
         "Expected an email to be sent to '{}'.\n\nEmails sent:\n{}",
         email,
         format_email_summary(&emails)
-    );
+    ))
 }
 
 /// Assert that no email was sent to a specific address.
@@ -139,7 +198,13 @@ pub fn assert_email_to(mailer: &LocalMailer, email: &str) {
 /// # Panics
 ///
 /// Panics if an email was sent to the address.
+#[track_caller]
 pub fn assert_no_emails_to(mailer: &LocalMailer, email: &str) {
+    assert_ok(check_no_emails_to(mailer, email));
+}
+
+#[doc(hidden)]
+pub fn check_no_emails_to(mailer: &LocalMailer, email: &str) -> Result<(), String> {
     let emails = mailer.emails();
     let found = emails.iter().find(|stored| {
         stored
@@ -149,14 +214,15 @@ pub fn assert_no_emails_to(mailer: &LocalMailer, email: &str) {
             .any(|a| a.email.eq_ignore_ascii_case(email))
     });
 
-    if let Some(found_email) = found {
-        panic!(
+    match found {
+        Some(found_email) => Err(format!(
             "Expected no email to be sent to '{}', but found one.\n\nMatching email:\n  Subject: \"{}\"\n  From: {}\n\nAll emails:\n{}",
             email,
             found_email.email.subject,
             found_email.email.from.as_ref().map(|a| a.email.as_str()).unwrap_or("<none>"),
             format_email_summary(&emails)
-        );
+        )),
+        None => Ok(()),
     }
 }
 
@@ -165,16 +231,22 @@ pub fn assert_no_emails_to(mailer: &LocalMailer, email: &str) {
 /// # Panics
 ///
 /// Panics if no email with the subject was found.
+#[track_caller]
 pub fn assert_email_subject(mailer: &LocalMailer, subject: &str) {
-    let emails = mailer.emails();
-    let found = emails.iter().any(|stored| stored.email.subject == subject);
+    assert_ok(check_email_subject(mailer, subject));
+}
 
-    assert!(
-        found,
+#[doc(hidden)]
+pub fn check_email_subject(mailer: &LocalMailer, subject: &str) -> Result<(), String> {
+    let emails = mailer.emails();
+    if emails.iter().any(|stored| stored.email.subject == subject) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected an email with subject '{}'.\n\nEmails sent:\n{}",
         subject,
         format_email_summary(&emails)
-    );
+    ))
 }
 
 /// Assert that an email with subject containing text was sent.
@@ -182,16 +254,22 @@ pub fn assert_email_subject(mailer: &LocalMailer, subject: &str) {
 /// # Panics
 ///
 /// Panics if no matching email was found.
+#[track_caller]
 pub fn assert_email_subject_contains(mailer: &LocalMailer, text: &str) {
-    let emails = mailer.emails();
-    let found = emails.iter().any(|stored| stored.email.subject.contains(text));
+    assert_ok(check_email_subject_contains(mailer, text));
+}
 
-    assert!(
-        found,
+#[doc(hidden)]
+pub fn check_email_subject_contains(mailer: &LocalMailer, text: &str) -> Result<(), String> {
+    let emails = mailer.emails();
+    if emails.iter().any(|stored| stored.email.subject.contains(text)) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected an email with subject containing '{}'.\n\nEmails sent:\n{}",
         text,
         format_email_summary(&emails)
-    );
+    ))
 }
 
 /// Assert that an email matching a predicate was sent.
@@ -199,16 +277,26 @@ pub fn assert_email_subject_contains(mailer: &LocalMailer, text: &str) {
 /// # Panics
 ///
 /// Panics if no matching email was found.
+#[track_caller]
 pub fn assert_email_matches<F>(mailer: &LocalMailer, predicate: F)
 where
     F: Fn(&crate::email::Email) -> bool,
 {
-    let matches = mailer.find_emails(predicate);
-    assert!(
-        !matches.is_empty(),
+    assert_ok(check_email_matches(mailer, predicate));
+}
+
+#[doc(hidden)]
+pub fn check_email_matches<F>(mailer: &LocalMailer, predicate: F) -> Result<(), String>
+where
+    F: Fn(&crate::email::Email) -> bool,
+{
+    if !mailer.find_emails(predicate).is_empty() {
+        return Ok(());
+    }
+    Err(format!(
         "Expected an email matching the predicate, but none was found.\n\nEmails sent:\n{}",
         format_email_summary(&mailer.emails())
-    );
+    ))
 }
 
 /// Get the last email sent, or panic if none.
@@ -216,6 +304,7 @@ where
 /// # Panics
 ///
 /// Panics if no emails were sent.
+#[track_caller]
 pub fn get_last_email(mailer: &LocalMailer) -> StoredEmail {
     mailer
         .last_email()
@@ -244,14 +333,18 @@ pub fn get_emails_to(mailer: &LocalMailer, email: &str) -> Vec<StoredEmail> {
 /// # Panics
 ///
 /// Panics if no email was sent or from address doesn't match.
+#[track_caller]
 pub fn assert_email_from(mailer: &LocalMailer, from_email: &str) {
+    assert_ok(check_email_from(mailer, from_email));
+}
+
+#[doc(hidden)]
+pub fn check_email_from(mailer: &LocalMailer, from_email: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    assert!(
-        !emails.is_empty(),
-        "Expected at least one email to check 'from', but none were sent"
-    );
+    let Some(last) = emails.first() else {
+        return Err("Expected at least one email to check 'from', but none were sent".into());
+    };
 
-    let last = &emails[0];
     let actual_from = last
         .email
         .from
@@ -259,13 +352,15 @@ pub fn assert_email_from(mailer: &LocalMailer, from_email: &str) {
         .map(|a| a.email.as_str())
         .unwrap_or("<none>");
 
-    assert!(
-        actual_from.eq_ignore_ascii_case(from_email),
+    if actual_from.eq_ignore_ascii_case(from_email) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected last email from '{}', but was from '{}'.\n\nEmails sent:\n{}",
         from_email,
         actual_from,
         format_email_summary(&emails)
-    );
+    ))
 }
 
 /// Assert the last email has HTML body containing text.
@@ -273,20 +368,26 @@ pub fn assert_email_from(mailer: &LocalMailer, from_email: &str) {
 /// # Panics
 ///
 /// Panics if no email was sent or HTML body doesn't contain text.
+#[track_caller]
 pub fn assert_email_html_contains(mailer: &LocalMailer, text: &str) {
+    assert_ok(check_email_html_contains(mailer, text));
+}
+
+#[doc(hidden)]
+pub fn check_email_html_contains(mailer: &LocalMailer, text: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    let last = emails
-        .first()
-        .expect("Expected at least one email to be sent, but none were sent");
+    let last = last_email(&emails)?;
     let html = last.email.html_body.as_deref().unwrap_or("");
 
-    assert!(
-        html.contains(text),
+    if html.contains(text) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected HTML body to contain '{}', but it didn't.\n\nLast email:\n{}\n\nHTML body (first 500 chars):\n{}",
         text,
         format_email_summary(&[last.clone()]),
         &html[..html.len().min(500)]
-    );
+    ))
 }
 
 /// Assert the last email has text body containing text.
@@ -294,20 +395,26 @@ pub fn assert_email_html_contains(mailer: &LocalMailer, text: &str) {
 /// # Panics
 ///
 /// Panics if no email was sent or text body doesn't contain text.
+#[track_caller]
 pub fn assert_email_text_contains(mailer: &LocalMailer, text: &str) {
+    assert_ok(check_email_text_contains(mailer, text));
+}
+
+#[doc(hidden)]
+pub fn check_email_text_contains(mailer: &LocalMailer, text: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    let last = emails
-        .first()
-        .expect("Expected at least one email to be sent, but none were sent");
+    let last = last_email(&emails)?;
     let body = last.email.text_body.as_deref().unwrap_or("");
 
-    assert!(
-        body.contains(text),
+    if body.contains(text) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected text body to contain '{}', but it didn't.\n\nLast email:\n{}\n\nText body (first 500 chars):\n{}",
         text,
         format_email_summary(&[last.clone()]),
         &body[..body.len().min(500)]
-    );
+    ))
 }
 
 /// Assert the last email has an attachment with the given filename.
@@ -315,12 +422,18 @@ pub fn assert_email_text_contains(mailer: &LocalMailer, text: &str) {
 /// # Panics
 ///
 /// Panics if no email was sent or no attachment with that name exists.
+#[track_caller]
 pub fn assert_email_has_attachment(mailer: &LocalMailer, filename: &str) {
+    assert_ok(check_email_has_attachment(mailer, filename));
+}
+
+#[doc(hidden)]
+pub fn check_email_has_attachment(mailer: &LocalMailer, filename: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    let last = emails
-        .first()
-        .expect("Expected at least one email to be sent, but none were sent");
-    let has_attachment = last.email.attachments.iter().any(|a| a.filename == filename);
+    let last = last_email(&emails)?;
+    if last.email.attachments.iter().any(|a| a.filename == filename) {
+        return Ok(());
+    }
 
     let attachment_list = last
         .email
@@ -330,13 +443,12 @@ pub fn assert_email_has_attachment(mailer: &LocalMailer, filename: &str) {
         .collect::<Vec<_>>()
         .join(", ");
 
-    assert!(
-        has_attachment,
+    Err(format!(
         "Expected email to have attachment '{}'.\n\nLast email:\n{}\n\nAttachments: [{}]",
         filename,
         format_email_summary(&[last.clone()]),
         attachment_list
-    );
+    ))
 }
 
 // ============================================================================
@@ -348,20 +460,25 @@ pub fn assert_email_has_attachment(mailer: &LocalMailer, filename: &str) {
 /// # Panics
 ///
 /// Panics if no email was sent or subject doesn't match.
+#[track_caller]
 pub fn assert_email_subject_matches(mailer: &LocalMailer, pattern: &str) {
+    assert_ok(check_email_subject_matches(mailer, pattern));
+}
+
+#[doc(hidden)]
+pub fn check_email_subject_matches(mailer: &LocalMailer, pattern: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    let last = emails
-        .first()
-        .expect("Expected at least one email to be sent, but none were sent");
-    let re = Regex::new(pattern).expect("Invalid regex pattern");
+    let last = last_email(&emails)?;
 
-    assert!(
-        re.is_match(&last.email.subject),
+    if compile(pattern)?.is_match(&last.email.subject) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected subject to match pattern '{}', but was '{}'.\n\nLast email:\n{}",
         pattern,
         last.email.subject,
         format_email_summary(&[last.clone()])
-    );
+    ))
 }
 
 /// Assert the last email HTML body matches a regex pattern.
@@ -369,21 +486,26 @@ pub fn assert_email_subject_matches(mailer: &LocalMailer, pattern: &str) {
 /// # Panics
 ///
 /// Panics if no email was sent or HTML body doesn't match.
+#[track_caller]
 pub fn assert_email_html_matches(mailer: &LocalMailer, pattern: &str) {
+    assert_ok(check_email_html_matches(mailer, pattern));
+}
+
+#[doc(hidden)]
+pub fn check_email_html_matches(mailer: &LocalMailer, pattern: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    let last = emails
-        .first()
-        .expect("Expected at least one email to be sent, but none were sent");
+    let last = last_email(&emails)?;
     let html = last.email.html_body.as_deref().unwrap_or("");
-    let re = Regex::new(pattern).expect("Invalid regex pattern");
 
-    assert!(
-        re.is_match(html),
+    if compile(pattern)?.is_match(html) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected HTML body to match pattern '{}', but it didn't.\n\nLast email:\n{}\n\nHTML body (first 500 chars):\n{}",
         pattern,
         format_email_summary(&[last.clone()]),
         &html[..html.len().min(500)]
-    );
+    ))
 }
 
 /// Assert the last email text body matches a regex pattern.
@@ -391,21 +513,26 @@ pub fn assert_email_html_matches(mailer: &LocalMailer, pattern: &str) {
 /// # Panics
 ///
 /// Panics if no email was sent or text body doesn't match.
+#[track_caller]
 pub fn assert_email_text_matches(mailer: &LocalMailer, pattern: &str) {
+    assert_ok(check_email_text_matches(mailer, pattern));
+}
+
+#[doc(hidden)]
+pub fn check_email_text_matches(mailer: &LocalMailer, pattern: &str) -> Result<(), String> {
     let emails = mailer.emails();
-    let last = emails
-        .first()
-        .expect("Expected at least one email to be sent, but none were sent");
+    let last = last_email(&emails)?;
     let text = last.email.text_body.as_deref().unwrap_or("");
-    let re = Regex::new(pattern).expect("Invalid regex pattern");
 
-    assert!(
-        re.is_match(text),
+    if compile(pattern)?.is_match(text) {
+        return Ok(());
+    }
+    Err(format!(
         "Expected text body to match pattern '{}', but it didn't.\n\nLast email:\n{}\n\nText body (first 500 chars):\n{}",
         pattern,
         format_email_summary(&[last.clone()]),
         &text[..text.len().min(500)]
-    );
+    ))
 }
 
 // ============================================================================
@@ -417,6 +544,7 @@ pub fn assert_email_text_matches(mailer: &LocalMailer, pattern: &str) {
 /// # Panics
 ///
 /// Panics if any email was sent.
+#[track_caller]
 pub fn refute_email_sent(mailer: &LocalMailer) {
     assert_no_emails_sent(mailer);
 }
@@ -426,6 +554,7 @@ pub fn refute_email_sent(mailer: &LocalMailer) {
 /// # Panics
 ///
 /// Panics if an email was sent to the address.
+#[track_caller]
 pub fn refute_email_to(mailer: &LocalMailer, email: &str) {
     assert_no_emails_to(mailer, email);
 }
@@ -435,18 +564,25 @@ pub fn refute_email_to(mailer: &LocalMailer, email: &str) {
 /// # Panics
 ///
 /// Panics if an email with that subject was sent.
+#[track_caller]
 pub fn refute_email_subject(mailer: &LocalMailer, subject: &str) {
+    assert_ok(check_refute_email_subject(mailer, subject));
+}
+
+#[doc(hidden)]
+pub fn check_refute_email_subject(mailer: &LocalMailer, subject: &str) -> Result<(), String> {
     let emails = mailer.emails();
     let found = emails.iter().find(|stored| stored.email.subject == subject);
 
-    if let Some(found_email) = found {
-        panic!(
+    match found {
+        Some(found_email) => Err(format!(
             "Expected no email with subject '{}', but found one.\n\nMatching email:\n  To: [{}]\n  From: {}\n\nAll emails:\n{}",
             subject,
             found_email.email.to.iter().map(|a| a.email.as_str()).collect::<Vec<_>>().join(", "),
             found_email.email.from.as_ref().map(|a| a.email.as_str()).unwrap_or("<none>"),
             format_email_summary(&emails)
-        );
+        )),
+        None => Ok(()),
     }
 }
 
@@ -455,18 +591,28 @@ pub fn refute_email_subject(mailer: &LocalMailer, subject: &str) {
 /// # Panics
 ///
 /// Panics if a matching email was found.
+#[track_caller]
 pub fn refute_email_matches<F>(mailer: &LocalMailer, predicate: F)
+where
+    F: Fn(&crate::email::Email) -> bool,
+{
+    assert_ok(check_refute_email_matches(mailer, predicate));
+}
+
+#[doc(hidden)]
+pub fn check_refute_email_matches<F>(mailer: &LocalMailer, predicate: F) -> Result<(), String>
 where
     F: Fn(&crate::email::Email) -> bool,
 {
     let matches = mailer.find_emails(predicate);
-    if !matches.is_empty() {
-        panic!(
-            "Expected no emails matching the predicate, but {} were found.\n\nMatching emails:\n{}",
-            matches.len(),
-            format_email_summary(&matches)
-        );
+    if matches.is_empty() {
+        return Ok(());
     }
+    Err(format!(
+        "Expected no emails matching the predicate, but {} were found.\n\nMatching emails:\n{}",
+        matches.len(),
+        format_email_summary(&matches)
+    ))
 }
 
 // ============================================================================
@@ -474,10 +620,337 @@ where
 // ============================================================================
 
 /// Assert exactly N emails were sent (alias for assert_email_count).
+#[track_caller]
 pub fn assert_emails_sent_count(mailer: &LocalMailer, expected: usize) {
     assert_email_count(mailer, expected);
 }
 
+// ============================================================================
+// Assertion Macros
+// ============================================================================
+//
+// Macro forms of the assertions above. They panic at the call site and accept
+// an optional custom message in `format!` syntax, which is printed before the
+// standard failure details:
+//
+//     assert_email_to!(mailer, "user@example.com", "signup for {} should email", user);
+
+pub use crate::{
+    assert_email_count, assert_email_from, assert_email_has_attachment,
+    assert_email_html_contains, assert_email_html_matches, assert_email_matches,
+    assert_email_sent, assert_email_subject, assert_email_subject_contains,
+    assert_email_subject_matches, assert_email_text_contains, assert_email_text_matches,
+    assert_email_to, assert_no_emails_sent, assert_no_emails_to, refute_email_matches,
+    refute_email_sent, refute_email_subject, refute_email_to,
+};
+
+/// Panic with a check's failure message and an optional custom message.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __missive_check {
+    ($check:expr) => {
+        if let ::std::result::Result::Err(details) = $check {
+            ::std::panic!("{}", details);
+        }
+    };
+    ($check:expr, $($arg:tt)+) => {
+        if let ::std::result::Result::Err(details) = $check {
+            ::std::panic!("{}\n\n{}", ::std::format_args!($($arg)+), details);
+        }
+    };
+}
+
+/// Assert that at least one email was sent, panicking at the call site.
+///
+/// ```rust,ignore
+/// assert_email_sent!(mailer);
+/// assert_email_sent!(mailer, "password reset for {} should send mail", user_id);
+/// ```
+#[macro_export]
+macro_rules! assert_email_sent {
+    ($mailer:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_sent(&$mailer))
+    };
+    ($mailer:expr, $($arg:tt)+) => {
+        $crate::__missive_check!($crate::testing::check_email_sent(&$mailer), $($arg)+)
+    };
+}
+
+/// Assert that no emails were sent, panicking at the call site.
+#[macro_export]
+macro_rules! assert_no_emails_sent {
+    ($mailer:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_no_emails_sent(&$mailer))
+    };
+    ($mailer:expr, $($arg:tt)+) => {
+        $crate::__missive_check!($crate::testing::check_no_emails_sent(&$mailer), $($arg)+)
+    };
+}
+
+/// Assert that exactly N emails were sent, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_count {
+    ($mailer:expr, $expected:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_count(&$mailer, $expected))
+    };
+    ($mailer:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_count(&$mailer, $expected),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert that an email was sent to an address, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_to {
+    ($mailer:expr, $email:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_to(&$mailer, $email))
+    };
+    ($mailer:expr, $email:expr, $($arg:tt)+) => {
+        $crate::__missive_check!($crate::testing::check_email_to(&$mailer, $email), $($arg)+)
+    };
+}
+
+/// Assert that no email was sent to an address, panicking at the call site.
+#[macro_export]
+macro_rules! assert_no_emails_to {
+    ($mailer:expr, $email:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_no_emails_to(&$mailer, $email))
+    };
+    ($mailer:expr, $email:expr, $($arg:tt)+) => {
+        $crate::__missive_check!($crate::testing::check_no_emails_to(&$mailer, $email), $($arg)+)
+    };
+}
+
+/// Assert that an email with the exact subject was sent, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_subject {
+    ($mailer:expr, $subject:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_subject(&$mailer, $subject))
+    };
+    ($mailer:expr, $subject:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_subject(&$mailer, $subject),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert that an email subject contains text, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_subject_contains {
+    ($mailer:expr, $text:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_subject_contains(&$mailer, $text))
+    };
+    ($mailer:expr, $text:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_subject_contains(&$mailer, $text),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert that an email matching a predicate was sent, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_matches {
+    ($mailer:expr, $predicate:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_matches(&$mailer, $predicate))
+    };
+    ($mailer:expr, $predicate:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_matches(&$mailer, $predicate),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert the last email was sent from an address, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_from {
+    ($mailer:expr, $from:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_from(&$mailer, $from))
+    };
+    ($mailer:expr, $from:expr, $($arg:tt)+) => {
+        $crate::__missive_check!($crate::testing::check_email_from(&$mailer, $from), $($arg)+)
+    };
+}
+
+/// Assert the last email's HTML body contains text, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_html_contains {
+    ($mailer:expr, $text:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_html_contains(&$mailer, $text))
+    };
+    ($mailer:expr, $text:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_html_contains(&$mailer, $text),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert the last email's text body contains text, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_text_contains {
+    ($mailer:expr, $text:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_text_contains(&$mailer, $text))
+    };
+    ($mailer:expr, $text:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_text_contains(&$mailer, $text),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert the last email has an attachment, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_has_attachment {
+    ($mailer:expr, $filename:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_has_attachment(&$mailer, $filename))
+    };
+    ($mailer:expr, $filename:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_has_attachment(&$mailer, $filename),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert the last email's subject matches a regex, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_subject_matches {
+    ($mailer:expr, $pattern:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_subject_matches(&$mailer, $pattern))
+    };
+    ($mailer:expr, $pattern:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_subject_matches(&$mailer, $pattern),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert the last email's HTML body matches a regex, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_html_matches {
+    ($mailer:expr, $pattern:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_html_matches(&$mailer, $pattern))
+    };
+    ($mailer:expr, $pattern:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_html_matches(&$mailer, $pattern),
+            $($arg)+
+        )
+    };
+}
+
+/// Assert the last email's text body matches a regex, panicking at the call site.
+#[macro_export]
+macro_rules! assert_email_text_matches {
+    ($mailer:expr, $pattern:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_email_text_matches(&$mailer, $pattern))
+    };
+    ($mailer:expr, $pattern:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_email_text_matches(&$mailer, $pattern),
+            $($arg)+
+        )
+    };
+}
+
+/// Refute that any email was sent, panicking at the call site.
+#[macro_export]
+macro_rules! refute_email_sent {
+    ($($args:tt)+) => {
+        $crate::assert_no_emails_sent!($($args)+)
+    };
+}
+
+/// Refute that an email was sent to an address, panicking at the call site.
+#[macro_export]
+macro_rules! refute_email_to {
+    ($($args:tt)+) => {
+        $crate::assert_no_emails_to!($($args)+)
+    };
+}
+
+/// Refute that an email with the exact subject was sent, panicking at the call site.
+#[macro_export]
+macro_rules! refute_email_subject {
+    ($mailer:expr, $subject:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_refute_email_subject(&$mailer, $subject))
+    };
+    ($mailer:expr, $subject:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_refute_email_subject(&$mailer, $subject),
+            $($arg)+
+        )
+    };
+}
+
+/// Refute that an email matching a predicate was sent, panicking at the call site.
+#[macro_export]
+macro_rules! refute_email_matches {
+    ($mailer:expr, $predicate:expr $(,)?) => {
+        $crate::__missive_check!($crate::testing::check_refute_email_matches(&$mailer, $predicate))
+    };
+    ($mailer:expr, $predicate:expr, $($arg:tt)+) => {
+        $crate::__missive_check!(
+            $crate::testing::check_refute_email_matches(&$mailer, $predicate),
+            $($arg)+
+        )
+    };
+}
+
+// ============================================================================
+// Snapshots
+// ============================================================================
+
+#[cfg(feature = "snapshots")]
+#[doc(hidden)]
+pub use insta as __insta;
+
+#[cfg(feature = "snapshots")]
+pub use crate::assert_email_snapshot;
+
+/// Snapshot the last email sent with `insta`.
+///
+/// Compares [`StoredEmail::to_snapshot`] against a stored snapshot. Accepts an
+/// optional snapshot name, or an inline `@"..."` snapshot, like
+/// `insta::assert_snapshot!`. Requires the `snapshots` feature.
+///
+/// ```rust,ignore
+/// assert_email_snapshot!(mailer);
+/// assert_email_snapshot!("welcome_email", mailer);
+/// assert_email_snapshot!(mailer, @r"
+/// To: user@example.com
+/// Subject: Welcome
+/// ");
+/// ```
+#[cfg(feature = "snapshots")]
+#[macro_export]
+macro_rules! assert_email_snapshot {
+    ($name:literal, $mailer:expr $(,)?) => {
+        $crate::testing::__insta::assert_snapshot!(
+            $name,
+            $crate::testing::get_last_email(&$mailer).to_snapshot()
+        )
+    };
+    ($mailer:expr, @$snapshot:literal $(,)?) => {
+        $crate::testing::__insta::assert_snapshot!(
+            $crate::testing::get_last_email(&$mailer).to_snapshot(),
+            @$snapshot
+        )
+    };
+    ($mailer:expr $(,)?) => {
+        $crate::testing::__insta::assert_snapshot!(
+            $crate::testing::get_last_email(&$mailer).to_snapshot()
+        )
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -518,6 +991,36 @@ mod tests {
         assert_email_sent(&mailer);
     }
 
+    #[tokio::test]
+    async fn test_assertion_macros() {
+        let mailer = LocalMailer::new();
+        mailer
+            .deliver(
+                &Email::new()
+                    .from("sender@example.com")
+                    .to("recipient@example.com")
+                    .subject("Welcome aboard!")
+                    .text_body("Hello"),
+            )
+            .await
+            .unwrap();
+
+        assert_email_sent!(mailer);
+        assert_email_count!(&mailer, 1);
+        assert_email_to!(mailer, "recipient@example.com", "custom {}", "message");
+        assert_email_subject_matches!(mailer, r"^Welcome");
+        assert_email_matches!(mailer, |e| e.text_body.is_some());
+        refute_email_to!(mailer, "other@example.com");
+        refute_email_subject!(mailer, "Goodbye");
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "signup for 42 should email\n\nExpected an email to be sent to")]
+    async fn test_assertion_macro_custom_message() {
+        let mailer = LocalMailer::new();
+        assert_email_to!(mailer, "user@example.com", "signup for {} should email", 42);
+    }
+
     #[tokio::test]
     #[should_panic(expected = "Expected no emails")]
     async fn test_assert_no_emails_fails_when_sent() {
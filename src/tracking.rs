@@ -0,0 +1,309 @@
+//! Self-hosted open and click tracking.
+//!
+//! [`Tracking`] rewrites every `http(s)` link in the HTML body to
+//! `{base_url}/click?t={token}` and adds a 1x1 pixel loading
+//! `{base_url}/open?t={token}`. Tokens are HMAC-SHA256 signed, so your
+//! redirect endpoint can trust the target URL and recipient they carry:
+//!
+//! ```rust,ignore
+//! use missive::providers::ResendMailer;
+//! use missive::{InterceptorExt, Tracking};
+//!
+//! let tracking = Tracking::new("https://t.example.com", secret);
+//! let mailer = ResendMailer::new(api_key).with_interceptor(tracking.clone());
+//!
+//! // In your web app:
+//! match tracking.verify(&token) {
+//!     Some(TrackingEvent::Click { url, .. }) => redirect(url),
+//!     Some(TrackingEvent::Open { .. }) => gif(missive::tracking::PIXEL_GIF),
+//!     None => not_found(),
+//! }
+//! ```
+//!
+//! The global `deliver()` functions apply tracking automatically when
+//! `EMAIL_TRACKING_URL` and `EMAIL_TRACKING_SECRET` are set.
+//!
+//! Tokens identify the email by `private["tracking_id"]` (a UUID is assigned
+//! if you don't set one) and the first `to` recipient. Links with a
+//! `data-no-track` attribute, non-HTTP links and the text body are left alone.
+//! Tokens don't expire; rotate the secret to invalidate old ones.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::interceptor::Interceptor;
+use crate::links;
+
+/// A transparent 1x1 GIF to serve from the open-tracking endpoint.
+pub const PIXEL_GIF: &[u8] = &[
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+];
+
+/// Link and open tracking interceptor.
+#[derive(Clone)]
+pub struct Tracking {
+    base_url: String,
+    key: hmac::Key,
+    clicks: bool,
+    opens: bool,
+}
+
+impl std::fmt::Debug for Tracking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tracking")
+            .field("base_url", &self.base_url)
+            .field("clicks", &self.clicks)
+            .field("opens", &self.opens)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A verified tracking token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackingEvent {
+    /// The tracking pixel was loaded.
+    Open {
+        /// The email's tracking ID.
+        id: String,
+        /// The first `to` recipient.
+        recipient: Option<String>,
+    },
+    /// A tracked link was followed.
+    Click {
+        /// The email's tracking ID.
+        id: String,
+        /// The first `to` recipient.
+        recipient: Option<String>,
+        /// The original link target.
+        url: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    #[serde(rename = "i")]
+    id: String,
+    #[serde(rename = "r", default, skip_serializing_if = "Option::is_none")]
+    recipient: Option<String>,
+    #[serde(rename = "u", default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+impl Tracking {
+    /// Track clicks and opens, redirecting through `base_url` and signing
+    /// tokens with `secret`.
+    pub fn new(base_url: impl Into<String>, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret.as_ref()),
+            clicks: true,
+            opens: true,
+        }
+    }
+
+    /// Build from `EMAIL_TRACKING_URL` and `EMAIL_TRACKING_SECRET`.
+    ///
+    /// Returns `None` unless both are set.
+    pub fn from_env() -> Option<Self> {
+        let url = config::var("EMAIL_TRACKING_URL").filter(|v| !v.trim().is_empty())?;
+        let secret = config::var("EMAIL_TRACKING_SECRET").filter(|v| !v.is_empty())?;
+        Some(Self::new(url.trim(), secret))
+    }
+
+    /// Rewrite links (default: on).
+    pub fn clicks(mut self, enabled: bool) -> Self {
+        self.clicks = enabled;
+        self
+    }
+
+    /// Add the open-tracking pixel (default: on).
+    pub fn opens(mut self, enabled: bool) -> Self {
+        self.opens = enabled;
+        self
+    }
+
+    /// Verify a token from a tracking URL's `t` parameter.
+    ///
+    /// Returns `None` if the token is malformed or its signature doesn't match.
+    pub fn verify(&self, token: &str) -> Option<TrackingEvent> {
+        let (payload, signature) = token.split_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        hmac::verify(&self.key, payload.as_bytes(), &signature).ok()?;
+
+        let payload: Payload =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+        Some(match payload.url {
+            Some(url) => TrackingEvent::Click {
+                id: payload.id,
+                recipient: payload.recipient,
+                url,
+            },
+            None => TrackingEvent::Open {
+                id: payload.id,
+                recipient: payload.recipient,
+            },
+        })
+    }
+
+    fn sign(&self, payload: &Payload) -> String {
+        let json = serde_json::to_vec(payload).unwrap_or_default();
+        let payload = URL_SAFE_NO_PAD.encode(json);
+        let signature = hmac::sign(&self.key, payload.as_bytes());
+        format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+    }
+
+    fn rewrite_links(&self, html: &str, id: &str, recipient: &Option<String>) -> String {
+        links::rewrite_hrefs(html, |url, tag| {
+            let lower = url.to_ascii_lowercase();
+            if tag.to_ascii_lowercase().contains("data-no-track")
+                || !(lower.starts_with("http://") || lower.starts_with("https://"))
+                || url.starts_with(&self.base_url)
+            {
+                return None;
+            }
+
+            let token = self.sign(&Payload {
+                id: id.to_string(),
+                recipient: recipient.clone(),
+                url: Some(url.to_string()),
+            });
+            Some(format!("{}/click?t={}", self.base_url, token))
+        })
+    }
+
+    fn pixel(&self, id: &str, recipient: &Option<String>) -> String {
+        let token = self.sign(&Payload {
+            id: id.to_string(),
+            recipient: recipient.clone(),
+            url: None,
+        });
+        format!(
+            "<img src=\"{}/open?t={}\" width=\"1\" height=\"1\" alt=\"\" style=\"display:none\">",
+            self.base_url, token
+        )
+    }
+}
+
+impl Interceptor for Tracking {
+    fn intercept(&self, mut email: Email) -> Result<Email, MailError> {
+        let Some(html) = email.html_body.take() else {
+            return Ok(email);
+        };
+
+        let id = match email.private.get("tracking_id").and_then(|v| v.as_str()) {
+            Some(id) => id.to_string(),
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                email
+                    .private
+                    .insert("tracking_id".to_string(), id.clone().into());
+                id
+            }
+        };
+        let recipient = email.to.first().map(|a| a.email.clone());
+
+        let mut html = if self.clicks {
+            self.rewrite_links(&html, &id, &recipient)
+        } else {
+            html
+        };
+        if self.opens {
+            let pixel = self.pixel(&id, &recipient);
+            match html.to_ascii_lowercase().rfind("</body>") {
+                Some(pos) => html.insert_str(pos, &pixel),
+                None => html.push_str(&pixel),
+            }
+        }
+
+        email.html_body = Some(html);
+        Ok(email)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracking() -> Tracking {
+        Tracking::new("https://t.example.com/", "secret")
+    }
+
+    fn email(html: &str) -> Email {
+        Email::new()
+            .to("alice@example.com")
+            .html_body(html)
+            .put_private("tracking_id", "msg-1")
+    }
+
+    #[test]
+    fn test_rewrites_links_and_adds_pixel() {
+        let tracking = tracking();
+        let html = r#"<html><body><a class="btn" href="https://example.com/a?x=1&amp;y=2">Go</a> <A HREF='mailto:a@b.c'>Mail</A></body></html>"#;
+        let email = tracking.intercept(email(html)).unwrap();
+        let html = email.html_body.unwrap();
+
+        let start = html.find("https://t.example.com/click?t=").unwrap();
+        let token = html[start + 30..].split('"').next().unwrap();
+        assert_eq!(
+            tracking.verify(token),
+            Some(TrackingEvent::Click {
+                id: "msg-1".into(),
+                recipient: Some("alice@example.com".into()),
+                url: "https://example.com/a?x=1&y=2".into(),
+            })
+        );
+        assert!(html.contains(r#"<a class="btn" href="https://t.example.com/click?t="#));
+        assert!(html.contains("<A HREF='mailto:a@b.c'>"));
+
+        let pixel = html.find("https://t.example.com/open?t=").unwrap();
+        assert!(pixel < html.find("</body>").unwrap());
+        let token = html[pixel + 29..].split('"').next().unwrap();
+        assert!(matches!(
+            tracking.verify(token),
+            Some(TrackingEvent::Open { id, .. }) if id == "msg-1"
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let tracking = tracking();
+        let token = tracking.sign(&Payload {
+            id: "msg-1".into(),
+            recipient: None,
+            url: Some("https://example.com".into()),
+        });
+        assert!(tracking.verify(&token).is_some());
+        assert!(Tracking::new("https://t.example.com", "other")
+            .verify(&token)
+            .is_none());
+
+        let forged = URL_SAFE_NO_PAD.encode(br#"{"i":"msg-1","u":"https://evil.example"}"#);
+        let signature = token.split_once('.').unwrap().1;
+        assert!(tracking
+            .verify(&format!("{}.{}", forged, signature))
+            .is_none());
+        assert!(tracking.verify("garbage").is_none());
+    }
+
+    #[test]
+    fn test_options() {
+        let html = r#"<a href="https://example.com" data-no-track>x</a>"#;
+        let email = tracking().opens(false).intercept(email(html)).unwrap();
+        assert_eq!(email.html_body.as_deref(), Some(html));
+
+        let email = tracking()
+            .clicks(false)
+            .intercept(Email::new().html_body("<p>Hi</p>"))
+            .unwrap();
+        let html = email.html_body.unwrap();
+        assert!(html.starts_with("<p>Hi</p><img src=\"https://t.example.com/open?t="));
+        assert!(email.private["tracking_id"].is_string());
+    }
+}
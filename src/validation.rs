@@ -0,0 +1,555 @@
+//! Strict pre-delivery validation.
+//!
+//! [`Email::validate_strict`] checks everything a provider would reject,
+//! and reports every problem at once:
+//!
+//! ```rust
+//! use missive::Email;
+//!
+//! let email = Email::new()
+//!     .from("sender@example.com")
+//!     .to("not-an-email")
+//!     .subject("Hello");
+//!
+//! let report = email.validate_strict();
+//! assert!(!report.is_ok());
+//! for issue in report.issues() {
+//!     println!("{}", issue);
+//! }
+//! ```
+//!
+//! Set `EMAIL_STRICT_VALIDATION=true` (or [`ConfigBuilder::strict_validation`])
+//! to run the checks, including the provider's recipient limit, on every
+//! `deliver()` call. Failures return [`MailError::Validation`].
+//!
+//! [`ConfigBuilder::strict_validation`]: crate::ConfigBuilder::strict_validation
+
+use std::fmt;
+
+use email_address::EmailAddress;
+
+use crate::address::Address;
+use crate::config;
+use crate::email::Email;
+use crate::error::MailError;
+use crate::mailer::Capabilities;
+
+/// Maximum line length in bytes, excluding CRLF (RFC 5322 section 2.1.1).
+///
+/// The default [`Capabilities`] limit for subjects and header values.
+pub const MAX_LINE_LENGTH: usize = 998;
+
+/// Provider options that supply the subject and body from a hosted template.
+const TEMPLATE_OPTIONS: &[&str] = &["template_id", "template_alias", "template"];
+
+/// Maximum recipients (to + cc + bcc) per message for a provider.
+///
+/// Returns `None` for providers without a documented limit.
+pub(crate) fn recipient_limit(provider: &str) -> Option<usize> {
+    match provider {
+        "resend" | "unsent" | "postmark" | "amazon_ses" | "mailjet" => Some(50),
+        "brevo" => Some(99),
+        "sendgrid" | "mailgun" | "mailtrap" => Some(1000),
+        _ => None,
+    }
+}
+
+/// Maximum encoded message size in bytes for a provider.
+fn provider_size_limit(provider: &str) -> Option<usize> {
+    const MB: usize = 1024 * 1024;
+    match provider {
+        "amazon_ses" | "postmark" => Some(10 * MB),
+        "mailjet" | "zeptomail" => Some(15 * MB),
+        "mailgun" => Some(25 * MB),
+        "sendgrid" => Some(30 * MB),
+        "resend" => Some(40 * MB),
+        _ => None,
+    }
+}
+
+/// The message size limit for `provider`: `EMAIL_MAX_MESSAGE_SIZE` if set
+/// (`0` disables the check), otherwise the provider's documented limit.
+pub(crate) fn size_limit(provider: &str) -> Option<usize> {
+    match config::var("EMAIL_MAX_MESSAGE_SIZE").and_then(|v| v.trim().parse::<usize>().ok()) {
+        Some(0) => None,
+        Some(limit) => Some(limit),
+        None => provider_size_limit(provider),
+    }
+}
+
+/// Fail with [`MailError::TooLarge`] if the email exceeds the size limit
+/// for `provider`.
+pub(crate) fn check_size(email: &Email, provider: &str) -> Result<(), MailError> {
+    let Some(limit) = size_limit(provider) else {
+        return Ok(());
+    };
+    let actual = email.estimated_size();
+    if actual > limit {
+        return Err(MailError::TooLarge { limit, actual });
+    }
+    Ok(())
+}
+
+/// What `deliver()` does with subjects and header values over the mailer's
+/// [`Capabilities`] limits.
+///
+/// Set with `EMAIL_LIMIT_POLICY` or [`ConfigBuilder::limit_policy`].
+/// Recipient lists over `max_recipients` always fail: dropping recipients
+/// would lose mail silently.
+///
+/// [`ConfigBuilder::limit_policy`]: crate::ConfigBuilder::limit_policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitPolicy {
+    /// Fail with [`MailError::LimitExceeded`] before sending.
+    #[default]
+    Error,
+    /// Cut the value at the limit (on a character boundary) and log a warning.
+    Truncate,
+}
+
+/// Enforce the subject, header and recipient limits in `caps` on `email`.
+pub(crate) fn check_limits(
+    email: &mut Email,
+    caps: &Capabilities,
+    policy: LimitPolicy,
+) -> Result<(), MailError> {
+    if let Some(limit) = caps.max_recipients {
+        let actual = email.to.len() + email.cc.len() + email.bcc.len();
+        if actual > limit {
+            return Err(MailError::LimitExceeded {
+                field: "recipients".into(),
+                limit,
+                actual,
+            });
+        }
+    }
+    if let Some(limit) = caps.max_subject_length {
+        fit("subject", &mut email.subject, limit, policy)?;
+    }
+    if let Some(limit) = caps.max_header_length {
+        for (name, value) in email.headers.iter_mut() {
+            fit(&format!("header {}", name), value, limit, policy)?;
+        }
+    }
+    Ok(())
+}
+
+/// Apply `policy` to a value longer than `limit` bytes.
+fn fit(
+    field: &str,
+    value: &mut String,
+    limit: usize,
+    policy: LimitPolicy,
+) -> Result<(), MailError> {
+    let actual = value.len();
+    if actual <= limit {
+        return Ok(());
+    }
+    match policy {
+        LimitPolicy::Error => Err(MailError::LimitExceeded {
+            field: field.to_string(),
+            limit,
+            actual,
+        }),
+        LimitPolicy::Truncate => {
+            let mut end = limit;
+            while !value.is_char_boundary(end) {
+                end -= 1;
+            }
+            value.truncate(end);
+            tracing::warn!(field = field, limit, actual, "Truncated over-long value");
+            Ok(())
+        }
+    }
+}
+
+/// What to do with senders outside `EMAIL_ALLOWED_FROM_DOMAINS`.
+///
+/// Set with `EMAIL_FROM_DOMAIN_POLICY` or [`ConfigBuilder::from_domain_policy`].
+///
+/// [`ConfigBuilder::from_domain_policy`]: crate::ConfigBuilder::from_domain_policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FromDomainPolicy {
+    /// Fail with [`MailError::Blocked`] before sending.
+    #[default]
+    Reject,
+    /// Send from the default sender (`EMAIL_FROM`) instead, keeping the
+    /// display name, with the original address as reply-to if none is set.
+    Rewrite,
+}
+
+/// Enforce the allowed from-domains on `email`, rewriting the sender to
+/// `fallback` under [`FromDomainPolicy::Rewrite`].
+pub(crate) fn check_from_domain(
+    email: &mut Email,
+    allowed: &[String],
+    policy: FromDomainPolicy,
+    fallback: Option<Address>,
+) -> Result<(), MailError> {
+    let is_allowed = |addr: &Address| {
+        let domain = addr
+            .email
+            .rsplit_once('@')
+            .map(|(_, d)| d)
+            .unwrap_or_default();
+        allowed.iter().any(|d| d.eq_ignore_ascii_case(domain))
+    };
+    let Some(from) = email.from.as_ref().filter(|from| !is_allowed(from)) else {
+        return Ok(());
+    };
+
+    let rewritten = match (policy, fallback) {
+        (FromDomainPolicy::Rewrite, Some(fallback)) if is_allowed(&fallback) => Address {
+            name: from.name.clone().or(fallback.name),
+            email: fallback.email,
+        },
+        (FromDomainPolicy::Rewrite, _) => {
+            return Err(MailError::Blocked(format!(
+                "sender {} is not at an allowed domain, and EMAIL_FROM can't replace it",
+                from.email
+            )))
+        }
+        (FromDomainPolicy::Reject, _) => {
+            return Err(MailError::Blocked(format!(
+                "sender {} is not at an allowed domain",
+                from.email
+            )))
+        }
+    };
+    tracing::warn!(
+        from = %from.email,
+        rewritten = %rewritten.email,
+        "Rewrote sender outside the allowed domains"
+    );
+    if email.reply_to.is_empty() {
+        email.reply_to.push(from.clone());
+    }
+    email.from = Some(rewritten);
+    Ok(())
+}
+
+/// A single problem found by strict validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationIssue {
+    /// A required field is missing (`from` or `to`).
+    MissingField(&'static str),
+    /// An address failed RFC 5321/5322 validation.
+    InvalidAddress {
+        /// The field holding the address (`from`, `to`, `cc`, `bcc`, `reply_to`, `return_path`).
+        field: &'static str,
+        /// The rejected address.
+        address: String,
+    },
+    /// The subject is empty or whitespace.
+    EmptySubject,
+    /// Neither a text nor an HTML body is set.
+    EmptyBody,
+    /// More recipients than the provider accepts in one message.
+    TooManyRecipients {
+        /// The provider the limit applies to.
+        provider: String,
+        /// Recipients on the email.
+        count: usize,
+        /// The provider's limit.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing {}", field),
+            Self::InvalidAddress { field, address } => {
+                write!(f, "invalid {} address '{}'", field, address)
+            }
+            Self::EmptySubject => write!(f, "subject is empty"),
+            Self::EmptyBody => write!(f, "body is empty"),
+            Self::TooManyRecipients {
+                provider,
+                count,
+                limit,
+            } => write!(
+                f,
+                "{} recipients exceeds the {} limit of {}",
+                count, provider, limit
+            ),
+        }
+    }
+}
+
+/// The result of [`Email::validate_strict`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether no issues were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The issues found, in field order.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// `Ok(())` if no issues were found, otherwise [`MailError::Validation`].
+    pub fn into_result(self) -> Result<(), MailError> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(MailError::Validation(self))
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl Email {
+    /// Check every address with the RFC validator, require a subject and a
+    /// body, and collect all problems in a [`ValidationReport`].
+    ///
+    /// The subject and body may be omitted when a hosted template option
+    /// (`template_id`, `template_alias` or `template`) is set.
+    pub fn validate_strict(&self) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        match &self.from {
+            Some(addr) => check_address(&mut issues, "from", addr),
+            None => issues.push(ValidationIssue::MissingField("from")),
+        }
+        if self.to.is_empty() && self.personalizations.is_empty() {
+            issues.push(ValidationIssue::MissingField("to"));
+        }
+        let recipients = [
+            ("to", &self.to),
+            ("cc", &self.cc),
+            ("bcc", &self.bcc),
+            ("reply_to", &self.reply_to),
+        ];
+        for (field, addrs) in recipients {
+            for addr in addrs {
+                check_address(&mut issues, field, addr);
+            }
+        }
+        for p in &self.personalizations {
+            check_address(&mut issues, "to", &p.to);
+        }
+        if let Some(addr) = &self.return_path {
+            check_address(&mut issues, "return_path", addr);
+        }
+
+        let templated = TEMPLATE_OPTIONS
+            .iter()
+            .any(|key| self.provider_options.contains_key(*key));
+        if !templated {
+            if self.subject.trim().is_empty() {
+                issues.push(ValidationIssue::EmptySubject);
+            }
+            let has_content =
+                |body: &Option<String>| body.as_deref().is_some_and(|b| !b.trim().is_empty());
+            if !has_content(&self.text_body) && !has_content(&self.html_body) {
+                issues.push(ValidationIssue::EmptyBody);
+            }
+        }
+
+        ValidationReport { issues }
+    }
+
+    /// [`validate_strict`](Email::validate_strict), plus the provider's
+    /// per-message recipient limit.
+    pub fn validate_strict_for(&self, provider: &str) -> ValidationReport {
+        let mut report = self.validate_strict();
+        let count = self.to.len() + self.cc.len() + self.bcc.len();
+        if let Some(limit) = recipient_limit(provider) {
+            if count > limit {
+                report.issues.push(ValidationIssue::TooManyRecipients {
+                    provider: provider.to_string(),
+                    count,
+                    limit,
+                });
+            }
+        }
+        report
+    }
+}
+
+fn check_address(issues: &mut Vec<ValidationIssue>, field: &'static str, addr: &Address) {
+    if !EmailAddress::is_valid(&addr.email) {
+        issues.push(ValidationIssue::InvalidAddress {
+            field,
+            address: addr.email.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_from_domain() {
+        let allowed = vec!["example.com".to_string(), "mail.example.com".to_string()];
+        let email = Email::new()
+            .from(("Acme", "billing@acme.test"))
+            .to("user@gmail.com");
+
+        let mut ok = email.clone().from("noreply@Mail.Example.com");
+        check_from_domain(&mut ok, &allowed, FromDomainPolicy::Reject, None).unwrap();
+
+        let mut rejected = email.clone();
+        let err = check_from_domain(&mut rejected, &allowed, FromDomainPolicy::Reject, None);
+        assert!(matches!(err, Err(MailError::Blocked(_))));
+
+        let mut rewritten = email.clone();
+        let fallback = Some(Address::with_name("Example", "noreply@example.com"));
+        check_from_domain(
+            &mut rewritten,
+            &allowed,
+            FromDomainPolicy::Rewrite,
+            fallback,
+        )
+        .unwrap();
+        let from = rewritten.from.unwrap();
+        assert_eq!(from.email, "noreply@example.com");
+        assert_eq!(from.name.as_deref(), Some("Acme"));
+        assert_eq!(rewritten.reply_to[0].email, "billing@acme.test");
+
+        // The fallback must be allowed too
+        let mut stuck = email;
+        let fallback = Some(Address::new("noreply@acme.test"));
+        let err = check_from_domain(&mut stuck, &allowed, FromDomainPolicy::Rewrite, fallback);
+        assert!(matches!(err, Err(MailError::Blocked(_))));
+    }
+
+    #[test]
+    fn test_valid_email() {
+        let email = Email::new()
+            .from("sender@example.com")
+            .to("user@example.com")
+            .subject("Hello")
+            .text_body("Hi");
+        assert!(email.validate_strict().is_ok());
+        assert!(email.validate_strict_for("resend").into_result().is_ok());
+    }
+
+    #[test]
+    fn test_collects_all_issues() {
+        let email = Email::new()
+            .to("user@example.com")
+            .cc("not-an-email")
+            .html_body("  ");
+
+        let report = email.validate_strict();
+        assert_eq!(
+            report.issues(),
+            [
+                ValidationIssue::MissingField("from"),
+                ValidationIssue::InvalidAddress {
+                    field: "cc",
+                    address: "not-an-email".into()
+                },
+                ValidationIssue::EmptySubject,
+                ValidationIssue::EmptyBody,
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "missing from; invalid cc address 'not-an-email'; subject is empty; body is empty"
+        );
+
+        let err = report.into_result().unwrap_err();
+        assert_eq!(err.kind(), "validation");
+    }
+
+    #[test]
+    fn test_check_size() {
+        let email = Email::new()
+            .from("sender@example.com")
+            .to("user@example.com")
+            .attachment(crate::Attachment::from_bytes(
+                "big.bin",
+                vec![0; 8 * 1024 * 1024],
+            ));
+
+        assert!(check_size(&email, "mailgun").is_ok());
+        assert!(check_size(&email, "smtp").is_ok());
+        match check_size(&email, "amazon_ses") {
+            Err(MailError::TooLarge { limit, actual }) => {
+                assert_eq!(limit, 10 * 1024 * 1024);
+                assert!(actual > limit);
+            }
+            other => panic!("expected TooLarge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_template_and_recipient_limit() {
+        let email = Email::new()
+            .from("sender@example.com")
+            .to((0..51)
+                .map(|i| format!("user{}@example.com", i))
+                .collect::<Vec<_>>())
+            .provider_option("template_id", "d-123");
+
+        assert!(email.validate_strict().is_ok());
+        assert_eq!(
+            email.validate_strict_for("postmark").issues(),
+            [ValidationIssue::TooManyRecipients {
+                provider: "postmark".into(),
+                count: 51,
+                limit: 50
+            }]
+        );
+        assert!(email.validate_strict_for("smtp").is_ok());
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let caps = Capabilities::for_provider("postmark");
+        let email = Email::new()
+            .from("sender@example.com")
+            .to("user@example.com")
+            .subject("é".repeat(500))
+            .header("X-Note", "n".repeat(1000));
+
+        let mut rejected = email.clone();
+        match check_limits(&mut rejected, &caps, LimitPolicy::Error) {
+            Err(MailError::LimitExceeded {
+                field,
+                limit,
+                actual,
+            }) => {
+                assert_eq!(field, "subject");
+                assert_eq!(limit, MAX_LINE_LENGTH);
+                assert_eq!(actual, 1000);
+            }
+            other => panic!("expected LimitExceeded, got {:?}", other),
+        }
+
+        let mut truncated = email.clone();
+        check_limits(&mut truncated, &caps, LimitPolicy::Truncate).unwrap();
+        assert_eq!(truncated.subject, "é".repeat(499));
+        assert_eq!(truncated.headers["X-Note"].len(), MAX_LINE_LENGTH);
+
+        // Recipients are never dropped
+        let mut crowded = email.to((0..50)
+            .map(|i| format!("user{}@example.com", i))
+            .collect::<Vec<_>>());
+        let err = check_limits(&mut crowded, &caps, LimitPolicy::Truncate).unwrap_err();
+        assert_eq!(err.to_string(), "recipients exceeds the limit of 50: 51");
+        assert_eq!(err.kind(), "validation");
+    }
+}
@@ -0,0 +1,220 @@
+//! Disposable and role-account address detection.
+//!
+//! Signup flows can refuse throwaway inboxes, and transactional mail can
+//! skip shared mailboxes:
+//!
+//! ```rust
+//! use missive::Address;
+//!
+//! assert!(Address::new("x@mailinator.com").is_disposable());
+//! assert!(Address::new("Support+billing@example.com").is_role_account());
+//! assert!(!Address::new("jane@example.com").is_disposable());
+//! ```
+//!
+//! The disposable domain list is embedded at build time from
+//! `data/disposable_domains.txt`. Subdomains of listed domains match too.
+//! Add your own domains at runtime with [`add_disposable_domains`] or
+//! [`load_disposable_domains`].
+//!
+//! # Recipient Checks
+//!
+//! With the `dns` feature, [`check_recipient`] looks up the recipient
+//! domain's MX records before an expensive campaign send. The `smtp-probe`
+//! feature adds an optional `RCPT TO` probe against the mail server:
+//!
+//! ```rust,ignore
+//! use missive::verify::{check_recipient_with, RecipientCheckOptions, RecipientStatus};
+//!
+//! let options = RecipientCheckOptions::new()
+//!     .smtp_probe(true)
+//!     .helo_domain("mail.example.com");
+//!
+//! let check = check_recipient_with("jane@example.org", &options).await?;
+//! if let RecipientStatus::Rejected { message, .. } = &check.status {
+//!     println!("{} bounced: {}", check.address, message);
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use parking_lot::RwLock;
+
+use crate::address::Address;
+use crate::error::MailError;
+
+#[cfg(feature = "dns")]
+mod recipient;
+#[cfg(feature = "dns")]
+pub use recipient::{
+    check_recipient, check_recipient_with, RecipientCheck, RecipientCheckOptions, RecipientStatus,
+};
+
+/// The embedded disposable domain list.
+const DISPOSABLE_DOMAINS: &str = include_str!("../../data/disposable_domains.txt");
+
+/// Local parts of shared mailboxes rather than people.
+pub const ROLE_ACCOUNTS: &[&str] = &[
+    "abuse",
+    "accounting",
+    "admin",
+    "administrator",
+    "billing",
+    "compliance",
+    "contact",
+    "devnull",
+    "dns",
+    "enquiries",
+    "feedback",
+    "ftp",
+    "help",
+    "hostmaster",
+    "hr",
+    "info",
+    "inquiries",
+    "it",
+    "jobs",
+    "legal",
+    "list",
+    "mail",
+    "mailer-daemon",
+    "marketing",
+    "media",
+    "news",
+    "no-reply",
+    "noc",
+    "noreply",
+    "office",
+    "orders",
+    "postmaster",
+    "press",
+    "privacy",
+    "root",
+    "sales",
+    "security",
+    "service",
+    "support",
+    "sysadmin",
+    "team",
+    "webmaster",
+    "www",
+];
+
+fn domains() -> &'static RwLock<HashSet<String>> {
+    static DOMAINS: OnceLock<RwLock<HashSet<String>>> = OnceLock::new();
+    DOMAINS.get_or_init(|| RwLock::new(parse_list(DISPOSABLE_DOMAINS).collect()))
+}
+
+/// Non-empty, non-comment lines, lowercased.
+fn parse_list(contents: &str) -> impl Iterator<Item = String> + '_ {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_ascii_lowercase)
+}
+
+/// Whether `domain` (or a parent domain) is a known disposable domain.
+pub fn is_disposable_domain(domain: &str) -> bool {
+    let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+    let domains = domains().read();
+    let mut candidate = domain.as_str();
+    loop {
+        if domains.contains(candidate) {
+            return true;
+        }
+        match candidate.split_once('.') {
+            Some((_, parent)) if parent.contains('.') => candidate = parent,
+            _ => return false,
+        }
+    }
+}
+
+/// Whether a local part (before `@`) names a shared mailbox such as
+/// `support` or `noreply`. A `+tag` suffix is ignored.
+pub fn is_role_local_part(local: &str) -> bool {
+    let local = local.split('+').next().unwrap_or_default();
+    ROLE_ACCOUNTS
+        .iter()
+        .any(|role| role.eq_ignore_ascii_case(local))
+}
+
+/// Add domains to the disposable list for this process.
+pub fn add_disposable_domains<I, S>(domains: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut list = self::domains().write();
+    for domain in domains {
+        list.extend(parse_list(domain.as_ref()));
+    }
+}
+
+/// Add the domains in a file (one per line, `#` comments) to the
+/// disposable list. Returns the number of lines read.
+pub fn load_disposable_domains(path: impl AsRef<Path>) -> Result<usize, MailError> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        MailError::Configuration(format!("failed to read {}: {}", path.display(), e))
+    })?;
+    let new: Vec<String> = parse_list(&contents).collect();
+    let count = new.len();
+    domains().write().extend(new);
+    Ok(count)
+}
+
+impl Address {
+    /// Whether the address is at a known disposable (throwaway) domain.
+    pub fn is_disposable(&self) -> bool {
+        self.email
+            .rsplit_once('@')
+            .is_some_and(|(_, domain)| is_disposable_domain(domain))
+    }
+
+    /// Whether the address is a shared role mailbox such as `support@` or
+    /// `noreply@`.
+    pub fn is_role_account(&self) -> bool {
+        self.email
+            .rsplit_once('@')
+            .is_some_and(|(local, _)| is_role_local_part(local))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_disposable() {
+        assert!(Address::new("x@Mailinator.com").is_disposable());
+        assert!(Address::new("x@eu.mailinator.com").is_disposable());
+        assert!(!Address::new("x@example.com").is_disposable());
+        assert!(!Address::new("x@com").is_disposable());
+        assert!(!Address::new("not-an-email").is_disposable());
+    }
+
+    #[test]
+    fn test_add_disposable_domains() {
+        assert!(!is_disposable_domain("throwaway.test"));
+        add_disposable_domains(["Throwaway.test"]);
+        assert!(is_disposable_domain("throwaway.test"));
+    }
+
+    #[test]
+    fn test_embedded_list_is_sorted() {
+        let domains: Vec<String> = parse_list(DISPOSABLE_DOMAINS).collect();
+        let mut sorted = domains.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(domains, sorted);
+    }
+
+    #[test]
+    fn test_is_role_account() {
+        assert!(Address::new("support@example.com").is_role_account());
+        assert!(Address::new("No-Reply+x@example.com").is_role_account());
+        assert!(!Address::new("jane@example.com").is_role_account());
+    }
+}
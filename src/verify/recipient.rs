@@ -0,0 +1,422 @@
+//! Recipient domain checks (MX lookup and optional SMTP probe).
+
+use std::fmt;
+use std::time::Duration;
+
+use hickory_resolver::error::ResolveErrorKind;
+use hickory_resolver::proto::op::ResponseCode;
+use hickory_resolver::TokioAsyncResolver;
+
+use crate::address::ToAddress;
+use crate::error::MailError;
+
+/// Whether a recipient looks deliverable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecipientStatus {
+    /// The domain accepts mail (and the SMTP probe, if run, accepted the
+    /// recipient).
+    Deliverable,
+    /// The address is malformed.
+    InvalidAddress,
+    /// The domain does not exist.
+    UnknownDomain,
+    /// The domain exists but has no mail server, or publishes a null MX.
+    NoMailServer,
+    /// The mail server rejected the recipient during the SMTP probe.
+    Rejected {
+        /// SMTP reply code (5xx).
+        code: u16,
+        /// SMTP reply text.
+        message: String,
+    },
+    /// The check was inconclusive (temporary DNS or SMTP failure, greylisting).
+    Unknown(String),
+}
+
+impl fmt::Display for RecipientStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deliverable => write!(f, "deliverable"),
+            Self::InvalidAddress => write!(f, "invalid address"),
+            Self::UnknownDomain => write!(f, "domain does not exist"),
+            Self::NoMailServer => write!(f, "domain does not accept mail"),
+            Self::Rejected { code, message } => write!(f, "rejected ({}): {}", code, message),
+            Self::Unknown(reason) => write!(f, "unknown: {}", reason),
+        }
+    }
+}
+
+/// Result of [`check_recipient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipientCheck {
+    /// The address checked.
+    pub address: String,
+    /// Mail servers for the domain, most preferred first.
+    pub mx_hosts: Vec<String>,
+    /// The verdict.
+    pub status: RecipientStatus,
+}
+
+impl RecipientCheck {
+    /// Whether the recipient looks deliverable.
+    pub fn is_deliverable(&self) -> bool {
+        self.status == RecipientStatus::Deliverable
+    }
+}
+
+/// Options for [`check_recipient_with`].
+#[derive(Debug, Clone)]
+pub struct RecipientCheckOptions {
+    #[cfg_attr(not(feature = "smtp-probe"), allow(dead_code))]
+    smtp_probe: bool,
+    helo_domain: String,
+    mail_from: String,
+    timeout: Duration,
+}
+
+impl Default for RecipientCheckOptions {
+    fn default() -> Self {
+        Self {
+            smtp_probe: false,
+            helo_domain: "localhost".to_string(),
+            mail_from: String::new(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RecipientCheckOptions {
+    /// Create the default options (MX lookup only).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also connect to the mail server and ask whether it accepts the
+    /// recipient (`RCPT TO`), without sending a message.
+    ///
+    /// Many networks block outbound port 25, and some servers accept every
+    /// recipient or greylist unknown senders, so treat a rejection as the
+    /// only strong signal.
+    #[cfg(feature = "smtp-probe")]
+    pub fn smtp_probe(mut self, enabled: bool) -> Self {
+        self.smtp_probe = enabled;
+        self
+    }
+
+    /// Domain announced in `EHLO` (default: `localhost`). Use your sending
+    /// domain; some servers reject unknown names.
+    pub fn helo_domain(mut self, domain: impl Into<String>) -> Self {
+        self.helo_domain = domain.into();
+        self
+    }
+
+    /// Envelope sender for the probe (default: the null sender `<>`).
+    pub fn mail_from(mut self, addr: impl ToAddress) -> Self {
+        self.mail_from = addr.to_address().email;
+        self
+    }
+
+    /// Timeout for each DNS lookup and SMTP step (default: 10 seconds).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Check that a recipient's domain accepts mail, via DNS MX lookup.
+///
+/// Returns `Err` only if the DNS resolver can't be set up; lookup failures
+/// are reported as [`RecipientStatus::Unknown`].
+pub async fn check_recipient(addr: impl ToAddress) -> Result<RecipientCheck, MailError> {
+    check_recipient_with(addr, &RecipientCheckOptions::default()).await
+}
+
+/// Check a recipient with the given options.
+pub async fn check_recipient_with(
+    addr: impl ToAddress,
+    options: &RecipientCheckOptions,
+) -> Result<RecipientCheck, MailError> {
+    let address = addr.to_address().email;
+    let mut check = RecipientCheck {
+        address: address.clone(),
+        mx_hosts: Vec::new(),
+        status: RecipientStatus::Deliverable,
+    };
+
+    let domain = match address.rsplit_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => {
+            domain.trim_end_matches('.').to_lowercase()
+        }
+        _ => {
+            check.status = RecipientStatus::InvalidAddress;
+            return Ok(check);
+        }
+    };
+
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()
+        .map_err(|e| MailError::Internal(format!("DNS resolver setup failed: {}", e)))?;
+
+    match lookup_mx(&resolver, &domain, options.timeout).await {
+        Ok(records) => match evaluate_mx(&domain, records) {
+            Ok(hosts) => check.mx_hosts = hosts,
+            Err(status) => check.status = status,
+        },
+        Err(status) => check.status = status,
+    }
+
+    #[cfg(feature = "smtp-probe")]
+    if options.smtp_probe && check.is_deliverable() {
+        check.status = probe::probe_hosts(&check.mx_hosts, &address, options).await;
+    }
+
+    Ok(check)
+}
+
+/// Look up MX records, falling back to the domain itself (implicit MX) when
+/// it has an address record but no MX.
+async fn lookup_mx(
+    resolver: &TokioAsyncResolver,
+    domain: &str,
+    timeout: Duration,
+) -> Result<Vec<(u16, String)>, RecipientStatus> {
+    let timed_out = || RecipientStatus::Unknown(format!("DNS lookup for {} timed out", domain));
+    let lookup = tokio::time::timeout(timeout, resolver.mx_lookup(domain))
+        .await
+        .map_err(|_| timed_out())?;
+
+    match lookup {
+        Ok(mx) => Ok(mx
+            .iter()
+            .map(|r| (r.preference(), r.exchange().to_utf8()))
+            .collect()),
+        Err(e) => match e.kind() {
+            ResolveErrorKind::NoRecordsFound { response_code, .. }
+                if *response_code == ResponseCode::NXDomain =>
+            {
+                Err(RecipientStatus::UnknownDomain)
+            }
+            ResolveErrorKind::NoRecordsFound { .. } => {
+                let ip = tokio::time::timeout(timeout, resolver.lookup_ip(domain))
+                    .await
+                    .map_err(|_| timed_out())?;
+                match ip {
+                    Ok(_) => Ok(vec![(0, domain.to_string())]),
+                    Err(_) => Err(RecipientStatus::NoMailServer),
+                }
+            }
+            _ => Err(RecipientStatus::Unknown(format!(
+                "DNS lookup for {} failed: {}",
+                domain, e
+            ))),
+        },
+    }
+}
+
+/// Sort MX records by preference, rejecting a null MX (RFC 7505).
+fn evaluate_mx(
+    domain: &str,
+    mut records: Vec<(u16, String)>,
+) -> Result<Vec<String>, RecipientStatus> {
+    records.sort();
+    let hosts: Vec<String> = records
+        .into_iter()
+        .map(|(_, host)| host.trim_end_matches('.').to_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+    if hosts.is_empty() {
+        tracing::debug!(domain = domain, "Domain publishes a null MX");
+        return Err(RecipientStatus::NoMailServer);
+    }
+    Ok(hosts)
+}
+
+#[cfg(feature = "smtp-probe")]
+mod probe {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpStream;
+
+    use super::{RecipientCheckOptions, RecipientStatus};
+
+    /// Probe each MX host in order until one gives an answer.
+    pub(super) async fn probe_hosts(
+        hosts: &[String],
+        address: &str,
+        options: &RecipientCheckOptions,
+    ) -> RecipientStatus {
+        let mut last = RecipientStatus::Unknown("no mail servers to probe".to_string());
+        for host in hosts {
+            match probe(host, 25, address, options).await {
+                Ok(status) => return status,
+                Err(reason) => {
+                    tracing::debug!(host = %host, reason = %reason, "SMTP probe failed");
+                    last = RecipientStatus::Unknown(reason);
+                }
+            }
+        }
+        last
+    }
+
+    /// Run `EHLO`, `MAIL FROM` and `RCPT TO` against one server.
+    ///
+    /// `Err` means the server couldn't be asked (connection or protocol
+    /// failure), so the next MX host should be tried.
+    pub(super) async fn probe(
+        host: &str,
+        port: u16,
+        address: &str,
+        options: &RecipientCheckOptions,
+    ) -> Result<RecipientStatus, String> {
+        let step = |what: &str| format!("{}:{} {} timed out", host, port, what);
+        let stream = tokio::time::timeout(options.timeout, TcpStream::connect((host, port)))
+            .await
+            .map_err(|_| step("connect"))?
+            .map_err(|e| format!("{}:{}: {}", host, port, e))?;
+        let mut stream = BufReader::new(stream);
+
+        let commands = [
+            None,
+            Some(format!("EHLO {}", options.helo_domain)),
+            Some(format!("MAIL FROM:<{}>", options.mail_from)),
+        ];
+        for command in commands {
+            if let Some(command) = &command {
+                write_line(&mut stream, command).await?;
+            }
+            let (code, message) = tokio::time::timeout(options.timeout, read_reply(&mut stream))
+                .await
+                .map_err(|_| step("reply"))??;
+            if !(200..400).contains(&code) {
+                return Err(format!("{} replied {} {}", host, code, message));
+            }
+        }
+
+        write_line(&mut stream, &format!("RCPT TO:<{}>", address)).await?;
+        let (code, message) = tokio::time::timeout(options.timeout, read_reply(&mut stream))
+            .await
+            .map_err(|_| step("reply"))??;
+        let _ = write_line(&mut stream, "QUIT").await;
+
+        Ok(match code {
+            200..=299 => RecipientStatus::Deliverable,
+            500..=599 => RecipientStatus::Rejected { code, message },
+            _ => RecipientStatus::Unknown(format!("{} replied {} {}", host, code, message)),
+        })
+    }
+
+    async fn write_line(stream: &mut BufReader<TcpStream>, line: &str) -> Result<(), String> {
+        stream
+            .get_mut()
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Read a (possibly multi-line) SMTP reply.
+    async fn read_reply(stream: &mut BufReader<TcpStream>) -> Result<(u16, String), String> {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            if stream
+                .read_line(&mut line)
+                .await
+                .map_err(|e| e.to_string())?
+                == 0
+            {
+                return Err("connection closed".to_string());
+            }
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|c| c.parse::<u16>().ok())
+                .ok_or_else(|| format!("invalid SMTP reply: {}", line))?;
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, lines.join(" ")));
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use tokio::net::TcpListener;
+
+        /// A fake SMTP server that rejects `rejected@` recipients.
+        async fn server() -> u16 {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            tokio::spawn(async move {
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    tokio::spawn(async move {
+                        let (read, mut write) = stream.into_split();
+                        let mut read = BufReader::new(read);
+                        write.write_all(b"220 mx.test ESMTP\r\n").await.unwrap();
+                        let mut line = String::new();
+                        while read.read_line(&mut line).await.unwrap() > 0 {
+                            let reply: &[u8] = match line.trim_end() {
+                                l if l.starts_with("EHLO") => b"250-mx.test\r\n250 SIZE 1000\r\n",
+                                l if l.contains("rejected@") => b"550 5.1.1 No such user\r\n",
+                                "QUIT" => b"221 Bye\r\n",
+                                _ => b"250 OK\r\n",
+                            };
+                            write.write_all(reply).await.unwrap();
+                            line.clear();
+                        }
+                    });
+                }
+            });
+            port
+        }
+
+        #[tokio::test]
+        async fn test_probe() {
+            let port = server().await;
+            let options = RecipientCheckOptions::new().smtp_probe(true);
+
+            let status = probe("127.0.0.1", port, "jane@example.com", &options)
+                .await
+                .unwrap();
+            assert_eq!(status, RecipientStatus::Deliverable);
+
+            let status = probe("127.0.0.1", port, "rejected@example.com", &options)
+                .await
+                .unwrap();
+            assert_eq!(
+                status,
+                RecipientStatus::Rejected {
+                    code: 550,
+                    message: "5.1.1 No such user".into()
+                }
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_mx() {
+        let records = vec![
+            (20, "backup.example.com.".to_string()),
+            (10, "MX1.example.com.".to_string()),
+        ];
+        assert_eq!(
+            evaluate_mx("example.com", records).unwrap(),
+            ["mx1.example.com", "backup.example.com"]
+        );
+        assert_eq!(
+            evaluate_mx("example.com", vec![(0, ".".to_string())]),
+            Err(RecipientStatus::NoMailServer)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_address() {
+        let check = check_recipient("not-an-email").await.unwrap();
+        assert_eq!(check.status, RecipientStatus::InvalidAddress);
+        assert!(!check.is_deliverable());
+    }
+}
@@ -0,0 +1,341 @@
+//! Minimal streaming ZIP writer for [`Attachment::zip`](crate::Attachment::zip).
+//!
+//! Entries are deflated in a single pass: each local header is followed by
+//! the compressed data and a data descriptor carrying the CRC and sizes, so
+//! nothing is buffered beyond the archive itself. ZIP64 is not supported;
+//! the size cap keeps archives well below its 4 GiB limits.
+
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::attachment::Attachment;
+use crate::error::MailError;
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const DATA_DESCRIPTOR: u32 = 0x0807_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+
+/// Version 2.0: deflate.
+const VERSION: u16 = 20;
+/// Bit 3: sizes in data descriptor; bit 11: UTF-8 names.
+const FLAGS: u16 = 0x0808;
+const DEFLATE: u16 = 8;
+
+struct Entry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    uncompressed: u32,
+    offset: u32,
+}
+
+/// Output buffer that fails once it grows past `limit`.
+struct Capped {
+    buf: Vec<u8>,
+    limit: usize,
+    exceeded: bool,
+}
+
+impl Write for Capped {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + data.len() > self.limit {
+            self.exceeded = true;
+            return Err(io::Error::other("size limit exceeded"));
+        }
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reader that tracks the CRC-32 and length of what passes through.
+struct Checksum<R> {
+    inner: R,
+    hasher: crc32fast::Hasher,
+    len: u64,
+}
+
+impl<R: Read> Read for Checksum<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.len += n as u64;
+        Ok(n)
+    }
+}
+
+/// Write `files` into a ZIP archive of at most `limit` bytes.
+pub(crate) fn write(
+    name: &str,
+    files: Vec<Attachment>,
+    limit: usize,
+) -> Result<Vec<u8>, MailError> {
+    if files.len() > u16::MAX as usize {
+        return Err(MailError::AttachmentError(format!(
+            "{}: too many files ({})",
+            name,
+            files.len()
+        )));
+    }
+    let too_large = || MailError::AttachmentError(format!("{}: exceeds {} bytes", name, limit));
+    let mut out = Capped {
+        buf: Vec::new(),
+        limit: limit.min(u32::MAX as usize),
+        exceeded: false,
+    };
+    let (time, date) = dos_datetime(chrono::Local::now().naive_local());
+    let mut names = HashSet::new();
+    let mut entries = Vec::with_capacity(files.len());
+
+    for file in &files {
+        if !names.insert(file.filename.as_str()) {
+            return Err(MailError::AttachmentError(format!(
+                "{}: duplicate file name {}",
+                name, file.filename
+            )));
+        }
+        let offset = out.buf.len() as u32;
+        let header = local_header(&file.filename, time, date);
+        out.write_all(&header).map_err(|_| too_large())?;
+
+        let source: Box<dyn Read> = match &file.path {
+            Some(path) => Box::new(std::fs::File::open(path).map_err(|e| {
+                if e.kind() == io::ErrorKind::NotFound {
+                    MailError::AttachmentFileNotFound(path.clone())
+                } else {
+                    MailError::AttachmentReadError(format!("{}: {}", path, e))
+                }
+            })?),
+            None if file.data.is_empty() => {
+                return Err(MailError::AttachmentMissingContent(file.filename.clone()))
+            }
+            None => Box::new(file.data.as_slice()),
+        };
+        let mut source = Checksum {
+            inner: source,
+            hasher: crc32fast::Hasher::new(),
+            len: 0,
+        };
+
+        let start = out.buf.len();
+        let copied = {
+            let mut encoder = DeflateEncoder::new(&mut out, Compression::default());
+            io::copy(&mut source, &mut encoder).and_then(|_| encoder.finish().map(drop))
+        };
+        if let Err(e) = copied {
+            return Err(if out.exceeded {
+                too_large()
+            } else {
+                MailError::AttachmentReadError(format!("{}: {}", file.filename, e))
+            });
+        }
+
+        let entry = Entry {
+            name: file.filename.clone(),
+            crc: source.hasher.finalize(),
+            compressed: (out.buf.len() - start) as u32,
+            uncompressed: u32::try_from(source.len).map_err(|_| too_large())?,
+            offset,
+        };
+        let mut descriptor = Vec::with_capacity(16);
+        put32(&mut descriptor, DATA_DESCRIPTOR);
+        put32(&mut descriptor, entry.crc);
+        put32(&mut descriptor, entry.compressed);
+        put32(&mut descriptor, entry.uncompressed);
+        out.write_all(&descriptor).map_err(|_| too_large())?;
+        entries.push(entry);
+    }
+
+    let directory_offset = out.buf.len() as u32;
+    let mut directory = Vec::new();
+    for entry in &entries {
+        put32(&mut directory, CENTRAL_HEADER);
+        put16(&mut directory, VERSION); // made by
+        put16(&mut directory, VERSION); // needed to extract
+        put16(&mut directory, FLAGS);
+        put16(&mut directory, DEFLATE);
+        put16(&mut directory, time);
+        put16(&mut directory, date);
+        put32(&mut directory, entry.crc);
+        put32(&mut directory, entry.compressed);
+        put32(&mut directory, entry.uncompressed);
+        put16(&mut directory, entry.name.len() as u16);
+        put16(&mut directory, 0); // extra field length
+        put16(&mut directory, 0); // comment length
+        put16(&mut directory, 0); // disk number
+        put16(&mut directory, 0); // internal attributes
+        put32(&mut directory, 0); // external attributes
+        put32(&mut directory, entry.offset);
+        directory.extend_from_slice(entry.name.as_bytes());
+    }
+    let directory_size = directory.len() as u32;
+
+    put32(&mut directory, END_OF_CENTRAL_DIRECTORY);
+    put16(&mut directory, 0); // this disk
+    put16(&mut directory, 0); // disk with the central directory
+    put16(&mut directory, entries.len() as u16);
+    put16(&mut directory, entries.len() as u16);
+    put32(&mut directory, directory_size);
+    put32(&mut directory, directory_offset);
+    put16(&mut directory, 0); // comment length
+    out.write_all(&directory).map_err(|_| too_large())?;
+
+    Ok(out.buf)
+}
+
+fn local_header(name: &str, time: u16, date: u16) -> Vec<u8> {
+    let mut header = Vec::with_capacity(30 + name.len());
+    put32(&mut header, LOCAL_HEADER);
+    put16(&mut header, VERSION);
+    put16(&mut header, FLAGS);
+    put16(&mut header, DEFLATE);
+    put16(&mut header, time);
+    put16(&mut header, date);
+    put32(&mut header, 0); // CRC, in the data descriptor
+    put32(&mut header, 0); // compressed size
+    put32(&mut header, 0); // uncompressed size
+    put16(&mut header, name.len() as u16);
+    put16(&mut header, 0); // extra field length
+    header.extend_from_slice(name.as_bytes());
+    header
+}
+
+/// MS-DOS time and date, clamped to the format's 1980-2107 range.
+fn dos_datetime(now: chrono::NaiveDateTime) -> (u16, u16) {
+    use chrono::{Datelike, Timelike};
+
+    let year = now.year().clamp(1980, 2107) as u16;
+    let time = (now.hour() as u16) << 11 | (now.minute() as u16) << 5 | (now.second() as u16 / 2);
+    let date = (year - 1980) << 9 | (now.month() as u16) << 5 | now.day() as u16;
+    (time, date)
+}
+
+fn put16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::DeflateDecoder;
+
+    fn u16_at(buf: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([buf[at], buf[at + 1]])
+    }
+
+    fn u32_at(buf: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+    }
+
+    /// Read every entry through the central directory.
+    fn unzip(archive: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let end = archive.len() - 22;
+        assert_eq!(u32_at(archive, end), END_OF_CENTRAL_DIRECTORY);
+        let count = u16_at(archive, end + 10) as usize;
+        let mut at = u32_at(archive, end + 16) as usize;
+
+        (0..count)
+            .map(|_| {
+                assert_eq!(u32_at(archive, at), CENTRAL_HEADER);
+                let crc = u32_at(archive, at + 16);
+                let compressed = u32_at(archive, at + 20) as usize;
+                let name_len = u16_at(archive, at + 28) as usize;
+                let offset = u32_at(archive, at + 42) as usize;
+                let name =
+                    String::from_utf8(archive[at + 46..at + 46 + name_len].to_vec()).unwrap();
+                at += 46 + name_len;
+
+                assert_eq!(u32_at(archive, offset), LOCAL_HEADER);
+                let data_start = offset + 30 + u16_at(archive, offset + 26) as usize;
+                let mut data = Vec::new();
+                DeflateDecoder::new(&archive[data_start..data_start + compressed])
+                    .read_to_end(&mut data)
+                    .unwrap();
+                assert_eq!(crc32fast::hash(&data), crc);
+                assert_eq!(u32_at(archive, data_start + compressed), DATA_DESCRIPTOR);
+                (name, data)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = std::env::temp_dir().join(format!("missive-zip-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("report.csv");
+        std::fs::write(&path, "a,b\n1,2\n".repeat(1000)).unwrap();
+
+        let archive = write(
+            "bundle.zip",
+            vec![
+                Attachment::from_bytes("notes.txt", b"hello".to_vec()),
+                Attachment::from_path_lazy(&path).unwrap(),
+            ],
+            usize::MAX,
+        )
+        .unwrap();
+
+        let entries = unzip(&archive);
+        assert_eq!(entries[0], ("notes.txt".to_string(), b"hello".to_vec()));
+        assert_eq!(entries[1].0, "report.csv");
+        assert_eq!(entries[1].1, "a,b\n1,2\n".repeat(1000).into_bytes());
+        assert!(archive.len() < 8000);
+    }
+
+    #[test]
+    fn test_limit() {
+        // xorshift output doesn't compress
+        let mut state = 0x2545_f491u32;
+        let noise: Vec<u8> = (0..10_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        let result = write(
+            "bundle.zip",
+            vec![Attachment::from_bytes("noise.bin", noise)],
+            4096,
+        );
+        assert!(matches!(result, Err(MailError::AttachmentError(msg)) if msg.contains("4096")));
+    }
+
+    #[test]
+    fn test_rejects_duplicates_and_empty() {
+        let file = Attachment::from_bytes("a.txt", b"a".to_vec());
+        assert!(write("x.zip", vec![file.clone(), file], usize::MAX).is_err());
+        assert!(matches!(
+            write(
+                "x.zip",
+                vec![Attachment::from_bytes("empty.txt", Vec::new())],
+                usize::MAX
+            ),
+            Err(MailError::AttachmentMissingContent(_))
+        ));
+    }
+
+    #[test]
+    fn test_dos_datetime() {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(13, 45, 30)
+            .unwrap();
+        assert_eq!(
+            dos_datetime(now),
+            (13 << 11 | 45 << 5 | 15, 44 << 9 | 3 << 5 | 15)
+        );
+    }
+}
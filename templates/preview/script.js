@@ -65,6 +65,8 @@ function formatRelativeDate(isoString) {
 
 let currentEmailId = null;
 const basePath = window.location.pathname.replace(/\/?$/, '');
+const mailbox = new URLSearchParams(window.location.search).get('mailbox') || '';
+const mailboxQuery = mailbox ? `?mailbox=${encodeURIComponent(mailbox)}` : '';
 
 // ============================================================================
 // Email Selection
@@ -84,6 +86,7 @@ async function selectEmail(id) {
 
         const email = await response.json();
         renderEmail(email);
+        if (!email.read) markRead(id);
     } catch (error) {
         console.error('Error loading email:', error);
         document.getElementById('email-view').innerHTML = `
@@ -122,16 +125,84 @@ function renderEmail(email) {
 
     document.getElementById('email-view').innerHTML = `
         <div class="email-detail">
-            ${metadataHtml}
-            ${headersHtml}
-            ${providerOptionsHtml}
-            ${textBodyHtml}
-            ${htmlBodyHtml}
-            ${attachmentsHtml}
+            <div class="tabs">
+                <button class="tab active" data-tab="preview" onclick="showTab('preview')">Preview</button>
+                <button class="tab" data-tab="source" onclick="showTab('source')">Source</button>
+                <button class="tab" data-tab="compat" onclick="showTab('compat')">Compatibility</button>
+                <div class="tab-actions">
+                    <button class="btn-action" onclick="forwardEmail('${email.id}')" title="Re-deliver through a real provider">Send to real address</button>
+                    <button class="btn-action btn-delete" onclick="deleteEmail('${email.id}')" title="Delete email">Delete</button>
+                </div>
+            </div>
+            <div class="tab-panel" data-tab="preview">
+                ${metadataHtml}
+                ${headersHtml}
+                ${providerOptionsHtml}
+                ${textBodyHtml}
+                ${htmlBodyHtml}
+                ${attachmentsHtml}
+            </div>
+            <div class="tab-panel" data-tab="source" hidden>
+                <pre class="source-content"></pre>
+            </div>
+            <div class="tab-panel" data-tab="compat" hidden>
+                <div class="compat-content"></div>
+            </div>
         </div>
     `;
 }
 
+async function showTab(name) {
+    document.querySelectorAll('.tab').forEach(tab => {
+        tab.classList.toggle('active', tab.dataset.tab === name);
+    });
+    document.querySelectorAll('.tab-panel').forEach(panel => {
+        panel.hidden = panel.dataset.tab !== name;
+    });
+
+    if (name === 'source') await loadSource();
+    if (name === 'compat') await loadCompat();
+}
+
+async function loadSource() {
+    const source = document.querySelector('.source-content');
+    if (!currentEmailId || source.dataset.loaded) return;
+
+    try {
+        const response = await fetch(`${basePath}/${currentEmailId}/source`);
+        if (!response.ok) throw new Error('Failed to load source');
+        source.textContent = await response.text();
+        source.dataset.loaded = 'true';
+    } catch (error) {
+        console.error('Error loading source:', error);
+        source.textContent = 'Error loading source';
+    }
+}
+
+async function loadCompat() {
+    const compat = document.querySelector('.compat-content');
+    if (!currentEmailId || compat.dataset.loaded) return;
+
+    try {
+        const response = await fetch(`${basePath}/${currentEmailId}/compat`);
+        if (!response.ok) throw new Error('Failed to load compatibility report');
+        const warnings = await response.json();
+        compat.innerHTML = warnings.length === 0
+            ? '<p class="compat-empty">No compatibility issues found</p>'
+            : warnings.map(w => `
+                <div class="compat-item ${w.severity}">
+                    <span class="compat-severity">${escapeHtml(w.severity)}</span>
+                    <span class="compat-message">${escapeHtml(w.message)}</span>
+                    <span class="compat-rule">${escapeHtml(w.rule)}</span>
+                </div>
+            `).join('');
+        compat.dataset.loaded = 'true';
+    } catch (error) {
+        console.error('Error loading compatibility report:', error);
+        compat.textContent = 'Error loading compatibility report';
+    }
+}
+
 function renderMetadata(email) {
     const rows = [
         { label: 'From', value: email.from },
@@ -229,17 +300,87 @@ function renderHtmlBody(emailId) {
         <div class="html-body-section">
             <div class="section-header">
                 <span>HTML body</span>
+                <div class="viewport-toggles">
+                    <button class="viewport-toggle" data-viewport="desktop" onclick="setViewport('desktop')" title="Full width">Desktop</button>
+                    <button class="viewport-toggle" data-viewport="tablet" onclick="setViewport('tablet')" title="768px wide">Tablet</button>
+                    <button class="viewport-toggle" data-viewport="mobile" onclick="setViewport('mobile')" title="375px wide">Mobile</button>
+                    <button class="viewport-toggle" data-dark onclick="toggleEmailDark()" title="Simulate an email client's dark mode">Dark</button>
+                </div>
                 <a href="${basePath}/${emailId}/html" target="_blank" class="open-external" title="Open in new tab">
                     ${externalSvg}
                 </a>
             </div>
             <div class="html-body-content">
-                <iframe src="${basePath}/${emailId}/html" sandbox="allow-same-origin"></iframe>
+                <iframe src="${basePath}/${emailId}/html" sandbox="allow-same-origin" onload="applyViewport()"></iframe>
             </div>
         </div>
     `;
 }
 
+// ============================================================================
+// Viewport Simulation
+// ============================================================================
+
+const viewportWidths = { desktop: null, tablet: 768, mobile: 375 };
+
+function setViewport(name) {
+    localStorage.setItem('missive-viewport', name);
+    applyViewport();
+}
+
+function toggleEmailDark() {
+    const dark = localStorage.getItem('missive-email-dark') !== 'true';
+    localStorage.setItem('missive-email-dark', dark);
+    applyViewport();
+}
+
+function applyViewport() {
+    const iframe = document.querySelector('.html-body-content iframe');
+    if (!iframe) return;
+
+    const saved = localStorage.getItem('missive-viewport');
+    const name = saved in viewportWidths ? saved : 'desktop';
+    const width = viewportWidths[name];
+    const dark = localStorage.getItem('missive-email-dark') === 'true';
+
+    document.querySelectorAll('.viewport-toggle[data-viewport]').forEach(btn => {
+        btn.classList.toggle('active', btn.dataset.viewport === name);
+    });
+    document.querySelector('.viewport-toggle[data-dark]').classList.toggle('active', dark);
+
+    iframe.parentElement.classList.toggle('framed', width != null);
+    iframe.style.width = width ? `${width}px` : '';
+    iframe.style.colorScheme = dark ? 'dark' : '';
+    simulateDarkMode(iframe.contentDocument, dark);
+}
+
+// Emails with their own dark styles get them through `color-scheme`; others
+// are inverted, as Gmail and Outlook do, keeping images as they are.
+function simulateDarkMode(doc, dark) {
+    if (!doc || !doc.documentElement) return;
+
+    const filter = dark && !hasDarkStyles(doc) ? 'invert(1) hue-rotate(180deg)' : '';
+    doc.documentElement.style.filter = filter;
+    doc.documentElement.style.background = filter ? 'white' : '';
+    doc.querySelectorAll('img, video').forEach(el => {
+        el.style.filter = filter;
+    });
+}
+
+function hasDarkStyles(doc) {
+    const meta = doc.querySelector('meta[name="color-scheme"], meta[name="supported-color-schemes"]');
+    if (meta && /dark/.test(meta.content)) return true;
+
+    return Array.from(doc.styleSheets).some(sheet => {
+        try {
+            return Array.from(sheet.cssRules).some(rule =>
+                rule.media && /prefers-color-scheme:\s*dark/.test(rule.media.mediaText));
+        } catch (error) {
+            return false;
+        }
+    });
+}
+
 function renderAttachments(email) {
     if (!email.attachments || email.attachments.length === 0) return '';
 
@@ -285,16 +426,112 @@ function toggleCollapsible(header) {
 // ============================================================================
 
 async function clearAll() {
-    if (!confirm('Clear all emails?')) return;
+    if (!confirm(mailbox ? `Clear all emails in ${mailbox}?` : 'Clear all emails?')) return;
 
     try {
-        await fetch(`${basePath}/clear`, { method: 'POST' });
+        await fetch(`${basePath}/clear${mailboxQuery}`, { method: 'POST' });
         location.reload();
     } catch (error) {
         console.error('Error clearing emails:', error);
     }
 }
 
+async function markRead(id) {
+    try {
+        const response = await fetch(`${basePath}/${id}/read`, { method: 'POST' });
+        if (!response.ok) return;
+        const item = document.querySelector(`.email-item[data-id="${CSS.escape(id)}"]`);
+        if (item) item.classList.remove('unread');
+        updateCount();
+    } catch (error) {
+        console.error('Error marking email read:', error);
+    }
+}
+
+async function deleteEmail(id) {
+    try {
+        const response = await fetch(`${basePath}/${id}`, { method: 'DELETE' });
+        if (response.ok) removeEmailItem(id);
+    } catch (error) {
+        console.error('Error deleting email:', error);
+    }
+}
+
+async function forwardEmail(id) {
+    const to = prompt('Send this email to:', localStorage.getItem('missive-forward-to') || '');
+    if (!to) return;
+    localStorage.setItem('missive-forward-to', to);
+
+    try {
+        const response = await fetch(`${basePath}/${id}/forward?to=${encodeURIComponent(to)}`, { method: 'POST' });
+        const result = await response.json().catch(() => ({}));
+        alert(response.ok ? `Sent to ${to}` : `Could not send: ${result.error || response.statusText}`);
+    } catch (error) {
+        console.error('Error forwarding email:', error);
+    }
+}
+
+function switchMailbox(name) {
+    const params = new URLSearchParams(window.location.search);
+    if (name) {
+        params.set('mailbox', name);
+    } else {
+        params.delete('mailbox');
+    }
+    window.location.search = params.toString();
+}
+
+// ============================================================================
+// Live Updates
+// ============================================================================
+
+function updateCount() {
+    const count = document.querySelectorAll('.email-item').length;
+    const unread = document.querySelectorAll('.email-item.unread').length;
+    document.querySelector('.email-count').textContent =
+        `${count} message${count === 1 ? '' : 's'}${unread ? `, ${unread} unread` : ''}`;
+    const emptyState = document.querySelector('.empty-state');
+    if (emptyState) emptyState.style.display = count === 0 ? '' : 'none';
+}
+
+function addEmailItem(email) {
+    const item = document.createElement('div');
+    item.className = email.read ? 'email-item' : 'email-item unread';
+    item.dataset.id = email.id;
+    item.innerHTML = `
+        <div class="email-item-from">${escapeHtml(email.from || '(no sender)')}</div>
+        <div class="email-item-subject">${escapeHtml(email.subject)}</div>
+    `;
+    item.addEventListener('click', () => selectEmail(email.id));
+    document.querySelector('.email-list').prepend(item);
+    updateCount();
+
+    if (currentEmailId === null) selectEmail(email.id);
+}
+
+function removeEmailItem(id) {
+    const item = document.querySelector(`.email-item[data-id="${CSS.escape(id)}"]`);
+    if (item) item.remove();
+    updateCount();
+
+    if (currentEmailId === id) {
+        currentEmailId = null;
+        document.getElementById('email-view').innerHTML = `
+            <div class="no-selection">
+                <p>Select an email to view</p>
+            </div>
+        `;
+    }
+}
+
+function connectLiveUpdates() {
+    if (!window.EventSource) return;
+
+    const events = new EventSource(`${basePath}/events${mailboxQuery}`);
+    events.addEventListener('email', (e) => addEmailItem(JSON.parse(e.data)));
+    events.addEventListener('remove', (e) => removeEmailItem(JSON.parse(e.data)));
+}
+
 // ============================================================================
 // Utilities
 // ============================================================================
@@ -324,6 +561,9 @@ function formatBytes(bytes) {
 // Initialize theme
 initTheme();
 
+// Push new emails into the list as they arrive
+connectLiveUpdates();
+
 // Auto-select first email if available
 const firstItem = document.querySelector('.email-item');
 if (firstItem) {
@@ -6,6 +6,8 @@
 mod amazon_ses_test;
 #[path = "adapters/brevo_test.rs"]
 mod brevo_test;
+#[path = "adapters/customerio_test.rs"]
+mod customerio_test;
 #[path = "adapters/local_test.rs"]
 mod local_test;
 #[path = "adapters/logger_test.rs"]
@@ -24,3 +26,5 @@ mod resend_test;
 mod sendgrid_test;
 #[path = "adapters/unsent_test.rs"]
 mod unsent_test;
+#[path = "adapters/zeptomail_test.rs"]
+mod zeptomail_test;
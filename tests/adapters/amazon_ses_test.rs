@@ -7,7 +7,7 @@
 //! we verify the request path, method, and response parsing.
 
 use missive::providers::AmazonSesMailer;
-use missive::{Email, Mailer};
+use missive::{BounceClass, Email, Mailer, MessageStatus};
 use serde_json::json;
 use wiremock::matchers::{body_string_contains, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -173,6 +173,25 @@ async fn optional_config_params_are_present_when_set() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn return_path_overrides_source() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri())
+        .ses_source("default@pirates.grog");
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("Source=bounces%2Bcaptain%3Dgrog%40pirates.grog"))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let email = valid_email().return_path("bounces+captain=grog@pirates.grog");
+    mailer.deliver(&email).await.unwrap();
+}
+
 #[tokio::test]
 async fn optional_config_params_not_present_when_not_set() {
     let server = MockServer::start().await;
@@ -216,6 +235,71 @@ async fn api_error_parses_correctly() {
     assert!(err.to_string().contains("Error Message"));
 }
 
+#[tokio::test]
+async fn throttling_error_is_rate_limited() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    let throttled = |message: &str| {
+        ResponseTemplate::new(400).set_body_string(format!(
+            r#"<ErrorResponse xmlns="http://ses.amazonaws.com/doc/2010-12-01/">
+                <Error>
+                    <Type>Sender</Type>
+                    <Code>Throttling</Code>
+                    <Message>{}</Message>
+                </Error>
+                <RequestId>a97266f7-b062-11e7-b126-6b0f7a9b3379</RequestId>
+            </ErrorResponse>"#,
+            message
+        ))
+    };
+
+    Mock::given(method("POST"))
+        .respond_with(throttled("Maximum sending rate exceeded."))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    let err = mailer.deliver(&valid_email()).await.unwrap_err();
+    assert_eq!(err.provider_error_code(), Some("Throttling"));
+    assert_eq!(err.kind(), "rate_limited");
+    assert!(err.is_retryable());
+
+    Mock::given(method("POST"))
+        .respond_with(throttled("Daily message quota exceeded."))
+        .mount(&server)
+        .await;
+    let err = mailer.deliver(&valid_email()).await.unwrap_err();
+    assert_eq!(err.status_code(), Some(400));
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn namespaced_response_parses_correctly() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <SendRawEmailResponse xmlns="http://ses.amazonaws.com/doc/2010-12-01/">
+                <SendRawEmailResult>
+                    <MessageId lang="en">0100018f-5e4a&amp;1</MessageId>
+                </SendRawEmailResult>
+                <ResponseMetadata>
+                    <RequestId>requestId</RequestId>
+                </ResponseMetadata>
+            </SendRawEmailResponse>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let result = mailer.deliver(&valid_email()).await.unwrap();
+    assert_eq!(result.message_id, "0100018f-5e4a&1");
+    assert_eq!(result.provider_response.unwrap()["request_id"], "requestId");
+}
+
 // ============================================================================
 // Validation Tests
 // ============================================================================
@@ -282,6 +366,181 @@ async fn delivery_with_security_token() {
     assert!(result.is_ok());
 }
 
+// ============================================================================
+// Bulk Templated Tests
+// ============================================================================
+
+#[tokio::test]
+async fn deliver_personalized_with_template_sends_bulk_templated_email() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    let email = Email::new()
+        .from("guybrush.threepwood@pirates.grog")
+        .provider_option("template", "newsletter")
+        .personalize(vec![
+            ("elaine.marley@triisland.gov", json!({"name": "Elaine"})),
+            ("lechuck@ghostship.grog", json!({"name": "LeChuck"})),
+        ]);
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("Action=SendBulkTemplatedEmail"))
+        .and(body_string_contains("Template=newsletter"))
+        .and(body_string_contains(
+            "Destinations.member.2.Destination.ToAddresses.member.1=lechuck%40ghostship.grog",
+        ))
+        .and(body_string_contains(
+            "Destinations.member.1.ReplacementTemplateData=%7B%22name%22%3A%22Elaine%22%7D",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<SendBulkTemplatedEmailResponse>
+                <SendBulkTemplatedEmailResult>
+                    <Status>
+                        <member><Status>Success</Status><MessageId>id-1</MessageId></member>
+                        <member><Status>Success</Status><MessageId>id-2</MessageId></member>
+                    </Status>
+                </SendBulkTemplatedEmailResult>
+                <ResponseMetadata><RequestId>requestId</RequestId></ResponseMetadata>
+            </SendBulkTemplatedEmailResponse>"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_personalized(&email).await.unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.message_id.as_str()).collect();
+    assert_eq!(ids, ["id-1", "id-2"]);
+}
+
+#[tokio::test]
+async fn deliver_personalized_reports_failed_destination() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    let email = Email::new()
+        .from("guybrush.threepwood@pirates.grog")
+        .provider_option("template", "newsletter")
+        .personalize(vec![("elaine.marley@triisland.gov", json!({}))]);
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<SendBulkTemplatedEmailResponse><SendBulkTemplatedEmailResult><Status>
+                <member><Status>MessageRejected</Status><Error>Address blacklisted</Error></member>
+            </Status></SendBulkTemplatedEmailResult></SendBulkTemplatedEmailResponse>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let err = mailer.deliver_personalized(&email).await.unwrap_err();
+    assert_eq!(err.provider_error_code(), Some("MessageRejected"));
+}
+
+#[tokio::test]
+async fn deliver_many_with_template_sends_bulk_templated_email() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    let templated = |i: usize| {
+        Email::new()
+            .from("guybrush.threepwood@pirates.grog")
+            .to(format!("pirate{}@triisland.gov", i).as_str())
+            .provider_option("template", "newsletter")
+            .provider_option("template_data", json!({"n": i}))
+    };
+    let mut emails: Vec<Email> = (0..52).map(templated).collect();
+    emails.insert(1, valid_email());
+
+    let statuses = "<member><Status>Success</Status><MessageId>bulk-id</MessageId></member>"
+        .repeat(50);
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("Action=SendBulkTemplatedEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            "<SendBulkTemplatedEmailResponse><SendBulkTemplatedEmailResult><Status>{}\
+             </Status></SendBulkTemplatedEmailResult></SendBulkTemplatedEmailResponse>",
+            statuses
+        )))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("Action=SendRawEmail"))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_many(&emails).await.unwrap();
+    assert_eq!(results.len(), 53);
+    assert_eq!(results[0].message_id, "bulk-id");
+    assert_eq!(results[1].message_id, "messageId");
+    assert_eq!(results[52].message_id, "bulk-id");
+
+    let requests = server.received_requests().await.unwrap();
+    let bulk: Vec<String> = requests
+        .iter()
+        .map(|r| String::from_utf8_lossy(&r.body).into_owned())
+        .filter(|body| body.contains("SendBulkTemplatedEmail"))
+        .collect();
+    assert!(bulk[0]
+        .contains("Destinations.member.1.ReplacementTemplateData=%7B%22n%22%3A0%7D"));
+    assert!(bulk[0].contains("Destinations.member.50."));
+    assert!(!bulk[0].contains("Destinations.member.51."));
+    assert!(bulk[1].contains("Destinations.member.2."));
+    assert!(!bulk[1].contains("Destinations.member.3."));
+}
+
+#[tokio::test]
+async fn deliver_many_does_not_share_template_data_between_recipients() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    let email = |to: &str, data| {
+        Email::new()
+            .from("guybrush.threepwood@pirates.grog")
+            .to(to)
+            .provider_option("template", "reset")
+            .provider_option("template_data", data)
+    };
+    let emails = vec![
+        email(
+            "elaine.marley@triisland.gov",
+            json!({"name": "Elaine", "token": "secret-1"}),
+        ),
+        email("lechuck@ghostship.grog", json!({"name": "LeChuck"})),
+    ];
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("Action=SendBulkTemplatedEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<SendBulkTemplatedEmailResponse><SendBulkTemplatedEmailResult><Status>
+                <member><Status>Success</Status><MessageId>id-1</MessageId></member>
+                <member><Status>Success</Status><MessageId>id-2</MessageId></member>
+            </Status></SendBulkTemplatedEmailResult></SendBulkTemplatedEmailResponse>"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.deliver_many(&emails).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&requests[0].body);
+    assert!(body.contains("DefaultTemplateData=%7B%7D"));
+    assert!(body.contains(
+        "Destinations.member.2.ReplacementTemplateData=%7B%22name%22%3A%22LeChuck%22%7D"
+    ));
+    assert_eq!(body.matches("secret-1").count(), 1);
+}
+
 // ============================================================================
 // Region Tests
 // ============================================================================
@@ -296,6 +555,128 @@ async fn uses_correct_region_endpoint() {
     assert_eq!(mailer_eu_west.provider_name(), "amazon_ses");
 }
 
+// ============================================================================
+// Health Check Tests
+// ============================================================================
+
+#[tokio::test]
+async fn health_check_gets_send_quota() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("Action=GetSendQuota"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<GetSendQuotaResponse><GetSendQuotaResult>
+                <Max24HourSend>200.0</Max24HourSend>
+            </GetSendQuotaResult></GetSendQuotaResponse>"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.health_check().await.unwrap();
+}
+
+#[tokio::test]
+async fn health_check_rejects_invalid_credentials() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "bad_access", "bad_secret")
+        .host(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(403).set_body_string(
+            r#"<ErrorResponse><Error>
+                <Code>InvalidClientTokenId</Code>
+                <Message>The security token included in the request is invalid.</Message>
+            </Error></ErrorResponse>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let err = mailer.health_check().await.unwrap_err();
+    assert_eq!(err.status_code(), Some(403));
+    assert_eq!(err.provider_error_code(), Some("InvalidClientTokenId"));
+}
+
+// ============================================================================
+// Message Status Tests
+// ============================================================================
+
+#[tokio::test]
+async fn message_status_reads_message_insights() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "test_access", "test_secret")
+        .host(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/v2/email/insights/msg-bounced/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "MessageId": "msg-bounced",
+            "Insights": [{
+                "Destination": "user@example.com",
+                "Events": [
+                    {"Timestamp": 1.0, "Type": "SEND"},
+                    {"Timestamp": 2.0, "Type": "BOUNCE", "Details": {"Bounce": {
+                        "BounceType": "PERMANENT",
+                        "BounceSubType": "General",
+                        "DiagnosticCode": "smtp; 550 5.7.1 Message rejected as spam"
+                    }}}
+                ]
+            }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v2/email/insights/msg-delivered/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "Insights": [{"Events": [
+                {"Type": "SEND"},
+                {"Type": "DELIVERY"},
+                {"Type": "OPEN"}
+            ]}]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v2/email/insights/missing/"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .insert_header("x-amzn-ErrorType", "NotFoundException:")
+                .set_body_json(json!({"message": "Message not found"})),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v2/email/insights/forbidden/"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-amzn-ErrorType", "AccessDeniedException:")
+                .set_body_json(json!({"message": "VDM is not enabled"})),
+        )
+        .mount(&server)
+        .await;
+
+    assert_eq!(
+        mailer.message_status("msg-bounced").await.unwrap(),
+        MessageStatus::Bounced(BounceClass::Blocked)
+    );
+    assert_eq!(
+        mailer.message_status("msg-delivered").await.unwrap(),
+        MessageStatus::Delivered
+    );
+    assert_eq!(
+        mailer.message_status("missing").await.unwrap(),
+        MessageStatus::Unknown
+    );
+    let err = mailer.message_status("forbidden").await.unwrap_err();
+    assert_eq!(err.status_code(), Some(403));
+    assert_eq!(err.provider_error_code(), Some("AccessDeniedException"));
+}
+
 // ============================================================================
 // Provider Name Test
 // ============================================================================
@@ -3,9 +3,9 @@
 //! Ported from Swoosh's brevo_test.exs
 
 use missive::providers::BrevoMailer;
-use missive::{Email, Mailer};
+use missive::{Email, MailError, Mailer};
 use serde_json::json;
-use wiremock::matchers::{body_json, header, method, path};
+use wiremock::matchers::{body_json, body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ============================================================================
@@ -427,6 +427,53 @@ async fn deliver_many_with_two_emails_returns_ok() {
     assert_eq!(results[1].message_id, "<53.22@relay.example.com>");
 }
 
+#[tokio::test]
+async fn deliver_personalized_sends_message_versions_with_params() {
+    let server = MockServer::start().await;
+    let mailer = BrevoMailer::new("test-api-key").base_url(server.uri());
+
+    let email = Email::new()
+        .from("tony.stark@example.com")
+        .subject("Hello, {{ name }}!")
+        .provider_option("template_id", 42)
+        .personalize(vec![
+            ("steve.rogers@example.com", json!({"name": "Steve"})),
+            ("natasha.romanova@example.com", json!({"name": "Natasha"})),
+        ]);
+
+    Mock::given(method("POST"))
+        .and(path("/smtp/email"))
+        .and(body_json(json!({
+            "sender": {"email": "tony.stark@example.com"},
+            "subject": "Hello, Steve!",
+            "templateId": 42,
+            "messageVersions": [
+                {
+                    "to": [{"email": "steve.rogers@example.com"}],
+                    "subject": "Hello, Steve!",
+                    "templateId": 42,
+                    "params": {"name": "Steve"}
+                },
+                {
+                    "to": [{"email": "natasha.romanova@example.com"}],
+                    "subject": "Hello, Natasha!",
+                    "templateId": 42,
+                    "params": {"name": "Natasha"}
+                }
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "messageIds": ["<1@relay.example.com>", "<2@relay.example.com>"]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_personalized(&email).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].message_id, "<2@relay.example.com>");
+}
+
 #[tokio::test]
 async fn deliver_many_with_400_response() {
     let server = MockServer::start().await;
@@ -450,6 +497,97 @@ async fn deliver_many_with_400_response() {
         .contains("missing_parameter"));
 }
 
+#[tokio::test]
+async fn deliver_many_splits_at_1000_message_versions() {
+    let server = MockServer::start().await;
+    let mailer = BrevoMailer::new("test-api-key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/smtp/email"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "messageIds": ["<1@relay.example.com>"]
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let emails = vec![valid_email(); 1001];
+    mailer.deliver_many(&emails).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let sizes: Vec<usize> = requests
+        .iter()
+        .map(|r| {
+            let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+            body["messageVersions"].as_array().unwrap().len()
+        })
+        .collect();
+    assert_eq!(sizes, vec![1000, 1]);
+}
+
+#[tokio::test]
+async fn deliver_many_with_different_senders_returns_error() {
+    let server = MockServer::start().await;
+    let mailer = BrevoMailer::new("test-api-key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .respond_with(success_response())
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let other = valid_email().from("pepper.potts@example.com");
+    let err = mailer
+        .deliver_many(&[valid_email(), other])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MailError::UnsupportedFeature(msg) if msg.contains("pepper.potts")));
+}
+
+#[tokio::test]
+async fn deliver_with_invalid_schedule_at_returns_error() {
+    let server = MockServer::start().await;
+    let mailer = BrevoMailer::new("test-api-key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .respond_with(success_response())
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let email = valid_email().provider_option("schedule_at", "tomorrow at noon");
+    let err = mailer.deliver(&email).await.unwrap_err();
+    assert!(matches!(
+        err,
+        MailError::InvalidOption {
+            option: "schedule_at",
+            ..
+        }
+    ));
+
+    let err = mailer.deliver_many(&[email]).await.unwrap_err();
+    assert_eq!(err.kind(), "validation");
+}
+
+#[tokio::test]
+async fn deliver_with_schedule_at_returns_ok() {
+    let server = MockServer::start().await;
+    let mailer = BrevoMailer::new("test-api-key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/smtp/email"))
+        .and(body_partial_json(json!({
+            "scheduledAt": "2030-01-01T09:30:00+02:00"
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let email = valid_email().provider_option("schedule_at", "2030-01-01T09:30:00+02:00");
+    mailer.deliver(&email).await.unwrap();
+}
+
 // ============================================================================
 // TEMPLATE Sender Tests
 // ============================================================================
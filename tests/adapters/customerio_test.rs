@@ -0,0 +1,180 @@
+//! Customer.io adapter tests.
+
+use missive::providers::CustomerIoMailer;
+use missive::{Attachment, Email, MailError, Mailer};
+use serde_json::json;
+use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn valid_email() -> Email {
+    Email::new()
+        .from(("T Stark", "tony.stark@example.com"))
+        .to("steve.rogers@example.com")
+        .subject("Hello, Avengers!")
+        .html_body("<h1>Hello</h1>")
+        .text_body("Hello")
+}
+
+fn success_response() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "delivery_id": "RAECAAFwnUSneIa0ZXkmq8EdkAM==",
+        "queued_at": 1500111111
+    }))
+}
+
+// ============================================================================
+// Basic Delivery Tests
+// ============================================================================
+
+#[tokio::test]
+async fn successful_delivery_returns_ok() {
+    let server = MockServer::start().await;
+    let mailer = CustomerIoMailer::new("app_key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/send/email"))
+        .and(header("Authorization", "Bearer app_key"))
+        .and(body_json(json!({
+            "identifiers": {"email": "steve.rogers@example.com"},
+            "to": "steve.rogers@example.com",
+            "from": "T Stark <tony.stark@example.com>",
+            "subject": "Hello, Avengers!",
+            "body": "<h1>Hello</h1>",
+            "body_plain": "Hello"
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = mailer.deliver(&valid_email()).await.unwrap();
+    assert_eq!(result.message_id, "RAECAAFwnUSneIa0ZXkmq8EdkAM==");
+    assert_eq!(result.provider_response.unwrap()["queued_at"], 1500111111);
+}
+
+#[tokio::test]
+async fn deliver_with_all_fields() {
+    let server = MockServer::start().await;
+    let mailer = CustomerIoMailer::new("app_key").base_url(server.uri());
+
+    let at = "2030-01-01T00:00:00Z".parse().unwrap();
+    let email = valid_email()
+        .to("thor@example.com")
+        .bcc("hulk@example.com")
+        .reply_to("office@example.com")
+        .header("X-Campaign", "launch")
+        .scheduled_at(at)
+        .provider_option("identifiers", json!({"id": "user-42"}))
+        .provider_option("tracked", false)
+        .provider_option("preheader", "Assemble")
+        .attachment(Attachment::from_bytes("notes.txt", b"hello".to_vec()));
+
+    Mock::given(method("POST"))
+        .and(path("/send/email"))
+        .and(body_partial_json(json!({
+            "identifiers": {"id": "user-42"},
+            "to": "steve.rogers@example.com, thor@example.com",
+            "bcc": "hulk@example.com",
+            "reply_to": "office@example.com",
+            "headers": {"X-Campaign": "launch"},
+            "send_at": 1893456000,
+            "tracked": false,
+            "preheader": "Assemble",
+            "attachments": {"notes.txt": "aGVsbG8="}
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(mailer.deliver(&email).await.is_ok());
+}
+
+// ============================================================================
+// Template Tests
+// ============================================================================
+
+#[tokio::test]
+async fn transactional_message_without_from() {
+    let server = MockServer::start().await;
+    let mailer = CustomerIoMailer::new("app_key").base_url(server.uri());
+
+    let email = Email::new()
+        .to("steve.rogers@example.com")
+        .provider_option("transactional_message_id", 44)
+        .provider_option("message_data", json!({"token": "abc123"}));
+
+    Mock::given(method("POST"))
+        .and(path("/send/email"))
+        .and(body_json(json!({
+            "transactional_message_id": 44,
+            "identifiers": {"email": "steve.rogers@example.com"},
+            "to": "steve.rogers@example.com",
+            "message_data": {"token": "abc123"}
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(mailer.deliver(&email).await.is_ok());
+}
+
+// ============================================================================
+// Error Tests
+// ============================================================================
+
+#[tokio::test]
+async fn error_response_message_is_extracted() {
+    let server = MockServer::start().await;
+    let mailer = CustomerIoMailer::new("app_key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/send/email"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "meta": {"error": "transactional message 44 not found"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = mailer.deliver(&valid_email()).await.unwrap_err();
+    match err {
+        MailError::ProviderError {
+            provider,
+            message,
+            status,
+            ..
+        } => {
+            assert_eq!(provider, "customerio");
+            assert_eq!(status, Some(400));
+            assert_eq!(message, "transactional message 44 not found");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn cc_is_rejected() {
+    let mailer = CustomerIoMailer::new("app_key");
+
+    let result = mailer.deliver(&valid_email().cc("hulk@example.com")).await;
+    assert!(matches!(result, Err(MailError::UnsupportedFeature(_))));
+}
+
+#[tokio::test]
+async fn deliver_without_from_returns_error() {
+    let mailer = CustomerIoMailer::new("app_key");
+
+    let email = Email::new()
+        .to("steve.rogers@example.com")
+        .subject("Hello!")
+        .text_body("Hi");
+
+    let result = mailer.deliver(&email).await;
+    assert!(result.unwrap_err().to_string().contains("from"));
+}
@@ -3,9 +3,9 @@
 //! Ported from Swoosh's mailgun_test.exs
 
 use missive::providers::MailgunMailer;
-use missive::{Email, Mailer};
+use missive::{BounceClass, Email, Mailer, MessageStatus};
 use serde_json::json;
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_string_contains, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ============================================================================
@@ -262,6 +262,119 @@ async fn deliver_with_custom_headers_returns_ok() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn deliver_with_scheduled_at_sets_deliverytime() {
+    let server = MockServer::start().await;
+    let mailer = MailgunMailer::new("fake-api-key", "avengers.com").base_url(server.uri());
+
+    let at = "2030-01-01T09:30:00Z".parse().unwrap();
+    let email = valid_email().scheduled_at(at);
+
+    Mock::given(method("POST"))
+        .and(path("/avengers.com/messages"))
+        .and(body_string_contains("name=\"o:deliverytime\""))
+        .and(body_string_contains("Tue, 1 Jan 2030 09:30:00 +0000"))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.deliver(&email).await.unwrap();
+}
+
+#[tokio::test]
+async fn deliver_with_return_path_sets_header() {
+    let server = MockServer::start().await;
+    let mailer = MailgunMailer::new("fake-api-key", "avengers.com").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/avengers.com/messages"))
+        .and(body_string_contains("name=\"h:Return-Path\""))
+        .and(body_string_contains("bounces@avengers.com"))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let email = valid_email().return_path("bounces@avengers.com");
+    mailer.deliver(&email).await.unwrap();
+}
+
+#[tokio::test]
+async fn deliver_personalized_uses_recipient_variables() {
+    let server = MockServer::start().await;
+    let mailer = MailgunMailer::new("fake-api-key", "avengers.com").base_url(server.uri());
+
+    let email = Email::new()
+        .from("tony.stark@example.com")
+        .subject("Hi {{ name }}")
+        .text_body("Team {{ team }}, plan {{ account.plan }}, {{ unknown }}")
+        .assign("team", "Avengers")
+        .personalize(vec![
+            ("steve.rogers@example.com", json!({"name": "Steve", "account": {"plan": "pro"}})),
+            ("natasha.romanova@example.com", json!({"name": "Natasha"})),
+        ]);
+
+    Mock::given(method("POST"))
+        .and(path("/avengers.com/messages"))
+        .and(body_string_contains("Hi %recipient.name%"))
+        .and(body_string_contains(
+            "Team %recipient.team%, plan %recipient.account_plan%, %recipient.unknown%",
+        ))
+        .and(body_string_contains(
+            "steve.rogers@example.com, natasha.romanova@example.com",
+        ))
+        .and(body_string_contains(
+            r#""steve.rogers@example.com":{"account_plan":"pro","name":"Steve","team":"Avengers","unknown":"{{ unknown }}"}"#,
+        ))
+        .and(body_string_contains(
+            r#""natasha.romanova@example.com":{"account_plan":"{{ account.plan }}","name":"Natasha""#,
+        ))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_personalized(&email).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[1].provider_response.as_ref().unwrap()["recipient"],
+        "natasha.romanova@example.com"
+    );
+}
+
+#[tokio::test]
+async fn deliver_with_mime_posts_raw_message() {
+    let server = MockServer::start().await;
+    let mailer = MailgunMailer::new("fake-api-key", "avengers.com")
+        .base_url(server.uri())
+        .mime(true);
+
+    let email = valid_email()
+        .bcc("nick.fury@example.com")
+        .header("X-Signed", "yes")
+        .provider_option("tags", vec!["mime"]);
+
+    Mock::given(method("POST"))
+        .and(path("/avengers.com/messages.mime"))
+        .and(body_string_contains("filename=\"message.mime\""))
+        .and(body_string_contains("MIME-Version: 1.0"))
+        .and(body_string_contains("X-Signed: yes"))
+        .and(body_string_contains("nick.fury@example.com"))
+        .and(body_string_contains("name=\"o:tag\""))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.deliver(&email).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&requests[0].body);
+    assert!(!body.contains("Bcc:"));
+    assert!(!body.contains("name=\"subject\""));
+}
+
 // ============================================================================
 // Error Response Tests
 // ============================================================================
@@ -341,6 +454,12 @@ async fn deliver_without_to_returns_error() {
 // EU Base URL Test
 // ============================================================================
 
+#[tokio::test]
+async fn eu_constructor_builds_mailer() {
+    let mailer = MailgunMailer::eu("fake-api-key", "avengers.eu");
+    assert_eq!(mailer.provider_name(), "mailgun");
+}
+
 #[tokio::test]
 async fn deliver_with_eu_base_url_returns_ok() {
     let server = MockServer::start().await;
@@ -367,3 +486,67 @@ fn provider_name_returns_mailgun() {
     let mailer = MailgunMailer::new("fake-api-key", "avengers.com");
     assert_eq!(mailer.provider_name(), "mailgun");
 }
+
+// ============================================================================
+// Message Status Tests
+// ============================================================================
+
+#[tokio::test]
+async fn message_status_reads_events() {
+    let server = MockServer::start().await;
+    let mailer = MailgunMailer::new("fake-api-key", "avengers.com").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/avengers.com/events"))
+        .and(query_param("message-id", "delivered@avengers.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {"event": "accepted", "timestamp": 1.0},
+                {"event": "failed", "severity": "temporary", "timestamp": 2.0},
+                {"event": "delivered", "timestamp": 3.0},
+                {"event": "opened", "timestamp": 4.0}
+            ],
+            "paging": {}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/avengers.com/events"))
+        .and(query_param("message-id", "failed@avengers.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {"event": "accepted"},
+                {
+                    "event": "failed",
+                    "severity": "permanent",
+                    "reason": "bounce",
+                    "delivery-status": {"code": 550, "enhanced-code": "5.1.1"}
+                }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/avengers.com/events"))
+        .and(query_param("message-id", "old@avengers.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"items": []})))
+        .mount(&server)
+        .await;
+
+    // Angle brackets from the send response are stripped
+    assert_eq!(
+        mailer
+            .message_status("<delivered@avengers.com>")
+            .await
+            .unwrap(),
+        MessageStatus::Delivered
+    );
+    assert_eq!(
+        mailer.message_status("failed@avengers.com").await.unwrap(),
+        MessageStatus::Bounced(BounceClass::HardBounce)
+    );
+    assert_eq!(
+        mailer.message_status("old@avengers.com").await.unwrap(),
+        MessageStatus::Unknown
+    );
+}
@@ -5,7 +5,7 @@
 use missive::providers::MailtrapMailer;
 use missive::{Email, Mailer};
 use serde_json::json;
-use wiremock::matchers::{body_json, header, method, path};
+use wiremock::matchers::{body_json, body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ============================================================================
@@ -354,6 +354,88 @@ async fn deliver_with_sandbox_config_returns_ok() {
     assert!(result.is_ok());
 }
 
+// ============================================================================
+// Batch Tests
+// ============================================================================
+
+#[tokio::test]
+async fn deliver_many_uses_batch_endpoint() {
+    let server = MockServer::start().await;
+    let mailer = MailtrapMailer::new("test-api-key")
+        .base_url(server.uri())
+        .bulk(true);
+
+    let emails = vec![
+        valid_email(),
+        valid_email().to("nobody@invalid").subject("Second"),
+    ];
+
+    Mock::given(method("POST"))
+        .and(path("/api/batch"))
+        .and(body_partial_json(json!({
+            "requests": [
+                {"subject": "Hello, Avengers!"},
+                {"subject": "Second"}
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "responses": [
+                {"success": true, "message_ids": ["id-1"]},
+                {"success": false, "errors": ["invalid recipient"]}
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_many(&emails).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].message_id, "id-1");
+    assert!(!results[0].is_failed());
+    assert!(results[1].is_failed());
+    assert_eq!(
+        results[1].provider_response.as_ref().unwrap()["errors"][0],
+        "invalid recipient"
+    );
+}
+
+#[tokio::test]
+async fn deliver_many_in_sandbox_uses_inbox_batch_endpoint() {
+    let server = MockServer::start().await;
+    let mailer = MailtrapMailer::new("test-api-key")
+        .base_url(server.uri())
+        .sandbox_inbox_id("11111");
+
+    Mock::given(method("POST"))
+        .and(path("/api/batch/11111"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "responses": [{"success": true, "message_ids": ["id-1"]}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_many(&[valid_email()]).await.unwrap();
+    assert_eq!(results[0].message_id, "id-1");
+}
+
+#[tokio::test]
+async fn deliver_many_with_error_response() {
+    let server = MockServer::start().await;
+    let mailer = MailtrapMailer::new("test-api-key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/api/batch"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({"errors": ["Unauthorized"]})))
+        .mount(&server)
+        .await;
+
+    let err = mailer.deliver_many(&[valid_email()]).await.unwrap_err();
+    assert!(err.to_string().contains("Unauthorized"));
+}
+
 // ============================================================================
 // Error Response Tests
 // ============================================================================
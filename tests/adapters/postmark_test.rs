@@ -3,7 +3,7 @@
 //! Ported from Swoosh's postmark_test.exs
 
 use missive::providers::PostmarkMailer;
-use missive::{Email, Mailer};
+use missive::{BounceClass, Email, Mailer, MessageStatus};
 use serde_json::json;
 use wiremock::matchers::{body_string_contains, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -235,6 +235,33 @@ async fn deliver_with_message_stream_returns_ok() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn deliver_uses_mailer_message_stream_unless_overridden() {
+    let server = MockServer::start().await;
+    let mailer = PostmarkMailer::new("jarvis")
+        .base_url(server.uri())
+        .message_stream("broadcast");
+
+    Mock::given(method("POST"))
+        .and(path("/email"))
+        .and(body_string_contains("\"MessageStream\":\"broadcast\""))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/email"))
+        .and(body_string_contains("\"MessageStream\":\"outbound\""))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.deliver(&valid_email()).await.unwrap();
+    let email = valid_email().provider_option("message_stream", "outbound");
+    mailer.deliver(&email).await.unwrap();
+}
+
 // ============================================================================
 // Error Response Tests
 // ============================================================================
@@ -616,7 +643,82 @@ async fn deliver_many_with_partial_failure_returns_ok() {
     let results = result.unwrap();
     assert_eq!(results.len(), 2);
     assert_eq!(results[0].message_id, "msg-id-1");
+    assert!(!results[0].is_failed());
     // The second result should still be returned (with empty message_id)
+    assert!(results[1].is_failed());
+    assert_eq!(results[1].message_id, "");
+    let response = results[1].provider_response.as_ref().unwrap();
+    assert_eq!(response["error_code"], 406);
+    assert_eq!(response["message"], "Inactive recipient");
+}
+
+// ============================================================================
+// Message Status Tests
+// ============================================================================
+
+#[tokio::test]
+async fn message_status_reads_latest_event() {
+    let server = MockServer::start().await;
+    let mailer = PostmarkMailer::new("jarvis").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/messages/outbound/msg-delivered/details"))
+        .and(header("X-Postmark-Server-Token", "jarvis"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "MessageID": "msg-delivered",
+            "Status": "Sent",
+            "MessageEvents": [
+                {"Recipient": "tony.stark@example.com", "Type": "Transient", "Details": {}},
+                {"Recipient": "tony.stark@example.com", "Type": "Delivered", "Details": {}},
+                {"Recipient": "tony.stark@example.com", "Type": "Opened", "Details": {}}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/messages/outbound/msg-bounced/details"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "Status": "Sent",
+            "MessageEvents": [{
+                "Type": "Bounced",
+                "Details": {"Summary": "smtp;550 5.1.1 The email account does not exist.", "BounceID": "42"}
+            }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/messages/outbound/msg-queued/details"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "Status": "Queued",
+            "MessageEvents": []
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/messages/outbound/missing/details"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "ErrorCode": 701,
+            "Message": "This message was not found."
+        })))
+        .mount(&server)
+        .await;
+
+    assert_eq!(
+        mailer.message_status("msg-delivered").await.unwrap(),
+        MessageStatus::Delivered
+    );
+    assert_eq!(
+        mailer.message_status("msg-bounced").await.unwrap(),
+        MessageStatus::Bounced(BounceClass::HardBounce)
+    );
+    assert_eq!(
+        mailer.message_status("msg-queued").await.unwrap(),
+        MessageStatus::Queued
+    );
+    assert_eq!(
+        mailer.message_status("missing").await.unwrap(),
+        MessageStatus::Unknown
+    );
 }
 
 // ============================================================================
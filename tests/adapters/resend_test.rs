@@ -2,8 +2,11 @@
 //!
 //! Ported from Swoosh's resend_test.exs
 
-use missive::providers::ResendMailer;
-use missive::{Attachment, Email, Mailer};
+use std::time::Duration;
+
+use missive::http::HttpConfig;
+use missive::providers::{ResendBroadcast, ResendContact, ResendMailer};
+use missive::{Attachment, Email, MailError, Mailer};
 use serde_json::json;
 use wiremock::matchers::{body_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -268,6 +271,8 @@ async fn deliver_with_400_response() {
     assert!(result.is_err());
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Missing required field"));
+    assert_eq!(err.provider_error_code(), Some("validation_error"));
+    assert!(!err.is_retryable());
 }
 
 #[tokio::test]
@@ -277,11 +282,15 @@ async fn deliver_with_429_response() {
 
     Mock::given(method("POST"))
         .and(path("/emails"))
-        .respond_with(ResponseTemplate::new(429).set_body_json(json!({
-            "statusCode": 429,
-            "message": "Too many requests",
-            "name": "rate_limit_exceeded"
-        })))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "30")
+                .set_body_json(json!({
+                    "statusCode": 429,
+                    "message": "Too many requests",
+                    "name": "rate_limit_exceeded"
+                })),
+        )
         .expect(1)
         .mount(&server)
         .await;
@@ -290,6 +299,10 @@ async fn deliver_with_429_response() {
     assert!(result.is_err());
     let err = result.unwrap_err();
     assert!(err.to_string().contains("Too many requests"));
+    assert_eq!(err.status_code(), Some(429));
+    assert_eq!(err.provider_error_code(), Some("rate_limit_exceeded"));
+    assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+    assert!(err.is_retryable());
 }
 
 #[tokio::test]
@@ -308,6 +321,24 @@ async fn deliver_with_500_response() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn deliver_times_out_with_http_config() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789")
+        .base_url(server.uri())
+        .http_config(&HttpConfig::new().timeout(Duration::from_millis(100)));
+
+    Mock::given(method("POST"))
+        .and(path("/emails"))
+        .respond_with(success_response().set_delay(Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let err = mailer.deliver(&valid_email()).await.unwrap_err();
+    assert!(matches!(err, MailError::HttpError(_)));
+    assert!(err.is_retryable());
+}
+
 // ============================================================================
 // Validation Tests
 // ============================================================================
@@ -342,6 +373,23 @@ async fn deliver_without_to_returns_error() {
     assert!(result.unwrap_err().to_string().contains("to"));
 }
 
+#[tokio::test]
+async fn deliver_with_header_injection_returns_error() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .respond_with(success_response())
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let email = valid_email().header("X-Note", "hi\r\nBcc: eve@example.com");
+
+    let result = mailer.deliver(&email).await;
+    assert!(matches!(result, Err(MailError::InvalidHeader(_))));
+}
+
 // ============================================================================
 // Batch Delivery Tests (validate_batch)
 // ============================================================================
@@ -556,6 +604,249 @@ async fn deliver_many_with_two_emails_returns_ok() {
     assert_eq!(results[1].message_id, "msg-id-2");
 }
 
+#[tokio::test]
+async fn deliver_many_with_attachment_returns_error() {
+    let mailer = ResendMailer::new("re_123456789");
+    let email = valid_email().attachment(Attachment::from_bytes("a.txt", b"a".to_vec()));
+
+    let err = mailer
+        .deliver_many(&[valid_email(), email])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MailError::UnsupportedFeature(msg) if msg.contains("(email 2)")));
+}
+
+#[tokio::test]
+async fn deliver_many_with_batch_fallback_sends_unsupported_individually() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789")
+        .base_url(server.uri())
+        .batch_fallback(true);
+
+    let scheduled = valid_email()
+        .to("natasha.romanova@example.com")
+        .provider_option("scheduled_at", "2024-01-01T00:00:00Z");
+    let batchable = Email::new()
+        .from("tony.stark@example.com")
+        .to("bruce.banner@example.com")
+        .subject("Hello Bruce!")
+        .text_body("Hi Bruce");
+    let with_attachment = valid_email().attachment(Attachment::from_bytes("a.txt", b"a".to_vec()));
+
+    Mock::given(method("POST"))
+        .and(path("/emails/batch"))
+        .and(body_json(json!([{
+            "from": "tony.stark@example.com",
+            "to": ["bruce.banner@example.com"],
+            "subject": "Hello Bruce!",
+            "text": "Hi Bruce"
+        }])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"id": "batch-id"}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/emails"))
+        .respond_with(success_response())
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let results = mailer
+        .deliver_many(&[scheduled, batchable, with_attachment])
+        .await
+        .unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.message_id.as_str()).collect();
+    assert_eq!(
+        ids,
+        [
+            "049b9217-30b5-4f61-a8e3-4d2d12f9f5a7",
+            "batch-id",
+            "049b9217-30b5-4f61-a8e3-4d2d12f9f5a7"
+        ]
+    );
+}
+
+// ============================================================================
+// Health Check Tests
+// ============================================================================
+
+#[tokio::test]
+async fn health_check_lists_domains() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/domains"))
+        .and(header("Authorization", "Bearer re_123456789"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.health_check().await.unwrap();
+}
+
+#[tokio::test]
+async fn health_check_accepts_sending_only_key() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/domains"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "name": "restricted_api_key",
+            "message": "This API key is restricted to only send emails"
+        })))
+        .mount(&server)
+        .await;
+
+    mailer.health_check().await.unwrap();
+}
+
+#[tokio::test]
+async fn health_check_rejects_invalid_key() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_bad").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/domains"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "name": "validation_error",
+            "message": "API key is invalid"
+        })))
+        .mount(&server)
+        .await;
+
+    let err = mailer.health_check().await.unwrap_err();
+    assert_eq!(err.status_code(), Some(400));
+    assert_eq!(err.provider_error_code(), Some("validation_error"));
+}
+
+// ============================================================================
+// Audience and Broadcast Tests
+// ============================================================================
+
+#[tokio::test]
+async fn create_audience_and_add_contact() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/audiences"))
+        .and(header("Authorization", "Bearer re_123456789"))
+        .and(body_json(json!({"name": "Newsletter"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "object": "audience",
+            "id": "aud_1",
+            "name": "Newsletter"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/audiences/aud_1/contacts"))
+        .and(body_json(json!({
+            "email": "jane@example.com",
+            "first_name": "Jane",
+            "unsubscribed": false
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "object": "contact",
+            "id": "con_1"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/audiences"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [{"id": "aud_1", "name": "Newsletter", "created_at": "2024-06-01 00:00:00"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let audience = mailer.create_audience("Newsletter").await.unwrap();
+    assert_eq!(audience.id, "aud_1");
+    let contact = ResendContact::new("jane@example.com").first_name("Jane");
+    assert_eq!(
+        mailer.add_contact(&audience.id, &contact).await.unwrap(),
+        "con_1"
+    );
+    assert_eq!(mailer.list_audiences().await.unwrap(), vec![audience]);
+}
+
+#[tokio::test]
+async fn create_and_send_broadcast() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/broadcasts"))
+        .and(body_json(json!({
+            "audience_id": "aud_1",
+            "from": "News <news@example.com>",
+            "subject": "June update",
+            "reply_to": ["support@example.com"],
+            "html": "<p>Hi {{{FIRST_NAME|there}}}</p>",
+            "name": "June"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": "bc_1"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/broadcasts/bc_1/send"))
+        .and(body_json(
+            json!({"scheduled_at": "2024-06-01T09:00:00+00:00"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "bc_1"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let broadcast = ResendBroadcast::new("aud_1", ("News", "news@example.com"), "June update")
+        .reply_to("support@example.com")
+        .html("<p>Hi {{{FIRST_NAME|there}}}</p>")
+        .name("June");
+    let id = mailer.create_broadcast(&broadcast).await.unwrap();
+    let at = chrono::DateTime::parse_from_rfc3339("2024-06-01T09:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    mailer.schedule_broadcast(&id, at).await.unwrap();
+}
+
+#[tokio::test]
+async fn create_broadcast_without_body_returns_error() {
+    let mailer = ResendMailer::new("re_123456789").base_url("http://127.0.0.1:9");
+    let broadcast = ResendBroadcast::new("aud_1", "news@example.com", "Empty");
+
+    let err = mailer.create_broadcast(&broadcast).await.unwrap_err();
+    assert!(matches!(err, MailError::MissingField("html")));
+}
+
+#[tokio::test]
+async fn send_broadcast_error_returns_provider_error() {
+    let server = MockServer::start().await;
+    let mailer = ResendMailer::new("re_123456789").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/broadcasts/bc_missing/send"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "name": "not_found",
+            "message": "Broadcast not found"
+        })))
+        .mount(&server)
+        .await;
+
+    let err = mailer.send_broadcast("bc_missing").await.unwrap_err();
+    assert_eq!(err.status_code(), Some(404));
+    assert_eq!(err.provider_error_code(), Some("not_found"));
+}
+
 // ============================================================================
 // Provider Name Test
 // ============================================================================
@@ -565,3 +856,19 @@ fn provider_name_returns_resend() {
     let mailer = ResendMailer::new("re_123456789");
     assert_eq!(mailer.provider_name(), "resend");
 }
+
+#[test]
+fn capabilities() {
+    let caps = ResendMailer::new("re_123456789").capabilities();
+    assert!(caps.supports_batch);
+    assert!(caps.supports_scheduling);
+    assert!(caps.supports_templates);
+    assert!(!caps.supports_attachments_in_batch);
+    assert_eq!(caps.max_recipients, Some(50));
+    assert_eq!(caps.max_message_size, Some(40 * 1024 * 1024));
+
+    let caps = ResendMailer::new("re_123456789")
+        .batch_fallback(true)
+        .capabilities();
+    assert!(caps.supports_attachments_in_batch);
+}
@@ -689,6 +689,251 @@ async fn deliver_with_custom_personalizations_returns_ok() {
     assert!(result.is_ok(), "Expected Ok, got: {:?}", result);
 }
 
+#[tokio::test]
+async fn deliver_personalized_with_template_sends_one_request() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.test-api-key").base_url(server.uri());
+
+    let email = Email::new()
+        .from("tony.stark@example.com")
+        .provider_option("template_id", "d-123")
+        .provider_option("dynamic_template_data", json!({"team": "Avengers"}))
+        .personalize(vec![
+            ("steve.rogers@example.com", json!({"name": "Steve"})),
+            ("natasha.romanova@example.com", json!({"name": "Natasha"})),
+        ]);
+
+    Mock::given(method("POST"))
+        .and(path("/mail/send"))
+        .and(body_json(json!({
+            "from": {"email": "tony.stark@example.com"},
+            "personalizations": [
+                {
+                    "to": [{"email": "steve.rogers@example.com"}],
+                    "dynamic_template_data": {"team": "Avengers", "name": "Steve"}
+                },
+                {
+                    "to": [{"email": "natasha.romanova@example.com"}],
+                    "dynamic_template_data": {"team": "Avengers", "name": "Natasha"}
+                }
+            ],
+            "subject": "",
+            "template_id": "d-123"
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_personalized(&email).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].message_id, "123-xyz");
+    assert_eq!(
+        results[1].provider_response.as_ref().unwrap()["recipient"],
+        "natasha.romanova@example.com"
+    );
+}
+
+#[tokio::test]
+async fn deliver_personalized_without_template_renders_locally() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.test-api-key").base_url(server.uri());
+
+    let email = Email::new()
+        .from("tony.stark@example.com")
+        .subject("Hello, {{ name }}!")
+        .text_body("Hello")
+        .personalize(vec![
+            ("steve.rogers@example.com", json!({"name": "Steve"})),
+            ("natasha.romanova@example.com", json!({"name": "Natasha"})),
+        ]);
+
+    for (to, subject) in [
+        ("steve.rogers@example.com", "Hello, Steve!"),
+        ("natasha.romanova@example.com", "Hello, Natasha!"),
+    ] {
+        Mock::given(method("POST"))
+            .and(path("/mail/send"))
+            .and(body_json(json!({
+                "from": {"email": "tony.stark@example.com"},
+                "personalizations": [{"to": [{"email": to}]}],
+                "content": [{"type": "text/plain", "value": "Hello"}],
+                "subject": subject
+            })))
+            .respond_with(success_response())
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let results = mailer.deliver_personalized(&email).await.unwrap();
+    assert_eq!(results.len(), 2);
+}
+
+#[tokio::test]
+async fn deliver_many_groups_shared_content_into_one_request() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.test-api-key").base_url(server.uri());
+
+    let steve = valid_email().provider_option("custom_args", json!({"user": "1"}));
+    let other = Email::new()
+        .from("tony.stark@example.com")
+        .to("bruce.banner@example.com")
+        .subject("Different")
+        .text_body("Hi");
+    let natasha = Email::new()
+        .from("tony.stark@example.com")
+        .to("natasha.romanova@example.com")
+        .subject("Hello, Avengers!")
+        .html_body("<h1>Hello</h1>")
+        .text_body("Hello");
+
+    Mock::given(method("POST"))
+        .and(path("/mail/send"))
+        .and(body_json(json!({
+            "from": {"email": "tony.stark@example.com"},
+            "personalizations": [
+                {"to": [{"email": "steve.rogers@example.com"}], "custom_args": {"user": "1"}},
+                {"to": [{"email": "natasha.romanova@example.com"}]}
+            ],
+            "subject": "Hello, Avengers!",
+            "content": [
+                {"type": "text/plain", "value": "Hello"},
+                {"type": "text/html", "value": "<h1>Hello</h1>"}
+            ]
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mail/send"))
+        .and(body_json(json!({
+            "from": {"email": "tony.stark@example.com"},
+            "personalizations": [{"to": [{"email": "bruce.banner@example.com"}]}],
+            "subject": "Different",
+            "content": [{"type": "text/plain", "value": "Hi"}]
+        })))
+        .respond_with(ResponseTemplate::new(202).insert_header("X-Message-Id", "other-id"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = mailer.deliver_many(&[steve, other, natasha]).await.unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.message_id.as_str()).collect();
+    assert_eq!(ids, ["123-xyz", "other-id", "123-xyz"]);
+}
+
+#[tokio::test]
+async fn deliver_many_chunks_at_1000_personalizations() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.test-api-key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/mail/send"))
+        .respond_with(success_response())
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let emails: Vec<Email> = (0..1001)
+        .map(|i| {
+            Email::new()
+                .from("tony.stark@example.com")
+                .to(format!("user{}@example.com", i).as_str())
+                .subject("Hello")
+                .text_body("Hello")
+        })
+        .collect();
+    let results = mailer.deliver_many(&emails).await.unwrap();
+    assert_eq!(results.len(), 1001);
+
+    let requests = server.received_requests().await.unwrap();
+    let sizes: Vec<usize> = requests
+        .iter()
+        .map(|r| {
+            let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+            body["personalizations"].as_array().unwrap().len()
+        })
+        .collect();
+    assert_eq!(sizes, [1000, 1]);
+}
+
+// ============================================================================
+// Health Check Tests
+// ============================================================================
+
+#[tokio::test]
+async fn health_check_requires_mail_send_scope() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.test-api-key").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/scopes"))
+        .and(header("Authorization", "Bearer SG.test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"scopes": ["mail.send"]})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.health_check().await.unwrap();
+
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.read-only").base_url(server.uri());
+    Mock::given(method("GET"))
+        .and(path("/scopes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"scopes": ["stats.read"]})))
+        .mount(&server)
+        .await;
+
+    let err = mailer.health_check().await.unwrap_err();
+    assert!(err.to_string().contains("mail.send"));
+}
+
+#[tokio::test]
+async fn on_behalf_of_sets_subuser_header() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.parent-key")
+        .base_url(server.uri())
+        .on_behalf_of("acme-eu");
+
+    Mock::given(method("GET"))
+        .and(path("/scopes"))
+        .and(header("on-behalf-of", "acme-eu"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"scopes": ["mail.send"]})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mail/send"))
+        .and(header("on-behalf-of", "acme-eu"))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    mailer.health_check().await.unwrap();
+    mailer.deliver(&valid_email()).await.unwrap();
+}
+
+#[tokio::test]
+async fn health_check_rejects_invalid_key() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.bad").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/scopes"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "errors": [{"message": "authorization required", "field": null}]
+        })))
+        .mount(&server)
+        .await;
+
+    let err = mailer.health_check().await.unwrap_err();
+    assert_eq!(err.status_code(), Some(401));
+    assert!(err.to_string().contains("authorization required"));
+}
+
 // ============================================================================
 // Provider Name Test
 // ============================================================================
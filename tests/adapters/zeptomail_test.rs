@@ -0,0 +1,217 @@
+//! ZeptoMail adapter tests.
+
+use missive::providers::ZeptoMailer;
+use missive::{Attachment, Email, MailError, Mailer};
+use serde_json::json;
+use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn valid_email() -> Email {
+    Email::new()
+        .from(("T Stark", "tony.stark@example.com"))
+        .to("steve.rogers@example.com")
+        .subject("Hello, Avengers!")
+        .html_body("<h1>Hello</h1>")
+        .text_body("Hello")
+}
+
+fn success_response() -> ResponseTemplate {
+    ResponseTemplate::new(201).set_body_json(json!({
+        "data": [{
+            "code": "EM_104",
+            "additional_info": [],
+            "message": "Email request received"
+        }],
+        "message": "OK",
+        "request_id": "2d6f.50b4d3c4b1c4.m1.f8a3a6e0",
+        "object": "email"
+    }))
+}
+
+// ============================================================================
+// Basic Delivery Tests
+// ============================================================================
+
+#[tokio::test]
+async fn successful_delivery_returns_ok() {
+    let server = MockServer::start().await;
+    let mailer = ZeptoMailer::new("wSsVR61").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/email"))
+        .and(header("Authorization", "Zoho-enczapikey wSsVR61"))
+        .and(body_json(json!({
+            "from": {"address": "tony.stark@example.com", "name": "T Stark"},
+            "to": [{"email_address": {"address": "steve.rogers@example.com"}}],
+            "subject": "Hello, Avengers!",
+            "htmlbody": "<h1>Hello</h1>",
+            "textbody": "Hello"
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = mailer.deliver(&valid_email()).await.unwrap();
+    assert_eq!(result.message_id, "2d6f.50b4d3c4b1c4.m1.f8a3a6e0");
+    assert_eq!(result.provider_response.unwrap()["code"], "EM_104");
+}
+
+#[tokio::test]
+async fn token_with_scheme_is_not_doubled() {
+    let server = MockServer::start().await;
+    let mailer = ZeptoMailer::new("Zoho-enczapikey wSsVR61").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(header("Authorization", "Zoho-enczapikey wSsVR61"))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(mailer.deliver(&valid_email()).await.is_ok());
+}
+
+#[tokio::test]
+async fn deliver_with_all_fields() {
+    let server = MockServer::start().await;
+    let mailer = ZeptoMailer::new("wSsVR61").base_url(server.uri());
+
+    let email = valid_email()
+        .cc(("Bruce Banner", "hulk@example.com"))
+        .bcc("thor@example.com")
+        .reply_to("office@example.com")
+        .return_path("bounces@bounce.example.com")
+        .header("X-Campaign", "launch")
+        .provider_option("track_opens", true)
+        .provider_option("client_reference", "order-1234")
+        .attachment(Attachment::from_bytes("notes.txt", b"hello".to_vec()))
+        .attachment(
+            Attachment::from_bytes("logo.png", vec![0x89, 0x50])
+                .content_type("image/png")
+                .content_id("logo")
+                .inline(),
+        );
+
+    Mock::given(method("POST"))
+        .and(path("/email"))
+        .and(body_partial_json(json!({
+            "cc": [{"email_address": {"address": "hulk@example.com", "name": "Bruce Banner"}}],
+            "bcc": [{"email_address": {"address": "thor@example.com"}}],
+            "reply_to": [{"address": "office@example.com"}],
+            "bounce_address": "bounces@bounce.example.com",
+            "mime_headers": {"X-Campaign": "launch"},
+            "track_opens": true,
+            "client_reference": "order-1234",
+            "attachments": [{
+                "name": "notes.txt",
+                "content": "aGVsbG8=",
+                "mime_type": "text/plain"
+            }],
+            "inline_images": [{
+                "name": "logo.png",
+                "content": "iVA=",
+                "mime_type": "image/png",
+                "cid": "logo"
+            }]
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(mailer.deliver(&email).await.is_ok());
+}
+
+// ============================================================================
+// Template Tests
+// ============================================================================
+
+#[tokio::test]
+async fn template_email_uses_template_endpoint() {
+    let server = MockServer::start().await;
+    let mailer = ZeptoMailer::new("wSsVR61").base_url(server.uri());
+
+    let email = Email::new()
+        .from("tony.stark@example.com")
+        .to("steve.rogers@example.com")
+        .provider_option("template_key", "2d6f.6b5b2ca0.k1.a7ee3f70")
+        .provider_option("merge_info", json!({"name": "Steve"}));
+
+    Mock::given(method("POST"))
+        .and(path("/email/template"))
+        .and(body_json(json!({
+            "from": {"address": "tony.stark@example.com"},
+            "to": [{"email_address": {"address": "steve.rogers@example.com"}}],
+            "template_key": "2d6f.6b5b2ca0.k1.a7ee3f70",
+            "merge_info": {"name": "Steve"}
+        })))
+        .respond_with(success_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(mailer.deliver(&email).await.is_ok());
+}
+
+// ============================================================================
+// Error Tests
+// ============================================================================
+
+#[tokio::test]
+async fn error_response_includes_code_and_details() {
+    let server = MockServer::start().await;
+    let mailer = ZeptoMailer::new("wSsVR61").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/email"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": {
+                "code": "TM_3201",
+                "details": [{
+                    "code": "GE_102",
+                    "message": "Mandatory Field 'subject' was set as Empty Value.",
+                    "target": "subject"
+                }],
+                "message": "Mandatory Field 'subject' was set as Empty Value.",
+                "request_id": "2d6f.50b4d3c4b1c4.m1.f8a3a6e0"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = mailer.deliver(&valid_email()).await.unwrap_err();
+    match err {
+        MailError::ProviderError {
+            provider,
+            message,
+            status,
+            code,
+            ..
+        } => {
+            assert_eq!(provider, "zeptomail");
+            assert_eq!(status, Some(400));
+            assert_eq!(code.as_deref(), Some("TM_3201"));
+            assert!(message.contains("subject: Mandatory Field"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn deliver_without_from_returns_error() {
+    let mailer = ZeptoMailer::new("wSsVR61");
+
+    let email = Email::new()
+        .to("steve.rogers@example.com")
+        .subject("Hello!")
+        .text_body("Hi");
+
+    let result = mailer.deliver(&email).await;
+    assert!(result.unwrap_err().to_string().contains("from"));
+}
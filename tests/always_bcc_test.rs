@@ -0,0 +1,31 @@
+//! Tests for the global archive BCC.
+//!
+//! Kept in its own binary: the installed config is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{deliver_with, Config, Email};
+
+#[tokio::test]
+async fn test_always_bcc_applies_to_deliveries() {
+    Config::builder()
+        .always_bcc("archive@example.com")
+        .always_bcc("legal@example.com")
+        .build()
+        .install();
+
+    let mailer = LocalMailer::new();
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .bcc("Legal@Example.com")
+        .subject("Receipt");
+    deliver_with(&email, &mailer).await.unwrap();
+    Config::uninstall();
+
+    let stored = mailer.last_email().unwrap().email;
+    let bcc: Vec<&str> = stored.bcc.iter().map(|a| a.email.as_str()).collect();
+    assert_eq!(bcc, vec!["Legal@Example.com", "archive@example.com"]);
+    assert_eq!(email.bcc.len(), 1);
+}
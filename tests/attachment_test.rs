@@ -185,3 +185,40 @@ fn email_can_have_inline_attachment() {
         Some("company-logo".to_string())
     );
 }
+
+// ============================================================================
+// ZIP Bundles
+// ============================================================================
+
+#[cfg(feature = "zip")]
+#[test]
+fn zip_bundles_files_into_one_attachment() {
+    let dir = std::env::temp_dir().join(format!("missive-zip-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let report = dir.join("report.csv");
+    std::fs::write(&report, "id,total\n1,100\n").unwrap();
+
+    let bundle = Attachment::zip(
+        "bundle.zip",
+        [
+            Attachment::from_path_lazy(&report).unwrap(),
+            Attachment::from_bytes("notes.txt", b"See attached".to_vec()),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(bundle.filename, "bundle.zip");
+    assert_eq!(bundle.content_type, "application/zip");
+    assert_eq!(bundle.disposition, AttachmentType::Attachment);
+    assert!(bundle.data.starts_with(b"PK\x03\x04"));
+    assert!(bundle.data.ends_with(&[0, 0]));
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn zip_with_limit_rejects_large_archives() {
+    let files = [Attachment::from_bytes("a.txt", vec![b'a'; 1024])];
+
+    assert!(Attachment::zip_with_limit("a.zip", files.clone(), 1024).is_ok());
+    assert!(Attachment::zip_with_limit("a.zip", files, 64).is_err());
+}
@@ -0,0 +1,65 @@
+//! Blocking API tests.
+//!
+//! Run with: cargo test --features blocking,local,resend --test blocking_test
+
+#![cfg(all(feature = "blocking", feature = "local", feature = "resend"))]
+
+use missive::providers::{LocalMailer, ResendMailer};
+use missive::{Email, MailError};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn email() -> Email {
+    Email::new()
+        .from("tools@example.com")
+        .to("ops@example.com")
+        .subject("Export finished")
+        .text_body("All good.")
+}
+
+#[test]
+fn deliver_with_local_mailer() {
+    let mailer = LocalMailer::new();
+
+    let result = missive::blocking::deliver_with(&email(), &mailer).unwrap();
+    assert!(!result.message_id.is_empty());
+    assert_eq!(mailer.email_count(), 1);
+}
+
+#[test]
+fn deliver_via_global_mailer() {
+    let mailer = LocalMailer::new();
+    missive::register("blocking", mailer.clone());
+
+    missive::blocking::deliver_via("blocking", &email()).unwrap();
+    assert_eq!(
+        mailer.last_email().unwrap().email.subject,
+        "Export finished"
+    );
+}
+
+#[test]
+fn deliver_with_http_provider() {
+    // The mock server runs on its own runtime; the test thread stays synchronous.
+    let server_runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = server_runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/emails"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "re_123"})))
+            .mount(&server)
+            .await;
+        server
+    });
+
+    let mailer = ResendMailer::new("re_key").base_url(server.uri());
+    let result = missive::blocking::deliver_with(&email(), &mailer).unwrap();
+    assert_eq!(result.message_id, "re_123");
+}
+
+#[tokio::test]
+async fn refuses_to_run_inside_a_runtime() {
+    let result = missive::blocking::deliver_with(&email(), &LocalMailer::new());
+    assert!(matches!(result, Err(MailError::Configuration(_))));
+}
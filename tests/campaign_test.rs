@@ -0,0 +1,118 @@
+//! Campaign orchestrator tests.
+//!
+//! Run with: cargo test --features local --test campaign_test
+
+#![cfg(feature = "local")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use missive::providers::{FailingMailer, LocalMailer};
+use missive::{Campaign, DeliveryResult, Email, MailError, Mailer};
+use serde_json::{json, Value};
+
+fn template() -> Email {
+    Email::new()
+        .from("news@example.com")
+        .subject("Hi {{ name }}")
+        .text_body("Hello {{ name }}")
+}
+
+fn recipients(n: usize) -> impl Iterator<Item = (String, Value)> {
+    (0..n).map(|i| {
+        (
+            format!("user{}@example.com", i),
+            json!({ "name": format!("User {}", i) }),
+        )
+    })
+}
+
+#[tokio::test]
+async fn test_sends_in_chunks_with_progress() {
+    let mailer = LocalMailer::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let campaign = Campaign::new(template())
+        .chunk_size(100)
+        .concurrency(2)
+        .on_progress(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+    let report = campaign.run(&mailer, recipients(250)).await;
+
+    assert_eq!(report.sent, 250);
+    assert!(report.is_complete());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(mailer.email_count(), 250);
+    assert!(mailer.sent_with_subject("Hi User 249"));
+}
+
+#[tokio::test]
+async fn test_failed_chunk_is_reported() {
+    let mailer = FailingMailer::local().fail_for("user15@example.com");
+    let campaign = Campaign::new(template()).chunk_size(10);
+
+    let report = campaign.run(&mailer, recipients(30)).await;
+
+    assert_eq!(report.sent, 20);
+    assert_eq!(report.failed.len(), 10);
+    assert_eq!(report.failed[0].recipient.email, "user10@example.com");
+    assert!(!report.is_complete());
+}
+
+/// Returns one result fewer than the chunk's recipients.
+struct ShortBatch;
+
+#[async_trait]
+impl Mailer for ShortBatch {
+    async fn deliver(&self, _email: &Email) -> Result<DeliveryResult, MailError> {
+        Ok(DeliveryResult::new("1"))
+    }
+
+    async fn deliver_personalized(&self, email: &Email) -> Result<Vec<DeliveryResult>, MailError> {
+        let count = email.personalizations.len() - 1;
+        Ok((0..count)
+            .map(|i| DeliveryResult::new(i.to_string()))
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn test_missing_batch_results_are_failures() {
+    let campaign = Campaign::new(template()).chunk_size(10);
+
+    let report = campaign.run(&ShortBatch, recipients(20)).await;
+
+    assert_eq!(report.sent, 18);
+    assert_eq!(report.failed.len(), 2);
+    assert_eq!(report.failed[0].error, "missing batch result");
+    assert!(!report.is_complete());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_pause_resume_and_cancel() {
+    let mailer = LocalMailer::new();
+    let campaign = Campaign::new(template()).chunk_size(10).rate_limit(10);
+    let control = campaign.control();
+    control.pause();
+
+    let driver = async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(mailer.email_count(), 0);
+        control.resume();
+        // 10 per second: the second chunk waits a second
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(mailer.email_count(), 20);
+        control.cancel();
+    };
+    let (report, ()) = tokio::join!(campaign.run(&mailer, recipients(100)), driver);
+
+    assert!(report.cancelled);
+    assert!(report.sent < 100);
+    assert_eq!(report.sent, mailer.email_count());
+    assert!(report.failed.is_empty());
+}
+
@@ -0,0 +1,135 @@
+//! Typed email catalog (`#[derive(EmailTemplate)]`).
+//!
+//! Run with: cargo test --features derive,local --test catalog_test
+
+#![cfg(all(feature = "derive", feature = "local"))]
+
+use missive::catalog::CatalogEmail;
+use missive::providers::LocalMailer;
+use missive::{EmailTemplate, MailError};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Team {
+    name: String,
+}
+
+#[derive(EmailTemplate)]
+#[email(template = "welcome", dir = "tests/templates")]
+struct WelcomeEmail {
+    name: String,
+    team: Team,
+}
+
+#[derive(EmailTemplate)]
+#[email(
+    template = "login_code",
+    dir = "tests/templates",
+    subject = "Sign-in code for {{ account }}"
+)]
+struct LoginCode<'a> {
+    code: u32,
+    account: &'a str,
+}
+
+fn welcome() -> WelcomeEmail {
+    WelcomeEmail {
+        name: "Alice".into(),
+        team: Team {
+            name: "Avengers".into(),
+        },
+    }
+}
+
+#[test]
+fn render_fills_placeholders() {
+    let email = welcome().render().unwrap();
+
+    assert_eq!(email.subject, "Welcome, Alice!");
+    assert_eq!(
+        email.html_body.as_deref(),
+        Some("<p>Hi Alice, thanks for joining Avengers.</p>\n")
+    );
+    assert_eq!(
+        email.text_body.as_deref(),
+        Some("Hi Alice, thanks for joining Avengers.\n")
+    );
+    assert_eq!(email.assigns["name"], "Alice");
+    assert_eq!(email.private["template"], "welcome");
+}
+
+#[test]
+fn subject_attribute_and_text_only_template() {
+    let email = LoginCode {
+        code: 123456,
+        account: "alice@example.com",
+    }
+    .render()
+    .unwrap();
+
+    assert_eq!(email.subject, "Sign-in code for alice@example.com");
+    assert_eq!(email.text_body.as_deref(), Some("Your code is 123456\n"));
+    assert!(email.html_body.is_none());
+    assert_eq!(LoginCode::TEMPLATE.name, "login_code");
+}
+
+#[tokio::test]
+async fn to_and_deliver_with() {
+    let mailer = LocalMailer::new();
+
+    welcome()
+        .to("alice@example.com")
+        .from("app@example.com")
+        .cc("team@example.com")
+        .deliver_with(&mailer)
+        .await
+        .unwrap();
+
+    let sent = mailer.emails();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].email.to[0].email, "alice@example.com");
+    assert_eq!(sent[0].email.cc[0].email, "team@example.com");
+    assert_eq!(sent[0].email.subject, "Welcome, Alice!");
+}
+
+#[tokio::test]
+async fn into_email_allows_changes() {
+    let email = welcome()
+        .to("alice@example.com")
+        .header("X-Campaign", "onboarding")
+        .into_email()
+        .unwrap();
+
+    assert_eq!(email.get_header("x-campaign"), Some("onboarding"));
+}
+
+#[test]
+fn with_layout_wraps_html_body() {
+    missive::templates::layout::register("catalog_base", "<body>{{ content }}</body>");
+
+    let email = welcome()
+        .to("alice@example.com")
+        .with_layout("catalog_base")
+        .into_email()
+        .unwrap();
+
+    assert_eq!(
+        email.html_body.as_deref(),
+        Some("<body><p>Hi Alice, thanks for joining Avengers.</p>\n</body>")
+    );
+    assert!(welcome()
+        .to("alice@example.com")
+        .with_layout("missing")
+        .into_email()
+        .is_err());
+}
+
+#[tokio::test]
+async fn deliver_reports_missing_sender() {
+    let result = welcome()
+        .to("alice@example.com")
+        .deliver_with(&LocalMailer::new())
+        .await;
+
+    assert!(matches!(result, Err(MailError::MissingField("from"))));
+}
@@ -0,0 +1,171 @@
+//! `missive` binary tests.
+//!
+//! Run with: cargo test --features cli --test cli_test
+
+#![cfg(feature = "cli")]
+
+use std::io::Read;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+
+fn missive(args: &[&str], env: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_missive"))
+        .args(args)
+        .env_clear()
+        .envs(env.iter().copied())
+        .output()
+        .unwrap()
+}
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("missive-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn send_prints_message_id() {
+    let html = temp_file("body.html", "<p>Hello</p>");
+    let attachment = temp_file("notes.txt", "notes");
+
+    let output = missive(
+        &[
+            "send",
+            "--to",
+            "user@example.com",
+            "--subject",
+            "Hi",
+            "--html",
+            html.to_str().unwrap(),
+            "--attach",
+            attachment.to_str().unwrap(),
+        ],
+        &[
+            ("EMAIL_PROVIDER", "logger"),
+            ("EMAIL_FROM", "app@example.com"),
+        ],
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Sent: "));
+}
+
+#[test]
+fn send_requires_a_body() {
+    let output = missive(
+        &["send", "--to", "user@example.com", "--subject", "Hi"],
+        &[("EMAIL_PROVIDER", "logger")],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--html"));
+}
+
+#[test]
+fn send_reports_delivery_errors() {
+    let text = temp_file("body.txt", "Hello");
+
+    let output = missive(
+        &[
+            "send",
+            "--to",
+            "user@example.com",
+            "--subject",
+            "Hi",
+            "--text",
+            text.to_str().unwrap(),
+        ],
+        &[("EMAIL_PROVIDER", "logger")],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("from"));
+}
+
+#[test]
+fn doctor_reports_configuration() {
+    let output = missive(&["doctor"], &[("EMAIL_PROVIDER", "logger")]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("provider     ok    logger"));
+    assert!(stdout.contains("from         warn"));
+    assert!(stdout.contains("dns          skip"));
+}
+
+#[test]
+fn doctor_sends_test_probe() {
+    let output = missive(
+        &["doctor", "--send-test"],
+        &[
+            ("EMAIL_PROVIDER", "logger"),
+            ("EMAIL_FROM", "app@example.com"),
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("self-test    ok    logger accepted probe"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn doctor_fails_without_provider() {
+    let output = missive(&["doctor"], &[("EMAIL_PROVIDER", "nonexistent")]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("provider     FAIL"));
+    assert!(stdout.contains("- error: Unknown EMAIL_PROVIDER: nonexistent"));
+}
+
+#[test]
+fn preview_serve_loads_json_emails() {
+    let emails = temp_file(
+        "emails.json",
+        r#"[{"from": {"email": "app@example.com"}, "to": [{"email": "user@example.com"}], "subject": "Loaded"}]"#,
+    );
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .to_string();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_missive"))
+        .args([
+            "preview",
+            "serve",
+            "--addr",
+            &addr,
+            emails.to_str().unwrap(),
+        ])
+        .env_clear()
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let url = format!("http://{}/json", addr);
+    let mut body = None;
+    for _ in 0..50 {
+        if let Ok(response) = ureq::get(&url).call() {
+            let mut text = String::new();
+            response.into_reader().read_to_string(&mut text).unwrap();
+            body = Some(text);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let body = body.expect("preview server did not start");
+    assert!(body.contains("\"subject\":\"Loaded\""));
+    assert!(body.contains("\"total\":1"));
+}
@@ -0,0 +1,29 @@
+//! Tests that installing a configuration keeps registered mailers.
+//!
+//! Kept in its own binary: it installs a process-wide `Config`.
+
+use missive::providers::LocalMailer;
+use missive::{Config, Email};
+
+/// `Config::install` and `Config::uninstall` drop env-built mailers only.
+#[tokio::test]
+async fn test_install_keeps_registered_mailers() {
+    let marketing = LocalMailer::new();
+    missive::register("marketing", marketing.clone());
+
+    Config::builder()
+        .set("EMAIL_PROVIDER", "local")
+        .build()
+        .install();
+    assert!(missive::named_mailer("marketing").is_some());
+
+    let email = Email::new()
+        .from("noreply@example.com")
+        .to("user@example.com")
+        .subject("Still registered");
+    missive::deliver_via("marketing", &email).await.unwrap();
+    assert_eq!(marketing.email_count(), 1);
+
+    Config::uninstall();
+    assert!(missive::named_mailer("marketing").is_some());
+}
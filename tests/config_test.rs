@@ -0,0 +1,40 @@
+//! Tests for config profiles and the global mailer.
+
+use missive::{deliver, local_storage, Config, Email, Storage};
+
+/// An installed profile configures the global mailer without env vars.
+#[tokio::test]
+async fn test_installed_profile_configures_global_mailer() {
+    let toml = r#"
+        [default]
+        from = "noreply@example.com"
+
+        [production]
+        provider = "logger"
+
+        [test]
+        provider = "local"
+        from_name = "Test App"
+        rate_limit = 100
+
+        [test.sandbox]
+        allowed_domains = ["example.com"]
+        redirect_to = "qa@example.com"
+    "#;
+
+    Config::from_toml_str(toml, "test").unwrap().install();
+    assert!(missive::is_configured());
+
+    let email = Email::new()
+        .to("customer@gmail.com")
+        .subject("Profile test");
+    deliver(&email).await.unwrap();
+
+    let stored = local_storage().unwrap().all().remove(0).email;
+    let from = stored.from.unwrap();
+    assert_eq!(from.email, "noreply@example.com");
+    assert_eq!(from.name.as_deref(), Some("Test App"));
+    assert_eq!(stored.to[0].email, "qa@example.com");
+
+    Config::uninstall();
+}
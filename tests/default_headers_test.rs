@@ -0,0 +1,45 @@
+//! Tests for the configured default reply-to and headers.
+//!
+//! Kept in its own binary: the installed config is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{deliver_with, Config, Email};
+
+#[tokio::test]
+async fn test_defaults_fill_in_missing_values() {
+    Config::builder()
+        .reply_to(("Support", "support@example.com"))
+        .header("X-Environment", "staging")
+        .header("X-Tags", "a,b")
+        .build()
+        .install();
+
+    let mailer = LocalMailer::new();
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .subject("Defaults");
+    deliver_with(&email, &mailer).await.unwrap();
+
+    let custom = email
+        .clone()
+        .reply_to("help@example.com")
+        .header("x-environment", "canary");
+    deliver_with(&custom, &mailer).await.unwrap();
+    Config::uninstall();
+
+    let emails = mailer.emails(); // newest first
+    let stored = &emails[1].email;
+    assert_eq!(stored.reply_to[0].email, "support@example.com");
+    assert_eq!(stored.reply_to[0].name.as_deref(), Some("Support"));
+    assert_eq!(stored.headers["X-Environment"], "staging");
+    assert_eq!(stored.headers["X-Tags"], "a,b");
+
+    let stored = &emails[0].email;
+    assert_eq!(stored.reply_to.len(), 1);
+    assert_eq!(stored.reply_to[0].email, "help@example.com");
+    assert_eq!(stored.headers["x-environment"], "canary");
+    assert!(!stored.headers.contains_key("X-Environment"));
+}
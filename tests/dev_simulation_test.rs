@@ -0,0 +1,64 @@
+//! Tests for simulated latency and failures in the development mailers.
+//!
+//! Run with: cargo test --features local --test dev_simulation_test
+//!
+//! Kept in its own binary: it changes process-wide environment variables.
+
+#![cfg(feature = "local")]
+
+use std::time::Duration;
+
+use missive::providers::{LocalMailer, LoggerMailer};
+use missive::{Email, MailError, Mailer};
+
+fn email() -> Email {
+    Email::new()
+        .from("sender@example.com")
+        .to("recipient@example.com")
+        .subject("Hi")
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dev_latency_and_fail_rate() {
+    let local = LocalMailer::new();
+    let logger = LoggerMailer::new();
+
+    std::env::set_var("MISSIVE_DEV_LATENCY_MS", "1500");
+    let start = tokio::time::Instant::now();
+    local.deliver(&email()).await.unwrap();
+    logger.deliver(&email()).await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(3000));
+    std::env::remove_var("MISSIVE_DEV_LATENCY_MS");
+
+    std::env::set_var("MISSIVE_DEV_FAIL_RATE", "1");
+    let err = local.deliver(&email()).await.unwrap_err();
+    assert!(err.is_retryable());
+    assert!(matches!(
+        err,
+        MailError::ProviderError {
+            provider: "local",
+            status: Some(503),
+            ..
+        }
+    ));
+    assert!(logger.deliver(&email()).await.is_err());
+    assert_eq!(local.email_count(), 1);
+
+    std::env::set_var("MISSIVE_DEV_FAIL_RATE", "0.5");
+    let mut failures = 0;
+    for _ in 0..200 {
+        if local.deliver(&email()).await.is_err() {
+            failures += 1;
+        }
+    }
+    assert!((50..150).contains(&failures), "{} failures", failures);
+
+    std::env::set_var("MISSIVE_DEV_FAIL_RATE", "20%");
+    let err = local.deliver(&email()).await.unwrap_err();
+    assert!(matches!(err, MailError::Configuration(_)));
+    std::env::remove_var("MISSIVE_DEV_FAIL_RATE");
+
+    local.clear();
+    local.deliver(&email()).await.unwrap();
+    assert_eq!(local.email_count(), 1);
+}
@@ -341,3 +341,46 @@ fn address_formatted_rfc5322_escapes_special_chars() {
         "\"Stark, Tony\" <tony.stark@example.com>"
     );
 }
+
+// ============================================================================
+// Serialization Tests
+// ============================================================================
+
+#[test]
+fn email_round_trips_through_json() {
+    let email = Email::new()
+        .from(("Tony Stark", "tony.stark@example.com"))
+        .to("steve.rogers@example.com")
+        .bcc("natasha.romanoff@example.com")
+        .subject("Hello, Avengers!")
+        .html_body("<h1>Hello</h1>")
+        .header("X-Mission", "assemble")
+        .attachment(missive::Attachment::from_bytes("plan.txt", b"assemble".to_vec()).inline())
+        .provider_option("tags", json!(["team"]))
+        .put_private("job_id", 42);
+
+    let value = serde_json::to_value(&email).unwrap();
+    assert_eq!(value["attachments"][0]["data"], "YXNzZW1ibGU=");
+    assert_eq!(
+        value["from"],
+        json!({"name": "Tony Stark", "email": "tony.stark@example.com"})
+    );
+
+    let decoded: Email = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), value);
+    assert_eq!(decoded.attachments[0].data, b"assemble");
+}
+
+#[test]
+fn email_deserializes_minimal_json() {
+    let email: Email = serde_json::from_value(json!({
+        "to": [{"email": "steve.rogers@example.com"}],
+        "attachments": [{"filename": "a.bin", "content_type": "application/octet-stream", "data": [1, 2, 3]}]
+    }))
+    .unwrap();
+
+    assert!(email.from.is_none());
+    assert_eq!(email.to[0].name, None);
+    assert!(email.subject.is_empty());
+    assert_eq!(email.attachments[0].data, [1, 2, 3]);
+}
@@ -0,0 +1,47 @@
+//! Tests for the allowed from-domains on every delivery.
+//!
+//! Kept in its own binary: the installed config is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{deliver_with, Config, Email, FromDomainPolicy, MailError};
+
+#[tokio::test]
+async fn test_allowed_from_domains() {
+    let mailer = LocalMailer::new();
+    let email = Email::new()
+        .from(("Acme Billing", "billing@acme.test"))
+        .to("user@example.com")
+        .subject("Invoice")
+        .text_body("Hi");
+
+    Config::builder()
+        .from("noreply@example.com")
+        .allowed_from_domain("example.com")
+        .allowed_from_domain("mail.example.com")
+        .build()
+        .install();
+    let allowed = deliver_with(&email.clone().from("news@mail.example.com"), &mailer).await;
+    let rejected = deliver_with(&email, &mailer).await;
+
+    Config::builder()
+        .from("noreply@example.com")
+        .allowed_from_domain("example.com")
+        .from_domain_policy(FromDomainPolicy::Rewrite)
+        .build()
+        .install();
+    let rewritten = deliver_with(&email, &mailer).await;
+    Config::uninstall();
+
+    allowed.unwrap();
+    assert!(matches!(rejected, Err(MailError::Blocked(_))));
+    rewritten.unwrap();
+
+    assert_eq!(mailer.email_count(), 2);
+    let sent = mailer.emails().remove(0).email;
+    let from = sent.from.unwrap();
+    assert_eq!(from.email, "noreply@example.com");
+    assert_eq!(from.name.as_deref(), Some("Acme Billing"));
+    assert_eq!(sent.reply_to[0].email, "billing@acme.test");
+}
@@ -0,0 +1,176 @@
+//! Send quota and statistics tests.
+//!
+//! Run with: cargo test --features insights,amazon_ses,sendgrid --test insights_test
+
+#![cfg(all(feature = "insights", feature = "amazon_ses", feature = "sendgrid"))]
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use missive::insights::{Insights, SendQuota, SendStatistics};
+use missive::providers::{AmazonSesMailer, SendGridMailer};
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn ses_send_quota() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "key", "secret").host(server.uri());
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("Action=GetSendQuota"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<GetSendQuotaResponse><GetSendQuotaResult>
+                <SentLast24Hours>127.0</SentLast24Hours>
+                <Max24HourSend>200.0</Max24HourSend>
+                <MaxSendRate>1.0</MaxSendRate>
+            </GetSendQuotaResult></GetSendQuotaResponse>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let quota = mailer.send_quota().await.unwrap();
+    assert_eq!(
+        quota,
+        SendQuota {
+            limit: Some(200),
+            sent: 127,
+            max_send_rate: Some(1.0),
+            resets_at: None,
+        }
+    );
+    assert_eq!(quota.remaining(), Some(73));
+}
+
+#[tokio::test]
+async fn ses_send_statistics_sorted_and_filtered() {
+    let server = MockServer::start().await;
+    let mailer = AmazonSesMailer::new("us-east-1", "key", "secret").host(server.uri());
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("Action=GetSendStatistics"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<GetSendStatisticsResponse><GetSendStatisticsResult><SendDataPoints>
+                <member>
+                    <DeliveryAttempts>8</DeliveryAttempts>
+                    <Timestamp>2024-05-02T10:15:00Z</Timestamp>
+                    <Rejects>1</Rejects>
+                    <Bounces>2</Bounces>
+                    <Complaints>0</Complaints>
+                </member>
+                <member>
+                    <DeliveryAttempts>5</DeliveryAttempts>
+                    <Timestamp>2024-05-01T09:00:00Z</Timestamp>
+                    <Rejects>0</Rejects>
+                    <Bounces>0</Bounces>
+                    <Complaints>1</Complaints>
+                </member>
+            </SendDataPoints></GetSendStatisticsResult></GetSendStatisticsResponse>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let all = mailer.get_send_statistics().await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(
+        all[0].start,
+        Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap()
+    );
+
+    let since = Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap();
+    let recent = mailer.send_statistics(since).await.unwrap();
+    assert_eq!(
+        recent,
+        [SendStatistics {
+            start: Utc.with_ymd_and_hms(2024, 5, 2, 10, 15, 0).unwrap(),
+            attempts: 8,
+            delivered: None,
+            bounces: 2,
+            complaints: 0,
+            rejects: 1,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn sendgrid_send_quota() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.key").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/user/credits"))
+        .and(header("Authorization", "Bearer SG.key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "remain": 60,
+            "total": 100,
+            "overage": 0,
+            "used": 40,
+            "last_reset": "2024-05-01",
+            "next_reset": "2024-06-01",
+            "reset_frequency": "monthly"
+        })))
+        .mount(&server)
+        .await;
+
+    let quota = mailer.send_quota().await.unwrap();
+    assert_eq!(quota.remaining(), Some(60));
+    assert_eq!(
+        quota.resets_at,
+        Some(Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap())
+    );
+}
+
+#[tokio::test]
+async fn sendgrid_send_statistics() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.key").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/stats"))
+        .and(query_param("start_date", "2024-05-01"))
+        .and(query_param("aggregated_by", "day"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "date": "2024-05-01",
+                "stats": [{"metrics": {
+                    "requests": 10, "delivered": 8, "bounces": 1,
+                    "spam_reports": 1, "blocks": 0, "opens": 4
+                }}]
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let stats = mailer
+        .get_send_statistics(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        stats,
+        [SendStatistics {
+            start: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            attempts: 10,
+            delivered: Some(8),
+            bounces: 1,
+            complaints: 1,
+            rejects: 0,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn sendgrid_errors_carry_status() {
+    let server = MockServer::start().await;
+    let mailer = SendGridMailer::new("SG.bad").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/user/credits"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "errors": [{"message": "access forbidden"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let err = mailer.send_quota().await.unwrap_err();
+    assert_eq!(err.status_code(), Some(403));
+    assert!(err.to_string().contains("access forbidden"));
+}
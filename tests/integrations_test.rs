@@ -0,0 +1,103 @@
+//! Web framework mailer integration tests.
+//!
+//! Run with: cargo test --features axum,actix,local --test integrations_test
+
+#![cfg(all(feature = "axum", feature = "actix", feature = "local"))]
+
+use missive::providers::LocalMailer;
+use missive::Email;
+
+fn welcome() -> Email {
+    Email::new()
+        .from("app@example.com")
+        .to("user@example.com")
+        .subject("Welcome")
+        .text_body("Hi")
+}
+
+mod axum_tests {
+    use super::*;
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use missive::integrations::axum::MailerExtension;
+    use tower::ServiceExt;
+
+    async fn signup(mailer: MailerExtension) -> StatusCode {
+        match mailer.deliver(&welcome()).await {
+            Ok(_) => StatusCode::OK,
+            Err(_) => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    fn request() -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/signup")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn handler_uses_layer_mailer() {
+        let mailer = LocalMailer::new();
+        let app = Router::new()
+            .route("/signup", post(signup))
+            .layer(MailerExtension::new(mailer.clone()));
+
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(mailer.email_count(), 1);
+        assert_eq!(mailer.last_email().unwrap().email.subject, "Welcome");
+    }
+
+    #[tokio::test]
+    async fn missing_layer_is_a_server_error() {
+        let app = Router::new().route("/signup", post(signup));
+
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
+
+mod actix_tests {
+    use super::*;
+
+    use actix_web::http::StatusCode;
+    use actix_web::{test, web, App, HttpResponse};
+    use missive::integrations::actix::MailerData;
+
+    async fn signup(mailer: MailerData) -> HttpResponse {
+        match mailer.deliver(&welcome()).await {
+            Ok(_) => HttpResponse::Ok().finish(),
+            Err(_) => HttpResponse::BadGateway().finish(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn handler_uses_app_data_mailer() {
+        let mailer = LocalMailer::new();
+        let app = test::init_service(
+            App::new()
+                .app_data(MailerData::new(mailer.clone()))
+                .route("/signup", web::post().to(signup)),
+        )
+        .await;
+
+        let request = test::TestRequest::post().uri("/signup").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(mailer.email_count(), 1);
+    }
+
+    #[actix_web::test]
+    async fn missing_app_data_is_a_server_error() {
+        let app = test::init_service(App::new().route("/signup", web::post().to(signup))).await;
+
+        let request = test::TestRequest::post().uri("/signup").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,73 @@
+//! Background job integration tests.
+//!
+//! Run with: cargo test --features local,apalis,fang --test jobs_test
+
+#![cfg(feature = "local")]
+
+use missive::jobs::SendEmailJob;
+use missive::providers::{FailingMailer, LocalMailer};
+use missive::Email;
+
+fn email(to: &str) -> Email {
+    Email::new()
+        .from("sender@example.com")
+        .to(to)
+        .subject("Queued")
+        .text_body("Hello")
+}
+
+#[tokio::test]
+async fn job_round_trips_and_delivers() {
+    let mailer = LocalMailer::new();
+    missive::register("jobs", mailer.clone());
+
+    let payload =
+        serde_json::to_string(&SendEmailJob::new(email("user@example.com")).via("jobs")).unwrap();
+    let job: SendEmailJob = serde_json::from_str(&payload).unwrap();
+    assert_eq!(job.mailer.as_deref(), Some("jobs"));
+
+    job.run().await.unwrap();
+    assert_eq!(
+        mailer.last_email().unwrap().email.to[0].email,
+        "user@example.com"
+    );
+}
+
+#[cfg(feature = "apalis")]
+#[tokio::test]
+async fn apalis_aborts_permanent_failures() {
+    use apalis_core::error::Error;
+    use missive::MailError;
+
+    missive::register(
+        "jobs-retryable",
+        FailingMailer::local().fail_for("user@example.com"),
+    );
+    missive::register(
+        "jobs-permanent",
+        FailingMailer::local()
+            .fail_for("user@example.com")
+            .error(|_| MailError::InvalidAddress("user@example.com".into())),
+    );
+
+    let job = SendEmailJob::new(email("user@example.com"));
+    let retryable = missive::jobs::apalis::send_email(job.clone().via("jobs-retryable")).await;
+    assert!(matches!(retryable, Err(Error::Failed(_))));
+
+    let permanent = missive::jobs::apalis::send_email(job.via("jobs-permanent")).await;
+    assert!(matches!(permanent, Err(Error::Abort(_))));
+}
+
+#[cfg(feature = "fang")]
+#[test]
+fn fang_task_is_tagged() {
+    use fang::AsyncRunnable;
+
+    let task: Box<dyn AsyncRunnable> = Box::new(SendEmailJob::new(email("user@example.com")));
+    let value = serde_json::to_value(&task).unwrap();
+    assert_eq!(value["type"], "missive::SendEmailJob");
+    assert_eq!(task.task_type(), missive::jobs::fang::TASK_TYPE);
+
+    let decoded: Box<dyn AsyncRunnable> = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded.task_type(), "missive_email");
+}
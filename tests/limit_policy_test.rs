@@ -0,0 +1,36 @@
+//! Tests for subject, header and recipient limits on every delivery.
+//!
+//! Kept in its own binary: the installed config is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{deliver_with, Config, Email, LimitPolicy, MailError};
+
+#[tokio::test]
+async fn test_limit_policy() {
+    let mailer = LocalMailer::new();
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .subject("x".repeat(1200))
+        .text_body("Hi");
+
+    // Rejected by default, before anything is sent
+    let err = deliver_with(&email, &mailer).await.unwrap_err();
+    assert!(matches!(
+        err,
+        MailError::LimitExceeded { ref field, limit: 998, actual: 1200 } if field == "subject"
+    ));
+    assert_eq!(mailer.email_count(), 0);
+
+    Config::builder()
+        .limit_policy(LimitPolicy::Truncate)
+        .build()
+        .install();
+    let result = deliver_with(&email, &mailer).await;
+    Config::uninstall();
+
+    result.unwrap();
+    assert_eq!(mailer.emails()[0].email.subject.len(), 998);
+}
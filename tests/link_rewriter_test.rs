@@ -0,0 +1,36 @@
+//! Tests for the global link rewriter.
+//!
+//! Kept in its own binary: the rewriter is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{clear_link_rewriter, deliver_with, set_link_rewriter, Email};
+
+#[tokio::test]
+async fn test_link_rewriter_applies_to_deliveries() {
+    set_link_rewriter(|url: &str, _email: &Email| {
+        url.starts_with("https://example.com")
+            .then(|| format!("{}?utm_source=email", url))
+    });
+
+    let mailer = LocalMailer::new();
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .subject("Links")
+        .html_body(r#"<a href="https://example.com/pricing">Pricing</a> <a href="mailto:help@example.com">Help</a>"#);
+
+    deliver_with(&email, &mailer).await.unwrap();
+    clear_link_rewriter();
+    deliver_with(&email, &mailer).await.unwrap();
+
+    let emails = mailer.emails(); // newest first
+    assert_eq!(
+        emails[1].email.html_body.as_deref(),
+        Some(
+            r#"<a href="https://example.com/pricing?utm_source=email">Pricing</a> <a href="mailto:help@example.com">Help</a>"#
+        )
+    );
+    assert_eq!(emails[0].email.html_body, email.html_body);
+}
@@ -0,0 +1,52 @@
+//! Tests for the named mailer registry.
+
+use missive::providers::LocalMailer;
+use missive::{deliver_many_via, deliver_via, Email, MailError};
+
+fn email(to: &str) -> Email {
+    Email::new()
+        .from("sender@example.com")
+        .to(to)
+        .subject("Named mailer")
+}
+
+#[tokio::test]
+async fn test_deliver_via_registered_mailer() {
+    let transactional = LocalMailer::new();
+    let marketing = LocalMailer::new();
+    missive::register("transactional", transactional.clone());
+    missive::register("marketing", marketing.clone());
+
+    deliver_via("transactional", &email("receipt@example.com"))
+        .await
+        .unwrap();
+    deliver_many_via(
+        "marketing",
+        &[email("a@example.com"), email("b@example.com")],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(transactional.email_count(), 1);
+    assert_eq!(marketing.email_count(), 2);
+    assert!(missive::named_mailer("transactional").is_some());
+}
+
+#[tokio::test]
+async fn test_unknown_mailer_is_an_error() {
+    let result = deliver_via("does-not-exist", &email("user@example.com")).await;
+
+    match result {
+        Err(MailError::Configuration(msg)) => {
+            assert!(msg.contains("EMAIL_PROVIDER__DOES_NOT_EXIST"));
+        }
+        other => panic!("expected configuration error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unregister() {
+    missive::register("temporary", LocalMailer::new());
+    assert!(missive::unregister("temporary").is_some());
+    assert!(missive::named_mailer("temporary").is_none());
+}
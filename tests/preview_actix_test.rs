@@ -0,0 +1,110 @@
+//! Actix preview integration tests.
+//!
+//! Mirrors the Axum tests in `preview_test.rs` to keep both adapters at parity.
+//!
+//! Run with: cargo test --features preview-actix --test preview_actix_test
+
+#![cfg(feature = "preview-actix")]
+
+use std::sync::Arc;
+
+use actix_web::{test, web, App};
+use missive::preview::{actix_configure, ActixAppState, PreviewConfig};
+use missive::{Attachment, Email, MemoryStorage, Storage};
+
+fn create_test_storage() -> Arc<MemoryStorage> {
+    let storage = MemoryStorage::shared();
+    storage.push(
+        Email::new()
+            .subject("Hello")
+            .from(("Admin", "admin@example.com"))
+            .to("user@example.com")
+            .html_body("<p>Hello</p>")
+            .attachment(Attachment::from_bytes("file.txt", b"data".to_vec())),
+    );
+    storage
+}
+
+macro_rules! app {
+    ($state:expr) => {
+        test::init_service(
+            App::new().service(
+                web::scope("/mailbox").configure(|cfg| actix_configure(cfg, $state.clone())),
+            ),
+        )
+        .await
+    };
+}
+
+#[actix_web::test]
+async fn test_index_nonce_override() {
+    let config = PreviewConfig {
+        script_nonce: Some("config-nonce".into()),
+        style_nonce: None,
+    };
+    let state = ActixAppState::with_config(create_test_storage(), config);
+    let app = app!(state);
+
+    let req = test::TestRequest::get()
+        .uri("/mailbox/?script_nonce=query-nonce")
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    let html = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(html.contains("nonce=\"query-nonce\""));
+    assert!(!html.contains("config-nonce"));
+}
+
+#[actix_web::test]
+async fn test_json_and_endpoints() {
+    let storage = create_test_storage();
+    let id = storage.all()[0].id.clone();
+    let state = ActixAppState::new(Arc::clone(&storage));
+    let app = app!(state);
+
+    let req = test::TestRequest::get().uri("/mailbox/json").to_request();
+    let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(json["data"][0]["subject"], "Hello");
+    assert_eq!(json["data"][0]["attachments"][0]["filename"], "file.txt");
+
+    for (path, content_type) in [
+        (format!("/mailbox/{}", id), "application/json"),
+        (format!("/mailbox/{}/html", id), "text/html"),
+        (format!("/mailbox/{}/source", id), "text/plain"),
+        (format!("/mailbox/{}/compat", id), "application/json"),
+        (format!("/mailbox/{}/attachments/0", id), "text/plain"),
+    ] {
+        let req = test::TestRequest::get().uri(&path).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success(), "{}", path);
+        let header = resp
+            .headers()
+            .get("content-type")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(header.starts_with(content_type), "{}: {}", path, header);
+    }
+
+    let req = test::TestRequest::get()
+        .uri("/mailbox/00000000-0000-0000-0000-000000000000")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/mailbox/{}/read", id))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+    assert!(storage.get(&id).unwrap().read);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/mailbox/{}", id))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+    assert!(storage.get(&id).is_none());
+
+    storage.push(Email::new().subject("Another"));
+    let req = test::TestRequest::post().uri("/mailbox/clear").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+    assert_eq!(storage.count(), 0);
+}
@@ -0,0 +1,65 @@
+//! Standalone preview server forwarding tests.
+//!
+//! Kept in its own binary: it registers the process-wide forwarding mailer.
+//!
+//! Run with: cargo test --features preview --test preview_forward_test
+
+#![cfg(feature = "preview")]
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use missive::preview::{PreviewServer, DEFAULT_FORWARD_MAILER};
+use missive::{DeliveryResult, Email, MailError, Mailer, MemoryStorage, Storage};
+
+/// Panics after a delay, like a wedged provider client.
+struct Panics;
+
+#[async_trait]
+impl Mailer for Panics {
+    async fn deliver(&self, _email: &Email) -> Result<DeliveryResult, MailError> {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        panic!("provider client crashed");
+    }
+}
+
+fn get_test_addr() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    format!("127.0.0.1:{}", port)
+}
+
+#[test]
+fn test_forward_runs_in_background_and_reports_panics() {
+    let storage = MemoryStorage::shared();
+    let id = storage.push(
+        Email::new()
+            .subject("Test Email")
+            .from("sender@example.com")
+            .to("recipient@example.com"),
+    );
+    missive::register(DEFAULT_FORWARD_MAILER, Panics);
+    let addr = get_test_addr();
+    PreviewServer::new(&addr, storage).unwrap().spawn();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let url = format!("http://{}/{}/forward?to=me%40example.com", addr, id);
+    let forwarding = std::thread::spawn(move || match ureq::post(&url).call() {
+        Ok(response) => (response.status(), response.into_string().unwrap()),
+        Err(ureq::Error::Status(status, response)) => (status, response.into_string().unwrap()),
+        Err(e) => panic!("Request failed: {}", e),
+    });
+    std::thread::sleep(Duration::from_millis(100));
+
+    // The UI keeps answering while the delivery is in flight
+    let started = Instant::now();
+    ureq::get(&format!("http://{}/json", addr))
+        .call()
+        .expect("Request failed");
+    assert!(started.elapsed() < Duration::from_millis(300));
+
+    let (status, body) = forwarding.join().unwrap();
+    assert_eq!(status, 500);
+    assert!(body.contains("provider client crashed"), "{}", body);
+}
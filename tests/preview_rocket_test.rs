@@ -0,0 +1,102 @@
+//! Rocket preview integration tests.
+//!
+//! Mirrors the Axum tests in `preview_test.rs` to keep all adapters at parity.
+//!
+//! Run with: cargo test --features preview-rocket --test preview_rocket_test
+
+#![cfg(feature = "preview-rocket")]
+
+use std::sync::Arc;
+
+use missive::preview::{mailbox_routes, mailbox_routes_with_config, PreviewConfig};
+use missive::{Attachment, Email, MemoryStorage, Storage};
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+
+fn create_test_storage() -> Arc<MemoryStorage> {
+    let storage = MemoryStorage::shared();
+    storage.push(
+        Email::new()
+            .subject("Hello")
+            .from(("Admin", "admin@example.com"))
+            .to("user@example.com")
+            .html_body("<p>Hello</p>")
+            .attachment(Attachment::from_bytes("file.txt", b"data".to_vec())),
+    );
+    storage
+}
+
+async fn client(routes: Vec<rocket::Route>) -> Client {
+    let rocket = rocket::build().mount("/mailbox", routes);
+    Client::tracked(rocket).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn test_index_nonce_override() {
+    let config = PreviewConfig {
+        script_nonce: Some("config-nonce".into()),
+        style_nonce: None,
+    };
+    let client = client(mailbox_routes_with_config(create_test_storage(), config)).await;
+
+    let html = client
+        .get("/mailbox/?script_nonce=query-nonce")
+        .dispatch()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(html.contains("nonce=\"query-nonce\""));
+    assert!(!html.contains("config-nonce"));
+}
+
+#[rocket::async_test]
+async fn test_json_and_endpoints() {
+    let storage = create_test_storage();
+    let id = storage.all()[0].id.clone();
+    let client = client(mailbox_routes(Arc::clone(&storage))).await;
+
+    let body = client
+        .get("/mailbox/json")
+        .dispatch()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["data"][0]["subject"], "Hello");
+    assert_eq!(json["data"][0]["attachments"][0]["filename"], "file.txt");
+
+    for (path, content_type) in [
+        (format!("/mailbox/{}", id), "application/json"),
+        (format!("/mailbox/{}/html", id), "text/html"),
+        (format!("/mailbox/{}/source", id), "text/plain"),
+        (format!("/mailbox/{}/compat", id), "application/json"),
+        (format!("/mailbox/{}/attachments/0", id), "text/plain"),
+    ] {
+        let resp = client.get(path.clone()).dispatch().await;
+        assert_eq!(resp.status(), Status::Ok, "{}", path);
+        let header = resp.headers().get_one("content-type").unwrap();
+        assert!(header.starts_with(content_type), "{}: {}", path, header);
+    }
+
+    let resp = client
+        .get("/mailbox/00000000-0000-0000-0000-000000000000")
+        .dispatch()
+        .await;
+    assert_eq!(resp.status(), Status::NotFound);
+
+    let resp = client.post(format!("/mailbox/{}/read", id)).dispatch().await;
+    assert_eq!(resp.status(), Status::NoContent);
+    assert!(storage.get(&id).unwrap().read);
+
+    let resp = client.delete(format!("/mailbox/{}", id)).dispatch().await;
+    assert_eq!(resp.status(), Status::NoContent);
+    assert!(storage.get(&id).is_none());
+
+    storage.push(Email::new().subject("Another"));
+    let resp = client.post("/mailbox/clear").dispatch().await;
+    assert_eq!(resp.status(), Status::NoContent);
+    assert_eq!(storage.count(), 0);
+}
@@ -74,6 +74,26 @@ fn test_json_endpoint() {
     assert_eq!(body["data"].as_array().unwrap().len(), 1);
 }
 
+#[test]
+fn test_json_pagination() {
+    let storage = create_test_storage();
+    storage.push(Email::new().subject("Newest"));
+    let addr = get_test_addr();
+
+    PreviewServer::new(&addr, storage).unwrap().spawn();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let body: serde_json::Value = ureq::get(&format!("http://{}/json?limit=1", addr))
+        .call()
+        .expect("Request failed")
+        .into_json()
+        .unwrap();
+
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["subject"], "Newest");
+    assert_eq!(body["total"], 2);
+}
+
 #[test]
 fn test_view_single_email() {
     let storage = create_test_storage();
@@ -160,3 +180,91 @@ fn test_clear_emails() {
     assert_eq!(response.status(), 204);
     assert_eq!(storage.count(), 0);
 }
+
+#[test]
+fn test_delete_and_mark_read() {
+    let storage = create_test_storage();
+    let id = storage.all()[0].id.clone();
+    let addr = get_test_addr();
+
+    PreviewServer::new(&addr, Arc::clone(&storage))
+        .unwrap()
+        .spawn();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let response = ureq::post(&format!("http://{}/{}/read", addr, id))
+        .call()
+        .expect("Request failed");
+    assert_eq!(response.status(), 204);
+    assert!(storage.get(&id).unwrap().read);
+
+    let response = ureq::delete(&format!("http://{}/{}", addr, id))
+        .call()
+        .expect("Request failed");
+    assert_eq!(response.status(), 204);
+    assert_eq!(storage.count(), 0);
+
+    let err = ureq::delete(&format!("http://{}/{}", addr, id))
+        .call()
+        .unwrap_err();
+    assert!(matches!(err, ureq::Error::Status(404, _)));
+}
+
+#[test]
+fn test_forward_to_real_address() {
+    let storage = create_test_storage();
+    let id = storage.all()[0].id.clone();
+    let addr = get_test_addr();
+    let mailer = missive::providers::LocalMailer::new();
+    missive::register(missive::preview::DEFAULT_FORWARD_MAILER, mailer.clone());
+
+    PreviewServer::new(&addr, Arc::clone(&storage))
+        .unwrap()
+        .spawn();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let body: serde_json::Value = ureq::post(&format!(
+        "http://{}/{}/forward?to=me%2Bpreview%40example.com",
+        addr, id
+    ))
+    .call()
+    .expect("Request failed")
+    .into_json()
+    .unwrap();
+    assert!(body["message_id"].is_string());
+    assert_eq!(mailer.emails()[0].email.to[0].email, "me+preview@example.com");
+
+    let err = ureq::post(&format!("http://{}/{}/forward", addr, id))
+        .call()
+        .unwrap_err();
+    assert!(matches!(err, ureq::Error::Status(400, _)));
+}
+
+#[test]
+fn test_local_server_uses_global_storage() {
+    let addr = get_test_addr();
+
+    PreviewServer::local(&addr).unwrap().spawn();
+    std::thread::sleep(Duration::from_millis(50));
+
+    // The global storage exists once the server has been created
+    let storage = missive::local_storage().expect("local storage initialized");
+    storage.push(
+        Email::new()
+            .subject("From a worker")
+            .from("sender@example.com")
+            .to("recipient@example.com"),
+    );
+
+    let body: serde_json::Value = ureq::get(&format!("http://{}/json", addr))
+        .call()
+        .expect("Request failed")
+        .into_json()
+        .unwrap();
+
+    assert!(body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|email| email["subject"] == "From a worker"));
+}
@@ -133,6 +133,33 @@ async fn test_json_renders_emails() {
     assert!(email2["attachments"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn test_json_pagination() {
+    let storage = create_test_storage();
+    let app = missive::preview::mailbox_router(storage);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/json?offset=1&limit=1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    // Second-newest email only, with the overall total
+    let data = json["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["subject"], "Peace, love, not war");
+    assert_eq!(json["total"], 2);
+}
+
 #[tokio::test]
 async fn test_json_empty_storage() {
     let storage = create_empty_storage();
@@ -299,6 +326,84 @@ async fn test_email_html_body() {
     assert!(html.contains("<p>Lorem ipsum dolor sit amet</p>"));
 }
 
+// ============================================================================
+// Source Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_email_source() {
+    let storage = create_test_storage();
+    let emails = storage.all();
+    let target_email = emails
+        .iter()
+        .find(|e| e.email.subject == "Peace, love, not war")
+        .unwrap();
+    let target_id = &target_email.id;
+
+    let app = missive::preview::mailbox_router(storage);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}/source", target_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let source = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(source.contains("From: Admin <admin@avengers.org>\r\n"));
+    assert!(source.contains("X-Magic-Number: 7\r\n"));
+    assert!(source.contains("Content-Type: multipart/mixed;"));
+    assert!(source.contains("Content-Disposition: attachment; filename=\"file.png\""));
+}
+
+#[tokio::test]
+async fn test_email_compat() {
+    let storage = create_empty_storage();
+    let id = storage.push(
+        Email::new()
+            .from("sender@example.com")
+            .to("user@example.com")
+            .html_body(r#"<div style="display: flex"><img src="https://example.com/a.png"></div>"#),
+    );
+
+    let app = missive::preview::mailbox_router(storage);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/{}/compat", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let rules: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["rule"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(rules, vec!["css-unsupported", "img-missing-width"]);
+    assert_eq!(json[0]["severity"], "warning");
+}
+
 // ============================================================================
 // Attachment Tests
 // ============================================================================
@@ -399,3 +504,339 @@ async fn test_clear_all() {
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
     assert!(storage.all().is_empty());
 }
+
+#[tokio::test]
+async fn test_delete_and_mark_read() {
+    let storage = create_test_storage();
+    let ids: Vec<String> = storage.all().into_iter().map(|e| e.id).collect();
+    let app = missive::preview::mailbox_router(Arc::clone(&storage));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/{}/read", ids[0]))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/json").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["unread"], 1);
+    assert_eq!(json["data"][0]["read"], true);
+    assert_eq!(json["data"][1]["read"], false);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/{}", ids[1]))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(storage.count(), 1);
+    assert!(storage.get(&ids[1]).is_none());
+
+    for (method, uri) in [
+        ("DELETE", format!("/{}", ids[1])),
+        ("POST", format!("/{}/read", ids[1])),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+#[tokio::test]
+async fn test_forward_to_real_address() {
+    let storage = create_test_storage();
+    let id = storage.all()[1].id.clone();
+    let app = missive::preview::mailbox_router(Arc::clone(&storage));
+
+    let forward = |uri: String| {
+        app.clone().oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    // No forwarding mailer configured yet
+    let response = forward(format!("/{}/forward?to=me@example.com", id))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let mailer = missive::providers::LocalMailer::new();
+    missive::register(missive::preview::DEFAULT_FORWARD_MAILER, mailer.clone());
+
+    let response = forward(format!("/{}/forward?to=me%2Bpreview@example.com", id))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["message_id"].is_string());
+
+    let sent = mailer.emails().remove(0).email;
+    assert_eq!(sent.to.len(), 1);
+    assert_eq!(sent.to[0].email, "me+preview@example.com");
+    assert_eq!(sent.subject, "Peace, love, not war");
+    assert!(sent.cc.is_empty() && sent.bcc.is_empty());
+
+    let response = forward(format!("/{}/forward", id)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = forward(format!("/{}/forward?to=not-an-address", id))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = forward("/missing/forward?to=me@example.com".to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    missive::unregister(missive::preview::DEFAULT_FORWARD_MAILER);
+    assert_eq!(mailer.email_count(), 1);
+}
+
+// ============================================================================
+// Mailbox Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_mailboxes() {
+    let storage = missive::Mailboxes::shared();
+    storage.push(Email::new().subject("Default"));
+    storage.push(
+        Email::new()
+            .subject("For Acme")
+            .header("X-Missive-Mailbox", "acme"),
+    );
+    let app = missive::preview::mailbox_router(Arc::clone(&storage));
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/json").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 2);
+    assert_eq!(
+        json["mailboxes"],
+        serde_json::json!([
+            {"name": "acme", "count": 1, "unread": 1},
+            {"name": "default", "count": 1, "unread": 1}
+        ])
+    );
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/json?mailbox=acme")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["data"][0]["subject"], "For Acme");
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/?mailbox=acme")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains(r#"<option value="acme" selected>acme (1)</option>"#));
+    assert!(html.contains("For Acme"));
+    assert!(!html.contains(">Default<"));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/json?mailbox=globex")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/clear?mailbox=acme")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(storage.count(), 1);
+    assert_eq!(storage.all()[0].email.subject, "Default");
+}
+
+// ============================================================================
+// Live Update Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_events_stream_new_emails() {
+    let storage = create_test_storage();
+    let app = missive::preview::mailbox_router(Arc::clone(&storage));
+
+    let response = app
+        .oneshot(Request::builder().uri("/events").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    storage.push(
+        Email::new()
+            .from("sender@example.com")
+            .to("user@example.com")
+            .subject("Live"),
+    );
+
+    let mut body = response.into_body();
+    let frame = body.frame().await.unwrap().unwrap().into_data().unwrap();
+    let text = String::from_utf8(frame.to_vec()).unwrap();
+    assert!(text.starts_with("event: email\n"));
+    assert!(text.contains("\"subject\":\"Live\""));
+}
+
+// ============================================================================
+// Custom Storage Backend Tests
+// ============================================================================
+
+/// A custom backend that only exposes emails with a given subject prefix.
+struct FilteredStorage {
+    inner: MemoryStorage,
+    prefix: &'static str,
+}
+
+impl Storage for FilteredStorage {
+    fn push(&self, email: Email) -> String {
+        self.inner.push(email)
+    }
+
+    fn pop(&self) -> Option<missive::StoredEmail> {
+        self.inner.pop()
+    }
+
+    fn get(&self, id: &str) -> Option<missive::StoredEmail> {
+        self.inner
+            .get(id)
+            .filter(|e| e.email.subject.starts_with(self.prefix))
+    }
+
+    fn all(&self) -> Vec<missive::StoredEmail> {
+        self.inner
+            .all()
+            .into_iter()
+            .filter(|e| e.email.subject.starts_with(self.prefix))
+            .collect()
+    }
+
+    fn delete(&self, id: &str) -> bool {
+        self.inner.delete(id)
+    }
+
+    fn clear(&self) {
+        self.inner.clear()
+    }
+
+    fn count(&self) -> usize {
+        self.all().len()
+    }
+
+    fn flush(&self) -> Vec<missive::StoredEmail> {
+        self.inner.flush()
+    }
+}
+
+#[tokio::test]
+async fn test_custom_storage_backend() {
+    let storage = FilteredStorage {
+        inner: MemoryStorage::new(),
+        prefix: "[shown]",
+    };
+    storage.push(Email::new().to("a@example.com").subject("[shown] Visible"));
+    storage.push(Email::new().to("b@example.com").subject("Hidden"));
+
+    let storage: Arc<dyn Storage> = Arc::new(storage);
+    let app = missive::preview::mailbox_router(Arc::clone(&storage));
+
+    let response = app
+        .oneshot(Request::builder().uri("/json").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let data = json["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["subject"], "[shown] Visible");
+}
+
+#[cfg(feature = "storage-encryption")]
+#[tokio::test]
+async fn test_encrypted_storage_is_decrypted() {
+    let key = [9u8; 32];
+    let inner = MemoryStorage::shared();
+    let storage = missive::EncryptedStorage::new(Arc::clone(&inner), &key).unwrap();
+    storage.push(Email::new().to("jane@example.com").subject("Private"));
+    assert_ne!(inner.all()[0].email.subject, "Private");
+
+    let app = missive::preview::mailbox_router(storage);
+
+    let response = app
+        .oneshot(Request::builder().uri("/json").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let data = json["data"].as_array().unwrap();
+    assert_eq!(data[0]["subject"], "Private");
+    assert_eq!(data[0]["to"][0], "jane@example.com");
+}
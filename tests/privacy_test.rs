@@ -0,0 +1,81 @@
+//! Recipient and subject redaction in delivery spans.
+//!
+//! Run with: cargo test --features opentelemetry,local --test privacy_test
+
+#![cfg(all(feature = "opentelemetry", feature = "local"))]
+
+use missive::privacy::{self, LogRecipients};
+use missive::providers::LocalMailer;
+use missive::{deliver_with, Email};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Deliver one email and return the `missive.deliver` span.
+async fn deliver_span() -> SpanData {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("Alice@Example.com")
+        .subject("Your invoice");
+    {
+        let _default = tracing::subscriber::set_default(subscriber);
+        deliver_with(&email, &LocalMailer::new()).await.unwrap();
+    }
+    provider.force_flush().unwrap();
+    exporter
+        .get_finished_spans()
+        .unwrap()
+        .into_iter()
+        .find(|s| s.name == "missive.deliver")
+        .unwrap()
+}
+
+fn attribute(span: &SpanData, key: &str) -> Option<String> {
+    span.attributes
+        .iter()
+        .find(|kv: &&KeyValue| kv.key.as_str() == key)
+        .map(|kv| kv.value.as_str().into_owned())
+}
+
+// Settings are global, so every mode is checked in one test.
+#[tokio::test]
+async fn test_span_redaction() {
+    let span = deliver_span().await;
+    assert_eq!(
+        attribute(&span, "messaging.destination.name").as_deref(),
+        Some("Alice@Example.com")
+    );
+    assert_eq!(attribute(&span, "subject").as_deref(), Some("Your invoice"));
+
+    privacy::set_log_recipients(LogRecipients::Hashed);
+    let span = deliver_span().await;
+    let hashed = attribute(&span, "messaging.destination.name").unwrap();
+    assert_eq!(hashed.len(), 64);
+    assert!(!hashed.contains('@'));
+
+    privacy::set_log_recipients(LogRecipients::DomainOnly);
+    let span = deliver_span().await;
+    assert_eq!(
+        attribute(&span, "messaging.destination.name").as_deref(),
+        Some("example.com")
+    );
+
+    privacy::set_log_recipients(LogRecipients::Off);
+    privacy::set_log_subjects(false);
+    let span = deliver_span().await;
+    assert_eq!(attribute(&span, "messaging.destination.name"), None);
+    assert_eq!(attribute(&span, "to"), None);
+    assert_eq!(attribute(&span, "subject"), None);
+
+    privacy::reset();
+    let span = deliver_span().await;
+    assert_eq!(attribute(&span, "subject").as_deref(), Some("Your invoice"));
+}
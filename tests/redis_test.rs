@@ -0,0 +1,359 @@
+//! Redis storage and job queue tests, against an in-process fake Redis.
+//!
+//! Run with: cargo test --features local,redis --test redis_test
+
+#![cfg(all(feature = "local", feature = "redis"))]
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use missive::jobs::redis::RedisQueue;
+use missive::jobs::SendEmailJob;
+use missive::providers::{FailingMailer, LocalMailer};
+use missive::{Email, MailError, RedisStorage, Storage};
+
+// ============================================================================
+// Fake Redis: just the commands the backends use
+// ============================================================================
+
+#[derive(Default)]
+struct Db {
+    hashes: HashMap<String, HashMap<String, String>>,
+    lists: HashMap<String, VecDeque<String>>,
+}
+
+enum Reply {
+    Nil,
+    Ok,
+    Queued,
+    Int(i64),
+    Bulk(String),
+    Array(Vec<Reply>),
+    Error(String),
+}
+
+impl Reply {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Reply::Nil => out.extend_from_slice(b"$-1\r\n"),
+            Reply::Ok => out.extend_from_slice(b"+OK\r\n"),
+            Reply::Queued => out.extend_from_slice(b"+QUEUED\r\n"),
+            Reply::Int(n) => out.extend_from_slice(format!(":{}\r\n", n).as_bytes()),
+            Reply::Bulk(s) => {
+                out.extend_from_slice(format!("${}\r\n{}\r\n", s.len(), s).as_bytes())
+            }
+            Reply::Array(items) => {
+                out.extend_from_slice(format!("*{}\r\n", items.len()).as_bytes());
+                for item in items {
+                    item.write(out);
+                }
+            }
+            Reply::Error(e) => out.extend_from_slice(format!("-ERR {}\r\n", e).as_bytes()),
+        }
+    }
+}
+
+fn bulk(value: Option<String>) -> Reply {
+    value.map(Reply::Bulk).unwrap_or(Reply::Nil)
+}
+
+fn range(len: usize, start: i64, stop: i64) -> std::ops::Range<usize> {
+    let index = |i: i64| if i < 0 { len as i64 + i } else { i };
+    let start = index(start).max(0) as usize;
+    let stop = (index(stop) + 1).clamp(0, len as i64) as usize;
+    start..stop.max(start)
+}
+
+impl Db {
+    fn execute(&mut self, args: &[String]) -> Reply {
+        let int = |i: usize| args[i].parse::<i64>().unwrap();
+        match args[0].to_uppercase().as_str() {
+            "HSET" => {
+                let hash = self.hashes.entry(args[1].clone()).or_default();
+                Reply::Int(i64::from(
+                    hash.insert(args[2].clone(), args[3].clone()).is_none(),
+                ))
+            }
+            "HGET" => bulk(
+                self.hashes
+                    .get(&args[1])
+                    .and_then(|h| h.get(&args[2]).cloned()),
+            ),
+            "HMGET" => {
+                let hash = self.hashes.get(&args[1]);
+                Reply::Array(
+                    args[2..]
+                        .iter()
+                        .map(|field| bulk(hash.and_then(|h| h.get(field).cloned())))
+                        .collect(),
+                )
+            }
+            "HDEL" => {
+                let hash = self.hashes.entry(args[1].clone()).or_default();
+                Reply::Int(
+                    args[2..]
+                        .iter()
+                        .filter(|f| hash.remove(*f).is_some())
+                        .count() as i64,
+                )
+            }
+            "HLEN" => Reply::Int(self.hashes.get(&args[1]).map_or(0, |h| h.len() as i64)),
+            "LPUSH" => {
+                let list = self.lists.entry(args[1].clone()).or_default();
+                for value in &args[2..] {
+                    list.push_front(value.clone());
+                }
+                Reply::Int(list.len() as i64)
+            }
+            "LPOP" => bulk(self.lists.get_mut(&args[1]).and_then(|l| l.pop_front())),
+            "LLEN" => Reply::Int(self.lists.get(&args[1]).map_or(0, |l| l.len() as i64)),
+            "LRANGE" => {
+                let list = self.lists.get(&args[1]).cloned().unwrap_or_default();
+                let range = range(list.len(), int(2), int(3));
+                Reply::Array(list.range(range).map(|v| Reply::Bulk(v.clone())).collect())
+            }
+            "LREM" => {
+                let list = self.lists.entry(args[1].clone()).or_default();
+                let mut count = int(2);
+                let before = list.len();
+                list.retain(|v| {
+                    if count > 0 && *v == args[3] {
+                        count -= 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+                Reply::Int((before - list.len()) as i64)
+            }
+            "RPOPLPUSH" => {
+                let value = self.lists.get_mut(&args[1]).and_then(|l| l.pop_back());
+                if let Some(value) = &value {
+                    self.lists
+                        .entry(args[2].clone())
+                        .or_default()
+                        .push_front(value.clone());
+                }
+                bulk(value)
+            }
+            "DEL" => Reply::Int(
+                args[1..]
+                    .iter()
+                    .filter(|k| self.hashes.remove(*k).is_some() | self.lists.remove(*k).is_some())
+                    .count() as i64,
+            ),
+            // Only RedisStorage's mark-read script: HSET if the field exists
+            "EVAL" => match self.hashes.get_mut(&args[3]) {
+                Some(hash) if hash.contains_key(&args[4]) => {
+                    hash.insert(args[4].clone(), args[5].clone());
+                    Reply::Int(1)
+                }
+                _ => Reply::Int(0),
+            },
+            other => Reply::Error(format!("unknown command '{}'", other)),
+        }
+    }
+}
+
+/// Read one command (an array of bulk strings).
+fn read_command(reader: &mut impl BufRead) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+        let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+        let mut buf = vec![0; len + 2];
+        reader.read_exact(&mut buf).ok()?;
+        buf.truncate(len);
+        args.push(String::from_utf8(buf).ok()?);
+    }
+    Some(args)
+}
+
+fn serve(stream: TcpStream, db: Arc<Mutex<Db>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    let mut transaction: Option<Vec<Vec<String>>> = None;
+    while let Some(args) = read_command(&mut reader) {
+        let reply = match (args[0].to_uppercase().as_str(), &mut transaction) {
+            ("MULTI", _) => {
+                transaction = Some(Vec::new());
+                Reply::Ok
+            }
+            ("EXEC", _) => {
+                let mut db = db.lock().unwrap();
+                let queued = transaction.take().unwrap_or_default();
+                Reply::Array(queued.iter().map(|cmd| db.execute(cmd)).collect())
+            }
+            (_, Some(queued)) => {
+                queued.push(args);
+                Reply::Queued
+            }
+            (_, None) => db.lock().unwrap().execute(&args),
+        };
+        let mut out = Vec::new();
+        reply.write(&mut out);
+        if writer.write_all(&out).is_err() {
+            return;
+        }
+    }
+}
+
+/// Start a fake Redis and return its URL.
+fn spawn_redis() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let db = Arc::new(Mutex::new(Db::default()));
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let db = Arc::clone(&db);
+            std::thread::spawn(move || serve(stream, db));
+        }
+    });
+    format!("redis://127.0.0.1:{}/", port)
+}
+
+fn email(subject: &str) -> Email {
+    Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .subject(subject)
+        .text_body("Hello")
+}
+
+// ============================================================================
+// RedisStorage
+// ============================================================================
+
+#[test]
+fn test_storage_round_trip() {
+    let storage = RedisStorage::connect(&spawn_redis()).unwrap().prefix("dev");
+
+    let first = storage.push(email("First"));
+    storage.push(email("Second"));
+    storage.push(email("Third"));
+
+    assert_eq!(storage.count(), 3);
+    let subjects: Vec<_> = storage.all().into_iter().map(|e| e.email.subject).collect();
+    assert_eq!(subjects, ["Third", "Second", "First"]);
+    assert_eq!(storage.page(1, 5).len(), 2);
+    assert_eq!(storage.get(&first).unwrap().email.subject, "First");
+
+    assert_eq!(storage.unread_count(), 3);
+    assert!(storage.mark_read(&first));
+    assert!(storage.get(&first).unwrap().read);
+    assert_eq!(storage.unread_count(), 2);
+
+    assert!(storage.delete(&first));
+    assert!(!storage.mark_read(&first));
+    assert!(!storage.delete(&first));
+    assert_eq!(storage.pop().unwrap().email.subject, "Third");
+    assert_eq!(storage.count(), 1);
+
+    storage.clear();
+    assert!(storage.all().is_empty());
+}
+
+#[test]
+fn test_storage_is_shared_between_instances() {
+    let url = spawn_redis();
+    let app_a = RedisStorage::connect(&url).unwrap();
+    let app_b = RedisStorage::connect(&url).unwrap();
+    let other_env = RedisStorage::connect(&url).unwrap().prefix("staging");
+
+    app_a.push(email("Shared"));
+
+    assert_eq!(app_b.all()[0].email.subject, "Shared");
+    assert_eq!(other_env.count(), 0);
+}
+
+#[test]
+fn test_storage_connect_rejects_bad_url() {
+    assert!(matches!(
+        RedisStorage::connect("not a url"),
+        Err(MailError::Configuration(_))
+    ));
+}
+
+// ============================================================================
+// RedisQueue
+// ============================================================================
+
+#[tokio::test]
+async fn test_queue_delivers_jobs_in_order() {
+    let mailer = LocalMailer::new();
+    missive::register("redis-queue", mailer.clone());
+    let queue = RedisQueue::connect(&spawn_redis()).await.unwrap();
+
+    for subject in ["One", "Two"] {
+        queue
+            .push(SendEmailJob::new(email(subject)).via("redis-queue"))
+            .await
+            .unwrap();
+    }
+    assert_eq!(queue.len().await.unwrap(), 2);
+
+    assert!(matches!(queue.process_next().await, Ok(Some(Ok(())))));
+    assert!(matches!(queue.process_next().await, Ok(Some(Ok(())))));
+    assert!(matches!(queue.process_next().await, Ok(None)));
+
+    let subjects: Vec<_> = mailer
+        .emails()
+        .into_iter()
+        .map(|e| e.email.subject)
+        .collect();
+    assert_eq!(subjects, ["Two", "One"]);
+    assert!(queue.is_empty().await.unwrap());
+    assert_eq!(queue.recover().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_queue_retries_then_dead_letters() {
+    missive::register(
+        "redis-retryable",
+        FailingMailer::local().fail_for("user@example.com"),
+    );
+    missive::register(
+        "redis-permanent",
+        FailingMailer::local()
+            .fail_for("user@example.com")
+            .error(|_| MailError::InvalidAddress("user@example.com".into())),
+    );
+    let queue = RedisQueue::connect(&spawn_redis())
+        .await
+        .unwrap()
+        .name("retry-test")
+        .max_attempts(2);
+
+    queue
+        .push(SendEmailJob::new(email("Flaky")).via("redis-retryable"))
+        .await
+        .unwrap();
+    assert!(matches!(queue.process_next().await, Ok(Some(Err(_)))));
+    assert_eq!(queue.len().await.unwrap(), 1);
+    assert!(matches!(queue.process_next().await, Ok(Some(Err(_)))));
+    assert!(queue.is_empty().await.unwrap());
+
+    queue
+        .push(SendEmailJob::new(email("Bad")).via("redis-permanent"))
+        .await
+        .unwrap();
+    assert!(matches!(queue.process_next().await, Ok(Some(Err(_)))));
+    assert!(queue.is_empty().await.unwrap());
+
+    let dead: Vec<_> = queue
+        .dead()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|job| job.email.subject)
+        .collect();
+    assert_eq!(dead, ["Flaky", "Bad"]);
+}
@@ -0,0 +1,52 @@
+//! Tests for reloading the global mailer at runtime.
+//!
+//! Kept in its own binary: it changes process-wide environment variables.
+
+#![cfg(feature = "local")]
+
+use std::time::{Duration, Instant};
+
+use missive::providers::LocalMailer;
+
+fn provider() -> &'static str {
+    missive::mailer().unwrap().provider_name()
+}
+
+#[test]
+fn test_reload_swaps_global_mailer() {
+    std::env::set_var("EMAIL_PROVIDER", "local");
+    missive::init().unwrap();
+    assert_eq!(provider(), "local");
+
+    let manual = LocalMailer::new();
+    missive::register("manual", manual);
+    let registered = missive::named_mailer("manual").unwrap();
+
+    // The cached mailer is kept until reloaded
+    std::env::set_var("EMAIL_PROVIDER", "logger");
+    assert_eq!(provider(), "local");
+    missive::reload().unwrap();
+    assert_eq!(provider(), "logger");
+
+    // Registered mailers survive a reload
+    let after = missive::named_mailer("manual").unwrap();
+    assert!(std::sync::Arc::ptr_eq(&registered, &after));
+
+    // A broken configuration leaves the current mailer in place
+    std::env::set_var("EMAIL_PROVIDER", "carrier-pigeon");
+    assert!(missive::reload().is_err());
+    assert_eq!(provider(), "logger");
+
+    // The watcher picks up changes on its own
+    let watcher = missive::watch(Duration::from_millis(10));
+    std::env::set_var("EMAIL_PROVIDER", "local");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while provider() != "local" && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(provider(), "local");
+    watcher.stop();
+
+    std::env::remove_var("EMAIL_PROVIDER");
+    missive::reset();
+}
@@ -0,0 +1,166 @@
+//! Hosted template management tests.
+//!
+//! Run with: cargo test --features remote-templates,postmark,sendgrid,brevo --test remote_templates_test
+
+#![cfg(all(
+    feature = "remote-templates",
+    feature = "postmark",
+    feature = "sendgrid",
+    feature = "brevo"
+))]
+
+use missive::templates::remote::{
+    self, BrevoTemplates, PostmarkTemplates, RemoteTemplate, SendGridTemplates, TemplateClient,
+    TemplateSource,
+};
+use missive::MailError;
+use serde_json::json;
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn welcome() -> TemplateSource {
+    TemplateSource::new("welcome")
+        .subject("Welcome, {{name}}")
+        .html_body("<p>Hi {{name}}</p>")
+}
+
+#[tokio::test]
+async fn postmark_sync_creates_and_updates() {
+    let server = MockServer::start().await;
+    let client = PostmarkTemplates::new("server-token").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/templates"))
+        .and(header("X-Postmark-Server-Token", "server-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "TotalCount": 1,
+            "Templates": [{"TemplateId": 7, "Name": "welcome", "Active": true}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/templates/7"))
+        .and(body_json(json!({
+            "Name": "welcome",
+            "Subject": "Welcome, {{name}}",
+            "HtmlBody": "<p>Hi {{name}}</p>"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"TemplateId": 7, "Name": "welcome"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/templates"))
+        .and(body_json(json!({"Name": "receipt", "TextBody": "Thanks"})))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({"TemplateId": 8, "Name": "receipt"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let templates = [
+        welcome(),
+        TemplateSource::new("receipt").text_body("Thanks"),
+    ];
+    let report = remote::sync(&client, &templates).await.unwrap();
+
+    assert_eq!(
+        report.updated,
+        [RemoteTemplate {
+            id: "7".into(),
+            name: "welcome".into()
+        }]
+    );
+    assert_eq!(report.created[0].id, "8");
+}
+
+#[tokio::test]
+async fn sendgrid_create_adds_first_version() {
+    let server = MockServer::start().await;
+    let client = SendGridTemplates::new("SG.key").base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/templates"))
+        .and(header("Authorization", "Bearer SG.key"))
+        .and(body_json(
+            json!({"name": "welcome", "generation": "dynamic"}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(json!({"id": "d-123", "name": "welcome"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/templates/d-123/versions"))
+        .and(body_json(json!({
+            "name": "welcome",
+            "active": 1,
+            "subject": "Welcome, {{name}}",
+            "html_content": "<p>Hi {{name}}</p>"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": "v-1"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let created = client.create(&welcome()).await.unwrap();
+    assert_eq!(created.id, "d-123");
+}
+
+#[tokio::test]
+async fn sendgrid_list_reports_errors() {
+    let server = MockServer::start().await;
+    let client = SendGridTemplates::new("SG.bad").base_url(server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/templates"))
+        .and(query_param("generations", "dynamic"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "errors": [{"message": "authorization required"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let err = client.list().await.unwrap_err();
+    assert_eq!(err.status_code(), Some(401));
+    assert!(err.to_string().contains("authorization required"));
+}
+
+#[tokio::test]
+async fn brevo_create_requires_sender() {
+    let client = BrevoTemplates::new("key").base_url("http://127.0.0.1:9");
+
+    let err = client.create(&welcome()).await.unwrap_err();
+    assert!(matches!(err, MailError::Configuration(_)));
+}
+
+#[tokio::test]
+async fn brevo_create_sends_sender() {
+    let server = MockServer::start().await;
+    let client = BrevoTemplates::new("key")
+        .sender(("News", "news@example.com"))
+        .base_url(server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/smtp/templates"))
+        .and(header("Api-Key", "key"))
+        .and(body_json(json!({
+            "templateName": "welcome",
+            "subject": "Welcome, {{name}}",
+            "htmlContent": "<p>Hi {{name}}</p>",
+            "isActive": true,
+            "sender": {"email": "news@example.com", "name": "News"}
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 12})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let created = client.create(&welcome()).await.unwrap();
+    assert_eq!(created.id, "12");
+}
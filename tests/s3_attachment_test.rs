@@ -0,0 +1,99 @@
+//! Tests for S3-backed attachments.
+//!
+//! Kept in its own binary: the installed S3 client is process-wide.
+//!
+//! Run with: cargo test --features attachment-s3,local,resend --test s3_attachment_test
+
+#![cfg(all(feature = "attachment-s3", feature = "local", feature = "resend"))]
+
+use std::time::Duration;
+
+use missive::providers::{LocalMailer, ResendMailer};
+use missive::s3::{self, S3Client};
+use missive::{deliver_with, Attachment, Email, MailError};
+use wiremock::matchers::{body_partial_json, header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn email(attachment: Attachment) -> Email {
+    Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .subject("Your export")
+        .text_body("Attached.")
+        .attachment(attachment)
+}
+
+#[tokio::test]
+async fn test_s3_attachments() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/exports/2024/report.csv"))
+        .and(header_exists("authorization"))
+        .and(header_exists("x-amz-date"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"a,b\n1,2\n".to_vec()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/exports/missing.csv"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("NoSuchKey"))
+        .mount(&server)
+        .await;
+    s3::set_client(S3Client::new("us-east-1", "AKID", "secret").endpoint(server.uri()));
+
+    // The reference survives a job queue round trip
+    let attachment = Attachment::from_s3("exports", "2024/report.csv");
+    assert_eq!(attachment.filename, "report.csv");
+    assert_eq!(attachment.content_type, "text/csv");
+    assert!(attachment.data.is_empty());
+    let queued: Email =
+        serde_json::from_str(&serde_json::to_string(&email(attachment)).unwrap()).unwrap();
+
+    // Downloaded at delivery
+    let mailer = LocalMailer::new();
+    deliver_with(&queued, &mailer).await.unwrap();
+    let sent = &mailer.emails()[0].email;
+    assert_eq!(sent.attachments[0].data, b"a,b\n1,2\n");
+    assert!(sent.attachments[0].path.is_none());
+
+    let missing = email(Attachment::from_s3("exports", "missing.csv"));
+    let err = deliver_with(&missing, &mailer).await.unwrap_err();
+    assert!(matches!(err, MailError::AttachmentReadError(msg) if msg.contains("404")));
+
+    // Presigned URLs go to providers that fetch attachments themselves
+    s3::set_client(
+        S3Client::new("us-east-1", "AKID", "secret")
+            .endpoint("https://s3.example.com")
+            .presign(Duration::from_secs(3600)),
+    );
+    let resend = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/emails"))
+        .and(body_partial_json(serde_json::json!({
+            "attachments": [{"filename": "report.csv"}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "1"})))
+        .expect(1)
+        .mount(&resend)
+        .await;
+    let mailer = ResendMailer::new("re_123").base_url(resend.uri());
+    deliver_with(&queued, &mailer).await.unwrap();
+
+    let requests = resend.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    let sent = &body["attachments"][0];
+    assert!(sent.get("content").is_none());
+    let url = sent["path"].as_str().unwrap();
+    assert!(url.starts_with("https://s3.example.com/exports/2024/report.csv?X-Amz-Algorithm="));
+    assert!(url.contains("X-Amz-Expires=3600"));
+
+    s3::reset();
+}
+
+#[test]
+fn test_get_data_without_delivery_fails() {
+    let attachment = Attachment::from_s3("exports", "report.csv");
+    assert!(matches!(
+        attachment.get_data(),
+        Err(MailError::AttachmentReadError(_))
+    ));
+}
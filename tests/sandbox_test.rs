@@ -0,0 +1,95 @@
+//! Tests for the recipient sandbox.
+
+use missive::providers::LocalMailer;
+use missive::{Email, InterceptorExt, MailError, Mailer, Sandbox};
+
+/// Recipients outside the allowlist are redirected to the catch-all address.
+#[tokio::test]
+async fn test_sandbox_redirects_disallowed_recipients() {
+    let local = LocalMailer::new();
+    let mailer = local.clone().with_interceptor(
+        Sandbox::new()
+            .allow_domain("example.com")
+            .redirect_to("qa@example.com"),
+    );
+
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("teammate@example.com")
+        .to("customer@gmail.com")
+        .cc("other@gmail.com")
+        .subject("Test");
+
+    mailer.deliver(&email).await.unwrap();
+
+    let stored = local.last_email().unwrap().email;
+    let to: Vec<&str> = stored.to.iter().map(|a| a.email.as_str()).collect();
+    assert_eq!(to, vec!["teammate@example.com", "qa@example.com"]);
+    assert!(stored.cc.is_empty());
+}
+
+/// Without a catch-all address, disallowed recipients are dropped.
+#[tokio::test]
+async fn test_sandbox_drops_disallowed_recipients() {
+    let local = LocalMailer::new();
+    let mailer = local
+        .clone()
+        .with_interceptor(Sandbox::new().deny_domain("customer.com"));
+
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("dev@example.com")
+        .bcc("real@customer.com")
+        .subject("Test");
+
+    mailer.deliver(&email).await.unwrap();
+
+    let stored = local.last_email().unwrap().email;
+    assert_eq!(stored.to.len(), 1);
+    assert!(stored.bcc.is_empty());
+}
+
+/// An email whose recipients are all filtered is blocked.
+#[tokio::test]
+async fn test_sandbox_blocks_when_no_recipients_remain() {
+    let local = LocalMailer::new();
+    let mailer = local
+        .clone()
+        .with_interceptor(Sandbox::new().allow_domain("example.com"));
+
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("customer@gmail.com")
+        .subject("Test");
+
+    let result = mailer.deliver(&email).await;
+    assert!(matches!(result, Err(MailError::Blocked(_))));
+    assert_eq!(local.email_count(), 0);
+}
+
+/// With only `EMAIL_SANDBOX_REDIRECT_TO` set, every recipient is redirected.
+#[tokio::test]
+async fn test_sandbox_from_env_redirect_only_rewrites_everyone() {
+    missive::Config::builder()
+        .set("EMAIL_SANDBOX_REDIRECT_TO", "qa@example.com")
+        .build()
+        .install();
+    let sandbox = Sandbox::from_env().unwrap();
+    missive::Config::uninstall();
+
+    let local = LocalMailer::new();
+    let mailer = local.clone().with_interceptor(sandbox);
+
+    let email = Email::new()
+        .from("sender@example.com")
+        .to("customer@gmail.com")
+        .cc("other@gmail.com")
+        .subject("Test");
+
+    mailer.deliver(&email).await.unwrap();
+
+    let stored = local.last_email().unwrap().email;
+    let to: Vec<&str> = stored.to.iter().map(|a| a.email.as_str()).collect();
+    assert_eq!(to, vec!["qa@example.com"]);
+    assert!(stored.cc.is_empty());
+}
@@ -0,0 +1,22 @@
+//! JSON Schema tests.
+//!
+//! Run with: cargo test --features schemars --test schema_test
+
+#![cfg(feature = "schemars")]
+
+use missive::Email;
+
+#[test]
+fn email_schema_describes_json_format() {
+    let schema = serde_json::to_value(schemars::schema_for!(Email)).unwrap();
+
+    assert_eq!(schema["title"], "Email");
+    let properties = &schema["properties"];
+    for field in ["from", "to", "subject", "attachments", "provider_options"] {
+        assert!(properties.get(field).is_some(), "missing {}", field);
+    }
+    assert!(schema.get("required").is_none());
+
+    let attachment = &schema["$defs"]["Attachment"]["properties"];
+    assert_eq!(attachment["data"]["type"], "string");
+}
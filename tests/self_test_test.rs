@@ -0,0 +1,66 @@
+//! Tests for the deliverability self-test.
+//!
+//! Kept in its own binary: the installed config is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{test_scope, Config, MailError, SelfTest, Storage, SELF_TEST_HEADER};
+
+#[tokio::test]
+async fn test_self_test_sends_probe() {
+    let mailer = LocalMailer::new();
+    let storage = mailer.storage();
+
+    Config::builder()
+        .set("EMAIL_FROM", "app@example.com")
+        .build()
+        .install();
+    let report = test_scope(mailer.clone(), missive::self_test())
+        .await
+        .unwrap();
+    assert_eq!(report.provider, "local");
+    assert_eq!(report.to.email, "app@example.com");
+    assert!(!report.sandbox);
+    assert!(report.to_string().starts_with("local accepted probe "));
+
+    let sent = storage.all();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].id, report.message_id);
+    assert_eq!(sent[0].email.headers[SELF_TEST_HEADER], report.probe_id);
+    assert_eq!(sent[0].email.to[0].email, "app@example.com");
+
+    Config::builder()
+        .set("EMAIL_FROM", "app@example.com")
+        .set("EMAIL_SELF_TEST_TO", "ops@example.com")
+        .set("EMAIL_SELF_TEST_SANDBOX", "true")
+        .build()
+        .install();
+    let report = test_scope(mailer.clone(), missive::self_test())
+        .await
+        .unwrap();
+    assert_eq!(report.to.email, "ops@example.com");
+    assert!(report.sandbox);
+    assert!(report.to_string().ends_with(" [sandbox]"));
+
+    let probe = SelfTest::new().to("qa@example.com");
+    let report = test_scope(mailer.clone(), async { probe.run().await })
+        .await
+        .unwrap();
+    assert_eq!(report.to.email, "qa@example.com");
+    assert_eq!(storage.count(), 3);
+
+    Config::builder()
+        .set("EMAIL_SELF_TEST_TO", "not an address")
+        .build()
+        .install();
+    let err = test_scope(mailer.clone(), missive::self_test())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MailError::Configuration(_)));
+
+    Config::builder().build().install();
+    let err = test_scope(mailer, missive::self_test()).await.unwrap_err();
+    assert!(matches!(err, MailError::Configuration(_)));
+    Config::uninstall();
+}
@@ -0,0 +1,266 @@
+//! SMTP capture server integration tests.
+//!
+//! Sends real SMTP traffic through `SmtpMailer` and checks what lands in storage.
+//!
+//! Run with: cargo test --features smtp-capture,smtp --test smtp_capture_test
+
+#![cfg(all(feature = "smtp-capture", feature = "smtp"))]
+
+use std::sync::Arc;
+
+use missive::providers::SmtpMailer;
+use missive::smtp_capture::SmtpCaptureServer;
+use missive::{Attachment, Email, Mailer, MemoryStorage, Priority, Storage};
+
+#[tokio::test]
+async fn test_captures_smtp_delivery() {
+    let storage = MemoryStorage::shared();
+    let server = SmtpCaptureServer::new("127.0.0.1:0", Arc::clone(&storage)).unwrap();
+    let port = server.local_addr().unwrap().port();
+    server.spawn();
+
+    let mailer = SmtpMailer::new("127.0.0.1", port).no_tls().build();
+    let email = Email::new()
+        .from(("Alice", "alice@example.com"))
+        .to("bob@example.com")
+        .bcc("audit@example.com")
+        .subject("Quarterly report")
+        .text_body("See attached.")
+        .html_body("<p>See attached.</p>")
+        .priority(Priority::High)
+        .header("X-Campaign", "q3")
+        .request_read_receipt("alice@example.com")
+        .attachment(Attachment::from_bytes("report.csv", b"a,b\n1,2\n".to_vec()));
+
+    mailer.deliver(&email).await.expect("delivery failed");
+
+    let stored = storage.all();
+    assert_eq!(stored.len(), 1);
+    let captured = &stored[0].email;
+
+    assert_eq!(captured.subject, "Quarterly report");
+    assert_eq!(captured.from.as_ref().unwrap().email, "alice@example.com");
+    assert_eq!(captured.to[0].email, "bob@example.com");
+    assert_eq!(captured.bcc[0].email, "audit@example.com");
+    assert_eq!(
+        captured.text_body.as_deref().map(str::trim),
+        Some("See attached.")
+    );
+    assert!(captured
+        .html_body
+        .as_deref()
+        .unwrap()
+        .contains("<p>See attached.</p>"));
+    assert_eq!(captured.attachments.len(), 1);
+    assert_eq!(captured.attachments[0].filename, "report.csv");
+    assert_eq!(captured.attachments[0].data, b"a,b\n1,2\n");
+    assert_eq!(captured.get_priority(), Some(Priority::High));
+    assert_eq!(captured.headers["Importance"], "high");
+    assert_eq!(captured.headers["X-Campaign"], "q3");
+    assert_eq!(
+        captured.headers["Disposition-Notification-To"],
+        "alice@example.com"
+    );
+}
+
+#[tokio::test]
+async fn test_envelope_sender() {
+    let storage = MemoryStorage::shared();
+    let server = SmtpCaptureServer::new("127.0.0.1:0", Arc::clone(&storage)).unwrap();
+    let port = server.local_addr().unwrap().port();
+    server.spawn();
+
+    let mailer = SmtpMailer::new("127.0.0.1", port)
+        .no_tls()
+        .envelope_from("bounces@example.com")
+        .build();
+    let email = Email::new()
+        .from("alice@example.com")
+        .to("bob@example.com")
+        .subject("Hi")
+        .text_body("Hello");
+
+    mailer.deliver(&email).await.unwrap();
+    let verp = email
+        .clone()
+        .return_path("bounces+bob=example.com@example.com");
+    mailer.deliver(&verp).await.unwrap();
+
+    let stored = storage.all();
+    let mut return_paths: Vec<_> = stored
+        .iter()
+        .map(|s| s.email.return_path.as_ref().unwrap().email.as_str())
+        .collect();
+    return_paths.sort();
+    assert_eq!(
+        return_paths,
+        ["bounces+bob=example.com@example.com", "bounces@example.com"]
+    );
+    assert!(stored
+        .iter()
+        .all(|s| s.email.from.as_ref().unwrap().email == "alice@example.com"));
+}
+
+#[tokio::test]
+async fn test_relays_signature_headers_verbatim() {
+    let storage = MemoryStorage::shared();
+    let server = SmtpCaptureServer::new("127.0.0.1:0", Arc::clone(&storage)).unwrap();
+    let port = server.local_addr().unwrap().port();
+    server.spawn();
+    let mailer = SmtpMailer::new("127.0.0.1", port).no_tls().build();
+
+    let seals = [
+        "ARC-Seal: i=2; a=rsa-sha256; cv=pass; d=relay.example;\r\n\tb=c2Vjb25k",
+        "ARC-Seal: i=1; a=rsa-sha256; cv=none; d=example.com;\r\n\tb=Zmlyc3Q=",
+    ];
+    let email = Email::new()
+        .from("alice@example.com")
+        .to("bob@example.com")
+        .subject("Forwarded")
+        .text_body("Hello")
+        .raw_header(seals[0])
+        .raw_header(seals[1]);
+    mailer.deliver(&email).await.unwrap();
+
+    // Relay what was received; the chain survives both hops unchanged
+    let received = storage.pop().unwrap().email;
+    assert_eq!(received.raw_headers, seals);
+    mailer.deliver(&received).await.unwrap();
+    assert_eq!(storage.pop().unwrap().email.raw_headers, seals);
+}
+
+#[tokio::test]
+async fn test_internationalized_addresses() {
+    let email = Email::new()
+        .from("alice@example.com")
+        .to("user@例え.jp")
+        .subject("Hi")
+        .text_body("Hello");
+
+    // Sent as-is when the server advertises SMTPUTF8
+    let storage = MemoryStorage::shared();
+    let server = SmtpCaptureServer::new("127.0.0.1:0", Arc::clone(&storage)).unwrap();
+    let port = server.local_addr().unwrap().port();
+    server.spawn();
+    let mailer = SmtpMailer::new("127.0.0.1", port).no_tls().build();
+    mailer.deliver(&email).await.unwrap();
+    mailer
+        .deliver(&email.clone().to("josé@example.com"))
+        .await
+        .unwrap();
+    assert_eq!(storage.all()[0].email.to[0].email, "user@例え.jp");
+    assert_eq!(storage.count(), 2);
+
+    // Otherwise domains fall back to Punycode
+    let storage = MemoryStorage::shared();
+    let server = SmtpCaptureServer::new("127.0.0.1:0", Arc::clone(&storage))
+        .unwrap()
+        .smtputf8(false);
+    let port = server.local_addr().unwrap().port();
+    server.spawn();
+    let mailer = SmtpMailer::new("127.0.0.1", port).no_tls().build();
+    mailer.deliver(&email).await.unwrap();
+    assert_eq!(storage.all()[0].email.to[0].email, "user@xn--r8jz45g.jp");
+
+    // ...and non-ASCII local parts can't be delivered
+    let err = mailer
+        .deliver(&email.clone().to("josé@example.com"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, missive::MailError::InvalidAddress(msg) if msg.contains("SMTPUTF8")));
+    assert_eq!(storage.count(), 1);
+}
+
+/// Minimal SOCKS5 server (no auth, CONNECT only) that counts connections.
+#[cfg(feature = "socks")]
+async fn spawn_socks_proxy() -> (u16, Arc<std::sync::atomic::AtomicUsize>) {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&connections);
+
+    tokio::spawn(async move {
+        loop {
+            let (mut client, _) = listener.accept().await.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut header = [0u8; 2];
+                client.read_exact(&mut header).await.unwrap();
+                let mut methods = vec![0u8; header[1] as usize];
+                client.read_exact(&mut methods).await.unwrap();
+                client.write_all(&[5, 0]).await.unwrap();
+
+                let mut request = [0u8; 4];
+                client.read_exact(&mut request).await.unwrap();
+                let host = match request[3] {
+                    1 => {
+                        let mut ip = [0u8; 4];
+                        client.read_exact(&mut ip).await.unwrap();
+                        std::net::Ipv4Addr::from(ip).to_string()
+                    }
+                    3 => {
+                        let len = client.read_u8().await.unwrap();
+                        let mut name = vec![0u8; len as usize];
+                        client.read_exact(&mut name).await.unwrap();
+                        String::from_utf8(name).unwrap()
+                    }
+                    atyp => panic!("unsupported address type {}", atyp),
+                };
+                let target_port = client.read_u16().await.unwrap();
+                let mut target = TcpStream::connect((host.as_str(), target_port))
+                    .await
+                    .unwrap();
+                client
+                    .write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0])
+                    .await
+                    .unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut client, &mut target).await;
+            });
+        }
+    });
+
+    (port, connections)
+}
+
+#[cfg(feature = "socks")]
+#[tokio::test]
+async fn test_delivers_through_socks5_proxy() {
+    let storage = MemoryStorage::shared();
+    let server = SmtpCaptureServer::new("127.0.0.1:0", Arc::clone(&storage)).unwrap();
+    let port = server.local_addr().unwrap().port();
+    server.spawn();
+    let (proxy_port, connections) = spawn_socks_proxy().await;
+
+    let mailer = SmtpMailer::new("127.0.0.1", port)
+        .no_tls()
+        .proxy(&format!("socks5://127.0.0.1:{}", proxy_port))
+        .build();
+    let email = Email::new()
+        .from("alice@example.com")
+        .to("bob@example.com")
+        .subject("Via proxy")
+        .text_body("Hello");
+
+    mailer.deliver(&email).await.expect("delivery failed");
+    mailer.deliver(&email).await.expect("delivery failed");
+
+    assert_eq!(storage.count(), 2);
+    assert_eq!(storage.all()[0].email.subject, "Via proxy");
+    assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+#[cfg(feature = "socks")]
+#[tokio::test]
+async fn test_invalid_proxy_url_fails_validation() {
+    let mailer = SmtpMailer::new("127.0.0.1", 2525)
+        .proxy("http://proxy.internal:3128")
+        .build();
+    assert!(matches!(
+        mailer.validate_config(),
+        Err(missive::MailError::Configuration(_))
+    ));
+}
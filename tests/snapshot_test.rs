@@ -0,0 +1,39 @@
+//! Email snapshot tests.
+//!
+//! Run with: cargo test --features snapshots --test snapshot_test
+
+#![cfg(feature = "snapshots")]
+
+use missive::providers::LocalMailer;
+use missive::testing::*;
+use missive::{Attachment, Email, Mailer};
+
+#[tokio::test]
+async fn test_assert_email_snapshot_inline() {
+    let mailer = LocalMailer::new();
+    mailer
+        .deliver(
+            &Email::new()
+                .from(("Tony Stark", "tony.stark@example.com"))
+                .to("steve.rogers@example.com")
+                .subject("Hello, Avengers!")
+                .header("Date", "Tue, 1 Jul 2025 10:00:00 +0000")
+                .text_body("Some text")
+                .attachment(Attachment::from_bytes("plans.pdf", vec![0; 1024])),
+        )
+        .await
+        .unwrap();
+
+    assert_email_snapshot!(mailer, @r"
+    From: Tony Stark <tony.stark@example.com>
+    To: steve.rogers@example.com
+    Subject: Hello, Avengers!
+    Date: [redacted]
+
+    --- text ---
+    Some text
+
+    --- attachments ---
+    plans.pdf (application/pdf, 1024 bytes)
+    ");
+}
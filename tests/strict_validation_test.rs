@@ -0,0 +1,33 @@
+//! Tests for strict validation on every delivery.
+//!
+//! Kept in its own binary: the installed config is process-wide.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{deliver_with, Config, Email, MailError, ValidationIssue};
+
+#[tokio::test]
+async fn test_strict_validation_rejects_before_delivery() {
+    Config::builder()
+        .from("sender@example.com")
+        .strict_validation(true)
+        .build()
+        .install();
+
+    let mailer = LocalMailer::new();
+    let email = Email::new().to("user@example.com").subject("No body");
+    let err = deliver_with(&email, &mailer).await.unwrap_err();
+
+    let ok = deliver_with(&email.clone().text_body("Hi"), &mailer).await;
+    Config::uninstall();
+
+    match err {
+        MailError::Validation(report) => {
+            assert_eq!(report.issues(), [ValidationIssue::EmptyBody]);
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+    assert!(ok.is_ok());
+    assert_eq!(mailer.email_count(), 1);
+}
@@ -0,0 +1,118 @@
+//! OpenTelemetry span attribute and trace propagation tests.
+//!
+//! Run with: cargo test --features opentelemetry,resend --test telemetry_test
+
+#![cfg(all(feature = "opentelemetry", feature = "resend"))]
+
+use missive::providers::ResendMailer;
+use missive::{deliver_with, Email};
+use opentelemetry::trace::{Status, TraceContextExt, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use serde_json::json;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use wiremock::matchers::{header_exists, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn email() -> Email {
+    Email::new()
+        .from("sender@example.com")
+        .to("user@example.com")
+        .subject("Traced")
+}
+
+/// Run `f` with an OpenTelemetry-backed subscriber, returning the exported spans.
+async fn with_tracing<F: std::future::Future>(f: F) -> (F::Output, Vec<SpanData>) {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+    let _default = tracing::subscriber::set_default(subscriber);
+    let output = f.await;
+    provider.force_flush().unwrap();
+    (output, exporter.get_finished_spans().unwrap())
+}
+
+fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a opentelemetry::Value> {
+    span.attributes
+        .iter()
+        .find(|kv: &&KeyValue| kv.key.as_str() == key)
+        .map(|kv| &kv.value)
+}
+
+#[tokio::test]
+async fn test_delivery_span_attributes_and_traceparent() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(header_exists("traceparent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "msg_123"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mailer = ResendMailer::new("re_123").base_url(server.uri());
+
+    let (trace_id, spans) = with_tracing(async {
+        let parent = tracing::info_span!("request");
+        deliver_with(&email(), &mailer)
+            .instrument(parent.clone())
+            .await
+            .unwrap();
+        parent.context().span().span_context().trace_id()
+    })
+    .await;
+
+    let span = spans.iter().find(|s| s.name == "missive.deliver").unwrap();
+    assert_eq!(span.span_context.trace_id(), trace_id);
+    assert_eq!(
+        attribute(span, "messaging.system").unwrap().as_str(),
+        "resend"
+    );
+    assert_eq!(
+        attribute(span, "messaging.destination.name")
+            .unwrap()
+            .as_str(),
+        "user@example.com"
+    );
+    assert_eq!(
+        attribute(span, "messaging.message.id").unwrap().as_str(),
+        "msg_123"
+    );
+
+    // The provider request carried the delivery span's trace context
+    let requests = server.received_requests().await.unwrap();
+    let traceparent = requests[0]
+        .headers
+        .get("traceparent")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(traceparent.contains(&trace_id.to_string()));
+}
+
+#[tokio::test]
+async fn test_failed_delivery_records_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({"message": "Invalid"})))
+        .mount(&server)
+        .await;
+    let mailer = ResendMailer::new("re_123").base_url(server.uri());
+
+    let (result, spans) = with_tracing(deliver_with(&email(), &mailer)).await;
+    assert!(result.is_err());
+
+    let span = spans.iter().find(|s| s.name == "missive.deliver").unwrap();
+    assert!(matches!(span.status, Status::Error { .. }));
+    assert_eq!(attribute(span, "error.type").unwrap().as_str(), "provider_4xx");
+    assert!(span.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|kv| kv.key.as_str() == "exception.message")));
+}
@@ -0,0 +1,94 @@
+//! Tests for per-tenant mailers.
+//!
+//! Run with: cargo test --features local --test tenant_test
+
+#![cfg(feature = "local")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use missive::providers::LocalMailer;
+use missive::tenant::{self, TenantMailer, TenantMailerProvider};
+use missive::{deliver_for, deliver_many_for, Email, MailError};
+
+struct Workspaces {
+    acme: LocalMailer,
+    globex: LocalMailer,
+    lookups: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl TenantMailerProvider for Workspaces {
+    async fn resolve(&self, tenant_id: &str) -> Result<TenantMailer, MailError> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        match tenant_id {
+            "acme" => Ok(TenantMailer::new(self.acme.clone())
+                .from(("Acme", "hello@acme.test"))
+                .from_domain("acme.test")),
+            "globex" => Ok(TenantMailer::new(self.globex.clone()).from("noreply@globex.test")),
+            other => Err(MailError::Configuration(format!(
+                "unknown workspace {}",
+                other
+            ))),
+        }
+    }
+}
+
+fn email(to: &str) -> Email {
+    Email::new().to(to).subject("Welcome").text_body("Hi")
+}
+
+#[tokio::test]
+async fn test_deliver_for_resolves_and_caches_tenants() {
+    let acme = LocalMailer::new();
+    let globex = LocalMailer::new();
+    let lookups = Arc::new(AtomicUsize::new(0));
+    tenant::set_provider(Workspaces {
+        acme: acme.clone(),
+        globex: globex.clone(),
+        lookups: Arc::clone(&lookups),
+    });
+
+    deliver_for("acme", &email("a@example.com")).await.unwrap();
+    deliver_for("acme", &email("b@example.com")).await.unwrap();
+    deliver_many_for("globex", &[email("c@example.com"), email("d@example.com")])
+        .await
+        .unwrap();
+
+    assert_eq!(acme.email_count(), 2);
+    assert_eq!(globex.email_count(), 2);
+    let sent = acme.emails().remove(0).email;
+    let from = sent.from.unwrap();
+    assert_eq!(from.email, "hello@acme.test");
+    assert_eq!(from.name.as_deref(), Some("Acme"));
+    assert_eq!(
+        globex.emails()[0].email.from.as_ref().unwrap().email,
+        "noreply@globex.test"
+    );
+
+    // Each tenant was resolved once
+    assert_eq!(lookups.load(Ordering::SeqCst), 2);
+    tenant::invalidate("acme");
+    deliver_for("acme", &email("e@example.com")).await.unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 3);
+
+    // Senders outside the tenant's domain are rejected
+    let spoofed = email("f@example.com").from("ceo@globex.test");
+    let result = deliver_for("acme", &spoofed).await;
+    assert!(matches!(result, Err(MailError::InvalidAddress(_))));
+    assert_eq!(acme.email_count(), 3);
+
+    // Resolution errors are returned, and not cached
+    assert!(deliver_for("initech", &email("g@example.com"))
+        .await
+        .is_err());
+    assert!(deliver_for("initech", &email("g@example.com"))
+        .await
+        .is_err());
+    assert_eq!(lookups.load(Ordering::SeqCst), 5);
+
+    tenant::clear_provider();
+    let result = deliver_for("acme", &email("h@example.com")).await;
+    assert!(matches!(result, Err(MailError::Configuration(_))));
+}
@@ -416,3 +416,62 @@ async fn get_emails_to_returns_matching_emails() {
     assert_eq!(steve_emails.len(), 1);
     assert_eq!(steve_emails[0].email.subject, "For Steve");
 }
+
+// ============================================================================
+// Assertion Macros
+// ============================================================================
+
+/// Run `f`, returning the file and line where it panicked.
+fn panic_location(f: impl FnOnce() + std::panic::UnwindSafe) -> (String, u32) {
+    use std::sync::{Arc, Mutex};
+
+    let location = Arc::new(Mutex::new(None));
+    let captured = Arc::clone(&location);
+    let thread = std::thread::current().id();
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Other tests may panic concurrently; only record this thread
+        if std::thread::current().id() == thread {
+            let loc = info.location().unwrap();
+            *captured.lock().unwrap() = Some((loc.file().to_string(), loc.line()));
+        }
+    }));
+    let result = std::panic::catch_unwind(f);
+    std::panic::set_hook(previous);
+
+    assert!(result.is_err(), "expected a panic");
+    let location = location.lock().unwrap().take().unwrap();
+    location
+}
+
+#[test]
+fn assertion_failures_point_at_the_caller() {
+    let mailer = LocalMailer::new();
+
+    let (file, line) = panic_location(|| assert_email_sent(&mailer));
+    assert_eq!(file, file!());
+    assert_eq!(line, line!() - 2);
+
+    let (file, line) = panic_location(|| assert_email_to!(mailer, "user@example.com"));
+    assert_eq!(file, file!());
+    assert_eq!(line, line!() - 2);
+}
+
+#[tokio::test]
+async fn assertion_macros_pass_when_email_sent() {
+    let mailer = LocalMailer::new();
+    send_email(&mailer).await;
+
+    assert_email_sent!(mailer);
+    assert_email_to!(mailer, "steve.rogers@example.com");
+    assert_email_subject!(mailer, "Hello, Avengers!", "subject for {}", "avengers");
+    assert_email_html_contains!(mailer, "Some html");
+    refute_email_to!(mailer, "wrong@example.com");
+}
+
+#[tokio::test]
+#[should_panic(expected = "welcome email missing")]
+async fn assertion_macros_include_custom_message() {
+    let mailer = LocalMailer::new();
+    assert_email_subject!(mailer, "Welcome", "welcome email missing");
+}
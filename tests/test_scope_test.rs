@@ -0,0 +1,98 @@
+//! Tests for scoped mailer overrides.
+
+#![cfg(feature = "local")]
+
+use missive::providers::LocalMailer;
+use missive::{deliver, test_scope, Email};
+
+fn email(to: &str) -> Email {
+    Email::new().from("sender@example.com").to(to).subject("Scoped")
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_concurrent_scopes_are_isolated() {
+    let first = LocalMailer::new();
+    let second = LocalMailer::new();
+
+    let a = test_scope(first.clone(), async {
+        for _ in 0..5 {
+            deliver(&email("first@example.com")).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+    });
+    let b = test_scope(second.clone(), async {
+        for _ in 0..3 {
+            deliver(&email("second@example.com")).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+    });
+    tokio::join!(a, b);
+
+    assert_eq!(first.email_count(), 5);
+    assert_eq!(second.email_count(), 3);
+    assert!(first
+        .emails()
+        .iter()
+        .all(|e| e.email.to[0].email == "first@example.com"));
+    assert!(second
+        .emails()
+        .iter()
+        .all(|e| e.email.to[0].email == "second@example.com"));
+}
+
+#[tokio::test]
+async fn test_nested_scopes_restore_outer() {
+    let outer = LocalMailer::new();
+    let inner = LocalMailer::new();
+
+    test_scope(outer.clone(), async {
+        deliver(&email("a@example.com")).await.unwrap();
+        test_scope(inner.clone(), async {
+            deliver(&email("b@example.com")).await.unwrap();
+        })
+        .await;
+        deliver(&email("c@example.com")).await.unwrap();
+    })
+    .await;
+
+    assert_eq!(outer.email_count(), 2);
+    assert_eq!(inner.email_count(), 1);
+}
+
+#[tokio::test]
+async fn test_mailer_returns_scoped() {
+    let scoped = LocalMailer::new();
+
+    test_scope(scoped.clone(), async {
+        let mailer = missive::mailer().expect("scoped mailer");
+        mailer.deliver(&email("a@example.com")).await.unwrap();
+    })
+    .await;
+
+    assert_eq!(scoped.email_count(), 1);
+}
+
+/// Merge data that puts a line break into a header is rejected before sending.
+#[tokio::test]
+async fn test_deliver_personalized_rejects_header_injection() {
+    let local = LocalMailer::new();
+    let email = Email::new()
+        .from("sender@example.com")
+        .subject("Hi {{ name }}")
+        .text_body("Hello")
+        .personalize(vec![
+            ("alice@example.com", serde_json::json!({"name": "Alice"})),
+            (
+                "mallory@example.com",
+                serde_json::json!({"name": "Mallory\r\nBcc: victim@example.com"}),
+            ),
+        ]);
+
+    let result = test_scope(local.clone(), missive::deliver_personalized(&email)).await;
+    assert!(matches!(result, Err(missive::MailError::InvalidHeader(_))));
+    assert_eq!(local.email_count(), 0);
+
+    let template = email.clone().subject("Hi\r\nBcc: victim@example.com");
+    let result = test_scope(local.clone(), missive::deliver_personalized(&template)).await;
+    assert!(matches!(result, Err(missive::MailError::InvalidHeader(_))));
+}